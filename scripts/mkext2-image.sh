@@ -0,0 +1,61 @@
+#!/bin/bash
+# ext2 테스트 이미지 생성 스크립트
+# 빌드 호스트의 mke2fs(e2fsprogs 1.43 이상)로 일반 파일, 중첩 디렉토리, 짧은/긴 심볼릭 링크,
+# 간접 블록이 필요한 큰 파일, 여러 소유자와 모드를 담은 ext2 이미지를 만듭니다.
+#
+# 사용법: scripts/mkext2-image.sh [이미지 경로] [크기(MiB)]
+#
+# `EXT2_IMAGE=target/ext2-test.img scripts/run.sh`로 실행하면 이미지가 없을 때 이 스크립트로
+# 만들고 아래처럼 붙입니다. QEMU에서는 ATA Primary Master가 부트 이미지이므로 NVMe 디스크로 붙입니다:
+#   qemu-system-x86_64 ... \
+#       -drive file=target/ext2-test.img,if=none,format=raw,id=nvm \
+#       -device nvme,serial=ext2test,drive=nvm
+# 커널 셸에서: mount -t ext2 /dev/nvme0n1 /mnt
+#
+# 커널에서 쓴 뒤에는 호스트에서 검사할 수 있습니다: e2fsck -fn target/ext2-test.img
+
+set -e
+
+IMAGE="${1:-target/ext2-test.img}"
+SIZE_MB="${2:-16}"
+
+for tool in mke2fs debugfs; do
+    if ! command -v "$tool" > /dev/null; then
+        echo "Error: $tool not found (install e2fsprogs)" >&2
+        exit 1
+    fi
+done
+
+STAGING=$(mktemp -d)
+trap 'rm -rf "$STAGING"' EXIT
+
+mkdir -p "$STAGING/docs/nested/deeper" "$STAGING/shared"
+echo "hello from the build host" > "$STAGING/hello.txt"
+echo "deep file" > "$STAGING/docs/nested/deeper/leaf.txt"
+echo "team notes" > "$STAGING/shared/notes.txt"
+echo "owner only" > "$STAGING/docs/private.txt"
+# 1 KiB 블록에서 3 MiB는 2중 간접 블록까지 사용
+head -c $((3 * 1024 * 1024)) /dev/urandom > "$STAGING/big.bin"
+# 빠른 심볼릭 링크 (대상이 inode 안에 저장됨)
+ln -s hello.txt "$STAGING/hello-link"
+ln -s /shared "$STAGING/docs/shared-abs"
+# 60바이트 이상이라 데이터 블록에 저장되는 심볼릭 링크
+ln -s "docs/nested/deeper/../deeper/./../../nested/deeper/../deeper/leaf.txt" "$STAGING/long-link"
+
+mkdir -p "$(dirname "$IMAGE")"
+rm -f "$IMAGE"
+mke2fs -q -F -t ext2 -b 1024 -L ext2test -E root_owner=0:0 -d "$STAGING" "$IMAGE" "${SIZE_MB}M"
+
+# 호스트 사용자와 관계없이 소유자와 모드 고정
+debugfs -w "$IMAGE" > /dev/null 2>&1 <<'EOF'
+sif /shared uid 1000
+sif /shared gid 100
+sif /shared mode 042775
+sif /shared/notes.txt uid 1000
+sif /shared/notes.txt gid 100
+sif /docs/private.txt uid 1000
+sif /docs/private.txt gid 1000
+sif /docs/private.txt mode 0100600
+EOF
+
+echo "Created $IMAGE (${SIZE_MB} MiB ext2, 1 KiB blocks)"
@@ -46,8 +46,24 @@ echo "참고: 커널 부팅 후 자동으로 전력 통계 수집이 시작됩
 echo "      CSV 형식으로 내보내려면 커널에서 export 함수를 호출하세요."
 echo ""
 
+# EXT2_IMAGE를 지정하면 ext2 테스트 이미지를 NVMe 디스크로 붙임 (없으면 새로 만듦)
+# 커널 셸에서: mount -t ext2 /dev/nvme0n1 /mnt
+EXTRA_DRIVES=()
+if [ -n "$EXT2_IMAGE" ]; then
+    if [ ! -f "$EXT2_IMAGE" ]; then
+        "$(dirname "$0")/mkext2-image.sh" "$EXT2_IMAGE"
+    fi
+    echo "ext2 이미지: $EXT2_IMAGE (/dev/nvme0n1)"
+    EXTRA_DRIVES=(
+        -drive file="$EXT2_IMAGE",if=none,format=raw,id=nvm
+        -device nvme,serial=ext2test,drive=nvm
+    )
+fi
+
 # QEMU 실행 (시리얼 포트를 콘솔과 파일로 리다이렉트)
 qemu-system-x86_64 \
     -drive format=raw,file="$BOOTIMAGE_PATH" \
+    "${EXTRA_DRIVES[@]}" \
+    -smp "${QEMU_SMP:-4}" \
     -serial stdio 2>&1 | tee "$POWER_LOG" | grep -E "(Power:|timestamp|pkg_w|wakeups)" > "$BOOT_TIMELINE" &
 
@@ -60,6 +60,11 @@ pub fn acpi_rsdp_addr() -> Option<u64> {
     })
 }
 
+/// 부트로더가 프레임버퍼를 넘겨줬는지 확인
+pub fn has_framebuffer() -> bool {
+    FRAMEBUFFER_ADDR.lock().is_some()
+}
+
 /// 프레임버퍼 가져오기
 ///
 /// # Safety
@@ -1,6 +1,7 @@
 //! 부팅 타임라인 추적
 //!
 //! 커널 초기화 단계별 시간을 추적하여 부팅 성능을 분석합니다.
+//! 타임스탬프는 클럭소스 기준 마이크로초 단위로 기록됩니다.
 
 use spin::Mutex;
 use alloc::vec::Vec;
@@ -22,32 +23,8 @@ pub enum BootStage {
     InterruptsEnabled,
     /// 메모리 관리 초기화
     MemoryInit,
-    /// 타이머 초기화
-    TimerInit,
-    /// 키보드 드라이버 초기화
-    KeyboardInit,
-    /// VGA 초기화
-    VgaInit,
-    /// 스케줄러 초기화
-    SchedulerInit,
-    /// 시스템 콜 핸들러 초기화
-    SyscallInit,
-    /// ATA 드라이버 초기화
-    AtaInit,
-    /// 파일시스템 준비
-    FilesystemReady,
-    /// 전력 관리 초기화
-    PowerInit,
-    /// 네트워크 드라이버 초기화
-    NetworkInit,
-    /// 프레임버퍼 초기화
-    FramebufferInit,
-    /// GUI 시스템 초기화
-    GuiInit,
-    /// 마우스 드라이버 초기화
-    MouseInit,
-    /// I2C/터치패드 초기화
-    TouchpadInit,
+    /// 서브시스템 initcall (`kernel::initcall`)
+    Initcall(&'static str),
     /// 커널 초기화 완료
     KernelInitComplete,
     /// 데스크톱/GUI 시작
@@ -60,24 +37,27 @@ pub enum BootStage {
 #[derive(Debug, Clone, Copy)]
 struct TimelineEntry {
     stage: BootStage,
-    timestamp_ms: u64,
-    relative_ms: u64, // 부트 시작부터의 상대 시간
+    timestamp_us: u64,
+    relative_us: u64, // 부트 시작부터의 상대 시간
+    /// 단계 소요 시간 (initcall만 기록)
+    duration_us: Option<u64>,
 }
 
 static TIMELINE: Mutex<Vec<TimelineEntry>> = Mutex::new(Vec::new());
-static BOOT_START_MS: Mutex<Option<u64>> = Mutex::new(None);
+static BOOT_START_US: Mutex<Option<u64>> = Mutex::new(None);
 
 /// 부팅 시작 기록
 pub fn mark_boot_start() {
     // 타이머가 아직 초기화되지 않았을 수 있으므로, 0으로 시작
-    let mut start = BOOT_START_MS.lock();
+    let mut start = BOOT_START_US.lock();
     if start.is_none() {
         *start = Some(0);
         let mut timeline = TIMELINE.lock();
         timeline.push(TimelineEntry {
             stage: BootStage::BootStart,
-            timestamp_ms: 0,
-            relative_ms: 0,
+            timestamp_us: 0,
+            relative_us: 0,
+            duration_us: None,
         });
     }
 }
@@ -85,21 +65,35 @@ pub fn mark_boot_start() {
 /// 타임라인 엔트리 기록
 pub fn mark_stage(stage: BootStage) {
     // 타이머가 초기화된 후에만 정확한 타임스탬프 사용
-    let timestamp_ms = crate::drivers::timer::get_milliseconds();
-    let mut start = BOOT_START_MS.lock();
-    let relative_ms = if let Some(start_ms) = *start {
-        timestamp_ms.saturating_sub(start_ms)
+    let timestamp_us = crate::time::now_ns() / crate::time::NSEC_PER_USEC;
+    push_entry(stage, timestamp_us, None);
+}
+
+/// initcall 실행 구간 기록
+///
+/// # Arguments
+/// * `start_us` - 시작 시각 (클럭소스 기준 마이크로초)
+/// * `duration_us` - 소요 시간
+pub fn record_initcall(name: &'static str, start_us: u64, duration_us: u64) {
+    push_entry(BootStage::Initcall(name), start_us, Some(duration_us));
+}
+
+fn push_entry(stage: BootStage, timestamp_us: u64, duration_us: Option<u64>) {
+    let mut start = BOOT_START_US.lock();
+    let relative_us = if let Some(start_us) = *start {
+        timestamp_us.saturating_sub(start_us)
     } else {
         // 아직 시작이 기록되지 않았으면 지금 기록
-        *start = Some(timestamp_ms);
-        timestamp_ms
+        *start = Some(timestamp_us);
+        timestamp_us
     };
     
     let mut timeline = TIMELINE.lock();
     timeline.push(TimelineEntry {
         stage,
-        timestamp_ms,
-        relative_ms,
+        timestamp_us,
+        relative_us,
+        duration_us,
     });
 }
 
@@ -116,32 +110,40 @@ pub fn print_timeline() {
             BootStage::IdtInit => "IDT Init",
             BootStage::InterruptsEnabled => "Interrupts Enabled",
             BootStage::MemoryInit => "Memory Init",
-            BootStage::TimerInit => "Timer Init",
-            BootStage::KeyboardInit => "Keyboard Init",
-            BootStage::VgaInit => "VGA Init",
-            BootStage::SchedulerInit => "Scheduler Init",
-            BootStage::SyscallInit => "Syscall Init",
-            BootStage::AtaInit => "ATA Init",
-            BootStage::FilesystemReady => "Filesystem Ready",
-            BootStage::PowerInit => "Power Init",
-            BootStage::NetworkInit => "Network Init",
-            BootStage::FramebufferInit => "Framebuffer Init",
-            BootStage::GuiInit => "GUI Init",
-            BootStage::MouseInit => "Mouse Init",
-            BootStage::TouchpadInit => "Touchpad Init",
+            BootStage::Initcall(name) => name,
             BootStage::KernelInitComplete => "Kernel Init Complete",
             BootStage::DesktopStart => "Desktop Start",
             BootStage::ShellStart => "Shell Start",
         };
-        crate::log_info!("{}: {}ms (absolute: {}ms)", stage_name, entry.relative_ms, entry.timestamp_ms);
+        match entry.duration_us {
+            Some(duration_us) => {
+                crate::log_info!(
+                    "{}: {}.{:03}ms (took {}.{:03}ms)",
+                    stage_name,
+                    entry.relative_us / 1000, entry.relative_us % 1000,
+                    duration_us / 1000, duration_us % 1000
+                );
+            }
+            None => {
+                crate::log_info!(
+                    "{}: {}.{:03}ms (absolute: {}.{:03}ms)",
+                    stage_name,
+                    entry.relative_us / 1000, entry.relative_us % 1000,
+                    entry.timestamp_us / 1000, entry.timestamp_us % 1000
+                );
+            }
+        }
     }
     crate::log_info!("===================");
 }
 
 /// 타임라인을 CSV 형식으로 내보내기
+///
+/// 앞의 세 열(`stage,timestamp_ms,relative_ms`)은 기존 형식 그대로이고,
+/// 마이크로초 열과 initcall 소요 시간은 그 뒤에 덧붙입니다.
 pub fn export_timeline_csv() {
     let timeline = TIMELINE.lock();
-    crate::serial_println!("stage,timestamp_ms,relative_ms");
+    crate::serial_println!("stage,timestamp_ms,relative_ms,timestamp_us,relative_us,duration_us");
     for entry in timeline.iter() {
         let stage_name = match entry.stage {
             BootStage::BootStart => "BootStart",
@@ -151,31 +153,33 @@ pub fn export_timeline_csv() {
             BootStage::IdtInit => "IdtInit",
             BootStage::InterruptsEnabled => "InterruptsEnabled",
             BootStage::MemoryInit => "MemoryInit",
-            BootStage::TimerInit => "TimerInit",
-            BootStage::KeyboardInit => "KeyboardInit",
-            BootStage::VgaInit => "VgaInit",
-            BootStage::SchedulerInit => "SchedulerInit",
-            BootStage::SyscallInit => "SyscallInit",
-            BootStage::AtaInit => "AtaInit",
-            BootStage::FilesystemReady => "FilesystemReady",
-            BootStage::PowerInit => "PowerInit",
-            BootStage::NetworkInit => "NetworkInit",
-            BootStage::FramebufferInit => "FramebufferInit",
-            BootStage::GuiInit => "GuiInit",
-            BootStage::MouseInit => "MouseInit",
-            BootStage::TouchpadInit => "TouchpadInit",
+            BootStage::Initcall(name) => name,
             BootStage::KernelInitComplete => "KernelInitComplete",
             BootStage::DesktopStart => "DesktopStart",
             BootStage::ShellStart => "ShellStart",
         };
-        crate::serial_println!("{},{},{}", stage_name, entry.timestamp_ms, entry.relative_ms);
+        crate::serial_println!(
+            "{},{},{},{},{},{}",
+            stage_name,
+            entry.timestamp_us / 1000,
+            entry.relative_us / 1000,
+            entry.timestamp_us,
+            entry.relative_us,
+            entry.duration_us.unwrap_or(0)
+        );
     }
 }
 
 /// 총 부팅 시간 가져오기
 pub fn get_total_boot_time_ms() -> Option<u64> {
     let timeline = TIMELINE.lock();
-    timeline.last().map(|entry| entry.relative_ms)
+    timeline.last().map(|entry| entry.relative_us / 1000)
+}
+
+/// 총 부팅 시간 가져오기 (마이크로초)
+pub fn get_total_boot_time_us() -> Option<u64> {
+    let timeline = TIMELINE.lock();
+    timeline.last().map(|entry| entry.relative_us)
 }
 
 
@@ -0,0 +1,45 @@
+//! 커널 부트 옵션 (명령줄)
+//!
+//! bootloader 0.11은 커널 명령줄을 전달하지 않으므로, 빌드 시
+//! `SIMPLE_OS_CMDLINE` 환경 변수로 지정한 문자열을 부트 옵션으로 사용합니다.
+//!
+//! 형식은 공백으로 구분된 `key=value` 또는 `flag` 목록입니다.
+//! 예: `SIMPLE_OS_CMDLINE="isolcpus=2-3 quiet" cargo build`
+
+use spin::Mutex;
+
+/// 빌드 시 지정된 기본 명령줄
+pub const BUILTIN_CMDLINE: &str = match option_env!("SIMPLE_OS_CMDLINE") {
+    Some(s) => s,
+    None => "",
+};
+
+static CMDLINE: Mutex<&'static str> = Mutex::new(BUILTIN_CMDLINE);
+
+/// 명령줄 설정 (부팅 초기에 한 번 호출)
+pub fn init(cmdline: &'static str) {
+    *CMDLINE.lock() = cmdline.trim();
+    if !cmdline.trim().is_empty() {
+        crate::log_info!("Kernel command line: {}", cmdline.trim());
+    }
+}
+
+/// 전체 명령줄
+pub fn cmdline() -> &'static str {
+    *CMDLINE.lock()
+}
+
+/// `key=value` 옵션의 값 (같은 키가 여러 번 있으면 마지막 값)
+pub fn get(key: &str) -> Option<&'static str> {
+    cmdline()
+        .split_whitespace()
+        .filter_map(|opt| opt.split_once('='))
+        .filter(|(k, _)| *k == key)
+        .map(|(_, v)| v)
+        .last()
+}
+
+/// 값 없는 플래그 옵션이 있는지 확인
+pub fn has_flag(flag: &str) -> bool {
+    cmdline().split_whitespace().any(|opt| opt == flag)
+}
@@ -1,3 +1,4 @@
+pub mod cmdline;
 pub mod profile;
 
 
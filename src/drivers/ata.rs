@@ -6,6 +6,8 @@
 use spin::Mutex;
 use x86_64::instructions::port::{Port, PortReadOnly, PortWriteOnly};
 
+use crate::drivers::base::{platform, Device, DeviceMatch, Driver};
+
 /// 블록 크기 (섹터 크기)
 pub const SECTOR_SIZE: usize = 512;
 
@@ -479,6 +481,37 @@ impl BlockDevice for AtaDriver {
 // 전역 ATA 드라이버 인스턴스 (Primary Master)
 pub static PRIMARY_MASTER: Mutex<Option<AtaDriver>> = Mutex::new(None);
 
+/// `PRIMARY_MASTER`를 블록마다 잠가서 쓰는 블록 디바이스 핸들
+///
+/// 파일시스템이 소유할 수 있는 `Box<dyn BlockDevice>`가 필요할 때 사용합니다.
+/// 드라이버가 제거되면 모든 연산이 `NotReady`를 반환합니다.
+pub struct PrimaryMasterDevice;
+
+impl PrimaryMasterDevice {
+    /// devfs 노드가 쓰는 생성 함수
+    pub fn open() -> alloc::boxed::Box<dyn BlockDevice> {
+        alloc::boxed::Box::new(PrimaryMasterDevice)
+    }
+}
+
+impl BlockDevice for PrimaryMasterDevice {
+    fn block_size(&self) -> usize {
+        SECTOR_SIZE
+    }
+    
+    fn read_block(&mut self, block: u64, buf: &mut [u8]) -> Result<usize, BlockDeviceError> {
+        PRIMARY_MASTER.lock().as_mut().ok_or(BlockDeviceError::NotReady)?.read_block(block, buf)
+    }
+    
+    fn write_block(&mut self, block: u64, buf: &[u8]) -> Result<usize, BlockDeviceError> {
+        PRIMARY_MASTER.lock().as_mut().ok_or(BlockDeviceError::NotReady)?.write_block(block, buf)
+    }
+    
+    fn num_blocks(&self) -> u64 {
+        PRIMARY_MASTER.lock().as_ref().map_or(0, |driver| driver.num_blocks())
+    }
+}
+
 // 간단한 유휴 관리 상태
 struct AtaPowerConfig {
     idle_timeout_ms: u64,
@@ -536,6 +569,43 @@ pub unsafe fn init() {
     }
 }
 
+/// 레거시 IDE 컨트롤러 드라이버 (디바이스 모델)
+struct AtaControllerDriver;
+
+/// 디바이스 모델에 등록할 ATA 드라이버
+pub static DRIVER: &dyn Driver = &AtaControllerDriver;
+
+impl Driver for AtaControllerDriver {
+    fn name(&self) -> &'static str {
+        "ata-pio"
+    }
+
+    fn match_table(&self) -> &'static [DeviceMatch] {
+        &[DeviceMatch::Acpi(platform::HID_IDE)]
+    }
+
+    fn probe(&self, _device: &Device) -> Result<(), &'static str> {
+        unsafe { init(); }
+        if PRIMARY_MASTER.lock().is_none() {
+            return Err("no ATA drive on primary channel");
+        }
+        Ok(())
+    }
+
+    fn remove(&self, _device: &Device) {
+        *PRIMARY_MASTER.lock() = None;
+    }
+
+    /// 디스크 standby (다음 I/O에서 `resume_if_needed`로 깨어남)
+    fn suspend(&self, _device: &Device) -> Result<(), &'static str> {
+        if let Some(driver) = PRIMARY_MASTER.lock().as_mut() {
+            unsafe { driver.enter_standby() }.map_err(|_| "ATA standby failed")?;
+            crate::log_info!("[ATA] Primary Master entered standby");
+        }
+        Ok(())
+    }
+}
+
 /// Primary Master 드라이버에 대한 접근 함수
 pub fn get_primary_master() -> Option<&'static Mutex<Option<AtaDriver>>> {
     if PRIMARY_MASTER.lock().is_some() {
@@ -0,0 +1,496 @@
+//! 디바이스 모델 (버스, 디바이스, 드라이버 바인딩)
+//!
+//! 버스 열거 코드(`platform`, `pci_bus`, USB, I2C)가 디바이스를 트리로 등록하고,
+//! 드라이버는 매치 테이블(PCI ID/클래스, USB ID/클래스, ACPI HID)과 함께
+//! `Driver` 구현을 등록합니다. 디바이스나 드라이버가 등록될 때마다 아직 바인딩되지
+//! 않은 디바이스에 맞는 드라이버를 찾아 `probe`합니다.
+//!
+//! ## 의존성 순서
+//! - 디바이스는 부모가 바인딩된 뒤에만 probe됩니다 (예: I2C 트랙패드는 I2C 컨트롤러 뒤).
+//!   PCI 브리지처럼 드라이버가 필요 없는 버스 노드는 `register_bus_device`로 등록하며
+//!   등록 즉시 바인딩된 것으로 취급합니다.
+//! - 부모는 항상 자식보다 먼저 등록되고 ID는 재사용하지 않으므로, ID 순서가 곧
+//!   위상 순서입니다. 절전 진입은 ID 역순(자식 먼저), 복귀는 ID 순(부모 먼저)으로
+//!   `suspend`/`resume`을 호출합니다.
+
+pub mod pci_bus;
+pub mod platform;
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+use spin::Mutex;
+
+use crate::drivers::pci::PciDevice;
+use crate::kernel::uevent::{self, KernelEvent};
+
+/// 디바이스 ID (등록 순서대로 증가, 재사용하지 않음)
+pub type DeviceId = usize;
+
+/// 디바이스가 속한 버스
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BusType {
+    /// 고정 I/O 포트, ACPI 열거 디바이스 등
+    Platform,
+    Pci,
+    Usb,
+    I2c,
+}
+
+/// ACPI 하드웨어 ID (예: `PNP0C50`, 최대 8바이트, NUL 패딩)
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct AcpiHid(pub [u8; 8]);
+
+impl AcpiHid {
+    /// 문자열에서 생성 (8바이트를 넘는 부분은 잘림)
+    pub const fn new(hid: &str) -> Self {
+        let bytes = hid.as_bytes();
+        let mut raw = [0u8; 8];
+        let mut i = 0;
+        while i < bytes.len() && i < raw.len() {
+            raw[i] = bytes[i];
+            i += 1;
+        }
+        Self(raw)
+    }
+
+    /// NUL 패딩을 제외한 문자열
+    pub fn as_str(&self) -> &str {
+        let len = self.0.iter().position(|&b| b == 0).unwrap_or(self.0.len());
+        core::str::from_utf8(&self.0[..len]).unwrap_or("")
+    }
+}
+
+impl fmt::Debug for AcpiHid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// 버스별 디바이스 정보
+#[derive(Debug, Clone, Copy)]
+pub enum DeviceInfo {
+    Platform {
+        hid: Option<AcpiHid>,
+        /// MMIO 베이스 물리 주소 (있는 경우)
+        mmio: Option<u64>,
+        /// 인터럽트 GSI (있는 경우)
+        irq: Option<u32>,
+    },
+    Pci(PciDevice),
+    Usb {
+        address: u8,
+        vendor_id: u16,
+        product_id: u16,
+        class: u8,
+        subclass: u8,
+        protocol: u8,
+    },
+    I2c {
+        /// 슬레이브 주소
+        address: u16,
+        hid: Option<AcpiHid>,
+    },
+}
+
+impl DeviceInfo {
+    /// 디바이스가 속한 버스
+    pub fn bus(&self) -> BusType {
+        match self {
+            DeviceInfo::Platform { .. } => BusType::Platform,
+            DeviceInfo::Pci(_) => BusType::Pci,
+            DeviceInfo::Usb { .. } => BusType::Usb,
+            DeviceInfo::I2c { .. } => BusType::I2c,
+        }
+    }
+
+    /// ACPI HID (ACPI로 열거된 디바이스만)
+    pub fn hid(&self) -> Option<&AcpiHid> {
+        match self {
+            DeviceInfo::Platform { hid, .. } | DeviceInfo::I2c { hid, .. } => hid.as_ref(),
+            _ => None,
+        }
+    }
+}
+
+/// 등록된 디바이스
+#[derive(Debug, Clone)]
+pub struct Device {
+    pub id: DeviceId,
+    /// 사람이 읽을 수 있는 이름 (예: `0000:00:03.0`, `i8042-kbd`)
+    pub name: String,
+    /// 부모 디바이스 (버스 컨트롤러 또는 브리지)
+    pub parent: Option<DeviceId>,
+    pub info: DeviceInfo,
+}
+
+/// 드라이버 매치 테이블 엔트리
+#[derive(Debug, Clone, Copy)]
+pub enum DeviceMatch {
+    /// 플랫폼 디바이스 이름
+    Name(&'static str),
+    /// ACPI HID (플랫폼/I2C 디바이스)
+    Acpi(&'static str),
+    PciId { vendor: u16, device: u16 },
+    /// PCI 클래스 코드 (`prog_if`가 `None`이면 모든 프로그래밍 인터페이스)
+    PciClass { class: u8, subclass: u8, prog_if: Option<u8> },
+    UsbId { vendor: u16, product: u16 },
+    /// USB 인터페이스 클래스 (`None` 필드는 와일드카드)
+    UsbClass { class: u8, subclass: Option<u8>, protocol: Option<u8> },
+}
+
+impl DeviceMatch {
+    /// 디바이스가 이 엔트리와 일치하는지 확인
+    pub fn matches(&self, device: &Device) -> bool {
+        let any = |want: Option<u8>, got: u8| want.map_or(true, |w| w == got);
+        match (*self, &device.info) {
+            (DeviceMatch::Name(name), DeviceInfo::Platform { .. }) => device.name == name,
+            (DeviceMatch::Acpi(hid), info) => info.hid().is_some_and(|h| h.as_str() == hid),
+            (DeviceMatch::PciId { vendor, device: id }, DeviceInfo::Pci(pci)) => {
+                pci.vendor_id == vendor && pci.device_id == id
+            }
+            (DeviceMatch::PciClass { class, subclass, prog_if }, DeviceInfo::Pci(pci)) => {
+                pci.class_code == class && pci.subclass == subclass && any(prog_if, pci.prog_if)
+            }
+            (DeviceMatch::UsbId { vendor, product }, DeviceInfo::Usb { vendor_id, product_id, .. }) => {
+                *vendor_id == vendor && *product_id == product
+            }
+            (
+                DeviceMatch::UsbClass { class, subclass, protocol },
+                DeviceInfo::Usb { class: c, subclass: s, protocol: p, .. },
+            ) => *c == class && any(subclass, *s) && any(protocol, *p),
+            _ => false,
+        }
+    }
+}
+
+/// 디바이스 드라이버
+///
+/// `probe`가 성공하면 디바이스가 드라이버에 바인딩되고, 이후 `remove`/`suspend`/
+/// `resume`이 같은 디바이스로 호출됩니다. 콜백은 레지스트리 락 밖에서 호출되므로
+/// 자식 디바이스를 등록해도 됩니다.
+pub trait Driver: Sync {
+    /// 드라이버 이름
+    fn name(&self) -> &'static str;
+
+    /// 처리할 수 있는 디바이스 목록
+    fn match_table(&self) -> &'static [DeviceMatch];
+
+    /// 디바이스 초기화
+    fn probe(&self, device: &Device) -> Result<(), &'static str>;
+
+    /// 디바이스 해제 (인터럽트, 메모리 반환)
+    fn remove(&self, _device: &Device) {}
+
+    /// 절전 진입 전 디바이스 정지
+    fn suspend(&self, _device: &Device) -> Result<(), &'static str> {
+        Ok(())
+    }
+
+    /// 절전 복귀 후 디바이스 재개
+    fn resume(&self, _device: &Device) -> Result<(), &'static str> {
+        Ok(())
+    }
+}
+
+/// 디바이스 바인딩 상태
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeviceState {
+    /// 맞는 드라이버가 없거나 아직 부모가 준비되지 않음
+    Unbound,
+    /// `probe` 진행 중
+    Probing,
+    Bound,
+    Suspended,
+}
+
+/// 레지스트리 노드
+struct Node {
+    device: Device,
+    driver: Option<&'static dyn Driver>,
+    state: DeviceState,
+    /// `probe`에 실패한 드라이버 (`DRIVERS` 인덱스, 다시 시도하지 않음)
+    failed: Vec<usize>,
+}
+
+/// 디바이스 목록 (인덱스 = `DeviceId`, 제거된 슬롯은 `None`)
+static DEVICES: Mutex<Vec<Option<Node>>> = Mutex::new(Vec::new());
+
+/// 등록된 드라이버
+static DRIVERS: Mutex<Vec<&'static dyn Driver>> = Mutex::new(Vec::new());
+
+/// 디바이스 목록 조회용 스냅샷
+#[derive(Debug, Clone)]
+pub struct DeviceEntry {
+    pub device: Device,
+    pub state: DeviceState,
+    /// 바인딩된 드라이버 이름
+    pub driver: Option<&'static str>,
+}
+
+/// 플랫폼 및 PCI 버스 열거
+///
+/// # Safety
+/// 메모리 관리와 `drivers::pci::init`이 끝난 뒤 한 번만 호출되어야 합니다.
+pub unsafe fn init() {
+    platform::enumerate();
+    pci_bus::enumerate();
+    crate::log_info!("Device model: {} device(s) registered", device_count());
+}
+
+fn insert(name: String, parent: Option<DeviceId>, info: DeviceInfo, state: DeviceState) -> Result<DeviceId, &'static str> {
+    let mut devices = DEVICES.lock();
+    if let Some(parent) = parent {
+        if !matches!(devices.get(parent), Some(Some(_))) {
+            return Err("parent device not registered");
+        }
+    }
+    let id = devices.len();
+    let device = Device { id, name, parent, info };
+    devices.push(Some(Node { device, driver: None, state, failed: Vec::new() }));
+    drop(devices);
+    uevent::publish(KernelEvent::DeviceAdded { id, info });
+    Ok(id)
+}
+
+/// 디바이스 등록 후 맞는 드라이버 probe
+///
+/// # Returns
+/// 새 디바이스 ID
+pub fn register_device(name: String, parent: Option<DeviceId>, info: DeviceInfo) -> Result<DeviceId, &'static str> {
+    let id = insert(name, parent, info, DeviceState::Unbound)?;
+    bind_pending();
+    Ok(id)
+}
+
+/// 드라이버 없이 바인딩된 것으로 취급하는 버스 노드 등록 (호스트 브리지, PCI 브리지)
+pub fn register_bus_device(name: String, parent: Option<DeviceId>, info: DeviceInfo) -> Result<DeviceId, &'static str> {
+    let id = insert(name, parent, info, DeviceState::Bound)?;
+    bind_pending();
+    Ok(id)
+}
+
+/// 디바이스와 그 하위 트리 제거
+///
+/// 자식부터 바인딩된 드라이버의 `remove`를 호출합니다.
+pub fn unregister_device(id: DeviceId) {
+    let removed: Vec<Node> = {
+        let mut devices = DEVICES.lock();
+        if !matches!(devices.get(id), Some(Some(_))) {
+            return;
+        }
+        // ID가 위상 순서이므로 한 번의 정방향 순회로 하위 트리를 모두 찾음
+        let mut subtree = alloc::vec![id];
+        for node in devices.iter().skip(id + 1).flatten() {
+            if node.device.parent.is_some_and(|p| subtree.contains(&p)) {
+                subtree.push(node.device.id);
+            }
+        }
+        subtree.iter().rev().filter_map(|&i| devices[i].take()).collect()
+    };
+
+    for node in removed {
+        if let Some(driver) = node.driver {
+            driver.remove(&node.device);
+            crate::log_info!("Device {}: removed from driver {}", node.device.name, driver.name());
+        }
+        uevent::publish(KernelEvent::DeviceRemoved {
+            id: node.device.id,
+            info: node.device.info,
+        });
+    }
+}
+
+/// 드라이버 등록 후 아직 바인딩되지 않은 디바이스에 probe
+pub fn register_driver(driver: &'static dyn Driver) {
+    DRIVERS.lock().push(driver);
+    bind_pending();
+}
+
+/// probe할 다음 (디바이스, 드라이버) 쌍을 찾아 `Probing`으로 표시
+fn next_candidate() -> Option<(Device, usize, &'static dyn Driver)> {
+    let mut devices = DEVICES.lock();
+    let drivers = DRIVERS.lock();
+    for id in 0..devices.len() {
+        let ready = match &devices[id] {
+            Some(node) if node.state == DeviceState::Unbound => match node.device.parent {
+                None => true,
+                Some(parent) => matches!(&devices[parent], Some(p) if p.state == DeviceState::Bound),
+            },
+            _ => false,
+        };
+        if !ready {
+            continue;
+        }
+        let Some(node) = devices[id].as_mut() else { continue };
+        let found = drivers.iter().enumerate().find(|(index, driver)| {
+            !node.failed.contains(index) && driver.match_table().iter().any(|m| m.matches(&node.device))
+        });
+        if let Some((index, &driver)) = found {
+            node.state = DeviceState::Probing;
+            return Some((node.device.clone(), index, driver));
+        }
+    }
+    None
+}
+
+/// 부모가 준비된 미바인딩 디바이스를 모두 probe
+///
+/// probe는 락 밖에서 호출되며, 바인딩되면 그 자식들도 같은 호출에서 probe됩니다.
+fn bind_pending() {
+    while let Some((device, index, driver)) = next_candidate() {
+        let result = driver.probe(&device);
+        let mut devices = DEVICES.lock();
+        let Some(node) = devices.get_mut(device.id).and_then(Option::as_mut) else { continue };
+        match result {
+            Ok(()) => {
+                node.driver = Some(driver);
+                node.state = DeviceState::Bound;
+                crate::log_info!("Device {}: bound to driver {}", device.name, driver.name());
+            }
+            Err(e) => {
+                node.failed.push(index);
+                node.state = DeviceState::Unbound;
+                crate::log_warn!("Device {}: driver {} probe failed: {}", device.name, driver.name(), e);
+            }
+        }
+    }
+}
+
+/// 특정 상태이면서 드라이버가 바인딩된 디바이스 (ID 순)
+fn bound_devices(state: DeviceState) -> Vec<(Device, &'static dyn Driver)> {
+    DEVICES
+        .lock()
+        .iter()
+        .flatten()
+        .filter(|node| node.state == state)
+        .filter_map(|node| node.driver.map(|driver| (node.device.clone(), driver)))
+        .collect()
+}
+
+fn set_state(id: DeviceId, state: DeviceState) {
+    if let Some(Some(node)) = DEVICES.lock().get_mut(id) {
+        node.state = state;
+    }
+}
+
+/// 모든 바인딩된 디바이스 정지 (자식부터)
+///
+/// 하나라도 실패하면 이미 정지한 디바이스를 다시 재개하고 오류를 반환합니다.
+pub fn suspend_all() -> Result<(), &'static str> {
+    let mut suspended: Vec<(Device, &'static dyn Driver)> = Vec::new();
+    for (device, driver) in bound_devices(DeviceState::Bound).into_iter().rev() {
+        if let Err(e) = driver.suspend(&device) {
+            crate::log_warn!("Device {}: suspend failed: {}", device.name, e);
+            for (device, driver) in suspended.iter().rev() {
+                let _ = driver.resume(device);
+                set_state(device.id, DeviceState::Bound);
+            }
+            return Err(e);
+        }
+        set_state(device.id, DeviceState::Suspended);
+        suspended.push((device, driver));
+    }
+    crate::log_info!("Device model: {} device(s) suspended", suspended.len());
+    Ok(())
+}
+
+/// 정지한 디바이스 재개 (부모부터)
+///
+/// 실패한 디바이스가 있어도 나머지는 계속 재개하고, 첫 오류를 반환합니다.
+pub fn resume_all() -> Result<(), &'static str> {
+    let mut result = Ok(());
+    for (device, driver) in bound_devices(DeviceState::Suspended) {
+        if let Err(e) = driver.resume(&device) {
+            crate::log_warn!("Device {}: resume failed: {}", device.name, e);
+            result = result.and(Err(e));
+        }
+        set_state(device.id, DeviceState::Bound);
+    }
+    result
+}
+
+/// 조건에 맞는 첫 디바이스 ID
+pub fn find_device(pred: impl Fn(&Device) -> bool) -> Option<DeviceId> {
+    DEVICES.lock().iter().flatten().find(|node| pred(&node.device)).map(|node| node.device.id)
+}
+
+/// 등록된 디바이스 수
+pub fn device_count() -> usize {
+    DEVICES.lock().iter().flatten().count()
+}
+
+/// 전체 디바이스 스냅샷 (ID 순)
+pub fn devices() -> Vec<DeviceEntry> {
+    DEVICES
+        .lock()
+        .iter()
+        .flatten()
+        .map(|node| DeviceEntry {
+            device: node.device.clone(),
+            state: node.state,
+            driver: node.driver.map(|d| d.name()),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn device(name: &str, info: DeviceInfo) -> Device {
+        Device { id: 0, name: String::from(name), parent: None, info }
+    }
+
+    #[test_case]
+    fn test_device_match_tables() {
+        let tp = device("i2c-PNP0C50", DeviceInfo::I2c { address: 0x15, hid: Some(AcpiHid::new("PNP0C50")) });
+        assert!(DeviceMatch::Acpi("PNP0C50").matches(&tp));
+        assert!(!DeviceMatch::Acpi("PNP0303").matches(&tp));
+        assert!(!DeviceMatch::Name("i2c-PNP0C50").matches(&tp));
+
+        let kbd = device("i8042-kbd", DeviceInfo::Platform { hid: None, mmio: None, irq: None });
+        assert!(DeviceMatch::Name("i8042-kbd").matches(&kbd));
+
+        let usb = device("usb1-1", DeviceInfo::Usb {
+            address: 1, vendor_id: 0x046D, product_id: 0xC077, class: 3, subclass: 1, protocol: 2,
+        });
+        assert!(DeviceMatch::UsbClass { class: 3, subclass: None, protocol: Some(2) }.matches(&usb));
+        assert!(!DeviceMatch::UsbClass { class: 3, subclass: None, protocol: Some(1) }.matches(&usb));
+        assert!(DeviceMatch::UsbId { vendor: 0x046D, product: 0xC077 }.matches(&usb));
+        assert!(!DeviceMatch::PciId { vendor: 0x046D, device: 0xC077 }.matches(&usb));
+    }
+
+    #[test_case]
+    fn test_unregister_publishes_removal_for_subtree() {
+        use crate::kernel::uevent::{subscribe, EventFilter};
+
+        let events = subscribe(EventFilter::DEVICE);
+        let hub = register_bus_device(String::from("usb-test-hub"), None, DeviceInfo::Platform { hid: None, mmio: None, irq: None }).unwrap();
+        // 어떤 드라이버에도 맞지 않는 벤더 전용 클래스
+        let child = register_device(String::from("usb-test-1"), Some(hub), DeviceInfo::Usb {
+            address: 1, vendor_id: 0xFFFF, product_id: 0xFFFF, class: 0xFF, subclass: 0, protocol: 0,
+        }).unwrap();
+        assert!(matches!(events.try_recv().map(|r| r.event), Some(KernelEvent::DeviceAdded { id, .. }) if id == hub));
+        assert!(matches!(events.try_recv().map(|r| r.event), Some(KernelEvent::DeviceAdded { id, .. }) if id == child));
+
+        // 자식부터 제거 이벤트가 발행되고 레지스트리에서도 빠짐
+        unregister_device(hub);
+        assert!(matches!(events.try_recv().map(|r| r.event), Some(KernelEvent::DeviceRemoved { id, .. }) if id == child));
+        assert!(matches!(events.try_recv().map(|r| r.event), Some(KernelEvent::DeviceRemoved { id, .. }) if id == hub));
+        assert!(events.try_recv().is_none());
+        assert!(find_device(|d| d.id == child || d.id == hub).is_none());
+
+        // 이미 제거된 디바이스는 다시 이벤트를 만들지 않음
+        unregister_device(child);
+        assert!(events.try_recv().is_none());
+    }
+
+    #[test_case]
+    fn test_acpi_hid_padding() {
+        let hid = AcpiHid::new("PNP0C50");
+        assert_eq!(hid.0, *b"PNP0C50\0");
+        assert_eq!(hid.as_str(), "PNP0C50");
+        assert_eq!(AcpiHid::new("TOOLONGHID").as_str(), "TOOLONGH");
+    }
+}
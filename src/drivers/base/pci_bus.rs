@@ -0,0 +1,75 @@
+//! PCI 버스 열거
+//!
+//! 호스트 브리지(버스 0)부터 PCI-PCI 브리지의 보조 버스를 따라 내려가며 모든
+//! 함수를 디바이스 트리에 등록합니다. 브리지는 자식 디바이스의 부모가 됩니다.
+
+use alloc::format;
+
+use super::{register_bus_device, register_device, AcpiHid, DeviceId, DeviceInfo};
+use crate::drivers::pci::PciDevice;
+
+/// 호스트 브리지 ACPI HID (PCI Express Root Bridge)
+const HOST_BRIDGE_HID: &str = "PNP0A08";
+
+/// PCI 디바이스 이름 (`세그먼트:버스:디바이스.함수`)
+fn device_name(device: &PciDevice) -> alloc::string::String {
+    format!("0000:{:02x}:{:02x}.{:x}", device.bus, device.device, device.function)
+}
+
+/// PCI 계층 구조 열거
+///
+/// # Safety
+/// `drivers::pci::init` 이후에 호출되어야 합니다.
+pub unsafe fn enumerate() {
+    let root = match register_bus_device(
+        alloc::string::String::from("pci0000:00"),
+        None,
+        DeviceInfo::Platform { hid: Some(AcpiHid::new(HOST_BRIDGE_HID)), mmio: None, irq: None },
+    ) {
+        Ok(id) => id,
+        Err(e) => {
+            crate::log_warn!("PCI: failed to register host bridge: {}", e);
+            return;
+        }
+    };
+    let mut visited = [false; 256];
+    scan_bus(0, root, &mut visited);
+}
+
+/// 버스 하나를 스캔하고 브리지 아래 버스로 재귀
+unsafe fn scan_bus(bus: u8, parent: DeviceId, visited: &mut [bool; 256]) {
+    // 잘못 설정된 브리지로 인한 순환 방지
+    if core::mem::replace(&mut visited[bus as usize], true) {
+        return;
+    }
+    for slot in 0..=31 {
+        let Some(first) = PciDevice::probe(bus, slot, 0) else { continue };
+        let functions = if first.header_type & 0x80 != 0 { 8 } else { 1 };
+        for function in 0..functions {
+            let Some(device) = PciDevice::probe(bus, slot, function) else { continue };
+            let name = device_name(&device);
+            match device.secondary_bus() {
+                Some(secondary) => match register_bus_device(name, Some(parent), DeviceInfo::Pci(device)) {
+                    Ok(id) if secondary != 0 => scan_bus(secondary, id, visited),
+                    Ok(_) => {}
+                    Err(e) => {
+                        crate::log_warn!("PCI: failed to register bridge: {}", e);
+                    }
+                },
+                None => {
+                    if let Err(e) = register_device(name, Some(parent), DeviceInfo::Pci(device)) {
+                        crate::log_warn!("PCI: failed to register device: {}", e);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// PCI 디바이스의 디바이스 모델 ID
+pub fn device_id(device: &PciDevice) -> Option<DeviceId> {
+    super::find_device(|d| match &d.info {
+        DeviceInfo::Pci(p) => (p.bus, p.device, p.function) == (device.bus, device.device, device.function),
+        _ => false,
+    })
+}
@@ -0,0 +1,58 @@
+//! 플랫폼 디바이스 열거
+//!
+//! 고정 I/O 포트에 있는 레거시 디바이스(i8042, IDE), 부트로더가 넘겨준 프레임버퍼,
+//! ACPI로 찾은 I2C 컨트롤러와 그 아래 I2C HID 디바이스를 등록합니다.
+
+use alloc::format;
+use alloc::string::String;
+
+use super::{register_device, AcpiHid, DeviceInfo};
+
+/// i8042 PS/2 키보드 포트
+pub const HID_PS2_KEYBOARD: &str = "PNP0303";
+/// i8042 PS/2 보조(마우스) 포트
+pub const HID_PS2_MOUSE: &str = "PNP0F13";
+/// 레거시 IDE 컨트롤러 (0x1F0/0x3F6)
+pub const HID_IDE: &str = "PNP0600";
+/// AMD FCH DesignWare I2C 컨트롤러
+pub const HID_AMD_I2C: &str = "AMDI0010";
+/// 부트 프레임버퍼 디바이스 이름
+pub const BOOT_FRAMEBUFFER: &str = "bootfb";
+
+fn register(name: String, hid: Option<&str>, mmio: Option<u64>, irq: Option<u32>) -> Option<super::DeviceId> {
+    let info = DeviceInfo::Platform { hid: hid.map(AcpiHid::new), mmio, irq };
+    match register_device(name, None, info) {
+        Ok(id) => Some(id),
+        Err(e) => {
+            crate::log_warn!("Platform: failed to register device: {}", e);
+            None
+        }
+    }
+}
+
+/// 플랫폼 디바이스 열거
+///
+/// # Safety
+/// 메모리 관리가 초기화된 후에 호출되어야 합니다 (ACPI 테이블 접근).
+pub unsafe fn enumerate() {
+    register(String::from("i8042-kbd"), Some(HID_PS2_KEYBOARD), None, None);
+    register(String::from("i8042-aux"), Some(HID_PS2_MOUSE), None, None);
+    #[cfg(feature = "fs")]
+    register(String::from("ide0"), Some(HID_IDE), None, None);
+
+    if crate::boot::info::has_framebuffer() {
+        register(String::from(BOOT_FRAMEBUFFER), None, None, None);
+    }
+
+    // TODO: DSDT에서 I2C 컨트롤러를 모두 열거 (현재는 터치패드가 붙은 컨트롤러 하나)
+    if let Some(info) = crate::power::acpi::find_i2c_touchpad() {
+        let Some(controller) = register(String::from("i2c0"), Some(HID_AMD_I2C), Some(info.base_address.as_u64()), Some(info.gsi)) else {
+            return;
+        };
+        let hid = AcpiHid(info.hid);
+        let child = DeviceInfo::I2c { address: info.slave_address as u16, hid: Some(hid) };
+        if let Err(e) = register_device(format!("i2c-{}:{:02x}", hid.as_str(), info.slave_address), Some(controller), child) {
+            crate::log_warn!("Platform: failed to register I2C device: {}", e);
+        }
+    }
+}
@@ -6,6 +6,8 @@
 use bootloader_api::info::{FrameBuffer, PixelFormat};
 use spin::Mutex;
 
+use crate::drivers::base::{platform, Device, DeviceMatch, Driver};
+
 /// 프레임버퍼 전역 인스턴스
 static FRAMEBUFFER: Mutex<Option<FrameBufferWriter>> = Mutex::new(None);
 static DISPLAY_BLANKED: Mutex<bool> = Mutex::new(false);
@@ -257,6 +259,16 @@ impl FrameBufferWriter {
     pub fn info(&self) -> &FrameBufferInfo {
         &self.info
     }
+
+    /// 픽셀 메모리 전체 (`stride * height * bytes_per_pixel` 바이트, 픽셀 형식 그대로)
+    pub fn bytes(&self) -> &[u8] {
+        self.buffer
+    }
+
+    /// 픽셀 메모리 전체 (쓰기용)
+    pub fn bytes_mut(&mut self) -> &mut [u8] {
+        self.buffer
+    }
 }
 
 /// 프레임버퍼 초기화
@@ -265,6 +277,42 @@ pub fn init(framebuffer: &'static mut FrameBuffer) {
     *FRAMEBUFFER.lock() = Some(writer);
 }
 
+/// 부트 프레임버퍼 디스플레이 드라이버 (디바이스 모델)
+struct FrameBufferDriver;
+
+/// 디바이스 모델에 등록할 프레임버퍼 드라이버
+pub static DRIVER: &dyn Driver = &FrameBufferDriver;
+
+impl Driver for FrameBufferDriver {
+    fn name(&self) -> &'static str {
+        "bootfb"
+    }
+
+    fn match_table(&self) -> &'static [DeviceMatch] {
+        &[DeviceMatch::Name(platform::BOOT_FRAMEBUFFER)]
+    }
+
+    fn probe(&self, _device: &Device) -> Result<(), &'static str> {
+        let framebuffer = unsafe { crate::boot::info::get_framebuffer() }.ok_or("no boot framebuffer")?;
+        init(framebuffer);
+        Ok(())
+    }
+
+    fn remove(&self, _device: &Device) {
+        *FRAMEBUFFER.lock() = None;
+    }
+
+    fn suspend(&self, _device: &Device) -> Result<(), &'static str> {
+        crate::power::device::dpms_set_display_sleep(true);
+        Ok(())
+    }
+
+    fn resume(&self, _device: &Device) -> Result<(), &'static str> {
+        crate::power::device::dpms_set_display_sleep(false);
+        Ok(())
+    }
+}
+
 /// 프레임버퍼가 초기화되었는지 확인
 pub fn is_initialized() -> bool {
     FRAMEBUFFER.lock().is_some()
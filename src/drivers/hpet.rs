@@ -0,0 +1,158 @@
+//! HPET (High Precision Event Timer) 드라이버
+//!
+//! ACPI HPET 테이블에서 레지스터 블록 주소를 찾아 메인 카운터를 활성화하고
+//! 클럭소스로 등록합니다. 비교기(타이머 0..N)는 사용하지 않습니다.
+//!
+//! # 참고 자료
+//! - IA-PC HPET (High Precision Event Timers) Specification 1.0a
+
+use core::ptr::{read_volatile, write_volatile};
+use core::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use x86_64::PhysAddr;
+
+use crate::time::clocksource::{self, rating, ClockSource};
+
+/// HPET 레지스터 오프셋
+mod reg {
+    /// General Capabilities and ID
+    pub const GCAP_ID: u64 = 0x000;
+    /// General Configuration
+    pub const GEN_CONF: u64 = 0x010;
+    /// Main Counter Value
+    pub const MAIN_COUNTER: u64 = 0x0F0;
+}
+
+/// GCAP_ID: 64비트 메인 카운터 지원 (COUNT_SIZE_CAP)
+const GCAP_COUNT_SIZE_64: u64 = 1 << 13;
+/// GEN_CONF: 메인 카운터 활성화 (ENABLE_CNF)
+const GEN_CONF_ENABLE: u64 = 1 << 0;
+/// GEN_CONF: 레거시 교체 라우팅 (LEG_RT_CNF)
+const GEN_CONF_LEGACY_ROUTE: u64 = 1 << 1;
+/// 명세상 최대 카운터 주기 (100ns = 10^8 fs)
+const MAX_PERIOD_FS: u64 = 100_000_000;
+/// 1초 = 10^15 fs
+const FS_PER_SEC: u64 = 1_000_000_000_000_000;
+
+/// ACPI HPET 테이블에서 읽은 정보
+#[derive(Debug, Clone, Copy)]
+pub struct HpetTable {
+    /// Event Timer Block ID (하드웨어 리비전, 비교기 수, 벤더 ID)
+    pub event_timer_block_id: u32,
+    /// 레지스터 블록 물리 주소
+    pub base_address: u64,
+    /// HPET 번호
+    pub hpet_number: u8,
+    /// 주기 모드 최소 틱
+    pub min_tick: u16,
+}
+
+/// HPET 레지스터 가상 주소 (0 = 미초기화)
+static HPET_VIRT_BASE: AtomicU64 = AtomicU64::new(0);
+/// 메인 카운터 주파수 (Hz)
+static FREQ_HZ: AtomicU64 = AtomicU64::new(0);
+/// 64비트 카운터 여부
+static COUNTER_64: AtomicBool = AtomicBool::new(false);
+
+/// ACPI HPET 테이블 파싱
+pub fn parse_table(data: &[u8]) -> Option<HpetTable> {
+    // 헤더(36) + Block ID(4) + GAS(12) + 번호(1) + 최소 틱(2) + 페이지 보호(1)
+    if data.len() < 56 || &data[0..4] != b"HPET" {
+        return None;
+    }
+    // Generic Address Structure: address space ID 0 = 시스템 메모리
+    if data[40] != 0 {
+        return None;
+    }
+    Some(HpetTable {
+        event_timer_block_id: u32::from_le_bytes(data[36..40].try_into().ok()?),
+        base_address: u64::from_le_bytes(data[44..52].try_into().ok()?),
+        hpet_number: data[52],
+        min_tick: u16::from_le_bytes(data[53..55].try_into().ok()?),
+    })
+}
+
+/// HPET 초기화 및 클럭소스 등록
+///
+/// # Safety
+/// 메모리 관리가 초기화된 후에 한 번 호출되어야 합니다.
+pub unsafe fn init() -> Result<(), &'static str> {
+    let table = crate::power::acpi_table_fetch(b"HPET").ok_or("ACPI HPET table not found")?;
+    let info = parse_table(table).ok_or("invalid ACPI HPET table")?;
+    if info.base_address == 0 {
+        return Err("HPET base address is zero");
+    }
+
+    let virt = crate::memory::paging::phys_to_virt(PhysAddr::new(info.base_address))
+        .ok_or("physical memory offset not available")?;
+    HPET_VIRT_BASE.store(virt.as_u64(), Ordering::Release);
+
+    let caps = read_reg(reg::GCAP_ID);
+    let period_fs = caps >> 32;
+    if period_fs == 0 || period_fs > MAX_PERIOD_FS {
+        HPET_VIRT_BASE.store(0, Ordering::Release);
+        return Err("invalid HPET counter period");
+    }
+    let counter_64 = caps & GCAP_COUNT_SIZE_64 != 0;
+    let freq_hz = FS_PER_SEC / period_fs;
+    let comparators = ((caps >> 8) & 0x1F) + 1;
+
+    // 레거시 라우팅은 끄고 (PIT/RTC IRQ 유지) 메인 카운터만 동작시킴
+    let conf = read_reg(reg::GEN_CONF);
+    write_reg(reg::GEN_CONF, (conf & !GEN_CONF_LEGACY_ROUTE) | GEN_CONF_ENABLE);
+
+    FREQ_HZ.store(freq_hz, Ordering::Release);
+    COUNTER_64.store(counter_64, Ordering::Release);
+
+    crate::log_info!(
+        "HPET #{} at 0x{:X}: {} Hz, {}-bit counter, {} comparators",
+        info.hpet_number,
+        info.base_address,
+        freq_hz,
+        if counter_64 { 64 } else { 32 },
+        comparators
+    );
+
+    clocksource::register(ClockSource {
+        name: "hpet",
+        rating: rating::HPET,
+        read: read_counter,
+        mask: counter_mask(),
+        freq_hz,
+    });
+    Ok(())
+}
+
+/// HPET 사용 가능 여부
+pub fn is_available() -> bool {
+    FREQ_HZ.load(Ordering::Acquire) != 0
+}
+
+/// 메인 카운터 주파수 (Hz, 미초기화 시 0)
+pub fn frequency_hz() -> u64 {
+    FREQ_HZ.load(Ordering::Acquire)
+}
+
+/// 메인 카운터 유효 비트 마스크
+pub fn counter_mask() -> u64 {
+    if COUNTER_64.load(Ordering::Acquire) { u64::MAX } else { u32::MAX as u64 }
+}
+
+/// 메인 카운터 읽기 (미초기화 시 0)
+pub fn read_counter() -> u64 {
+    if HPET_VIRT_BASE.load(Ordering::Acquire) == 0 {
+        return 0;
+    }
+    unsafe { read_reg(reg::MAIN_COUNTER) & counter_mask() }
+}
+
+/// HPET 레지스터 읽기
+unsafe fn read_reg(offset: u64) -> u64 {
+    let base = HPET_VIRT_BASE.load(Ordering::Acquire);
+    read_volatile((base + offset) as *const u64)
+}
+
+/// HPET 레지스터 쓰기
+unsafe fn write_reg(offset: u64, value: u64) {
+    let base = HPET_VIRT_BASE.load(Ordering::Acquire);
+    write_volatile((base + offset) as *mut u64, value);
+}
@@ -2,10 +2,17 @@
 //!
 //! AMD FCH (Fusion Controller Hub) I2C 컨트롤러를 지원합니다.
 
+use core::sync::atomic::{AtomicBool, AtomicU32, Ordering};
 use spin::Mutex;
 use x86_64::structures::paging::{PhysFrame, Size4KiB};
+use x86_64::instructions::interrupts;
 use x86_64::{PhysAddr, VirtAddr};
 
+use crate::drivers::base::{platform, Device, DeviceInfo, DeviceMatch, Driver};
+use crate::interrupts::irq;
+use crate::task::{self, Event};
+use crate::time::{NSEC_PER_MSEC, NSEC_PER_USEC};
+
 /// I2C 에러 타입
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum I2cError {
@@ -322,6 +329,49 @@ pub unsafe fn init_controller(index: usize, physical_base: PhysAddr) -> Result<(
     controllers[index].init(physical_base)
 }
 
+/// ACPI로 열거된 I2C 컨트롤러 드라이버 (디바이스 모델)
+///
+/// 디바이스 이름의 번호(`i2c0`)를 컨트롤러 인덱스로 사용합니다.
+struct I2cControllerDriver;
+
+/// 디바이스 모델에 등록할 I2C 컨트롤러 드라이버
+pub static DRIVER: &dyn Driver = &I2cControllerDriver;
+
+impl Driver for I2cControllerDriver {
+    fn name(&self) -> &'static str {
+        "i2c-designware"
+    }
+
+    fn match_table(&self) -> &'static [DeviceMatch] {
+        &[DeviceMatch::Acpi(platform::HID_AMD_I2C)]
+    }
+
+    fn probe(&self, device: &Device) -> Result<(), &'static str> {
+        let index = device.name.strip_prefix("i2c").and_then(|n| n.parse::<usize>().ok()).ok_or("bad I2C controller name")?;
+        let DeviceInfo::Platform { mmio: Some(base), irq: gsi, .. } = device.info else {
+            return Err("I2C controller has no MMIO base");
+        };
+        match unsafe { init_controller(index, PhysAddr::new(base)) } {
+            Ok(()) => {
+                crate::log_info!("I2C controller initialized at 0x{:X}", base);
+                if let Some(gsi) = gsi {
+                    CONTROLLER_GSI[index].store(gsi, Ordering::Release);
+                    if let Err(e) = irq::request_irq(gsi, irq_handler) {
+                        // 완료 인터럽트 없이도 비동기 전송은 짧은 주기로 상태를 재확인
+                        CONTROLLER_GSI[index].store(NO_GSI, Ordering::Release);
+                        crate::log_warn!("I2C: failed to request GSI {}: {}", gsi, e);
+                    }
+                }
+                Ok(())
+            }
+            Err(e) => {
+                crate::log_warn!("Failed to initialize I2C controller: {:?}", e);
+                Err("I2C controller init failed")
+            }
+        }
+    }
+}
+
 /// I2C 컨트롤러 가져오기
 pub fn get_controller(index: usize) -> Option<&'static Mutex<[I2cController; 4]>> {
     if index >= 4 {
@@ -332,13 +382,202 @@ pub fn get_controller(index: usize) -> Option<&'static Mutex<[I2cController; 4]>
 
 /// I2C 읽기 (컨트롤러 0 사용)
 pub fn read(slave_addr: u8, buffer: &mut [u8]) -> Result<usize, I2cError> {
+    if ASYNC_BUSY[0].load(Ordering::Acquire) {
+        return Err(I2cError::BusBusy);
+    }
     let controllers = I2C_CONTROLLERS.lock();
     controllers[0].read(slave_addr, buffer)
 }
 
 /// I2C 쓰기 (컨트롤러 0 사용)
 pub fn write(slave_addr: u8, buffer: &[u8]) -> Result<usize, I2cError> {
+    if ASYNC_BUSY[0].load(Ordering::Acquire) {
+        return Err(I2cError::BusBusy);
+    }
     let controllers = I2C_CONTROLLERS.lock();
     controllers[0].write(slave_addr, buffer)
 }
 
+
+/// 상태 레지스터 비트
+const STATUS_START_DONE: u32 = 0x08;
+const STATUS_STOP_DONE: u32 = 0x10;
+const STATUS_NACK: u32 = 0x20;
+const STATUS_TX_DONE: u32 = 0x40;
+const STATUS_RX_DONE: u32 = 0x80;
+
+/// 비동기 전송 중 상태 재확인 주기 (완료 인터럽트가 없을 때의 폴백)
+const ASYNC_POLL_NS: u64 = 100 * NSEC_PER_USEC;
+/// 비동기 전송 단계별 제한 시간
+const ASYNC_STEP_TIMEOUT_NS: u64 = 10 * NSEC_PER_MSEC;
+
+/// 전송 단계 완료 인터럽트 (InterruptControl 레지스터, 상태 비트와 같은 위치)
+const INTR_TRANSFER_DONE: u32 = STATUS_START_DONE | STATUS_STOP_DONE | STATUS_NACK | STATUS_TX_DONE | STATUS_RX_DONE;
+
+/// 인터럽트가 없는 컨트롤러
+const NO_GSI: u32 = u32::MAX;
+
+/// 컨트롤러별 인터럽트 GSI
+static CONTROLLER_GSI: [AtomicU32; 4] = [const { AtomicU32::new(NO_GSI) }; 4];
+
+/// 컨트롤러별 전송 완료 이벤트
+static TRANSFER_EVENTS: [Event; 4] = [const { Event::new() }; 4];
+
+/// 컨트롤러별 비동기 전송 진행 여부
+///
+/// 비동기 전송은 단계 사이에 컨트롤러 락을 놓으므로, 그 사이 동기 전송이
+/// 끼어들지 않도록 버스를 점유합니다.
+static ASYNC_BUSY: [AtomicBool; 4] = [const { AtomicBool::new(false) }; 4];
+
+/// I2C 컨트롤러 IRQ 핸들러
+///
+/// GSI를 공유하는 컨트롤러를 모두 확인합니다 (`interrupts::irq::request_irq`로 등록).
+fn irq_handler(gsi: u32) {
+    for (index, controller_gsi) in CONTROLLER_GSI.iter().enumerate() {
+        if controller_gsi.load(Ordering::Acquire) == gsi {
+            handle_interrupt(index);
+        }
+    }
+}
+
+/// I2C 컨트롤러 인터럽트 처리
+///
+/// 레벨 트리거 인터럽트가 반복되지 않도록 컨트롤러 인터럽트를 마스크하고,
+/// 진행 중인 비동기 전송을 깨웁니다. 다음 단계를 시작할 때 다시 활성화됩니다.
+/// 락을 잡지 못하면 동기 전송 중이며, 이때는 컨트롤러 인터럽트가 꺼져 있습니다.
+pub fn handle_interrupt(index: usize) {
+    let Some(event) = TRANSFER_EVENTS.get(index) else { return };
+    if let Some(controllers) = I2C_CONTROLLERS.try_lock() {
+        unsafe { controllers[index].write_reg(I2cReg::InterruptControl, 0) };
+    }
+    event.signal();
+}
+
+/// 비동기 전송 동안의 버스 점유 (drop 시 해제)
+struct BusClaim(usize);
+
+impl BusClaim {
+    fn acquire(index: usize) -> Result<Self, I2cError> {
+        let busy = ASYNC_BUSY.get(index).ok_or(I2cError::InvalidAddress)?;
+        if busy.swap(true, Ordering::AcqRel) {
+            return Err(I2cError::BusBusy);
+        }
+        let claim = BusClaim(index);
+        if !I2C_CONTROLLERS.lock()[index].initialized {
+            return Err(I2cError::NotInitialized);
+        }
+        Ok(claim)
+    }
+}
+
+impl Drop for BusClaim {
+    fn drop(&mut self) {
+        // 동기 전송에 버스를 넘기기 전에 완료 인터럽트 끄기
+        with_controller(self.0, |c| unsafe { c.write_reg(I2cReg::InterruptControl, 0) });
+        ASYNC_BUSY[self.0].store(false, Ordering::Release);
+    }
+}
+
+/// 컨트롤러 락을 잠깐 잡고 레지스터 접근
+///
+/// IRQ 핸들러도 같은 락을 쓰므로 인터럽트를 끈 상태로 잡습니다.
+fn with_controller<R>(index: usize, f: impl FnOnce(&I2cController) -> R) -> R {
+    interrupts::without_interrupts(|| {
+        let controllers = I2C_CONTROLLERS.lock();
+        f(&controllers[index])
+    })
+}
+
+/// 상태 비트가 설정될 때까지 대기 (스핀 대신 완료 이벤트 또는 짧은 sleep)
+async fn wait_status(index: usize, done: u32) -> Result<u32, I2cError> {
+    let deadline = crate::time::now_ns().saturating_add(ASYNC_STEP_TIMEOUT_NS);
+    loop {
+        // 아직 완료되지 않았으면 완료 인터럽트를 (다시) 켜고 대기
+        let status = with_controller(index, |c| unsafe {
+            let status = c.read_reg(I2cReg::Status);
+            if status & (done | STATUS_NACK) == 0 {
+                c.write_reg(I2cReg::InterruptControl, INTR_TRANSFER_DONE);
+            }
+            status
+        });
+        if status & STATUS_NACK != 0 && done == STATUS_TX_DONE {
+            return Err(I2cError::Nack);
+        }
+        if status & done != 0 {
+            return Ok(status);
+        }
+        if crate::time::now_ns() >= deadline {
+            return Err(I2cError::Timeout);
+        }
+        let _ = task::with_timeout(ASYNC_POLL_NS, TRANSFER_EVENTS[index].wait()).await;
+    }
+}
+
+/// 레지스터를 쓰고 완료 상태 대기
+async fn issue(index: usize, reg: I2cReg, value: impl FnOnce(u32) -> u32, done: u32) -> Result<u32, I2cError> {
+    TRANSFER_EVENTS[index].reset();
+    with_controller(index, |c| unsafe {
+        let current = match reg {
+            I2cReg::Data => 0,
+            _ => c.read_reg(I2cReg::Control),
+        };
+        c.write_reg(reg, value(current));
+    });
+    wait_status(index, done).await
+}
+
+async fn start_async(index: usize, addr_byte: u8) -> Result<(), I2cError> {
+    issue(index, I2cReg::Control, |c| c | 0x02, STATUS_START_DONE).await?;
+    issue(index, I2cReg::Data, |_| addr_byte as u32, STATUS_TX_DONE).await?;
+    Ok(())
+}
+
+async fn stop_async(index: usize) {
+    // STOP은 타임아웃 무시 (동기 경로와 동일)
+    let _ = issue(index, I2cReg::Control, |c| c | 0x04, STATUS_STOP_DONE).await;
+}
+
+/// 비동기 I2C 읽기
+///
+/// 각 바이트 전송 완료를 스핀하지 않고 기다리므로, 대기 중에는 다른 태스크가
+/// 실행되거나 CPU가 유휴 상태로 들어갈 수 있습니다.
+///
+/// # Arguments
+/// * `index` - 컨트롤러 인덱스 (0-3)
+/// * `slave_addr` - 슬레이브 장치 주소 (7비트)
+/// * `buffer` - 읽을 데이터를 저장할 버퍼
+pub async fn read_async(index: usize, slave_addr: u8, buffer: &mut [u8]) -> Result<usize, I2cError> {
+    let _claim = BusClaim::acquire(index)?;
+
+    start_async(index, (slave_addr << 1) | 0x01).await?;
+
+    let len = buffer.len();
+    for (i, byte) in buffer.iter_mut().enumerate() {
+        let last = i == len - 1;
+        // 마지막 바이트는 NACK
+        issue(index, I2cReg::Control, |c| if last { c | 0x08 } else { c & !0x08 }, STATUS_RX_DONE).await?;
+        *byte = with_controller(index, |c| unsafe { c.read_reg(I2cReg::Data) }) as u8;
+    }
+
+    stop_async(index).await;
+    Ok(len)
+}
+
+/// 비동기 I2C 쓰기
+///
+/// # Arguments
+/// * `index` - 컨트롤러 인덱스 (0-3)
+/// * `slave_addr` - 슬레이브 장치 주소 (7비트)
+/// * `buffer` - 전송할 데이터
+pub async fn write_async(index: usize, slave_addr: u8, buffer: &[u8]) -> Result<usize, I2cError> {
+    let _claim = BusClaim::acquire(index)?;
+
+    start_async(index, slave_addr << 1).await?;
+
+    for &byte in buffer {
+        issue(index, I2cReg::Data, |_| byte as u32, STATUS_TX_DONE).await?;
+    }
+
+    stop_async(index).await;
+    Ok(buffer.len())
+}
@@ -2,7 +2,9 @@
 //!
 //! I2C-HID 사양을 따르는 HID 장치를 지원합니다.
 
-use crate::drivers::i2c::{I2cError, read as i2c_read, write as i2c_write};
+use alloc::vec;
+
+use crate::drivers::i2c::{self, I2cError, read as i2c_read, write as i2c_write};
 
 /// I2C-HID 에러 타입
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -84,6 +86,7 @@ enum PowerState {
 }
 
 /// I2C-HID 장치
+#[derive(Clone, Copy)]
 pub struct I2cHidDevice {
     /// I2C 슬레이브 주소
     slave_addr: u8,
@@ -205,28 +208,37 @@ impl I2cHidDevice {
             ];
             i2c_write(self.slave_addr, &reg_addr)?;
             
-            // 데이터 읽기 (첫 2바이트는 길이)
-            let max_len = (desc.max_input_length as usize).min(buffer.len());
-            let mut temp_buffer = [0u8; 64]; // 임시 버퍼 (최대 64바이트)
-            
-            i2c_read(self.slave_addr, &mut temp_buffer[..max_len])?;
-            
-            // 첫 2바이트는 길이 정보
-            let data_len = u16::from_le_bytes([temp_buffer[0], temp_buffer[1]]) as usize;
-            
-            if data_len > buffer.len() {
-                return Err(I2cHidError::BufferOverflow);
-            }
-            
-            // 실제 데이터 복사 (길이 정보 제외)
-            buffer[..data_len].copy_from_slice(&temp_buffer[2..2 + data_len]);
+            // 데이터 읽기 (첫 2바이트는 길이, 최대 길이는 Descriptor 기준)
+            let mut raw = vec![0u8; desc.max_input_length as usize];
+            i2c_read(self.slave_addr, &mut raw)?;
             
-            Ok(data_len)
+            copy_input_report(&raw, buffer)
         } else {
             Err(I2cHidError::DescriptorReadError)
         }
     }
 
+    /// Input Report 비동기 읽기
+    ///
+    /// `read_input_report`와 같지만 I2C 전송 완료를 스핀하지 않고 기다립니다.
+    pub async fn read_input_report_async(&self, buffer: &mut [u8]) -> Result<usize, I2cHidError> {
+        if !self.initialized {
+            return Err(I2cHidError::I2cError(I2cError::NotInitialized));
+        }
+
+        let desc = self.descriptor.ok_or(I2cHidError::DescriptorReadError)?;
+        let reg_addr = [
+            (desc.input_register & 0xFF) as u8,
+            ((desc.input_register >> 8) & 0xFF) as u8,
+        ];
+        i2c::write_async(0, self.slave_addr, &reg_addr).await?;
+
+        let mut raw = vec![0u8; desc.max_input_length as usize];
+        i2c::read_async(0, self.slave_addr, &mut raw).await?;
+
+        copy_input_report(&raw, buffer)
+    }
+
     /// Output Report 쓰기
     ///
     /// # Arguments
@@ -295,3 +307,17 @@ impl I2cHidDevice {
     }
 }
 
+/// Input Register에서 읽은 원시 데이터에서 리포트 본문을 복사
+///
+/// 원시 데이터의 첫 2바이트는 리포트 길이입니다.
+fn copy_input_report(raw: &[u8], buffer: &mut [u8]) -> Result<usize, I2cHidError> {
+    if raw.len() < 2 {
+        return Err(I2cHidError::InvalidDescriptor);
+    }
+    let data_len = u16::from_le_bytes([raw[0], raw[1]]) as usize;
+    if data_len > buffer.len() || 2 + data_len > raw.len() {
+        return Err(I2cHidError::BufferOverflow);
+    }
+    buffer[..data_len].copy_from_slice(&raw[2..2 + data_len]);
+    Ok(data_len)
+}
@@ -0,0 +1,155 @@
+//! 입력 이벤트 스트림
+//!
+//! 키보드와 마우스 드라이버가 보고한 이벤트를 디바이스별 링 버퍼에 쌓습니다.
+//! 각 리더(devfs의 `/dev/input/eventN` 핸들 등)는 자기 순번을 들고 읽으므로 여러 리더가
+//! 같은 이벤트를 모두 받습니다. 느린 리더는 링을 넘친 오래된 이벤트를 놓칩니다.
+//!
+//! 이벤트 형식은 Linux evdev를 따릅니다 (종류, 코드, 값 + 동기화 이벤트로 묶음 구분).
+
+use spin::Mutex;
+
+/// 이벤트 묶음 끝
+pub const EV_SYN: u16 = 0x00;
+/// 키/버튼 (값: 1 = 누름, 0 = 뗌)
+pub const EV_KEY: u16 = 0x01;
+/// 상대 이동 (값: 이동량)
+pub const EV_REL: u16 = 0x02;
+
+/// `EV_SYN` 코드: 묶음 보고 완료
+pub const SYN_REPORT: u16 = 0x00;
+/// `EV_REL` 코드: X축 (오른쪽이 양수)
+pub const REL_X: u16 = 0x00;
+/// `EV_REL` 코드: Y축 (아래쪽이 양수)
+pub const REL_Y: u16 = 0x01;
+/// `EV_KEY` 코드: 마우스 버튼
+pub const BTN_LEFT: u16 = 0x110;
+pub const BTN_RIGHT: u16 = 0x111;
+pub const BTN_MIDDLE: u16 = 0x112;
+
+/// 디바이스당 보관하는 이벤트 수
+const RING_SIZE: usize = 256;
+
+/// 입력 디바이스 (`/dev/input/event<번호>`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputDevice {
+    Keyboard = 0,
+    Mouse = 1,
+}
+
+impl InputDevice {
+    pub const ALL: [InputDevice; 2] = [InputDevice::Keyboard, InputDevice::Mouse];
+}
+
+/// 입력 이벤트
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InputEvent {
+    /// 부팅 후 시각 (마이크로초)
+    pub time_us: u64,
+    pub kind: u16,
+    pub code: u16,
+    pub value: i32,
+}
+
+impl InputEvent {
+    /// 직렬화 크기 (바이트)
+    pub const SIZE: usize = 16;
+
+    const EMPTY: InputEvent = InputEvent { time_us: 0, kind: 0, code: 0, value: 0 };
+
+    /// 리틀 엔디안 레코드로 직렬화 (시각 8, 종류 2, 코드 2, 값 4바이트)
+    pub fn encode(&self) -> [u8; Self::SIZE] {
+        let mut out = [0u8; Self::SIZE];
+        out[0..8].copy_from_slice(&self.time_us.to_le_bytes());
+        out[8..10].copy_from_slice(&self.kind.to_le_bytes());
+        out[10..12].copy_from_slice(&self.code.to_le_bytes());
+        out[12..16].copy_from_slice(&self.value.to_le_bytes());
+        out
+    }
+}
+
+/// 디바이스 하나의 이벤트 링
+struct Stream {
+    events: [InputEvent; RING_SIZE],
+    /// 다음 이벤트의 순번 (지금까지 보고된 이벤트 수)
+    next_seq: u64,
+}
+
+impl Stream {
+    const fn new() -> Self {
+        Self { events: [InputEvent::EMPTY; RING_SIZE], next_seq: 0 }
+    }
+
+    fn push(&mut self, event: InputEvent) {
+        self.events[(self.next_seq % RING_SIZE as u64) as usize] = event;
+        self.next_seq += 1;
+    }
+
+    /// `seq`부터 `out`에 복사하고 `seq`를 다음 읽을 위치로 옮김
+    fn read(&self, seq: &mut u64, out: &mut [InputEvent]) -> usize {
+        // 링을 넘쳐 덮어쓴 이벤트는 건너뜀
+        let oldest = self.next_seq.saturating_sub(RING_SIZE as u64);
+        if *seq < oldest {
+            *seq = oldest;
+        }
+        let mut count = 0;
+        while *seq < self.next_seq && count < out.len() {
+            out[count] = self.events[(*seq % RING_SIZE as u64) as usize];
+            *seq += 1;
+            count += 1;
+        }
+        count
+    }
+}
+
+static STREAMS: [Mutex<Stream>; 2] = [Mutex::new(Stream::new()), Mutex::new(Stream::new())];
+
+/// 이벤트 묶음 보고 (끝에 `SYN_REPORT`를 붙임)
+///
+/// 인터럽트 컨텍스트에서 호출되므로 링이 잠겨 있으면 묶음을 버립니다.
+///
+/// # Arguments
+/// * `device` - 보고하는 디바이스
+/// * `events` - (종류, 코드, 값) 목록
+pub fn report(device: InputDevice, events: &[(u16, u16, i32)]) {
+    let Some(mut stream) = STREAMS[device as usize].try_lock() else {
+        return;
+    };
+    let time_us = crate::time::now_ns() / crate::time::NSEC_PER_USEC;
+    for &(kind, code, value) in events {
+        stream.push(InputEvent { time_us, kind, code, value });
+    }
+    stream.push(InputEvent { time_us, kind: EV_SYN, code: SYN_REPORT, value: 0 });
+}
+
+/// 다음에 보고될 이벤트의 순번 (새 리더의 시작 위치)
+pub fn current_seq(device: InputDevice) -> u64 {
+    STREAMS[device as usize].lock().next_seq
+}
+
+/// 순번 `seq`부터 이벤트 읽기 (논블로킹)
+///
+/// # Returns
+/// 읽은 이벤트 수. `seq`는 다음 읽을 순번으로 갱신됩니다.
+pub fn read_events(device: InputDevice, seq: &mut u64, out: &mut [InputEvent]) -> usize {
+    STREAMS[device as usize].lock().read(seq, out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test_case]
+    fn test_input_ring_overflow() {
+        let mut stream = Stream::new();
+        for i in 0..(RING_SIZE as i32 + 10) {
+            stream.push(InputEvent { time_us: 0, kind: EV_KEY, code: 1, value: i });
+        }
+        // 가장 오래된 10개는 덮어써졌으므로 11번째부터 읽힘
+        let mut seq = 0;
+        let mut out = [InputEvent::EMPTY; 4];
+        assert_eq!(stream.read(&mut seq, &mut out), 4);
+        assert_eq!(out[0].value, 10);
+        assert_eq!(seq, 14);
+        assert_eq!(InputEvent { time_us: 1, kind: EV_REL, code: REL_Y, value: -1 }.encode()[8..], [2, 0, 1, 0, 0xFF, 0xFF, 0xFF, 0xFF]);
+    }
+}
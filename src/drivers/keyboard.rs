@@ -4,7 +4,9 @@
 
 use x86_64::instructions::port::Port;
 use spin::Mutex;
-use crate::interrupts::pic;
+
+use crate::drivers::base::{platform, Device, DeviceMatch, Driver};
+use crate::interrupts::irq;
 
 /// 키보드 I/O 포트
 const KEYBOARD_DATA_PORT: u16 = 0x60;
@@ -15,6 +17,70 @@ const KEYBOARD_STATUS_PORT: u16 = 0x64;
 const STATUS_OUTPUT_BUFFER_FULL: u8 = 0x01;
 const STATUS_INPUT_BUFFER_FULL: u8 = 0x02;
 
+/// 확장 키 접두어 (다음 바이트가 확장 키의 스캔 코드)
+const SCAN_EXTENDED: u8 = 0xE0;
+/// Pause 키 접두어 (다음 두 바이트가 한 묶음)
+const SCAN_PAUSE: u8 = 0xE1;
+
+/// 확장 키(`E0 xx`)의 키 코드: `0xE000 | xx` (예: 위 화살표 `0xE048`, 오른쪽 Ctrl `0xE01D`)
+pub const KEY_EXTENDED: u16 = 0xE000;
+/// Pause 키(`E1 1D 45`)의 키 코드: `0xE100 | 45`
+pub const KEY_PAUSE: u16 = 0xE145;
+
+/// 세트 1 스캔 코드 바이트열을 키 이벤트로 묶는 상태
+///
+/// `E0` 접두어는 다음 바이트와 합쳐 확장 키 코드로 만들고, Print Screen 앞뒤에 붙는
+/// 가짜 Shift(`E0 2A`, `E0 36`)는 버립니다. Pause는 `E1 1D 45`(누름)와 `E1 9D C5`(뗌)로 옵니다.
+struct ScanDecoder {
+    extended: bool,
+    /// Pause 묶음에서 남은 바이트 수
+    pause_left: u8,
+}
+
+impl ScanDecoder {
+    const fn new() -> Self {
+        Self { extended: false, pause_left: 0 }
+    }
+
+    /// 스캔 코드 바이트 하나 넣기
+    ///
+    /// # Returns
+    /// 키 이벤트가 완성되면 `(키 코드, 눌림 여부)`. 키 코드는 일반 키면 스캔 코드의 하위
+    /// 7비트, 확장 키면 `KEY_EXTENDED | 하위 7비트`, Pause면 `KEY_PAUSE`입니다.
+    fn feed(&mut self, scan_code: u8) -> Option<(u16, bool)> {
+        let pressed = scan_code & 0x80 == 0;
+        let code = (scan_code & 0x7F) as u16;
+        if self.pause_left > 0 {
+            self.pause_left -= 1;
+            return (self.pause_left == 0).then_some((KEY_PAUSE, pressed));
+        }
+        match scan_code {
+            SCAN_EXTENDED => {
+                self.extended = true;
+                None
+            }
+            SCAN_PAUSE => {
+                self.extended = false;
+                self.pause_left = 2;
+                None
+            }
+            _ if self.extended => {
+                self.extended = false;
+                match code {
+                    0x2A | 0x36 => None,
+                    _ => Some((KEY_EXTENDED | code, pressed)),
+                }
+            }
+            _ => Some((code, pressed)),
+        }
+    }
+}
+
+/// IRQ 핸들러가 입력 이벤트를 만들 때 쓰는 디코더
+static IRQ_DECODER: Mutex<ScanDecoder> = Mutex::new(ScanDecoder::new());
+/// `read_char`가 쓰는 디코더 (키 버퍼는 접두어를 포함한 원래 바이트열)
+static CHAR_DECODER: Mutex<ScanDecoder> = Mutex::new(ScanDecoder::new());
+
 /// 키 코드 버퍼 (큐)
 const BUFFER_SIZE: usize = 256;
 static KEY_BUFFER: Mutex<KeyBuffer> = Mutex::new(KeyBuffer {
@@ -72,13 +138,13 @@ fn read_scan_code() -> Option<u8> {
     }
 }
 
-/// 키보드 인터럽트 핸들러
+/// PS/2 키보드 ISA IRQ 번호
+pub const KEYBOARD_IRQ: u8 = 1;
+
+/// 키보드 IRQ 핸들러
 ///
-/// IRQ 1 (인터럽트 33)에서 호출됩니다.
-pub extern "x86-interrupt" fn keyboard_interrupt_handler(_stack_frame: x86_64::structures::idt::InterruptStackFrame) {
-    // 인터럽트 메트릭 기록
-    crate::monitoring::record_interrupt();
-    
+/// ISA IRQ 1에서 호출됩니다 (`interrupts::irq::request_irq`로 등록).
+pub fn irq_handler(gsi: u32) {
     // 스캔 코드 읽기
     if let Some(scan_code) = read_scan_code() {
         // 사용자 활동 기록
@@ -89,12 +155,16 @@ pub extern "x86-interrupt" fn keyboard_interrupt_handler(_stack_frame: x86_64::s
         if !buffer.push(scan_code) {
             crate::log_warn!("Keyboard buffer full, dropping scan code: 0x{:02X}", scan_code);
         }
+        drop(buffer);
+
+        // 입력 이벤트 스트림 (코드는 `ScanDecoder::feed`의 키 코드)
+        if let Some((code, pressed)) = IRQ_DECODER.lock().feed(scan_code) {
+            use crate::drivers::input::{self, InputDevice, EV_KEY};
+            input::report(InputDevice::Keyboard, &[(EV_KEY, code, pressed as i32)]);
+        }
     }
-    
-    // PIC에 인터럽트 종료 신호 전송 (IRQ 1)
-    unsafe {
-        pic::end_of_interrupt(1);
-    }
+
+    crate::task::event::signal_irq(gsi);
 }
 
 /// 키보드 초기화
@@ -110,6 +180,41 @@ pub unsafe fn init() {
     crate::log_info!("Keyboard driver initialized");
 }
 
+/// i8042 키보드 포트 드라이버 (디바이스 모델)
+struct KeyboardDriver;
+
+/// 디바이스 모델에 등록할 키보드 드라이버
+pub static DRIVER: &dyn Driver = &KeyboardDriver;
+
+impl Driver for KeyboardDriver {
+    fn name(&self) -> &'static str {
+        "i8042-keyboard"
+    }
+
+    fn match_table(&self) -> &'static [DeviceMatch] {
+        &[DeviceMatch::Acpi(platform::HID_PS2_KEYBOARD)]
+    }
+
+    fn probe(&self, _device: &Device) -> Result<(), &'static str> {
+        unsafe { init(); }
+        irq::request_irq(irq::isa_irq_to_gsi(KEYBOARD_IRQ), irq_handler).map(|_| ())
+    }
+
+    fn remove(&self, _device: &Device) {
+        irq::free_irq(irq::isa_irq_to_gsi(KEYBOARD_IRQ), irq_handler);
+    }
+
+    fn suspend(&self, _device: &Device) -> Result<(), &'static str> {
+        irq::disable_irq(irq::isa_irq_to_gsi(KEYBOARD_IRQ));
+        Ok(())
+    }
+
+    fn resume(&self, _device: &Device) -> Result<(), &'static str> {
+        irq::enable_irq(irq::isa_irq_to_gsi(KEYBOARD_IRQ));
+        Ok(())
+    }
+}
+
 /// 키 코드 읽기 (논블로킹)
 ///
 /// 버퍼에서 키 코드를 읽습니다. 버퍼가 비어있으면 None을 반환합니다.
@@ -191,10 +296,43 @@ pub fn scan_code_to_ascii(scan_code: u8) -> Option<char> {
     }
 }
 
+/// 확장 키 중 문자가 있는 키 (키패드 Enter와 `/`)
+fn extended_key_to_ascii(code: u16) -> Option<char> {
+    match code {
+        0xE01C => Some('\n'),
+        0xE035 => Some('/'),
+        _ => None,
+    }
+}
+
 /// 키 입력 읽기 (ASCII 문자)
 ///
-/// 버퍼에서 키 코드를 읽고 ASCII 문자로 변환합니다.
+/// 버퍼에서 스캔 코드를 읽고 ASCII 문자로 변환합니다. 확장 키는 접두어와 함께 해석하므로
+/// 미디어 키(`E0 10` 등)가 같은 하위 바이트의 문자 키로 읽히지 않습니다.
 pub fn read_char() -> Option<char> {
-    read_key().and_then(scan_code_to_ascii)
+    let scan_code = read_key()?;
+    match CHAR_DECODER.lock().feed(scan_code)? {
+        (code, true) if code & KEY_EXTENDED == KEY_EXTENDED => extended_key_to_ascii(code),
+        (code, true) => scan_code_to_ascii(code as u8),
+        (_, false) => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test_case]
+    fn test_scan_decoder_extended_keys() {
+        let mut decoder = ScanDecoder::new();
+        let mut feed = |bytes: &[u8]| bytes.iter().filter_map(|&b| decoder.feed(b)).collect::<alloc::vec::Vec<_>>();
+        assert_eq!(feed(&[0x1D, 0x9D]), [(0x1D, true), (0x1D, false)]);
+        // 오른쪽 Ctrl은 왼쪽 Ctrl과 다른 코드
+        assert_eq!(feed(&[0xE0, 0x1D, 0xE0, 0x9D]), [(0xE01D, true), (0xE01D, false)]);
+        // Print Screen의 가짜 Shift는 버림
+        assert_eq!(feed(&[0xE0, 0x2A, 0xE0, 0x37]), [(0xE037, true)]);
+        assert_eq!(feed(&[0xE1, 0x1D, 0x45, 0xE1, 0x9D, 0xC5]), [(KEY_PAUSE, true), (KEY_PAUSE, false)]);
+        assert_eq!(feed(&[0x10]), [(0x10, true)]);
+    }
 }
 
@@ -0,0 +1,197 @@
+//! Local APIC 타이머 드라이버
+//!
+//! tickless idle(NO_HZ)을 위한 one-shot 타이머를 제공합니다.
+//! CPU가 TSC-deadline 모드를 지원하면 `IA32_TSC_DEADLINE` MSR에 만료 시점의 TSC 값을 쓰고,
+//! 그렇지 않으면 one-shot 모드의 Initial Count 레지스터를 사용합니다.
+//! TSC 주파수는 `time::tsc`에서 보정된 값을 사용하고,
+//! LAPIC 타이머 주파수는 부팅 시 PIT 채널 2를 기준으로 보정합니다.
+
+use core::arch::x86_64::__cpuid;
+use core::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use x86_64::registers::model_specific::Msr;
+
+use crate::smp::apic::{self, local_apic_reg as reg};
+use crate::time::tsc;
+
+/// LAPIC 타이머 인터럽트 벡터
+pub const LAPIC_TIMER_VECTOR: u8 = 0xEC;
+
+/// IA32_TSC_DEADLINE MSR
+const IA32_TSC_DEADLINE: u32 = 0x6E0;
+
+/// LVT 마스크 비트
+const LVT_MASKED: u32 = 1 << 16;
+/// LVT 타이머 모드: one-shot (bits 18:17 = 00)
+const LVT_MODE_ONESHOT: u32 = 0b00 << 17;
+/// LVT 타이머 모드: TSC-deadline (bits 18:17 = 10)
+const LVT_MODE_TSC_DEADLINE: u32 = 0b10 << 17;
+/// 분주비 16 (Divide Configuration Register 값)
+const TIMER_DIV_16: u32 = 0b0011;
+/// 보정 구간 길이 (ms)
+const CALIBRATION_MS: u32 = 10;
+/// 한 번에 프로그래밍할 수 있는 최대 지연 (ns, 약 1000초)
+const MAX_DELTA_NS: u64 = 1_000_000_000_000;
+
+/// LAPIC 타이머 동작 모드
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LapicTimerMode {
+    /// Initial Count 기반 one-shot
+    OneShot,
+    /// IA32_TSC_DEADLINE 기반 절대 시점 만료
+    TscDeadline,
+}
+
+/// TSC-deadline 모드 사용 여부
+static USE_TSC_DEADLINE: AtomicBool = AtomicBool::new(false);
+/// 밀리초당 LAPIC 타이머 카운트 (분주비 16 기준)
+static LAPIC_TICKS_PER_MS: AtomicU64 = AtomicU64::new(0);
+/// 초기화 완료 여부
+static INITIALIZED: AtomicBool = AtomicBool::new(false);
+
+/// CPU가 Local APIC을 가지고 있는지 확인 (CPUID.1:EDX[9])
+pub fn lapic_supported() -> bool {
+    unsafe { __cpuid(1).edx & (1 << 9) != 0 }
+}
+
+/// CPU가 TSC-deadline 타이머 모드를 지원하는지 확인 (CPUID.1:ECX[24])
+pub fn tsc_deadline_supported() -> bool {
+    unsafe { __cpuid(1).ecx & (1 << 24) != 0 }
+}
+
+/// LAPIC 타이머 초기화 여부
+pub fn is_initialized() -> bool {
+    INITIALIZED.load(Ordering::Acquire)
+}
+
+/// 현재 타이머 모드
+pub fn mode() -> Option<LapicTimerMode> {
+    if !is_initialized() {
+        return None;
+    }
+    if USE_TSC_DEADLINE.load(Ordering::Acquire) {
+        Some(LapicTimerMode::TscDeadline)
+    } else {
+        Some(LapicTimerMode::OneShot)
+    }
+}
+
+/// LAPIC 타이머 초기화
+///
+/// Local APIC을 소프트웨어 활성화하고, TSC/LAPIC 타이머 주파수를 보정한 뒤
+/// LVT 타이머를 one-shot(또는 TSC-deadline) 모드로 설정합니다.
+/// 타이머는 `arm_oneshot_ns`가 호출되기 전까지 만료되지 않습니다.
+///
+/// 보정은 처음 한 번만 수행하며, 이후 호출(S3 재개 등 LAPIC 상태가 사라진 경우)은
+/// 보정값을 재사용해 레지스터만 다시 설정합니다.
+///
+/// # Safety
+/// 메모리 관리가 초기화된 후 BSP에서 호출되어야 합니다.
+/// 첫 호출은 보정 중 PIT 채널 2를 사용합니다.
+pub unsafe fn init() -> Result<LapicTimerMode, &'static str> {
+    if !lapic_supported() {
+        return Err("Local APIC not present");
+    }
+
+    // 1. Local APIC 매핑 및 소프트웨어 활성화
+    apic::init_local_apic()?;
+
+    // 2. TSC 주파수 (클럭소스 초기화 시 보정됨)
+    let tsc_khz = tsc::calibrate()?;
+
+    // 3. LAPIC 타이머 주파수 보정 (마스크된 one-shot, 최대 카운트에서 감소량 측정)
+    let first = LAPIC_TICKS_PER_MS.load(Ordering::Acquire) == 0;
+    if first {
+        apic::write_local_apic_reg(reg::TIMER_DIV, TIMER_DIV_16);
+        apic::write_local_apic_reg(reg::LVT_TIMER, LVT_MASKED | LVT_MODE_ONESHOT | LAPIC_TIMER_VECTOR as u32);
+        apic::write_local_apic_reg(reg::TIMER_INIT, u32::MAX);
+        let lapic_ticks = crate::drivers::timer::calibrate_against_pit(CALIBRATION_MS, || {
+            (u32::MAX - apic::read_local_apic_reg(reg::TIMER_CURRENT)) as u64
+        });
+        apic::write_local_apic_reg(reg::TIMER_INIT, 0);
+        LAPIC_TICKS_PER_MS.store(lapic_ticks / CALIBRATION_MS as u64, Ordering::Release);
+    }
+
+    // 4. LVT 타이머 모드 설정 (마스크 해제)
+    let mode = setup_lvt()?;
+    INITIALIZED.store(true, Ordering::Release);
+
+    if first {
+        crate::log_info!(
+            "LAPIC timer initialized: mode={:?}, TSC {} kHz{}, LAPIC {} ticks/ms",
+            mode,
+            tsc_khz,
+            if tsc::invariant() { " (invariant)" } else { "" },
+            LAPIC_TICKS_PER_MS.load(Ordering::Acquire)
+        );
+    }
+
+    Ok(mode)
+}
+
+/// AP의 LAPIC 타이머 설정
+///
+/// BSP에서 `init`으로 구한 보정값을 공유하며, 현재 CPU의 LVT 타이머만 설정합니다.
+///
+/// # Safety
+/// AP에서 `smp::apic::init_local_apic` 이후 호출되어야 합니다.
+pub unsafe fn init_ap() -> Result<LapicTimerMode, &'static str> {
+    if !is_initialized() {
+        return Err("LAPIC timer not initialized on the BSP");
+    }
+    setup_lvt()
+}
+
+/// 현재 CPU의 LVT 타이머를 one-shot 또는 TSC-deadline 모드로 설정
+unsafe fn setup_lvt() -> Result<LapicTimerMode, &'static str> {
+    apic::write_local_apic_reg(reg::TIMER_DIV, TIMER_DIV_16);
+    if tsc_deadline_supported() {
+        apic::write_local_apic_reg(reg::LVT_TIMER, LVT_MODE_TSC_DEADLINE | LAPIC_TIMER_VECTOR as u32);
+        // SDM: LVT 쓰기와 IA32_TSC_DEADLINE 쓰기 사이의 순서 보장
+        core::arch::asm!("mfence", options(nostack, preserves_flags));
+        USE_TSC_DEADLINE.store(true, Ordering::Release);
+        Ok(LapicTimerMode::TscDeadline)
+    } else {
+        if LAPIC_TICKS_PER_MS.load(Ordering::Acquire) == 0 {
+            return Err("LAPIC timer calibration failed");
+        }
+        apic::write_local_apic_reg(reg::LVT_TIMER, LVT_MODE_ONESHOT | LAPIC_TIMER_VECTOR as u32);
+        USE_TSC_DEADLINE.store(false, Ordering::Release);
+        Ok(LapicTimerMode::OneShot)
+    }
+}
+
+/// 지금으로부터 `delta_ns` 후에 타이머 인터럽트가 한 번 발생하도록 프로그래밍
+///
+/// 이전에 설정된 만료 시점은 덮어씁니다.
+pub fn arm_oneshot_ns(delta_ns: u64) {
+    if !is_initialized() {
+        return;
+    }
+    let delta_ns = delta_ns.min(MAX_DELTA_NS);
+
+    unsafe {
+        if USE_TSC_DEADLINE.load(Ordering::Acquire) {
+            let cycles = (delta_ns as u128 * tsc::khz() as u128 / 1_000_000) as u64;
+            let deadline = tsc::rdtsc().wrapping_add(cycles.max(1));
+            Msr::new(IA32_TSC_DEADLINE).write(deadline);
+        } else {
+            let ticks_per_ms = LAPIC_TICKS_PER_MS.load(Ordering::Acquire);
+            let count = (delta_ns as u128 * ticks_per_ms as u128 / 1_000_000) as u64;
+            apic::write_local_apic_reg(reg::TIMER_INIT, count.clamp(1, u32::MAX as u64) as u32);
+        }
+    }
+}
+
+/// 프로그래밍된 만료 시점 취소
+pub fn cancel() {
+    if !is_initialized() {
+        return;
+    }
+    unsafe {
+        if USE_TSC_DEADLINE.load(Ordering::Acquire) {
+            Msr::new(IA32_TSC_DEADLINE).write(0);
+        } else {
+            apic::write_local_apic_reg(reg::TIMER_INIT, 0);
+        }
+    }
+}
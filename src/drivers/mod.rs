@@ -2,13 +2,19 @@
 //!
 //! 이 모듈은 다양한 하드웨어 장치의 드라이버를 포함합니다.
 
+pub mod base;
 pub mod serial;
 pub mod timer;
+pub mod lapic_timer;
+pub mod hpet;
+pub mod rtc;
 pub mod keyboard;
+pub mod input;
 pub mod vga;
 #[cfg(feature = "fs")]
 pub mod ata;
 pub mod pci;
+pub mod pci_msi;
 pub mod rtl8139;
 pub mod framebuffer;
 pub mod mouse;
@@ -5,6 +5,9 @@
 use spin::Mutex;
 use x86_64::instructions::port::Port;
 
+use crate::drivers::base::{platform, Device, DeviceMatch, Driver};
+use crate::interrupts::irq;
+
 /// 마우스 데이터 포트
 const MOUSE_DATA_PORT: u16 = 0x60;
 /// 마우스 명령/상태 포트
@@ -59,6 +62,12 @@ impl MouseState {
             let right_button = (flags & 0x02) != 0;
             let middle_button = (flags & 0x04) != 0;
 
+            report_packet(dx, dy, [
+                (left_button, self.left_button),
+                (right_button, self.right_button),
+                (middle_button, self.middle_button),
+            ]);
+
             // 위치 업데이트 (Y축 반전)
             self.x += dx as isize;
             self.y -= dy as isize; // Y축은 화면 좌표계와 반대
@@ -112,6 +121,35 @@ impl MouseState {
     }
 }
 
+/// 패킷 하나를 입력 이벤트 스트림에 보고 (상대 이동과 바뀐 버튼)
+///
+/// # Arguments
+/// * `buttons` - 왼쪽/오른쪽/가운데 버튼의 (새 상태, 이전 상태)
+fn report_packet(dx: i16, dy: i16, buttons: [(bool, bool); 3]) {
+    use crate::drivers::input::{self, InputDevice, BTN_LEFT, BTN_MIDDLE, BTN_RIGHT, EV_KEY, EV_REL, REL_X, REL_Y};
+
+    let mut events = [(0u16, 0u16, 0i32); 5];
+    let mut count = 0;
+    if dx != 0 {
+        events[count] = (EV_REL, REL_X, dx as i32);
+        count += 1;
+    }
+    if dy != 0 {
+        // PS/2는 위쪽이 양수
+        events[count] = (EV_REL, REL_Y, -(dy as i32));
+        count += 1;
+    }
+    for ((pressed, was_pressed), code) in buttons.into_iter().zip([BTN_LEFT, BTN_RIGHT, BTN_MIDDLE]) {
+        if pressed != was_pressed {
+            events[count] = (EV_KEY, code, pressed as i32);
+            count += 1;
+        }
+    }
+    if count > 0 {
+        input::report(InputDevice::Mouse, &events[..count]);
+    }
+}
+
 /// 마우스 이벤트
 #[derive(Debug, Clone, Copy)]
 pub enum MouseEvent {
@@ -190,6 +228,53 @@ pub unsafe fn init() {
     read_mouse();
 }
 
+/// PS/2 마우스 ISA IRQ 번호
+pub const MOUSE_IRQ: u8 = 12;
+
+/// IRQ 핸들러 (`interrupts::irq::request_irq`로 등록)
+pub fn irq_handler(gsi: u32) {
+    handle_interrupt();
+
+    // 사용자 활동 기록
+    crate::power::user_activity::record_activity(crate::power::user_activity::ActivityType::Mouse);
+    crate::task::event::signal_irq(gsi);
+}
+
+/// i8042 보조 포트 마우스 드라이버 (디바이스 모델)
+struct MouseDriver;
+
+/// 디바이스 모델에 등록할 마우스 드라이버
+pub static DRIVER: &dyn Driver = &MouseDriver;
+
+impl Driver for MouseDriver {
+    fn name(&self) -> &'static str {
+        "i8042-mouse"
+    }
+
+    fn match_table(&self) -> &'static [DeviceMatch] {
+        &[DeviceMatch::Acpi(platform::HID_PS2_MOUSE)]
+    }
+
+    fn probe(&self, _device: &Device) -> Result<(), &'static str> {
+        unsafe { init(); }
+        irq::request_irq(irq::isa_irq_to_gsi(MOUSE_IRQ), irq_handler).map(|_| ())
+    }
+
+    fn remove(&self, _device: &Device) {
+        irq::free_irq(irq::isa_irq_to_gsi(MOUSE_IRQ), irq_handler);
+    }
+
+    fn suspend(&self, _device: &Device) -> Result<(), &'static str> {
+        irq::disable_irq(irq::isa_irq_to_gsi(MOUSE_IRQ));
+        Ok(())
+    }
+
+    fn resume(&self, _device: &Device) -> Result<(), &'static str> {
+        irq::enable_irq(irq::isa_irq_to_gsi(MOUSE_IRQ));
+        Ok(())
+    }
+}
+
 /// 마우스 인터럽트 핸들러
 pub fn handle_interrupt() {
     let mut data_port = Port::<u8>::new(MOUSE_DATA_PORT);
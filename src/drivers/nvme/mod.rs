@@ -1,11 +1,16 @@
 //! NVMe (NVM Express) storage driver - skeleton
 //!
 //! Detect controller, map MMIO, and prepare Admin queue structures.
-//! IO queue and PRP-based read/write to be implemented incrementally.
-
-use crate::drivers::pci::PciDevice;
-use crate::drivers::pci;
-use core::ptr::{read_volatile, write_volatile};
+//! One I/O queue pair carries read/write/flush. Each queue's completions are
+//! signalled on its own MSI-X vector when available (polled otherwise).
+
+use crate::drivers::pci::{PciDevice, PCI_COMMAND_BUS_MASTER, PCI_COMMAND_MEMORY};
+use crate::drivers::base::{self, Device, DeviceInfo, DeviceMatch, Driver};
+use crate::drivers::pci_msi::{self, MsiVectors};
+use crate::memory::paging::phys_to_virt;
+use core::ptr::{addr_of, addr_of_mut, read_volatile, write_volatile};
+use core::sync::atomic::{AtomicU64, Ordering};
+use x86_64::instructions::interrupts;
 use x86_64::{PhysAddr, VirtAddr};
 
 #[derive(Debug)]
@@ -36,6 +41,8 @@ const CC_IOSQES_SHIFT: u32 = 16; // IO Submission Queue Entry Size (2^(SQES))
 const CSTS_RDY: u32 = 1 << 0;
 
 // Admin opcodes
+const OPC_CREATE_IO_SQ: u8 = 0x01;
+const OPC_CREATE_IO_CQ: u8 = 0x05;
 const OPC_IDENTIFY: u8 = 0x06;
 
 // NVM opcodes
@@ -43,6 +50,7 @@ const OPC_READ: u8 = 0x02;
 const OPC_WRITE: u8 = 0x01;
 const OPC_FLUSH: u8 = 0x00;
 
+#[derive(Clone, Copy)]
 #[repr(C, packed)]
 struct SqEntry {
     opc: u8,
@@ -61,6 +69,29 @@ struct SqEntry {
     cdw15: u32,
 }
 
+impl SqEntry {
+    /// Admin command with a single PRP (remaining dwords zero)
+    fn admin(opc: u8, cid: u16, prp1: PhysAddr) -> Self {
+        Self {
+            opc,
+            fuse_psdt: 0,
+            cid,
+            nsid: 0,
+            rsvd2: 0,
+            mptr: 0,
+            prp1: prp1.as_u64(),
+            prp2: 0,
+            cdw10: 0,
+            cdw11: 0,
+            cdw12: 0,
+            cdw13: 0,
+            cdw14: 0,
+            cdw15: 0,
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
 #[repr(C, packed)]
 struct CqEntry {
     dw0: u32,
@@ -71,19 +102,71 @@ struct CqEntry {
     status_p: u16, // bit15:14 phase/status; bit0 phase tag
 }
 
+/// Vector index used by the admin queue and by the first I/O queue
+const ADMIN_QUEUE_VECTOR: usize = 0;
+const IO_QUEUE_VECTOR: usize = 1;
+const ADMIN_QUEUE_ENTRIES: u16 = 32; // 32 * 64B SQ, 32 * 16B CQ
+const IO_QUEUE_ENTRIES: u16 = 64;    // 64 * 64B = one 4KiB page
+const IO_QUEUE_ID: u16 = 1;
+
+/// Command timeout when waiting for a completion interrupt
+const COMMAND_TIMEOUT_MS: u64 = 5000;
+
+/// Completion interrupts seen per vector (wake-up hint for waiters)
+static QUEUE_IRQS: [AtomicU64; 2] = [AtomicU64::new(0), AtomicU64::new(0)];
+
+fn admin_queue_irq(_vector: u32) {
+    QUEUE_IRQS[ADMIN_QUEUE_VECTOR].fetch_add(1, Ordering::Release);
+}
+
+fn io_queue_irq(_vector: u32) {
+    QUEUE_IRQS[IO_QUEUE_VECTOR].fetch_add(1, Ordering::Release);
+}
+
+/// Submission/completion queue pair
+struct QueuePair {
+    id: u16,
+    sq: PhysAddr,
+    cq: PhysAddr,
+    entries: u16,
+    sq_tail: u16,
+    cq_head: u16,
+    cq_phase: u16,
+    /// Index into `QUEUE_IRQS` when the CQ raises an MSI/MSI-X interrupt (None = polled)
+    vector: Option<usize>,
+}
+
+impl QueuePair {
+    unsafe fn allocate(id: u16, entries: u16) -> Result<Self, NvmeError> {
+        let sq = crate::memory::allocate_frame().ok_or(NvmeError::InitFailed)?;
+        let cq = crate::memory::allocate_frame().ok_or(NvmeError::InitFailed)?;
+        // The CQ phase tag starts at 1, so a stale page must read as "not posted"
+        let cq_va = phys_to_virt(cq.start_address()).ok_or(NvmeError::InitFailed)?;
+        core::ptr::write_bytes(cq_va.as_mut_ptr::<u8>(), 0, 4096);
+        Ok(Self {
+            id,
+            sq: sq.start_address(),
+            cq: cq.start_address(),
+            entries,
+            sq_tail: 0,
+            cq_head: 0,
+            cq_phase: 1,
+            vector: None,
+        })
+    }
+}
+
 pub struct NvmeController {
     pci: PciDevice,
     bar0_phys: PhysAddr,
     mmio_base: Option<VirtAddr>,
     initialized: bool,
-    // Admin queues
-    asq: Option<PhysAddr>,
-    acq: Option<PhysAddr>,
-    asq_entries: u16,
-    acq_entries: u16,
-    asq_tail: u16,
-    acq_head: u16,
-    acq_phase: u16,
+    /// Doorbell stride in bytes (4 << CAP.DSTRD)
+    doorbell_stride: usize,
+    admin: Option<QueuePair>,
+    io: Option<QueuePair>,
+    /// MSI-X (or single MSI) vectors; None = completions are polled
+    msi: Option<MsiVectors>,
     // Namespace info (minimal)
     nsid_default: u32,
     ns_total_blocks: u64,
@@ -93,18 +176,16 @@ impl NvmeController {
     pub fn new(pci: PciDevice) -> Result<Self, NvmeError> {
         let bar0 = pci.bar0;
         if (bar0 & 0x01) != 0 { return Err(NvmeError::InitFailed); }
+        let bar0_phys = unsafe { pci.memory_bar(0) }.unwrap_or((bar0 & !0xF) as u64);
         Ok(Self {
             pci,
-            bar0_phys: PhysAddr::new((bar0 & !0xF) as u64),
+            bar0_phys: PhysAddr::new(bar0_phys),
             mmio_base: None,
             initialized: false,
-            asq: None,
-            acq: None,
-            asq_entries: 0,
-            acq_entries: 0,
-            asq_tail: 0,
-            acq_head: 0,
-            acq_phase: 1,
+            doorbell_stride: 4,
+            admin: None,
+            io: None,
+            msi: None,
             nsid_default: 1,
             ns_total_blocks: 0,
         })
@@ -112,8 +193,7 @@ impl NvmeController {
 
     unsafe fn map_mmio(&mut self) -> Result<VirtAddr, NvmeError> {
         if let Some(v) = self.mmio_base { return Ok(v); }
-        let off = crate::memory::paging::get_physical_memory_offset(crate::boot::get_boot_info());
-        let v = off + self.bar0_phys.as_u64();
+        let v = phys_to_virt(self.bar0_phys).ok_or(NvmeError::InitFailed)?;
         self.mmio_base = Some(v);
         Ok(v)
     }
@@ -130,13 +210,16 @@ impl NvmeController {
         Ok(())
     }
 
-    /// Initialize Admin queue (skeleton only)
+    /// SQ tail (completion = false) or CQ head (completion = true) doorbell offset
+    fn doorbell(&self, qid: u16, completion: bool) -> usize {
+        NVME_REG_DBS + (2 * qid as usize + completion as usize) * self.doorbell_stride
+    }
+
+    /// Reset the controller, set up the admin queue, MSI-X vectors and one I/O queue pair
     pub unsafe fn init(&mut self) -> Result<(), NvmeError> {
         // Enable bus master & memory space
-        let cmd = self.pci.read_config_register(0x04);
-        self.pci.write_config_register(0x04, cmd | 0x04 | 0x02);
+        self.pci.update_command(PCI_COMMAND_BUS_MASTER | PCI_COMMAND_MEMORY, 0);
 
-        let cap_lo = self.read_u32(NVME_REG_CAP)?;
         let vs = self.read_u32(NVME_REG_VS)?;
         crate::log_info!("NVMe: MMIO={:#016X}, VS=0x{:08X}", self.bar0_phys.as_u64(), vs);
 
@@ -144,6 +227,7 @@ impl NvmeController {
         let cap_hi = self.read_u32(NVME_REG_CAP + 4)?;
         let mpsmin = ((cap_hi >> 16) & 0x0F) as u32; // CAP.MPSMIN
         let page_shift = 12 + mpsmin; // choose minimum supported
+        self.doorbell_stride = 4 << (cap_hi & 0x0F); // CAP.DSTRD (bits 35:32)
 
         // Disable controller if enabled
         let mut cc = self.read_u32(NVME_REG_CC)?;
@@ -156,26 +240,19 @@ impl NvmeController {
         }
 
         // Allocate Admin SQ/CQ (one page each)
-        let asq_frame = crate::memory::allocate_frame().ok_or(NvmeError::InitFailed)?;
-        let acq_frame = crate::memory::allocate_frame().ok_or(NvmeError::InitFailed)?;
-        self.asq = Some(asq_frame.start_address());
-        self.acq = Some(acq_frame.start_address());
-        self.asq_entries = 32; // 32 entries * 64B = 2KiB (fits in 4KiB page)
-        self.acq_entries = 32; // 32 entries * 16B = 512B
-        self.asq_tail = 0;
-        self.acq_head = 0;
-        self.acq_phase = 1;
+        let admin = QueuePair::allocate(0, ADMIN_QUEUE_ENTRIES)?;
 
         // Program AQA
-        let aqa = ((self.acq_entries as u32 - 1) & 0xFFF) | ((((self.asq_entries as u32 - 1) & 0xFFF) << 16));
+        let aqa = ((admin.entries as u32 - 1) & 0xFFF) | (((admin.entries as u32 - 1) & 0xFFF) << 16);
         self.write_u32(NVME_REG_AQA, aqa)?;
         // Program ASQ/ACQ (64-bit)
-        let asq = self.asq.unwrap().as_u64();
-        let acq = self.acq.unwrap().as_u64();
+        let asq = admin.sq.as_u64();
+        let acq = admin.cq.as_u64();
         self.write_u32(NVME_REG_ASQ, (asq & 0xFFFF_FFFF) as u32)?;
         self.write_u32(NVME_REG_ASQ + 4, (asq >> 32) as u32)?;
         self.write_u32(NVME_REG_ACQ, (acq & 0xFFFF_FFFF) as u32)?;
         self.write_u32(NVME_REG_ACQ + 4, (acq >> 32) as u32)?;
+        self.admin = Some(admin);
 
         // Configure CC (enable, page size, entry sizes 64B/16B)
         let iosqes = 6; // 2^6 = 64 bytes
@@ -191,41 +268,123 @@ impl NvmeController {
         let mut t = 100000;
         while (self.read_u32(NVME_REG_CSTS)? & CSTS_RDY) == 0 && t > 0 { t -= 1; core::hint::spin_loop(); }
         if t == 0 { return Err(NvmeError::InitFailed); }
-
         self.initialized = true;
+
+        // Completion interrupts: admin CQ on entry 0, I/O CQ on entry 1.
+        // The admin CQ always uses interrupt vector 0, which is enabled by default.
+        match pci_msi::enable_vectors(&self.pci, &[admin_queue_irq, io_queue_irq]) {
+            Ok(msi) => {
+                if let Some(admin) = self.admin.as_mut() { admin.vector = Some(ADMIN_QUEUE_VECTOR); }
+                self.msi = Some(msi);
+            }
+            Err(e) => crate::log_info!("NVMe: MSI/MSI-X unavailable ({}), polling completions", e),
+        }
+
+        if let Err(_e) = self.create_io_queue() {
+            crate::log_warn!("NVMe: I/O queue creation failed, using admin queue for I/O");
+        }
+        Ok(())
+    }
+
+    /// Create I/O completion + submission queue 1
+    unsafe fn create_io_queue(&mut self) -> Result<(), NvmeError> {
+        let mut io = QueuePair::allocate(IO_QUEUE_ID, IO_QUEUE_ENTRIES)?;
+        // With a single MSI vector both queues share interrupt vector 0
+        io.vector = self.msi.as_ref().map(|m| IO_QUEUE_VECTOR.min(m.vectors.len() - 1));
+        let qsize = (io.entries as u32 - 1) << 16;
+
+        // Create I/O CQ: CDW11 = IV << 16 | IEN | PC
+        let cq_flags = match io.vector {
+            Some(iv) => ((iv as u32) << 16) | (1 << 1) | 1,
+            None => 1,
+        };
+        let mut sqe = SqEntry::admin(OPC_CREATE_IO_CQ, 6, io.cq);
+        sqe.cdw10 = qsize | io.id as u32;
+        sqe.cdw11 = cq_flags;
+        check_status(&self.admin_submit_and_wait(&sqe)?)?;
+
+        // Create I/O SQ bound to that CQ: CDW11 = CQID << 16 | PC
+        let mut sqe = SqEntry::admin(OPC_CREATE_IO_SQ, 7, io.sq);
+        sqe.cdw10 = qsize | io.id as u32;
+        sqe.cdw11 = ((io.id as u32) << 16) | 1;
+        check_status(&self.admin_submit_and_wait(&sqe)?)?;
+
+        crate::log_info!("NVMe: I/O queue {} ready ({} entries, {})", io.id, io.entries,
+                         if io.vector.is_some() { "interrupt driven" } else { "polled" });
+        self.io = Some(io);
         Ok(())
     }
 
-    /// Submit an admin command and wait for completion (polled)
+    /// Submit an admin command and wait for completion
     unsafe fn admin_submit_and_wait(&mut self, sqe: &SqEntry) -> Result<CqEntry, NvmeError> {
-        let phys_off = crate::memory::paging::get_physical_memory_offset(crate::boot::get_boot_info());
-        // ASQ virtual mapping
-        let asq_va = (phys_off + self.asq.unwrap().as_u64()).as_mut_ptr::<SqEntry>();
-        let acq_va = (phys_off + self.acq.unwrap().as_u64()).as_mut_ptr::<CqEntry>();
-        // Write SQ entry at tail
-        let tail = self.asq_tail as usize % self.asq_entries as usize;
-        core::ptr::write_volatile(asq_va.add(tail), *sqe);
-        // Ring SQ tail doorbell (admin qid=0)
-        self.write_u32(NVME_REG_DBS + (0 * 2 * 4), (self.asq_tail as u32).wrapping_add(1))?;
-        self.asq_tail = self.asq_tail.wrapping_add(1);
-
-        // Poll completion
-        let mut timeout = 1000000;
+        let mut admin = self.admin.take().ok_or(NvmeError::InitFailed)?;
+        let result = self.submit_and_wait(&mut admin, sqe);
+        self.admin = Some(admin);
+        result
+    }
+
+    /// Submit an NVM command on the I/O queue (admin queue if it was not created)
+    unsafe fn io_submit_and_wait(&mut self, sqe: &SqEntry) -> Result<CqEntry, NvmeError> {
+        match self.io.take() {
+            Some(mut io) => {
+                let result = self.submit_and_wait(&mut io, sqe);
+                self.io = Some(io);
+                result
+            }
+            None => self.admin_submit_and_wait(sqe),
+        }
+    }
+
+    /// Write the command at the SQ tail, ring the doorbell and wait for its completion
+    ///
+    /// Interrupt-driven queues halt until their vector fires (bounded by
+    /// `COMMAND_TIMEOUT_MS`); other queues spin on the CQ phase tag.
+    unsafe fn submit_and_wait(&mut self, queue: &mut QueuePair, sqe: &SqEntry) -> Result<CqEntry, NvmeError> {
+        let sq_va = phys_to_virt(queue.sq).ok_or(NvmeError::IoError)?.as_mut_ptr::<SqEntry>();
+        let cq_va = phys_to_virt(queue.cq).ok_or(NvmeError::IoError)?.as_mut_ptr::<CqEntry>();
+
+        core::ptr::write_volatile(sq_va.add(queue.sq_tail as usize), *sqe);
+        queue.sq_tail = (queue.sq_tail + 1) % queue.entries;
+        let sq_doorbell = self.doorbell(queue.id, false);
+        self.write_u32(sq_doorbell, queue.sq_tail as u32)?;
+
+        let wait_irq = queue.vector.filter(|_| interrupts::are_enabled());
+        let deadline = crate::drivers::timer::get_milliseconds() + COMMAND_TIMEOUT_MS;
+        let mut spins = 1000000;
         loop {
-            let head = self.acq_head as usize % self.acq_entries as usize;
-            let cqe = core::ptr::read_volatile(acq_va.add(head));
-            let phase = (cqe.status_p & 1) as u16;
-            if phase == self.acq_phase {
+            let seen = wait_irq.map(|v| QUEUE_IRQS[v].load(Ordering::Acquire));
+            let cqe = core::ptr::read_volatile(cq_va.add(queue.cq_head as usize));
+            if cqe.status_p & 1 == queue.cq_phase {
                 // advance head and toggle phase when wrap
-                self.acq_head = self.acq_head.wrapping_add(1);
-                if self.acq_head as usize % self.acq_entries as usize == 0 { self.acq_phase ^= 1; }
-                // Ring CQ head doorbell
-                self.write_u32(NVME_REG_DBS + ((0 * 2 + 1) * 4), self.acq_head as u32)?;
+                queue.cq_head = (queue.cq_head + 1) % queue.entries;
+                if queue.cq_head == 0 { queue.cq_phase ^= 1; }
+                let cq_doorbell = self.doorbell(queue.id, true);
+                self.write_u32(cq_doorbell, queue.cq_head as u32)?;
                 return Ok(cqe);
             }
-            timeout -= 1;
-            if timeout == 0 { return Err(NvmeError::IoError); }
-            core::hint::spin_loop();
+
+            match (wait_irq, seen) {
+                (Some(v), Some(seen)) => {
+                    // Check the deadline, the vector and the CQ phase with interrupts off so a
+                    // completion landing between the check and `hlt` still wakes us
+                    interrupts::disable();
+                    if crate::drivers::timer::get_milliseconds() >= deadline {
+                        interrupts::enable();
+                        return Err(NvmeError::IoError);
+                    }
+                    let posted = core::ptr::read_volatile(cq_va.add(queue.cq_head as usize)).status_p & 1 == queue.cq_phase;
+                    if QUEUE_IRQS[v].load(Ordering::Acquire) == seen && !posted {
+                        interrupts::enable_and_hlt();
+                    } else {
+                        interrupts::enable();
+                    }
+                }
+                _ => {
+                    spins -= 1;
+                    if spins == 0 { return Err(NvmeError::IoError); }
+                    core::hint::spin_loop();
+                }
+            }
         }
     }
 
@@ -302,7 +461,7 @@ impl NvmeController {
             cdw14: 0,
             cdw15: 0,
         };
-        let cqe = self.admin_submit_and_wait(&sqe)?;
+        let cqe = self.io_submit_and_wait(&sqe)?;
         let status = (cqe.status_p >> 1) & 0x7FFF;
         if status != 0 { return Err(NvmeError::IoError); }
         Ok(())
@@ -326,7 +485,7 @@ impl NvmeController {
             cdw14: 0,
             cdw15: 0,
         };
-        let cqe = self.admin_submit_and_wait(&sqe)?;
+        let cqe = self.io_submit_and_wait(&sqe)?;
         let status = (cqe.status_p >> 1) & 0x7FFF;
         if status != 0 { return Err(NvmeError::IoError); }
         Ok(())
@@ -350,62 +509,104 @@ impl NvmeController {
             cdw14: 0,
             cdw15: 0,
         };
-        let cqe = self.admin_submit_and_wait(&sqe)?;
+        let cqe = self.io_submit_and_wait(&sqe)?;
         let status = (cqe.status_p >> 1) & 0x7FFF;
         if status != 0 { return Err(NvmeError::IoError); }
         Ok(())
     }
 }
 
+/// Map a completion entry's status field to a result
+fn check_status(cqe: &CqEntry) -> Result<(), NvmeError> {
+    let status = (cqe.status_p >> 1) & 0x7FFF;
+    if status != 0 { return Err(NvmeError::IoError); }
+    Ok(())
+}
+
 static mut NVME: Option<NvmeController> = None;
 
-/// Detect and initialize the first NVMe controller (if present)
-pub unsafe fn init() -> Result<(), NvmeError> {
-    if let Some(pci_dev) = find_nvme_controller() {
-        let mut ctrl = NvmeController::new(pci_dev)?;
-        ctrl.init()?;
-        // Try Identify Controller to verify path
-        let frame = crate::memory::allocate_frame().ok_or(NvmeError::InitFailed)?;
-        let phys = frame.start_address();
-        if let Err(_e) = ctrl.identify_controller(phys) {
-            crate::log_warn!("NVMe: Identify controller failed (continuing)");
+/// Bring up a controller bound by the device model and make it the active one
+unsafe fn probe_controller(pci_dev: PciDevice) -> Result<(), NvmeError> {
+    crate::log_info!("Found NVMe controller {:04X}:{:04X}", pci_dev.vendor_id, pci_dev.device_id);
+    let mut ctrl = NvmeController::new(pci_dev)?;
+    ctrl.init()?;
+    // Try Identify Controller to verify path
+    let frame = crate::memory::allocate_frame().ok_or(NvmeError::InitFailed)?;
+    let phys = frame.start_address();
+    if let Err(_e) = ctrl.identify_controller(phys) {
+        crate::log_warn!("NVMe: Identify controller failed (continuing)");
+    }
+    // Identify namespace 1 to get total blocks
+    let ns_frame = crate::memory::allocate_frame().ok_or(NvmeError::InitFailed)?;
+    let ns_phys = ns_frame.start_address();
+    match ctrl.identify_namespace(1, ns_phys) {
+        Ok(nsze) => {
+            ctrl.nsid_default = 1;
+            ctrl.ns_total_blocks = nsze;
+            crate::log_info!("NVMe: NSID=1 total LBAs {}", nsze);
+        }
+        Err(_) => {
+            crate::log_warn!("NVMe: Identify namespace failed; defaulting to unknown size");
         }
-        // Identify namespace 1 to get total blocks
-        let ns_frame = crate::memory::allocate_frame().ok_or(NvmeError::InitFailed)?;
-        let ns_phys = ns_frame.start_address();
-        match ctrl.identify_namespace(1, ns_phys) {
-            Ok(nsze) => {
-                ctrl.nsid_default = 1;
-                ctrl.ns_total_blocks = nsze;
-                crate::log_info!("NVMe: NSID=1 total LBAs {}", nsze);
+    }
+    NVME = Some(ctrl);
+    Ok(())
+}
+
+/// NVMe PCI driver for the device model.
+/// Only the first controller that probes successfully is used.
+struct NvmeDriver;
+
+impl Driver for NvmeDriver {
+    fn name(&self) -> &'static str {
+        "nvme"
+    }
+
+    fn match_table(&self) -> &'static [DeviceMatch] {
+        // Class 0x01 = Mass Storage, Subclass 0x08 = NVM, ProgIF 0x02 = NVMe
+        &[DeviceMatch::PciClass { class: 0x01, subclass: 0x08, prog_if: Some(0x02) }]
+    }
+
+    fn probe(&self, device: &Device) -> Result<(), &'static str> {
+        let DeviceInfo::Pci(pci_dev) = device.info else {
+            return Err("not a PCI device");
+        };
+        unsafe {
+            if (*addr_of!(NVME)).is_some() {
+                return Err("an NVMe controller is already active");
             }
-            Err(_) => {
-                crate::log_warn!("NVMe: Identify namespace failed; defaulting to unknown size");
+            probe_controller(pci_dev).map_err(|_| "NVMe controller init failed")
+        }
+    }
+
+    /// Flush the volatile write cache before power goes away
+    fn suspend(&self, _device: &Device) -> Result<(), &'static str> {
+        unsafe {
+            match (*addr_of_mut!(NVME)).as_mut() {
+                Some(ctrl) => ctrl.flush_nsid(ctrl.nsid_default).map_err(|_| "NVMe flush failed"),
+                None => Ok(()),
             }
         }
-        NVME = Some(ctrl);
-        Ok(())
-    } else {
-        Err(NvmeError::DeviceNotFound)
     }
 }
 
-unsafe fn find_nvme_controller() -> Option<PciDevice> {
-    let mut found: Option<PciDevice> = None;
-    pci::scan_pci_bus(|d| {
-        // Class 0x01 = Mass Storage, Subclass 0x08 = NVM, ProgIF 0x02 = NVMe
-        if d.class_code == 0x01 && d.subclass == 0x08 && d.prog_if == 0x02 {
-            crate::log_info!("Found NVMe controller {:04X}:{:04X}", d.vendor_id, d.device_id);
-            found = Some(*d);
-            true
-        } else { false }
-    });
-    found
+/// Register the NVMe driver with the device model.
+/// Fails with `DeviceNotFound` if no controller got bound.
+pub unsafe fn init() -> Result<(), NvmeError> {
+    base::register_driver(&NvmeDriver);
+    if (*addr_of!(NVME)).is_some() { Ok(()) } else { Err(NvmeError::DeviceNotFound) }
 }
 
 // Block device adapter to existing ATA trait
 pub struct NvmeBlockDevice;
 
+impl NvmeBlockDevice {
+    /// Constructor used by the devfs node (`/dev/nvme0n1`)
+    pub fn open() -> alloc::boxed::Box<dyn crate::drivers::ata::BlockDevice> {
+        alloc::boxed::Box::new(NvmeBlockDevice)
+    }
+}
+
 impl crate::drivers::ata::BlockDevice for NvmeBlockDevice {
     fn block_size(&self) -> usize { 512 }
     fn read_block(&mut self, block: u64, buf: &mut [u8]) -> Result<usize, crate::drivers::ata::BlockDeviceError> {
@@ -1,8 +1,17 @@
 //! PCI (Peripheral Component Interconnect) 버스 관리
 //!
 //! 이 모듈은 PCI 버스를 스캔하여 디바이스를 발견하고 관리합니다.
+//!
+//! 구성 공간은 ACPI MCFG에 ECAM 영역이 있으면 메모리 매핑(4KiB, 확장 Capability 포함)으로,
+//! 없으면 0xCF8/0xCFC 포트(256바이트)로 접근합니다.
 
+use alloc::vec::Vec;
+use core::ptr::{read_volatile, write_volatile};
+use spin::Once;
 use x86_64::instructions::port::Port;
+use x86_64::{PhysAddr, VirtAddr};
+
+use crate::power::acpi_mcfg::{self, EcamRegion};
 
 /// PCI 구성 공간 포트
 const PCI_CONFIG_ADDRESS: u16 = 0xCF8;
@@ -18,8 +27,102 @@ const PCI_SUBCLASS: u8 = 0x0A;
 const PCI_PROG_IF: u8 = 0x09;
 const PCI_HEADER_TYPE: u8 = 0x0E;
 const PCI_BAR0: u8 = 0x10;
+const PCI_SECONDARY_BUS: u8 = 0x19;
+const PCI_CAPABILITY_POINTER: u8 = 0x34;
 const PCI_INTERRUPT_LINE: u8 = 0x3C;
 
+/// 커맨드 레지스터 비트
+pub const PCI_COMMAND_MEMORY: u16 = 1 << 1;
+pub const PCI_COMMAND_BUS_MASTER: u16 = 1 << 2;
+pub const PCI_COMMAND_INTX_DISABLE: u16 = 1 << 10;
+
+/// 상태 레지스터 비트: Capability List 존재
+const PCI_STATUS_CAP_LIST: u16 = 1 << 4;
+
+/// PCI Capability ID
+pub const PCI_CAP_ID_MSI: u8 = 0x05;
+pub const PCI_CAP_ID_PCIE: u8 = 0x10;
+pub const PCI_CAP_ID_MSIX: u8 = 0x11;
+
+/// PCIe 확장 Capability ID
+pub const PCI_EXT_CAP_ID_L1SS: u16 = 0x001E;
+
+/// PCIe 확장 구성 공간 크기와 확장 Capability 시작 오프셋
+pub const PCI_EXT_CONFIG_SIZE: u16 = 4096;
+const PCI_EXT_CAP_START: u16 = 0x100;
+
+/// 매핑된 ECAM 영역
+struct Ecam {
+    region: EcamRegion,
+    /// 버스 0 기준 가상 주소
+    virt_base: u64,
+}
+
+/// ECAM 영역 목록 (`init` 이후 고정, 세그먼트 0만 사용)
+static ECAM: Once<Vec<Ecam>> = Once::new();
+
+/// ECAM 영역을 캐시 불가로 매핑하는 가상 주소 창 (영역마다 256MiB)
+const ECAM_VIRT_BASE: u64 = 0x_5555_0000_0000;
+const ECAM_WINDOW_SIZE: u64 = 256 << 20;
+
+/// 2MiB 페이지 정렬 마스크
+const HUGE_PAGE_MASK: u64 = (2 << 20) - 1;
+
+/// ECAM 구성 공간 접근 설정
+///
+/// MCFG의 ECAM 영역을 캐시 불가(UC)로 매핑하고, 포트 방식으로 읽은 값과 비교해
+/// 검증합니다. 검증에 실패하거나 MCFG가 없으면 포트 방식만 사용합니다.
+/// 이어서 PCIe 포트의 보조 버스 맵을 한 번 만들어 둡니다.
+///
+/// # Safety
+/// 메모리 관리가 초기화된 후, PCI 드라이버가 초기화되기 전에 한 번 호출되어야 합니다.
+pub unsafe fn init() {
+    let regions = acpi_mcfg::load().unwrap_or(&[]);
+    let mut mapped = Vec::new();
+    for (index, region) in regions.iter().filter(|r| r.segment == 0).enumerate() {
+        let (start, len) = region.mapped_range();
+        let aligned = start & !HUGE_PAGE_MASK;
+        let window = ECAM_VIRT_BASE + index as u64 * ECAM_WINDOW_SIZE;
+        if let Err(e) = crate::memory::paging::map_mmio_uncached(
+            VirtAddr::new(window),
+            PhysAddr::new(aligned),
+            len + (start - aligned),
+        ) {
+            crate::log_warn!("PCI: failed to map ECAM at 0x{:X}: {:?}", region.base_address, e);
+            continue;
+        }
+        // 버스 0 기준 주소 (`start_bus`가 0이 아니면 창 앞쪽을 가리킬 수 있음)
+        let virt_base = window.wrapping_sub(aligned - region.base_address);
+        let ecam = Ecam { region: *region, virt_base };
+
+        // 영역 첫 버스의 00.0 ID를 두 방식으로 읽어 비교
+        let probe = PciDevice::at(region.start_bus, 0, 0);
+        let via_ecam = read_volatile((virt_base + probe.ecam_offset(&ecam.region, 0)) as *const u32);
+        let via_port = probe.read_legacy(PCI_VENDOR_ID);
+        if via_ecam != via_port {
+            crate::log_warn!(
+                "PCI: ECAM at 0x{:X} mismatch (0x{:08X} != 0x{:08X}), ignoring",
+                region.base_address, via_ecam, via_port
+            );
+            continue;
+        }
+        mapped.push(ecam);
+    }
+
+    let count = mapped.len();
+    ECAM.call_once(|| mapped);
+    if count > 0 {
+        crate::log_info!("PCI: ECAM configuration access enabled ({} region(s))", count);
+    }
+
+    UPSTREAM_PORTS.call_once(|| {
+        let mut ports = Vec::new();
+        let mut visited = [false; 256];
+        collect_upstream_ports(0, &mut ports, &mut visited);
+        ports
+    });
+}
+
 /// PCI 헤더 타입
 const PCI_HEADER_TYPE_DEVICE: u8 = 0x00;
 const PCI_HEADER_TYPE_BRIDGE: u8 = 0x01;
@@ -60,11 +163,93 @@ pub struct PciDevice {
 }
 
 impl PciDevice {
+    /// 위치만 채운 디바이스 (`read_info` 전)
+    fn at(bus: u8, device: u8, function: u8) -> Self {
+        Self {
+            bus,
+            device,
+            function,
+            vendor_id: 0,
+            device_id: 0,
+            class_code: 0,
+            subclass: 0,
+            prog_if: 0,
+            header_type: 0,
+            bar0: 0,
+            interrupt_line: 0,
+        }
+    }
+
+    /// 주어진 위치의 디바이스를 읽어 반환 (없으면 `None`)
+    ///
+    /// # Safety
+    /// 메모리 관리가 초기화된 후에 호출되어야 합니다.
+    pub unsafe fn probe(bus: u8, device: u8, function: u8) -> Option<Self> {
+        let mut dev = Self::at(bus, device, function);
+        if !dev.exists() {
+            return None;
+        }
+        dev.read_info();
+        Some(dev)
+    }
+
     /// PCI 구성 공간에서 32비트 레지스터 읽기
     ///
     /// # Safety
     /// 유효한 PCI 디바이스에 대한 접근이어야 합니다.
     pub unsafe fn read_config_register(&self, offset: u8) -> u32 {
+        match self.ecam_address(offset as u16) {
+            Some(addr) => read_volatile(addr as *const u32),
+            None => self.read_legacy(offset),
+        }
+    }
+    
+    /// PCI 구성 공간에 32비트 레지스터 쓰기
+    ///
+    /// # Safety
+    /// 유효한 PCI 디바이스에 대한 접근이어야 합니다.
+    pub unsafe fn write_config_register(&self, offset: u8, value: u32) {
+        match self.ecam_address(offset as u16) {
+            Some(addr) => write_volatile(addr as *mut u32, value),
+            None => self.write_legacy(offset, value),
+        }
+    }
+
+    /// 확장 구성 공간(0x000-0xFFF)에서 32비트 레지스터 읽기
+    ///
+    /// ECAM이 없으면 0x100 이상은 읽을 수 없으므로 `None`을 반환합니다.
+    ///
+    /// # Safety
+    /// 유효한 PCI 디바이스에 대한 접근이어야 합니다.
+    pub unsafe fn read_extended_config(&self, offset: u16) -> Option<u32> {
+        if offset >= PCI_EXT_CONFIG_SIZE {
+            return None;
+        }
+        match self.ecam_address(offset) {
+            Some(addr) => Some(read_volatile(addr as *const u32)),
+            None if offset < 0x100 => Some(self.read_legacy(offset as u8)),
+            None => None,
+        }
+    }
+
+    /// 확장 구성 공간(0x000-0xFFF)에 32비트 레지스터 쓰기
+    ///
+    /// # Safety
+    /// 유효한 PCI 디바이스에 대한 접근이어야 합니다.
+    pub unsafe fn write_extended_config(&self, offset: u16, value: u32) -> Result<(), &'static str> {
+        if offset >= PCI_EXT_CONFIG_SIZE {
+            return Err("offset beyond PCIe configuration space");
+        }
+        match self.ecam_address(offset) {
+            Some(addr) => write_volatile(addr as *mut u32, value),
+            None if offset < 0x100 => self.write_legacy(offset as u8, value),
+            None => return Err("extended configuration space requires ECAM"),
+        }
+        Ok(())
+    }
+
+    /// 포트 방식 32비트 읽기
+    unsafe fn read_legacy(&self, offset: u8) -> u32 {
         let address = self.make_config_address(offset);
         
         // 주소 포트에 주소 쓰기
@@ -75,12 +260,9 @@ impl PciDevice {
         let mut data_port: Port<u32> = Port::new(PCI_CONFIG_DATA);
         data_port.read()
     }
-    
-    /// PCI 구성 공간에 32비트 레지스터 쓰기
-    ///
-    /// # Safety
-    /// 유효한 PCI 디바이스에 대한 접근이어야 합니다.
-    pub unsafe fn write_config_register(&self, offset: u8, value: u32) {
+
+    /// 포트 방식 32비트 쓰기
+    unsafe fn write_legacy(&self, offset: u8, value: u32) {
         let address = self.make_config_address(offset);
         
         // 주소 포트에 주소 쓰기
@@ -91,7 +273,139 @@ impl PciDevice {
         let mut data_port: Port<u32> = Port::new(PCI_CONFIG_DATA);
         data_port.write(value);
     }
+
+    /// ECAM 영역 내 이 함수의 레지스터 오프셋 (dword 정렬)
+    fn ecam_offset(&self, region: &EcamRegion, offset: u16) -> u64 {
+        region.function_address(self.bus, self.device, self.function) - region.base_address
+            + (offset & 0xFFC) as u64
+    }
+
+    /// ECAM으로 접근할 수 있으면 레지스터의 가상 주소
+    fn ecam_address(&self, offset: u16) -> Option<u64> {
+        let ecam = ECAM.get()?.iter().find(|e| e.region.contains(0, self.bus))?;
+        Some(ecam.virt_base + self.ecam_offset(&ecam.region, offset))
+    }
     
+    /// PCI 구성 공간에서 16비트 값 읽기
+    ///
+    /// # Safety
+    /// 유효한 PCI 디바이스에 대한 접근이어야 합니다.
+    pub unsafe fn read_config_u16(&self, offset: u8) -> u16 {
+        (self.read_config_register(offset) >> ((offset & 0x2) * 8)) as u16
+    }
+
+    /// PCI 구성 공간에서 8비트 값 읽기
+    ///
+    /// # Safety
+    /// 유효한 PCI 디바이스에 대한 접근이어야 합니다.
+    pub unsafe fn read_config_u8(&self, offset: u8) -> u8 {
+        (self.read_config_register(offset) >> ((offset & 0x3) * 8)) as u8
+    }
+
+    /// PCI 구성 공간에 16비트 값 쓰기 (같은 dword의 나머지 절반은 유지)
+    ///
+    /// # Safety
+    /// 유효한 PCI 디바이스에 대한 접근이어야 합니다.
+    pub unsafe fn write_config_u16(&self, offset: u8, value: u16) {
+        let shift = (offset & 0x2) * 8;
+        let dword = self.read_config_register(offset);
+        let dword = (dword & !(0xFFFF << shift)) | ((value as u32) << shift);
+        self.write_config_register(offset, dword);
+    }
+
+    /// 커맨드 레지스터 비트 설정/해제
+    ///
+    /// 커맨드와 상태 레지스터는 같은 dword에 있습니다. 상태 레지스터의 오류 비트는
+    /// RW1C이므로 읽은 값을 그대로 되쓰면 지워지므로, 상태 절반은 0으로 씁니다.
+    ///
+    /// # Safety
+    /// 유효한 PCI 디바이스에 대한 접근이어야 합니다.
+    pub unsafe fn update_command(&self, set: u16, clear: u16) {
+        let command = self.read_config_u16(PCI_COMMAND);
+        self.write_config_register(PCI_COMMAND, ((command & !clear) | set) as u32);
+    }
+
+    /// Capability 목록 순회
+    ///
+    /// 각 Capability의 (ID, 구성 공간 오프셋)을 반환합니다.
+    ///
+    /// # Safety
+    /// 유효한 PCI 디바이스에 대한 접근이어야 합니다.
+    pub unsafe fn capabilities(&self) -> PciCapabilities<'_> {
+        let next = if self.read_config_u16(PCI_STATUS) & PCI_STATUS_CAP_LIST != 0 {
+            self.read_config_u8(PCI_CAPABILITY_POINTER) & 0xFC
+        } else {
+            0
+        };
+        PciCapabilities { device: self, next, remaining: MAX_CAPABILITIES }
+    }
+
+    /// 특정 Capability의 구성 공간 오프셋 찾기
+    ///
+    /// # Safety
+    /// 유효한 PCI 디바이스에 대한 접근이어야 합니다.
+    pub unsafe fn find_capability(&self, id: u8) -> Option<u8> {
+        self.capabilities().find(|&(cap_id, _)| cap_id == id).map(|(_, offset)| offset)
+    }
+
+    /// 확장 Capability 목록 순회 (ECAM 필요)
+    ///
+    /// 각 확장 Capability의 (ID, 버전, 구성 공간 오프셋)을 반환합니다.
+    ///
+    /// # Safety
+    /// 유효한 PCI 디바이스에 대한 접근이어야 합니다.
+    pub unsafe fn extended_capabilities(&self) -> PciExtCapabilities<'_> {
+        let next = if self.ecam_address(PCI_EXT_CAP_START).is_some() { PCI_EXT_CAP_START } else { 0 };
+        PciExtCapabilities { device: self, next, remaining: MAX_EXT_CAPABILITIES }
+    }
+
+    /// 특정 확장 Capability의 구성 공간 오프셋 찾기
+    ///
+    /// # Safety
+    /// 유효한 PCI 디바이스에 대한 접근이어야 합니다.
+    pub unsafe fn find_extended_capability(&self, id: u16) -> Option<u16> {
+        self.extended_capabilities().find(|&(cap_id, _, _)| cap_id == id).map(|(_, _, offset)| offset)
+    }
+
+    /// 브리지(헤더 타입 1) 여부
+    pub fn is_bridge(&self) -> bool {
+        self.header_type & 0x7F == PCI_HEADER_TYPE_BRIDGE
+    }
+
+    /// 브리지의 보조(secondary) 버스 번호 (브리지가 아니면 `None`)
+    ///
+    /// # Safety
+    /// 유효한 PCI 디바이스에 대한 접근이어야 합니다.
+    pub unsafe fn secondary_bus(&self) -> Option<u8> {
+        if !self.is_bridge() {
+            return None;
+        }
+        Some(self.read_config_u8(PCI_SECONDARY_BUS))
+    }
+
+    /// 메모리 BAR의 물리 주소 읽기 (64비트 BAR 지원)
+    ///
+    /// I/O BAR이거나 비어 있으면 `None`을 반환합니다.
+    ///
+    /// # Safety
+    /// 유효한 PCI 디바이스에 대한 접근이어야 합니다.
+    pub unsafe fn memory_bar(&self, index: u8) -> Option<u64> {
+        if index > 5 {
+            return None;
+        }
+        let offset = PCI_BAR0 + index * 4;
+        let low = self.read_config_register(offset);
+        if low & 0x1 != 0 {
+            return None;
+        }
+        let mut address = (low & 0xFFFF_FFF0) as u64;
+        // 타입 필드 0b10 = 64비트 BAR (다음 BAR가 상위 32비트)
+        if (low >> 1) & 0x3 == 0x2 && index < 5 {
+            address |= (self.read_config_register(offset + 4) as u64) << 32;
+        }
+        if address == 0 { None } else { Some(address) }
+    }
+
     /// PCI 구성 공간 주소 생성
     fn make_config_address(&self, offset: u8) -> u32 {
         let enable_bit = 1 << 31;
@@ -139,6 +453,63 @@ impl PciDevice {
     }
 }
 
+/// Capability 목록 순회 상한 (잘못된 연결 리스트로 인한 무한 루프 방지)
+const MAX_CAPABILITIES: usize = 48;
+
+/// PCI Capability 목록 반복자
+pub struct PciCapabilities<'a> {
+    device: &'a PciDevice,
+    next: u8,
+    remaining: usize,
+}
+
+impl Iterator for PciCapabilities<'_> {
+    /// (Capability ID, 구성 공간 오프셋)
+    type Item = (u8, u8);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // 표준 헤더(0x00-0x3F) 안을 가리키면 목록의 끝으로 간주
+        if self.next < 0x40 || self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+        let offset = self.next;
+        let header = unsafe { self.device.read_config_u16(offset) };
+        self.next = ((header >> 8) as u8) & 0xFC;
+        Some((header as u8, offset))
+    }
+}
+
+/// 확장 Capability 순회 상한 ((4096 - 256) / 4)
+const MAX_EXT_CAPABILITIES: usize = 960;
+
+/// PCIe 확장 Capability 목록 반복자
+pub struct PciExtCapabilities<'a> {
+    device: &'a PciDevice,
+    next: u16,
+    remaining: usize,
+}
+
+impl Iterator for PciExtCapabilities<'_> {
+    /// (확장 Capability ID, 버전, 구성 공간 오프셋)
+    type Item = (u16, u8, u16);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.next < PCI_EXT_CAP_START || self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+        let offset = self.next;
+        let header = unsafe { self.device.read_extended_config(offset) }?;
+        // 헤더가 0이면 확장 Capability 없음, 0xFFFFFFFF면 디바이스 없음
+        if header == 0 || header == 0xFFFF_FFFF {
+            return None;
+        }
+        self.next = ((header >> 20) & 0xFFC) as u16;
+        Some((header as u16, ((header >> 16) & 0xF) as u8, offset))
+    }
+}
+
 /// PCI 버스 스캔 콜백 타입
 pub type PciScanCallback = fn(&PciDevice) -> bool;
 
@@ -258,35 +629,177 @@ pub unsafe fn find_network_device() -> Option<PciDevice> {
 }
 
 /// PCI Express Capability 레지스터 오프셋
-const PCI_CAP_ID_PCIE: u8 = 0x10;
-const PCI_EXP_LNKCTL: u16 = 0x10; // Link Control Register
+const PCI_EXP_FLAGS: u8 = 0x02;   // PCI Express Capabilities Register
+const PCI_EXP_DEVCAP: u8 = 0x04;  // Device Capabilities
+const PCI_EXP_LNKCAP: u8 = 0x0C;  // Link Capabilities
+const PCI_EXP_LNKCTL: u8 = 0x10;  // Link Control Register
 const PCI_EXP_LNKCTL_ASPM_L0S: u16 = 0x01; // ASPM L0s enable
 const PCI_EXP_LNKCTL_ASPM_L1: u16 = 0x02;  // ASPM L1 enable
+const PCI_EXP_LNKCTL_ASPM_MASK: u16 = PCI_EXP_LNKCTL_ASPM_L0S | PCI_EXP_LNKCTL_ASPM_L1;
+
+/// PCIe 디바이스/포트 타입 (PCI_EXP_FLAGS 비트 4-7)
+const PCI_EXP_TYPE_ENDPOINT: u8 = 0x0;
+const PCI_EXP_TYPE_LEG_END: u8 = 0x1;
+const PCI_EXP_TYPE_ROOT_PORT: u8 = 0x4;
+const PCI_EXP_TYPE_DOWNSTREAM: u8 = 0x6;
+
+/// 지연 시간 인코딩 중 "제한 없음" (DEVCAP acceptable latency)
+const LATENCY_UNLIMITED: u8 = 7;
+
+/// ASPM 판단에 필요한 링크 한쪽 끝의 능력 (Link Capabilities)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AspmLinkCaps {
+    /// 지원하는 ASPM 상태 (LNKCTL 비트와 같은 배치: bit0 L0s, bit1 L1)
+    pub support: u16,
+    /// L0s exit latency 인코딩 (0: <64ns ... 7: >4us)
+    pub l0s_exit: u8,
+    /// L1 exit latency 인코딩 (0: <1us ... 7: >64us)
+    pub l1_exit: u8,
+}
+
+impl AspmLinkCaps {
+    fn from_lnkcap(lnkcap: u32) -> Self {
+        Self {
+            support: ((lnkcap >> 10) & 0x3) as u16,
+            l0s_exit: ((lnkcap >> 12) & 0x7) as u8,
+            l1_exit: ((lnkcap >> 15) & 0x7) as u8,
+        }
+    }
+}
+
+/// 엔드포인트가 허용하는 ASPM 진입 지연 (Device Capabilities)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AspmAcceptable {
+    /// L0s acceptable latency 인코딩 (0: 64ns ... 6: 4us, 7: 제한 없음)
+    pub l0s: u8,
+    /// L1 acceptable latency 인코딩 (0: 1us ... 6: 64us, 7: 제한 없음)
+    pub l1: u8,
+}
+
+impl AspmAcceptable {
+    fn from_devcap(devcap: u32) -> Self {
+        Self {
+            l0s: ((devcap >> 6) & 0x7) as u8,
+            l1: ((devcap >> 9) & 0x7) as u8,
+        }
+    }
+}
+
+/// 링크 양 끝의 능력으로 켤 수 있는 ASPM 상태 결정
+///
+/// 두 포트가 모두 지원하고, 두 포트 중 느린 쪽의 exit latency가 엔드포인트의
+/// acceptable latency 이내인 상태만 켭니다 (두 인코딩은 같은 배율을 사용).
+///
+/// # Returns
+/// Link Control에 쓸 ASPM 비트
+pub fn aspm_policy(endpoint: AspmLinkCaps, upstream: AspmLinkCaps, acceptable: AspmAcceptable) -> u16 {
+    let within = |exit: u8, limit: u8| limit == LATENCY_UNLIMITED || exit <= limit;
+    let mut control = endpoint.support & upstream.support & PCI_EXP_LNKCTL_ASPM_MASK;
+    if !within(endpoint.l0s_exit.max(upstream.l0s_exit), acceptable.l0s) {
+        control &= !PCI_EXP_LNKCTL_ASPM_L0S;
+    }
+    if !within(endpoint.l1_exit.max(upstream.l1_exit), acceptable.l1) {
+        control &= !PCI_EXP_LNKCTL_ASPM_L1;
+    }
+    control
+}
+
+/// PCIe 포트 타입 읽기
+unsafe fn pcie_port_type(device: &PciDevice, cap: u8) -> u8 {
+    ((device.read_config_u16(cap + PCI_EXP_FLAGS) >> 4) & 0xF) as u8
+}
+
+/// 업스트림 PCIe 포트 맵 (보조 버스, 포트, PCIe Capability 오프셋)
+///
+/// `init`에서 브리지를 따라 한 번 열거해 채웁니다.
+static UPSTREAM_PORTS: Once<Vec<(u8, PciDevice, u8)>> = Once::new();
+
+/// 버스를 스캔하며 루트 포트/다운스트림 스위치 포트를 기록하고 보조 버스로 재귀
+unsafe fn collect_upstream_ports(bus: u8, ports: &mut Vec<(u8, PciDevice, u8)>, visited: &mut [bool; 256]) {
+    // 잘못 설정된 브리지로 인한 순환 방지
+    if core::mem::replace(&mut visited[bus as usize], true) {
+        return;
+    }
+    for slot in 0..=31 {
+        let Some(first) = PciDevice::probe(bus, slot, 0) else { continue };
+        let functions = if first.header_type & 0x80 != 0 { 8 } else { 1 };
+        for function in 0..functions {
+            let Some(port) = PciDevice::probe(bus, slot, function) else { continue };
+            let Some(secondary) = port.secondary_bus().filter(|&b| b != 0) else { continue };
+            if let Some(cap) = port.find_capability(PCI_CAP_ID_PCIE) {
+                if matches!(pcie_port_type(&port, cap), PCI_EXP_TYPE_ROOT_PORT | PCI_EXP_TYPE_DOWNSTREAM) {
+                    ports.push((secondary, port, cap));
+                }
+            }
+            collect_upstream_ports(secondary, ports, visited);
+        }
+    }
+}
+
+/// 버스의 업스트림 PCIe 포트 (보조 버스가 `bus`인 루트 포트 또는 다운스트림 스위치 포트)
+fn find_upstream_port(bus: u8) -> Option<(PciDevice, u8)> {
+    UPSTREAM_PORTS
+        .get()?
+        .iter()
+        .find(|(secondary, _, _)| *secondary == bus)
+        .map(|&(_, port, cap)| (port, cap))
+}
+
+/// Link Control의 ASPM 비트만 교체
+unsafe fn write_aspm_control(device: &PciDevice, cap: u8, control: u16) {
+    let lnkctl = device.read_config_u16(cap + PCI_EXP_LNKCTL);
+    device.write_config_u16(cap + PCI_EXP_LNKCTL, (lnkctl & !PCI_EXP_LNKCTL_ASPM_MASK) | control);
+}
 
 /// PCIe ASPM (Active State Power Management) 활성화
 /// 
+/// 엔드포인트와 그 업스트림 포트(루트 포트 또는 스위치 다운스트림 포트)의
+/// Link Capabilities를 비교해 양쪽이 지원하고 지연 시간 조건을 만족하는 L0s/L1만
+/// 켭니다. 켤 때는 업스트림 포트를 먼저 설정합니다.
+///
 /// # Arguments
 /// * `device` - PCI 디바이스
+///
+/// # Returns
+/// 켜진 ASPM 상태 (Link Control 비트)
 /// 
 /// # Safety
 /// 유효한 PCI 디바이스에 대한 접근이어야 합니다.
-pub unsafe fn enable_pcie_aspm(device: &PciDevice) -> Result<(), &'static str> {
-    // PCIe Capability 찾기 (PCIe 디바이스만)
-    // 간단화: 일반 PCI 디바이스는 ASPM이 없을 수 있음
-    
-    // PCIe Capability는 Extended Capability List에 있음
-    // 여기서는 간단한 구현만 제공
-    
-    // Link Control Register 읽기/쓰기 (Capability가 있는 경우)
-    // 실제로는 PCIe Capability를 찾아서 Link Control Register를 수정해야 함
-    
-    crate::log_info!("PCIe ASPM: attempting to enable for device {:02X}:{:02X}.{:X}",
-                     device.bus, device.device, device.function);
-    
-    // TODO: 실제 PCIe Capability 찾기 및 ASPM 활성화
-    // 현재는 로그만 출력
-    
-    Ok(())
+pub unsafe fn enable_pcie_aspm(device: &PciDevice) -> Result<u16, &'static str> {
+    let cap = device.find_capability(PCI_CAP_ID_PCIE).ok_or("not a PCIe device")?;
+    match pcie_port_type(device, cap) {
+        PCI_EXP_TYPE_ENDPOINT | PCI_EXP_TYPE_LEG_END => {}
+        _ => return Err("not a PCIe endpoint"),
+    }
+    let (port, port_cap) = find_upstream_port(device.bus).ok_or("no upstream PCIe port")?;
+
+    let endpoint = AspmLinkCaps::from_lnkcap(device.read_config_register(cap + PCI_EXP_LNKCAP));
+    let upstream = AspmLinkCaps::from_lnkcap(port.read_config_register(port_cap + PCI_EXP_LNKCAP));
+    let acceptable = AspmAcceptable::from_devcap(device.read_config_register(cap + PCI_EXP_DEVCAP));
+    let control = aspm_policy(endpoint, upstream, acceptable);
+
+    write_aspm_control(&port, port_cap, control);
+    write_aspm_control(device, cap, control);
+
+    crate::log_info!(
+        "PCIe ASPM: {:02X}:{:02X}.{:X} via port {:02X}:{:02X}.{:X}: L0s {}, L1 {}",
+        device.bus, device.device, device.function,
+        port.bus, port.device, port.function,
+        if control & PCI_EXP_LNKCTL_ASPM_L0S != 0 { "on" } else { "off" },
+        if control & PCI_EXP_LNKCTL_ASPM_L1 != 0 { "on" } else { "off" }
+    );
+
+    // L1 PM Substates는 LTR/T_POWER_ON 설정이 필요하므로 지원 여부만 보고
+    if let (Some(ep_l1ss), Some(port_l1ss)) = (
+        device.find_extended_capability(PCI_EXT_CAP_ID_L1SS),
+        port.find_extended_capability(PCI_EXT_CAP_ID_L1SS),
+    ) {
+        let ep = device.read_extended_config(ep_l1ss + 4).unwrap_or(0);
+        let up = port.read_extended_config(port_l1ss + 4).unwrap_or(0);
+        crate::log_debug!("PCIe ASPM: L1 substates supported by both ends: 0x{:X}", ep & up & 0xF);
+    }
+
+    Ok(control)
 }
 
 /// PCIe Clock Gating 활성화
@@ -308,9 +821,16 @@ pub unsafe fn enable_pcie_clock_gating(device: &PciDevice) -> Result<(), &'stati
 
 /// 모든 PCIe 디바이스에 ASPM 활성화
 /// 
+/// FADT가 OS의 ASPM 제어를 금지하면 아무것도 바꾸지 않습니다.
+///
 /// # Safety
 /// 메모리 관리가 초기화된 후에 호출되어야 합니다.
 pub unsafe fn enable_all_pcie_aspm() {
+    let fadt = crate::power::acpi_table_fetch(b"FACP").and_then(crate::power::acpi_fadt::parse_fadt);
+    if fadt.is_some_and(|f| !f.aspm_allowed()) {
+        crate::log_info!("PCIe ASPM: disabled by firmware (FADT IAPC_BOOT_ARCH)");
+        return;
+    }
     scan_pci_bus(|device| {
         // PCIe 디바이스에 대해 ASPM 활성화 시도
         let _ = enable_pcie_aspm(device);
@@ -319,3 +839,28 @@ pub unsafe fn enable_all_pcie_aspm() {
     });
 }
 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test_case]
+    fn test_aspm_policy_requires_both_ends() {
+        let both = AspmLinkCaps { support: 0b11, l0s_exit: 2, l1_exit: 1 };
+        let l1_only = AspmLinkCaps { support: 0b10, l0s_exit: 2, l1_exit: 1 };
+        let any = AspmAcceptable { l0s: LATENCY_UNLIMITED, l1: LATENCY_UNLIMITED };
+        assert_eq!(aspm_policy(both, both, any), PCI_EXP_LNKCTL_ASPM_L0S | PCI_EXP_LNKCTL_ASPM_L1);
+        assert_eq!(aspm_policy(both, l1_only, any), PCI_EXP_LNKCTL_ASPM_L1);
+    }
+
+    #[test_case]
+    fn test_aspm_policy_respects_acceptable_latency() {
+        let endpoint = AspmLinkCaps { support: 0b11, l0s_exit: 1, l1_exit: 2 };
+        let port = AspmLinkCaps { support: 0b11, l0s_exit: 4, l1_exit: 1 };
+        // 루트 포트의 느린 L0s exit (4)가 허용치 (3)를 넘으므로 L1만 켬
+        let acceptable = AspmAcceptable { l0s: 3, l1: 2 };
+        assert_eq!(aspm_policy(endpoint, port, acceptable), PCI_EXP_LNKCTL_ASPM_L1);
+        let strict = AspmAcceptable { l0s: 3, l1: 1 };
+        assert_eq!(aspm_policy(endpoint, port, strict), 0);
+    }
+}
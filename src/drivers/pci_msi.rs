@@ -0,0 +1,176 @@
+//! PCI MSI / MSI-X 인터럽트
+//!
+//! 디바이스의 MSI 또는 MSI-X Capability를 찾아 `interrupts::irq`에서 할당받은
+//! 벡터의 메시지(주소/데이터)를 기록합니다. MSI-X는 엔트리마다 다른 벡터를
+//! 가질 수 있으므로 큐별 인터럽트에 사용하고, MSI는 벡터 하나만 사용합니다.
+//! 둘 다 사용할 수 없으면 오류를 반환하므로 드라이버는 레거시 INTx
+//! (`request_irq`)로 돌아가면 됩니다.
+
+use alloc::vec::Vec;
+use core::ptr::write_volatile;
+use x86_64::PhysAddr;
+
+use crate::drivers::pci::{PciDevice, PCI_CAP_ID_MSI, PCI_CAP_ID_MSIX, PCI_COMMAND_BUS_MASTER, PCI_COMMAND_INTX_DISABLE};
+use crate::interrupts::irq::{self, IrqHandler, MsiMessage};
+
+/// MSI Message Control 비트
+const MSI_CTRL_ENABLE: u16 = 1 << 0;
+const MSI_CTRL_MME_MASK: u16 = 0x7 << 4;
+const MSI_CTRL_64BIT: u16 = 1 << 7;
+
+/// MSI-X Message Control 비트
+const MSIX_CTRL_TABLE_SIZE_MASK: u16 = 0x7FF;
+const MSIX_CTRL_FUNCTION_MASK: u16 = 1 << 14;
+const MSIX_CTRL_ENABLE: u16 = 1 << 15;
+
+/// MSI-X 테이블 엔트리 크기와 필드 오프셋
+const MSIX_ENTRY_SIZE: u64 = 16;
+const MSIX_ENTRY_ADDR_LO: u64 = 0x0;
+const MSIX_ENTRY_ADDR_HI: u64 = 0x4;
+const MSIX_ENTRY_DATA: u64 = 0x8;
+const MSIX_ENTRY_VECTOR_CTRL: u64 = 0xC;
+const MSIX_ENTRY_MASKED: u32 = 1 << 0;
+
+/// 사용 중인 인터럽트 방식
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MsiKind {
+    Msi,
+    MsiX,
+}
+
+/// 디바이스에 설정된 MSI/MSI-X 벡터
+#[derive(Debug)]
+pub struct MsiVectors {
+    pub kind: MsiKind,
+    /// 엔트리 순서대로 할당된 인터럽트 벡터 (MSI는 1개)
+    pub vectors: Vec<u8>,
+    /// Capability 오프셋
+    cap: u8,
+}
+
+/// MSI-X로 엔트리별 벡터를 설정하고, 지원하지 않으면 MSI 벡터 하나로 대체
+///
+/// MSI로 대체되면 `handlers[0]`만 연결되므로, 드라이버는 `vectors.len()`을
+/// 보고 큐를 하나의 벡터로 처리해야 합니다.
+///
+/// # Safety
+/// 유효한 PCI 디바이스에 대한 접근이어야 하며, BAR가 매핑 가능해야 합니다.
+pub unsafe fn enable_vectors(device: &PciDevice, handlers: &[IrqHandler]) -> Result<MsiVectors, &'static str> {
+    let first = *handlers.first().ok_or("no MSI handler given")?;
+    match enable_msix(device, handlers) {
+        Ok(vectors) => Ok(vectors),
+        Err(_) => enable_msi(device, first),
+    }
+}
+
+/// MSI 벡터 하나 설정
+///
+/// # Safety
+/// 유효한 PCI 디바이스에 대한 접근이어야 합니다.
+pub unsafe fn enable_msi(device: &PciDevice, handler: IrqHandler) -> Result<MsiVectors, &'static str> {
+    let cap = device.find_capability(PCI_CAP_ID_MSI).ok_or("device has no MSI capability")?;
+    let (vector, message) = irq::request_msi(handler)?;
+
+    let control = device.read_config_u16(cap + 2) & !(MSI_CTRL_ENABLE | MSI_CTRL_MME_MASK);
+    device.write_config_u16(cap + 2, control);
+
+    device.write_config_register(cap + 4, message.address as u32);
+    if control & MSI_CTRL_64BIT != 0 {
+        device.write_config_register(cap + 8, (message.address >> 32) as u32);
+        device.write_config_u16(cap + 12, message.data as u16);
+    } else {
+        device.write_config_u16(cap + 8, message.data as u16);
+    }
+
+    device.write_config_u16(cap + 2, control | MSI_CTRL_ENABLE);
+    device.update_command(PCI_COMMAND_BUS_MASTER | PCI_COMMAND_INTX_DISABLE, 0);
+
+    crate::log_info!(
+        "PCI {:02X}:{:02X}.{:X}: MSI enabled, vector {}",
+        device.bus, device.device, device.function, vector
+    );
+    Ok(MsiVectors { kind: MsiKind::Msi, vectors: alloc::vec![vector], cap })
+}
+
+/// MSI-X 엔트리별 벡터 설정
+///
+/// `handlers[i]`가 테이블 엔트리 `i`에 연결됩니다.
+///
+/// # Safety
+/// 유효한 PCI 디바이스에 대한 접근이어야 하며, 테이블이 있는 BAR가 매핑 가능해야 합니다.
+pub unsafe fn enable_msix(device: &PciDevice, handlers: &[IrqHandler]) -> Result<MsiVectors, &'static str> {
+    let cap = device.find_capability(PCI_CAP_ID_MSIX).ok_or("device has no MSI-X capability")?;
+    let control = device.read_config_u16(cap + 2);
+    let table_size = ((control & MSIX_CTRL_TABLE_SIZE_MASK) + 1) as usize;
+    if handlers.is_empty() || handlers.len() > table_size {
+        return Err("MSI-X table too small");
+    }
+    let table = msix_table(device, cap)?;
+
+    // 테이블을 채우는 동안 함수 전체를 마스크
+    device.write_config_u16(cap + 2, control | MSIX_CTRL_ENABLE | MSIX_CTRL_FUNCTION_MASK);
+
+    let mut vectors = Vec::with_capacity(handlers.len());
+    for (index, &handler) in handlers.iter().enumerate() {
+        match irq::request_msi(handler) {
+            Ok((vector, message)) => {
+                write_msix_entry(table, index, &message);
+                vectors.push(vector);
+            }
+            Err(e) => {
+                for &vector in &vectors {
+                    irq::free_msi(vector);
+                }
+                device.write_config_u16(cap + 2, control & !MSIX_CTRL_ENABLE);
+                return Err(e);
+            }
+        }
+    }
+
+    device.write_config_u16(cap + 2, (control | MSIX_CTRL_ENABLE) & !MSIX_CTRL_FUNCTION_MASK);
+    device.update_command(PCI_COMMAND_BUS_MASTER | PCI_COMMAND_INTX_DISABLE, 0);
+
+    crate::log_info!(
+        "PCI {:02X}:{:02X}.{:X}: MSI-X enabled, {} of {} entries, vectors {:?}",
+        device.bus, device.device, device.function, vectors.len(), table_size, vectors
+    );
+    Ok(MsiVectors { kind: MsiKind::MsiX, vectors, cap })
+}
+
+/// MSI/MSI-X 비활성화 및 벡터 반환 (INTx 다시 허용)
+///
+/// # Safety
+/// `vectors`를 설정한 디바이스에 대해 호출되어야 합니다.
+pub unsafe fn disable(device: &PciDevice, vectors: MsiVectors) {
+    let control = device.read_config_u16(vectors.cap + 2);
+    let enable = match vectors.kind {
+        MsiKind::Msi => MSI_CTRL_ENABLE,
+        MsiKind::MsiX => MSIX_CTRL_ENABLE,
+    };
+    device.write_config_u16(vectors.cap + 2, control & !enable);
+    device.update_command(0, PCI_COMMAND_INTX_DISABLE);
+    for vector in vectors.vectors {
+        irq::free_msi(vector);
+    }
+}
+
+/// MSI-X 테이블의 가상 주소
+unsafe fn msix_table(device: &PciDevice, cap: u8) -> Result<u64, &'static str> {
+    let table_reg = device.read_config_register(cap + 4);
+    let bir = (table_reg & 0x7) as u8;
+    let offset = (table_reg & !0x7) as u64;
+    let bar = device.memory_bar(bir).ok_or("MSI-X table BAR is not a memory BAR")?;
+    let virt = crate::memory::paging::phys_to_virt(PhysAddr::new(bar + offset))
+        .ok_or("physical memory offset not available")?;
+    Ok(virt.as_u64())
+}
+
+/// MSI-X 테이블 엔트리 기록 (마스크한 상태에서 쓰고 마지막에 언마스크)
+unsafe fn write_msix_entry(table: u64, index: usize, message: &MsiMessage) {
+    let entry = table + index as u64 * MSIX_ENTRY_SIZE;
+    write_volatile((entry + MSIX_ENTRY_VECTOR_CTRL) as *mut u32, MSIX_ENTRY_MASKED);
+    write_volatile((entry + MSIX_ENTRY_ADDR_LO) as *mut u32, message.address as u32);
+    write_volatile((entry + MSIX_ENTRY_ADDR_HI) as *mut u32, (message.address >> 32) as u32);
+    write_volatile((entry + MSIX_ENTRY_DATA) as *mut u32, message.data);
+    write_volatile((entry + MSIX_ENTRY_VECTOR_CTRL) as *mut u32, 0);
+}
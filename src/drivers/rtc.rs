@@ -0,0 +1,212 @@
+//! CMOS RTC (Real-Time Clock) 드라이버
+//!
+//! CMOS 레지스터(포트 0x70/0x71)에서 날짜/시간을 읽고 씁니다.
+//! Status B 레지스터에 따라 BCD/바이너리, 12/24시간 형식을 처리하며,
+//! 세기(century) 레지스터 위치는 ACPI FADT에서 가져옵니다.
+//! RTC는 보통 UTC로 설정되어 있다고 가정합니다.
+
+use spin::Mutex;
+use x86_64::instructions::interrupts;
+use x86_64::instructions::port::Port;
+
+use crate::time::wallclock::DateTime;
+
+/// CMOS 인덱스/데이터 포트
+const CMOS_INDEX: u16 = 0x70;
+const CMOS_DATA: u16 = 0x71;
+
+/// RTC 레지스터
+mod reg {
+    pub const SECONDS: u8 = 0x00;
+    pub const MINUTES: u8 = 0x02;
+    pub const HOURS: u8 = 0x04;
+    pub const DAY: u8 = 0x07;
+    pub const MONTH: u8 = 0x08;
+    pub const YEAR: u8 = 0x09;
+    pub const STATUS_A: u8 = 0x0A;
+    pub const STATUS_B: u8 = 0x0B;
+}
+
+/// Status A: 업데이트 진행 중
+const STATUS_A_UIP: u8 = 1 << 7;
+/// Status B: 갱신 중지 (쓰기 시 사용)
+const STATUS_B_SET: u8 = 1 << 7;
+/// Status B: 바이너리 모드 (0이면 BCD)
+const STATUS_B_BINARY: u8 = 1 << 2;
+/// Status B: 24시간 모드 (0이면 12시간)
+const STATUS_B_24H: u8 = 1 << 1;
+/// 12시간 모드에서 PM 표시 비트
+const HOUR_PM: u8 = 0x80;
+
+/// 세기 레지스터가 없을 때 사용할 세기
+const DEFAULT_CENTURY: u16 = 20;
+
+/// CMOS 접근 직렬화 (인덱스/데이터 쌍이 끊기지 않도록)
+static CMOS_LOCK: Mutex<()> = Mutex::new(());
+
+/// 세기 레지스터 인덱스 (FADT `CENTURY` 필드, 0 = 없음)
+static CENTURY_REG: Mutex<u8> = Mutex::new(0);
+
+/// RTC 원시 레지스터 값
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct RawTime {
+    second: u8,
+    minute: u8,
+    hour: u8,
+    day: u8,
+    month: u8,
+    year: u8,
+    century: u8,
+}
+
+/// RTC 초기화
+///
+/// ACPI FADT에서 세기 레지스터 위치를 읽습니다.
+///
+/// # Safety
+/// 메모리 관리가 초기화된 후에 호출되어야 합니다 (ACPI 테이블 접근).
+pub unsafe fn init() {
+    let century = crate::power::acpi_table_fetch(b"FACP")
+        .and_then(crate::power::acpi_fadt::parse_fadt)
+        .map(|fadt| fadt.century_reg)
+        .unwrap_or(0);
+    *CENTURY_REG.lock() = century;
+
+    match read_time() {
+        Some(now) => {
+            crate::log_info!("RTC initialized: {} UTC (century reg 0x{:02X})", now, century);
+        }
+        None => {
+            crate::log_warn!("RTC returned an invalid date");
+        }
+    }
+}
+
+/// 현재 날짜/시간 읽기 (UTC)
+///
+/// 업데이트 사이클과 겹치지 않도록 같은 값이 두 번 연속 읽힐 때까지 반복합니다.
+pub fn read_time() -> Option<DateTime> {
+    let century_reg = *CENTURY_REG.lock();
+
+    let (raw, status_b) = interrupts::without_interrupts(|| {
+        let _guard = CMOS_LOCK.lock();
+        let mut last = read_raw(century_reg);
+        loop {
+            let current = read_raw(century_reg);
+            if current == last {
+                break;
+            }
+            last = current;
+        }
+        (last, read_cmos(reg::STATUS_B))
+    });
+
+    decode(raw, status_b)
+}
+
+/// 날짜/시간 쓰기 (UTC)
+pub fn write_time(time: &DateTime) {
+    let century_reg = *CENTURY_REG.lock();
+
+    interrupts::without_interrupts(|| {
+        let _guard = CMOS_LOCK.lock();
+        let status_b = read_cmos(reg::STATUS_B);
+        let encode = |v: u8| if status_b & STATUS_B_BINARY != 0 { v } else { to_bcd(v) };
+
+        let hour = if status_b & STATUS_B_24H != 0 {
+            encode(time.hour)
+        } else {
+            // 0시 → 12 AM, 12시 → 12 PM
+            let h12 = match time.hour % 12 { 0 => 12, h => h };
+            encode(h12) | if time.hour >= 12 { HOUR_PM } else { 0 }
+        };
+
+        // 쓰는 동안 갱신 중지
+        write_cmos(reg::STATUS_B, status_b | STATUS_B_SET);
+        write_cmos(reg::SECONDS, encode(time.second));
+        write_cmos(reg::MINUTES, encode(time.minute));
+        write_cmos(reg::HOURS, hour);
+        write_cmos(reg::DAY, encode(time.day));
+        write_cmos(reg::MONTH, encode(time.month));
+        write_cmos(reg::YEAR, encode((time.year % 100) as u8));
+        if century_reg != 0 {
+            write_cmos(century_reg, encode((time.year / 100) as u8));
+        }
+        write_cmos(reg::STATUS_B, status_b & !STATUS_B_SET);
+    });
+}
+
+/// 업데이트가 끝날 때까지 기다린 뒤 시간 레지스터 읽기
+fn read_raw(century_reg: u8) -> RawTime {
+    while read_cmos(reg::STATUS_A) & STATUS_A_UIP != 0 {
+        core::hint::spin_loop();
+    }
+    RawTime {
+        second: read_cmos(reg::SECONDS),
+        minute: read_cmos(reg::MINUTES),
+        hour: read_cmos(reg::HOURS),
+        day: read_cmos(reg::DAY),
+        month: read_cmos(reg::MONTH),
+        year: read_cmos(reg::YEAR),
+        century: if century_reg != 0 { read_cmos(century_reg) } else { 0 },
+    }
+}
+
+/// 원시 레지스터 값을 `DateTime`으로 변환
+fn decode(raw: RawTime, status_b: u8) -> Option<DateTime> {
+    let binary = status_b & STATUS_B_BINARY != 0;
+    let decode = |v: u8| if binary { v } else { from_bcd(v) };
+
+    let pm = raw.hour & HOUR_PM != 0;
+    let mut hour = decode(raw.hour & !HOUR_PM);
+    if status_b & STATUS_B_24H == 0 {
+        // 12시간 형식: 12 AM = 0시, 12 PM = 12시
+        hour %= 12;
+        if pm {
+            hour += 12;
+        }
+    }
+
+    let year = decode(raw.year) as u16;
+    let century = if raw.century != 0 {
+        decode(raw.century) as u16
+    } else {
+        DEFAULT_CENTURY
+    };
+
+    let time = DateTime {
+        year: century * 100 + year,
+        month: decode(raw.month),
+        day: decode(raw.day),
+        hour,
+        minute: decode(raw.minute),
+        second: decode(raw.second),
+    };
+    if time.is_valid() { Some(time) } else { None }
+}
+
+fn from_bcd(v: u8) -> u8 {
+    (v & 0x0F) + (v >> 4) * 10
+}
+
+fn to_bcd(v: u8) -> u8 {
+    ((v / 10) << 4) | (v % 10)
+}
+
+fn read_cmos(index: u8) -> u8 {
+    unsafe {
+        let mut index_port: Port<u8> = Port::new(CMOS_INDEX);
+        let mut data_port: Port<u8> = Port::new(CMOS_DATA);
+        index_port.write(index);
+        data_port.read()
+    }
+}
+
+fn write_cmos(index: u8, value: u8) {
+    unsafe {
+        let mut index_port: Port<u8> = Port::new(CMOS_INDEX);
+        let mut data_port: Port<u8> = Port::new(CMOS_DATA);
+        index_port.write(index);
+        data_port.write(value);
+    }
+}
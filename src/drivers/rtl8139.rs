@@ -644,6 +644,11 @@ pub fn maybe_enter_low_power(now_ms: u64, driver: &mut Rtl8139Driver) {
     let (last, timeout) = { let c = NET_POWER.lock(); (c.last_activity_ms, c.idle_timeout_ms) };
     if timeout == 0 { return; }
     if now_ms.saturating_sub(last) < timeout { return; }
+    enter_low_power(driver);
+}
+
+/// 네트워크 전원관리: 즉시 RX 중지로 저전력 진입 (절전 진입 시 사용)
+pub fn enter_low_power(driver: &mut Rtl8139Driver) {
     if !driver.low_power && driver.initialized {
         unsafe { 
             driver.stop_receive();
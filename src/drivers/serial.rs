@@ -43,3 +43,21 @@ pub fn write_byte(byte: u8) {
     SERIAL1.lock().send(byte);
 }
 
+/// COM1 라인 상태 레지스터
+const COM1_LINE_STATUS: u16 = 0x3F8 + 5;
+/// 라인 상태: 수신 데이터 있음
+const LSR_DATA_READY: u8 = 0x01;
+
+/// 수신된 바이트 하나 읽기 (논블로킹)
+///
+/// `SerialPort::receive`는 데이터가 올 때까지 기다리므로 라인 상태를 먼저 확인합니다.
+pub fn try_read_byte() -> Option<u8> {
+    use x86_64::instructions::port::PortReadOnly;
+    let mut port = SERIAL1.lock();
+    let status = unsafe { PortReadOnly::<u8>::new(COM1_LINE_STATUS).read() };
+    if status & LSR_DATA_READY == 0 {
+        return None;
+    }
+    Some(port.receive())
+}
+
@@ -1,11 +1,22 @@
-//! PIT (Programmable Interval Timer) 드라이버
+//! 시스템 타이머 드라이버
 //!
 //! 이 모듈은 x86 PIT를 사용하여 시스템 타이머를 구현합니다.
 //! PIT는 1.193182 MHz의 고정 클럭을 사용하며, 분주기를 통해 원하는 주파수로 설정할 수 있습니다.
+//!
+//! LAPIC 타이머를 사용할 수 있으면 tickless 모드(NO_HZ)로 전환합니다.
+//! tickless 모드에서는 주기적인 PIT 인터럽트 대신 LAPIC one-shot 타이머로
+//! 다음 만료 시점만 프로그래밍하며, CPU가 유휴 상태일 때는 다음 실제 이벤트
+//! (하우스키핑, 요청된 wakeup)까지 틱을 멈춥니다.
+//!
+//! 시각은 `time::clocksource`(TSC/HPET)에서 읽으며, 틱 카운터는 클럭소스가
+//! 등록되기 전 부팅 초기의 기준(jiffies)으로만 사용됩니다.
 
+use core::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use x86_64::instructions::port::Port;
 use spin::Mutex;
-use crate::interrupts::pic;
+use crate::drivers::lapic_timer;
+use crate::interrupts::irq;
+use crate::scheduler::affinity::MAX_CPUS;
 
 /// PIT I/O 포트 주소
 const PIT_CHANNEL0_DATA: u16 = 0x40;
@@ -13,14 +24,23 @@ const PIT_CHANNEL1_DATA: u16 = 0x41;
 const PIT_CHANNEL2_DATA: u16 = 0x42;
 const PIT_COMMAND: u16 = 0x43;
 
+/// PIT ISA IRQ 번호
+const PIT_IRQ: u8 = 0;
+
 /// PIT 기본 클럭 주파수 (Hz)
 const PIT_BASE_FREQUENCY: u32 = 1_193_182;
 
-/// 타이머 틱 카운터
-static TICK_COUNT: Mutex<u64> = Mutex::new(0);
+/// PIT 채널 2 게이트/스피커 제어 포트
+const PIT_GATE_PORT: u16 = 0x61;
+
+/// 타이머 틱 카운터 (밀리초)
+///
+/// 주기 모드에서는 PIT 인터럽트마다 1씩 증가하고,
+/// tickless 모드에서는 타이머 인터럽트마다 클럭소스 기준 현재 시각으로 갱신됩니다.
+static TICK_COUNT: AtomicU64 = AtomicU64::new(0);
 
-/// 밀리초당 틱 수 (1000Hz = 1ms마다 인터럽트)
-const TICKS_PER_SECOND: u32 = 1000;
+/// 초당 틱 수 (1000Hz = 1ms마다 인터럽트)
+pub const TICKS_PER_SECOND: u32 = 1000;
 
 /// Idle 상태에서 동적 tick 조정
 /// Idle 상태일 때는 더 긴 간격으로 tick (타겟 ≥ 10ms)
@@ -28,9 +48,45 @@ static IDLE_TICK_MODE: Mutex<bool> = Mutex::new(false);
 static SKIP_TICKS: Mutex<u32> = Mutex::new(0);
 static TICK_SKIP_COUNTER: Mutex<u32> = Mutex::new(0);
 
+/// 틱 주기 (ns) - tickless 모드에서 CPU가 바쁠 때 사용
+const TICK_PERIOD_NS: u64 = 1_000_000_000 / TICKS_PER_SECOND as u64;
+
+/// tickless 유휴 상태에서 한 번에 잠들 수 있는 최대 시간 (ms)
+const NOHZ_MAX_IDLE_MS: u64 = 1000;
+
+/// tickless 모드 활성화 여부 (LAPIC one-shot 타이머 사용)
+static TICKLESS: AtomicBool = AtomicBool::new(false);
+
+/// 유휴 시 틱 정지 허용 여부 (전력 정책에 따라 변경)
+static NOHZ_IDLE_ENABLED: AtomicBool = AtomicBool::new(true);
+
+/// AP별 틱 정지 여부 (실행할 스레드가 없으면 AP는 틱 없이 잠듦)
+static AP_TICK_STOPPED: [AtomicBool; MAX_CPUS] = [const { AtomicBool::new(true) }; MAX_CPUS];
+
+/// 다음 하우스키핑(열/메모리/전력 주기 작업) 시각 (ms)
+static NEXT_HOUSEKEEPING_MS: AtomicU64 = AtomicU64::new(1000);
+
+/// 하우스키핑이 실행된 횟수 (초 단위 카운터)
+static HOUSEKEEPING_SECONDS: AtomicU64 = AtomicU64::new(0);
+
+/// 다음 유휴에서 깨어나야 하는 가장 이른 시각 (ms, `u64::MAX` = 없음)
+static WAKEUP_REQUEST_MS: AtomicU64 = AtomicU64::new(u64::MAX);
+
 /// 밀리초 가져오기
 pub fn get_milliseconds() -> u64 {
-    *TICK_COUNT.lock()
+    get_nanoseconds() / 1_000_000
+}
+
+/// 나노초 가져오기
+///
+/// 현재 클럭소스(TSC/HPET) 해상도를 가지며, 클럭소스 초기화 전에는 틱(1ms) 해상도입니다.
+pub fn get_nanoseconds() -> u64 {
+    crate::time::now_ns()
+}
+
+/// 틱 카운터 값 (jiffies 클럭소스)
+pub fn get_ticks() -> u64 {
+    TICK_COUNT.load(Ordering::Acquire)
 }
 
 /// 초 가져오기
@@ -66,11 +122,193 @@ pub unsafe fn init() {
     crate::log_info!("PIT initialized: {} Hz (divisor: {})", TICKS_PER_SECOND, divisor);
 }
 
-/// 타이머 인터럽트 핸들러
+/// PIT 채널 2를 기준으로 다른 카운터의 증가량 측정
+///
+/// 채널 2를 mode 0(one-shot)으로 `window_ms` 동안 카운트시키고,
+/// 그 구간 동안 `sample`이 반환하는 값의 증가량을 돌려줍니다.
+/// 인터럽트 없이 폴링으로 동작하므로 인터럽트 설정 전후 모두 사용할 수 있습니다.
+/// `window_ms`는 최대 54ms입니다 (16비트 카운터 한계).
+pub fn calibrate_against_pit<F: FnMut() -> u64>(window_ms: u32, mut sample: F) -> u64 {
+    let count = (PIT_BASE_FREQUENCY as u64 * window_ms.min(54) as u64 / 1000) as u16;
+
+    unsafe {
+        let mut gate: Port<u8> = Port::new(PIT_GATE_PORT);
+        let mut command_port: Port<u8> = Port::new(PIT_COMMAND);
+        let mut channel2: Port<u8> = Port::new(PIT_CHANNEL2_DATA);
+
+        // 스피커 출력 끄고 채널 2 게이트 올리기
+        let value = gate.read();
+        gate.write((value & !0x02) | 0x01);
+
+        // Channel 2, lobyte/hibyte, Mode 0 (Interrupt on Terminal Count), Binary
+        command_port.write(0xB0);
+        channel2.write((count & 0xFF) as u8);
+        channel2.write((count >> 8) as u8);
+
+        // 게이트를 내렸다 올려 카운트 재시작
+        let value = gate.read();
+        gate.write(value & !0x01);
+        gate.write(value | 0x01);
+
+        let start = sample();
+        // OUT2 (bit 5)가 올라가면 카운트 종료
+        while gate.read() & 0x20 == 0 {
+            core::hint::spin_loop();
+        }
+        let end = sample();
+
+        end.wrapping_sub(start)
+    }
+}
+
+/// tickless 모드 전환 시도
+///
+/// LAPIC 타이머를 보정하고 one-shot 모드로 설정한 뒤 PIT 인터럽트(IRQ 0)를 마스크합니다.
+/// 이후 틱은 LAPIC 타이머가 생성하며, 유휴 상태에서는 다음 이벤트까지 정지됩니다.
+///
+/// 이미 tickless 모드여도 LAPIC 타이머를 다시 설정하고 첫 틱을 예약합니다.
+/// S3 재개 후에는 LAPIC 상태가 사라지므로, 다시 프로그래밍하지 않으면 CPU가
+/// 깨어날 인터럽트 없이 잠들게 됩니다.
+///
+/// # Returns
+/// tickless 모드로 전환되면 `true`, LAPIC 타이머를 사용할 수 없으면 `false`
+/// (이 경우 호출자는 PIT 주기 틱을 계속 사용해야 합니다).
+///
+/// # Safety
+/// 메모리 관리와 IDT가 초기화된 후에 호출되어야 합니다.
+pub unsafe fn enable_tickless() -> bool {
+    match lapic_timer::init() {
+        Ok(mode) => {
+            irq::disable_irq(irq::isa_irq_to_gsi(PIT_IRQ));
+            let now = get_milliseconds();
+            NEXT_HOUSEKEEPING_MS.store(now - now % 1000 + 1000, Ordering::Release);
+            crate::per_cpu!(tick_stopped).store(false, Ordering::Release);
+            let resumed = TICKLESS.swap(true, Ordering::AcqRel);
+            lapic_timer::arm_oneshot_ns(TICK_PERIOD_NS);
+            if resumed {
+                crate::log_info!("Tickless idle re-armed (LAPIC {:?})", mode);
+            } else {
+                crate::log_info!("Tickless idle enabled (LAPIC {:?})", mode);
+            }
+            true
+        }
+        Err(e) => {
+            TICKLESS.store(false, Ordering::Release);
+            crate::log_warn!("LAPIC timer unavailable ({}), using periodic PIT ticks", e);
+            false
+        }
+    }
+}
+
+/// PIT 주기 틱 인터럽트 등록 및 활성화 (tickless 모드를 쓸 수 없을 때)
+pub fn enable_pit_irq() {
+    if let Err(e) = irq::request_irq(irq::isa_irq_to_gsi(PIT_IRQ), pit_irq_handler) {
+        crate::log_error!("Failed to request PIT IRQ: {}", e);
+    }
+}
+
+/// tickless 모드 여부
+pub fn is_tickless() -> bool {
+    TICKLESS.load(Ordering::Acquire)
+}
+
+/// 다음 유휴 구간에서 늦어도 `deadline_ms`에 깨어나도록 요청
+///
+/// tickless 유휴 상태에서는 주기 틱이 없으므로, 시간 기반으로 폴링하는 루프
+/// (예: 데스크톱 렌더링)는 유휴 진입 전에 다음 작업 시각을 알려야 합니다.
+/// 여러 요청 중 가장 이른 시각이 사용되며, 유휴에서 깨어나면 초기화됩니다.
+pub fn request_wakeup_at(deadline_ms: u64) {
+    WAKEUP_REQUEST_MS.fetch_min(deadline_ms, Ordering::AcqRel);
+}
+
+/// 유휴 진입 직전 호출: 가능하면 틱을 멈추고 다음 이벤트 시각에 one-shot 타이머 설정
+///
+/// 준비 큐에 실행할 스레드가 있으면 선점을 위해 틱을 유지합니다. 인터럽트가 비활성화된
+/// 상태에서 호출하고 `sti; hlt`로 잠들어야 합니다. 그러지 않으면 틱을 멈춘 뒤 잠들기 전에
+/// 온 인터럽트를 놓쳐 깨어날 시각 없이 잠들 수 있습니다.
+pub fn nohz_idle_enter() {
+    if !TICKLESS.load(Ordering::Acquire) || !NOHZ_IDLE_ENABLED.load(Ordering::Acquire) {
+        return;
+    }
+    if crate::scheduler::ready_count() > 0 {
+        return;
+    }
+
+    let now_ns = get_nanoseconds();
+    let next = nohz_next_event_ns(
+        now_ns,
+        NEXT_HOUSEKEEPING_MS.load(Ordering::Acquire),
+        WAKEUP_REQUEST_MS.load(Ordering::Acquire),
+        crate::time::next_event_ns(),
+    );
+    let Some(next_event_ns) = next else {
+        // 다음 틱보다 가까운 이벤트면 주기 틱 유지
+        return;
+    };
+
+    crate::per_cpu!(tick_stopped).store(true, Ordering::Release);
+    lapic_timer::arm_oneshot_ns(next_event_ns - now_ns);
+}
+
+/// 틱을 멈추고 잠들 때 깨어날 시각 (ns)
+///
+/// 하우스키핑, wakeup 요청, 가장 이른 hrtimer 중 먼저 오는 것이며 최대
+/// `NOHZ_MAX_IDLE_MS`까지만 잠듭니다. 다음 틱(`TICK_PERIOD_NS`)보다 가까우면
+/// 틱을 멈출 이유가 없으므로 `None`입니다.
+fn nohz_next_event_ns(now_ns: u64, housekeeping_ms: u64, wakeup_ms: u64, hrtimer_ns: Option<u64>) -> Option<u64> {
+    let now = now_ns / 1_000_000;
+    let next_event_ms = housekeeping_ms.min(wakeup_ms).min(now + NOHZ_MAX_IDLE_MS);
+    let next_event_ns = next_event_ms.saturating_mul(1_000_000);
+    let next_event_ns = hrtimer_ns.map_or(next_event_ns, |t| t.min(next_event_ns));
+    (next_event_ns > now_ns + TICK_PERIOD_NS).then_some(next_event_ns)
+}
+
+/// 유휴에서 깨어난 직후 호출: 틱 재개 및 wakeup 통계 기록
+pub fn nohz_idle_exit() {
+    let now = get_milliseconds();
+    if crate::per_cpu!(tick_stopped).swap(false, Ordering::AcqRel) {
+        TICK_COUNT.store(now, Ordering::Release);
+        program_next_tick(get_nanoseconds());
+    }
+    WAKEUP_REQUEST_MS.store(u64::MAX, Ordering::Release);
+    crate::power::stats::record_wakeup(now);
+}
+
+/// 다음 인터럽트까지 CPU를 유휴 상태로 대기
+///
+/// 인터럽트가 비활성화된 상태에서 호출해야 합니다. 대기 조건을 확인한 뒤
+/// `sti; hlt`로 원자적으로 잠들기 때문에 확인과 대기 사이의 wakeup을 놓치지 않습니다.
+/// 반환 시 인터럽트는 활성화되어 있습니다.
+pub fn idle_wait() {
+    nohz_idle_enter();
+    x86_64::instructions::interrupts::enable_and_hlt();
+    nohz_idle_exit();
+    crate::kernel::softirq::run_pending();
+}
+
+/// 현재 시각 기준 다음 LAPIC 타이머 인터럽트 프로그래밍
+fn program_next_tick(now_ns: u64) {
+    lapic_timer::arm_oneshot_ns(next_tick_delta_ns(now_ns, crate::time::next_event_ns()));
+}
+
+/// 다음 LAPIC 타이머 인터럽트까지의 간격 (ns)
+///
+/// 기본은 1틱 후이며, 그보다 먼저 만료되는 hrtimer가 있으면 그 시각에 맞춥니다.
+/// 이미 지난 hrtimer는 바로 처리되도록 1µs 뒤로 잡습니다.
+fn next_tick_delta_ns(now_ns: u64, hrtimer_ns: Option<u64>) -> u64 {
+    match hrtimer_ns {
+        Some(next) if next > now_ns => (next - now_ns).min(TICK_PERIOD_NS),
+        Some(_) => 1_000,
+        None => TICK_PERIOD_NS,
+    }
+}
+
+/// PIT 타이머 IRQ 핸들러
 ///
 /// 타이머 틱이 발생할 때마다 호출됩니다.
 /// 이 함수는 인터럽트 컨텍스트에서 실행되므로 빠르게 처리해야 합니다.
-pub extern "x86-interrupt" fn timer_interrupt_handler(_stack_frame: x86_64::structures::idt::InterruptStackFrame) {
+/// `irq_enter`/`irq_exit`와 EOI는 공통 IRQ 디스패처가 처리합니다.
+fn pit_irq_handler(_gsi: u32) {
     // Idle tick coalescing: 일부 tick을 스킵하여 wakeup 감소
     let mut skip_counter = TICK_SKIP_COUNTER.lock();
     let skip_ticks = *SKIP_TICKS.lock();
@@ -80,18 +318,78 @@ pub extern "x86-interrupt" fn timer_interrupt_handler(_stack_frame: x86_64::stru
         *skip_counter += 1;
         if *skip_counter < skip_ticks {
             // Tick 스킵 - 타이머만 증가, 나머지는 처리하지 않음
-            *TICK_COUNT.lock() += 1;
-            unsafe {
-                pic::end_of_interrupt(0);
-            }
+            TICK_COUNT.fetch_add(1, Ordering::AcqRel);
             return;
         }
         *skip_counter = 0;
     }
+    drop(skip_counter);
     
     // 타이머 틱 증가
-    *TICK_COUNT.lock() += 1;
+    TICK_COUNT.fetch_add(1, Ordering::AcqRel);
+    
+    let now_ns = get_nanoseconds();
+    handle_tick(now_ns / 1_000_000, now_ns);
+}
+
+/// LAPIC 타이머 인터럽트 핸들러 (tickless 모드)
+///
+/// one-shot 타이머가 만료될 때마다 호출됩니다.
+/// 다음 틱을 다시 프로그래밍하며, 유휴 중이었다면 `nohz_idle_exit`이
+/// 유휴 루프에서 틱 재개를 마무리합니다.
+pub extern "x86-interrupt" fn lapic_timer_interrupt_handler(_stack_frame: x86_64::structures::idt::InterruptStackFrame) {
+    crate::kernel::softirq::irq_enter();
+    
+    if crate::kernel::percpu::cpu_id() != 0 {
+        ap_tick();
+        crate::smp::apic::send_eoi();
+        crate::kernel::softirq::irq_exit();
+        return;
+    }
     
+    let now_ns = get_nanoseconds();
+    let now = now_ns / 1_000_000;
+    TICK_COUNT.store(now, Ordering::Release);
+    
+    handle_tick(now, now_ns);
+    
+    // 다음 틱 예약 (유휴 진입 시 nohz_idle_enter가 덮어씀)
+    program_next_tick(now_ns);
+    
+    crate::smp::apic::send_eoi();
+    
+    // 만료된 타이머 콜백 등 softirq 처리
+    crate::kernel::softirq::irq_exit();
+}
+
+/// AP의 LAPIC 타이머 틱
+///
+/// 타이머 만료와 하우스키핑은 BSP가 처리하므로 스케줄러 틱만 수행합니다.
+/// 실행할 스레드가 없고 병렬 initcall 단계도 아니면 틱을 멈추고, 스레드가 배치되면
+/// 재스케줄링 IPI로 깨어난 유휴 루프가 `start_ap_tick`으로 다시 시작합니다.
+fn ap_tick() {
+    crate::scheduler::tick();
+    if crate::scheduler::has_local_work() || crate::kernel::initcall::parallel_active() {
+        lapic_timer::arm_oneshot_ns(TICK_PERIOD_NS);
+    } else {
+        AP_TICK_STOPPED[crate::kernel::percpu::cpu_id() as usize].store(true, Ordering::Release);
+    }
+}
+
+/// AP 틱 시작 (실행할 스레드가 생겼거나 AP에서 병렬 initcall을 실행할 때)
+///
+/// 이미 틱이 돌고 있으면 아무것도 하지 않습니다. LAPIC 타이머 레지스터는 CPU마다
+/// 있으므로 현재 CPU의 타이머만 설정됩니다.
+pub fn start_ap_tick() {
+    x86_64::instructions::interrupts::without_interrupts(|| {
+        if AP_TICK_STOPPED[crate::kernel::percpu::cpu_id() as usize].swap(false, Ordering::AcqRel) {
+            lapic_timer::arm_oneshot_ns(TICK_PERIOD_NS);
+        }
+    });
+}
+
+/// 틱마다 수행하는 공통 작업 (스케줄러, 타이머 만료 확인, 주기적 하우스키핑)
+fn handle_tick(now_ms: u64, now_ns: u64) {
     // 스케줄러 틱 처리
     // TODO: 컨텍스트 스위칭이 필요하면 여기서 처리
     let context_switch_needed = crate::scheduler::tick();
@@ -99,70 +397,79 @@ pub extern "x86-interrupt" fn timer_interrupt_handler(_stack_frame: x86_64::stru
     // Wakeup 이벤트 기록 (C-State 추적용)
     if context_switch_needed {
         // 컨텍스트 스위칭이 발생하면 wakeup으로 간주
-        if let Some(pm) = crate::power::get_manager() {
-            if let Some(manager) = pm.lock().as_mut() {
-                // Idle manager는 PowerManager 내부에 있으므로 직접 접근 불가
-                // 대신 전역 함수를 통해 wakeup 기록
-                crate::power::cpu_usage::update_cpu_usage();
-            }
-        }
+        crate::power::cpu_usage::update_cpu_usage();
     }
     
-    // Watchdog 체크
-    crate::kernel::watchdog::check();
+    // 만료된 hrtimer/coarse 타이머가 있으면 타이머 softirq 올림
+    crate::time::check_expired(now_ns);
     
     // 인터럽트 메트릭 기록
     crate::monitoring::record_interrupt();
     
-    // CPU 온도 모니터링 (주기적 체크)
-    // 1초마다 체크 (1000 틱마다)
-    let tick_count = *TICK_COUNT.lock();
-    if tick_count % 1000 == 0 {
-        crate::power::temps::periodic_thermal_check();
-        
-        // 메모리 단편화 통계 업데이트 (1초마다)
-        crate::memory::fragmentation::update_fragmentation_stats();
+    // 주기적 하우스키핑 (1초마다)
+    // tickless 모드에서는 틱이 건너뛰어질 수 있으므로 틱 번호 대신 마감 시각으로 판단
+    let next = NEXT_HOUSEKEEPING_MS.load(Ordering::Acquire);
+    if now_ms >= next {
+        NEXT_HOUSEKEEPING_MS.store(now_ms - now_ms % 1000 + 1000, Ordering::Release);
+        let seconds = HOUSEKEEPING_SECONDS.fetch_add(1, Ordering::AcqRel) + 1;
+        run_housekeeping(seconds);
+    }
+}
+
+/// 1초 주기 하우스키핑
+///
+/// # Arguments
+/// * `seconds` - 하우스키핑 실행 횟수 (5초/60초 주기 작업 판단용)
+fn run_housekeeping(seconds: u64) {
+    // 클럭소스 기준점 갱신 (32비트 카운터 래핑 대비)
+    crate::time::clocksource::update();
+    
+    // CPU 온도 모니터링 (1초마다)
+    crate::power::temps::periodic_thermal_check();
+    
+    // 메모리 단편화 통계 업데이트 (1초마다)
+    crate::memory::fragmentation::update_fragmentation_stats();
+    
+    // 힙 여유가 모자라면 페이지 캐시 줄이기 (1초마다)
+    #[cfg(feature = "fs")]
+    crate::fs::cache::reclaim();
+    
+    if seconds % 60 == 0 {
+        // 압축된 페이지 정리 (1분마다)
+        crate::memory::compression::cleanup_compressed_pages(60000); // 1분 이상 오래된 것 정리
         
-        // 압축된 페이지 정리 (1분마다, 60000 틱)
-        if tick_count % 60000 == 0 {
-            crate::memory::compression::cleanup_compressed_pages(60000); // 1분 이상 오래된 것 정리
+        // 프레임 캐시 정리 (1분마다)
+        crate::memory::frame_cache::cleanup_cache(60000);
+    }
+    
+    // OOM Killer 체크 (5초마다)
+    if seconds % 5 == 0 {
+        if crate::memory::oom_killer::check_oom() {
+            let killed = crate::memory::oom_killer::try_kill_oom();
+            if killed > 0 {
+                crate::log_warn!("OOM Killer activated: {} thread(s) terminated", killed);
+            }
         }
         
-        // 프레임 캐시 정리 (1분마다)
-        if tick_count % 60000 == 0 {
-            crate::memory::frame_cache::cleanup_cache(60000);
+        // 사용자 활동 기반 전원 관리 조정 (5초마다)
+        if let Err(e) = crate::power::user_activity::adjust_power_based_on_activity() {
+            crate::log_debug!("Failed to adjust power based on activity: {:?}", e);
         }
         
-        // OOM Killer 체크 (5초마다, 5000 틱)
-        if tick_count % 5000 == 0 {
-            if crate::memory::oom_killer::check_oom() {
-                let killed = crate::memory::oom_killer::try_kill_oom();
-                if killed > 0 {
-                    crate::log_warn!("OOM Killer activated: {} thread(s) terminated", killed);
-                }
-            }
-            
-            // 사용자 활동 기반 전원 관리 조정 (5초마다)
-            if let Err(e) = crate::power::user_activity::adjust_power_based_on_activity() {
-                crate::log_debug!("Failed to adjust power based on activity: {:?}", e);
-            }
-            
-            // 배터리 수준 기반 전원 관리 조정 (5초마다)
-            if let Err(e) = crate::power::battery::adjust_power_based_on_battery() {
-                crate::log_debug!("Failed to adjust power based on battery: {:?}", e);
-            }
+        // 배터리 수준 기반 전원 관리 조정 (5초마다)
+        if let Err(e) = crate::power::battery::adjust_power_based_on_battery() {
+            crate::log_debug!("Failed to adjust power based on battery: {:?}", e);
         }
     }
-    
-    // PIC에 인터럽트 종료 신호 전송 (IRQ 0)
-    unsafe {
-        pic::end_of_interrupt(0);
-    }
 }
 
 /// Idle 상태에서 tick coalescing 활성화
 /// skip_ticks: 몇 개의 tick을 스킵할지 (예: 10 = 10ms마다 tick)
+///
+/// tickless 모드에서는 `enabled`가 유휴 시 틱 정지(NO_HZ) 허용 여부를 결정하고,
+/// `skip_ticks`는 PIT 주기 모드에서만 사용됩니다.
 pub fn set_idle_tick_coalescing(enabled: bool, skip_ticks: u32) {
+    NOHZ_IDLE_ENABLED.store(enabled, Ordering::Release);
     *IDLE_TICK_MODE.lock() = enabled;
     *SKIP_TICKS.lock() = skip_ticks;
     *TICK_SKIP_COUNTER.lock() = 0;
@@ -173,9 +480,38 @@ pub fn set_idle_tick_coalescing(enabled: bool, skip_ticks: u32) {
 /// # Arguments
 /// * `ms` - 대기할 밀리초
 pub fn sleep_ms(ms: u64) {
-    let start = get_milliseconds();
-    while get_milliseconds() - start < ms {
-        x86_64::instructions::hlt();
-    }
+    crate::time::hrtimer::sleep_ns(ms * 1_000_000);
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MS: u64 = 1_000_000;
+
+    #[test_case]
+    fn test_next_tick_delta() {
+        assert_eq!(next_tick_delta_ns(10 * MS, None), TICK_PERIOD_NS);
+        // 틱보다 이른 hrtimer에 맞춤
+        assert_eq!(next_tick_delta_ns(10 * MS, Some(10 * MS + 300_000)), 300_000);
+        // 틱보다 늦은 hrtimer는 다음 틱에서 다시 계산
+        assert_eq!(next_tick_delta_ns(10 * MS, Some(50 * MS)), TICK_PERIOD_NS);
+        // 이미 지난 hrtimer
+        assert_eq!(next_tick_delta_ns(10 * MS, Some(10 * MS)), 1_000);
+    }
+
+    #[test_case]
+    fn test_nohz_next_event() {
+        let now = 2_500 * MS;
+        // 하우스키핑이 가장 이름
+        assert_eq!(nohz_next_event_ns(now, 3_000, u64::MAX, None), Some(3_000 * MS));
+        // wakeup 요청과 hrtimer 중 이른 것
+        assert_eq!(nohz_next_event_ns(now, 3_000, 2_800, None), Some(2_800 * MS));
+        assert_eq!(nohz_next_event_ns(now, 3_000, 2_800, Some(2_600 * MS)), Some(2_600 * MS));
+        // 이벤트가 없으면 최대 유휴 시간까지만
+        assert_eq!(nohz_next_event_ns(now, u64::MAX, u64::MAX, None), Some(now + NOHZ_MAX_IDLE_MS * MS));
+        // 다음 틱 안에 이벤트가 있으면 틱 유지
+        assert_eq!(nohz_next_event_ns(now, 3_000, u64::MAX, Some(now + TICK_PERIOD_NS / 2)), None);
+        assert_eq!(nohz_next_event_ns(now, 2_501, u64::MAX, None), None);
+    }
+}
@@ -3,9 +3,11 @@
 //! ELAN I2C-HID 트랙패드를 지원합니다.
 //! 특히 ELAN708:00 04F3:30A0 모델을 타겟으로 합니다.
 
+use core::sync::atomic::{AtomicU64, Ordering};
 use spin::Mutex;
 use crate::drivers::i2c_hid::{I2cHidDevice, I2cHidError};
 use crate::drivers::mouse::MouseEvent;
+use crate::drivers::base::{Device, DeviceInfo, DeviceMatch, Driver};
 
 /// ELAN 트랙패드 제조사 ID
 pub const ELAN_VENDOR_ID: u16 = 0x04F3;
@@ -13,6 +15,9 @@ pub const ELAN_VENDOR_ID: u16 = 0x04F3;
 /// ELAN708 제품 ID
 pub const ELAN708_PRODUCT_ID: u16 = 0x30A0;
 
+/// I2C HID 디바이스 표준 ACPI HID
+pub const HID_I2C_HID: &str = "PNP0C50";
+
 /// 트랙패드 에러
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum TouchpadError {
@@ -128,28 +133,16 @@ impl ElanTouchpad {
 
     /// Input Report 처리
     ///
+    /// # Arguments
+    /// * `buffer` - 길이 필드를 제외한 Input Report
+    ///
     /// # Returns
     /// 생성된 마우스 이벤트 (있는 경우)
-    pub fn process_input(&mut self) -> Result<Option<MouseEvent>, TouchpadError> {
-        if !self.initialized {
-            return Err(TouchpadError::NotInitialized);
-        }
-
-        let mut buffer = [0u8; 64];
-        let len = match self.device.read_input_report(&mut buffer) {
-            Ok(len) => len,
-            Err(I2cHidError::I2cError(crate::drivers::i2c::I2cError::Nack)) => {
-                // NACK는 데이터가 없다는 의미 (정상)
-                return Ok(None);
-            }
-            Err(e) => {
-                return Err(TouchpadError::I2cHidError(e));
-            }
-        };
-
-        if len < 6 {
+    pub fn handle_report(&mut self, buffer: &[u8]) -> Option<MouseEvent> {
+        let len = buffer.len();
+        if !self.initialized || len < 6 {
             // 유효한 리포트가 아님
-            return Ok(None);
+            return None;
         }
 
         // ELAN 트랙패드 리포트 파싱
@@ -186,7 +179,7 @@ impl ElanTouchpad {
         // 상태 업데이트
         self.prev_touch = current_touch;
 
-        Ok(event)
+        event
     }
 
     /// 터치 정보를 마우스 이벤트로 변환
@@ -271,6 +264,43 @@ impl ElanTouchpad {
 /// 전역 트랙패드 인스턴스
 static TOUCHPAD: Mutex<Option<ElanTouchpad>> = Mutex::new(None);
 
+/// 트랙패드 인스턴스 세대 (초기화/제거마다 증가, 이전 리포트 태스크 종료용)
+static GENERATION: AtomicU64 = AtomicU64::new(0);
+
+/// Input Report 확인 주기 (ms)
+///
+/// 장치의 인터럽트 라인(GPIO)은 아직 사용하지 않으므로 타이머로 깨어나 읽습니다.
+const REPORT_POLL_MS: u64 = 8;
+
+/// Input Report 태스크
+///
+/// 리포트를 비동기 I2C 전송으로 읽어 마우스 이벤트 큐에 넣습니다. 전송 중에는
+/// 트랙패드 락을 잡지 않으므로 커서 위치 조회가 막히지 않습니다.
+async fn report_task(generation: u64) {
+    let mut buffer = [0u8; 64];
+    loop {
+        crate::task::sleep_ms(REPORT_POLL_MS).await;
+
+        let device = match *TOUCHPAD.lock() {
+            Some(ref tp) if GENERATION.load(Ordering::Acquire) == generation => tp.device,
+            _ => return,
+        };
+        let len = match device.read_input_report_async(&mut buffer).await {
+            Ok(len) => len,
+            // NACK는 데이터가 없다는 의미 (정상), 그 밖의 에러도 다음 주기에 재시도
+            Err(_) => continue,
+        };
+
+        let event = match TOUCHPAD.lock().as_mut() {
+            Some(tp) if GENERATION.load(Ordering::Acquire) == generation => tp.handle_report(&buffer[..len]),
+            _ => return,
+        };
+        if let Some(event) = event {
+            crate::drivers::mouse::inject_event(event);
+        }
+    }
+}
+
 /// 트랙패드 초기화
 ///
 /// # Arguments
@@ -283,25 +313,44 @@ pub unsafe fn init(slave_addr: u8) -> Result<(), TouchpadError> {
     touchpad.init()?;
     
     *TOUCHPAD.lock() = Some(touchpad);
+    let generation = GENERATION.fetch_add(1, Ordering::AcqRel) + 1;
+    crate::task::spawn("touchpad", report_task(generation));
     
     crate::log_info!("ELAN touchpad driver initialized");
     Ok(())
 }
 
-/// 트랙패드 이벤트 폴링
-///
-/// GUI 루프에서 주기적으로 호출되어야 합니다.
-pub fn poll_event() -> Option<MouseEvent> {
-    if let Some(ref mut touchpad) = *TOUCHPAD.lock() {
-        match touchpad.process_input() {
-            Ok(event) => event,
-            Err(_e) => {
-                // 에러는 무시하고 계속 진행
-                None
+/// I2C HID 트랙패드 드라이버 (디바이스 모델)
+struct TouchpadDriver;
+
+/// 디바이스 모델에 등록할 트랙패드 드라이버
+pub static DRIVER: &dyn Driver = &TouchpadDriver;
+
+impl Driver for TouchpadDriver {
+    fn name(&self) -> &'static str {
+        "elan-touchpad"
+    }
+
+    fn match_table(&self) -> &'static [DeviceMatch] {
+        &[DeviceMatch::Acpi(HID_I2C_HID)]
+    }
+
+    fn probe(&self, device: &Device) -> Result<(), &'static str> {
+        let DeviceInfo::I2c { address, .. } = device.info else {
+            return Err("not an I2C device");
+        };
+        match unsafe { init(address as u8) } {
+            Ok(()) => Ok(()),
+            Err(e) => {
+                crate::log_warn!("Failed to initialize touchpad: {:?}", e);
+                Err("touchpad init failed")
             }
         }
-    } else {
-        None
+    }
+
+    fn remove(&self, _device: &Device) {
+        GENERATION.fetch_add(1, Ordering::AcqRel);
+        *TOUCHPAD.lock() = None;
     }
 }
 
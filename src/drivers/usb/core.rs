@@ -3,21 +3,41 @@
 //! 이 모듈은 USB 시스템의 중앙 관리자입니다.
 
 use crate::drivers::usb::error::UsbError;
-use crate::drivers::usb::host_controller::{UsbHostController, UsbHostControllerType, GenericUsbHostController, find_usb_host_controller};
+use crate::drivers::base::{self, DeviceId, DeviceInfo};
+use crate::drivers::pci::PciDevice;
+use crate::drivers::usb::host_controller::{UsbHostController, GenericUsbHostController, get_usb_controller_type};
 use crate::drivers::usb::device::UsbDevice;
 use spin::Mutex;
+use alloc::format;
+use alloc::string::String;
 use alloc::vec::Vec;
+use core::sync::atomic::{AtomicU64, Ordering};
+use crate::time::{hrtimer, TimerRestart, NSEC_PER_MSEC};
+
+/// 포트에 연결되어 열거된 디바이스
+struct AttachedPort {
+    /// `host_controllers` 인덱스
+    controller: usize,
+    port: u8,
+    address: u8,
+    /// 디바이스 모델 ID (등록 전이거나 디스크립터가 없으면 `None`)
+    device_id: Option<DeviceId>,
+}
 
 /// USB 매니저
 pub struct UsbManager {
     /// 호스트 컨트롤러 목록
     host_controllers: Vec<GenericUsbHostController>,
+    /// 호스트 컨트롤러의 디바이스 모델 ID (`host_controllers`와 같은 순서)
+    controller_ids: Vec<DeviceId>,
+    /// 디바이스 모델에 아직 등록하지 않은 열거된 디바이스 (주소, 이름, 부모, 정보; 매니저 락 밖에서 등록)
+    pending_devices: Vec<(u8, String, Option<DeviceId>, DeviceInfo)>,
+    /// 포트별로 열거된 디바이스 (분리 시 정리와 등록 해제에 사용)
+    attached: Vec<AttachedPort>,
     /// 연결된 USB 디바이스 목록
     devices: Vec<UsbDevice>,
     /// 감지된 HID 장치 (주소와 프로퍼티)
     hid_devices: Vec<(u8, crate::drivers::usb::hid::HidDevice)>,
-    /// 다음 디바이스 주소 (1-127)
-    next_address: u8,
     /// 초기화 여부
     initialized: bool,
 }
@@ -27,54 +47,51 @@ impl UsbManager {
     fn new() -> Self {
         Self {
             host_controllers: Vec::new(),
+            controller_ids: Vec::new(),
+            pending_devices: Vec::new(),
+            attached: Vec::new(),
             devices: Vec::new(),
             hid_devices: Vec::new(),
-            next_address: 1,
             initialized: false,
         }
     }
     
-    /// USB 매니저 초기화
+    /// USB 호스트 컨트롤러 추가
+    ///
+    /// 디바이스 모델이 USB 호스트 컨트롤러(PCI 클래스 0x0C/0x03)를 이 드라이버에
+    /// 바인딩할 때 호출됩니다.
     ///
     /// # Safety
     /// PCI 버스 및 메모리 관리가 초기화된 후에 호출되어야 합니다.
-    pub unsafe fn init() -> Result<(), UsbError> {
+    pub unsafe fn add_controller(device_id: DeviceId, pci_device: PciDevice) -> Result<(), UsbError> {
+        let controller_type = get_usb_controller_type(&pci_device).ok_or(UsbError::DeviceNotFound)?;
+        crate::log_info!(
+            "Found USB host controller: {:?} (Vendor=0x{:04X}, Device=0x{:04X})",
+            controller_type, pci_device.vendor_id, pci_device.device_id
+        );
+
         let mut manager = MANAGER.lock();
+        let mut controller = GenericUsbHostController::new(pci_device, controller_type);
         
-        if manager.initialized {
-            return Ok(()); // 이미 초기화됨
-        }
+        // 드라이버 재시도 메커니즘 적용
+        use crate::kernel::error_recovery::{driver_retry, RetryConfig};
         
-        crate::log_info!("Scanning for USB host controllers...");
+        let retry_config = RetryConfig {
+            max_retries: 3,
+            retry_delay_ms: 50,
+            exponential_backoff: true,
+        };
         
-        // USB 호스트 컨트롤러 찾기
-        if let Some((pci_device, controller_type)) = find_usb_host_controller() {
-            crate::log_info!("Found USB host controller: {:?}", controller_type);
-            
-            let mut controller = GenericUsbHostController::new(pci_device, controller_type);
-            
-            // 드라이버 재시도 메커니즘 적용
-            use crate::kernel::error_recovery::{driver_retry, RetryConfig};
-            
-            let retry_config = RetryConfig {
-                max_retries: 3,
-                retry_delay_ms: 50,
-                exponential_backoff: true,
-            };
-            
-            match driver_retry(|| controller.init(), retry_config) {
-                Ok(()) => {
-                    manager.host_controllers.push(controller);
-                    crate::log_info!("USB host controller initialized successfully");
-                }
-                Err(e) => {
-                    crate::log_warn!("Failed to initialize USB host controller after retries: {}", e);
-                    return Err(e);
-                }
+        match driver_retry(|| controller.init(), retry_config) {
+            Ok(()) => {
+                manager.host_controllers.push(controller);
+                manager.controller_ids.push(device_id);
+                crate::log_info!("USB host controller initialized successfully");
+            }
+            Err(e) => {
+                crate::log_warn!("Failed to initialize USB host controller after retries: {}", e);
+                return Err(e);
             }
-        } else {
-            crate::log_warn!("No USB host controller found");
-            return Err(UsbError::DeviceNotFound);
         }
         
         manager.initialized = true;
@@ -84,18 +101,56 @@ impl UsbManager {
     }
     
     /// USB 디바이스 열거 (Enumeration) - 공개 인터페이스
+    ///
+    /// 아직 열거되지 않은 연결된 포트만 처리하므로 다시 호출해도 됩니다.
     pub unsafe fn enumerate_devices() -> Result<(), UsbError> {
-        let mut manager = MANAGER.lock();
-        manager.enumerate_devices()
+        let pending = {
+            let mut manager = MANAGER.lock();
+            manager.scan_ports()?;
+            core::mem::take(&mut manager.pending_devices)
+        };
+        register_pending(pending);
+        Ok(())
+    }
+
+    /// 포트 연결 상태 변화 처리 (hotplug)
+    ///
+    /// 포트에 열거된 디바이스가 있으면 먼저 분리 처리(정리 후 디바이스 모델에서
+    /// 등록 해제)하고, 포트에 디바이스가 연결되어 있으면 다시 열거해 등록합니다.
+    /// 같은 포트에서 장치를 빠르게 바꿔 끼운 경우도 이 순서로 처리됩니다.
+    ///
+    /// # Safety
+    /// 호스트 컨트롤러가 초기화된 후에 호출되어야 합니다.
+    pub unsafe fn handle_port_change(controller: usize, port: u8) {
+        let (removed, pending) = {
+            let mut manager = MANAGER.lock();
+            let removed = manager.detach_port(controller, port);
+            let connected = manager
+                .host_controllers
+                .get(controller)
+                .is_some_and(|c| c.check_port_connection(port).unwrap_or(false));
+            if connected {
+                crate::log_info!("USB device attached on port {}", port);
+                if let Err(e) = manager.attach_port(controller, port) {
+                    crate::log_warn!("Failed to enumerate device on port {}: {:?}", port, e);
+                }
+            }
+            (removed, core::mem::take(&mut manager.pending_devices))
+        };
+        // 드라이버 remove/probe가 매니저 락을 잡을 수 있으므로 락을 놓고 처리
+        if let Some(id) = removed {
+            base::unregister_device(id);
+        }
+        register_pending(pending);
     }
     
-    /// USB 디바이스 열거 (Enumeration) - 내부 구현
+    /// 연결된 포트 스캔 - 내부 구현
     ///
-    /// 연결된 USB 디바이스를 발견하고 초기화합니다.
+    /// 연결된 USB 디바이스를 발견하고 초기화합니다. 이미 열거된 포트는 건너뜁니다.
     ///
     /// # Safety
     /// USB 매니저가 초기화된 후에 호출되어야 합니다.
-    pub unsafe fn enumerate_devices(&mut self) -> Result<(), UsbError> {
+    unsafe fn scan_ports(&mut self) -> Result<(), UsbError> {
         if !self.initialized {
             return Err(UsbError::NotInitialized);
         }
@@ -103,29 +158,26 @@ impl UsbManager {
         crate::log_info!("Starting USB device enumeration...");
         
         // 각 호스트 컨트롤러에 대해 디바이스 열거
-        for controller in &mut self.host_controllers {
+        for index in 0..self.host_controllers.len() {
             // 실제 포트 수 가져오기
-            let port_count = controller.port_count();
+            let port_count = self.host_controllers[index].port_count();
             if port_count == 0 {
                 crate::log_warn!("Controller has no ports, skipping");
                 continue;
             }
             
             for port in 1..=port_count {
+                if self.attached.iter().any(|a| a.controller == index && a.port == port) {
+                    continue;
+                }
                 // 포트 연결 상태 확인
-                if let Ok(connected) = self.check_port_connection(controller, port) {
-                    if connected {
-                        crate::log_info!("USB device detected on port {}", port);
-                        
-                        // 디바이스 초기화 및 열거
-                        if let Ok(device) = self.enumerate_device_on_port(&mut *controller, port) {
-                            self.devices.push(device);
-                            crate::log_info!("USB device enumerated successfully (address: {})", 
-                                           self.devices.len());
-                        } else {
-                            crate::log_warn!("Failed to enumerate device on port {}", port);
-                        }
-                    }
+                if !self.host_controllers[index].check_port_connection(port).unwrap_or(false) {
+                    continue;
+                }
+                crate::log_info!("USB device detected on port {}", port);
+                match self.attach_port(index, port) {
+                    Ok(address) => crate::log_info!("USB device enumerated successfully (address: {})", address),
+                    Err(_) => crate::log_warn!("Failed to enumerate device on port {}", port),
                 }
             }
         }
@@ -133,13 +185,44 @@ impl UsbManager {
         crate::log_info!("USB enumeration complete: {} device(s) found", self.devices.len());
         Ok(())
     }
-    
-    /// 포트 연결 상태 확인
-    /// 
+
+    /// 포트의 디바이스를 열거하고 등록 대기 목록에 추가
+    ///
+    /// # Returns
+    /// 할당된 디바이스 주소
+    ///
     /// # Safety
     /// 호스트 컨트롤러가 초기화되어 있어야 합니다.
-    unsafe fn check_port_connection(&self, controller: &GenericUsbHostController, port: u8) -> Result<bool, UsbError> {
-        controller.check_port_connection(port)
+    unsafe fn attach_port(&mut self, index: usize, port: u8) -> Result<u8, UsbError> {
+        // 열거 중 `self`의 주소/HID 목록을 갱신하므로 컨트롤러 목록을 잠시 분리
+        let mut controllers = core::mem::take(&mut self.host_controllers);
+        let result = match controllers.get_mut(index) {
+            Some(controller) => self.enumerate_device_on_port(controller, port),
+            None => Err(UsbError::InvalidParameter),
+        };
+        self.host_controllers = controllers;
+        let device = result?;
+
+        let address = device.address();
+        if let Some(info) = device_model_info(&device) {
+            let parent = self.controller_ids.get(index).copied();
+            self.pending_devices.push((address, format!("usb{}-{}", index + 1, port), parent, info));
+        }
+        self.attached.push(AttachedPort { controller: index, port, address, device_id: None });
+        self.devices.push(device);
+        Ok(address)
+    }
+
+    /// 포트의 디바이스 분리 처리
+    ///
+    /// # Returns
+    /// 등록 해제할 디바이스 모델 ID
+    fn detach_port(&mut self, controller: usize, port: u8) -> Option<DeviceId> {
+        let index = self.attached.iter().position(|a| a.controller == controller && a.port == port)?;
+        let entry = self.attached.remove(index);
+        crate::log_info!("USB device detached from port {} (address: {})", port, entry.address);
+        self.remove_device(entry.address);
+        entry.device_id
     }
     
     /// 특정 포트의 디바이스 열거
@@ -187,12 +270,10 @@ impl UsbManager {
                         device_descriptor.product_id,
                         max_packet_size);
         
-        // 3. 주소 할당
-        let new_address = self.next_address;
-        if new_address > 127 {
-            return Err(UsbError::DeviceLimitReached);
-        }
-        self.next_address += 1;
+        // 3. 주소 할당 (분리된 디바이스의 주소는 재사용)
+        let new_address = (1..=127u8)
+            .find(|&a| !self.devices.iter().any(|d| d.address() == a))
+            .ok_or(UsbError::DeviceLimitReached)?;
         
         // Set Address 요청
         let set_addr_request = UsbControlRequest::new_set_address(new_address);
@@ -276,13 +357,20 @@ impl UsbManager {
 
         // HID 인터페이스 감지 (키보드/마우스) 및 저장
         for (intf, eps) in interfaces.iter() {
-            if let Some(hid) = crate::drivers::usb::hid::HidDevice::from_interface(intf, eps) {
+            if let Some(mut hid) = crate::drivers::usb::hid::HidDevice::from_interface(intf, eps) {
                 crate::log_info!(
                     "Detected USB HID {:?} (INT IN ep=0x{:02X}, interval={}ms)",
                     hid.kind,
                     hid.interrupt_in.map(|e| e.address).unwrap_or(0),
                     hid.interrupt_in.map(|e| e.interval_ms).unwrap_or(0)
                 );
+                // 엔드포인트 폴링 간격마다 폴링 필요 표시 (장치 제거 시 `remove_device`가 취소)
+                let interval_ms = hid.interrupt_in.map(|e| e.interval_ms).unwrap_or(0).max(1) as u64;
+                hid.poll_timer = Some(hrtimer::start_periodic(
+                    interval_ms * NSEC_PER_MSEC,
+                    hid_poll_timer,
+                    new_address as usize | (interval_ms as usize) << 8,
+                ));
                 self.hid_devices.push((new_address, hid));
             }
         }
@@ -294,6 +382,28 @@ impl UsbManager {
         Ok(device)
     }
     
+    /// 분리된 디바이스 정리
+    ///
+    /// HID 폴링 타이머를 취소하고 디바이스와 HID 인터페이스 목록에서 제거합니다.
+    /// 타이머를 남겨 두면 해제된 장치를 계속 폴링하게 됩니다.
+    ///
+    /// # Returns
+    /// 제거된 디바이스 (해당 주소의 디바이스가 없으면 `None`)
+    pub fn remove_device(&mut self, address: u8) -> Option<UsbDevice> {
+        self.hid_devices.retain(|(addr, hid)| {
+            if *addr != address {
+                return true;
+            }
+            if let Some(timer) = hid.poll_timer {
+                hrtimer::cancel(timer);
+            }
+            false
+        });
+        clear_hid_poll_due(address);
+        let index = self.devices.iter().position(|d| d.address() == address)?;
+        Some(self.devices.remove(index))
+    }
+    
     /// 연결된 디바이스 수 가져오기
     pub fn device_count(&self) -> usize {
         self.devices.len()
@@ -305,16 +415,107 @@ impl UsbManager {
     }
 }
 
+/// 디바이스 모델에 등록할 USB 디바이스 정보 (디스크립터 기준)
+fn device_model_info(device: &UsbDevice) -> Option<DeviceInfo> {
+    let descriptor = device.device_descriptor()?;
+    Some(DeviceInfo::Usb {
+        address: device.address(),
+        vendor_id: descriptor.vendor_id,
+        product_id: descriptor.product_id,
+        class: descriptor.device_class,
+        subclass: descriptor.device_subclass,
+        protocol: descriptor.device_protocol,
+    })
+}
+
+/// 열거된 디바이스를 디바이스 모델에 등록하고 포트 기록에 ID 저장
+fn register_pending(pending: Vec<(u8, String, Option<DeviceId>, DeviceInfo)>) {
+    for (address, name, parent, info) in pending {
+        match base::register_device(name, parent, info) {
+            Ok(id) => {
+                if let Some(entry) = MANAGER.lock().attached.iter_mut().find(|a| a.address == address) {
+                    entry.device_id = Some(id);
+                }
+            }
+            Err(e) => {
+                crate::log_warn!("USB: failed to register device: {}", e);
+            }
+        }
+    }
+}
+
 /// 전역 USB 매니저
 static MANAGER: Mutex<UsbManager> = Mutex::new(UsbManager {
     host_controllers: Vec::new(),
+    controller_ids: Vec::new(),
+    pending_devices: Vec::new(),
+    attached: Vec::new(),
     devices: Vec::new(),
     hid_devices: Vec::new(),
-    next_address: 1,
     initialized: false,
 });
 
+/// 연결된 USB 디바이스 열거 (`UsbManager::enumerate_devices`)
+///
+/// # Safety
+/// USB 매니저가 초기화된 후에 호출되어야 합니다.
+pub unsafe fn enumerate_devices() -> Result<(), UsbError> {
+    UsbManager::enumerate_devices()
+}
+
+/// 포트 연결/분리 처리 (hotplug)
+///
+/// 각 컨트롤러의 Port Status Change Event를 처리하고, 연결 상태가 바뀐 포트마다
+/// `UsbManager::handle_port_change`로 디바이스를 열거하거나 제거합니다. 디바이스
+/// 모델 등록/해제가 `DeviceAdded`/`DeviceRemoved` 이벤트를 발행합니다.
+pub fn poll_port_changes() {
+    let changes: Vec<(usize, u8)> = {
+        let mut manager = MANAGER.lock();
+        if !manager.initialized {
+            return;
+        }
+        manager
+            .host_controllers
+            .iter_mut()
+            .enumerate()
+            .flat_map(|(index, controller)| {
+                unsafe { controller.take_port_changes() }.into_iter().map(move |port| (index, port))
+            })
+            .collect()
+    };
+    for (controller, port) in changes {
+        unsafe { UsbManager::handle_port_change(controller, port) };
+    }
+}
+
+/// 호스트 컨트롤러가 하나 이상 초기화되었는지 확인
+pub fn is_initialized() -> bool {
+    MANAGER.lock().initialized
+}
+
+/// 폴링 간격이 지난 HID 장치 비트맵 (비트 번호 = USB 주소 0-127)
+static HID_POLL_DUE: [AtomicU64; 2] = [AtomicU64::new(0), AtomicU64::new(0)];
+
+/// HID 폴링 타이머 콜백 (타이머 softirq)
+///
+/// `data`의 하위 8비트는 장치 주소, 나머지는 폴링 간격(ms)입니다.
+/// USB 컨트롤러 락을 softirq에서 잡지 않도록 표시만 하고, 실제 전송은 `poll_hid`가 수행합니다.
+fn hid_poll_timer(data: usize) -> TimerRestart {
+    let address = data & 0x7F;
+    let interval_ms = (data >> 8) as u64;
+    HID_POLL_DUE[address / 64].fetch_or(1 << (address % 64), Ordering::AcqRel);
+    TimerRestart::Restart { period_ns: interval_ms * NSEC_PER_MSEC }
+}
+
+/// 장치의 폴링 필요 표시 제거 (장치 분리 시)
+fn clear_hid_poll_due(address: u8) {
+    let address = address as usize & 0x7F;
+    HID_POLL_DUE[address / 64].fetch_and(!(1 << (address % 64)), Ordering::AcqRel);
+}
+
 /// Poll HID devices for input and feed into GUI mouse when available
+///
+/// 엔드포인트의 폴링 간격이 지난 장치만 폴링합니다.
 pub fn poll_hid() {
     use crate::drivers::usb::hid::HidDeviceKind;
     use crate::drivers::usb::hid::build_get_report_request;
@@ -323,7 +524,13 @@ pub fn poll_hid() {
     if !mgr.initialized || mgr.host_controllers.is_empty() { return; }
     // 단일 컨트롤러 가정
     let controller: *mut GenericUsbHostController = &mut mgr.host_controllers[0];
-    for (_addr, hid) in mgr.hid_devices.iter() {
+    let due = [HID_POLL_DUE[0].swap(0, Ordering::AcqRel), HID_POLL_DUE[1].swap(0, Ordering::AcqRel)];
+    for (addr, hid) in mgr.hid_devices.iter() {
+        // 타이머가 있는 장치는 간격이 지났을 때만 폴링
+        let addr = *addr as usize & 0x7F;
+        if hid.poll_timer.is_some() && due[addr / 64] & (1 << (addr % 64)) == 0 {
+            continue;
+        }
         match hid.kind {
             HidDeviceKind::Keyboard => unsafe {
                 // Boot keyboard report: 8 bytes (modifiers, reserved, 6 keycodes)
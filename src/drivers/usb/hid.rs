@@ -24,6 +24,8 @@ pub struct HidDevice {
     pub kind: HidDeviceKind,
     pub interrupt_in: Option<HidEndpoint>,
     pub interface_number: u8,
+    /// 폴링 주기 타이머 (엔드포인트 interval 기준)
+    pub poll_timer: Option<crate::time::TimerId>,
 }
 
 impl HidDevice {
@@ -54,6 +56,7 @@ impl HidDevice {
             kind,
             interrupt_in: ep_in,
             interface_number: intf.interface_number,
+            poll_timer: None,
         })
     }
 }
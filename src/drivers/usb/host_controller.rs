@@ -6,6 +6,7 @@
 //! - EHCI (USB 2.0)
 //! - OHCI/UHCI (USB 1.1)
 
+use alloc::vec::Vec;
 use crate::drivers::pci::PciDevice;
 use crate::drivers::usb::error::UsbError;
 use crate::drivers::usb::xhci::XhciController;
@@ -167,6 +168,17 @@ impl GenericUsbHostController {
         }
     }
     
+    /// 연결 상태가 바뀐 포트 목록 가져오기 (xHCI Port Status Change Event)
+    ///
+    /// # Safety
+    /// 컨트롤러가 초기화되어 있어야 합니다.
+    pub unsafe fn take_port_changes(&mut self) -> Vec<u8> {
+        match self {
+            GenericUsbHostController::Xhci(ctrl) => ctrl.take_port_changes(),
+            _ => Vec::new(),
+        }
+    }
+    
     /// USB 제어 요청 전송
     ///
     /// # Safety
@@ -30,6 +30,8 @@ pub mod xhci_trb;
 pub mod ehci;
 pub mod hid;
 
+use crate::drivers::base::{self, Device, DeviceInfo, DeviceMatch, Driver};
+
 pub use host_controller::{UsbHostController, UsbHostControllerType};
 pub use error::UsbError;
 pub use device::UsbDevice;
@@ -65,17 +67,43 @@ impl From<u8> for UsbClassCode {
     }
 }
 
+/// USB 호스트 컨트롤러 PCI 드라이버 (디바이스 모델)
+struct UsbHostDriver;
+
+impl Driver for UsbHostDriver {
+    fn name(&self) -> &'static str {
+        "usb-host"
+    }
+
+    fn match_table(&self) -> &'static [DeviceMatch] {
+        // Serial Bus Controller (0x0C) / USB (0x03), 컨트롤러 타입은 프로그래밍 인터페이스로 구분
+        &[DeviceMatch::PciClass { class: 0x0C, subclass: 0x03, prog_if: None }]
+    }
+
+    fn probe(&self, device: &Device) -> Result<(), &'static str> {
+        let DeviceInfo::Pci(pci_device) = device.info else {
+            return Err("not a PCI device");
+        };
+        unsafe { UsbManager::add_controller(device.id, pci_device) }.map_err(|_| "USB host controller init failed")
+    }
+}
+
 /// USB 전역 관리자 초기화
 ///
+/// 호스트 컨트롤러 드라이버를 디바이스 모델에 등록하며, 바인딩된 컨트롤러가
+/// 없으면 `DeviceNotFound`를 반환합니다.
+///
 /// # Safety
-/// 메모리 관리 및 PCI 버스가 초기화된 후에 호출되어야 합니다.
+/// 메모리 관리와 `drivers::base::init`이 끝난 후에 호출되어야 합니다.
 pub unsafe fn init() -> Result<(), UsbError> {
     crate::log_info!("Initializing USB subsystem...");
     
-    // USB 매니저 초기화
-    UsbManager::init()?;
+    base::register_driver(&UsbHostDriver);
+    if !self::core::is_initialized() {
+        crate::log_warn!("No USB host controller found");
+        return Err(UsbError::DeviceNotFound);
+    }
     
     crate::log_info!("USB subsystem initialized");
     Ok(())
 }
-
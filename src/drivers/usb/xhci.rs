@@ -7,12 +7,16 @@
 //! - Intel xHCI Architecture Overview
 
 use crate::drivers::pci::PciDevice;
+use crate::drivers::pci_msi::{self, MsiVectors};
 use crate::drivers::usb::error::UsbError;
 use crate::drivers::usb::host_controller::{UsbHostController, UsbHostControllerType};
 use crate::drivers::usb::request::UsbControlRequest;
 use crate::drivers::usb::xhci_trb::Trb;
 use crate::memory::{allocate_frame, paging::get_physical_memory_offset};
+use alloc::vec::Vec;
 use core::ptr::{read_volatile, write_volatile};
+use core::sync::atomic::{AtomicU64, Ordering};
+use x86_64::instructions::interrupts;
 use x86_64::structures::paging::{Page, PhysFrame, Size4KiB};
 use x86_64::PhysAddr;
 
@@ -35,6 +39,7 @@ const XHCI_CONFIG: usize = 0x38;
 const XHCI_PORTSC: usize = 0x400; // Port Status and Control (포트별 0x10 오프셋)
 
 /// Interrupter 레지스터 오프셋 (Runtime Register Space)
+const XHCI_IR0_OFFSET: u64 = 0x20; // Interrupter Register Set 0 (런타임 레지스터 기준)
 const XHCI_IMAN: usize = 0x00; // Interrupter Management (Interrupter 0)
 const XHCI_IMOD: usize = 0x04; // Interrupter Moderation
 const XHCI_ERSTSZ: usize = 0x08; // Event Ring Segment Table Size
@@ -44,6 +49,7 @@ const XHCI_ERDP: usize = 0x18; // Event Ring Dequeue Pointer
 /// xHCI 명령 코드
 const XHCI_CMD_RUN: u32 = 1 << 0;
 const XHCI_CMD_HCRST: u32 = 1 << 1;
+const XHCI_CMD_INTE: u32 = 1 << 2; // Interrupter Enable
 const XHCI_CMD_RS: u32 = 1 << 0;
 
 /// xHCI 상태 비트
@@ -56,6 +62,62 @@ const XHCI_PORTSC_CCS: u32 = 1 << 0; // Current Connect Status
 const XHCI_PORTSC_PED: u32 = 1 << 1; // Port Enabled/Disabled
 const XHCI_PORTSC_PR: u32 = 1 << 4; // Port Reset
 const XHCI_PORTSC_PLS: u32 = 0xF << 5; // Port Link State
+const XHCI_PORTSC_LWS: u32 = 1 << 16; // Port Link State Write Strobe
+const XHCI_PORTSC_CSC: u32 = 1 << 17; // Connect Status Change
+/// 변경 비트 (CSC, PEC, WRC, OCC, PRC, PLC, CEC; 1을 써서 클리어)
+const XHCI_PORTSC_CHANGE_MASK: u32 = 0x7F << 17;
+
+/// Port Status Change Event TRB 타입
+const XHCI_TRB_PORT_STATUS_CHANGE: u8 = 34;
+
+/// 인터럽트 모드에서 이벤트를 기다리는 최대 시간
+const XHCI_EVENT_TIMEOUT_MS: u64 = 1000;
+
+/// Event Ring 인터럽트 수 (MSI/MSI-X 핸들러가 증가)
+static EVENT_IRQS: AtomicU64 = AtomicU64::new(0);
+
+/// USBSTS 레지스터 가상 주소 (인터럽트 핸들러에서 EINT 클리어용)
+static USBSTS_ADDR: AtomicU64 = AtomicU64::new(0);
+
+/// Interrupter 0 MSI/MSI-X 핸들러
+///
+/// MSI 모드에서는 IMAN.IP가 자동으로 클리어되므로 USBSTS.EINT만 클리어하고
+/// 대기 중인 쪽을 깨웁니다. 이벤트 자체는 Event Ring을 읽는 쪽에서 처리합니다.
+fn event_irq(_vector: u32) {
+    let usbsts = USBSTS_ADDR.load(Ordering::Acquire);
+    if usbsts != 0 {
+        unsafe { write_volatile(usbsts as *mut u32, XHCI_STS_EINT) };
+    }
+    EVENT_IRQS.fetch_add(1, Ordering::Release);
+}
+
+/// 다음 이벤트까지 대기
+///
+/// 인터럽트 모드에서는 `seen` 이후 Event Ring 인터럽트가 없고 링에도 새 이벤트가
+/// 없으면 CPU를 재우고, 폴링 모드에서는 잠깐 스핀합니다. 인터럽트 모드에서
+/// `deadline_ms`가 지나면 false.
+///
+/// 마감 시각, 인터럽트 수, 링 확인은 모두 인터럽트를 끈 채로 하므로 확인과
+/// `hlt` 사이에 도착한 완료를 놓치지 않습니다.
+fn wait_for_event(interrupt_driven: bool, seen: u64, deadline_ms: u64, ring: &EventRing) -> bool {
+    if !interrupt_driven || !interrupts::are_enabled() {
+        for _ in 0..10 {
+            core::hint::spin_loop();
+        }
+        return true;
+    }
+    interrupts::disable();
+    if crate::drivers::timer::get_milliseconds() >= deadline_ms {
+        interrupts::enable();
+        return false;
+    }
+    if EVENT_IRQS.load(Ordering::Acquire) == seen && !unsafe { ring.has_event() } {
+        interrupts::enable_and_hlt();
+    } else {
+        interrupts::enable();
+    }
+    true
+}
 
 /// Command Ring 구조
 struct CommandRing {
@@ -132,6 +194,12 @@ impl EventRing {
         Some(trb)
     }
     
+    /// 소비하지 않은 이벤트가 있는지 확인 (Dequeue Pointer는 그대로)
+    unsafe fn has_event(&self) -> bool {
+        let trb = core::ptr::read_volatile(self.buffer.add(self.dequeue_ptr));
+        (trb.control & 1 != 0) == self.cycle_state
+    }
+    
     /// Dequeue Pointer 가져오기 (물리 주소)
     fn dequeue_pointer(&self) -> PhysAddr {
         let offset = self.dequeue_ptr * core::mem::size_of::<Trb>();
@@ -226,6 +294,10 @@ pub struct XhciController {
     event_ring: Option<EventRing>,
     /// Interrupter Register Base (Interrupter 0)
     interrupter_base: u64,
+    /// Interrupter 0 MSI/MSI-X 벡터 (`None`이면 Event Ring 폴링)
+    msi: Option<MsiVectors>,
+    /// 연결 상태가 바뀐 포트 (Port Status Change Event, `take_port_changes`가 비움)
+    port_changes: Vec<u8>,
 }
 
 impl XhciController {
@@ -237,12 +309,13 @@ impl XhciController {
             command_ring: None,
             event_ring: None,
             interrupter_base: 0,
+            msi: None,
             cap_length: 0,
             op_base: 0,
             runtime_base: 0,
             initialized: false,
             port_count: 0,
-            command_ring: None,
+            port_changes: Vec::new(),
         }
     }
     
@@ -386,7 +459,7 @@ impl XhciController {
             core::ptr::write_volatile(erst_virt.add(2), 0); // Reserved
             
             // Interrupter 0 레지스터 설정
-            self.interrupter_base = self.runtime_base; // Interrupter 0
+            self.interrupter_base = self.runtime_base + XHCI_IR0_OFFSET; // Interrupter 0
             
             // ERSTBA (Event Ring Segment Table Base Address)
             let erstba_low = (erst_phys.as_u64() & 0xFFFF_FFFF) as u32;
@@ -434,9 +507,21 @@ impl XhciController {
             crate::log_info!("xHCI: Command Ring initialized at {:#016X}", ring_phys.as_u64());
         }
         
-        // 5. 컨트롤러 시작 (Run)
+        // 5. Interrupter 0을 MSI-X/MSI 벡터에 연결 (불가능하면 Event Ring 폴링)
+        USBSTS_ADDR.store(self.op_base + XHCI_USBSTS as u64, Ordering::Release);
+        match pci_msi::enable_vectors(&self.pci_device, &[event_irq]) {
+            Ok(msi) => self.msi = Some(msi),
+            Err(e) => {
+                crate::log_info!("xHCI: MSI/MSI-X unavailable ({}), polling event ring", e);
+            }
+        }
+
+        // 6. 컨트롤러 시작 (Run)
         let mut usbcmd = self.read_op(XHCI_USBCMD);
         usbcmd |= XHCI_CMD_RUN;
+        if self.msi.is_some() {
+            usbcmd |= XHCI_CMD_INTE;
+        }
         self.write_op(XHCI_USBCMD, usbcmd);
         
         // Running 상태 대기
@@ -448,7 +533,7 @@ impl XhciController {
             }
         }
         
-        // 6. 포트 상태 확인
+        // 7. 포트 상태 확인
         crate::log_info!("xHCI: Checking {} ports...", self.port_count);
         for port in 1..=self.port_count {
             let portsc = self.read_portsc(port);
@@ -559,11 +644,14 @@ impl XhciController {
         self.write_u32(self.runtime_base as usize + doorbell_offset, doorbell_ep);
 
         // Event Ring에서 Transfer Event를 기다림
+        let interrupt_driven = self.msi.is_some();
+        let deadline = crate::drivers::timer::get_milliseconds() + XHCI_EVENT_TIMEOUT_MS;
         let event_ring = self.event_ring.as_mut().ok_or(UsbError::DeviceError)?;
         let mut timeout = 10000u32;
         let mut completion_received = false;
         while !completion_received && timeout > 0 {
             timeout -= 1;
+            let seen = EVENT_IRQS.load(Ordering::Acquire);
 
             if let Some(event_trb) = event_ring.read_event() {
                 let trb_type = ((event_trb.control >> 4) & 0x3F) as u8;
@@ -584,10 +672,9 @@ impl XhciController {
                 let erdp_high = ((erdp.as_u64() >> 32) & 0xFFFF_FFFF) as u32;
                 self.write_u32(self.interrupter_base as usize + XHCI_ERDP, erdp_low);
                 self.write_u32(self.interrupter_base as usize + XHCI_ERDP + 4, erdp_high);
+            } else if !wait_for_event(interrupt_driven, seen, deadline, event_ring) {
+                break;
             }
-
-            // 짧은 지연
-            for _ in 0..10 { core::hint::spin_loop(); }
         }
 
         if !completion_received {
@@ -668,10 +755,13 @@ impl UsbHostController for XhciController {
         self.write_u32(self.runtime_base as usize + doorbell_offset, ep_num);
 
         // Event Ring 완료 대기
+        let interrupt_driven = self.msi.is_some();
+        let deadline = crate::drivers::timer::get_milliseconds() + XHCI_EVENT_TIMEOUT_MS;
         let event_ring = self.event_ring.as_mut().ok_or(UsbError::DeviceError)?;
         let mut timeout = 10000;
         while timeout > 0 {
             timeout -= 1;
+            let seen = EVENT_IRQS.load(Ordering::Acquire);
             if let Some(event_trb) = event_ring.read_event() {
                 let trb_type = ((event_trb.control >> 4) & 0x3F) as u8;
                 if trb_type == 32 { // Transfer Event
@@ -695,8 +785,9 @@ impl UsbHostController for XhciController {
                 let erdp_high = ((erdp.as_u64() >> 32) & 0xFFFF_FFFF) as u32;
                 self.write_u32(self.interrupter_base as usize + XHCI_ERDP, erdp_low);
                 self.write_u32(self.interrupter_base as usize + XHCI_ERDP + 4, erdp_high);
+            } else if !wait_for_event(interrupt_driven, seen, deadline, event_ring) {
+                break;
             }
-            core::hint::spin_loop();
         }
 
         Err(UsbError::Timeout)
@@ -764,12 +855,15 @@ impl UsbHostController for XhciController {
         self.write_u32(self.runtime_base as usize + doorbell_offset, doorbell_value);
         
         // Event Ring에서 완료 이벤트 대기
+        let interrupt_driven = self.msi.is_some();
+        let deadline = crate::drivers::timer::get_milliseconds() + XHCI_EVENT_TIMEOUT_MS;
         let event_ring = self.event_ring.as_mut().ok_or(UsbError::DeviceError)?;
         let mut timeout = 10000;
         let mut completion_received = false;
         
         while !completion_received && timeout > 0 {
             timeout -= 1;
+            let seen = EVENT_IRQS.load(Ordering::Acquire);
             
             // Event Ring에서 이벤트 읽기
             if let Some(event_trb) = event_ring.read_event() {
@@ -800,11 +894,8 @@ impl UsbHostController for XhciController {
                 let erdp_high = ((erdp.as_u64() >> 32) & 0xFFFF_FFFF) as u32;
                 self.write_u32(self.interrupter_base as usize + XHCI_ERDP, erdp_low);
                 self.write_u32(self.interrupter_base as usize + XHCI_ERDP + 4, erdp_high);
-            }
-            
-            // 짧은 지연
-            for _ in 0..10 {
-                core::hint::spin_loop();
+            } else if !wait_for_event(interrupt_driven, seen, deadline, event_ring) {
+                break;
             }
         }
         
@@ -816,7 +907,31 @@ impl UsbHostController for XhciController {
         Ok(())
     }
     
+    /// 포트 변경 비트 클리어
+    ///
+    /// PED(쓰면 포트가 꺼짐)와 PR, LWS는 0으로 쓰고 변경 비트만 1로 써서 클리어합니다.
+    ///
+    /// # Returns
+    /// 클리어 전 PORTSC 값
+    unsafe fn ack_port_change(&self, port: u8) -> u32 {
+        let portsc = self.read_portsc(port);
+        let preserved = portsc & !(XHCI_PORTSC_PED | XHCI_PORTSC_PR | XHCI_PORTSC_LWS | XHCI_PORTSC_CHANGE_MASK);
+        self.write_portsc(port, preserved | (portsc & XHCI_PORTSC_CHANGE_MASK));
+        portsc
+    }
+
+    /// 연결 상태가 바뀐 포트 목록 가져오기 (Event Ring을 먼저 처리)
+    pub unsafe fn take_port_changes(&mut self) -> Vec<u8> {
+        if self.initialized {
+            let _ = self.process_events();
+        }
+        core::mem::take(&mut self.port_changes)
+    }
+    
     /// Event Ring에서 이벤트 처리 (주기적 호출)
+    ///
+    /// 인터럽트 모드에서도 링을 직접 확인합니다. 다른 대기 루프가 인터럽트를
+    /// 먼저 소비했더라도 링에 남은 이벤트는 여기서 처리됩니다.
     pub unsafe fn process_events(&mut self) -> Result<(), UsbError> {
         let event_ring = self.event_ring.as_mut().ok_or(UsbError::DeviceError)?;
         
@@ -835,10 +950,12 @@ impl UsbHostController for XhciController {
                     let slot_id = ((event_trb.parameter2 >> 24) & 0xFF) as u8;
                     crate::log_debug!("xHCI: Command Completion Event, slot: {}", slot_id);
                 }
-                34 => {
-                    // Port Status Change Event
+                XHCI_TRB_PORT_STATUS_CHANGE => {
                     let port_id = ((event_trb.parameter0 >> 24) & 0xFF) as u8;
                     crate::log_info!("xHCI: Port {} status changed", port_id);
+                    if self.ack_port_change(port_id) & XHCI_PORTSC_CSC != 0 && !self.port_changes.contains(&port_id) {
+                        self.port_changes.push(port_id);
+                    }
                 }
                 _ => {
                     crate::log_debug!("xHCI: Unknown event type: {}", trb_type);
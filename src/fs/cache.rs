@@ -1,168 +1,242 @@
 //! 파일시스템 캐시
 //!
-//! 파일 시스템 성능 향상을 위한 블록 캐시 구현
+//! 파일 내용을 4 KiB 페이지 단위로 캐시하는 페이지 캐시입니다. 키는 (파일시스템 인스턴스,
+//! inode, 페이지 번호)이며 각 파일시스템의 `File::read`가 이 캐시를 거칩니다. 페이지
+//! 폴트로 파일 페이지를 채우는 메모리 매핑도 같은 경로(`read_page`)를 쓸 수 있습니다.
+//!
+//! 쓰기는 파일시스템이 디스크에 바로 기록한 뒤 `update`로 캐시된 페이지를 고칩니다
+//! (write-through). 캐시 페이지는 항상 디스크와 같으므로 언제든 버릴 수 있습니다.
+//!
+//! 파일마다 마지막으로 읽은 위치를 기억해 순차 읽기를 감지하면 미리 읽기(readahead)
+//! 창을 `READAHEAD_INITIAL`부터 `READAHEAD_MAX` 페이지까지 두 배씩 넓히고, 임의 접근이면
+//! 창을 닫습니다. 창의 절반 이상을 소비하면 다음 창을 미리 읽습니다.
+//!
+//! 캐시 크기는 `MAX_CACHE_PAGES`와 힙 여유(`PRESSURE_RESERVE`를 남긴 나머지) 중 작은
+//! 쪽으로 제한되며, 새 페이지를 할당하기 전에 넘치는 만큼 가장 오래전에 쓴 페이지부터
+//! 버립니다. 다른 힙 할당이 실패하면 할당자가 메모리 복구(`shrink`) 뒤 한 번 다시 시도하고,
+//! 1초 주기 하우스키핑이 `reclaim`으로 캐시를 줄입니다.
 
-use alloc::vec::Vec;
+use alloc::boxed::Box;
 use alloc::collections::BTreeMap;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::sync::atomic::{AtomicU32, Ordering};
 use spin::Mutex;
+use super::vfs::FsResult;
+
+/// 페이지 크기 (바이트)
+pub const PAGE_SIZE: usize = 4096;
+
+/// 최대 캐시 페이지 수 (16 MiB)
+pub const MAX_CACHE_PAGES: usize = 4096;
 
-/// 캐시 블록 크기 (512바이트 - 1섹터)
-pub const BLOCK_SIZE: usize = 512;
-
-/// 최대 캐시 블록 수
-pub const MAX_CACHE_BLOCKS: usize = 256;
-
-/// 캐시 블록
-#[derive(Clone)]
-pub struct CacheBlock {
-    /// 블록 번호
-    pub block_num: u64,
-    /// 블록 데이터
-    pub data: [u8; BLOCK_SIZE],
-    /// 수정 여부 (dirty)
-    pub dirty: bool,
-    /// 마지막 접근 시간 (틱)
-    pub last_access: u64,
+/// 캐시가 남겨 둘 힙 여유 (이보다 여유가 적으면 캐시를 줄임, 힙은 최대 2 MiB)
+const PRESSURE_RESERVE: usize = 256 * 1024;
+
+/// 순차 읽기를 처음 감지했을 때의 미리 읽기 창 (페이지)
+const READAHEAD_INITIAL: u64 = 4;
+
+/// 미리 읽기 창 최대 크기 (페이지, 128 KiB)
+const READAHEAD_MAX: u64 = 32;
+
+/// 캐시된 파일 (파일시스템 인스턴스, inode)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct FileKey {
+    /// `new_cache_id`로 받은 파일시스템 인스턴스 번호
+    pub fs: u32,
+    /// 파일시스템 안에서 파일을 가리키는 번호 (inode, FAT32는 첫 클러스터)
+    pub ino: u64,
 }
 
-impl CacheBlock {
-    /// 새 캐시 블록 생성
-    pub fn new(block_num: u64) -> Self {
-        Self {
-            block_num,
-            data: [0; BLOCK_SIZE],
-            dirty: false,
-            last_access: 0,
+/// 캐시된 페이지
+struct Page {
+    data: Box<[u8]>,
+    /// 마지막 접근 시각 (LRU 색인 키)
+    last_access: u64,
+}
+
+/// 파일별 미리 읽기 상태
+#[derive(Debug, Clone, Copy, Default)]
+struct Readahead {
+    /// 순차 읽기라면 다음에 읽을 페이지
+    next: u64,
+    /// 현재 창 크기 (0이면 임의 접근)
+    window: u64,
+    /// 지금까지 미리 읽은 범위의 끝 (배타적)
+    end: u64,
+}
+
+impl Readahead {
+    /// `first..=last` 페이지 접근을 기록하고 미리 읽을 범위 반환
+    ///
+    /// 파일 처음부터 읽거나 직전 읽기에 바로 이어 읽으면 순차 읽기로 봅니다.
+    fn on_access(&mut self, first: u64, last: u64, file_pages: u64) -> core::ops::Range<u64> {
+        if first != self.next {
+            self.window = 0;
+            self.end = 0;
+            if first != 0 {
+                // 임의 접근
+                self.next = last + 1;
+                return 0..0;
+            }
         }
-    }
-    
-    /// 데이터 읽기
-    pub fn read(&self) -> &[u8] {
-        &self.data
-    }
-    
-    /// 데이터 쓰기
-    pub fn write(&mut self, data: &[u8]) {
-        let len = data.len().min(BLOCK_SIZE);
-        self.data[..len].copy_from_slice(&data[..len]);
-        self.dirty = true;
-    }
-    
-    /// 접근 시간 업데이트
-    pub fn touch(&mut self, time: u64) {
-        self.last_access = time;
-    }
-    
-    /// 수정 여부 확인
-    pub fn is_dirty(&self) -> bool {
-        self.dirty
-    }
-    
-    /// 수정 플래그 초기화
-    pub fn clear_dirty(&mut self) {
-        self.dirty = false;
+        self.next = last + 1;
+
+        if self.window == 0 {
+            self.window = READAHEAD_INITIAL;
+        } else if last + self.window / 2 < self.end {
+            // 미리 읽은 창이 절반 이상 남음
+            return 0..0;
+        } else {
+            self.window = (self.window * 2).min(READAHEAD_MAX);
+        }
+        let start = self.end.max(last + 1);
+        let end = (last + 1 + self.window).min(file_pages);
+        if start >= end {
+            return 0..0;
+        }
+        self.end = end;
+        start..end
     }
 }
 
-/// 블록 캐시
-pub struct BlockCache {
-    /// 캐시된 블록들 (블록 번호 -> 캐시 블록)
-    blocks: BTreeMap<u64, CacheBlock>,
-    /// 현재 시간 (틱)
+/// 페이지 캐시
+pub struct PageCache {
+    /// 캐시된 페이지들 ((파일, 페이지 번호) -> 페이지)
+    pages: BTreeMap<(FileKey, u64), Page>,
+    /// 접근 시각 -> 페이지 (가장 오래된 것부터 버림)
+    lru: BTreeMap<u64, (FileKey, u64)>,
+    /// 파일별 미리 읽기 상태
+    readahead: BTreeMap<FileKey, Readahead>,
+    /// 현재 시간 (접근마다 증가)
     current_time: u64,
     /// 히트 수
     hits: usize,
     /// 미스 수
     misses: usize,
+    /// 미리 읽은 페이지 수
+    readahead_pages: usize,
+    /// 메모리 압박으로 버린 페이지 수
+    evictions: usize,
 }
 
-impl BlockCache {
-    /// 새 블록 캐시 생성
-    pub fn new() -> Self {
+impl Default for PageCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PageCache {
+    /// 새 페이지 캐시 생성
+    pub const fn new() -> Self {
         Self {
-            blocks: BTreeMap::new(),
+            pages: BTreeMap::new(),
+            lru: BTreeMap::new(),
+            readahead: BTreeMap::new(),
             current_time: 0,
             hits: 0,
             misses: 0,
+            readahead_pages: 0,
+            evictions: 0,
         }
     }
-    
-    /// 블록 가져오기
-    ///
-    /// # Arguments
-    /// * `block_num` - 블록 번호
-    ///
-    /// # Returns
-    /// 캐시된 블록 (없으면 None)
-    pub fn get(&mut self, block_num: u64) -> Option<&mut CacheBlock> {
+
+    /// 페이지를 최근 사용으로 표시
+    fn touch(&mut self, key: (FileKey, u64)) {
         self.current_time += 1;
-        
-        if let Some(block) = self.blocks.get_mut(&block_num) {
-            block.touch(self.current_time);
-            self.hits += 1;
-            Some(block)
-        } else {
-            self.misses += 1;
-            None
+        if let Some(page) = self.pages.get_mut(&key) {
+            self.lru.remove(&page.last_access);
+            page.last_access = self.current_time;
+            self.lru.insert(self.current_time, key);
         }
     }
-    
-    /// 블록 추가/업데이트
-    ///
-    /// # Arguments
-    /// * `block` - 추가할 블록
-    pub fn put(&mut self, mut block: CacheBlock) {
+
+    /// 페이지 추가 (이미 있으면 그대로 둠, 자리는 호출자가 `make_room`으로 미리 확보)
+    fn insert(&mut self, key: (FileKey, u64), data: Box<[u8]>) {
+        if self.pages.contains_key(&key) {
+            return;
+        }
         self.current_time += 1;
-        block.touch(self.current_time);
-        
-        // 캐시가 가득 찬 경우 가장 오래된 블록 제거 (LRU)
-        if self.blocks.len() >= MAX_CACHE_BLOCKS {
-            self.evict_lru();
+        self.lru.insert(self.current_time, key);
+        self.pages.insert(key, Page { data, last_access: self.current_time });
+    }
+
+    /// 페이지 하나 제거
+    fn remove(&mut self, key: &(FileKey, u64)) {
+        if let Some(page) = self.pages.remove(key) {
+            self.lru.remove(&page.last_access);
         }
-        
-        self.blocks.insert(block.block_num, block);
     }
-    
-    /// LRU (Least Recently Used) 블록 제거
-    fn evict_lru(&mut self) {
-        if let Some((&block_num, _)) = self.blocks.iter()
-            .min_by_key(|(_, block)| block.last_access)
-        {
-            self.blocks.remove(&block_num);
+
+    /// 가장 오래된 페이지부터 `count`개 버림
+    fn evict(&mut self, count: usize) -> usize {
+        let mut evicted = 0;
+        while evicted < count {
+            let Some((_, key)) = self.lru.pop_first() else { break };
+            self.pages.remove(&key);
+            evicted += 1;
         }
+        self.evictions += evicted;
+        evicted
     }
-    
-    /// 모든 dirty 블록 가져오기
+
+    /// 페이지 `incoming`개를 더 넣을 수 있게 캐시 크기 조정
     ///
-    /// # Returns
-    /// dirty 블록 번호 리스트
-    pub fn get_dirty_blocks(&self) -> Vec<u64> {
-        self.blocks.iter()
-            .filter(|(_, block)| block.is_dirty())
-            .map(|(&block_num, _)| block_num)
-            .collect()
+    /// 힙 여유가 `PRESSURE_RESERVE`보다 적으면 그만큼 캐시를 줄입니다.
+    fn make_room(&mut self, incoming: usize) {
+        let spare = crate::memory::heap::heap_free().saturating_sub(PRESSURE_RESERVE) / PAGE_SIZE;
+        let budget = MAX_CACHE_PAGES.min(self.pages.len() + spare);
+        let wanted = self.pages.len() + incoming;
+        if wanted > budget {
+            self.evict(wanted - budget);
+        }
+    }
+
+    /// 파일의 페이지를 모두 버림
+    fn invalidate(&mut self, file: FileKey) {
+        let keys: Vec<(FileKey, u64)> = self.pages
+            .range((file, 0)..=(file, u64::MAX))
+            .map(|(&key, _)| key)
+            .collect();
+        for key in &keys {
+            self.remove(key);
+        }
+        self.readahead.remove(&file);
     }
-    
-    /// 특정 블록을 clean으로 표시
-    pub fn mark_clean(&mut self, block_num: u64) {
-        if let Some(block) = self.blocks.get_mut(&block_num) {
-            block.clear_dirty();
+
+    /// 파일시스템 인스턴스의 페이지를 모두 버림
+    fn invalidate_fs(&mut self, fs: u32) {
+        let mut files: Vec<FileKey> = self.pages.keys()
+            .map(|&(file, _)| file)
+            .chain(self.readahead.keys().copied())
+            .filter(|file| file.fs == fs)
+            .collect();
+        files.sort();
+        files.dedup();
+        for file in files {
+            self.invalidate(file);
         }
     }
-    
+
     /// 캐시 초기화
     pub fn clear(&mut self) {
-        self.blocks.clear();
+        self.pages.clear();
+        self.lru.clear();
+        self.readahead.clear();
         self.hits = 0;
         self.misses = 0;
+        self.readahead_pages = 0;
+        self.evictions = 0;
     }
-    
+
     /// 캐시 통계
     pub fn stats(&self) -> CacheStats {
         CacheStats {
-            size: self.blocks.len(),
+            size: self.pages.len(),
             hits: self.hits,
             misses: self.misses,
+            readahead: self.readahead_pages,
+            evictions: self.evictions,
             hit_rate: if self.hits + self.misses > 0 {
                 (self.hits as f64) / ((self.hits + self.misses) as f64)
             } else {
@@ -175,67 +249,205 @@ impl BlockCache {
 /// 캐시 통계 정보
 #[derive(Debug, Clone)]
 pub struct CacheStats {
-    /// 캐시된 블록 수
+    /// 캐시된 페이지 수
     pub size: usize,
     /// 히트 수
     pub hits: usize,
     /// 미스 수
     pub misses: usize,
+    /// 미리 읽은 페이지 수
+    pub readahead: usize,
+    /// 메모리 압박으로 버린 페이지 수
+    pub evictions: usize,
     /// 히트율
     pub hit_rate: f64,
 }
 
 impl core::fmt::Display for CacheStats {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        write!(f, "Cache: {} blocks, {} hits, {} misses, {:.2}% hit rate",
-               self.size, self.hits, self.misses, self.hit_rate * 100.0)
+        write!(f, "Cache: {} pages, {} hits, {} misses, {:.2}% hit rate, {} read ahead, {} evicted",
+               self.size, self.hits, self.misses, self.hit_rate * 100.0, self.readahead, self.evictions)
     }
 }
 
-/// 전역 블록 캐시
-static GLOBAL_CACHE: Mutex<Option<BlockCache>> = Mutex::new(None);
+/// 전역 페이지 캐시
+static PAGE_CACHE: Mutex<PageCache> = Mutex::new(PageCache::new());
+
+/// 다음 파일시스템 인스턴스 번호
+static NEXT_CACHE_ID: AtomicU32 = AtomicU32::new(1);
 
-/// 블록 캐시 초기화
-pub fn init() {
-    let mut cache = GLOBAL_CACHE.lock();
-    *cache = Some(BlockCache::new());
-    crate::log_info!("Block cache initialized");
+/// 파일시스템 인스턴스용 캐시 번호 할당 (인스턴스마다 한 번)
+pub fn new_cache_id() -> u32 {
+    NEXT_CACHE_ID.fetch_add(1, Ordering::Relaxed)
 }
 
-/// 캐시에서 블록 가져오기
-pub fn get_cached_block(block_num: u64) -> Option<[u8; BLOCK_SIZE]> {
-    let mut cache = GLOBAL_CACHE.lock();
-    if let Some(ref mut c) = *cache {
-        c.get(block_num).map(|block| block.data)
-    } else {
-        None
+/// 캐시를 거쳐 파일 읽기
+///
+/// 빠진 페이지와 미리 읽을 페이지마다 `read_page(페이지 번호, 버퍼)`를 불러 캐시에 넣을
+/// 버퍼를 바로 채웁니다. 버퍼는 0으로 초기화된 `PAGE_SIZE` 바이트이며, 페이지 번호
+/// 오름차순으로 불리므로 이어 읽기 위치를 함수에 기억해 둘 수 있습니다.
+///
+/// # Arguments
+/// * `file` - 캐시 키
+/// * `size` - 현재 파일 크기
+/// * `offset` - 읽기 시작 오프셋
+/// * `buf` - 읽은 데이터를 저장할 버퍼
+/// * `read_page` - 디스크에서 페이지 하나를 읽는 함수
+///
+/// # Returns
+/// 읽은 바이트 수
+pub fn read<F>(file: FileKey, size: u64, offset: u64, buf: &mut [u8], mut read_page: F) -> FsResult<usize>
+where
+    F: FnMut(u64, &mut [u8]) -> FsResult<()>,
+{
+    if offset >= size || buf.is_empty() {
+        return Ok(0);
+    }
+    let len = buf.len().min((size - offset) as usize);
+    let first = offset / PAGE_SIZE as u64;
+    let last = (offset + len as u64 - 1) / PAGE_SIZE as u64;
+    let file_pages = size.div_ceil(PAGE_SIZE as u64);
+
+    // 디스크에서 읽을 페이지 (요청 범위의 빠진 페이지 + 미리 읽기, 오름차순)
+    let mut missing: Vec<u64> = Vec::new();
+    {
+        let mut cache = PAGE_CACHE.lock();
+        let ahead = cache.readahead.entry(file).or_default().on_access(first, last, file_pages);
+        for index in first..=last {
+            if cache.pages.contains_key(&(file, index)) {
+                cache.hits += 1;
+            } else {
+                cache.misses += 1;
+                missing.push(index);
+            }
+        }
+        for index in ahead {
+            if !cache.pages.contains_key(&(file, index)) {
+                cache.readahead_pages += 1;
+                missing.push(index);
+            }
+        }
+        // 페이지를 할당하기 전에 자리를 만듦
+        cache.make_room(missing.len());
+    }
+
+    // 캐시에 넣을 버퍼에 바로 읽음 (디스크 I/O 동안은 캐시 락을 잡지 않음)
+    let mut loaded: Vec<(u64, Box<[u8]>)> = Vec::with_capacity(missing.len());
+    for index in missing {
+        let mut data = vec![0u8; PAGE_SIZE].into_boxed_slice();
+        match read_page(index, &mut data) {
+            Ok(()) => loaded.push((index, data)),
+            // 미리 읽기 실패는 무시하고 나머지 미리 읽기도 그만둠
+            Err(e) if index > last => {
+                crate::log_debug!("page cache: readahead at page {} failed: {:?}", index, e);
+                break;
+            }
+            Err(e) => return Err(e),
+        }
+    }
+
+    let mut cache = PAGE_CACHE.lock();
+    for (index, data) in loaded {
+        cache.insert((file, index), data);
+    }
+
+    let mut done = 0;
+    while done < len {
+        let pos = offset + done as u64;
+        let index = pos / PAGE_SIZE as u64;
+        let in_page = (pos % PAGE_SIZE as u64) as usize;
+        let chunk = (PAGE_SIZE - in_page).min(len - done);
+        cache.touch((file, index));
+        match cache.pages.get(&(file, index)) {
+            Some(page) => buf[done..done + chunk].copy_from_slice(&page.data[in_page..in_page + chunk]),
+            // 방금 넣은 페이지가 메모리 압박으로 바로 밀려남: 캐시 없이 읽음
+            None => {
+                drop(cache);
+                let mut page = vec![0u8; PAGE_SIZE];
+                read_page(index, &mut page)?;
+                buf[done..done + chunk].copy_from_slice(&page[in_page..in_page + chunk]);
+                cache = PAGE_CACHE.lock();
+            }
+        }
+        done += chunk;
+    }
+    Ok(len)
+}
+
+/// 페이지 하나 읽기 (메모리 매핑 파일의 페이지 폴트 처리용)
+///
+/// `dest`는 `PAGE_SIZE` 바이트이며 파일 끝 뒤는 0으로 채웁니다.
+pub fn read_page<F>(file: FileKey, size: u64, index: u64, dest: &mut [u8], read_one: F) -> FsResult<()>
+where
+    F: FnMut(u64, &mut [u8]) -> FsResult<()>,
+{
+    let dest = &mut dest[..PAGE_SIZE];
+    dest.fill(0);
+    read(file, size, index * PAGE_SIZE as u64, dest, read_one).map(|_| ())
+}
+
+/// 디스크에 쓴 내용을 캐시된 페이지에 반영 (캐시에 없는 페이지는 건드리지 않음)
+///
+/// # Arguments
+/// * `file` - 캐시 키
+/// * `offset` - 쓴 위치
+/// * `data` - 쓴 데이터
+pub fn update(file: FileKey, offset: u64, data: &[u8]) {
+    let mut cache = PAGE_CACHE.lock();
+    let mut done = 0;
+    while done < data.len() {
+        let pos = offset + done as u64;
+        let index = pos / PAGE_SIZE as u64;
+        let in_page = (pos % PAGE_SIZE as u64) as usize;
+        let chunk = (PAGE_SIZE - in_page).min(data.len() - done);
+        if let Some(page) = cache.pages.get_mut(&(file, index)) {
+            page.data[in_page..in_page + chunk].copy_from_slice(&data[done..done + chunk]);
+        }
+        done += chunk;
     }
 }
 
-/// 캐시에 블록 추가
-pub fn cache_block(block_num: u64, data: &[u8]) {
-    let mut cache = GLOBAL_CACHE.lock();
-    if let Some(ref mut c) = *cache {
-        let mut block = CacheBlock::new(block_num);
-        block.write(data);
-        c.put(block);
+/// 파일의 캐시된 페이지를 모두 버림 (파일 삭제, inode 해제, 쓰기 실패 시)
+pub fn invalidate(file: FileKey) {
+    PAGE_CACHE.lock().invalidate(file);
+}
+
+/// 파일시스템 인스턴스의 캐시된 페이지를 모두 버림 (언마운트 시)
+pub fn invalidate_fs(fs: u32) {
+    PAGE_CACHE.lock().invalidate_fs(fs);
+}
+
+/// 메모리 압박 시 캐시 줄이기
+///
+/// 힙 할당 실패 처리 중에 불릴 수 있으므로 캐시 락을 기다리지 않습니다.
+///
+/// # Arguments
+/// * `pages` - 버릴 페이지 수
+///
+/// # Returns
+/// 실제로 버린 페이지 수
+pub fn shrink(pages: usize) -> usize {
+    match PAGE_CACHE.try_lock() {
+        Some(mut cache) => cache.evict(pages),
+        None => 0,
     }
 }
 
-/// dirty 블록 리스트 가져오기
-pub fn get_dirty_blocks() -> Vec<u64> {
-    let cache = GLOBAL_CACHE.lock();
-    if let Some(ref c) = *cache {
-        c.get_dirty_blocks()
-    } else {
-        Vec::new()
+/// 힙 여유가 `PRESSURE_RESERVE`보다 적으면 모자란 만큼 캐시를 줄임 (주기적 하우스키핑용)
+///
+/// # Returns
+/// 버린 페이지 수
+pub fn reclaim() -> usize {
+    let free = crate::memory::heap::heap_free();
+    if free >= PRESSURE_RESERVE {
+        return 0;
     }
+    shrink((PRESSURE_RESERVE - free).div_ceil(PAGE_SIZE))
 }
 
 /// 캐시 통계 가져오기
-pub fn get_cache_stats() -> Option<CacheStats> {
-    let cache = GLOBAL_CACHE.lock();
-    cache.as_ref().map(|c| c.stats())
+pub fn get_cache_stats() -> CacheStats {
+    PAGE_CACHE.lock().stats()
 }
 
 /// Writeback 정책 설정
@@ -262,57 +474,93 @@ pub fn get_writeback_policy() -> WritebackPolicy {
     *WRITEBACK_POLICY.lock()
 }
 
-/// Bursty flush 임계값
-const BURSTY_THRESHOLD: usize = 32; // 32개 이상 dirty 블록이면 flush
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-/// Dirty 블록 플러시 (정책에 따라)
-/// 
-/// # Arguments
-/// * `force` - 강제 플러시 (true면 즉시, false면 정책에 따라)
-/// * `write_fn` - 실제 쓰기 함수
-pub fn flush_dirty_blocks<F>(force: bool, write_fn: F) -> Result<usize, &'static str>
-where
-    F: Fn(u64, &[u8]) -> Result<(), &'static str>,
-{
-    let policy = get_writeback_policy();
-    let dirty_blocks = get_dirty_blocks();
-    
-    if dirty_blocks.is_empty() {
-        return Ok(0);
-    }
-    
-    // 정책에 따라 플러시 결정
-    let should_flush = match policy {
-        WritebackPolicy::Immediate => true,
-        WritebackPolicy::Periodic => force, // 주기적 호출 시에만
-        WritebackPolicy::Bursty => force || dirty_blocks.len() >= BURSTY_THRESHOLD,
-    };
-    
-    if !should_flush && !force {
-        return Ok(0);
+    #[test_case]
+    fn test_readahead_window() {
+        let mut ra = Readahead::default();
+        // 파일 처음부터 순차 읽기: 창을 열고, 절반을 소비할 때마다 두 배로 넓혀 다음 창을 읽음
+        assert_eq!(ra.on_access(0, 0, 1000), 1..5);
+        assert_eq!(ra.on_access(1, 1, 1000), 0..0);
+        assert_eq!(ra.on_access(2, 2, 1000), 0..0);
+        assert_eq!(ra.on_access(3, 3, 1000), 5..12);
+        assert_eq!(ra.on_access(4, 40, 1000), 41..57);
+        assert_eq!(ra.on_access(41, 60, 1000), 61..93);
+        assert_eq!(ra.on_access(61, 80, 1000), 93..113);
+        assert_eq!(ra.window, READAHEAD_MAX);
+        // 파일 끝에서 잘림
+        assert_eq!(ra.on_access(81, 990, 1000), 991..1000);
+        // 임의 접근이면 창을 닫고, 이어 읽으면 다시 엶
+        assert_eq!(ra.on_access(10, 10, 1000), 0..0);
+        assert_eq!(ra.window, 0);
+        assert_eq!(ra.on_access(11, 11, 1000), 12..16);
+        // 처음으로 되감기
+        assert_eq!(ra.on_access(0, 0, 1000), 1..5);
     }
-    
-    // Dirty 블록 쓰기
-    let mut cache = GLOBAL_CACHE.lock();
-    let mut flushed = 0;
-    
-    if let Some(ref mut c) = *cache {
-        for &block_num in &dirty_blocks {
-            if let Some(block) = c.get(block_num) {
-                match write_fn(block_num, block.read()) {
-                    Ok(()) => {
-                        block.clear_dirty();
-                        flushed += 1;
-                    }
-                    Err(e) => {
-                        return Err(e);
-                    }
-                }
-            }
+
+    /// 페이지 번호 + 1로 채운 페이지를 읽고 읽은 페이지 번호를 기록하는 함수
+    fn fill(reads: &mut Vec<u64>) -> impl FnMut(u64, &mut [u8]) -> FsResult<()> + '_ {
+        move |index, page| {
+            reads.push(index);
+            page.fill(index as u8 + 1);
+            Ok(())
         }
     }
-    
-    Ok(flushed)
-}
 
+    #[test_case]
+    fn test_page_cache_hit_update_invalidate() {
+        let file = FileKey { fs: new_cache_id(), ino: 1 };
+        let size = 3 * PAGE_SIZE as u64;
+        let mut reads = Vec::new();
+        let mut buf = [0u8; 8];
+
+        // 첫 읽기는 빠진 페이지와 미리 읽을 페이지를 디스크에서 읽음
+        assert_eq!(read(file, size, 0, &mut buf, fill(&mut reads)), Ok(8));
+        assert_eq!(buf, [1; 8]);
+        assert_eq!(reads, [0, 1, 2]);
+
+        // 다시 읽으면 캐시에서
+        reads.clear();
+        assert_eq!(read(file, size, PAGE_SIZE as u64 + 4, &mut buf, fill(&mut reads)), Ok(8));
+        assert_eq!(buf, [2; 8]);
+        assert!(reads.is_empty());
+
+        // 페이지 경계를 넘는 쓰기가 캐시된 두 페이지에 반영됨 (write-through)
+        update(file, PAGE_SIZE as u64 - 4, b"abcdefgh");
+        assert_eq!(read(file, size, PAGE_SIZE as u64 - 4, &mut buf, fill(&mut reads)), Ok(8));
+        assert_eq!(&buf, b"abcdefgh");
+        assert!(reads.is_empty());
 
+        // 캐시에 없는 페이지는 update가 만들지 않음
+        let other = FileKey { fs: file.fs, ino: 2 };
+        update(other, 0, b"abcdefgh");
+        assert!(!PAGE_CACHE.lock().pages.contains_key(&(other, 0)));
+
+        // 버린 뒤에는 다시 디스크에서 읽음
+        invalidate(file);
+        assert_eq!(read(file, size, PAGE_SIZE as u64 - 4, &mut buf, fill(&mut reads)), Ok(8));
+        assert_eq!(buf, [1, 1, 1, 1, 2, 2, 2, 2]);
+        assert_eq!(reads, [0, 1, 2]);
+        invalidate(file);
+    }
+
+    #[test_case]
+    fn test_page_cache_lru_order() {
+        let file = FileKey { fs: new_cache_id(), ino: 1 };
+        let mut cache = PageCache::new();
+        for index in 0..4 {
+            cache.insert((file, index), vec![0u8; PAGE_SIZE].into_boxed_slice());
+        }
+        // 1을 최근 사용으로 표시하면 0, 2, 3, 1 순으로 버림
+        cache.touch((file, 1));
+        assert_eq!(cache.evict(1), 1);
+        assert!(!cache.pages.contains_key(&(file, 0)));
+        assert_eq!(cache.evict(2), 2);
+        assert!(!cache.pages.contains_key(&(file, 2)) && !cache.pages.contains_key(&(file, 3)));
+        assert!(cache.pages.contains_key(&(file, 1)));
+        assert_eq!(cache.evict(5), 1);
+        assert_eq!(cache.stats().evictions, 4);
+    }
+}
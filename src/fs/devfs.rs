@@ -0,0 +1,855 @@
+//! devfs: 디바이스 노드 파일시스템
+//!
+//! 드라이버가 등록한 디바이스 노드를 `/dev` 아래 파일로 보여줍니다. 노드 목록은
+//! 전역 레지스트리에 있으므로 마운트 전후 언제든 등록할 수 있고, 모든 devfs 마운트가
+//! 같은 목록을 봅니다. 이름에 `/`가 있으면 (`input/event0`) 중간 디렉토리가 생깁니다.
+//!
+//! - 블록 디바이스 (`ata0`, `nvme0n1`과 파티션 `ata0p1` 등): 바이트 단위 읽기/쓰기,
+//!   섹터에 걸친 쓰기는 읽고-고쳐-쓰기로 처리합니다. 파일시스템 마운트도 이 이름을 씁니다.
+//!   디스크나 그 파티션이 마운트되어 있는 동안에는 파일시스템 캐시와 어긋나지 않도록
+//!   쓰기를 `Busy`로 거부합니다.
+//! - 문자 디바이스: `null`, `zero`, `random`, `ttyS0`, `fb0`, `input/event<N>`
+//!
+//! 모든 노드는 root 소유이며, 읽기와 쓰기는 노드의 권한 비트로 검사합니다. 노드를 새로
+//! 만들거나 지울 수는 없습니다 (`PermissionDenied`).
+
+use super::partition::{self, Partition, PartitionDevice};
+use super::path::Path;
+use super::vfs::{Directory, File, FileMetadata, FileMode, FileSystem, FileType, FsError, FsResult, FsStats, Offset};
+use crate::drivers::ata::BlockDevice;
+use crate::drivers::input::{self, InputDevice, InputEvent};
+use crate::security::user::{get_current_uid, is_user_in_group};
+use alloc::boxed::Box;
+use alloc::collections::BTreeMap;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::sync::Arc;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::sync::atomic::{AtomicBool, Ordering};
+use spin::Mutex;
+
+/// 디바이스 노드
+pub trait DeviceNode: Send + Sync {
+    /// `FileType::Block` 또는 `FileType::Character`
+    fn file_type(&self) -> FileType;
+
+    /// 권한 비트
+    fn mode(&self) -> u32 {
+        0o660
+    }
+
+    /// 파일 크기 (블록 디바이스는 용량, 문자 디바이스는 0)
+    fn size(&self) -> u64 {
+        0
+    }
+
+    /// 디바이스가 지금 있는지 (없으면 목록과 조회에서 빠짐)
+    fn present(&self) -> bool {
+        true
+    }
+
+    /// 파일 핸들 열기
+    fn open(&self) -> FsResult<Box<dyn File>>;
+
+    /// 파일시스템이 소유할 블록 디바이스 열기 (블록 디바이스만)
+    fn open_block(&self) -> Option<Box<dyn BlockDevice>> {
+        None
+    }
+}
+
+/// 등록된 디바이스 노드 (이름 → 노드)
+static NODES: Mutex<BTreeMap<String, Arc<dyn DeviceNode>>> = Mutex::new(BTreeMap::new());
+
+/// 디바이스 노드 등록
+///
+/// # Arguments
+/// * `name` - `/dev` 아래 경로 (예: "ttyS0", "input/event0")
+/// * `node` - 디바이스 노드
+pub fn register(name: &str, node: Arc<dyn DeviceNode>) -> FsResult<()> {
+    if name.is_empty() || name.starts_with('/') || name.ends_with('/') || name.contains("//") {
+        return Err(FsError::InvalidPath);
+    }
+    let mut nodes = NODES.lock();
+    if nodes.contains_key(name) {
+        return Err(FsError::AlreadyExists);
+    }
+    nodes.insert(name.to_string(), node);
+    Ok(())
+}
+
+/// 디바이스 노드 등록 해제 (열린 핸들은 계속 동작)
+pub fn unregister(name: &str) {
+    NODES.lock().remove(name);
+}
+
+/// 이름으로 노드 찾기 (없거나 디바이스가 빠졌으면 `NotFound`)
+fn lookup(name: &str) -> FsResult<Arc<dyn DeviceNode>> {
+    let node = NODES.lock().get(name).cloned().ok_or(FsError::NotFound)?;
+    if !node.present() {
+        return Err(FsError::NotFound);
+    }
+    Ok(node)
+}
+
+/// 블록 디바이스 노드를 파일시스템용으로 열기
+///
+/// # Arguments
+/// * `name` - 노드 이름 (예: "ata0", "nvme0n1p2")
+pub fn open_block(name: &str) -> FsResult<Box<dyn BlockDevice>> {
+    lookup(name)?.open_block().ok_or(FsError::NotFound)
+}
+
+/// 블록 디바이스 노드 (디스크 전체 또는 파티션 하나)
+struct BlockNode {
+    /// 노드 이름 (마운트 여부 확인용)
+    name: String,
+    open: fn() -> Box<dyn BlockDevice>,
+    partition: Option<Partition>,
+}
+
+impl DeviceNode for BlockNode {
+    fn file_type(&self) -> FileType {
+        FileType::Block
+    }
+
+    fn size(&self) -> u64 {
+        self.open_block().map_or(0, |device| device.num_blocks() * device.block_size() as u64)
+    }
+
+    fn open(&self) -> FsResult<Box<dyn File>> {
+        let device = self.open_block().ok_or(FsError::NotFound)?;
+        Ok(Box::new(BlockFile { name: self.name.clone(), device, offset: 0 }))
+    }
+
+    fn open_block(&self) -> Option<Box<dyn BlockDevice>> {
+        let disk = (self.open)();
+        Some(match self.partition {
+            Some(partition) => Box::new(PartitionDevice::new(disk, partition)),
+            None => disk,
+        })
+    }
+}
+
+/// 디스크와 그 파티션을 노드로 등록
+///
+/// 파티션은 `<name>p<번호>`로 등록됩니다 (이름이 숫자로 끝나도 같은 규칙).
+///
+/// # Arguments
+/// * `name` - 디스크 노드 이름 (예: "ata0")
+/// * `open` - 디스크를 블록 디바이스로 여는 함수
+///
+/// # Returns
+/// 등록한 파티션 수
+pub fn add_disk(name: &str, open: fn() -> Box<dyn BlockDevice>) -> FsResult<usize> {
+    register(name, Arc::new(BlockNode { name: name.to_string(), open, partition: None }))?;
+    let partitions = match partition::scan(&mut *open()) {
+        Ok(partitions) => partitions,
+        Err(e) => {
+            crate::log_warn!("devfs: cannot read partition table of {}: {:?}", name, e);
+            Vec::new()
+        }
+    };
+    for partition in &partitions {
+        let node_name = format!("{}p{}", name, partition.number);
+        register(&node_name, Arc::new(BlockNode { name: node_name.clone(), open, partition: Some(*partition) }))?;
+    }
+    crate::log_info!("devfs: /dev/{} ({} partitions)", name, partitions.len());
+    Ok(partitions.len())
+}
+
+/// 문자 디바이스 핸들을 여는 함수
+type OpenCharFn = fn() -> Box<dyn File>;
+
+/// 문자 디바이스 노드
+struct CharNode {
+    open: OpenCharFn,
+    mode: u32,
+    present: fn() -> bool,
+}
+
+impl DeviceNode for CharNode {
+    fn file_type(&self) -> FileType {
+        FileType::Character
+    }
+
+    fn mode(&self) -> u32 {
+        self.mode
+    }
+
+    fn present(&self) -> bool {
+        (self.present)()
+    }
+
+    fn open(&self) -> FsResult<Box<dyn File>> {
+        Ok((self.open)())
+    }
+}
+
+fn always() -> bool {
+    true
+}
+
+/// 기본 문자 디바이스 등록 (부팅 시 한 번)
+pub fn init() {
+    let devices: [(&str, OpenCharFn, u32, fn() -> bool); 7] = [
+        ("null", || Box::new(NullFile), 0o666, always),
+        ("zero", || Box::new(ZeroFile), 0o666, always),
+        ("random", || Box::new(RandomFile::new()), 0o666, always),
+        ("ttyS0", || Box::new(SerialFile), 0o660, always),
+        ("fb0", || Box::new(FramebufferFile { offset: 0 }), 0o660, crate::drivers::framebuffer::is_initialized),
+        ("input/event0", || Box::new(InputFile::new(InputDevice::Keyboard)), 0o640, always),
+        ("input/event1", || Box::new(InputFile::new(InputDevice::Mouse)), 0o640, always),
+    ];
+    for (name, open, mode, present) in devices {
+        if let Err(e) = register(name, Arc::new(CharNode { open, mode, present })) {
+            crate::log_warn!("devfs: cannot register {}: {:?}", name, e);
+        }
+    }
+}
+
+/// 디바이스 파일 메타데이터 (root 소유)
+fn device_metadata(file_type: FileType, size: u64, mode: u32) -> FileMetadata {
+    FileMetadata {
+        file_type,
+        size,
+        mode: FileMode::new(mode),
+        created: 0,
+        modified: 0,
+        accessed: 0,
+        uid: 0,
+        gid: 0,
+    }
+}
+
+/// 현재 사용자가 root 소유 노드에 가진 권한 (`rwx` 3비트)
+fn access_bits(mode: u32) -> u32 {
+    let uid = get_current_uid();
+    if uid == 0 {
+        0o7
+    } else if is_user_in_group(uid, 0) {
+        (mode >> 3) & 0o7
+    } else {
+        mode & 0o7
+    }
+}
+
+/// 권한 비트로 읽기/쓰기를 거르는 핸들
+struct AccessFile {
+    inner: Box<dyn File>,
+    readable: bool,
+    writable: bool,
+}
+
+impl AccessFile {
+    /// 노드를 열고 권한을 확인 (읽기도 쓰기도 안 되면 `PermissionDenied`)
+    fn open(node: &dyn DeviceNode) -> FsResult<Box<dyn File>> {
+        let access = access_bits(node.mode());
+        let readable = access & 0o4 != 0;
+        let writable = access & 0o2 != 0;
+        if !readable && !writable {
+            return Err(FsError::PermissionDenied);
+        }
+        Ok(Box::new(Self { inner: node.open()?, readable, writable }))
+    }
+}
+
+impl File for AccessFile {
+    fn read(&mut self, buf: &mut [u8], offset: Option<Offset>) -> FsResult<usize> {
+        if !self.readable {
+            return Err(FsError::PermissionDenied);
+        }
+        self.inner.read(buf, offset)
+    }
+
+    fn write(&mut self, buf: &[u8], offset: Option<Offset>) -> FsResult<usize> {
+        if !self.writable {
+            return Err(FsError::PermissionDenied);
+        }
+        self.inner.write(buf, offset)
+    }
+
+    fn metadata(&self) -> FsResult<FileMetadata> {
+        self.inner.metadata()
+    }
+
+    fn size(&self) -> FsResult<u64> {
+        self.inner.size()
+    }
+
+    fn seek(&mut self, offset: Offset) -> FsResult<Offset> {
+        self.inner.seek(offset)
+    }
+
+    fn tell(&self) -> FsResult<Offset> {
+        self.inner.tell()
+    }
+
+    fn block_device(&self) -> Option<&str> {
+        self.inner.block_device()
+    }
+}
+
+/// 블록 디바이스 파일 (바이트 오프셋으로 읽기/쓰기)
+struct BlockFile {
+    name: String,
+    device: Box<dyn BlockDevice>,
+    offset: Offset,
+}
+
+impl BlockFile {
+    fn capacity(&self) -> u64 {
+        self.device.num_blocks() * self.device.block_size() as u64
+    }
+}
+
+impl File for BlockFile {
+    fn read(&mut self, buf: &mut [u8], offset: Option<Offset>) -> FsResult<usize> {
+        let start = offset.unwrap_or(self.offset);
+        if start < 0 {
+            return Ok(0);
+        }
+        let block_size = self.device.block_size();
+        let end = (start as u64 + buf.len() as u64).min(self.capacity());
+        let mut position = start as u64;
+        let mut sector = vec![0u8; block_size];
+        while position < end {
+            let block = position / block_size as u64;
+            let within = (position % block_size as u64) as usize;
+            let count = (block_size - within).min((end - position) as usize);
+            self.device.read_block(block, &mut sector).map_err(|_| FsError::IOError)?;
+            let done = (position - start as u64) as usize;
+            buf[done..done + count].copy_from_slice(&sector[within..within + count]);
+            position += count as u64;
+        }
+        let bytes_read = (position - start as u64) as usize;
+        if offset.is_none() {
+            self.offset = start + bytes_read as Offset;
+        }
+        Ok(bytes_read)
+    }
+
+    fn write(&mut self, buf: &[u8], offset: Option<Offset>) -> FsResult<usize> {
+        let start = offset.unwrap_or(self.offset);
+        if start < 0 {
+            return Err(FsError::InvalidPath);
+        }
+        let capacity = self.capacity();
+        if !buf.is_empty() && start as u64 >= capacity {
+            return Err(FsError::OutOfSpace);
+        }
+        let block_size = self.device.block_size();
+        let end = (start as u64 + buf.len() as u64).min(capacity);
+        let mut position = start as u64;
+        let mut sector = vec![0u8; block_size];
+        while position < end {
+            let block = position / block_size as u64;
+            let within = (position % block_size as u64) as usize;
+            let count = (block_size - within).min((end - position) as usize);
+            // 섹터 일부만 바꾸면 나머지를 보존하도록 먼저 읽음
+            if count < block_size {
+                self.device.read_block(block, &mut sector).map_err(|_| FsError::IOError)?;
+            }
+            let done = (position - start as u64) as usize;
+            sector[within..within + count].copy_from_slice(&buf[done..done + count]);
+            self.device.write_block(block, &sector).map_err(|_| FsError::IOError)?;
+            position += count as u64;
+        }
+        let bytes_written = (position - start as u64) as usize;
+        if offset.is_none() {
+            self.offset = start + bytes_written as Offset;
+        }
+        Ok(bytes_written)
+    }
+
+    fn metadata(&self) -> FsResult<FileMetadata> {
+        Ok(device_metadata(FileType::Block, self.capacity(), 0o660))
+    }
+
+    fn size(&self) -> FsResult<u64> {
+        Ok(self.capacity())
+    }
+
+    fn seek(&mut self, offset: Offset) -> FsResult<Offset> {
+        if offset < 0 {
+            return Err(FsError::InvalidPath);
+        }
+        self.offset = offset;
+        Ok(offset)
+    }
+
+    fn tell(&self) -> FsResult<Offset> {
+        Ok(self.offset)
+    }
+
+    fn block_device(&self) -> Option<&str> {
+        Some(&self.name)
+    }
+}
+
+/// 문자 디바이스의 `File` 메서드 중 위치와 메타데이터 관련 부분 (위치가 없는 스트림)
+macro_rules! stream_file_common {
+    ($mode:expr) => {
+        fn metadata(&self) -> FsResult<FileMetadata> {
+            Ok(device_metadata(FileType::Character, 0, $mode))
+        }
+
+        fn size(&self) -> FsResult<u64> {
+            Ok(0)
+        }
+
+        fn seek(&mut self, _offset: Offset) -> FsResult<Offset> {
+            Ok(0)
+        }
+
+        fn tell(&self) -> FsResult<Offset> {
+            Ok(0)
+        }
+    };
+}
+
+/// `/dev/null`: 읽으면 파일 끝, 쓰면 버림
+struct NullFile;
+
+impl File for NullFile {
+    fn read(&mut self, _buf: &mut [u8], _offset: Option<Offset>) -> FsResult<usize> {
+        Ok(0)
+    }
+
+    fn write(&mut self, buf: &[u8], _offset: Option<Offset>) -> FsResult<usize> {
+        Ok(buf.len())
+    }
+
+    stream_file_common!(0o666);
+}
+
+/// `/dev/zero`: 읽으면 0, 쓰면 버림
+struct ZeroFile;
+
+impl File for ZeroFile {
+    fn read(&mut self, buf: &mut [u8], _offset: Option<Offset>) -> FsResult<usize> {
+        buf.fill(0);
+        Ok(buf.len())
+    }
+
+    fn write(&mut self, buf: &[u8], _offset: Option<Offset>) -> FsResult<usize> {
+        Ok(buf.len())
+    }
+
+    stream_file_common!(0o666);
+}
+
+/// RDRAND로 64비트 난수 하나 (미지원이거나 실패하면 `None`)
+fn rdrand() -> Option<u64> {
+    if unsafe { core::arch::x86_64::__cpuid(1) }.ecx & (1 << 30) == 0 {
+        return None;
+    }
+    for _ in 0..10 {
+        let value: u64;
+        let ok: u8;
+        unsafe {
+            core::arch::asm!(
+                "rdrand {value}; setc {ok}",
+                value = out(reg) value,
+                ok = lateout(reg_byte) ok,
+                options(nomem, nostack)
+            );
+        }
+        if ok != 0 {
+            return Some(value);
+        }
+    }
+    None
+}
+
+/// RDSEED로 엔트로피 원천의 64비트 값 하나 (미지원이거나 실패하면 `None`)
+///
+/// RDRAND는 하드웨어 DRBG 출력이고 RDSEED는 그 시드로 쓰이는 원천 값이므로 시드에 더 알맞습니다.
+fn rdseed() -> Option<u64> {
+    if unsafe { core::arch::x86_64::__cpuid_count(7, 0) }.ebx & (1 << 18) == 0 {
+        return None;
+    }
+    for _ in 0..100 {
+        let value: u64;
+        let ok: u8;
+        unsafe {
+            core::arch::asm!(
+                "rdseed {value}; setc {ok}",
+                value = out(reg) value,
+                ok = lateout(reg_byte) ok,
+                options(nomem, nostack)
+            );
+        }
+        if ok != 0 {
+            return Some(value);
+        }
+        core::hint::spin_loop();
+    }
+    None
+}
+
+/// `/dev/random`: 하드웨어 난수와 xorshift64* 스트림을 섞은 출력
+///
+/// 상태는 RDSEED(없으면 RDRAND, 둘 다 없으면 TSC)로 시드하고, 쓴 데이터도 상태에 섞습니다.
+/// RDRAND가 있으면 8바이트마다 새 RDRAND 값을 XOR하므로 출력의 예측 불가능성은
+/// RDRAND에서 옵니다. RDRAND가 없는 CPU에서는 TSC로 시드한 xorshift64*만 남으므로
+/// 암호용으로 쓸 수 없습니다 (`read`가 처음 한 번 경고를 남김).
+struct RandomFile {
+    state: u64,
+    hardware: bool,
+}
+
+impl RandomFile {
+    fn new() -> Self {
+        let hardware = rdrand().is_some();
+        let seed = rdseed().or_else(rdrand).unwrap_or_else(crate::time::tsc::rdtsc) ^ 0x9E37_79B9_7F4A_7C15;
+        Self { state: seed.max(1), hardware }
+    }
+
+    fn next(&mut self) -> u64 {
+        self.state ^= self.state >> 12;
+        self.state ^= self.state << 25;
+        self.state ^= self.state >> 27;
+        self.state.wrapping_mul(0x2545_F491_4F6C_DD1D)
+    }
+}
+
+/// 하드웨어 난수 없이 `/dev/random`을 읽었다는 경고를 이미 남겼는지
+static WEAK_RANDOM_WARNED: AtomicBool = AtomicBool::new(false);
+
+impl File for RandomFile {
+    fn read(&mut self, buf: &mut [u8], _offset: Option<Offset>) -> FsResult<usize> {
+        if !self.hardware && !WEAK_RANDOM_WARNED.swap(true, Ordering::Relaxed) {
+            crate::log_warn!("devfs: no RDRAND, /dev/random is not cryptographically secure");
+        }
+        for chunk in buf.chunks_mut(8) {
+            let mut value = self.next();
+            if self.hardware {
+                match rdrand() {
+                    Some(fresh) => value ^= fresh,
+                    // 일시적 고갈이 이어지면 의사난수를 내보내지 않음
+                    None => return Err(FsError::IOError),
+                }
+            }
+            chunk.copy_from_slice(&value.to_le_bytes()[..chunk.len()]);
+        }
+        Ok(buf.len())
+    }
+
+    fn write(&mut self, buf: &[u8], _offset: Option<Offset>) -> FsResult<usize> {
+        for chunk in buf.chunks(8) {
+            let mut bytes = [0u8; 8];
+            bytes[..chunk.len()].copy_from_slice(chunk);
+            self.state = (self.state ^ u64::from_le_bytes(bytes)).max(1);
+            self.next();
+        }
+        Ok(buf.len())
+    }
+
+    stream_file_common!(0o666);
+}
+
+/// `/dev/ttyS0`: COM1 (읽기는 이미 도착한 바이트만, 논블로킹)
+struct SerialFile;
+
+impl File for SerialFile {
+    fn read(&mut self, buf: &mut [u8], _offset: Option<Offset>) -> FsResult<usize> {
+        let mut count = 0;
+        while count < buf.len() {
+            match crate::drivers::serial::try_read_byte() {
+                Some(byte) => {
+                    buf[count] = byte;
+                    count += 1;
+                }
+                None => break,
+            }
+        }
+        Ok(count)
+    }
+
+    fn write(&mut self, buf: &[u8], _offset: Option<Offset>) -> FsResult<usize> {
+        for &byte in buf {
+            crate::drivers::serial::write_byte(byte);
+        }
+        Ok(buf.len())
+    }
+
+    stream_file_common!(0o660);
+}
+
+/// `/dev/fb0`: 프레임버퍼 픽셀 메모리 (픽셀 형식과 stride는 디바이스 그대로)
+struct FramebufferFile {
+    offset: Offset,
+}
+
+impl FramebufferFile {
+    fn len() -> u64 {
+        crate::drivers::framebuffer::with_framebuffer(|fb| fb.bytes().len() as u64).unwrap_or(0)
+    }
+}
+
+impl File for FramebufferFile {
+    fn read(&mut self, buf: &mut [u8], offset: Option<Offset>) -> FsResult<usize> {
+        let start = offset.unwrap_or(self.offset);
+        if start < 0 {
+            return Ok(0);
+        }
+        let count = crate::drivers::framebuffer::with_framebuffer(|fb| {
+            let pixels = fb.bytes();
+            let start = (start as usize).min(pixels.len());
+            let count = buf.len().min(pixels.len() - start);
+            buf[..count].copy_from_slice(&pixels[start..start + count]);
+            count
+        })
+        .ok_or(FsError::NotFound)?;
+        if offset.is_none() {
+            self.offset = start + count as Offset;
+        }
+        Ok(count)
+    }
+
+    fn write(&mut self, buf: &[u8], offset: Option<Offset>) -> FsResult<usize> {
+        let start = offset.unwrap_or(self.offset);
+        if start < 0 {
+            return Err(FsError::InvalidPath);
+        }
+        let count = crate::drivers::framebuffer::with_framebuffer(|fb| {
+            let pixels = fb.bytes_mut();
+            let start = (start as usize).min(pixels.len());
+            let count = buf.len().min(pixels.len() - start);
+            pixels[start..start + count].copy_from_slice(&buf[..count]);
+            count
+        })
+        .ok_or(FsError::NotFound)?;
+        if count == 0 && !buf.is_empty() {
+            return Err(FsError::OutOfSpace);
+        }
+        if offset.is_none() {
+            self.offset = start + count as Offset;
+        }
+        Ok(count)
+    }
+
+    fn metadata(&self) -> FsResult<FileMetadata> {
+        Ok(device_metadata(FileType::Character, Self::len(), 0o660))
+    }
+
+    fn size(&self) -> FsResult<u64> {
+        Ok(Self::len())
+    }
+
+    fn seek(&mut self, offset: Offset) -> FsResult<Offset> {
+        if offset < 0 {
+            return Err(FsError::InvalidPath);
+        }
+        self.offset = offset;
+        Ok(offset)
+    }
+
+    fn tell(&self) -> FsResult<Offset> {
+        Ok(self.offset)
+    }
+}
+
+/// `/dev/input/event<N>`: 연 뒤에 보고된 입력 이벤트 (`InputEvent::SIZE`바이트 레코드)
+///
+/// 읽기는 논블로킹이며 버퍼에 들어가는 만큼의 온전한 레코드만 돌려줍니다.
+struct InputFile {
+    device: InputDevice,
+    seq: u64,
+}
+
+impl InputFile {
+    fn new(device: InputDevice) -> Self {
+        Self { device, seq: input::current_seq(device) }
+    }
+}
+
+impl File for InputFile {
+    fn read(&mut self, buf: &mut [u8], _offset: Option<Offset>) -> FsResult<usize> {
+        let mut events = [InputEvent { time_us: 0, kind: 0, code: 0, value: 0 }; 16];
+        let capacity = (buf.len() / InputEvent::SIZE).min(events.len());
+        let count = input::read_events(self.device, &mut self.seq, &mut events[..capacity]);
+        for (event, record) in events[..count].iter().zip(buf.chunks_exact_mut(InputEvent::SIZE)) {
+            record.copy_from_slice(&event.encode());
+        }
+        Ok(count * InputEvent::SIZE)
+    }
+
+    fn write(&mut self, _buf: &[u8], _offset: Option<Offset>) -> FsResult<usize> {
+        Err(FsError::PermissionDenied)
+    }
+
+    stream_file_common!(0o640);
+}
+
+/// 경로를 레지스트리 이름으로 (루트는 빈 문자열)
+fn node_name(path: &str) -> FsResult<String> {
+    let path = Path::parse(path).map_err(|_| FsError::InvalidPath)?.normalize();
+    let names: Vec<&str> = path.components.iter().map(|c| c.as_str()).collect();
+    Ok(names.join("/"))
+}
+
+/// 디렉토리 `dir` 바로 아래 항목 (중간 디렉토리 포함)
+fn children(dir: &str) -> Vec<(String, FileType)> {
+    let nodes = NODES.lock();
+    let mut entries: Vec<(String, FileType)> = Vec::new();
+    for (name, node) in nodes.iter() {
+        let rest = match dir {
+            "" => name.as_str(),
+            _ => match name.strip_prefix(dir).and_then(|rest| rest.strip_prefix('/')) {
+                Some(rest) => rest,
+                None => continue,
+            },
+        };
+        if !node.present() {
+            continue;
+        }
+        let entry = match rest.split_once('/') {
+            Some((subdir, _)) => (subdir.to_string(), FileType::Directory),
+            None => (rest.to_string(), node.file_type()),
+        };
+        if !entries.contains(&entry) {
+            entries.push(entry);
+        }
+    }
+    entries
+}
+
+/// `dir`가 디렉토리인지 (루트이거나 아래에 노드가 있음)
+fn is_dir(dir: &str) -> bool {
+    dir.is_empty() || !children(dir).is_empty()
+}
+
+/// devfs 인스턴스 (노드는 전역 레지스트리에 있음)
+pub struct DevFs {
+    mounted: bool,
+}
+
+impl DevFs {
+    pub const fn new() -> Self {
+        Self { mounted: false }
+    }
+
+    fn check_mounted(&self) -> FsResult<()> {
+        if !self.mounted {
+            return Err(FsError::InvalidFilesystem);
+        }
+        Ok(())
+    }
+}
+
+impl Default for DevFs {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FileSystem for DevFs {
+    fn mount(&mut self) -> FsResult<()> {
+        if self.mounted {
+            return Err(FsError::Busy);
+        }
+        self.mounted = true;
+        Ok(())
+    }
+
+    fn unmount(&mut self) -> FsResult<()> {
+        self.check_mounted()?;
+        self.mounted = false;
+        Ok(())
+    }
+
+    fn open_file(&mut self, path: &str) -> FsResult<Box<dyn File>> {
+        self.check_mounted()?;
+        let name = node_name(path)?;
+        match lookup(&name) {
+            Ok(node) => AccessFile::open(&*node),
+            Err(_) if is_dir(&name) => Err(FsError::IsDirectory),
+            Err(e) => Err(e),
+        }
+    }
+
+    fn create_file(&mut self, _path: &str) -> FsResult<Box<dyn File>> {
+        Err(FsError::PermissionDenied)
+    }
+
+    fn open_dir(&mut self, path: &str) -> FsResult<Box<dyn Directory>> {
+        self.check_mounted()?;
+        let name = node_name(path)?;
+        if is_dir(&name) {
+            Ok(Box::new(DevfsDirectory { name }))
+        } else if lookup(&name).is_ok() {
+            Err(FsError::NotADirectory)
+        } else {
+            Err(FsError::NotFound)
+        }
+    }
+
+    fn create_dir(&mut self, _path: &str) -> FsResult<()> {
+        Err(FsError::PermissionDenied)
+    }
+
+    fn remove(&mut self, _path: &str) -> FsResult<()> {
+        Err(FsError::PermissionDenied)
+    }
+
+    fn rename(&mut self, _old_path: &str, _new_path: &str) -> FsResult<()> {
+        Err(FsError::PermissionDenied)
+    }
+
+    fn metadata(&mut self, path: &str) -> FsResult<FileMetadata> {
+        self.check_mounted()?;
+        let name = node_name(path)?;
+        match lookup(&name) {
+            Ok(node) => Ok(device_metadata(node.file_type(), node.size(), node.mode())),
+            Err(_) if is_dir(&name) => Ok(device_metadata(FileType::Directory, 0, 0o755)),
+            Err(e) => Err(e),
+        }
+    }
+
+    fn is_mounted(&self) -> bool {
+        self.mounted
+    }
+
+    fn fs_type(&self) -> &'static str {
+        "devfs"
+    }
+
+    fn statfs(&mut self) -> FsResult<FsStats> {
+        Ok(FsStats {
+            block_size: 4096,
+            total_blocks: 0,
+            free_blocks: 0,
+            total_inodes: NODES.lock().len() as u64,
+            free_inodes: 0,
+        })
+    }
+}
+
+/// devfs 디렉토리 핸들 (읽기 전용)
+struct DevfsDirectory {
+    name: String,
+}
+
+impl Directory for DevfsDirectory {
+    fn read_dir(&self) -> FsResult<Vec<(String, FileType)>> {
+        Ok(children(&self.name))
+    }
+
+    fn create_file(&mut self, _name: &str) -> FsResult<()> {
+        Err(FsError::PermissionDenied)
+    }
+
+    fn create_dir(&mut self, _name: &str) -> FsResult<()> {
+        Err(FsError::PermissionDenied)
+    }
+
+    fn remove(&mut self, _name: &str) -> FsResult<()> {
+        Err(FsError::PermissionDenied)
+    }
+
+    fn rename(&mut self, _old_name: &str, _new_name: &str) -> FsResult<()> {
+        Err(FsError::PermissionDenied)
+    }
+}
@@ -0,0 +1,148 @@
+//! ext2 블록/inode 할당자
+//!
+//! 그룹마다 블록 비트맵과 inode 비트맵이 한 블록씩 있습니다. 블록 비트맵의 비트 `n`은
+//! 그룹의 `first_data_block + 그룹 * blocks_per_group + n`번 블록, inode 비트맵의
+//! 비트 `n`은 `그룹 * inodes_per_group + n + 1`번 inode입니다. 비트맵은 바로 쓰고,
+//! 그룹 디스크립터와 슈퍼블록의 카운터는 `Ext2::modify`가 끝날 때 한꺼번에 기록합니다.
+
+use super::Ext2;
+use crate::fs::vfs::{FsError, FsResult};
+
+/// 비트맵에서 `limit`비트 안의 첫 빈 비트 (`from`부터, 못 찾으면 처음부터)
+fn find_clear_bit(bitmap: &[u8], from: usize, limit: usize) -> Option<usize> {
+    (from..limit).chain(0..from.min(limit)).find(|&bit| bitmap[bit / 8] & (1 << (bit % 8)) == 0)
+}
+
+impl Ext2 {
+    /// 그룹 `group`에 속한 블록 수 (마지막 그룹은 짧을 수 있음)
+    fn blocks_in_group(&self, group: u32) -> u32 {
+        let start = self.sb.first_data_block + group * self.sb.blocks_per_group;
+        (self.sb.blocks_count - start).min(self.sb.blocks_per_group)
+    }
+
+    /// 블록이 속한 그룹
+    pub(super) fn block_group(&self, block: u32) -> u32 {
+        block.saturating_sub(self.sb.first_data_block) / self.sb.blocks_per_group
+    }
+
+    /// inode가 속한 그룹
+    pub(super) fn inode_group(&self, ino: u32) -> u32 {
+        (ino - 1) / self.sb.inodes_per_group
+    }
+
+    /// 블록 하나 할당 (`goal` 그룹부터 찾음, 내용은 초기화하지 않음)
+    pub(super) fn alloc_block(&mut self, goal: u32) -> FsResult<u32> {
+        let groups = self.sb.group_count();
+        for i in 0..groups {
+            let group = (goal + i) % groups;
+            if self.groups[group as usize].free_blocks == 0 {
+                continue;
+            }
+            let bitmap_block = self.groups[group as usize].block_bitmap;
+            let mut bitmap = self.read_block(bitmap_block)?;
+            let Some(bit) = find_clear_bit(&bitmap, 0, self.blocks_in_group(group) as usize) else {
+                continue;
+            };
+            bitmap[bit / 8] |= 1 << (bit % 8);
+            self.write_block(bitmap_block, &bitmap)?;
+            self.groups[group as usize].free_blocks -= 1;
+            self.sb.free_blocks -= 1;
+            self.meta_dirty = true;
+            return Ok(self.sb.first_data_block + group * self.sb.blocks_per_group + bit as u32);
+        }
+        Err(FsError::OutOfSpace)
+    }
+
+    /// 0으로 채운 블록 할당 (간접 블록, 디렉토리 블록)
+    pub(super) fn alloc_zeroed_block(&mut self, goal: u32) -> FsResult<u32> {
+        let block = self.alloc_block(goal)?;
+        let zero = alloc::vec![0u8; self.block_size];
+        self.write_block(block, &zero)?;
+        Ok(block)
+    }
+
+    /// 블록 해제
+    pub(super) fn free_block(&mut self, block: u32) -> FsResult<()> {
+        if block < self.sb.first_data_block || block >= self.sb.blocks_count {
+            return Err(FsError::InvalidFilesystem);
+        }
+        let group = self.block_group(block);
+        let bit = ((block - self.sb.first_data_block) % self.sb.blocks_per_group) as usize;
+        let bitmap_block = self.groups[group as usize].block_bitmap;
+        let mut bitmap = self.read_block(bitmap_block)?;
+        if bitmap[bit / 8] & (1 << (bit % 8)) == 0 {
+            crate::log_warn!("ext2: block {} freed twice", block);
+            return Err(FsError::InvalidFilesystem);
+        }
+        bitmap[bit / 8] &= !(1 << (bit % 8));
+        self.write_block(bitmap_block, &bitmap)?;
+        self.groups[group as usize].free_blocks += 1;
+        self.sb.free_blocks += 1;
+        self.meta_dirty = true;
+        Ok(())
+    }
+
+    /// inode 하나 할당
+    ///
+    /// 디렉토리는 빈 inode가 평균보다 많은 그룹에 흩어 놓고, 나머지는 부모와 같은 그룹에서
+    /// 시작해 찾습니다.
+    pub(super) fn alloc_inode(&mut self, parent: u32, is_dir: bool) -> FsResult<u32> {
+        let groups = self.sb.group_count();
+        let goal = if is_dir {
+            let average = self.sb.free_inodes / groups;
+            (0..groups)
+                .filter(|&g| self.groups[g as usize].free_inodes as u32 >= average.max(1))
+                .max_by_key(|&g| self.groups[g as usize].free_blocks)
+                .unwrap_or(0)
+        } else {
+            self.inode_group(parent)
+        };
+        for i in 0..groups {
+            let group = (goal + i) % groups;
+            if self.groups[group as usize].free_inodes == 0 {
+                continue;
+            }
+            let bitmap_block = self.groups[group as usize].inode_bitmap;
+            let mut bitmap = self.read_block(bitmap_block)?;
+            // 예약 inode(첫 그룹의 `first_ino` 앞)는 mkfs가 사용 중으로 표시해 둠
+            let Some(bit) = find_clear_bit(&bitmap, 0, self.sb.inodes_per_group as usize) else {
+                continue;
+            };
+            let ino = group * self.sb.inodes_per_group + bit as u32 + 1;
+            if ino < self.sb.first_ino || ino > self.sb.inodes_count {
+                return Err(FsError::InvalidFilesystem);
+            }
+            bitmap[bit / 8] |= 1 << (bit % 8);
+            self.write_block(bitmap_block, &bitmap)?;
+            let desc = &mut self.groups[group as usize];
+            desc.free_inodes -= 1;
+            if is_dir {
+                desc.used_dirs += 1;
+            }
+            self.sb.free_inodes -= 1;
+            self.meta_dirty = true;
+            return Ok(ino);
+        }
+        Err(FsError::OutOfSpace)
+    }
+
+    /// inode 해제
+    pub(super) fn free_inode(&mut self, ino: u32, is_dir: bool) -> FsResult<()> {
+        // 번호가 재사용되기 전에 캐시된 내용 버림
+        crate::fs::cache::invalidate(self.cache_key(ino));
+        let group = self.inode_group(ino);
+        let bit = ((ino - 1) % self.sb.inodes_per_group) as usize;
+        let bitmap_block = self.groups[group as usize].inode_bitmap;
+        let mut bitmap = self.read_block(bitmap_block)?;
+        bitmap[bit / 8] &= !(1 << (bit % 8));
+        self.write_block(bitmap_block, &bitmap)?;
+        let desc = &mut self.groups[group as usize];
+        desc.free_inodes += 1;
+        if is_dir {
+            desc.used_dirs -= 1;
+        }
+        self.sb.free_inodes += 1;
+        self.meta_dirty = true;
+        Ok(())
+    }
+}
@@ -0,0 +1,293 @@
+//! ext2 선형 디렉토리
+//!
+//! 디렉토리 블록은 가변 길이 항목(inode, rec_len, name_len, file_type, 이름)의 연결로
+//! 빈틈없이 채워집니다. 항목 삭제는 앞 항목의 `rec_len`에 합치고, 추가는 `rec_len`에
+//! 남는 공간을 쪼개 씁니다. 해시 인덱스(htree) 디렉토리도 색인 블록이 빈 항목으로 보이므로
+//! 그대로 읽을 수 있고, 항목을 추가할 때는 `INDEX_FL`을 지워 선형 디렉토리로 바꿉니다.
+
+use super::layout::{
+    dirent_file_type, dirent_size, dirent_type, get_u16, get_u32, put_u16, put_u32, Inode, DIRENT_HEADER,
+    INDEX_FL, MAX_NAME_LEN,
+};
+use super::Ext2;
+use crate::fs::vfs::{FileType, FsError, FsResult};
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+
+/// 디렉토리 항목 이름 검사
+pub(super) fn validate_name(name: &str) -> FsResult<()> {
+    if name.is_empty() || name == "." || name == ".." || name.len() > MAX_NAME_LEN {
+        return Err(FsError::InvalidPath);
+    }
+    if name.bytes().any(|b| b == b'/' || b == 0) {
+        return Err(FsError::InvalidPath);
+    }
+    Ok(())
+}
+
+/// 블록 안의 항목 하나
+struct RawEntry {
+    offset: usize,
+    ino: u32,
+    rec_len: usize,
+    name_len: usize,
+    file_type: u8,
+}
+
+impl RawEntry {
+    fn name<'a>(&self, block: &'a [u8]) -> &'a [u8] {
+        &block[self.offset + DIRENT_HEADER..self.offset + DIRENT_HEADER + self.name_len]
+    }
+
+    /// 이 항목이 실제로 쓰는 크기 (빈 항목이면 0)
+    fn used(&self) -> usize {
+        if self.ino == 0 {
+            0
+        } else {
+            dirent_size(self.name_len)
+        }
+    }
+}
+
+/// 디렉토리 블록의 항목 목록 (빈 항목 포함, `rec_len` 사슬이 깨졌으면 오류)
+fn parse_block(block: &[u8]) -> FsResult<Vec<RawEntry>> {
+    let mut entries = Vec::new();
+    let mut offset = 0;
+    while offset < block.len() {
+        if offset + DIRENT_HEADER > block.len() {
+            return Err(FsError::InvalidFilesystem);
+        }
+        let entry = RawEntry {
+            offset,
+            ino: get_u32(block, offset),
+            rec_len: get_u16(block, offset + 4) as usize,
+            name_len: block[offset + 6] as usize,
+            file_type: block[offset + 7],
+        };
+        if entry.rec_len < DIRENT_HEADER
+            || entry.rec_len % 4 != 0
+            || offset + entry.rec_len > block.len()
+            || DIRENT_HEADER + entry.name_len > entry.rec_len
+        {
+            return Err(FsError::InvalidFilesystem);
+        }
+        offset += entry.rec_len;
+        entries.push(entry);
+    }
+    Ok(entries)
+}
+
+/// 항목 헤더와 이름 기록
+fn write_entry(block: &mut [u8], offset: usize, ino: u32, rec_len: usize, name: &[u8], file_type: u8) {
+    put_u32(block, offset, ino);
+    put_u16(block, offset + 4, rec_len as u16);
+    block[offset + 6] = name.len() as u8;
+    block[offset + 7] = file_type;
+    block[offset + DIRENT_HEADER..offset + DIRENT_HEADER + name.len()].copy_from_slice(name);
+}
+
+/// 새 항목을 넣을 자리
+#[derive(Debug, PartialEq, Eq)]
+struct Slot {
+    offset: usize,
+    /// 남는 공간을 내어 줄 앞 항목의 오프셋과 줄인 `rec_len` (빈 항목을 재사용하면 `None`)
+    shrink: Option<(usize, usize)>,
+}
+
+/// 크기 `needed`인 새 항목을 넣을 자리 찾기
+fn find_slot(block: &[u8], needed: usize) -> FsResult<Option<Slot>> {
+    for entry in parse_block(block)? {
+        if entry.ino == 0 && entry.rec_len >= needed {
+            return Ok(Some(Slot { offset: entry.offset, shrink: None }));
+        }
+        let used = entry.used();
+        if used > 0 && entry.rec_len - used >= needed {
+            return Ok(Some(Slot { offset: entry.offset + used, shrink: Some((entry.offset, used)) }));
+        }
+    }
+    Ok(None)
+}
+
+impl Ext2 {
+    /// `file_type` 바이트 값 (FILETYPE 기능이 없으면 0)
+    fn dirent_type_byte(&self, file_type: FileType) -> u8 {
+        if self.sb.has_filetype() {
+            dirent_type(file_type)
+        } else {
+            0
+        }
+    }
+
+    /// 디렉토리의 데이터 블록 목록 (구멍은 건너뜀)
+    fn dir_blocks(&mut self, dir: &Inode) -> FsResult<Vec<u32>> {
+        let mut blocks = Vec::new();
+        for logical in 0..dir.size / self.block_size as u64 {
+            let block = self.lookup_block(dir, logical)?;
+            if block != 0 {
+                blocks.push(block);
+            }
+        }
+        Ok(blocks)
+    }
+
+    /// 디렉토리에서 이름 찾기 (`..` 포함)
+    pub(super) fn dir_lookup(&mut self, dir: &Inode, name: &str) -> FsResult<Option<u32>> {
+        for block in self.dir_blocks(dir)? {
+            let data = self.read_block(block)?;
+            let found = parse_block(&data)?.into_iter().find(|e| e.ino != 0 && e.name(&data) == name.as_bytes());
+            if let Some(entry) = found {
+                return Ok(Some(entry.ino));
+            }
+        }
+        Ok(None)
+    }
+
+    /// 디렉토리의 항목 (이름, inode, 종류), `.`과 `..` 제외
+    pub(super) fn dir_entries(&mut self, dir: &Inode) -> FsResult<Vec<(String, u32, FileType)>> {
+        let mut entries = Vec::new();
+        for block in self.dir_blocks(dir)? {
+            let data = self.read_block(block)?;
+            for entry in parse_block(&data)? {
+                let name = entry.name(&data);
+                if entry.ino == 0 || name == b"." || name == b".." {
+                    continue;
+                }
+                let file_type = match dirent_file_type(entry.file_type).filter(|_| self.sb.has_filetype()) {
+                    Some(file_type) => file_type,
+                    None => self.read_inode(entry.ino)?.file_type(),
+                };
+                entries.push((String::from_utf8_lossy(name).into_owned(), entry.ino, file_type));
+            }
+        }
+        Ok(entries)
+    }
+
+    /// 디렉토리가 비었는지 확인 (`.`과 `..`만 있음)
+    pub(super) fn dir_is_empty(&mut self, dir: &Inode) -> FsResult<bool> {
+        for block in self.dir_blocks(dir)? {
+            let data = self.read_block(block)?;
+            let occupied = parse_block(&data)?.into_iter().any(|e| {
+                let name = e.name(&data);
+                e.ino != 0 && name != b"." && name != b".."
+            });
+            if occupied {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+
+    /// 디렉토리에 항목 추가 (디렉토리 inode 기록은 호출자 몫)
+    ///
+    /// 빈 자리가 없으면 블록을 하나 덧붙입니다. `goal`은 새 블록을 찾기 시작할 그룹입니다.
+    pub(super) fn dir_insert(
+        &mut self,
+        dir: &mut Inode,
+        goal: u32,
+        name: &str,
+        ino: u32,
+        file_type: FileType,
+    ) -> FsResult<()> {
+        validate_name(name)?;
+        if self.dir_lookup(dir, name)?.is_some() {
+            return Err(FsError::AlreadyExists);
+        }
+        let type_byte = self.dirent_type_byte(file_type);
+        let needed = dirent_size(name.len());
+        dir.flags &= !INDEX_FL;
+        for block in self.dir_blocks(dir)? {
+            let mut data = self.read_block(block)?;
+            let Some(slot) = find_slot(&data, needed)? else { continue };
+            let rec_len = match slot.shrink {
+                Some((prev, used)) => {
+                    let rest = get_u16(&data, prev + 4) as usize - used;
+                    put_u16(&mut data, prev + 4, used as u16);
+                    rest
+                }
+                None => get_u16(&data, slot.offset + 4) as usize,
+            };
+            write_entry(&mut data, slot.offset, ino, rec_len, name.as_bytes(), type_byte);
+            return self.write_block(block, &data);
+        }
+
+        let logical = dir.size / self.block_size as u64;
+        let block = self.map_block(dir, logical, Some(goal))?;
+        let mut data = vec![0u8; self.block_size];
+        write_entry(&mut data, 0, ino, self.block_size, name.as_bytes(), type_byte);
+        self.write_block(block, &data)?;
+        dir.size += self.block_size as u64;
+        Ok(())
+    }
+
+    /// 디렉토리에서 항목 제거
+    ///
+    /// # Returns
+    /// 제거한 항목의 inode
+    pub(super) fn dir_remove(&mut self, dir: &Inode, name: &str) -> FsResult<u32> {
+        for block in self.dir_blocks(dir)? {
+            let mut data = self.read_block(block)?;
+            let entries = parse_block(&data)?;
+            let Some(index) = entries.iter().position(|e| e.ino != 0 && e.name(&data) == name.as_bytes()) else {
+                continue;
+            };
+            let entry = &entries[index];
+            match index.checked_sub(1).map(|i| &entries[i]) {
+                // 앞 항목이 이 항목의 공간을 흡수
+                Some(prev) => put_u16(&mut data, prev.offset + 4, (prev.rec_len + entry.rec_len) as u16),
+                // 블록의 첫 항목은 빈 항목으로 남김
+                None => put_u32(&mut data, entry.offset, 0),
+            }
+            let ino = entry.ino;
+            self.write_block(block, &data)?;
+            return Ok(ino);
+        }
+        Err(FsError::NotFound)
+    }
+
+    /// 디렉토리의 `..` 항목을 새 부모로 변경
+    pub(super) fn dir_set_parent(&mut self, dir: &Inode, parent: u32) -> FsResult<()> {
+        let block = self.lookup_block(dir, 0)?;
+        let mut data = self.read_block(block)?;
+        let entry = parse_block(&data)?
+            .into_iter()
+            .find(|e| e.name(&data) == b"..")
+            .ok_or(FsError::InvalidFilesystem)?;
+        put_u32(&mut data, entry.offset, parent);
+        self.write_block(block, &data)
+    }
+
+    /// 새 디렉토리의 첫 블록 (`.`과 `..`)
+    pub(super) fn dot_block(&self, ino: u32, parent: u32) -> Vec<u8> {
+        let mut data = vec![0u8; self.block_size];
+        let dot_len = dirent_size(1);
+        let type_byte = self.dirent_type_byte(FileType::Directory);
+        write_entry(&mut data, 0, ino, dot_len, b".", type_byte);
+        write_entry(&mut data, dot_len, parent, self.block_size - dot_len, b"..", type_byte);
+        data
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test_case]
+    fn test_ext2_dir_slot_reuse() {
+        let mut block = vec![0u8; 1024];
+        write_entry(&mut block, 0, 2, 12, b".", 2);
+        write_entry(&mut block, 12, 2, 1012, b"..", 2);
+        // `..`의 남는 공간을 쪼갬
+        assert_eq!(find_slot(&block, dirent_size(5)).unwrap(), Some(Slot { offset: 24, shrink: Some((12, 12)) }));
+
+        write_entry(&mut block, 12, 2, 12, b"..", 2);
+        write_entry(&mut block, 24, 0, 1000, b"", 0);
+        // 빈 항목은 그 자리를 그대로 씀
+        assert_eq!(find_slot(&block, dirent_size(5)).unwrap(), Some(Slot { offset: 24, shrink: None }));
+        assert_eq!(find_slot(&block, 1004).unwrap(), None);
+
+        // rec_len 사슬이 블록 끝을 넘으면 손상
+        put_u16(&mut block, 28, 1004);
+        assert!(parse_block(&block).is_err());
+    }
+}
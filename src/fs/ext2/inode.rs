@@ -0,0 +1,287 @@
+//! ext2 inode 테이블과 블록 매핑
+//!
+//! inode의 `i_block`은 직접 블록 12개와 1/2/3중 간접 블록 포인터를 담습니다. 포인터가
+//! 0인 블록은 구멍(hole)이며 읽으면 0입니다.
+
+use super::layout::{get_u32, put_u32, Inode, DIND_BLOCK, DIRECT_BLOCKS, IND_BLOCK, N_BLOCKS, TIND_BLOCK, XATTR_MAGIC};
+use super::Ext2;
+use crate::fs::vfs::{FsError, FsResult};
+use alloc::vec;
+use alloc::vec::Vec;
+
+/// LARGE_FILE 기능이 없는 파일시스템의 최대 파일 크기
+const MAX_SMALL_FILE: u64 = i32::MAX as u64;
+
+impl Ext2 {
+    /// inode가 들어 있는 테이블 블록과 블록 내 오프셋
+    fn inode_location(&self, ino: u32) -> FsResult<(u32, usize)> {
+        if ino == 0 || ino > self.sb.inodes_count {
+            return Err(FsError::InvalidFilesystem);
+        }
+        let group = self.inode_group(ino) as usize;
+        let offset = ((ino - 1) % self.sb.inodes_per_group) as usize * self.sb.inode_size;
+        let block = self.groups[group].inode_table + (offset / self.block_size) as u32;
+        Ok((block, offset % self.block_size))
+    }
+
+    /// inode 읽기
+    pub(super) fn read_inode(&mut self, ino: u32) -> FsResult<Inode> {
+        let (block, offset) = self.inode_location(ino)?;
+        let table = self.read_block(block)?;
+        let inode = Inode::decode(&table[offset..offset + self.sb.inode_size]);
+        if inode.links == 0 || inode.mode == 0 {
+            // 해제된 inode를 가리키는 디렉토리 항목
+            return Err(FsError::InvalidFilesystem);
+        }
+        Ok(inode)
+    }
+
+    /// inode 기록 (확장 영역은 보존)
+    pub(super) fn write_inode(&mut self, ino: u32, inode: &Inode) -> FsResult<()> {
+        let (block, offset) = self.inode_location(ino)?;
+        let mut table = self.read_block(block)?;
+        inode.encode(&mut table[offset..offset + self.sb.inode_size]);
+        self.write_block(block, &table)
+    }
+
+    /// 새 inode 레코드 기록 (이전 내용을 지우고 확장 영역 크기만 설정)
+    pub(super) fn init_inode(&mut self, ino: u32, inode: &Inode) -> FsResult<()> {
+        let (block, offset) = self.inode_location(ino)?;
+        let mut table = self.read_block(block)?;
+        let record = &mut table[offset..offset + self.sb.inode_size];
+        record.fill(0);
+        if record.len() > 128 {
+            // i_extra_isize: Linux가 확장 타임스탬프 필드를 쓸 수 있게 함
+            record[128] = 32;
+        }
+        inode.encode(record);
+        self.write_block(block, &table)
+    }
+
+    /// 간접 블록 하나에 든 포인터 수
+    fn pointers_per_block(&self) -> u64 {
+        (self.block_size / 4) as u64
+    }
+
+    /// 논리 블록 `index`에 닿는 경로 (`i_block` 위치, 간접 블록 안의 포인터 위치들)
+    fn block_path(&self, index: u64) -> FsResult<(usize, Vec<usize>)> {
+        let per = self.pointers_per_block();
+        if index < DIRECT_BLOCKS as u64 {
+            return Ok((index as usize, Vec::new()));
+        }
+        let mut rest = index - DIRECT_BLOCKS as u64;
+        let mut span = per;
+        for (top, depth) in [(IND_BLOCK, 1), (DIND_BLOCK, 2), (TIND_BLOCK, 3)] {
+            if rest < span {
+                let mut offsets = vec![0usize; depth];
+                for slot in offsets.iter_mut().rev() {
+                    *slot = (rest % per) as usize;
+                    rest /= per;
+                }
+                return Ok((top, offsets));
+            }
+            rest -= span;
+            span *= per;
+        }
+        Err(FsError::OutOfSpace)
+    }
+
+    /// 파일에 매핑된 블록 하나 할당 (간접 블록은 0으로 채움)
+    fn alloc_mapped(&mut self, inode: &mut Inode, goal: u32, zeroed: bool) -> FsResult<u32> {
+        let block = if zeroed { self.alloc_zeroed_block(goal)? } else { self.alloc_block(goal)? };
+        inode.sectors += (self.block_size / 512) as u32;
+        Ok(block)
+    }
+
+    /// 논리 블록 `index`의 디스크 블록
+    ///
+    /// `goal`이 `Some(그룹)`이면 빠진 블록(간접 블록 포함)을 그 그룹부터 할당하고,
+    /// `None`이면 구멍에서 0을 반환합니다. 새로 할당한 데이터 블록은 초기화하지 않습니다.
+    pub(super) fn map_block(&mut self, inode: &mut Inode, index: u64, goal: Option<u32>) -> FsResult<u32> {
+        let (top, offsets) = self.block_path(index)?;
+        let mut block = inode.block[top];
+        if block == 0 {
+            let Some(goal) = goal else { return Ok(0) };
+            block = self.alloc_mapped(inode, goal, !offsets.is_empty())?;
+            inode.block[top] = block;
+        }
+        for (depth, &slot) in offsets.iter().enumerate() {
+            let mut table = self.read_block(block)?;
+            let mut next = get_u32(&table, slot * 4);
+            if next == 0 {
+                let Some(goal) = goal else { return Ok(0) };
+                next = self.alloc_mapped(inode, goal, depth + 1 < offsets.len())?;
+                put_u32(&mut table, slot * 4, next);
+                self.write_block(block, &table)?;
+            }
+            block = next;
+        }
+        Ok(block)
+    }
+
+    /// 할당하지 않고 논리 블록 찾기 (구멍이면 0)
+    pub(super) fn lookup_block(&mut self, inode: &Inode, index: u64) -> FsResult<u32> {
+        self.map_block(&mut inode.clone(), index, None)
+    }
+
+    /// 간접 블록 트리 해제 (`depth`는 남은 간접 단계, 0이면 데이터 블록)
+    fn free_tree(&mut self, block: u32, depth: u32) -> FsResult<()> {
+        if depth > 0 {
+            let table = self.read_block(block)?;
+            for slot in table.chunks_exact(4) {
+                let child = get_u32(slot, 0);
+                if child != 0 {
+                    self.free_tree(child, depth - 1)?;
+                }
+            }
+        }
+        self.free_block(block)
+    }
+
+    /// inode의 데이터 블록, 간접 블록, 확장 속성 블록 모두 해제
+    pub(super) fn free_inode_blocks(&mut self, inode: &mut Inode) -> FsResult<()> {
+        // 빠른 심볼릭 링크의 i_block은 블록 번호가 아니라 경로 문자열
+        if !inode.is_fast_symlink(self.block_size) {
+            for (i, &block) in inode.block.iter().enumerate() {
+                if block != 0 {
+                    self.free_tree(block, i.saturating_sub(DIRECT_BLOCKS - 1) as u32)?;
+                }
+            }
+        }
+        if inode.file_acl != 0 {
+            // 확장 속성 블록은 여러 inode가 공유할 수 있음 (h_refcount)
+            let mut xattr = self.read_block(inode.file_acl)?;
+            let refcount = get_u32(&xattr, 4);
+            if get_u32(&xattr, 0) == XATTR_MAGIC && refcount > 1 {
+                put_u32(&mut xattr, 4, refcount - 1);
+                self.write_block(inode.file_acl, &xattr)?;
+            } else {
+                self.free_block(inode.file_acl)?;
+            }
+            inode.file_acl = 0;
+        }
+        inode.block = [0; N_BLOCKS];
+        inode.sectors = 0;
+        inode.size = 0;
+        Ok(())
+    }
+
+    /// 파일 데이터 읽기 (구멍은 0)
+    pub(super) fn read_data(&mut self, inode: &Inode, offset: u64, buf: &mut [u8]) -> FsResult<usize> {
+        if offset >= inode.size {
+            return Ok(0);
+        }
+        let bs = self.block_size as u64;
+        let len = buf.len().min((inode.size - offset) as usize);
+        let mut done = 0;
+        while done < len {
+            let pos = offset + done as u64;
+            let in_block = (pos % bs) as usize;
+            let chunk = (self.block_size - in_block).min(len - done);
+            let block = self.lookup_block(inode, pos / bs)?;
+            if block == 0 {
+                buf[done..done + chunk].fill(0);
+            } else {
+                let data = self.read_block(block)?;
+                buf[done..done + chunk].copy_from_slice(&data[in_block..in_block + chunk]);
+            }
+            done += chunk;
+        }
+        Ok(len)
+    }
+
+    /// 파일 데이터 쓰기
+    ///
+    /// 쓰는 범위의 블록만 할당하므로 파일 끝 너머에 쓰면 그 사이는 구멍이 됩니다.
+    /// 공간이 중간에 모자라면 쓴 만큼만 반환합니다. 오류가 나도 inode의 블록 포인터가
+    /// 바뀌었을 수 있으므로 호출자는 항상 inode를 기록해야 합니다.
+    pub(super) fn write_data(&mut self, inode: &mut Inode, goal: u32, offset: u64, data: &[u8]) -> FsResult<usize> {
+        let end = offset.checked_add(data.len() as u64).ok_or(FsError::OutOfSpace)?;
+        if !self.sb.has_large_file() && end > MAX_SMALL_FILE {
+            return Err(FsError::OutOfSpace);
+        }
+        let bs = self.block_size as u64;
+        let mut done = 0;
+        while done < data.len() {
+            let pos = offset + done as u64;
+            let in_block = (pos % bs) as usize;
+            let chunk = (self.block_size - in_block).min(data.len() - done);
+            let existing = self.lookup_block(inode, pos / bs)?;
+            let block = if existing != 0 {
+                existing
+            } else {
+                match self.map_block(inode, pos / bs, Some(goal)) {
+                    Ok(block) => block,
+                    Err(FsError::OutOfSpace) if done > 0 => break,
+                    Err(e) => return Err(e),
+                }
+            };
+            let mut buf = if existing == 0 || chunk == self.block_size {
+                vec![0u8; self.block_size]
+            } else {
+                let mut buf = self.read_block(block)?;
+                // 이전 파일 끝 뒤의 바이트는 0으로 보여야 함
+                let block_start = pos - in_block as u64;
+                if inode.size < block_start + bs {
+                    buf[inode.size.saturating_sub(block_start) as usize..].fill(0);
+                }
+                buf
+            };
+            buf[in_block..in_block + chunk].copy_from_slice(&data[done..done + chunk]);
+            self.write_block(block, &buf)?;
+            done += chunk;
+            inode.size = inode.size.max(pos + chunk as u64);
+        }
+        Ok(done)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::layout::S_IFREG;
+    use super::super::tests::{format_image, mounted};
+    use super::*;
+    use crate::fs::vfs::FileSystem;
+
+    #[test_case]
+    fn test_ext2_indirect_mapping() {
+        let mut fs = mounted(format_image(0));
+        // 1 KiB 블록: 간접 블록 하나에 포인터 256개
+        assert_eq!(fs.block_path(11), Ok((11, vec![])));
+        assert_eq!(fs.block_path(12), Ok((IND_BLOCK, vec![0])));
+        assert_eq!(fs.block_path(267), Ok((IND_BLOCK, vec![255])));
+        assert_eq!(fs.block_path(268), Ok((DIND_BLOCK, vec![0, 0])));
+        assert_eq!(fs.block_path(268 + 256 + 3), Ok((DIND_BLOCK, vec![1, 3])));
+        assert_eq!(fs.block_path(268 + 65536), Ok((TIND_BLOCK, vec![0, 0, 0])));
+        assert_eq!(fs.block_path(268 + 65536 + (1 << 24)), Err(FsError::OutOfSpace));
+
+        // 직접, 1중 간접, 2중 간접 범위에 하나씩 쓰고 나머지는 구멍
+        let free = fs.sb.free_blocks;
+        let mut inode = Inode::new(S_IFREG | 0o644, 0, 0, 0);
+        for (index, byte) in [(0u64, 1u8), (100, 2), (300, 3)] {
+            assert_eq!(fs.write_data(&mut inode, 0, index * 1024 + 5, &[byte; 10]), Ok(10));
+        }
+        assert_eq!(inode.size, 300 * 1024 + 15);
+        // 데이터 3 + 1중 간접 1 + 2중 간접 2 (최상위와 그 아래 하나)
+        assert_eq!(fs.sb.free_blocks, free - 6);
+        assert_eq!(inode.sectors, 6 * 2);
+
+        let single = fs.read_block(inode.block[IND_BLOCK]).unwrap();
+        assert_eq!(fs.lookup_block(&inode, 100), Ok(get_u32(&single, (100 - 12) * 4)));
+        let top = fs.read_block(inode.block[DIND_BLOCK]).unwrap();
+        let second = fs.read_block(get_u32(&top, 0)).unwrap();
+        assert_eq!(fs.lookup_block(&inode, 300), Ok(get_u32(&second, (300 - 268) * 4)));
+        assert_eq!(fs.lookup_block(&inode, 299), Ok(0));
+
+        let mut buf = [0xFFu8; 20];
+        assert_eq!(fs.read_data(&inode, 300 * 1024, &mut buf), Ok(15));
+        assert_eq!(buf[..5], [0; 5]);
+        assert_eq!(buf[5..15], [3; 10]);
+        assert_eq!(fs.read_data(&inode, 200 * 1024, &mut buf), Ok(20));
+        assert_eq!(buf, [0; 20]);
+
+        fs.free_inode_blocks(&mut inode).unwrap();
+        assert_eq!(fs.sb.free_blocks, free);
+        fs.unmount().unwrap();
+    }
+}
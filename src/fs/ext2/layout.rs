@@ -0,0 +1,489 @@
+//! ext2 디스크 형식
+//!
+//! 모든 다중 바이트 필드는 리틀 엔디언입니다. 슈퍼블록은 블록 크기와 관계없이 디스크의
+//! 1024번째 바이트부터 1024바이트이고, 블록 그룹 디스크립터 테이블은 슈퍼블록 다음
+//! 블록(`first_data_block + 1`)부터 시작합니다.
+//!
+//! 디코딩한 구조체는 원본 바이트를 함께 들고 있다가 인코딩할 때 아는 필드만 덮어씁니다.
+//! 이 드라이버가 해석하지 않는 필드(UUID, 확장 inode 영역 등)는 그대로 보존됩니다.
+
+use crate::fs::vfs::{FileType, FsError, FsResult};
+
+/// 슈퍼블록 위치와 크기 (바이트)
+pub const SUPERBLOCK_OFFSET: u64 = 1024;
+pub const SUPERBLOCK_SIZE: usize = 1024;
+
+/// 슈퍼블록 매직 (`s_magic`)
+pub const MAGIC: u16 = 0xEF53;
+
+/// 루트 디렉토리 inode 번호
+pub const ROOT_INODE: u32 = 2;
+
+/// rev 0 파일시스템의 첫 일반 inode와 inode 크기
+const GOOD_OLD_FIRST_INO: u32 = 11;
+const GOOD_OLD_INODE_SIZE: usize = 128;
+
+/// `s_state`: 깨끗하게 언마운트됨 / 오류 발견됨
+pub const STATE_VALID: u16 = 0x0001;
+pub const STATE_ERROR: u16 = 0x0002;
+
+/// incompat 기능: 디렉토리 항목에 파일 종류 저장
+pub const INCOMPAT_FILETYPE: u32 = 0x0002;
+/// 이 드라이버가 이해하는 incompat 기능 (나머지가 켜져 있으면 마운트 거부)
+pub const INCOMPAT_SUPPORTED: u32 = INCOMPAT_FILETYPE;
+
+/// ro_compat 기능: 일부 그룹에만 슈퍼블록 백업 / 2 GiB 이상 파일
+pub const RO_COMPAT_SPARSE_SUPER: u32 = 0x0001;
+pub const RO_COMPAT_LARGE_FILE: u32 = 0x0002;
+/// 쓰기를 지원하는 ro_compat 기능 (나머지가 켜져 있으면 읽기 전용)
+pub const RO_COMPAT_SUPPORTED: u32 = RO_COMPAT_SPARSE_SUPER | RO_COMPAT_LARGE_FILE;
+
+/// 블록 그룹 디스크립터 크기
+pub const GROUP_DESC_SIZE: usize = 32;
+
+/// inode 구조 중 이 드라이버가 해석하는 부분 (rev 0 inode 전체)
+pub const INODE_BASE_SIZE: usize = 128;
+
+/// 직접 블록 포인터 수와 간접 포인터 위치 (`i_block[12..15]`)
+pub const DIRECT_BLOCKS: usize = 12;
+pub const IND_BLOCK: usize = 12;
+pub const DIND_BLOCK: usize = 13;
+pub const TIND_BLOCK: usize = 14;
+pub const N_BLOCKS: usize = 15;
+
+/// 빠른 심볼릭 링크: 대상 경로를 `i_block`에 직접 저장할 수 있는 최대 길이
+pub const FAST_SYMLINK_MAX: usize = N_BLOCKS * 4 - 1;
+
+/// `i_flags`: 해시 인덱스 디렉토리 (인덱스를 갱신하지 않으므로 디렉토리를 바꾸면 해제)
+pub const INDEX_FL: u32 = 0x0000_1000;
+
+/// 파일 종류 비트 (`i_mode`)
+pub const S_IFMT: u16 = 0o170000;
+pub const S_IFSOCK: u16 = 0o140000;
+pub const S_IFLNK: u16 = 0o120000;
+pub const S_IFREG: u16 = 0o100000;
+pub const S_IFBLK: u16 = 0o060000;
+pub const S_IFDIR: u16 = 0o040000;
+pub const S_IFCHR: u16 = 0o020000;
+pub const S_IFIFO: u16 = 0o010000;
+/// 권한 비트 (setuid/setgid/sticky 포함)
+pub const PERM_MASK: u16 = 0o7777;
+
+/// 디렉토리 항목 헤더 크기 (inode 4, rec_len 2, name_len 1, file_type 1)
+pub const DIRENT_HEADER: usize = 8;
+/// 디렉토리 항목 이름 최대 길이
+pub const MAX_NAME_LEN: usize = 255;
+
+/// 확장 속성 블록 매직 (`h_magic`)
+pub const XATTR_MAGIC: u32 = 0xEA02_0000;
+
+pub fn get_u16(buf: &[u8], off: usize) -> u16 {
+    u16::from_le_bytes([buf[off], buf[off + 1]])
+}
+
+pub fn get_u32(buf: &[u8], off: usize) -> u32 {
+    let mut bytes = [0u8; 4];
+    bytes.copy_from_slice(&buf[off..off + 4]);
+    u32::from_le_bytes(bytes)
+}
+
+pub fn put_u16(buf: &mut [u8], off: usize, value: u16) {
+    buf[off..off + 2].copy_from_slice(&value.to_le_bytes());
+}
+
+pub fn put_u32(buf: &mut [u8], off: usize, value: u32) {
+    buf[off..off + 4].copy_from_slice(&value.to_le_bytes());
+}
+
+/// 슈퍼블록
+#[derive(Clone)]
+pub struct Superblock {
+    raw: [u8; SUPERBLOCK_SIZE],
+    pub inodes_count: u32,
+    pub blocks_count: u32,
+    pub free_blocks: u32,
+    pub free_inodes: u32,
+    pub first_data_block: u32,
+    pub block_size: usize,
+    pub blocks_per_group: u32,
+    pub inodes_per_group: u32,
+    pub mount_time: u32,
+    pub write_time: u32,
+    pub mount_count: u16,
+    pub state: u16,
+    pub first_ino: u32,
+    pub inode_size: usize,
+    pub feature_compat: u32,
+    pub feature_incompat: u32,
+    pub feature_ro_compat: u32,
+}
+
+impl Superblock {
+    /// 1024바이트 슈퍼블록 해석 (매직과 기하 구조 검증, 기능 플래그는 `check_features`)
+    pub fn decode(buf: &[u8]) -> Option<Self> {
+        if buf.len() < SUPERBLOCK_SIZE || get_u16(buf, 56) != MAGIC {
+            return None;
+        }
+        let mut raw = [0u8; SUPERBLOCK_SIZE];
+        raw.copy_from_slice(&buf[..SUPERBLOCK_SIZE]);
+        let log_block_size = get_u32(buf, 24);
+        if log_block_size > 2 {
+            // 1, 2, 4 KiB만 지원
+            return None;
+        }
+        let rev_level = get_u32(buf, 76);
+        let (first_ino, inode_size) = if rev_level == 0 {
+            (GOOD_OLD_FIRST_INO, GOOD_OLD_INODE_SIZE)
+        } else {
+            (get_u32(buf, 84), get_u16(buf, 88) as usize)
+        };
+        let sb = Self {
+            raw,
+            inodes_count: get_u32(buf, 0),
+            blocks_count: get_u32(buf, 4),
+            free_blocks: get_u32(buf, 12),
+            free_inodes: get_u32(buf, 16),
+            first_data_block: get_u32(buf, 20),
+            block_size: 1024 << log_block_size,
+            blocks_per_group: get_u32(buf, 32),
+            inodes_per_group: get_u32(buf, 40),
+            mount_time: get_u32(buf, 44),
+            write_time: get_u32(buf, 48),
+            mount_count: get_u16(buf, 52),
+            state: get_u16(buf, 58),
+            first_ino,
+            inode_size,
+            feature_compat: if rev_level == 0 { 0 } else { get_u32(buf, 92) },
+            feature_incompat: if rev_level == 0 { 0 } else { get_u32(buf, 96) },
+            feature_ro_compat: if rev_level == 0 { 0 } else { get_u32(buf, 100) },
+        };
+        let valid = sb.blocks_per_group > 0
+            && sb.blocks_per_group as usize <= sb.block_size * 8
+            && sb.inodes_per_group > 0
+            && sb.inodes_per_group as usize <= sb.block_size * 8
+            && sb.first_data_block < sb.blocks_count
+            && sb.inode_size >= INODE_BASE_SIZE
+            && sb.inode_size <= sb.block_size
+            && sb.inode_size.is_power_of_two()
+            && sb.inodes_count as u64 <= sb.group_count() as u64 * sb.inodes_per_group as u64
+            && sb.first_ino > ROOT_INODE;
+        valid.then_some(sb)
+    }
+
+    /// 모르는 incompat 기능이 있으면 `InvalidFilesystem`
+    pub fn check_features(&self) -> FsResult<()> {
+        if self.feature_incompat & !INCOMPAT_SUPPORTED != 0 {
+            return Err(FsError::InvalidFilesystem);
+        }
+        Ok(())
+    }
+
+    /// 쓰기를 지원하지 않는 ro_compat 기능 비트 (0이면 읽기/쓰기 가능)
+    pub fn unsupported_ro_compat(&self) -> u32 {
+        self.feature_ro_compat & !RO_COMPAT_SUPPORTED
+    }
+
+    pub fn has_filetype(&self) -> bool {
+        self.feature_incompat & INCOMPAT_FILETYPE != 0
+    }
+
+    pub fn has_large_file(&self) -> bool {
+        self.feature_ro_compat & RO_COMPAT_LARGE_FILE != 0
+    }
+
+    /// 블록 그룹 수
+    pub fn group_count(&self) -> u32 {
+        (self.blocks_count - self.first_data_block).div_ceil(self.blocks_per_group)
+    }
+
+    /// 볼륨 이름 (`s_volume_name`)
+    pub fn label(&self) -> &str {
+        let name = &self.raw[120..136];
+        let len = name.iter().position(|&b| b == 0).unwrap_or(name.len());
+        core::str::from_utf8(&name[..len]).unwrap_or("")
+    }
+
+    /// 바뀐 필드를 원본에 덮어쓴 1024바이트
+    pub fn encode(&self) -> [u8; SUPERBLOCK_SIZE] {
+        let mut buf = self.raw;
+        put_u32(&mut buf, 12, self.free_blocks);
+        put_u32(&mut buf, 16, self.free_inodes);
+        put_u32(&mut buf, 44, self.mount_time);
+        put_u32(&mut buf, 48, self.write_time);
+        put_u16(&mut buf, 52, self.mount_count);
+        put_u16(&mut buf, 58, self.state);
+        buf
+    }
+}
+
+/// 블록 그룹 디스크립터
+#[derive(Debug, Clone, Copy)]
+pub struct GroupDesc {
+    pub block_bitmap: u32,
+    pub inode_bitmap: u32,
+    pub inode_table: u32,
+    pub free_blocks: u16,
+    pub free_inodes: u16,
+    pub used_dirs: u16,
+}
+
+impl GroupDesc {
+    pub fn decode(buf: &[u8]) -> Self {
+        Self {
+            block_bitmap: get_u32(buf, 0),
+            inode_bitmap: get_u32(buf, 4),
+            inode_table: get_u32(buf, 8),
+            free_blocks: get_u16(buf, 12),
+            free_inodes: get_u16(buf, 14),
+            used_dirs: get_u16(buf, 16),
+        }
+    }
+
+    /// 카운터 필드만 덮어씀 (위치 필드는 바뀌지 않음)
+    pub fn encode(&self, buf: &mut [u8]) {
+        put_u16(buf, 12, self.free_blocks);
+        put_u16(buf, 14, self.free_inodes);
+        put_u16(buf, 16, self.used_dirs);
+    }
+}
+
+/// inode (앞 128바이트)
+#[derive(Debug, Clone)]
+pub struct Inode {
+    pub mode: u16,
+    pub uid: u32,
+    pub gid: u32,
+    pub size: u64,
+    pub atime: u32,
+    pub ctime: u32,
+    pub mtime: u32,
+    pub dtime: u32,
+    pub links: u16,
+    /// 할당된 512바이트 섹터 수 (간접 블록과 xattr 블록 포함)
+    pub sectors: u32,
+    pub flags: u32,
+    pub block: [u32; N_BLOCKS],
+    pub file_acl: u32,
+}
+
+impl Inode {
+    /// 새 inode (블록 없음, 링크 수 1)
+    pub fn new(mode: u16, uid: u32, gid: u32, now: u32) -> Self {
+        Self {
+            mode,
+            uid,
+            gid,
+            size: 0,
+            atime: now,
+            ctime: now,
+            mtime: now,
+            dtime: 0,
+            links: 1,
+            sectors: 0,
+            flags: 0,
+            block: [0; N_BLOCKS],
+            file_acl: 0,
+        }
+    }
+
+    pub fn decode(buf: &[u8]) -> Self {
+        let mode = get_u16(buf, 0);
+        let mut block = [0u32; N_BLOCKS];
+        for (i, slot) in block.iter_mut().enumerate() {
+            *slot = get_u32(buf, 40 + i * 4);
+        }
+        // i_size_high는 일반 파일에서만 크기 상위 32비트 (디렉토리에서는 i_dir_acl)
+        let size_high = if mode & S_IFMT == S_IFREG { get_u32(buf, 108) as u64 } else { 0 };
+        Self {
+            mode,
+            uid: get_u16(buf, 2) as u32 | (get_u16(buf, 120) as u32) << 16,
+            gid: get_u16(buf, 24) as u32 | (get_u16(buf, 122) as u32) << 16,
+            size: get_u32(buf, 4) as u64 | size_high << 32,
+            atime: get_u32(buf, 8),
+            ctime: get_u32(buf, 12),
+            mtime: get_u32(buf, 16),
+            dtime: get_u32(buf, 20),
+            links: get_u16(buf, 26),
+            sectors: get_u32(buf, 28),
+            flags: get_u32(buf, 32),
+            block,
+            file_acl: get_u32(buf, 104),
+        }
+    }
+
+    /// 앞 128바이트에 기록 (나머지 바이트는 보존)
+    pub fn encode(&self, buf: &mut [u8]) {
+        put_u16(buf, 0, self.mode);
+        put_u16(buf, 2, self.uid as u16);
+        put_u32(buf, 4, self.size as u32);
+        put_u32(buf, 8, self.atime);
+        put_u32(buf, 12, self.ctime);
+        put_u32(buf, 16, self.mtime);
+        put_u32(buf, 20, self.dtime);
+        put_u16(buf, 24, self.gid as u16);
+        put_u16(buf, 26, self.links);
+        put_u32(buf, 28, self.sectors);
+        put_u32(buf, 32, self.flags);
+        for (i, &block) in self.block.iter().enumerate() {
+            put_u32(buf, 40 + i * 4, block);
+        }
+        put_u32(buf, 104, self.file_acl);
+        if self.is_regular() {
+            put_u32(buf, 108, (self.size >> 32) as u32);
+        }
+        put_u16(buf, 120, (self.uid >> 16) as u16);
+        put_u16(buf, 122, (self.gid >> 16) as u16);
+    }
+
+    pub fn is_dir(&self) -> bool {
+        self.mode & S_IFMT == S_IFDIR
+    }
+
+    pub fn is_regular(&self) -> bool {
+        self.mode & S_IFMT == S_IFREG
+    }
+
+    pub fn is_symlink(&self) -> bool {
+        self.mode & S_IFMT == S_IFLNK
+    }
+
+    /// 대상 경로가 `i_block`에 들어 있는 심볼릭 링크 (데이터 블록 없음)
+    pub fn is_fast_symlink(&self, block_size: usize) -> bool {
+        let xattr_sectors = if self.file_acl != 0 { (block_size / 512) as u32 } else { 0 };
+        self.is_symlink() && self.sectors == xattr_sectors
+    }
+
+    /// `i_block` 배열을 바이트로 (빠른 심볼릭 링크 대상)
+    pub fn block_bytes(&self) -> [u8; N_BLOCKS * 4] {
+        let mut bytes = [0u8; N_BLOCKS * 4];
+        for (chunk, block) in bytes.chunks_exact_mut(4).zip(self.block.iter()) {
+            chunk.copy_from_slice(&block.to_le_bytes());
+        }
+        bytes
+    }
+
+    /// 바이트를 `i_block` 배열로 (남는 부분은 0)
+    pub fn set_block_bytes(&mut self, data: &[u8]) {
+        let mut bytes = [0u8; N_BLOCKS * 4];
+        bytes[..data.len()].copy_from_slice(data);
+        for (block, chunk) in self.block.iter_mut().zip(bytes.chunks_exact(4)) {
+            *block = get_u32(chunk, 0);
+        }
+    }
+
+    pub fn file_type(&self) -> FileType {
+        mode_file_type(self.mode)
+    }
+}
+
+/// `i_mode`의 파일 종류
+pub fn mode_file_type(mode: u16) -> FileType {
+    match mode & S_IFMT {
+        S_IFDIR => FileType::Directory,
+        S_IFLNK => FileType::Symlink,
+        S_IFCHR => FileType::Character,
+        S_IFBLK => FileType::Block,
+        S_IFIFO => FileType::Fifo,
+        S_IFSOCK => FileType::Socket,
+        _ => FileType::Regular,
+    }
+}
+
+/// 디렉토리 항목의 `file_type` 값
+pub fn dirent_type(file_type: FileType) -> u8 {
+    match file_type {
+        FileType::Regular => 1,
+        FileType::Directory => 2,
+        FileType::Character => 3,
+        FileType::Block => 4,
+        FileType::Fifo => 5,
+        FileType::Socket => 6,
+        FileType::Symlink => 7,
+    }
+}
+
+/// `file_type` 값의 파일 종류 (0이나 모르는 값은 `None`, inode를 읽어야 함)
+pub fn dirent_file_type(value: u8) -> Option<FileType> {
+    Some(match value {
+        1 => FileType::Regular,
+        2 => FileType::Directory,
+        3 => FileType::Character,
+        4 => FileType::Block,
+        5 => FileType::Fifo,
+        6 => FileType::Socket,
+        7 => FileType::Symlink,
+        _ => return None,
+    })
+}
+
+/// 이름 길이 `name_len`인 디렉토리 항목의 최소 `rec_len` (4바이트 정렬)
+pub fn dirent_size(name_len: usize) -> usize {
+    (DIRENT_HEADER + name_len + 3) & !3
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// mke2fs 기본값과 같은 4 MiB, 1 KiB 블록 파일시스템의 슈퍼블록
+    fn superblock() -> [u8; SUPERBLOCK_SIZE] {
+        let mut buf = [0u8; SUPERBLOCK_SIZE];
+        put_u32(&mut buf, 0, 1024);
+        put_u32(&mut buf, 4, 4096);
+        put_u32(&mut buf, 20, 1);
+        put_u32(&mut buf, 24, 0);
+        put_u32(&mut buf, 32, 8192);
+        put_u32(&mut buf, 40, 1024);
+        put_u16(&mut buf, 56, MAGIC);
+        put_u16(&mut buf, 58, STATE_VALID);
+        put_u32(&mut buf, 76, 1);
+        put_u32(&mut buf, 84, 11);
+        put_u16(&mut buf, 88, 256);
+        put_u32(&mut buf, 92, 0x38);
+        put_u32(&mut buf, 96, INCOMPAT_FILETYPE);
+        put_u32(&mut buf, 100, RO_COMPAT_SPARSE_SUPER | RO_COMPAT_LARGE_FILE);
+        buf[120..124].copy_from_slice(b"test");
+        buf
+    }
+
+    #[test_case]
+    fn test_ext2_superblock_features() {
+        let mut buf = superblock();
+        let sb = Superblock::decode(&buf).unwrap();
+        assert_eq!(sb.block_size, 1024);
+        assert_eq!(sb.group_count(), 1);
+        assert_eq!(sb.label(), "test");
+        assert!(sb.check_features().is_ok());
+        assert_eq!(sb.unsupported_ro_compat(), 0);
+
+        // extents (ext4)는 읽을 수 없으므로 거부
+        put_u32(&mut buf, 96, INCOMPAT_FILETYPE | 0x40);
+        assert_eq!(Superblock::decode(&buf).unwrap().check_features(), Err(FsError::InvalidFilesystem));
+        // metadata_csum은 읽기만 가능
+        put_u32(&mut buf, 96, INCOMPAT_FILETYPE);
+        put_u32(&mut buf, 100, RO_COMPAT_SPARSE_SUPER | 0x400);
+        assert_eq!(Superblock::decode(&buf).unwrap().unsupported_ro_compat(), 0x400);
+
+        put_u16(&mut buf, 56, 0);
+        assert!(Superblock::decode(&buf).is_none());
+    }
+
+    #[test_case]
+    fn test_ext2_inode_roundtrip() {
+        let mut inode = Inode::new(S_IFREG | 0o640, 70000, 1000, 1_700_000_000);
+        inode.size = 5 << 30;
+        inode.block[0] = 1234;
+        let mut buf = [0xAAu8; 256];
+        inode.encode(&mut buf);
+        let decoded = Inode::decode(&buf);
+        assert_eq!((decoded.uid, decoded.gid, decoded.size), (70000, 1000, 5 << 30));
+        assert_eq!(decoded.block[0], 1234);
+        // 128바이트 뒤 확장 영역은 그대로
+        assert_eq!(buf[128], 0xAA);
+
+        let mut link = Inode::new(S_IFLNK | 0o777, 0, 0, 0);
+        link.set_block_bytes(b"../target");
+        assert!(link.is_fast_symlink(1024));
+        assert_eq!(&link.block_bytes()[..9], b"../target");
+    }
+}
@@ -0,0 +1,905 @@
+//! ext2 파일시스템
+//!
+//! Linux 호스트와 디스크 이미지를 주고받기 위한 드라이버입니다. 빌드 호스트에서
+//! `mke2fs -t ext2`로 만든 이미지(`scripts/mkext2-image.sh`)를 그대로 마운트하며,
+//! FAT32에서 잃어버리는 uid/gid/모드와 심볼릭 링크를 보존합니다.
+//!
+//! - `layout`: 슈퍼블록, 블록 그룹 디스크립터, inode, 디렉토리 항목의 디스크 형식
+//! - `bitmap`: 블록/inode 할당자
+//! - `inode`: 직접/간접 블록 매핑과 파일 데이터 입출력
+//! - `dir`: 선형 디렉토리 (htree 디렉토리는 선형으로 읽고 바꾸면 색인 해제)
+//!
+//! 모르는 incompat 기능(extents, 64bit, journal 재생 필요 등)이 켜져 있으면 마운트를
+//! 거부하고, 모르는 ro_compat 기능이 있으면 읽기 전용으로 엽니다. 저널이 없으므로
+//! 비정상 종료 후에는 호스트에서 `e2fsck`로 검사해야 합니다. 읽기는 접근 시간을
+//! 갱신하지 않습니다 (noatime).
+
+mod bitmap;
+mod dir;
+mod inode;
+pub mod layout;
+
+pub use layout::Superblock;
+
+use self::layout::{
+    GroupDesc, Inode, FAST_SYMLINK_MAX, GROUP_DESC_SIZE, PERM_MASK, ROOT_INODE, STATE_ERROR, STATE_VALID,
+    SUPERBLOCK_OFFSET, SUPERBLOCK_SIZE, S_IFDIR, S_IFLNK, S_IFREG,
+};
+use crate::drivers::ata::BlockDevice;
+use crate::fs::cache::{self, FileKey, PAGE_SIZE};
+use crate::fs::path::{Path, PathComponent};
+use crate::fs::vfs::{Directory, File, FileMetadata, FileMode, FileSystem, FileType, FsError, FsResult, FsStats, Offset};
+use crate::security::user::{get_current_gid, get_current_uid};
+use crate::time::wallclock;
+use alloc::boxed::Box;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+
+/// 경로 하나를 해석하며 따라갈 수 있는 심볼릭 링크 수
+const MAX_SYMLINK_FOLLOWS: usize = 8;
+
+/// 슈퍼블록을 담은 앞부분 읽기 (섹터 크기에 맞춰 0..2048 바이트 이상)
+fn read_superblock(device: &mut dyn BlockDevice) -> FsResult<Superblock> {
+    let sector = device.block_size();
+    if sector == 0 || !sector.is_power_of_two() {
+        return Err(FsError::InvalidFilesystem);
+    }
+    let span = (SUPERBLOCK_OFFSET as usize + SUPERBLOCK_SIZE).max(sector);
+    let mut buf = vec![0u8; span];
+    for (i, chunk) in buf.chunks_mut(sector).enumerate() {
+        device.read_block(i as u64, chunk).map_err(|_| FsError::IOError)?;
+    }
+    let start = SUPERBLOCK_OFFSET as usize;
+    Superblock::decode(&buf[start..start + SUPERBLOCK_SIZE]).ok_or(FsError::InvalidFilesystem)
+}
+
+/// 파일시스템 블록 하나 읽기 (디바이스 섹터 여러 개)
+fn read_raw(device: &mut dyn BlockDevice, block_size: usize, block: u64, buf: &mut [u8]) -> FsResult<()> {
+    let sector = device.block_size();
+    let per_block = (block_size / sector) as u64;
+    for (i, chunk) in buf[..block_size].chunks_mut(sector).enumerate() {
+        device.read_block(block * per_block + i as u64, chunk).map_err(|_| FsError::IOError)?;
+    }
+    Ok(())
+}
+
+/// 파일시스템 블록 하나 쓰기
+fn write_raw(device: &mut dyn BlockDevice, block_size: usize, block: u64, buf: &[u8]) -> FsResult<()> {
+    let sector = device.block_size();
+    let per_block = (block_size / sector) as u64;
+    for (i, chunk) in buf[..block_size].chunks(sector).enumerate() {
+        device.write_block(block * per_block + i as u64, chunk).map_err(|_| FsError::IOError)?;
+    }
+    Ok(())
+}
+
+/// ext2 인스턴스
+pub struct Ext2 {
+    device: Box<dyn BlockDevice>,
+    sb: Superblock,
+    block_size: usize,
+    groups: Vec<GroupDesc>,
+    /// 블록 그룹 디스크립터 테이블 원본 (카운터만 고쳐 다시 씀)
+    gdt: Vec<u8>,
+    mounted: bool,
+    /// 모르는 ro_compat 기능이 있어 쓰기 금지
+    read_only: bool,
+    /// 마운트 전 `s_state` (언마운트 때 되돌림)
+    mount_state: u16,
+    /// 슈퍼블록/그룹 디스크립터 카운터가 바뀜 (`modify`가 끝날 때 기록)
+    meta_dirty: bool,
+    /// 페이지 캐시 인스턴스 번호
+    cache_id: u32,
+}
+
+impl Ext2 {
+    /// 디바이스의 ext2 열기 (슈퍼블록과 기능 플래그 검증)
+    ///
+    /// # Arguments
+    /// * `device` - 블록 디바이스
+    ///
+    /// # Returns
+    /// 마운트 전 파일시스템, 모르는 incompat 기능이 있으면 `InvalidFilesystem`
+    pub fn new(mut device: Box<dyn BlockDevice>) -> FsResult<Self> {
+        let sb = read_superblock(&mut *device)?;
+        if let Err(e) = sb.check_features() {
+            crate::log_warn!(
+                "ext2: unsupported incompat features {:#x}, refusing to mount",
+                sb.feature_incompat & !layout::INCOMPAT_SUPPORTED
+            );
+            return Err(e);
+        }
+        let block_size = sb.block_size;
+        let sector = device.block_size();
+        if block_size % sector != 0 || sb.blocks_count as u64 > device.num_blocks() / (block_size / sector) as u64 {
+            return Err(FsError::InvalidFilesystem);
+        }
+
+        // 그룹 디스크립터 테이블은 슈퍼블록이 든 블록 바로 다음
+        let group_count = sb.group_count() as usize;
+        let gdt_blocks = (group_count * GROUP_DESC_SIZE).div_ceil(block_size);
+        let mut gdt = vec![0u8; gdt_blocks * block_size];
+        for (i, chunk) in gdt.chunks_mut(block_size).enumerate() {
+            read_raw(&mut *device, block_size, (sb.first_data_block + 1) as u64 + i as u64, chunk)?;
+        }
+        let groups: Vec<GroupDesc> =
+            gdt.chunks(GROUP_DESC_SIZE).take(group_count).map(GroupDesc::decode).collect();
+        let table_blocks = (sb.inodes_per_group as usize * sb.inode_size).div_ceil(block_size) as u32;
+        let in_range = |block: u32, len: u32| block > sb.first_data_block && block as u64 + len as u64 <= sb.blocks_count as u64;
+        if !groups.iter().all(|g| {
+            in_range(g.block_bitmap, 1) && in_range(g.inode_bitmap, 1) && in_range(g.inode_table, table_blocks)
+        }) {
+            return Err(FsError::InvalidFilesystem);
+        }
+
+        let read_only = sb.unsupported_ro_compat() != 0;
+        if read_only {
+            crate::log_warn!("ext2: unsupported ro_compat features {:#x}, read-only", sb.unsupported_ro_compat());
+        }
+        let mount_state = sb.state;
+        Ok(Self {
+            device,
+            sb,
+            block_size,
+            groups,
+            gdt,
+            mounted: false,
+            read_only,
+            mount_state,
+            meta_dirty: false,
+            cache_id: cache::new_cache_id(),
+        })
+    }
+
+    /// 디바이스에 ext2 슈퍼블록이 있는지 확인 (ext3/ext4도 매직이 같음)
+    pub fn probe(device: &mut dyn BlockDevice) -> bool {
+        read_superblock(device).is_ok()
+    }
+
+    /// 슈퍼블록 참조 반환 (용량, 레이블 조회용)
+    pub fn superblock(&self) -> &Superblock {
+        &self.sb
+    }
+
+    /// 파일시스템 블록 읽기 (범위 밖 블록 번호는 손상으로 취급)
+    fn read_block(&mut self, block: u32) -> FsResult<Vec<u8>> {
+        if block == 0 || block >= self.sb.blocks_count {
+            return Err(FsError::InvalidFilesystem);
+        }
+        let mut buf = vec![0u8; self.block_size];
+        read_raw(&mut *self.device, self.block_size, block as u64, &mut buf)?;
+        Ok(buf)
+    }
+
+    fn write_block(&mut self, block: u32, buf: &[u8]) -> FsResult<()> {
+        if block == 0 || block >= self.sb.blocks_count {
+            return Err(FsError::InvalidFilesystem);
+        }
+        write_raw(&mut *self.device, self.block_size, block as u64, buf)
+    }
+
+    /// 슈퍼블록 기록 (슈퍼블록이 든 블록을 읽어 1024바이트만 교체)
+    fn write_superblock(&mut self) -> FsResult<()> {
+        let block = SUPERBLOCK_OFFSET / self.block_size as u64;
+        let offset = SUPERBLOCK_OFFSET as usize % self.block_size;
+        let mut buf = vec![0u8; self.block_size];
+        read_raw(&mut *self.device, self.block_size, block, &mut buf)?;
+        buf[offset..offset + SUPERBLOCK_SIZE].copy_from_slice(&self.sb.encode());
+        write_raw(&mut *self.device, self.block_size, block, &buf)
+    }
+
+    /// 슈퍼블록과 그룹 디스크립터 테이블 기록
+    ///
+    /// 다른 그룹의 백업 사본은 갱신하지 않습니다 (Linux와 같음, `e2fsck`가 맞춤).
+    fn flush_meta(&mut self) -> FsResult<()> {
+        self.sb.write_time = wallclock::now_unix() as u32;
+        self.write_superblock()?;
+        for (desc, raw) in self.groups.iter().zip(self.gdt.chunks_mut(GROUP_DESC_SIZE)) {
+            desc.encode(raw);
+        }
+        let first = self.sb.first_data_block as u64 + 1;
+        for i in 0..self.gdt.len() / self.block_size {
+            let chunk = &self.gdt[i * self.block_size..(i + 1) * self.block_size];
+            write_raw(&mut *self.device, self.block_size, first + i as u64, chunk)?;
+        }
+        self.meta_dirty = false;
+        Ok(())
+    }
+
+    /// 변경 연산 실행
+    ///
+    /// 읽기 전용이면 `ReadOnly`를 반환합니다. 비트맵과 inode는 연산 중에 바로 쓰고,
+    /// 바뀐 카운터는 연산이 끝나면 (실패해도) 기록합니다.
+    fn modify<T>(&mut self, op: impl FnOnce(&mut Self) -> FsResult<T>) -> FsResult<T> {
+        if self.read_only {
+            return Err(FsError::ReadOnly);
+        }
+        let result = op(self);
+        if self.meta_dirty {
+            self.flush_meta()?;
+        }
+        result
+    }
+
+    /// 심볼릭 링크의 대상 경로
+    fn link_target(&mut self, inode: &Inode) -> FsResult<String> {
+        if inode.size as usize >= self.block_size {
+            return Err(FsError::InvalidFilesystem);
+        }
+        let len = inode.size as usize;
+        let target = if inode.is_fast_symlink(self.block_size) {
+            inode.block_bytes()[..len.min(FAST_SYMLINK_MAX)].to_vec()
+        } else {
+            let mut buf = vec![0u8; len];
+            self.read_data(inode, 0, &mut buf)?;
+            buf
+        };
+        Ok(String::from_utf8_lossy(&target).into_owned())
+    }
+
+    /// 경로 구성요소를 따라 inode 찾기
+    ///
+    /// 중간의 심볼릭 링크는 항상 따라가고, 마지막 구성요소는 `follow_last`일 때만 따라갑니다.
+    /// 절대 경로 링크는 이 파일시스템의 루트를 기준으로 해석합니다 (마운트 지점 밖으로
+    /// 나가지 않음). `..`은 디렉토리의 실제 `..` 항목을 따릅니다.
+    fn walk(&mut self, components: &[PathComponent], follow_last: bool) -> FsResult<u32> {
+        // 남은 구성요소 (끝에서부터 꺼냄)
+        let mut pending: Vec<String> = components.iter().rev().map(|c| c.as_str().to_string()).collect();
+        let mut ino = ROOT_INODE;
+        let mut follows = 0;
+        while let Some(name) = pending.pop() {
+            let dir = self.read_inode(ino)?;
+            if !dir.is_dir() {
+                return Err(FsError::NotADirectory);
+            }
+            let next = self.dir_lookup(&dir, &name)?.ok_or(FsError::NotFound)?;
+            let inode = self.read_inode(next)?;
+            if inode.is_symlink() && (follow_last || !pending.is_empty()) {
+                follows += 1;
+                if follows > MAX_SYMLINK_FOLLOWS {
+                    return Err(FsError::InvalidPath);
+                }
+                let target = self.link_target(&inode)?;
+                if target.starts_with('/') {
+                    ino = ROOT_INODE;
+                }
+                let parts = target.split('/').filter(|part| !part.is_empty() && *part != ".");
+                pending.extend(parts.rev().map(String::from));
+                continue;
+            }
+            ino = next;
+        }
+        Ok(ino)
+    }
+
+    fn parse(path: &str) -> FsResult<Path> {
+        Ok(Path::parse(path).map_err(|_| FsError::InvalidPath)?.normalize())
+    }
+
+    /// 경로의 inode 번호
+    fn resolve(&mut self, path: &str, follow_last: bool) -> FsResult<u32> {
+        if !self.mounted {
+            return Err(FsError::InvalidFilesystem);
+        }
+        let path = Self::parse(path)?;
+        self.walk(&path.components, follow_last)
+    }
+
+    /// 경로의 부모 디렉토리 inode와 마지막 이름
+    fn resolve_parent(&mut self, path: &str) -> FsResult<(u32, String)> {
+        if !self.mounted {
+            return Err(FsError::InvalidFilesystem);
+        }
+        let path = Self::parse(path)?;
+        let (name, parents) = path.components.split_last().ok_or(FsError::InvalidPath)?;
+        let parent = self.walk(parents, true)?;
+        Ok((parent, name.as_str().to_string()))
+    }
+
+    fn metadata_of(inode: &Inode) -> FileMetadata {
+        FileMetadata {
+            file_type: inode.file_type(),
+            size: inode.size,
+            mode: FileMode::new((inode.mode & PERM_MASK) as u32),
+            // ext2에는 생성 시간이 없음
+            created: inode.ctime as u64,
+            modified: inode.mtime as u64,
+            accessed: inode.atime as u64,
+            uid: inode.uid,
+            gid: inode.gid,
+        }
+    }
+
+    /// inode의 페이지 캐시 키
+    fn cache_key(&self, ino: u32) -> FileKey {
+        FileKey { fs: self.cache_id, ino: ino as u64 }
+    }
+
+    /// 링크 수가 0이 된 inode 해제
+    fn release_inode(&mut self, ino: u32, inode: &mut Inode) -> FsResult<()> {
+        let is_dir = inode.is_dir();
+        self.free_inode_blocks(inode)?;
+        inode.links = 0;
+        inode.dtime = wallclock::now_unix() as u32;
+        self.write_inode(ino, inode)?;
+        self.free_inode(ino, is_dir)
+    }
+
+    /// 디렉토리 `parent`에 새 inode 만들고 항목 추가
+    ///
+    /// 새 항목은 현재 사용자 소유입니다. `init`은 inode를 기록하기 전에 내용을 채웁니다
+    /// (디렉토리의 `.`/`..` 블록, 심볼릭 링크 대상).
+    fn create_node(
+        &mut self,
+        parent: u32,
+        name: &str,
+        mode: u16,
+        init: impl FnOnce(&mut Self, u32, &mut Inode) -> FsResult<()>,
+    ) -> FsResult<u32> {
+        dir::validate_name(name)?;
+        self.modify(|fs| {
+            let mut parent_inode = fs.read_inode(parent)?;
+            if !parent_inode.is_dir() {
+                return Err(FsError::NotADirectory);
+            }
+            if fs.dir_lookup(&parent_inode, name)?.is_some() {
+                return Err(FsError::AlreadyExists);
+            }
+            let now = wallclock::now_unix() as u32;
+            let is_dir = mode & layout::S_IFMT == S_IFDIR;
+            let ino = fs.alloc_inode(parent, is_dir)?;
+            let mut inode = Inode::new(mode, get_current_uid(), get_current_gid(), now);
+            let mut result = init(fs, ino, &mut inode);
+            if result.is_ok() {
+                result = fs.init_inode(ino, &inode);
+            }
+            if result.is_ok() {
+                result = fs.dir_insert(&mut parent_inode, fs.inode_group(parent), name, ino, inode.file_type());
+            }
+            if let Err(e) = result {
+                fs.release_inode(ino, &mut inode)?;
+                return Err(e);
+            }
+            if is_dir {
+                parent_inode.links += 1;
+            }
+            parent_inode.mtime = now;
+            parent_inode.ctime = now;
+            fs.write_inode(parent, &parent_inode)?;
+            Ok(ino)
+        })
+    }
+
+    /// 디렉토리 `parent`에 파일 또는 디렉토리 생성 (모드는 파일 0644, 디렉토리 0755)
+    fn create_entry(&mut self, parent: u32, name: &str, file_type: FileType) -> FsResult<u32> {
+        match file_type {
+            FileType::Directory => self.create_node(parent, name, S_IFDIR | 0o755, |fs, ino, inode| {
+                let block = fs.map_block(inode, 0, Some(fs.inode_group(ino)))?;
+                let data = fs.dot_block(ino, parent);
+                fs.write_block(block, &data)?;
+                inode.size = fs.block_size as u64;
+                inode.links = 2;
+                Ok(())
+            }),
+            _ => self.create_node(parent, name, S_IFREG | 0o644, |_, _, _| Ok(())),
+        }
+    }
+
+    /// 디렉토리 `parent`에서 항목 삭제 (디렉토리는 비어 있어야 함)
+    ///
+    /// 다른 하드 링크가 남은 파일은 링크 수만 줄입니다.
+    fn remove_node(&mut self, parent: u32, name: &str) -> FsResult<()> {
+        self.modify(|fs| {
+            let mut parent_inode = fs.read_inode(parent)?;
+            if !parent_inode.is_dir() {
+                return Err(FsError::NotADirectory);
+            }
+            let ino = fs.dir_lookup(&parent_inode, name)?.ok_or(FsError::NotFound)?;
+            let mut inode = fs.read_inode(ino)?;
+            if inode.is_dir() {
+                if !fs.dir_is_empty(&inode)? {
+                    return Err(FsError::Busy);
+                }
+                parent_inode.links -= 1;
+            }
+            fs.dir_remove(&parent_inode, name)?;
+            let now = wallclock::now_unix() as u32;
+            if inode.is_dir() || inode.links <= 1 {
+                fs.release_inode(ino, &mut inode)?;
+            } else {
+                inode.links -= 1;
+                inode.ctime = now;
+                fs.write_inode(ino, &inode)?;
+            }
+            parent_inode.mtime = now;
+            parent_inode.ctime = now;
+            fs.write_inode(parent, &parent_inode)
+        })
+    }
+
+    /// 항목 이름 변경 또는 다른 디렉토리로 이동
+    ///
+    /// 대상 이름이 이미 있으면 `AlreadyExists`, 디렉토리를 자기 하위로 옮기려 하면
+    /// `InvalidPath`를 반환합니다. 새 항목을 먼저 추가하므로 공간이 모자라도 원래
+    /// 항목은 남습니다.
+    fn rename_node(&mut self, old_parent: u32, old_name: &str, new_parent: u32, new_name: &str) -> FsResult<()> {
+        dir::validate_name(new_name)?;
+        if old_parent == new_parent && old_name == new_name {
+            return Ok(());
+        }
+        self.modify(|fs| {
+            let mut old_dir = fs.read_inode(old_parent)?;
+            if !old_dir.is_dir() {
+                return Err(FsError::NotADirectory);
+            }
+            let ino = fs.dir_lookup(&old_dir, old_name)?.ok_or(FsError::NotFound)?;
+            let mut inode = fs.read_inode(ino)?;
+            let mut new_dir = if new_parent == old_parent { None } else { Some(fs.read_inode(new_parent)?) };
+            let target = new_dir.as_ref().unwrap_or(&old_dir);
+            if !target.is_dir() {
+                return Err(FsError::NotADirectory);
+            }
+            if fs.dir_lookup(target, new_name)?.is_some() {
+                return Err(FsError::AlreadyExists);
+            }
+            if inode.is_dir() && new_dir.is_some() {
+                // 새 부모에서 `..`을 따라 루트까지 올라가며 자기 자신을 만나면 순환
+                let mut ancestor = new_parent;
+                while ancestor != ROOT_INODE {
+                    if ancestor == ino {
+                        return Err(FsError::InvalidPath);
+                    }
+                    let dir = fs.read_inode(ancestor)?;
+                    ancestor = fs.dir_lookup(&dir, "..")?.ok_or(FsError::InvalidFilesystem)?;
+                }
+            }
+
+            let now = wallclock::now_unix() as u32;
+            match new_dir.as_mut() {
+                Some(new_dir) => {
+                    fs.dir_insert(new_dir, fs.inode_group(new_parent), new_name, ino, inode.file_type())?;
+                    fs.dir_remove(&old_dir, old_name)?;
+                    if inode.is_dir() {
+                        fs.dir_set_parent(&inode, new_parent)?;
+                        old_dir.links -= 1;
+                        new_dir.links += 1;
+                    }
+                    new_dir.mtime = now;
+                    new_dir.ctime = now;
+                    fs.write_inode(new_parent, new_dir)?;
+                }
+                None => {
+                    fs.dir_insert(&mut old_dir, fs.inode_group(old_parent), new_name, ino, inode.file_type())?;
+                    fs.dir_remove(&old_dir, old_name)?;
+                }
+            }
+            old_dir.mtime = now;
+            old_dir.ctime = now;
+            fs.write_inode(old_parent, &old_dir)?;
+            inode.ctime = now;
+            fs.write_inode(ino, &inode)
+        })
+    }
+
+    /// 권한 비트 변경 (소유자 또는 root만 가능)
+    ///
+    /// # Arguments
+    /// * `path` - 대상 경로
+    /// * `mode` - 새 권한 비트 (예: `0o640`, 파일 종류 비트는 무시)
+    pub fn chmod(&mut self, path: &str, mode: u32) -> FsResult<()> {
+        let ino = self.resolve(path, true)?;
+        self.modify(|fs| {
+            let mut inode = fs.read_inode(ino)?;
+            let uid = get_current_uid();
+            if uid != 0 && uid != inode.uid {
+                return Err(FsError::PermissionDenied);
+            }
+            inode.mode = (inode.mode & !PERM_MASK) | (mode as u16 & PERM_MASK);
+            inode.ctime = wallclock::now_unix() as u32;
+            fs.write_inode(ino, &inode)
+        })
+    }
+
+    /// 소유자와 그룹 변경 (root만 가능)
+    pub fn chown(&mut self, path: &str, uid: u32, gid: u32) -> FsResult<()> {
+        if get_current_uid() != 0 {
+            return Err(FsError::PermissionDenied);
+        }
+        let ino = self.resolve(path, true)?;
+        self.modify(|fs| {
+            let mut inode = fs.read_inode(ino)?;
+            inode.uid = uid;
+            inode.gid = gid;
+            inode.ctime = wallclock::now_unix() as u32;
+            fs.write_inode(ino, &inode)
+        })
+    }
+}
+
+impl FileSystem for Ext2 {
+    fn mount(&mut self) -> FsResult<()> {
+        if self.mounted {
+            return Err(FsError::Busy);
+        }
+        self.sb = read_superblock(&mut *self.device)?;
+        if self.sb.state & STATE_VALID == 0 || self.sb.state & STATE_ERROR != 0 {
+            crate::log_warn!("ext2: '{}' was not cleanly unmounted or has errors, run e2fsck", self.sb.label());
+        }
+        if !self.read_only {
+            // 마운트 중에는 s_state의 VALID를 지워 비정상 종료를 표시
+            self.mount_state = self.sb.state;
+            self.sb.state &= !STATE_VALID;
+            self.sb.mount_count = self.sb.mount_count.wrapping_add(1);
+            self.sb.mount_time = wallclock::now_unix() as u32;
+            self.write_superblock()?;
+        }
+        self.mounted = true;
+        Ok(())
+    }
+
+    fn unmount(&mut self) -> FsResult<()> {
+        if !self.mounted {
+            return Err(FsError::InvalidFilesystem);
+        }
+        if !self.read_only {
+            self.sb.state = self.mount_state;
+            self.flush_meta()?;
+        }
+        cache::invalidate_fs(self.cache_id);
+        self.mounted = false;
+        Ok(())
+    }
+
+    fn open_file(&mut self, path: &str) -> FsResult<Box<dyn File>> {
+        let ino = self.resolve(path, true)?;
+        if self.read_inode(ino)?.is_dir() {
+            return Err(FsError::IsDirectory);
+        }
+        Ok(Box::new(Ext2File { filesystem: self as *mut Ext2, ino, offset: 0 }))
+    }
+
+    fn create_file(&mut self, path: &str) -> FsResult<Box<dyn File>> {
+        let (parent, name) = self.resolve_parent(path)?;
+        let ino = self.create_entry(parent, &name, FileType::Regular)?;
+        Ok(Box::new(Ext2File { filesystem: self as *mut Ext2, ino, offset: 0 }))
+    }
+
+    fn open_dir(&mut self, path: &str) -> FsResult<Box<dyn Directory>> {
+        let ino = self.resolve(path, true)?;
+        if !self.read_inode(ino)?.is_dir() {
+            return Err(FsError::NotADirectory);
+        }
+        Ok(Box::new(Ext2Directory { filesystem: self as *mut Ext2, ino }))
+    }
+
+    fn create_dir(&mut self, path: &str) -> FsResult<()> {
+        let (parent, name) = self.resolve_parent(path)?;
+        self.create_entry(parent, &name, FileType::Directory).map(|_| ())
+    }
+
+    fn remove(&mut self, path: &str) -> FsResult<()> {
+        let (parent, name) = self.resolve_parent(path)?;
+        self.remove_node(parent, &name)
+    }
+
+    fn rename(&mut self, old_path: &str, new_path: &str) -> FsResult<()> {
+        let (old_parent, old_name) = self.resolve_parent(old_path)?;
+        let (new_parent, new_name) = self.resolve_parent(new_path)?;
+        self.rename_node(old_parent, &old_name, new_parent, &new_name)
+    }
+
+    fn metadata(&mut self, path: &str) -> FsResult<FileMetadata> {
+        let ino = self.resolve(path, true)?;
+        let inode = self.read_inode(ino)?;
+        Ok(Self::metadata_of(&inode))
+    }
+
+    fn symlink(&mut self, target: &str, path: &str) -> FsResult<()> {
+        if target.is_empty() || target.len() >= self.block_size || target.contains('\0') {
+            return Err(FsError::InvalidPath);
+        }
+        let (parent, name) = self.resolve_parent(path)?;
+        self.create_node(parent, &name, S_IFLNK | 0o777, |fs, ino, inode| {
+            if target.len() <= FAST_SYMLINK_MAX {
+                inode.set_block_bytes(target.as_bytes());
+                inode.size = target.len() as u64;
+                return Ok(());
+            }
+            let written = fs.write_data(inode, fs.inode_group(ino), 0, target.as_bytes())?;
+            if written < target.len() {
+                return Err(FsError::OutOfSpace);
+            }
+            Ok(())
+        })
+        .map(|_| ())
+    }
+
+    fn read_link(&mut self, path: &str) -> FsResult<String> {
+        let ino = self.resolve(path, false)?;
+        let inode = self.read_inode(ino)?;
+        if !inode.is_symlink() {
+            return Err(FsError::InvalidPath);
+        }
+        self.link_target(&inode)
+    }
+
+    fn is_mounted(&self) -> bool {
+        self.mounted
+    }
+
+    fn fs_type(&self) -> &'static str {
+        "ext2"
+    }
+
+    fn statfs(&mut self) -> FsResult<FsStats> {
+        Ok(FsStats {
+            block_size: self.block_size as u64,
+            total_blocks: self.sb.blocks_count as u64,
+            free_blocks: self.sb.free_blocks as u64,
+            total_inodes: self.sb.inodes_count as u64,
+            free_inodes: self.sb.free_inodes as u64,
+        })
+    }
+}
+
+/// ext2 파일 핸들
+struct Ext2File {
+    filesystem: *mut Ext2,
+    ino: u32,
+    offset: Offset,
+}
+
+// Safety: 핸들은 파일시스템 계층이 소유한 Ext2를 가리키며, 접근은 상위 파일시스템
+// 락으로 직렬화됩니다 (SjfsFile과 같은 방식).
+unsafe impl Send for Ext2File {}
+unsafe impl Sync for Ext2File {}
+
+impl File for Ext2File {
+    fn read(&mut self, buf: &mut [u8], offset: Option<Offset>) -> FsResult<usize> {
+        let read_offset = offset.unwrap_or(self.offset);
+        if read_offset < 0 {
+            return Ok(0);
+        }
+        let fs = unsafe { &mut *self.filesystem };
+        let inode = fs.read_inode(self.ino)?;
+        let bytes_read = cache::read(fs.cache_key(self.ino), inode.size, read_offset as u64, buf, |index, page| {
+            fs.read_data(&inode, index * PAGE_SIZE as u64, page).map(|_| ())
+        })?;
+        if offset.is_none() {
+            self.offset = read_offset + bytes_read as Offset;
+        }
+        Ok(bytes_read)
+    }
+
+    fn write(&mut self, buf: &[u8], offset: Option<Offset>) -> FsResult<usize> {
+        let write_offset = offset.unwrap_or(self.offset);
+        if write_offset < 0 {
+            return Err(FsError::InvalidPath);
+        }
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        let fs = unsafe { &mut *self.filesystem };
+        let ino = self.ino;
+        let key = fs.cache_key(ino);
+        let written = fs.modify(|fs| {
+            let mut inode = fs.read_inode(ino)?;
+            let result = fs.write_data(&mut inode, fs.inode_group(ino), write_offset as u64, buf);
+            let now = wallclock::now_unix() as u32;
+            inode.mtime = now;
+            inode.ctime = now;
+            fs.write_inode(ino, &inode)?;
+            result
+        });
+        let bytes_written = match written {
+            Ok(bytes_written) => bytes_written,
+            Err(e) => {
+                // 일부 블록은 이미 기록됐을 수 있으므로 캐시를 버림
+                cache::invalidate(key);
+                return Err(e);
+            }
+        };
+        cache::update(key, write_offset as u64, &buf[..bytes_written]);
+        if offset.is_none() {
+            self.offset = write_offset + bytes_written as Offset;
+        }
+        Ok(bytes_written)
+    }
+
+    fn metadata(&self) -> FsResult<FileMetadata> {
+        let fs = unsafe { &mut *self.filesystem };
+        Ok(Ext2::metadata_of(&fs.read_inode(self.ino)?))
+    }
+
+    fn size(&self) -> FsResult<u64> {
+        let fs = unsafe { &mut *self.filesystem };
+        Ok(fs.read_inode(self.ino)?.size)
+    }
+
+    fn seek(&mut self, offset: Offset) -> FsResult<Offset> {
+        // 파일 끝 너머로 이동 가능 (다음 쓰기가 사이를 구멍으로 남김)
+        if offset < 0 {
+            return Err(FsError::InvalidPath);
+        }
+        self.offset = offset;
+        Ok(offset)
+    }
+
+    fn tell(&self) -> FsResult<Offset> {
+        Ok(self.offset)
+    }
+}
+
+/// ext2 디렉토리 핸들
+struct Ext2Directory {
+    filesystem: *mut Ext2,
+    ino: u32,
+}
+
+unsafe impl Send for Ext2Directory {}
+unsafe impl Sync for Ext2Directory {}
+
+impl Directory for Ext2Directory {
+    fn read_dir(&self) -> FsResult<Vec<(String, FileType)>> {
+        let fs = unsafe { &mut *self.filesystem };
+        let dir = fs.read_inode(self.ino)?;
+        let entries = fs.dir_entries(&dir)?;
+        Ok(entries.into_iter().map(|(name, _, file_type)| (name, file_type)).collect())
+    }
+
+    fn create_file(&mut self, name: &str) -> FsResult<()> {
+        let fs = unsafe { &mut *self.filesystem };
+        fs.create_entry(self.ino, name, FileType::Regular).map(|_| ())
+    }
+
+    fn create_dir(&mut self, name: &str) -> FsResult<()> {
+        let fs = unsafe { &mut *self.filesystem };
+        fs.create_entry(self.ino, name, FileType::Directory).map(|_| ())
+    }
+
+    fn remove(&mut self, name: &str) -> FsResult<()> {
+        let fs = unsafe { &mut *self.filesystem };
+        fs.remove_node(self.ino, name)
+    }
+
+    fn rename(&mut self, old_name: &str, new_name: &str) -> FsResult<()> {
+        let fs = unsafe { &mut *self.filesystem };
+        fs.rename_node(self.ino, old_name, self.ino, new_name)
+    }
+}
+
+#[cfg(test)]
+pub(super) mod tests {
+    use super::layout::{put_u16, put_u32, INCOMPAT_FILETYPE, MAGIC, RO_COMPAT_LARGE_FILE, RO_COMPAT_SPARSE_SUPER};
+    use super::*;
+    use crate::fs::journal::tests::MemDevice;
+    use alloc::format;
+
+    /// 테스트 이미지: 1 KiB 블록 2048개, 그룹 하나, inode 64개 (128바이트)
+    const BLOCKS: u32 = 2048;
+    const INODES: u32 = 64;
+    /// 블록 1 슈퍼블록, 2 그룹 디스크립터, 3 블록 비트맵, 4 inode 비트맵, 5..=12 inode 테이블
+    const INODE_TABLE: u32 = 5;
+    /// 루트 디렉토리 데이터 블록 (여기까지 사용 중)
+    const ROOT_BLOCK: u32 = 13;
+
+    /// 블록 `block`에 `data`를 씀 (1 KiB 블록 = 섹터 2개)
+    fn put_block(device: &mut MemDevice, block: u32, data: &[u8]) {
+        write_raw(device, 1024, block as u64, data).unwrap();
+    }
+
+    /// mke2fs와 같은 배치의 빈 ext2 이미지 (루트 디렉토리만 있음)
+    ///
+    /// # Arguments
+    /// * `incompat` - 슈퍼블록의 incompat 기능 비트
+    pub(super) fn format_image(incompat: u32) -> MemDevice {
+        let mut device = MemDevice::new(BLOCKS as u64 * 2);
+        let used_blocks = ROOT_BLOCK;
+        let used_inodes = 10;
+
+        let mut sb = vec![0u8; 1024];
+        put_u32(&mut sb, 0, INODES);
+        put_u32(&mut sb, 4, BLOCKS);
+        put_u32(&mut sb, 12, BLOCKS - 1 - used_blocks);
+        put_u32(&mut sb, 16, INODES - used_inodes);
+        put_u32(&mut sb, 20, 1);
+        put_u32(&mut sb, 32, 8192);
+        put_u32(&mut sb, 40, INODES);
+        put_u16(&mut sb, 56, MAGIC);
+        put_u16(&mut sb, 58, STATE_VALID);
+        put_u32(&mut sb, 76, 1);
+        put_u32(&mut sb, 84, 11);
+        put_u16(&mut sb, 88, 128);
+        put_u32(&mut sb, 96, incompat);
+        put_u32(&mut sb, 100, RO_COMPAT_SPARSE_SUPER | RO_COMPAT_LARGE_FILE);
+        put_block(&mut device, 1, &sb);
+
+        let mut gdt = vec![0u8; 1024];
+        put_u32(&mut gdt, 0, 3);
+        put_u32(&mut gdt, 4, 4);
+        put_u32(&mut gdt, 8, INODE_TABLE);
+        put_u16(&mut gdt, 12, (BLOCKS - 1 - used_blocks) as u16);
+        put_u16(&mut gdt, 14, (INODES - used_inodes) as u16);
+        put_u16(&mut gdt, 16, 1);
+        put_block(&mut device, 2, &gdt);
+
+        // 비트 n은 블록 n + 1, inode n + 1
+        let mut bitmap = vec![0u8; 1024];
+        (0..used_blocks as usize).for_each(|bit| bitmap[bit / 8] |= 1 << (bit % 8));
+        put_block(&mut device, 3, &bitmap);
+        bitmap.fill(0);
+        (0..used_inodes as usize).for_each(|bit| bitmap[bit / 8] |= 1 << (bit % 8));
+        put_block(&mut device, 4, &bitmap);
+
+        let mut root = Inode::new(S_IFDIR | 0o755, 0, 0, 0);
+        root.links = 2;
+        root.size = 1024;
+        root.sectors = 2;
+        root.block[0] = ROOT_BLOCK;
+        let mut table = vec![0u8; 1024];
+        root.encode(&mut table[128..256]);
+        put_block(&mut device, INODE_TABLE, &table);
+
+        let mut dir = vec![0u8; 1024];
+        for (offset, rec_len, name) in [(0, 12, &b"."[..]), (12, 1012, &b".."[..])] {
+            put_u32(&mut dir, offset, ROOT_INODE);
+            put_u16(&mut dir, offset + 4, rec_len);
+            dir[offset + 6] = name.len() as u8;
+            dir[offset + 7] = 2;
+            dir[offset + 8..offset + 8 + name.len()].copy_from_slice(name);
+        }
+        put_block(&mut device, ROOT_BLOCK, &dir);
+        device
+    }
+
+    /// 이미지를 열고 마운트
+    pub(super) fn mounted(device: MemDevice) -> Ext2 {
+        let mut fs = Ext2::new(Box::new(device)).unwrap();
+        fs.mount().unwrap();
+        fs
+    }
+
+    #[test_case]
+    fn test_ext2_rejects_unknown_incompat() {
+        assert!(Ext2::new(Box::new(format_image(INCOMPAT_FILETYPE))).is_ok());
+        // extents (ext4)
+        assert_eq!(Ext2::new(Box::new(format_image(INCOMPAT_FILETYPE | 0x40))).err(), Some(FsError::InvalidFilesystem));
+        // journal 재생 필요 (ext3)
+        assert_eq!(Ext2::new(Box::new(format_image(0x04))).err(), Some(FsError::InvalidFilesystem));
+    }
+
+    #[test_case]
+    fn test_ext2_fast_and_slow_symlinks() {
+        let mut fs = mounted(format_image(INCOMPAT_FILETYPE));
+        fs.create_dir("/d").unwrap();
+        fs.create_dir("/d/e").unwrap();
+        assert_eq!(fs.create_file("/d/e/leaf").unwrap().write(b"leaf", None), Ok(4));
+        let leaf = fs.resolve("/d/e/leaf", true).unwrap();
+
+        // 짧은 상대 경로는 i_block에, 60바이트 이상은 데이터 블록에 저장
+        fs.symlink("d/e", "/fast").unwrap();
+        let slow_target = format!("/d{}/e/leaf", "/e/..".repeat(12));
+        assert!(slow_target.len() > FAST_SYMLINK_MAX);
+        fs.symlink(&slow_target, "/d/slow").unwrap();
+        let fast = fs.resolve("/fast", false).unwrap();
+        let fast = fs.read_inode(fast).unwrap();
+        assert!(fast.is_fast_symlink(1024));
+        assert_eq!(fast.sectors, 0);
+        let slow = fs.resolve("/d/slow", false).unwrap();
+        let slow = fs.read_inode(slow).unwrap();
+        assert!(!slow.is_fast_symlink(1024));
+        assert_eq!(fs.read_link("/d/slow"), Ok(slow_target));
+
+        // 중간 링크는 항상, 마지막 링크는 follow_last일 때만 따라감
+        assert_eq!(fs.resolve("/fast/leaf", false), Ok(leaf));
+        assert_eq!(fs.resolve("/d/slow", true), Ok(leaf));
+        assert_ne!(fs.resolve("/d/slow", false), Ok(leaf));
+        // 절대 경로 링크는 이 파일시스템의 루트 기준, `..`은 실제 부모
+        assert_eq!(fs.resolve("/d/e/../../fast/leaf", true), Ok(leaf));
+
+        fs.symlink("loop", "/loop").unwrap();
+        assert_eq!(fs.resolve("/loop", true), Err(FsError::InvalidPath));
+        fs.unmount().unwrap();
+    }
+}
@@ -2,12 +2,22 @@
 //!
 //! 이 모듈은 FAT32 파일시스템의 읽기/쓰기 기능을 제공합니다.
 //! 현재는 읽기 전용으로 시작하며, 향후 쓰기 기능을 추가할 예정입니다.
+//!
+//! 디렉토리 항목은 VFAT 긴 파일명(LFN)을 지원합니다. 8.3 형식으로 그대로 표현되지
+//! 않는 이름은 `~N` 꼬리가 붙은 짧은 이름과 함께 LFN 엔트리로 기록하며, 찾기는 긴
+//! 이름과 짧은 이름 모두 대소문자 구분 없이 합니다.
+//!
+//! FAT 엔트리와 디렉토리 엔트리 갱신은 예약 영역(부트 섹터와 FAT 사이)의 빈 섹터에 둔
+//! 저널을 거쳐 기록합니다. 파일 생성, 삭제, 이름 변경, 쓰기 한 번이 트랜잭션 하나이며,
+//! 마운트할 때 비정상 종료로 남은 트랜잭션을 먼저 재생합니다. 저널은
+//! `Fat32FileSystem::format_journal`로 명시적으로 만든 볼륨에서만 씁니다.
 
-use super::vfs::{FileSystem, File, Directory, FileMetadata, FileType, FileMode, FsResult, FsError, Offset};
-use super::journal::{begin_transaction, add_entry, commit, checkpoint, rollback, JournalEntryType};
+use super::vfs::{FileSystem, File, Directory, FileMetadata, FileType, FileMode, FsResult, FsError, FsStats, Offset};
+use super::cache::{self, FileKey, PAGE_SIZE};
+use super::journal::{self, Journal, JournalEntry, JournalEntryType, JournalState, MIN_JOURNAL_BLOCKS};
 use crate::drivers::ata::{BlockDevice, BlockDeviceError};
+use crate::time::wallclock::{self, DateTime};
 use alloc::vec::Vec;
-use alloc::string::ToString;
 use alloc::string::String;
 use alloc::boxed::Box;
 use core::mem::size_of;
@@ -124,6 +134,52 @@ impl Fat32BootSector {
     }
 }
 
+/// FAT 날짜/시간 필드를 Unix 시각(초)으로 변환
+///
+/// FAT 타임스탬프는 로컬 시간으로 저장되므로 현재 시간대 오프셋을 빼서 UTC로 변환합니다.
+/// 날짜가 0이면 (기록되지 않음) 0을 반환합니다.
+///
+/// - 날짜: bits 15-9 = 연도 - 1980, bits 8-5 = 월, bits 4-0 = 일
+/// - 시간: bits 15-11 = 시, bits 10-5 = 분, bits 4-0 = 초 / 2
+/// - `tenths`: 10ms 단위 (0..=199), 2초 단위 시간을 보정
+pub fn fat_to_unix(date: u16, time: u16, tenths: u8) -> u64 {
+    if date == 0 {
+        return 0;
+    }
+    let datetime = DateTime {
+        year: 1980 + (date >> 9),
+        month: ((date >> 5) & 0x0F) as u8,
+        day: (date & 0x1F) as u8,
+        hour: (time >> 11) as u8,
+        minute: ((time >> 5) & 0x3F) as u8,
+        second: ((time & 0x1F) * 2) as u8,
+    };
+    if !datetime.is_valid() {
+        return 0;
+    }
+    wallclock::local_to_unix(datetime.to_unix()) + tenths as u64 / 100
+}
+
+/// Unix 시각(초)을 FAT (날짜, 시간, 10ms 단위) 필드로 변환
+///
+/// FAT가 표현할 수 있는 범위(1980..=2107년) 밖이면 경계값으로 고정합니다.
+pub fn unix_to_fat(unix_secs: u64) -> (u16, u16, u8) {
+    let local = wallclock::unix_to_local(unix_secs);
+    let dt = DateTime::from_unix(local);
+    if dt.year < 1980 {
+        // 1980-01-01 00:00:00
+        return ((1 << 5) | 1, 0, 0);
+    }
+    if dt.year > 2107 {
+        // 2107-12-31 23:59:58
+        return ((127 << 9) | (12 << 5) | 31, (23 << 11) | (59 << 5) | 29, 0);
+    }
+    let date = ((dt.year - 1980) << 9) | ((dt.month as u16) << 5) | dt.day as u16;
+    let time = ((dt.hour as u16) << 11) | ((dt.minute as u16) << 5) | (dt.second as u16 / 2);
+    let tenths = (dt.second % 2) * 100;
+    (date, time, tenths)
+}
+
 /// FAT32 디렉토리 엔트리 (8.3 형식)
 #[repr(C, packed)]
 #[derive(Debug, Clone, Copy)]
@@ -184,41 +240,362 @@ impl Fat32DirEntry {
         (self.attributes & Self::ATTR_VOLUME_ID) != 0
     }
     
+    /// 생성 시각 (Unix 시각, 초)
+    pub fn created(&self) -> u64 {
+        fat_to_unix(self.create_date, self.create_time, self.create_time_tenth)
+    }
+    
+    /// 수정 시각 (Unix 시각, 초)
+    pub fn modified(&self) -> u64 {
+        fat_to_unix(self.write_date, self.write_time, 0)
+    }
+    
+    /// 접근 시각 (Unix 시각, 초, 날짜 단위)
+    pub fn accessed(&self) -> u64 {
+        fat_to_unix(self.access_date, 0, 0)
+    }
+    
+    /// 생성/수정/접근 시각을 모두 `unix_secs`로 설정
+    pub fn set_created(&mut self, unix_secs: u64) {
+        let (date, time, tenth) = unix_to_fat(unix_secs);
+        self.create_date = date;
+        self.create_time = time;
+        self.create_time_tenth = tenth;
+        self.write_date = date;
+        self.write_time = time;
+        self.access_date = date;
+    }
+    
+    /// 수정/접근 시각을 `unix_secs`로 설정
+    pub fn set_modified(&mut self, unix_secs: u64) {
+        let (date, time, _) = unix_to_fat(unix_secs);
+        self.write_date = date;
+        self.write_time = time;
+        self.access_date = date;
+    }
+    
     /// 첫 번째 클러스터 번호 가져오기
     pub fn first_cluster(&self) -> u32 {
         ((self.first_cluster_high as u32) << 16) | (self.first_cluster_low as u32)
     }
     
+    /// 32바이트 디스크 표현에서 읽기
+    pub fn from_bytes(bytes: &[u8]) -> Self {
+        assert!(bytes.len() >= size_of::<Self>());
+        unsafe { core::ptr::read_unaligned(bytes.as_ptr() as *const Self) }
+    }
+    
+    /// 32바이트 디스크 표현으로 변환
+    pub fn to_bytes(&self) -> [u8; 32] {
+        unsafe { core::mem::transmute(*self) }
+    }
+    
     /// 파일명을 문자열로 변환 (8.3 형식)
     pub fn short_name(&self) -> String {
-        let mut name = String::new();
-        
-        // 파일명 (8자)
-        let mut i = 0;
-        while i < 8 && self.name[i] != b' ' && self.name[i] != 0 {
-            name.push(self.name[i] as char);
+        format_8_3(&self.name)
+    }
+}
+
+/// 11바이트 8.3 이름을 "NAME.EXT" 형태로 변환
+fn format_8_3(raw: &[u8; 11]) -> String {
+    let mut name = String::new();
+    
+    // 파일명 (8자)
+    let mut i = 0;
+    while i < 8 && raw[i] != b' ' && raw[i] != 0 {
+        name.push(raw[i] as char);
+        i += 1;
+    }
+    
+    // 확장자 (3자)
+    if raw[8] != b' ' && raw[8] != 0 {
+        name.push('.');
+        i = 8;
+        while i < 11 && raw[i] != b' ' && raw[i] != 0 {
+            name.push(raw[i] as char);
             i += 1;
         }
-        
-        // 확장자 (3자)
-        if self.name[8] != b' ' && self.name[8] != 0 {
-            name.push('.');
-            i = 8;
-            while i < 11 && self.name[i] != b' ' && self.name[i] != 0 {
-                name.push(self.name[i] as char);
-                i += 1;
+    }
+    
+    name
+}
+
+/// VFAT 긴 파일명(LFN) 엔트리
+///
+/// 짧은 엔트리 바로 앞에 마지막 조각부터 역순으로 저장되며, 엔트리 하나에
+/// UCS-2 문자 13개가 들어갑니다. `checksum`은 짝이 되는 짧은 이름의 체크섬입니다.
+#[repr(C, packed)]
+#[derive(Debug, Clone, Copy)]
+pub struct Fat32LfnEntry {
+    // Sequence number (1-based, 0x40 on the last piece)
+    pub order: u8,
+    // Characters 1-5
+    pub name1: [u16; 5],
+    // Always ATTR_LONG_NAME
+    pub attributes: u8,
+    // Always 0
+    pub entry_type: u8,
+    // Checksum of the short name
+    pub checksum: u8,
+    // Characters 6-11
+    pub name2: [u16; 6],
+    // Always 0
+    pub first_cluster_low: u16,
+    // Characters 12-13
+    pub name3: [u16; 2],
+}
+
+impl Fat32LfnEntry {
+    /// 마지막 조각 표시 (디스크에서는 가장 앞)
+    pub const LAST_ENTRY: u8 = 0x40;
+    /// 엔트리당 문자 수
+    pub const CHARS_PER_ENTRY: usize = 13;
+    /// 긴 이름 최대 길이 (UCS-2 문자)
+    pub const MAX_NAME_LEN: usize = 255;
+    
+    /// 순번 `order`의 조각 생성 (`chars`는 최대 13자, 남는 칸은 0 하나 뒤 0xFFFF)
+    fn new(order: u8, chars: &[u16], checksum: u8) -> Self {
+        let mut all = [0xFFFFu16; Self::CHARS_PER_ENTRY];
+        all[..chars.len()].copy_from_slice(chars);
+        if chars.len() < Self::CHARS_PER_ENTRY {
+            all[chars.len()] = 0;
+        }
+        let mut name1 = [0u16; 5];
+        let mut name2 = [0u16; 6];
+        let mut name3 = [0u16; 2];
+        name1.copy_from_slice(&all[0..5]);
+        name2.copy_from_slice(&all[5..11]);
+        name3.copy_from_slice(&all[11..13]);
+        Self {
+            order,
+            name1,
+            attributes: Fat32DirEntry::ATTR_LONG_NAME,
+            entry_type: 0,
+            checksum,
+            name2,
+            first_cluster_low: 0,
+            name3,
+        }
+    }
+    
+    /// 이 조각의 문자 13개
+    fn chars(&self) -> [u16; 13] {
+        let (name1, name2, name3) = (self.name1, self.name2, self.name3);
+        let mut all = [0u16; Self::CHARS_PER_ENTRY];
+        all[0..5].copy_from_slice(&name1);
+        all[5..11].copy_from_slice(&name2);
+        all[11..13].copy_from_slice(&name3);
+        all
+    }
+    
+    /// 32바이트 디스크 표현에서 읽기
+    pub fn from_bytes(bytes: &[u8]) -> Self {
+        assert!(bytes.len() >= size_of::<Self>());
+        unsafe { core::ptr::read_unaligned(bytes.as_ptr() as *const Self) }
+    }
+    
+    /// 32바이트 디스크 표현으로 변환
+    pub fn to_bytes(&self) -> [u8; 32] {
+        unsafe { core::mem::transmute(*self) }
+    }
+}
+
+/// 짧은 이름의 LFN 체크섬
+pub fn lfn_checksum(short_name: &[u8; 11]) -> u8 {
+    short_name.iter().fold(0u8, |sum, &b| ((sum & 1) << 7).wrapping_add(sum >> 1).wrapping_add(b))
+}
+
+/// 긴 이름 검사 후 UCS-2(UTF-16)로 변환
+fn encode_long_name(name: &str) -> FsResult<Vec<u16>> {
+    if name.is_empty() || name == "." || name == ".." {
+        return Err(FsError::InvalidPath);
+    }
+    if name.chars().any(|c| (c as u32) < 0x20 || "\"*/:<>?\\|".contains(c)) {
+        return Err(FsError::InvalidPath);
+    }
+    let units: Vec<u16> = name.encode_utf16().collect();
+    if units.len() > Fat32LfnEntry::MAX_NAME_LEN {
+        return Err(FsError::InvalidPath);
+    }
+    Ok(units)
+}
+
+/// 긴 이름 조각들을 디스크 순서(마지막 조각 먼저)로 생성
+fn long_name_entries(units: &[u16], checksum: u8) -> Vec<Fat32LfnEntry> {
+    let chunks: Vec<&[u16]> = units.chunks(Fat32LfnEntry::CHARS_PER_ENTRY).collect();
+    let count = chunks.len();
+    chunks
+        .iter()
+        .enumerate()
+        .rev()
+        .map(|(i, chunk)| {
+            let mut order = (i + 1) as u8;
+            if i + 1 == count {
+                order |= Fat32LfnEntry::LAST_ENTRY;
+            }
+            Fat32LfnEntry::new(order, chunk, checksum)
+        })
+        .collect()
+}
+
+/// 디렉토리를 읽으며 LFN 조각을 모으는 상태
+///
+/// 조각은 순번이 하나씩 줄어들며 같은 체크섬을 가져야 하고, 순번 1 다음에 오는
+/// 짧은 엔트리의 체크섬과 맞을 때만 긴 이름으로 인정합니다. 어긋난 조각(다른 OS가
+/// 지운 짧은 엔트리의 고아 LFN 등)은 버립니다.
+#[derive(Default)]
+struct LfnParser {
+    units: Vec<u16>,
+    /// 다음에 기대하는 순번 (`None`이면 모으는 중 아님)
+    next: Option<u8>,
+    checksum: u8,
+    /// 첫 조각의 슬롯 번호
+    start: usize,
+}
+
+impl LfnParser {
+    fn reset(&mut self) {
+        self.units.clear();
+        self.next = None;
+    }
+    
+    /// LFN 조각 하나 추가
+    fn push(&mut self, lfn: &Fat32LfnEntry, slot: usize) {
+        let order = lfn.order & 0x1F;
+        if lfn.order & Fat32LfnEntry::LAST_ENTRY != 0 {
+            if order == 0 {
+                self.reset();
+                return;
             }
+            self.units = alloc::vec![0; order as usize * Fat32LfnEntry::CHARS_PER_ENTRY];
+            self.checksum = lfn.checksum;
+            self.start = slot;
+        } else if order == 0 || self.next != Some(order) || lfn.checksum != self.checksum {
+            self.reset();
+            return;
         }
-        
-        name
+        let at = (order as usize - 1) * Fat32LfnEntry::CHARS_PER_ENTRY;
+        self.units[at..at + Fat32LfnEntry::CHARS_PER_ENTRY].copy_from_slice(&lfn.chars());
+        self.next = Some(order - 1);
+    }
+    
+    /// 짧은 엔트리를 만나면 모은 긴 이름과 첫 조각 슬롯 반환 (맞지 않으면 `None`)
+    fn finish(&mut self, short_name: &[u8; 11]) -> Option<(String, usize)> {
+        let complete = self.next == Some(0) && self.checksum == lfn_checksum(short_name);
+        let result = if complete {
+            let len = self.units.iter().position(|&c| c == 0).unwrap_or(self.units.len());
+            let name: String = char::decode_utf16(self.units[..len].iter().copied())
+                .map(|c| c.unwrap_or(char::REPLACEMENT_CHARACTER))
+                .collect();
+            if name.is_empty() { None } else { Some((name, self.start)) }
+        } else {
+            None
+        };
+        self.reset();
+        result
+    }
+}
+
+/// 문자 하나의 단순 대문자 변환 (1:1 매핑만, `ß`처럼 여러 글자가 되면 그대로)
+///
+/// FAT의 대소문자 무시 비교는 UTF-16 코드 단위마다 대문자 표로 바꾸므로 글자 수가
+/// 바뀌는 변환은 하지 않습니다.
+fn simple_uppercase(c: char) -> char {
+    let mut upper = c.to_uppercase();
+    match (upper.next(), upper.next()) {
+        (Some(u), None) => u,
+        _ => c,
+    }
+}
+
+/// 대소문자 구분 없이 이름 비교 (ASCII 밖 문자 포함, 문자 단위)
+fn names_equal(a: &str, b: &str) -> bool {
+    a.chars().map(simple_uppercase).eq(b.chars().map(simple_uppercase))
+}
+
+/// 짧은 이름에 쓸 수 있는 문자로 변환 (쓸 수 없으면 `None`)
+fn short_name_char(c: char) -> Option<u8> {
+    if c.is_ascii_alphanumeric() || "$%'-_@~`!(){}^#&".contains(c) {
+        Some(c.to_ascii_uppercase() as u8)
+    } else {
+        None
+    }
+}
+
+/// 긴 이름에서 짧은 이름의 기본형 생성
+///
+/// 대문자로 바꾸고, 앞의 점과 공백·중간 점을 빼고, 쓸 수 없는 문자는 `_`로 바꾼 뒤
+/// 이름 8자·확장자 3자로 자릅니다.
+///
+/// # Returns
+/// (11바이트 이름, 정보 손실 여부 - 손실되면 `~N` 꼬리가 필요)
+fn short_name_basis(name: &str) -> ([u8; 11], bool) {
+    let mut result = [b' '; 11];
+    let trimmed = name.trim_start_matches('.');
+    let mut lossy = trimmed.len() != name.len();
+    let (base, ext) = match trimmed.rfind('.') {
+        Some(pos) => (&trimmed[..pos], &trimmed[pos + 1..]),
+        None => (trimmed, ""),
+    };
+    for (part, out) in [(base, 0..8), (ext, 8..11)] {
+        let mut at = out.start;
+        for c in part.chars() {
+            if c == ' ' || c == '.' {
+                lossy = true;
+                continue;
+            }
+            if at == out.end {
+                lossy = true;
+                break;
+            }
+            result[at] = short_name_char(c).unwrap_or_else(|| {
+                lossy = true;
+                b'_'
+            });
+            at += 1;
+        }
+    }
+    if result[0] == b' ' {
+        result[0] = b'_';
+        lossy = true;
     }
+    (result, lossy)
+}
+
+/// 기본형에 `~N` 꼬리 붙이기 (꼬리가 들어가도록 이름 부분을 자름)
+fn short_name_with_tail(basis: &[u8; 11], n: u32) -> [u8; 11] {
+    let tail = alloc::format!("~{}", n);
+    let base_len = basis[..8].iter().position(|&b| b == b' ').unwrap_or(8);
+    let keep = base_len.min(8 - tail.len());
+    let mut result = *basis;
+    result[keep..keep + tail.len()].copy_from_slice(tail.as_bytes());
+    result[keep + tail.len()..8].fill(b' ');
+    result
+}
+
+/// LFN을 해석한 디렉토리 항목
+struct DirRecord {
+    /// 표시 이름 (긴 이름이 없으면 8.3 이름)
+    name: String,
+    entry: Fat32DirEntry,
+    /// 첫 LFN 조각의 슬롯 번호 (LFN이 없으면 `slot`과 같음)
+    first_slot: usize,
+    /// 짧은 엔트리의 슬롯 번호 (디렉토리 처음부터 32바이트 단위)
+    slot: usize,
 }
 
+/// 저널 영역 시작 섹터 (Windows 부트 코드가 쓰는 12번 섹터 다음)
+const JOURNAL_FIRST_SECTOR: u64 = 13;
+
 /// FAT32 파일시스템
 pub struct Fat32FileSystem {
     device: Box<dyn BlockDevice>,
     boot_sector: Fat32BootSector,
     mounted: bool,
+    /// 예약 영역의 저널 (저널이 없는 볼륨이면 `None`, 이때는 바로 기록)
+    journal: Option<Journal>,
+    /// 페이지 캐시 인스턴스 번호
+    cache_id: u32,
     // FAT 캐시 (향후 구현)
     // fat_cache: Vec<u32>,
 }
@@ -255,6 +632,8 @@ impl Fat32FileSystem {
             device,
             boot_sector,
             mounted: false,
+            journal: None,
+            cache_id: cache::new_cache_id(),
         })
     }
 
@@ -263,6 +642,19 @@ impl Fat32FileSystem {
         &self.boot_sector
     }
     
+    /// 섹터 읽기 (아직 원래 위치에 기록되지 않은 저널 엔트리가 있으면 그 내용)
+    ///
+    /// 트랜잭션 도중 FAT나 디렉토리를 다시 읽을 때 같은 트랜잭션에서 바꾼 내용을 보게 합니다.
+    fn read_sector(&mut self, sector: u64, buf: &mut [u8; 512]) -> FsResult<()> {
+        if let Some(data) = self.journal.as_ref().and_then(|journal| journal.pending(sector)) {
+            buf.copy_from_slice(data);
+            return Ok(());
+        }
+        self.device.read_block(sector, buf)
+            .map(|_| ())
+            .map_err(|_| FsError::IOError)
+    }
+    
     /// FAT 엔트리 읽기
     /// 
     /// # Arguments
@@ -278,8 +670,7 @@ impl Fat32FileSystem {
         
         // FAT 섹터 읽기
         let mut fat_buf = [0u8; 512];
-        self.device.read_block(fat_sector, &mut fat_buf)
-            .map_err(|_| FsError::IOError)?;
+        self.read_sector(fat_sector, &mut fat_buf)?;
         
         // 엔트리 읽기 (little-endian)
         let entry = u32::from_le_bytes([
@@ -307,53 +698,210 @@ impl Fat32FileSystem {
         
         // FAT 섹터 읽기
         let mut fat_buf = [0u8; 512];
-        self.device.read_block(fat_sector, &mut fat_buf)
-            .map_err(|_| FsError::IOError)?;
+        self.read_sector(fat_sector, &mut fat_buf)?;
         
         // 엔트리 쓰기 (little-endian, 상위 4비트 보존)
-        let bytes = value.to_le_bytes();
-        fat_buf[entry_offset] = bytes[0];
-        fat_buf[entry_offset + 1] = bytes[1];
-        fat_buf[entry_offset + 2] = bytes[2];
-        fat_buf[entry_offset + 3] = bytes[3];
+        let old = u32::from_le_bytes([
+            fat_buf[entry_offset],
+            fat_buf[entry_offset + 1],
+            fat_buf[entry_offset + 2],
+            fat_buf[entry_offset + 3],
+        ]);
+        let bytes = ((old & 0xF0000000) | (value & 0x0FFFFFFF)).to_le_bytes();
+        fat_buf[entry_offset..entry_offset + 4].copy_from_slice(&bytes);
+        
+        // 모든 FAT 복사본의 같은 섹터
+        let sectors: Vec<(u64, &[u8])> = (0..self.boot_sector.num_fats as u64)
+            .map(|fat_index| {
+                let sector = self.boot_sector.fat_start_sector() +
+                    fat_index * self.boot_sector.sectors_per_fat_32 as u64 +
+                    (fat_offset / 512) as u64;
+                (sector, &fat_buf[..])
+            })
+            .collect();
         
-        // 저널링 사용 시 트랜잭션 시작 및 저널에 기록
         if use_journal {
-            // 트랜잭션이 없을 때만 시작
-            if !super::journal::in_transaction() {
-                begin_transaction();
-            }
-            // 첫 번째 FAT만 저널에 기록 (복사본은 체크포인트에서 처리)
-            if let Err(e) = add_entry(fat_sector, &fat_buf, JournalEntryType::MetadataWrite) {
-                rollback();
-                return Err(FsError::IOError);
-            }
+            // 복사본들이 한 트랜잭션으로 함께 바뀜
+            self.write_sectors_journaled(&sectors, JournalEntryType::MetadataWrite)
+        } else {
+            self.write_sectors_direct(&sectors)
         }
-        
-        // 모든 FAT 복사본에 쓰기
-        for fat_index in 0..self.boot_sector.num_fats {
-            let current_fat_sector = self.boot_sector.fat_start_sector() + 
-                (fat_index as u64 * self.boot_sector.sectors_per_fat_32 as u64) +
-                (fat_offset / 512) as u64;
-            
-            // 저널링 사용 시 두 번째 FAT부터는 저널에 기록
-            if use_journal && fat_index > 0 {
-                if let Err(e) = add_entry(current_fat_sector, &fat_buf, JournalEntryType::MetadataWrite) {
-                    rollback();
-                    return Err(FsError::IOError);
-                }
-            }
-            
-            self.device.write_block(current_fat_sector, &fat_buf)
+    }
+    
+    /// write_fat_entry의 기본 버전 (저널링 사용)
+    fn write_fat_entry_journaled(&mut self, cluster: u32, value: u32) -> FsResult<()> {
+        self.write_fat_entry(cluster, value, true)
+    }
+    
+    /// 섹터들을 저널 없이 바로 쓰기
+    fn write_sectors_direct(&mut self, sectors: &[(u64, &[u8])]) -> FsResult<()> {
+        for &(sector, data) in sectors {
+            self.device.write_block(sector, data)
                 .map_err(|_| FsError::IOError)?;
         }
+        Ok(())
+    }
+    
+    /// 연산 하나를 저널 트랜잭션 하나로 실행
+    ///
+    /// 연산이 바꾼 메타데이터 섹터를 모아 한 번에 커밋하므로 도중에 전원이 끊겨도 연산 전체가
+    /// 반영되거나 전혀 반영되지 않습니다. 연산이 실패하면 트랜잭션을 버립니다. 데이터
+    /// 클러스터는 저널을 거치지 않고 커밋 전에 바로 기록됩니다. 트랜잭션은 중첩하지 않습니다.
+    fn transaction<T>(&mut self, op: impl FnOnce(&mut Self) -> FsResult<T>) -> FsResult<T> {
+        self.begin_transaction()?;
+        let result = op(self);
+        self.end_transaction(result)
+    }
+    
+    /// 저널 트랜잭션 시작 (이전 체크포인트가 실패했으면 먼저 마무리)
+    fn begin_transaction(&mut self) -> FsResult<()> {
+        let Some(journal) = self.journal.as_mut() else {
+            return Ok(());
+        };
+        if journal.state() == JournalState::Checkpointing {
+            journal.checkpoint(self.device.as_mut()).map_err(|e| {
+                crate::log_warn!("fat32: journal checkpoint failed: {}", e);
+                FsError::IOError
+            })?;
+        }
+        journal.begin_transaction();
+        if journal.state() != JournalState::Transaction {
+            return Err(FsError::IOError);
+        }
+        Ok(())
+    }
+    
+    /// 연산 결과에 따라 트랜잭션을 커밋하고 체크포인트하거나 버림
+    fn end_transaction<T>(&mut self, result: FsResult<T>) -> FsResult<T> {
+        let Some(journal) = self.journal.as_mut() else {
+            return result;
+        };
+        if result.is_err() {
+            journal.rollback();
+            return result;
+        }
+        let device = self.device.as_mut();
+        journal.commit(device)
+            .and_then(|_| journal.checkpoint(device))
+            .map_err(|e| {
+                crate::log_warn!("fat32: journal write failed: {}", e);
+                FsError::IOError
+            })?;
+        result
+    }
+    
+    /// 섹터들을 저널을 거쳐 쓰기
+    ///
+    /// 진행 중인 트랜잭션이 있으면 거기에 담기만 하고, 없으면 이 섹터들만으로 트랜잭션을
+    /// 하나 만듭니다. 트랜잭션이 저널 용량을 넘으면 나눠 커밋하지 않고 `OutOfSpace`로
+    /// 실패합니다. 저널이 없으면 바로 씁니다.
+    fn write_sectors_journaled(&mut self, sectors: &[(u64, &[u8])], entry_type: JournalEntryType) -> FsResult<()> {
+        let Some(journal) = self.journal.as_mut() else {
+            return self.write_sectors_direct(sectors);
+        };
+        if journal.state() != JournalState::Transaction {
+            return self.transaction(|fs| fs.write_sectors_journaled(sectors, entry_type));
+        }
         
+        for &(sector, data) in sectors {
+            if !journal.has_room_for(sector) {
+                crate::log_warn!("fat32: operation needs more than {} journal sectors", journal.capacity());
+                return Err(FsError::OutOfSpace);
+            }
+            journal.add_entry(sector, data, entry_type).map_err(|e| {
+                crate::log_warn!("fat32: journal entry for sector {} rejected: {}", sector, e);
+                FsError::IOError
+            })?;
+        }
         Ok(())
     }
     
-    /// write_fat_entry의 기본 버전 (저널링 사용)
-    fn write_fat_entry_journaled(&mut self, cluster: u32, value: u32) -> FsResult<()> {
-        self.write_fat_entry(cluster, value, true)
+    /// 예약 영역에서 저널로 쓸 섹터 범위 (시작 섹터, 섹터 수)
+    ///
+    /// 부트 섹터, FSInfo, 백업 부트 섹터(3섹터)와 Windows 부트 코드가 쓰는 12번 섹터를
+    /// 피해 FAT 시작 전까지 사용합니다. 남는 섹터가 모자라면 `None`입니다.
+    pub fn journal_region(&self) -> Option<(u64, u64)> {
+        let fs_info = self.boot_sector.fs_info as u64;
+        let backup_boot = self.boot_sector.backup_boot_sector as u64;
+        let start = JOURNAL_FIRST_SECTOR.max(fs_info + 1).max(backup_boot + 3);
+        let end = self.boot_sector.fat_start_sector();
+        (end >= start + MIN_JOURNAL_BLOCKS).then(|| (start, end - start))
+    }
+    
+    /// 예약 영역의 저널 열기
+    ///
+    /// 저널 헤더가 없으면 저널링 없이 마운트합니다. 비어 있는 예약 섹터라도 다른 도구가
+    /// 쓰는 영역일 수 있으므로 마운트만으로는 저널을 만들지 않습니다 (`format_journal` 참고).
+    ///
+    /// # Returns
+    /// 저널 (영역이 없거나 저널 헤더가 없으면 `None`)
+    fn load_journal(&mut self) -> FsResult<Option<Journal>> {
+        let Some((start, blocks)) = self.journal_region() else {
+            return Ok(None);
+        };
+        match Journal::open(self.device.as_mut(), start, blocks) {
+            Ok(journal) => Ok(Some(journal)),
+            Err(_) => {
+                crate::log_info!("fat32: no journal in reserved sectors {}..{}, journaling disabled", start, start + blocks);
+                Ok(None)
+            }
+        }
+    }
+    
+    /// 예약 영역에 빈 저널 만들기 (mkfs 직후처럼 명시적으로 요청할 때만)
+    ///
+    /// 예약 섹터가 모두 0일 때만 만듭니다. 마운트된 상태면 바로 저널링을 시작합니다.
+    pub fn format_journal(&mut self) -> FsResult<()> {
+        let (start, blocks) = self.journal_region().ok_or(FsError::OutOfSpace)?;
+        let device = self.device.as_mut();
+        if Journal::open(device, start, blocks).is_ok() {
+            return Err(FsError::AlreadyExists);
+        }
+        if !journal::area_is_empty(device, start, blocks).map_err(|_| FsError::IOError)? {
+            crate::log_warn!("fat32: reserved sectors {}..{} in use, not creating a journal", start, start + blocks);
+            return Err(FsError::Busy);
+        }
+        let journal = Journal::format(device, start, blocks).map_err(|_| FsError::IOError)?;
+        if self.mounted {
+            self.journal = Some(journal);
+        }
+        Ok(())
+    }
+    
+    /// 커밋됐지만 원래 위치에 기록되지 않은 저널 엔트리 (디스크는 바꾸지 않음)
+    pub fn pending_journal_entries(&mut self) -> FsResult<Vec<JournalEntry>> {
+        let Some((start, blocks)) = self.journal_region() else {
+            return Ok(Vec::new());
+        };
+        let device = self.device.as_mut();
+        let Ok(mut journal) = Journal::open(device, start, blocks) else {
+            return Ok(Vec::new());
+        };
+        journal.recover(device).map_err(|_| FsError::IOError)
+    }
+    
+    /// 비정상 종료로 남은 저널 트랜잭션 재생
+    ///
+    /// # Returns
+    /// 재생한 트랜잭션 순번 (없으면 `None`)
+    pub fn replay_journal(&mut self) -> FsResult<Option<u64>> {
+        let mut journal = match self.journal.take() {
+            Some(journal) => journal,
+            None => match self.load_journal()? {
+                Some(journal) => journal,
+                None => return Ok(None),
+            },
+        };
+        let replayed = journal.replay(self.device.as_mut());
+        self.journal = Some(journal);
+        let replayed = replayed.map_err(|e| {
+            crate::log_warn!("fat32: journal replay failed: {}", e);
+            FsError::IOError
+        })?;
+        if let Some(sequence) = replayed {
+            crate::log_info!("fat32: replayed journal transaction {}", sequence);
+        }
+        Ok(replayed)
     }
     
     /// 빈 클러스터 찾기
@@ -370,8 +918,7 @@ impl Fat32FileSystem {
         let mut fat_buf = [0u8; 512];
         for sector_offset in 0..sectors_per_fat {
             let fat_sector = fat_start + sector_offset;
-            self.device.read_block(fat_sector, &mut fat_buf)
-                .map_err(|_| FsError::IOError)?;
+            self.read_sector(fat_sector, &mut fat_buf)?;
             
             // 섹터 내의 각 엔트리 확인 (128개 엔트리 = 512바이트 / 4바이트)
             for i in 0..128 {
@@ -405,86 +952,22 @@ impl Fat32FileSystem {
     /// # Arguments
     /// * `cluster` - 클러스터 번호
     /// * `data` - 쓸 데이터 (클러스터 크기)
-    /// * `use_journal` - 저널링 사용 여부 (기본값: false, 데이터는 일반적으로 저널링 안 함)
-    fn write_cluster(&mut self, cluster: u32, data: &[u8], use_journal: bool) -> FsResult<()> {
+    ///
+    /// 데이터 클러스터는 저널을 거치지 않습니다. 클러스터를 가리키는 FAT와 디렉토리 엔트리가
+    /// 트랜잭션으로 커밋되기 전에 기록되므로, 커밋되지 않은 연산의 클러스터는 빈 클러스터로 남습니다.
+    fn write_cluster(&mut self, cluster: u32, data: &[u8]) -> FsResult<()> {
         let cluster_size = self.boot_sector.sectors_per_cluster as usize * 512;
         if data.len() < cluster_size {
             return Err(FsError::IOError);
         }
         
         let sector = self.boot_sector.cluster_to_sector(cluster);
-        let mut offset = 0;
-        
-        // 저널링 사용 시 트랜잭션 시작
-        if use_journal {
-            // 트랜잭션이 없을 때만 시작
-            if !super::journal::in_transaction() {
-                begin_transaction();
-            }
-        }
-        
-        for i in 0..self.boot_sector.sectors_per_cluster {
-            let sector_buf = &data[offset..offset + 512];
-            let current_sector = sector + i as u64;
-            
-            // 저널링 사용 시 저널에 기록
-            if use_journal {
-                if let Err(e) = add_entry(current_sector, sector_buf, JournalEntryType::DataWrite) {
-                    rollback();
-                    return Err(FsError::IOError);
-                }
-            }
-            
-            self.device.write_block(current_sector, sector_buf)
-                .map_err(|_| FsError::IOError)?;
-            offset += 512;
-        }
-        
-        Ok(())
-    }
-    
-    /// write_cluster의 기본 버전 (저널링 사용 안 함)
-    fn write_cluster_direct(&mut self, cluster: u32, data: &[u8]) -> FsResult<()> {
-        self.write_cluster(cluster, data, false)
-    }
-    
-    /// 클러스터 체인 읽기
-    /// 
-    /// # Arguments
-    /// * `start_cluster` - 시작 클러스터 번호
-    /// * `buf` - 데이터를 저장할 버퍼
-    /// 
-    /// # Returns
-    /// 읽은 바이트 수
-    fn read_cluster_chain(&mut self, start_cluster: u32, buf: &mut [u8]) -> FsResult<usize> {
-        let mut cluster = start_cluster;
-        let mut offset = 0;
-        let cluster_size = self.boot_sector.sectors_per_cluster as usize * 512;
-        
-        while cluster >= 2 && cluster < 0x0FFFFFF8 {
-            if offset + cluster_size > buf.len() {
-                break;
-            }
-            
-            // 클러스터를 섹터로 변환
-            let sector = self.boot_sector.cluster_to_sector(cluster);
-            
-            // 클러스터 읽기
-            for i in 0..self.boot_sector.sectors_per_cluster {
-                let mut sector_buf = [0u8; 512];
-                self.device.read_block(sector + i as u64, &mut sector_buf)
-                    .map_err(|_| FsError::IOError)?;
-                
-                let copy_len = core::cmp::min(512, buf.len() - offset);
-                buf[offset..offset + copy_len].copy_from_slice(&sector_buf[..copy_len]);
-                offset += copy_len;
-            }
-            
-            // 다음 클러스터 읽기
-            cluster = self.read_fat_entry(cluster)?;
-        }
-        
-        Ok(offset)
+        let sectors: Vec<(u64, &[u8])> = data[..cluster_size]
+            .chunks_exact(512)
+            .enumerate()
+            .map(|(i, sector_buf)| (sector + i as u64, sector_buf))
+            .collect();
+        self.write_sectors_direct(&sectors)
     }
     
     /// 단일 클러스터 읽기
@@ -506,8 +989,7 @@ impl Fat32FileSystem {
         
         for i in 0..self.boot_sector.sectors_per_cluster {
             let mut sector_buf = [0u8; 512];
-            self.device.read_block(sector + i as u64, &mut sector_buf)
-                .map_err(|_| FsError::IOError)?;
+            self.read_sector(sector + i as u64, &mut sector_buf)?;
             
             buf[offset..offset + 512].copy_from_slice(&sector_buf);
             offset += 512;
@@ -540,73 +1022,181 @@ impl Fat32FileSystem {
         Ok((cluster, cluster_offset))
     }
     
-    /// 디렉토리 엔트리 찾기
-    /// 
-    /// # Arguments
-    /// * `dir_cluster` - 디렉토리 클러스터 번호
-    /// * `name` - 찾을 파일/디렉토리 이름
-    /// 
-    /// # Returns
-    /// 찾은 디렉토리 엔트리 또는 오류
-    fn find_directory_entry(&mut self, dir_cluster: u32, name: &str) -> FsResult<Fat32DirEntry> {
-        // 디렉토리 데이터 읽기
+    /// 파일의 페이지 캐시 키 (FAT32에는 inode가 없으므로 첫 클러스터로 구분)
+    fn cache_key(&self, first_cluster: u32) -> FileKey {
+        FileKey { fs: self.cache_id, ino: first_cluster as u64 }
+    }
+    
+    /// 파일의 `offset`부터 `buf`만큼 읽기
+    ///
+    /// `cursor`는 직전에 읽은 (클러스터 순번, 클러스터 번호)이며, 그 뒤를 이어 읽으면
+    /// 클러스터 체인을 처음부터 다시 따라가지 않습니다. 필요한 섹터만 읽고, 파일 끝 뒤의
+    /// 버퍼 내용은 건드리지 않습니다.
+    fn read_file_range(
+        &mut self,
+        start_cluster: u32,
+        file_size: usize,
+        offset: usize,
+        buf: &mut [u8],
+        cursor: &mut Option<(usize, u32)>,
+    ) -> FsResult<()> {
+        if offset >= file_size {
+            return Ok(());
+        }
+        let len = buf.len().min(file_size - offset);
         let cluster_size = self.boot_sector.sectors_per_cluster as usize * 512;
-        let mut dir_buf = alloc::vec![0u8; cluster_size * 4]; // 최대 4 클러스터
-        let len = self.read_cluster_chain(dir_cluster, &mut dir_buf)?;
-        
-        // 디렉토리 엔트리 검색
-        let num_entries = len / size_of::<Fat32DirEntry>();
-        for i in 0..num_entries {
-            let entry = unsafe {
-                core::ptr::read(dir_buf.as_ptr().add(i * size_of::<Fat32DirEntry>()) as *const Fat32DirEntry)
-            };
-            
-            if entry.is_deleted() || entry.is_long_name() || entry.is_volume_label() {
-                continue;
+        let mut index = offset / cluster_size;
+        let (mut cluster, mut cluster_offset) = match *cursor {
+            Some((hint_index, hint)) if hint_index <= index => {
+                self.find_cluster_for_offset(hint, offset - hint_index * cluster_size)?
+            }
+            _ => self.find_cluster_for_offset(start_cluster, offset)?,
+        };
+        let mut sector_buf = [0u8; 512];
+        let mut done = 0;
+        
+        loop {
+            *cursor = Some((index, cluster));
+            let sector = self.boot_sector.cluster_to_sector(cluster) + (cluster_offset / 512) as u64;
+            let in_sector = cluster_offset % 512;
+            let to_copy = (512 - in_sector).min(len - done);
+            self.read_sector(sector, &mut sector_buf)?;
+            buf[done..done + to_copy].copy_from_slice(&sector_buf[in_sector..in_sector + to_copy]);
+            done += to_copy;
+            if done == len {
+                return Ok(());
             }
             
-            let entry_name = entry.short_name();
-            if entry_name.eq_ignore_ascii_case(name) {
-                return Ok(entry);
+            cluster_offset += to_copy;
+            if cluster_offset == cluster_size {
+                cluster = self.read_fat_entry(cluster)?;
+                if !(2..0x0FFFFFF8).contains(&cluster) {
+                    return Err(FsError::IOError);
+                }
+                cluster_offset = 0;
+                index += 1;
             }
         }
-        
-        Err(FsError::NotFound)
     }
     
-    /// 경로를 디렉토리와 파일명으로 분리
-    /// 
-    /// # Arguments
-    /// * `path` - 파일/디렉토리 경로
+    /// 디렉토리 클러스터 체인
+    fn dir_clusters(&mut self, dir_cluster: u32) -> FsResult<Vec<u32>> {
+        let mut clusters = Vec::new();
+        let mut cluster = dir_cluster;
+        while cluster >= 2 && cluster < 0x0FFFFFF8 {
+            clusters.push(cluster);
+            cluster = self.read_fat_entry(cluster)?;
+        }
+        Ok(clusters)
+    }
+    
+    /// 디렉토리 전체를 읽어 LFN을 해석한 항목 목록 생성
+    ///
+    /// 삭제된 엔트리와 볼륨 레이블은 건너뛰고, 이름이 0으로 시작하는 엔트리에서 멈춥니다.
+    fn scan_directory(&mut self, dir_cluster: u32) -> FsResult<Vec<DirRecord>> {
+        let cluster_size = self.boot_sector.sectors_per_cluster as usize * 512;
+        let mut dir_buf = alloc::vec![0u8; cluster_size];
+        let mut records = Vec::new();
+        let mut lfn = LfnParser::default();
+        let mut slot = 0;
+        
+        for cluster in self.dir_clusters(dir_cluster)? {
+            self.read_single_cluster(cluster, &mut dir_buf)?;
+            for raw in dir_buf.chunks_exact(size_of::<Fat32DirEntry>()) {
+                let entry = Fat32DirEntry::from_bytes(raw);
+                if entry.name[0] == 0 {
+                    return Ok(records);
+                }
+                if entry.name[0] == 0xE5 || (entry.is_volume_label() && !entry.is_long_name()) {
+                    lfn.reset();
+                } else if entry.is_long_name() {
+                    lfn.push(&Fat32LfnEntry::from_bytes(raw), slot);
+                } else {
+                    let (name, first_slot) = lfn.finish(&entry.name)
+                        .unwrap_or_else(|| (entry.short_name(), slot));
+                    records.push(DirRecord { name, entry, first_slot, slot });
+                }
+                slot += 1;
+            }
+        }
+        
+        Ok(records)
+    }
+    
+    /// 디렉토리에서 이름으로 항목 찾기
+    ///
+    /// 긴 이름과 8.3 이름 모두 대소문자 구분 없이 비교합니다.
+    fn find_record(&mut self, dir_cluster: u32, name: &str) -> FsResult<DirRecord> {
+        self.scan_directory(dir_cluster)?
+            .into_iter()
+            .find(|record| names_equal(&record.name, name) || record.entry.short_name().eq_ignore_ascii_case(name))
+            .ok_or(FsError::NotFound)
+    }
+    
+    /// 디렉토리 엔트리 찾기
+    /// 
+    /// # Arguments
+    /// * `dir_cluster` - 디렉토리 클러스터 번호
+    /// * `name` - 찾을 파일/디렉토리 이름 (긴 이름 또는 8.3 이름)
     /// 
     /// # Returns
-    /// (디렉토리 클러스터, 파일명)
-    fn split_path<'a>(&mut self, path: &'a str) -> FsResult<(u32, &'a str)> {
-        let path = path.trim_start_matches('/');
-        if path.is_empty() {
-            return Ok((self.boot_sector.root_cluster(), ""));
+    /// 찾은 디렉토리 엔트리 또는 오류
+    fn find_directory_entry(&mut self, dir_cluster: u32, name: &str) -> FsResult<Fat32DirEntry> {
+        self.find_record(dir_cluster, name).map(|record| record.entry)
+    }
+    
+    /// 디렉토리 안의 하위 디렉토리 클러스터
+    ///
+    /// `..` 엔트리의 클러스터 0은 루트를 뜻합니다. 루트에는 `..`이 없으므로 루트 자신을 반환합니다.
+    fn child_dir(&mut self, dir_cluster: u32, name: &str) -> FsResult<u32> {
+        let root_cluster = self.boot_sector.root_cluster();
+        if name == ".." && dir_cluster == root_cluster {
+            return Ok(root_cluster);
         }
-        
-        let components: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
-        if components.is_empty() {
-            return Ok((self.boot_sector.root_cluster(), ""));
+        let entry = self.find_directory_entry(dir_cluster, name)?;
+        if !entry.is_directory() {
+            return Err(FsError::NotADirectory);
         }
+        Ok(if entry.first_cluster() == 0 { root_cluster } else { entry.first_cluster() })
+    }
+    
+    /// 경로를 부모 디렉토리와 마지막 이름으로 분리
+    /// 
+    /// # Arguments
+    /// * `base_cluster` - 상대 경로의 기준 디렉토리 (`/`로 시작하면 루트 기준)
+    /// * `path` - 파일/디렉토리 경로 (`.`과 `..` 포함 가능)
+    /// 
+    /// # Returns
+    /// (디렉토리 클러스터, 마지막 이름 - 경로가 디렉토리 자신이면 빈 문자열)
+    fn resolve_parent<'a>(&mut self, base_cluster: u32, path: &'a str) -> FsResult<(u32, &'a str)> {
+        let mut current_cluster = if path.starts_with('/') {
+            self.boot_sector.root_cluster()
+        } else {
+            base_cluster
+        };
         
-        if components.len() == 1 {
-            return Ok((self.boot_sector.root_cluster(), components[0]));
-        }
+        let components: Vec<&str> = path.split('/').filter(|s| !s.is_empty() && *s != ".").collect();
+        let Some((&name, dirs)) = components.split_last() else {
+            return Ok((current_cluster, ""));
+        };
         
         // 디렉토리 경로 탐색
-        let mut current_cluster = self.boot_sector.root_cluster();
-        for i in 0..components.len() - 1 {
-            let entry = self.find_directory_entry(current_cluster, components[i])?;
-            if !entry.is_directory() {
-                return Err(FsError::NotADirectory);
-            }
-            current_cluster = entry.first_cluster();
+        for component in dirs {
+            current_cluster = self.child_dir(current_cluster, component)?;
         }
         
-        Ok((current_cluster, components[components.len() - 1]))
+        Ok((current_cluster, name))
+    }
+    
+    /// 경로를 디렉토리와 파일명으로 분리
+    /// 
+    /// # Arguments
+    /// * `path` - 파일/디렉토리 경로
+    /// 
+    /// # Returns
+    /// (디렉토리 클러스터, 파일명)
+    fn split_path<'a>(&mut self, path: &'a str) -> FsResult<(u32, &'a str)> {
+        self.resolve_parent(self.boot_sector.root_cluster(), path)
     }
     
     /// 경로를 클러스터 번호로 변환
@@ -659,57 +1249,6 @@ impl Fat32FileSystem {
         Err(FsError::NotFound)
     }
     
-    /// 파일명을 8.3 형식으로 변환
-    /// 
-    /// # Arguments
-    /// * `name` - 파일명
-    /// 
-    /// # Returns
-    /// 11바이트 배열 (8바이트 이름 + 3바이트 확장자)
-    fn name_to_8_3(name: &str) -> [u8; 11] {
-        let mut result = [b' '; 11];
-        let parts: Vec<&str> = name.split('.').collect();
-        
-        let basename = if parts.is_empty() { "" } else { parts[0] };
-        let ext = if parts.len() > 1 { parts[parts.len() - 1] } else { "" };
-        
-        // 기본 이름 (최대 8자)
-        let basename_bytes = basename.as_bytes();
-        let basename_len = core::cmp::min(8, basename_bytes.len());
-        for i in 0..basename_len {
-            result[i] = basename_bytes[i].to_ascii_uppercase();
-        }
-        
-        // 확장자 (최대 3자)
-        let ext_bytes = ext.as_bytes();
-        let ext_len = core::cmp::min(3, ext_bytes.len());
-        for i in 0..ext_len {
-            result[8 + i] = ext_bytes[i].to_ascii_uppercase();
-        }
-        
-        result
-    }
-    
-    /// 경로를 부모 경로와 파일명으로 분리
-    ///
-    /// # Arguments
-    /// * `path` - 분리할 경로
-    ///
-    /// # Returns
-    /// (부모 경로, 파일명)
-    fn split_parent_path<'a>(&self, path: &'a str) -> FsResult<(&'a str, &'a str)> {
-        let path = path.trim_start_matches('/').trim_end_matches('/');
-        if path.is_empty() {
-            return Err(FsError::InvalidPath);
-        }
-        
-        if let Some(pos) = path.rfind('/') {
-            Ok((&path[..pos], &path[pos + 1..]))
-        } else {
-            Ok(("", path))
-        }
-    }
-    
     /// 디렉토리가 비어있는지 확인
     ///
     /// # Arguments
@@ -718,23 +1257,9 @@ impl Fat32FileSystem {
     /// # Returns
     /// 비어있으면 true
     fn is_directory_empty(&mut self, dir_cluster: u32) -> FsResult<bool> {
-        let cluster_size = self.boot_sector.sectors_per_cluster as usize * 512;
-        let mut dir_buf = alloc::vec![0u8; cluster_size];
-        
-        self.read_single_cluster(dir_cluster, &mut dir_buf)?;
-        
-        let num_entries = cluster_size / size_of::<Fat32DirEntry>();
-        for i in 2..num_entries { // 0과 1은 "." 와 ".." 엔트리
-            let entry = unsafe {
-                core::ptr::read(dir_buf.as_ptr().add(i * size_of::<Fat32DirEntry>()) as *const Fat32DirEntry)
-            };
-            
-            if !entry.is_deleted() && entry.name[0] != 0 {
-                return Ok(false); // 비어있지 않음
-            }
-        }
-        
-        Ok(true) // 비어있음
+        // "." 와 ".." 엔트리 외에 항목이 없으면 빈 디렉토리 (짧은 이름은 '.'으로 시작할 수 없음)
+        let records = self.scan_directory(dir_cluster)?;
+        Ok(records.iter().all(|record| record.entry.name[0] == b'.'))
     }
     
     /// FAT 클러스터 체인 해제
@@ -742,6 +1267,8 @@ impl Fat32FileSystem {
     /// # Arguments
     /// * `start_cluster` - 시작 클러스터
     fn free_cluster_chain(&mut self, start_cluster: u32) -> FsResult<()> {
+        // 첫 클러스터가 다른 파일에 재사용되기 전에 캐시된 내용 버림
+        cache::invalidate(self.cache_key(start_cluster));
         let mut current_cluster = start_cluster;
         
         loop {
@@ -758,206 +1285,150 @@ impl Fat32FileSystem {
         Ok(())
     }
     
-    /// 디렉토리 엔트리 삭제
+    /// 디렉토리 슬롯 `slots` 범위를 클러스터 단위로 읽어 고친 뒤 저널을 거쳐 기록
     ///
     /// # Arguments
-    /// * `dir_cluster` - 디렉토리 클러스터
-    /// * `filename` - 삭제할 파일명
-    fn delete_directory_entry(&mut self, dir_cluster: u32, filename: &str) -> FsResult<()> {
+    /// * `clusters` - 디렉토리 클러스터 체인
+    /// * `slots` - 고칠 슬롯 범위 (디렉토리 처음부터 32바이트 단위)
+    /// * `update` - (범위 내 순번, 슬롯 바이트)를 받아 슬롯을 고치는 함수
+    fn update_dir_slots(
+        &mut self,
+        clusters: &[u32],
+        slots: core::ops::Range<usize>,
+        mut update: impl FnMut(usize, &mut [u8]),
+    ) -> FsResult<()> {
         let cluster_size = self.boot_sector.sectors_per_cluster as usize * 512;
+        let per_cluster = cluster_size / size_of::<Fat32DirEntry>();
         let mut dir_buf = alloc::vec![0u8; cluster_size];
-        
-        self.read_single_cluster(dir_cluster, &mut dir_buf)?;
-        
-        let name_8_3 = Self::name_to_8_3(filename);
-        let num_entries = cluster_size / size_of::<Fat32DirEntry>();
-        
-        for i in 0..num_entries {
-            let offset = i * size_of::<Fat32DirEntry>();
-            let entry = unsafe {
-                core::ptr::read(dir_buf.as_ptr().add(offset) as *const Fat32DirEntry)
-            };
-            
-            if entry.name == name_8_3 {
-                // 엔트리 삭제 마킹 (첫 바이트를 0xE5로)
-                dir_buf[offset] = 0xE5;
-                // 디렉토리 엔트리 삭제는 메타데이터이므로 저널링 사용
-                self.write_cluster(dir_cluster, &dir_buf, true)?;
-                return Ok(());
+        let mut slot = slots.start;
+        
+        while slot < slots.end {
+            let cluster = *clusters.get(slot / per_cluster).ok_or(FsError::InvalidFilesystem)?;
+            let first = slot % per_cluster;
+            let count = (per_cluster - first).min(slots.end - slot);
+            self.read_single_cluster(cluster, &mut dir_buf)?;
+            for i in 0..count {
+                let offset = (first + i) * size_of::<Fat32DirEntry>();
+                update(slot - slots.start + i, &mut dir_buf[offset..offset + size_of::<Fat32DirEntry>()]);
             }
+            // 디렉토리 엔트리는 메타데이터이므로 바뀐 섹터만 저널링
+            let first_sector = first * size_of::<Fat32DirEntry>() / 512;
+            let last_sector = ((first + count) * size_of::<Fat32DirEntry>()).div_ceil(512);
+            let base = self.boot_sector.cluster_to_sector(cluster);
+            let sectors: Vec<(u64, &[u8])> = (first_sector..last_sector)
+                .map(|i| (base + i as u64, &dir_buf[i * 512..(i + 1) * 512]))
+                .collect();
+            self.write_sectors_journaled(&sectors, JournalEntryType::MetadataWrite)?;
+            slot += count;
         }
         
-        Err(FsError::NotFound)
+        Ok(())
     }
     
-    /// 디렉토리 엔트리 업데이트
-    ///
-    /// # Arguments
-    /// * `dir_cluster` - 디렉토리 클러스터
-    /// * `filename` - 파일명
-    /// * `new_entry` - 새 엔트리
-    fn update_directory_entry(&mut self, dir_cluster: u32, filename: &str, new_entry: Fat32DirEntry) -> FsResult<()> {
-        let cluster_size = self.boot_sector.sectors_per_cluster as usize * 512;
-        let mut dir_buf = alloc::vec![0u8; cluster_size];
-        
-        self.read_single_cluster(dir_cluster, &mut dir_buf)?;
-        
-        let name_8_3 = Self::name_to_8_3(filename);
-        let num_entries = cluster_size / size_of::<Fat32DirEntry>();
-        
-        for i in 0..num_entries {
-            let offset = i * size_of::<Fat32DirEntry>();
-            let entry = unsafe {
-                core::ptr::read(dir_buf.as_ptr().add(offset) as *const Fat32DirEntry)
-            };
-            
-            if entry.name == name_8_3 {
-                // 엔트리 업데이트
-                let entry_bytes = unsafe {
-                    core::slice::from_raw_parts(
-                        &new_entry as *const Fat32DirEntry as *const u8,
-                        size_of::<Fat32DirEntry>()
-                    )
-                };
-                dir_buf[offset..offset + size_of::<Fat32DirEntry>()].copy_from_slice(entry_bytes);
-                // 디렉토리 엔트리 업데이트는 메타데이터이므로 저널링 사용
-                self.write_cluster(dir_cluster, &dir_buf, true)?;
-                return Ok(());
-            }
-        }
-        
-        Err(FsError::NotFound)
+    /// 디렉토리 엔트리 삭제 (앞에 붙은 LFN 조각 포함)
+    fn delete_record(&mut self, dir_cluster: u32, record: &DirRecord) -> FsResult<()> {
+        let clusters = self.dir_clusters(dir_cluster)?;
+        self.update_dir_slots(&clusters, record.first_slot..record.slot + 1, |_, slot| {
+            // 엔트리 삭제 마킹 (첫 바이트를 0xE5로)
+            slot[0] = 0xE5;
+        })
     }
     
     /// 디렉토리에 새 엔트리 추가
     /// 
+    /// `name`에 맞는 짧은 이름을 만들어 `entry.name`에 넣고, 8.3 형식으로 그대로
+    /// 표현되지 않는 이름이면 LFN 조각을 짧은 엔트리 앞에 연속으로 기록합니다.
+    /// 연속된 빈 슬롯이 없으면 디렉토리 끝에 클러스터를 추가합니다.
+    /// 
     /// # Arguments
     /// * `dir_cluster` - 디렉토리 클러스터 번호
-    /// * `entry` - 추가할 디렉토리 엔트리
-    fn add_directory_entry(&mut self, dir_cluster: u32, entry: Fat32DirEntry) -> FsResult<()> {
-        let cluster_size = self.boot_sector.sectors_per_cluster as usize * 512;
-        let mut dir_buf = alloc::vec![0u8; cluster_size];
+    /// * `name` - 항목 이름
+    /// * `entry` - 추가할 디렉토리 엔트리 (`name` 필드는 무시)
+    /// 
+    /// # Returns
+    /// 짧은 이름이 채워진 엔트리
+    fn add_directory_entry(&mut self, dir_cluster: u32, name: &str, mut entry: Fat32DirEntry) -> FsResult<Fat32DirEntry> {
+        let units = encode_long_name(name)?;
+        let records = self.scan_directory(dir_cluster)?;
+        let taken = |short: &[u8; 11]| records.iter().any(|record| record.entry.name == *short);
+        
+        // 짧은 이름 생성 (정보가 손실되거나 겹치면 ~N 꼬리)
+        let (basis, lossy) = short_name_basis(name);
+        entry.name = if !lossy && !taken(&basis) {
+            basis
+        } else {
+            (1..=999_999)
+                .map(|n| short_name_with_tail(&basis, n))
+                .find(|short| !taken(short))
+                .ok_or(FsError::OutOfSpace)?
+        };
         
-        // 디렉토리 데이터 읽기
-        let mut current_cluster = dir_cluster;
-        let mut found_empty = false;
-        let mut empty_cluster = 0;
-        let mut empty_offset = 0;
+        let mut slots: Vec<[u8; 32]> = Vec::new();
+        if format_8_3(&entry.name) != name {
+            let checksum = lfn_checksum(&entry.name);
+            slots.extend(long_name_entries(&units, checksum).iter().map(Fat32LfnEntry::to_bytes));
+        }
+        slots.push(entry.to_bytes());
         
-        loop {
-            self.read_single_cluster(current_cluster, &mut dir_buf)?;
-            
-            // 빈 엔트리 찾기
-            let num_entries = cluster_size / size_of::<Fat32DirEntry>();
-            for i in 0..num_entries {
-                let entry_ptr = unsafe { dir_buf.as_ptr().add(i * size_of::<Fat32DirEntry>()) };
-                let existing_entry = unsafe { core::ptr::read(entry_ptr as *const Fat32DirEntry) };
-                
-                if existing_entry.is_deleted() || existing_entry.name[0] == 0 {
-                    // 빈 엔트리 발견
-                    found_empty = true;
-                    empty_cluster = current_cluster;
-                    empty_offset = i * size_of::<Fat32DirEntry>();
-                    break;
+        // 연속된 빈 슬롯 찾기
+        let cluster_size = self.boot_sector.sectors_per_cluster as usize * 512;
+        let per_cluster = cluster_size / size_of::<Fat32DirEntry>();
+        let mut dir_buf = alloc::vec![0u8; cluster_size];
+        let mut clusters = self.dir_clusters(dir_cluster)?;
+        let mut run_start = 0;
+        let mut run_len = 0;
+        'scan: for (index, &cluster) in clusters.iter().enumerate() {
+            self.read_single_cluster(cluster, &mut dir_buf)?;
+            for i in 0..per_cluster {
+                let first = dir_buf[i * size_of::<Fat32DirEntry>()];
+                if first != 0xE5 && first != 0 {
+                    run_len = 0;
+                    continue;
+                }
+                if run_len == 0 {
+                    run_start = index * per_cluster + i;
+                }
+                run_len += 1;
+                if run_len == slots.len() {
+                    break 'scan;
                 }
             }
-            
-            if found_empty {
-                break;
-            }
-            
-            // 다음 클러스터 확인
-            let next_cluster = self.read_fat_entry(current_cluster)?;
-            if next_cluster >= 0x0FFFFFF8 {
-                // 새 클러스터 할당 필요
-                let new_cluster = self.find_free_cluster()?;
-                self.write_fat_entry_journaled(current_cluster, new_cluster)?;
-                self.write_fat_entry_journaled(new_cluster, 0x0FFFFFFF)?; // EOF
-                
-                // 새 클러스터를 0으로 초기화 (데이터는 저널링 안 함)
-                let mut empty_cluster_buf = alloc::vec![0u8; cluster_size];
-                self.write_cluster_direct(new_cluster, &empty_cluster_buf)?;
-                
-                empty_cluster = new_cluster;
-                empty_offset = 0;
-                found_empty = true;
-                break;
-            }
-            
-            current_cluster = next_cluster;
         }
         
-        if !found_empty {
-            return Err(FsError::IOError);
+        // 모자라면 디렉토리 끝에 새 클러스터 추가
+        while run_len < slots.len() {
+            let last_cluster = *clusters.last().ok_or(FsError::InvalidFilesystem)?;
+            let new_cluster = self.find_free_cluster()?;
+            // 새 클러스터를 0으로 초기화 (데이터는 저널링 안 함)
+            dir_buf.fill(0);
+            self.write_cluster(new_cluster, &dir_buf)?;
+            self.write_fat_entry_journaled(new_cluster, 0x0FFFFFFF)?; // EOF
+            self.write_fat_entry_journaled(last_cluster, new_cluster)?;
+            if run_len == 0 {
+                run_start = clusters.len() * per_cluster;
+            }
+            clusters.push(new_cluster);
+            run_len += per_cluster;
         }
         
-        // 엔트리 쓰기
-        self.read_single_cluster(empty_cluster, &mut dir_buf)?;
-        let entry_bytes = unsafe {
-            core::slice::from_raw_parts(
-                &entry as *const Fat32DirEntry as *const u8,
-                size_of::<Fat32DirEntry>()
-            )
-        };
-        dir_buf[empty_offset..empty_offset + size_of::<Fat32DirEntry>()]
-            .copy_from_slice(entry_bytes);
-        // 디렉토리 엔트리 추가는 메타데이터이므로 저널링 사용
-        self.write_cluster(empty_cluster, &dir_buf, true)?;
+        self.update_dir_slots(&clusters, run_start..run_start + slots.len(), |i, slot| {
+            slot.copy_from_slice(&slots[i]);
+        })?;
         
-        Ok(())
-    }
-}
-
-impl FileSystem for Fat32FileSystem {
-    fn mount(&mut self) -> FsResult<()> {
-        if self.mounted {
-            return Err(FsError::Busy);
-        }
-        self.mounted = true;
-        Ok(())
-    }
-    
-    fn unmount(&mut self) -> FsResult<()> {
-        if !self.mounted {
-            return Err(FsError::InvalidFilesystem);
-        }
-        self.mounted = false;
-        Ok(())
+        Ok(entry)
     }
     
-    fn open_file(&mut self, path: &str) -> FsResult<Box<dyn File>> {
-        if !self.mounted {
-            return Err(FsError::InvalidFilesystem);
-        }
-        
-        let (cluster, entry) = self.path_to_cluster(path)?;
-        if entry.is_directory() {
-            return Err(FsError::IsDirectory);
-        }
-        
-        Ok(Box::new(Fat32File {
-            filesystem: self as *mut Fat32FileSystem,
-            entry,
-            cluster,
-            offset: 0,
-        }))
-    }
-    
-    fn create_file(&mut self, path: &str) -> FsResult<Box<dyn File>> {
-        if !self.mounted {
-            return Err(FsError::InvalidFilesystem);
-        }
-        
-        // 경로 분리
-        let (dir_cluster, filename_ref) = self.split_path(path)?;
-        let filename_owned = filename_ref.to_string();
-        
-        if filename_ref.is_empty() {
+    /// `dir_cluster` 디렉토리에 빈 파일 생성
+    ///
+    /// # Returns
+    /// (첫 클러스터, 디렉토리 엔트리)
+    fn create_file_in(&mut self, dir_cluster: u32, name: &str) -> FsResult<(u32, Fat32DirEntry)> {
+        if name.is_empty() {
             return Err(FsError::InvalidPath);
         }
         
         // 파일이 이미 존재하는지 확인
-        match self.find_directory_entry(dir_cluster, &filename_owned) {
+        match self.find_directory_entry(dir_cluster, name) {
             Ok(_) => return Err(FsError::AlreadyExists),
             Err(FsError::NotFound) => {},
             Err(e) => return Err(e),
@@ -969,13 +1440,12 @@ impl FileSystem for Fat32FileSystem {
         
         // 빈 클러스터 초기화 (데이터는 저널링 안 함)
         let cluster_size = self.boot_sector.sectors_per_cluster as usize * 512;
-        let mut empty_cluster = alloc::vec![0u8; cluster_size];
-        self.write_cluster_direct(first_cluster, &empty_cluster)?;
+        let empty_cluster = alloc::vec![0u8; cluster_size];
+        self.write_cluster(first_cluster, &empty_cluster)?;
         
-        // 디렉토리 엔트리 생성
-        let name_8_3 = Self::name_to_8_3(&filename_owned);
-        let entry = Fat32DirEntry {
-            name: name_8_3,
+        // 디렉토리 엔트리 생성 (짧은 이름은 add_directory_entry가 채움)
+        let mut entry = Fat32DirEntry {
+            name: [b' '; 11],
             attributes: 0, // 일반 파일
             reserved: 0,
             create_time_tenth: 0,
@@ -988,52 +1458,22 @@ impl FileSystem for Fat32FileSystem {
             first_cluster_low: (first_cluster & 0xFFFF) as u16,
             file_size: 0,
         };
+        entry.set_created(wallclock::now_unix());
         
         // 디렉토리에 엔트리 추가
-        self.add_directory_entry(dir_cluster, entry)?;
+        let entry = self.add_directory_entry(dir_cluster, name, entry)?;
         
-        // 파일 열기
-        let (cluster, entry) = self.path_to_cluster(path)?;
-        Ok(Box::new(Fat32File {
-            filesystem: self,
-            entry,
-            cluster,
-            offset: 0,
-        }))
+        Ok((first_cluster, entry))
     }
     
-    fn open_dir(&mut self, path: &str) -> FsResult<Box<dyn Directory>> {
-        if !self.mounted {
-            return Err(FsError::InvalidFilesystem);
-        }
-        
-        let (cluster, entry) = self.path_to_cluster(path)?;
-        if !entry.is_directory() {
-            return Err(FsError::NotADirectory);
-        }
-        
-        let root_cluster = self.boot_sector.root_cluster();
-        let resolved_cluster = if cluster == 0 { root_cluster } else { cluster };
-        Ok(Box::new(Fat32Directory {
-            filesystem: self as *mut Fat32FileSystem,
-            cluster: resolved_cluster,
-        }))
-    }
-    
-    fn create_dir(&mut self, path: &str) -> FsResult<()> {
-        if !self.mounted {
-            return Err(FsError::InvalidFilesystem);
-        }
-        
-        // 경로 분리
-        let (dir_cluster, dirname) = self.split_path(path)?;
-        
-        if dirname.is_empty() {
+    /// `dir_cluster` 디렉토리에 하위 디렉토리 생성 (`.`과 `..` 엔트리 포함)
+    fn create_dir_in(&mut self, dir_cluster: u32, name: &str) -> FsResult<()> {
+        if name.is_empty() {
             return Err(FsError::InvalidPath);
         }
         
         // 디렉토리가 이미 존재하는지 확인
-        match self.find_directory_entry(dir_cluster, dirname) {
+        match self.find_directory_entry(dir_cluster, name) {
             Ok(_) => return Err(FsError::AlreadyExists),
             Err(FsError::NotFound) => {},
             Err(e) => return Err(e),
@@ -1047,8 +1487,10 @@ impl FileSystem for Fat32FileSystem {
         let cluster_size = self.boot_sector.sectors_per_cluster as usize * 512;
         let mut dir_cluster_buf = alloc::vec![0u8; cluster_size];
         
+        let now = wallclock::now_unix();
+        
         // . 엔트리 (현재 디렉토리)
-        let dot_entry = Fat32DirEntry {
+        let mut dot_entry = Fat32DirEntry {
             name: *b".          ",
             attributes: Fat32DirEntry::ATTR_DIRECTORY,
             reserved: 0,
@@ -1069,7 +1511,7 @@ impl FileSystem for Fat32FileSystem {
         } else {
             dir_cluster
         };
-        let dotdot_entry = Fat32DirEntry {
+        let mut dotdot_entry = Fat32DirEntry {
             name: *b"..         ",
             attributes: Fat32DirEntry::ATTR_DIRECTORY,
             reserved: 0,
@@ -1083,6 +1525,8 @@ impl FileSystem for Fat32FileSystem {
             first_cluster_low: (dotdot_cluster & 0xFFFF) as u16,
             file_size: 0,
         };
+        dot_entry.set_created(now);
+        dotdot_entry.set_created(now);
         
         // 엔트리 쓰기
         let dot_bytes = unsafe {
@@ -1102,13 +1546,12 @@ impl FileSystem for Fat32FileSystem {
         dir_cluster_buf[size_of::<Fat32DirEntry>()..2 * size_of::<Fat32DirEntry>()]
             .copy_from_slice(dotdot_bytes);
         
-        // 디렉토리 엔트리는 메타데이터이므로 저널링 사용
-        self.write_cluster(first_cluster, &dir_cluster_buf, true)?;
+        // 새 클러스터는 부모 엔트리가 커밋되기 전까지 아무도 가리키지 않으므로 바로 씀
+        self.write_cluster(first_cluster, &dir_cluster_buf)?;
         
         // 부모 디렉토리에 엔트리 추가
-        let name_8_3 = Self::name_to_8_3(dirname);
-        let entry = Fat32DirEntry {
-            name: name_8_3,
+        let mut entry = Fat32DirEntry {
+            name: [b' '; 11],
             attributes: Fat32DirEntry::ATTR_DIRECTORY,
             reserved: 0,
             create_time_tenth: 0,
@@ -1121,69 +1564,214 @@ impl FileSystem for Fat32FileSystem {
             first_cluster_low: (first_cluster & 0xFFFF) as u16,
             file_size: 0,
         };
+        entry.set_created(now);
         
-        self.add_directory_entry(dir_cluster, entry)?;
+        self.add_directory_entry(dir_cluster, name, entry)?;
         
         Ok(())
     }
     
-    fn remove(&mut self, path: &str) -> FsResult<()> {
-        if !self.mounted {
-            return Err(FsError::InvalidFilesystem);
+    /// `dir_cluster` 디렉토리의 항목 삭제 (디렉토리는 비어 있어야 함)
+    fn remove_in(&mut self, dir_cluster: u32, name: &str) -> FsResult<()> {
+        if matches!(name, "" | "." | "..") {
+            return Err(FsError::InvalidPath);
         }
-        
-        // 경로에서 부모 디렉토리와 파일명 분리
-        let (parent_path, filename) = self.split_parent_path(path)?;
-        let (parent_cluster, _) = self.path_to_cluster(parent_path)?;
-        let (file_cluster, entry) = self.path_to_cluster(path)?;
+        let record = self.find_record(dir_cluster, name)?;
+        let file_cluster = record.entry.first_cluster();
         
         // 디렉토리인 경우 빈 디렉토리인지 확인
-        if entry.is_directory() {
-            if !self.is_directory_empty(file_cluster)? {
-                return Err(FsError::Busy);
-            }
+        if record.entry.is_directory() && !self.is_directory_empty(file_cluster)? {
+            return Err(FsError::Busy);
         }
         
-        // FAT 체인 해제
-        self.free_cluster_chain(file_cluster)?;
+        // FAT 체인 해제 (빈 파일은 클러스터가 없을 수 있음)
+        if file_cluster >= 2 {
+            self.free_cluster_chain(file_cluster)?;
+        }
         
         // 부모 디렉토리에서 엔트리 삭제
-        self.delete_directory_entry(parent_cluster, filename)?;
+        self.delete_record(dir_cluster, &record)
+    }
+    
+    /// 항목 이름 변경 및 디렉토리 간 이동
+    ///
+    /// 디렉토리를 다른 디렉토리로 옮기면 그 `..` 엔트리도 새 부모를 가리키도록 고칩니다.
+    fn rename_in(&mut self, old_parent_cluster: u32, old_filename: &str, new_parent_cluster: u32, new_filename: &str) -> FsResult<()> {
+        if matches!(old_filename, "" | "." | "..") || new_filename.is_empty() {
+            return Err(FsError::InvalidPath);
+        }
+        let old_record = self.find_record(old_parent_cluster, old_filename)?;
+        
+        // 새 파일명이 이미 존재하는지 확인 (같은 항목의 대소문자만 바꾸는 경우는 허용)
+        match self.find_record(new_parent_cluster, new_filename) {
+            Ok(record) if old_parent_cluster != new_parent_cluster || record.slot != old_record.slot => {
+                return Err(FsError::AlreadyExists);
+            }
+            Ok(_) | Err(FsError::NotFound) => {}
+            Err(e) => return Err(e),
+        }
+        
+        // 디렉토리를 옮길 때는 자기 자신이나 하위 디렉토리 안으로 옮길 수 없음
+        let moving_dir = old_record.entry.is_directory() && old_parent_cluster != new_parent_cluster;
+        if moving_dir && self.is_in_subtree(old_record.entry.first_cluster(), new_parent_cluster)? {
+            return Err(FsError::InvalidPath);
+        }
+        
+        // 새 이름(LFN 포함)으로 엔트리를 먼저 추가한 뒤 이전 엔트리 삭제
+        // 추가는 빈 슬롯에만 쓰므로 이전 엔트리의 위치는 그대로
+        self.add_directory_entry(new_parent_cluster, new_filename, old_record.entry)?;
+        self.delete_record(old_parent_cluster, &old_record)?;
+        
+        if moving_dir {
+            self.set_parent_link(old_record.entry.first_cluster(), new_parent_cluster)?;
+        }
         
         Ok(())
     }
     
-    fn rename(&mut self, old_path: &str, new_path: &str) -> FsResult<()> {
+    /// `dir_cluster`가 `ancestor` 자신이거나 그 하위 디렉토리인지 확인
+    fn is_in_subtree(&mut self, ancestor: u32, dir_cluster: u32) -> FsResult<bool> {
+        let root_cluster = self.boot_sector.root_cluster();
+        let mut current = dir_cluster;
+        // 손상된 `..` 고리에 갇히지 않도록 깊이 제한
+        for _ in 0..4096 {
+            if current == ancestor {
+                return Ok(true);
+            }
+            if current == root_cluster {
+                return Ok(false);
+            }
+            current = self.child_dir(current, "..")?;
+        }
+        Err(FsError::InvalidFilesystem)
+    }
+    
+    /// 디렉토리의 `..` 엔트리가 `parent_cluster`를 가리키도록 수정 (루트는 0으로 기록)
+    fn set_parent_link(&mut self, dir_cluster: u32, parent_cluster: u32) -> FsResult<()> {
+        let target = if parent_cluster == self.boot_sector.root_cluster() { 0 } else { parent_cluster };
+        let record = self.scan_directory(dir_cluster)?
+            .into_iter()
+            .find(|record| record.entry.name == *b"..         ")
+            .ok_or(FsError::InvalidFilesystem)?;
+        let clusters = self.dir_clusters(dir_cluster)?;
+        self.update_dir_slots(&clusters, record.slot..record.slot + 1, |_, slot| {
+            let mut entry = Fat32DirEntry::from_bytes(slot);
+            entry.first_cluster_high = (target >> 16) as u16;
+            entry.first_cluster_low = (target & 0xFFFF) as u16;
+            slot.copy_from_slice(&entry.to_bytes());
+        })
+    }
+}
+
+impl FileSystem for Fat32FileSystem {
+    fn mount(&mut self) -> FsResult<()> {
+        if self.mounted {
+            return Err(FsError::Busy);
+        }
+        // 비정상 종료로 남은 트랜잭션을 먼저 재생
+        self.journal = self.load_journal()?;
+        self.replay_journal()?;
+        self.mounted = true;
+        Ok(())
+    }
+    
+    fn unmount(&mut self) -> FsResult<()> {
+        if !self.mounted {
+            return Err(FsError::InvalidFilesystem);
+        }
+        if let Some(journal) = self.journal.as_mut() {
+            if journal.state() == JournalState::Checkpointing {
+                journal.checkpoint(self.device.as_mut()).map_err(|_| FsError::IOError)?;
+            }
+        }
+        self.journal = None;
+        cache::invalidate_fs(self.cache_id);
+        self.mounted = false;
+        Ok(())
+    }
+    
+    fn open_file(&mut self, path: &str) -> FsResult<Box<dyn File>> {
         if !self.mounted {
             return Err(FsError::InvalidFilesystem);
         }
         
-        // 기존 파일 정보 가져오기
-        let (old_parent_path, old_filename) = self.split_parent_path(old_path)?;
-        let (new_parent_path, new_filename) = self.split_parent_path(new_path)?;
+        let (cluster, entry) = self.path_to_cluster(path)?;
+        if entry.is_directory() {
+            return Err(FsError::IsDirectory);
+        }
+        
+        Ok(Box::new(Fat32File {
+            filesystem: self as *mut Fat32FileSystem,
+            entry,
+            cluster,
+            offset: 0,
+        }))
+    }
+    
+    fn create_file(&mut self, path: &str) -> FsResult<Box<dyn File>> {
+        if !self.mounted {
+            return Err(FsError::InvalidFilesystem);
+        }
         
-        let (old_parent_cluster, _) = self.path_to_cluster(old_parent_path)?;
-        let (new_parent_cluster, _) = self.path_to_cluster(new_parent_path)?;
-        let (file_cluster, mut entry) = self.path_to_cluster(old_path)?;
+        // 경로 분리
+        let (dir_cluster, filename) = self.split_path(path)?;
+        let (cluster, entry) = self.transaction(|fs| fs.create_file_in(dir_cluster, filename))?;
         
-        // 새 파일명이 이미 존재하는지 확인
-        if self.find_directory_entry(new_parent_cluster, new_filename).is_ok() {
-            return Err(FsError::AlreadyExists);
+        // 파일 열기
+        Ok(Box::new(Fat32File {
+            filesystem: self,
+            entry,
+            cluster,
+            offset: 0,
+        }))
+    }
+    
+    fn open_dir(&mut self, path: &str) -> FsResult<Box<dyn Directory>> {
+        if !self.mounted {
+            return Err(FsError::InvalidFilesystem);
         }
         
-        // 엔트리 이름 변경
-        entry.name = Self::name_to_8_3(new_filename);
+        let (cluster, entry) = self.path_to_cluster(path)?;
+        if !entry.is_directory() {
+            return Err(FsError::NotADirectory);
+        }
         
-        // 같은 디렉토리 내에서 이름만 변경하는 경우
-        if old_parent_cluster == new_parent_cluster {
-            self.update_directory_entry(old_parent_cluster, old_filename, entry)?;
-        } else {
-            // 다른 디렉토리로 이동
-            self.delete_directory_entry(old_parent_cluster, old_filename)?;
-            self.add_directory_entry(new_parent_cluster, entry)?;
+        let root_cluster = self.boot_sector.root_cluster();
+        let resolved_cluster = if cluster == 0 { root_cluster } else { cluster };
+        Ok(Box::new(Fat32Directory {
+            filesystem: self as *mut Fat32FileSystem,
+            cluster: resolved_cluster,
+        }))
+    }
+    
+    fn create_dir(&mut self, path: &str) -> FsResult<()> {
+        if !self.mounted {
+            return Err(FsError::InvalidFilesystem);
         }
         
-        Ok(())
+        // 경로 분리
+        let (dir_cluster, dirname) = self.split_path(path)?;
+        self.transaction(|fs| fs.create_dir_in(dir_cluster, dirname))
+    }
+    
+    fn remove(&mut self, path: &str) -> FsResult<()> {
+        if !self.mounted {
+            return Err(FsError::InvalidFilesystem);
+        }
+        
+        // 경로에서 부모 디렉토리와 파일명 분리
+        let (parent_cluster, filename) = self.split_path(path)?;
+        self.transaction(|fs| fs.remove_in(parent_cluster, filename))
+    }
+    
+    fn rename(&mut self, old_path: &str, new_path: &str) -> FsResult<()> {
+        if !self.mounted {
+            return Err(FsError::InvalidFilesystem);
+        }
+        
+        let (old_parent_cluster, old_filename) = self.split_path(old_path)?;
+        let (new_parent_cluster, new_filename) = self.split_path(new_path)?;
+        self.transaction(|fs| fs.rename_in(old_parent_cluster, old_filename, new_parent_cluster, new_filename))
     }
     
     fn metadata(&mut self, path: &str) -> FsResult<FileMetadata> {
@@ -1201,9 +1789,9 @@ impl FileSystem for Fat32FileSystem {
             },
             size: entry.file_size as u64,
             mode: FileMode::new(0o644),
-            created: 0, // TODO: 날짜/시간 변환
-            modified: 0,
-            accessed: 0,
+            created: entry.created(),
+            modified: entry.modified(),
+            accessed: entry.accessed(),
             uid: 0,
             gid: 0,
         })
@@ -1212,6 +1800,43 @@ impl FileSystem for Fat32FileSystem {
     fn is_mounted(&self) -> bool {
         self.mounted
     }
+    
+    fn fs_type(&self) -> &'static str {
+        "fat32"
+    }
+    
+    fn statfs(&mut self) -> FsResult<FsStats> {
+        let cluster_size = self.boot_sector.sectors_per_cluster as u64 * 512;
+        let data_sectors = (self.boot_sector.total_sectors_32 as u64)
+            .saturating_sub(self.boot_sector.data_start_sector());
+        let total_clusters = data_sectors / self.boot_sector.sectors_per_cluster as u64;
+        
+        // FSInfo의 빈 클러스터 수는 갱신하지 않으므로 FAT를 직접 셈
+        let fat_start = self.boot_sector.fat_start_sector();
+        let mut fat_buf = [0u8; 512];
+        let mut free_clusters = 0;
+        let last_cluster = total_clusters + 2;
+        for sector_offset in 0..last_cluster.div_ceil(128) {
+            self.read_sector(fat_start + sector_offset, &mut fat_buf)?;
+            for (i, raw) in fat_buf.chunks_exact(4).enumerate() {
+                let cluster = sector_offset * 128 + i as u64;
+                if cluster < 2 || cluster >= last_cluster {
+                    continue;
+                }
+                if u32::from_le_bytes([raw[0], raw[1], raw[2], raw[3]]) & 0x0FFFFFFF == 0 {
+                    free_clusters += 1;
+                }
+            }
+        }
+        
+        Ok(FsStats {
+            block_size: cluster_size,
+            total_blocks: total_clusters,
+            free_blocks: free_clusters,
+            total_inodes: 0,
+            free_inodes: 0,
+        })
+    }
 }
 
 /// FAT32 파일 핸들
@@ -1227,64 +1852,12 @@ struct Fat32File {
 unsafe impl Send for Fat32File {}
 unsafe impl Sync for Fat32File {}
 
-impl File for Fat32File {
-    fn read(&mut self, buf: &mut [u8], offset: Option<Offset>) -> FsResult<usize> {
-        let read_offset = offset.unwrap_or(self.offset);
-        if read_offset < 0 {
-            return Ok(0);
-        }
-        
-        let file_size = self.entry.file_size as usize;
-        let read_offset = read_offset as usize;
-        
-        if read_offset >= file_size {
-            return Ok(0);
-        }
-        
-        let fs = unsafe { &mut *self.filesystem };
-        let cluster_size = fs.boot_sector.sectors_per_cluster as usize * 512;
-        let mut cluster_buf = alloc::vec![0u8; cluster_size];
-        let mut current_offset = read_offset;
-        let mut bytes_read = 0;
-        let max_read = core::cmp::min(buf.len(), file_size - read_offset);
-        
-        while current_offset < file_size && bytes_read < max_read {
-            // 현재 오프셋에 해당하는 클러스터 찾기
-            let (cluster, cluster_offset) = fs.find_cluster_for_offset(self.cluster, current_offset)?;
-            
-            // 클러스터 읽기
-            fs.read_single_cluster(cluster, &mut cluster_buf)?;
-            
-            // 버퍼에 복사할 데이터 길이 계산
-            let available_in_cluster = cluster_size - cluster_offset;
-            let remaining_in_file = file_size - current_offset;
-            let to_copy = core::cmp::min(
-                core::cmp::min(available_in_cluster, remaining_in_file),
-                max_read - bytes_read,
-            );
-            
-            // 데이터 복사
-            buf[bytes_read..bytes_read + to_copy]
-                .copy_from_slice(&cluster_buf[cluster_offset..cluster_offset + to_copy]);
-            
-            bytes_read += to_copy;
-            current_offset += to_copy;
-        }
-        
-        if offset.is_none() {
-            self.offset = (read_offset + bytes_read) as Offset;
-        }
-        
-        Ok(bytes_read)
-    }
-    
-    fn write(&mut self, buf: &[u8], offset: Option<Offset>) -> FsResult<usize> {
-        let write_offset = offset.unwrap_or(self.offset);
-        if write_offset < 0 {
-            return Err(FsError::InvalidPath);
-        }
-        
-        let write_offset = write_offset as usize;
+impl Fat32File {
+    /// `write_offset`부터 디스크에 쓰기 (캐시 반영은 호출자 몫)
+    ///
+    /// # Returns
+    /// 쓴 바이트 수
+    fn write_at(&mut self, buf: &[u8], write_offset: usize) -> FsResult<usize> {
         let fs = unsafe { &mut *self.filesystem };
         let cluster_size = fs.boot_sector.sectors_per_cluster as usize * 512;
         let mut bytes_written = 0;
@@ -1338,7 +1911,7 @@ impl File for Fat32File {
                 .copy_from_slice(&buf[bytes_written..bytes_written + to_write]);
             
             // 클러스터 쓰기 (데이터는 저널링 안 함, 성능상 이유)
-            fs.write_cluster_direct(cluster, &cluster_buf)?;
+            fs.write_cluster(cluster, &cluster_buf)?;
             
             bytes_written += to_write;
             current_offset += to_write;
@@ -1351,6 +1924,71 @@ impl File for Fat32File {
             }
         }
         
+        Ok(bytes_written)
+    }
+}
+
+impl File for Fat32File {
+    fn read(&mut self, buf: &mut [u8], offset: Option<Offset>) -> FsResult<usize> {
+        let read_offset = offset.unwrap_or(self.offset);
+        if read_offset < 0 {
+            return Ok(0);
+        }
+        
+        let file_size = self.entry.file_size as usize;
+        let read_offset = read_offset as usize;
+        
+        if read_offset >= file_size {
+            return Ok(0);
+        }
+        
+        // 페이지 캐시를 거쳐 읽기 (빠진 페이지와 미리 읽을 페이지만 디스크에서 읽음)
+        let fs = unsafe { &mut *self.filesystem };
+        let start_cluster = self.cluster;
+        let mut cursor = None;
+        let bytes_read = cache::read(
+            fs.cache_key(start_cluster),
+            file_size as u64,
+            read_offset as u64,
+            buf,
+            |index, page| fs.read_file_range(start_cluster, file_size, index as usize * PAGE_SIZE, page, &mut cursor),
+        )?;
+        
+        if offset.is_none() {
+            self.offset = (read_offset + bytes_read) as Offset;
+        }
+        
+        Ok(bytes_read)
+    }
+    
+    fn write(&mut self, buf: &[u8], offset: Option<Offset>) -> FsResult<usize> {
+        let write_offset = offset.unwrap_or(self.offset);
+        if write_offset < 0 {
+            return Err(FsError::InvalidPath);
+        }
+        
+        let write_offset = write_offset as usize;
+        let key = unsafe { &*self.filesystem }.cache_key(self.cluster);
+        // 이번 쓰기로 늘어난 클러스터 체인을 한 트랜잭션으로 커밋
+        let size_before = self.entry.file_size;
+        unsafe { &mut *self.filesystem }.begin_transaction()?;
+        let result = self.write_at(buf, write_offset);
+        let bytes_written = match unsafe { &mut *self.filesystem }.end_transaction(result) {
+            Ok(bytes_written) => bytes_written,
+            Err(e) => {
+                // FAT 변경은 롤백됐지만 일부 클러스터는 이미 기록됐을 수 있으므로 캐시를 버림
+                self.entry.file_size = size_before;
+                cache::invalidate(key);
+                return Err(e);
+            }
+        };
+        // 디스크에 쓴 내용을 캐시된 페이지에 반영 (write-through)
+        cache::update(key, write_offset as u64, &buf[..bytes_written]);
+        
+        if bytes_written > 0 {
+            self.entry.set_modified(wallclock::now_unix());
+        }
+        
         if offset.is_none() {
             self.offset = (write_offset + bytes_written) as Offset;
         }
@@ -1363,9 +2001,9 @@ impl File for Fat32File {
             file_type: FileType::Regular,
             size: self.entry.file_size as u64,
             mode: FileMode::new(0o644),
-            created: 0,
-            modified: 0,
-            accessed: 0,
+            created: self.entry.created(),
+            modified: self.entry.modified(),
+            accessed: self.entry.accessed(),
             uid: 0,
             gid: 0,
         })
@@ -1389,6 +2027,8 @@ impl File for Fat32File {
 }
 
 /// FAT32 디렉토리 핸들
+///
+/// 연산의 이름은 이 디렉토리 기준 상대 경로로 해석합니다 (`sub/name`, `../name` 등).
 struct Fat32Directory {
     filesystem: *mut Fat32FileSystem,
     cluster: u32,
@@ -1397,59 +2037,208 @@ struct Fat32Directory {
 unsafe impl Send for Fat32Directory {}
 unsafe impl Sync for Fat32Directory {}
 
-impl Directory for Fat32Directory {
-    fn read_dir(&self) -> FsResult<Vec<(String, FileType)>> {
-        // TODO: 디렉토리 읽기 구현
-        // 현재는 빈 벡터 반환 (컴파일 오류 방지)
-        let mut result = Vec::new();
-        
-        // 디렉토리 데이터 읽기
+impl Fat32Directory {
+    /// 마운트된 파일시스템 참조
+    fn filesystem(&self) -> FsResult<&mut Fat32FileSystem> {
         let fs = unsafe { &mut *self.filesystem };
-        let cluster_size = fs.boot_sector.sectors_per_cluster as usize * 512;
-        let mut dir_buf = alloc::vec![0u8; cluster_size * 4];
-        let len = match fs.read_cluster_chain(self.cluster, &mut dir_buf) {
-            Ok(l) => l,
-            Err(_) => return Ok(result),
-        };
-        
-        // 디렉토리 엔트리 파싱
-        let num_entries = len / size_of::<Fat32DirEntry>();
-        for i in 0..num_entries {
-            let entry = unsafe {
-                core::ptr::read(dir_buf.as_ptr().add(i * size_of::<Fat32DirEntry>()) as *const Fat32DirEntry)
-            };
-            
-            if entry.is_deleted() || entry.is_long_name() || entry.is_volume_label() {
-                continue;
-            }
-            
-            let name = entry.short_name();
-            let file_type = if entry.is_directory() {
-                FileType::Directory
-            } else {
-                FileType::Regular
-            };
-            
-            result.push((name, file_type));
+        if !fs.mounted {
+            return Err(FsError::InvalidFilesystem);
         }
-        
-        Ok(result)
+        Ok(fs)
+    }
+}
+
+impl Directory for Fat32Directory {
+    fn read_dir(&self) -> FsResult<Vec<(String, FileType)>> {
+        let fs = self.filesystem()?;
+        let records = fs.scan_directory(self.cluster)?;
+        
+        Ok(records
+            .into_iter()
+            .map(|record| {
+                let file_type = if record.entry.is_directory() {
+                    FileType::Directory
+                } else {
+                    FileType::Regular
+                };
+                (record.name, file_type)
+            })
+            .collect())
     }
     
-    fn create_file(&mut self, _name: &str) -> FsResult<()> {
-        Err(FsError::IOError)
+    fn create_file(&mut self, name: &str) -> FsResult<()> {
+        let fs = self.filesystem()?;
+        let (dir_cluster, name) = fs.resolve_parent(self.cluster, name)?;
+        fs.transaction(|fs| fs.create_file_in(dir_cluster, name)).map(|_| ())
     }
     
-    fn create_dir(&mut self, _name: &str) -> FsResult<()> {
-        Err(FsError::IOError)
+    fn create_dir(&mut self, name: &str) -> FsResult<()> {
+        let fs = self.filesystem()?;
+        let (dir_cluster, name) = fs.resolve_parent(self.cluster, name)?;
+        fs.transaction(|fs| fs.create_dir_in(dir_cluster, name))
     }
     
-    fn remove(&mut self, _name: &str) -> FsResult<()> {
-        Err(FsError::IOError)
+    fn remove(&mut self, name: &str) -> FsResult<()> {
+        let fs = self.filesystem()?;
+        let (dir_cluster, name) = fs.resolve_parent(self.cluster, name)?;
+        fs.transaction(|fs| fs.remove_in(dir_cluster, name))
     }
     
-    fn rename(&mut self, _old_name: &str, _new_name: &str) -> FsResult<()> {
-        Err(FsError::IOError)
+    fn rename(&mut self, old_name: &str, new_name: &str) -> FsResult<()> {
+        let fs = self.filesystem()?;
+        let (old_dir, old_name) = fs.resolve_parent(self.cluster, old_name)?;
+        let (new_dir, new_name) = fs.resolve_parent(self.cluster, new_name)?;
+        fs.transaction(|fs| fs.rename_in(old_dir, old_name, new_dir, new_name))
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test_case]
+    fn test_fat32_lfn_checksum() {
+        assert_eq!(lfn_checksum(b"LONGFI~1TXT"), 212);
+    }
+
+    #[test_case]
+    fn test_fat32_short_name_basis() {
+        assert_eq!(short_name_basis("README.TXT"), (*b"README  TXT", false));
+        // 대소문자는 LFN에 남으므로 손실 아님
+        assert_eq!(short_name_basis("readme.txt"), (*b"README  TXT", false));
+        assert_eq!(short_name_basis("long file name.text"), (*b"LONGFILETEX", true));
+        assert_eq!(short_name_basis(".bashrc"), (*b"BASHRC     ", true));
+        assert_eq!(short_name_basis("a+b.tar.gz"), (*b"A_BTAR  GZ ", true));
+        assert_eq!(short_name_basis("한글.txt"), (*b"__      TXT", true));
+    }
+
+    #[test_case]
+    fn test_fat32_short_name_tail() {
+        assert_eq!(short_name_with_tail(b"LONGFILETEX", 1), *b"LONGFI~1TEX");
+        assert_eq!(short_name_with_tail(b"LONGFILETEX", 12), *b"LONGF~12TEX");
+        assert_eq!(short_name_with_tail(b"AB      C  ", 3), *b"AB~3    C  ");
+    }
+
+    #[test_case]
+    fn test_fat32_lfn_roundtrip() {
+        let name = "A rather long file name — 긴 이름.txt";
+        let units = encode_long_name(name).unwrap();
+        let short = *b"ARATHE~1TXT";
+        let entries = long_name_entries(&units, lfn_checksum(&short));
+        assert_eq!(entries.len(), units.len().div_ceil(Fat32LfnEntry::CHARS_PER_ENTRY));
+        assert_eq!(entries[0].order, entries.len() as u8 | Fat32LfnEntry::LAST_ENTRY);
+
+        let mut parser = LfnParser::default();
+        for (slot, entry) in entries.iter().enumerate() {
+            parser.push(&Fat32LfnEntry::from_bytes(&entry.to_bytes()), slot + 5);
+        }
+        assert_eq!(parser.finish(&short), Some((String::from(name), 5)));
+
+        // 체크섬이 다른 짧은 엔트리면 긴 이름 무시
+        for (slot, entry) in entries.iter().enumerate() {
+            parser.push(entry, slot);
+        }
+        assert_eq!(parser.finish(b"OTHER   TXT"), None);
+
+        // 조각이 빠지면 무시
+        for (slot, entry) in entries.iter().enumerate().skip(1) {
+            parser.push(entry, slot);
+        }
+        assert_eq!(parser.finish(&short), None);
+    }
+
+    #[test_case]
+    fn test_fat32_long_name_validation() {
+        assert!(encode_long_name("My Document.txt").is_ok());
+        assert_eq!(encode_long_name("a:b"), Err(FsError::InvalidPath));
+        assert_eq!(encode_long_name(".."), Err(FsError::InvalidPath));
+        assert_eq!(encode_long_name(&"x".repeat(256)), Err(FsError::InvalidPath));
+        assert!(names_equal("Ärger.TXT", "äRGER.txt"));
+        // 글자 수가 바뀌는 대문자 변환은 하지 않음
+        assert!(!names_equal("Straße.TXT", "STRASSE.txt"));
+        assert!(!names_equal("a.txt", "b.txt"));
+    }
+
+    /// 예약 32섹터, FAT 2개(각 16섹터), 클러스터당 1섹터인 빈 FAT32 볼륨
+    fn format_image() -> journal::tests::MemDevice {
+        let mut device = journal::tests::MemDevice::new(32 + 2 * 16 + 1024);
+        let mut boot = [0u8; 512];
+        boot[11..13].copy_from_slice(&512u16.to_le_bytes());
+        boot[13] = 1;
+        boot[14..16].copy_from_slice(&32u16.to_le_bytes());
+        boot[16] = 2;
+        boot[32..36].copy_from_slice(&(32u32 + 2 * 16 + 1024).to_le_bytes());
+        boot[36..40].copy_from_slice(&16u32.to_le_bytes());
+        boot[44..48].copy_from_slice(&2u32.to_le_bytes());
+        boot[48..50].copy_from_slice(&1u16.to_le_bytes());
+        boot[50..52].copy_from_slice(&6u16.to_le_bytes());
+        boot[82..90].copy_from_slice(b"FAT32   ");
+        boot[510..512].copy_from_slice(&0xAA55u16.to_le_bytes());
+        device.write_block(0, &boot).unwrap();
+
+        // 클러스터 0, 1은 예약, 2는 루트 디렉토리
+        let mut fat = [0u8; 512];
+        fat[0..4].copy_from_slice(&0x0FFF_FFF8u32.to_le_bytes());
+        fat[4..8].copy_from_slice(&0x0FFF_FFFFu32.to_le_bytes());
+        fat[8..12].copy_from_slice(&0x0FFF_FFFFu32.to_le_bytes());
+        device.write_block(32, &fat).unwrap();
+        device.write_block(48, &fat).unwrap();
+        device
+    }
+
+    #[test_case]
+    fn test_fat32_journal_is_opt_in() {
+        let device = format_image();
+        let mut fs = Fat32FileSystem::new(Box::new(device.clone())).unwrap();
+        fs.mount().unwrap();
+        // 빈 예약 섹터를 마운트만으로 저널로 만들지 않음
+        assert!(fs.journal.is_none());
+        assert_eq!(device.sector(13), [0u8; 512]);
+
+        fs.format_journal().unwrap();
+        assert!(fs.journal.is_some());
+        assert_eq!(fs.format_journal(), Err(FsError::AlreadyExists));
+
+        // 다시 마운트하면 만들어 둔 저널을 씀
+        let mut fs = Fat32FileSystem::new(Box::new(device)).unwrap();
+        fs.mount().unwrap();
+        assert!(fs.journal.is_some());
+    }
+
+    #[test_case]
+    fn test_fat32_operation_is_one_transaction() {
+        let device = format_image();
+        let mut fs = Fat32FileSystem::new(Box::new(device.clone())).unwrap();
+        fs.mount().unwrap();
+        fs.format_journal().unwrap();
+
+        // 커밋 전 읽기도 같은 트랜잭션에서 바꾼 FAT를 봄
+        let (a, b) = fs.transaction(|fs| {
+            let a = fs.find_free_cluster()?;
+            fs.write_fat_entry_journaled(a, 0x0FFF_FFFF)?;
+            let b = fs.find_free_cluster()?;
+            fs.write_fat_entry_journaled(b, 0x0FFF_FFFF)?;
+            Ok((a, b))
+        }).unwrap();
+        assert_eq!((a, b), (3, 4));
+        assert_eq!(fs.journal.as_ref().unwrap().state(), JournalState::Idle);
+        for fat in [32, 48] {
+            let sector = device.sector(fat);
+            assert_eq!(&sector[12..16], &0x0FFF_FFFFu32.to_le_bytes());
+            assert_eq!(&sector[16..20], &0x0FFF_FFFFu32.to_le_bytes());
+        }
+
+        // 저널 용량을 넘는 연산은 나누지 않고 통째로 실패
+        let capacity = fs.journal.as_ref().unwrap().capacity() as u32;
+        let result = fs.transaction(|fs| {
+            for i in 0..capacity {
+                fs.write_fat_entry_journaled(5 + i * 128, 0x0FFF_FFFF)?;
+            }
+            Ok(())
+        });
+        assert_eq!(result, Err(FsError::OutOfSpace));
+        assert_eq!(fs.journal.as_ref().unwrap().state(), JournalState::Idle);
+        assert_eq!(fs.read_fat_entry(5).unwrap(), 0);
+        assert_eq!(device.sector(33), [0u8; 512]);
+    }
+}
@@ -4,10 +4,8 @@
 
 use super::fat32::Fat32FileSystem;
 use super::vfs::{FsResult, FsError};
-use super::journal::{recover, JournalEntry};
 use crate::drivers::ata::BlockDevice;
 use alloc::vec::Vec;
-use crate::fs::vfs::FileSystem;
 use alloc::boxed::Box;
 
 /// 파일시스템 오류 타입
@@ -111,32 +109,25 @@ impl Fsck {
     
     /// 저널 복구 (비정상 종료 감지 및 재생)
     fn recover_journal(&mut self, fs: &mut Fat32FileSystem) -> FsResult<()> {
-        // 저널에서 복구 가능한 엔트리 가져오기
-        // TODO: 실제 디바이스와 저널 시작 블록을 전달해야 함
-        match super::journal::recover(None, None) {
-            Ok(entries) => {
-                if !entries.is_empty() {
-                    self.result.add_error(); // 비정상 종료 감지
-                    crate::log_warn!("Unclean shutdown detected: {} journal entries to replay", entries.len());
-                    
-                    if self.repair {
-                        // 저널 엔트리를 재생
-                        for entry in &entries {
-                            // 실제로는 디스크에 쓰기 필요
-                            // 여기서는 로그만 남김
-                            crate::log_info!("Replaying journal entry: block={}, type={:?}", 
-                                          entry.block_num, entry.entry_type);
-                            self.result.repair_success();
-                        }
-                        crate::log_info!("Journal recovery complete: {} entries replayed", entries.len());
-                    } else {
-                        crate::log_warn!("Journal recovery needed but repair mode disabled");
-                    }
-                }
-            }
-            Err(e) => {
-                crate::log_warn!("Journal recovery failed: {}", e);
+        // 커밋됐지만 원래 위치에 기록되지 않은 엔트리
+        let entries = fs.pending_journal_entries()?;
+        if entries.is_empty() {
+            return Ok(());
+        }
+        
+        self.result.add_error(); // 비정상 종료 감지
+        crate::log_warn!("Unclean shutdown detected: {} journal entries to replay", entries.len());
+        
+        if self.repair {
+            for entry in &entries {
+                crate::log_info!("Replaying journal entry: block={}, type={:?}",
+                              entry.block_num, entry.entry_type);
             }
+            fs.replay_journal()?;
+            self.result.repair_success();
+            crate::log_info!("Journal recovery complete: {} entries replayed", entries.len());
+        } else {
+            crate::log_warn!("Journal recovery needed but repair mode disabled");
         }
         
         Ok(())
@@ -202,8 +193,8 @@ impl Fsck {
 
 /// 파일시스템 검사 실행
 pub fn run_fsck(device: Box<dyn BlockDevice>, repair: bool) -> FsResult<FsckResult> {
+    // 마운트하면 저널이 먼저 재생되므로 마운트하지 않고 검사
     let mut fs = Fat32FileSystem::new(device)?;
-    fs.mount()?;
     
     let mut fsck = Fsck::new(repair);
     let result = fsck.check(&mut fs)?;
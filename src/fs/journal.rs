@@ -1,11 +1,40 @@
 //! 파일시스템 저널링 (Journaling)
 //!
-//! 안전한 파일시스템 쓰기를 위한 저널 레이어 구현
+//! 블록 디바이스의 예약 영역에 두는 선행 기록(write-ahead) 저널입니다. 트랜잭션 동안 바뀔
+//! 섹터를 모아 두었다가 커밋 시 저널 영역에 다음 순서로 기록합니다.
+//!
+//! ```text
+//! | 헤더 (JRNL, 다음 순번) | 디스크립터 (JDSC, 순번, 원래 위치 목록) | 섹터 사본 ... | 커밋 (JCMT, 체크섬) |
+//! ```
+//!
+//! 커밋 블록까지 기록되면 섹터를 원래 위치에 쓰고(체크포인트) 헤더의 순번을 올립니다.
+//! 체크포인트 도중 전원이 끊기면 헤더 순번과 같은 순번의 트랜잭션이 남아 있으므로 다음
+//! 마운트에서 재생합니다. 커밋 블록이 없거나 체크섬이 맞지 않는 트랜잭션은 원래 위치를
+//! 건드리기 전에 중단된 것이므로 버립니다.
 
+use alloc::vec;
 use alloc::vec::Vec;
 use alloc::collections::BTreeMap;
-use spin::Mutex;
-use crate::drivers::ata::{BlockDevice, BlockDeviceError};
+use crate::drivers::ata::BlockDevice;
+use super::simple_journal_fs::layout::crc32_update;
+
+/// 저널 블록 크기 (섹터 단위로 기록)
+pub const JOURNAL_BLOCK_SIZE: usize = 512;
+
+/// 헤더, 디스크립터, 커밋 블록과 사본 하나를 담을 최소 크기
+pub const MIN_JOURNAL_BLOCKS: u64 = 4;
+
+const HEADER_MAGIC: &[u8; 4] = b"JRNL";
+const DESCRIPTOR_MAGIC: &[u8; 4] = b"JDSC";
+const COMMIT_MAGIC: &[u8; 4] = b"JCMT";
+const JOURNAL_VERSION: u32 = 1;
+
+/// 디스크립터 헤더 크기 (매직, 순번, 블록 수)
+const DESCRIPTOR_HEADER: usize = 24;
+/// 디스크립터 항목 크기 (원래 위치, 엔트리 타입)
+const DESCRIPTOR_ENTRY: usize = 16;
+/// 디스크립터 한 블록에 담을 수 있는 최대 섹터 수
+const MAX_DESCRIPTOR_ENTRIES: usize = (JOURNAL_BLOCK_SIZE - DESCRIPTOR_HEADER) / DESCRIPTOR_ENTRY;
 
 /// 저널 엔트리 타입
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -16,6 +45,23 @@ pub enum JournalEntryType {
     MetadataWrite,
 }
 
+impl JournalEntryType {
+    fn to_byte(self) -> u8 {
+        match self {
+            JournalEntryType::DataWrite => 0,
+            JournalEntryType::MetadataWrite => 1,
+        }
+    }
+
+    fn from_byte(byte: u8) -> Option<Self> {
+        match byte {
+            0 => Some(JournalEntryType::DataWrite),
+            1 => Some(JournalEntryType::MetadataWrite),
+            _ => None,
+        }
+    }
+}
+
 /// 저널 엔트리
 #[derive(Clone)]
 pub struct JournalEntry {
@@ -38,273 +84,455 @@ pub enum JournalState {
     Transaction,
     /// 커밋 중
     Committing,
-    /// 체크포인트 중
+    /// 체크포인트 중 (커밋은 디스크에 기록됨)
     Checkpointing,
 }
 
+fn get_u32(buf: &[u8], off: usize) -> u32 {
+    u32::from_le_bytes([buf[off], buf[off + 1], buf[off + 2], buf[off + 3]])
+}
+
+fn put_u32(buf: &mut [u8], off: usize, value: u32) {
+    buf[off..off + 4].copy_from_slice(&value.to_le_bytes());
+}
+
+fn get_u64(buf: &[u8], off: usize) -> u64 {
+    let mut bytes = [0u8; 8];
+    bytes.copy_from_slice(&buf[off..off + 8]);
+    u64::from_le_bytes(bytes)
+}
+
+fn put_u64(buf: &mut [u8], off: usize, value: u64) {
+    buf[off..off + 8].copy_from_slice(&value.to_le_bytes());
+}
+
+/// 트랜잭션 체크섬 (디스크립터와 섹터 사본)
+fn tx_checksum(descriptor: &[u8], entries: &[&JournalEntry]) -> u32 {
+    let mut crc = crc32_update(0xFFFF_FFFF, descriptor);
+    for entry in entries {
+        crc = crc32_update(crc, &entry.data);
+    }
+    crc ^ 0xFFFF_FFFF
+}
+
 /// 저널 관리자
 pub struct Journal {
+    /// 저널 영역 시작 블록 (헤더)
+    start: u64,
+    /// 저널 영역 블록 수
+    blocks: u64,
     /// 저널 엔트리들
     entries: BTreeMap<u64, JournalEntry>,
     /// 현재 상태
     state: JournalState,
-    /// 시퀀스 번호
+    /// 다음에 커밋할 트랜잭션 순번 (헤더에 기록된 값)
     sequence: u64,
-    /// 최대 저널 엔트리 수
-    max_entries: usize,
 }
 
 impl Journal {
-    /// 새 저널 생성
-    pub fn new() -> Self {
-        Self {
+    /// 빈 저널 영역 초기화
+    ///
+    /// # Arguments
+    /// * `device` - 블록 디바이스
+    /// * `start` - 저널 영역 시작 블록
+    /// * `blocks` - 저널 영역 블록 수
+    pub fn format(device: &mut dyn BlockDevice, start: u64, blocks: u64) -> Result<Self, &'static str> {
+        if blocks < MIN_JOURNAL_BLOCKS {
+            return Err("Journal area too small");
+        }
+        let journal = Self {
+            start,
+            blocks,
             entries: BTreeMap::new(),
             state: JournalState::Idle,
-            sequence: 0,
-            max_entries: 64, // 최대 64개 엔트리
+            sequence: 1,
+        };
+        device.write_block(start + 1, &[0u8; JOURNAL_BLOCK_SIZE])
+            .map_err(|_| "Failed to clear journal descriptor")?;
+        journal.write_header(device)?;
+        Ok(journal)
+    }
+
+    /// 디스크의 저널 영역 열기 (재생은 하지 않음)
+    ///
+    /// # Arguments
+    /// * `device` - 블록 디바이스
+    /// * `start` - 저널 영역 시작 블록
+    /// * `blocks` - 저널 영역 블록 수
+    pub fn open(device: &mut dyn BlockDevice, start: u64, blocks: u64) -> Result<Self, &'static str> {
+        let mut header = [0u8; JOURNAL_BLOCK_SIZE];
+        device.read_block(start, &mut header).map_err(|_| "Failed to read journal header")?;
+
+        if &header[0..4] != HEADER_MAGIC {
+            return Err("Invalid journal magic");
         }
+        let checksum = crc32_update(0xFFFF_FFFF, &header[..24]) ^ 0xFFFF_FFFF;
+        if get_u32(&header, 24) != checksum {
+            return Err("Journal header checksum mismatch");
+        }
+        if get_u32(&header, 4) != JOURNAL_VERSION {
+            return Err("Unsupported journal version");
+        }
+        if get_u64(&header, 8) != blocks || blocks < MIN_JOURNAL_BLOCKS {
+            return Err("Journal area size mismatch");
+        }
+
+        Ok(Self {
+            start,
+            blocks,
+            entries: BTreeMap::new(),
+            state: JournalState::Idle,
+            sequence: get_u64(&header, 16),
+        })
+    }
+
+    /// 헤더 기록 (다음 트랜잭션 순번 포함)
+    fn write_header(&self, device: &mut dyn BlockDevice) -> Result<(), &'static str> {
+        let mut header = [0u8; JOURNAL_BLOCK_SIZE];
+        header[0..4].copy_from_slice(HEADER_MAGIC);
+        put_u32(&mut header, 4, JOURNAL_VERSION);
+        put_u64(&mut header, 8, self.blocks);
+        put_u64(&mut header, 16, self.sequence);
+        let checksum = crc32_update(0xFFFF_FFFF, &header[..24]) ^ 0xFFFF_FFFF;
+        put_u32(&mut header, 24, checksum);
+        device.write_block(self.start, &header)
+            .map(|_| ())
+            .map_err(|_| "Failed to write journal header")
     }
-    
+
+    /// 트랜잭션 하나에 담을 수 있는 최대 섹터 수
+    pub fn capacity(&self) -> usize {
+        ((self.blocks - 3) as usize).min(MAX_DESCRIPTOR_ENTRIES)
+    }
+
+    /// 저널 영역 (시작 블록, 블록 수)
+    pub fn region(&self) -> (u64, u64) {
+        (self.start, self.blocks)
+    }
+
     /// 트랜잭션 시작
     pub fn begin_transaction(&mut self) {
         // 이미 트랜잭션이 진행 중이면 무시 (중첩 트랜잭션 지원)
         if self.state == JournalState::Idle {
             self.state = JournalState::Transaction;
-            self.sequence += 1;
         }
     }
-    
+
     /// 트랜잭션이 진행 중인지 확인
     pub fn in_transaction(&self) -> bool {
         matches!(self.state, JournalState::Transaction | JournalState::Committing | JournalState::Checkpointing)
     }
-    
-    /// 저널 엔트리 추가
+
+    /// 저널 엔트리 추가 (같은 블록을 다시 쓰면 마지막 내용만 남김)
     pub fn add_entry(&mut self, block_num: u64, data: &[u8], entry_type: JournalEntryType) -> Result<(), &'static str> {
         if self.state != JournalState::Transaction {
             return Err("Not in transaction");
         }
-        
-        if self.entries.len() >= self.max_entries {
+
+        if block_num >= self.start && block_num < self.start + self.blocks {
+            return Err("Block inside journal area");
+        }
+
+        if !self.has_room_for(block_num) {
             return Err("Journal full");
         }
-        
+
         let mut entry_data = [0u8; 512];
         let copy_len = data.len().min(512);
         entry_data[..copy_len].copy_from_slice(&data[..copy_len]);
-        
+
         let entry = JournalEntry {
             block_num,
             data: entry_data,
             entry_type,
             sequence: self.sequence,
         };
-        
+
         self.entries.insert(block_num, entry);
         Ok(())
     }
-    
-    /// 트랜잭션 커밋 (저널에 쓰기)
-    pub fn commit(&mut self) -> Result<(), &'static str> {
+
+    /// 이 블록을 트랜잭션에 더 담을 수 있는지 (이미 담긴 블록은 덮어쓰므로 항상 가능)
+    pub fn has_room_for(&self, block_num: u64) -> bool {
+        self.entries.contains_key(&block_num) || self.entries.len() < self.capacity()
+    }
+
+    /// 아직 원래 위치에 기록되지 않은 블록 내용
+    ///
+    /// 트랜잭션 도중의 읽기가 같은 트랜잭션에서 바꾼 내용을 보도록 할 때 씁니다.
+    pub fn pending(&self, block_num: u64) -> Option<&[u8; 512]> {
+        self.entries.get(&block_num).map(|entry| &entry.data)
+    }
+
+    /// 트랜잭션 커밋 (저널 영역에 디스크립터, 섹터 사본, 커밋 블록 기록)
+    ///
+    /// 실패하면 트랜잭션을 버립니다. 커밋 블록이 없으므로 디스크는 이전 상태 그대로입니다.
+    pub fn commit(&mut self, device: &mut dyn BlockDevice) -> Result<(), &'static str> {
         if self.state != JournalState::Transaction {
             return Err("Not in transaction");
         }
-        
+        if self.entries.is_empty() {
+            self.state = JournalState::Idle;
+            return Ok(());
+        }
+
         self.state = JournalState::Committing;
-        // 실제로는 저널 영역에 쓰기 필요
-        // 여기서는 간단히 상태만 변경
+        let entries: Vec<&JournalEntry> = self.entries.values().collect();
+
+        let mut descriptor = [0u8; JOURNAL_BLOCK_SIZE];
+        descriptor[0..4].copy_from_slice(DESCRIPTOR_MAGIC);
+        put_u64(&mut descriptor, 8, self.sequence);
+        put_u32(&mut descriptor, 16, entries.len() as u32);
+        for (i, entry) in entries.iter().enumerate() {
+            let off = DESCRIPTOR_HEADER + i * DESCRIPTOR_ENTRY;
+            put_u64(&mut descriptor, off, entry.block_num);
+            descriptor[off + 8] = entry.entry_type.to_byte();
+        }
+
+        let mut commit = [0u8; JOURNAL_BLOCK_SIZE];
+        commit[0..4].copy_from_slice(COMMIT_MAGIC);
+        put_u64(&mut commit, 8, self.sequence);
+        put_u32(&mut commit, 16, entries.len() as u32);
+        put_u32(&mut commit, 20, tx_checksum(&descriptor, &entries));
+
+        let start = self.start;
+        let written = (|| {
+            device.write_block(start + 1, &descriptor)?;
+            for (i, entry) in entries.iter().enumerate() {
+                device.write_block(start + 2 + i as u64, &entry.data)?;
+            }
+            device.write_block(start + 2 + entries.len() as u64, &commit)
+        })();
+        if written.is_err() {
+            self.rollback();
+            return Err("Failed to write journal transaction");
+        }
+
         self.state = JournalState::Checkpointing;
         Ok(())
     }
-    
-    /// 체크포인트 (실제 파일시스템에 쓰기)
-    pub fn checkpoint(&mut self) -> Result<(), &'static str> {
+
+    /// 체크포인트 (커밋된 섹터를 원래 위치에 쓰고 헤더 순번을 올림)
+    ///
+    /// 실패하면 상태를 유지하므로 다시 호출하거나 다음 마운트에서 재생할 수 있습니다.
+    pub fn checkpoint(&mut self, device: &mut dyn BlockDevice) -> Result<(), &'static str> {
         if self.state != JournalState::Checkpointing {
             return Err("Not checkpointing");
         }
-        
-        // 실제로는 저널 엔트리를 실제 파일시스템에 쓰기
-        // 여기서는 엔트리 클리어만 수행
+
+        for entry in self.entries.values() {
+            device.write_block(entry.block_num, &entry.data)
+                .map_err(|_| "Failed to checkpoint journal entry")?;
+        }
+        self.sequence += 1;
+        if let Err(e) = self.write_header(device) {
+            self.sequence -= 1;
+            return Err(e);
+        }
+
         self.entries.clear();
         self.state = JournalState::Idle;
         Ok(())
     }
-    
+
     /// 트랜잭션 롤백
     pub fn rollback(&mut self) {
         self.entries.clear();
         self.state = JournalState::Idle;
     }
-    
-    /// 저널 복구 (재부트 후)
-    /// 
-    /// # Arguments
-    /// * `device` - 블록 디바이스 (저널 읽기용)
-    /// * `journal_start_block` - 저널 영역 시작 블록
-    pub fn recover(&mut self, device: Option<&mut dyn BlockDevice>, journal_start_block: Option<u64>) -> Result<Vec<JournalEntry>, &'static str> {
-        // 디스크에서 저널 읽기
-        if let (Some(dev), Some(start_block)) = (device, journal_start_block) {
-            match self.read_journal_from_disk(dev, start_block) {
-                Ok(entries) => {
-                    if !entries.is_empty() {
-                        crate::log_info!("Journal recovery: found {} entries to replay", entries.len());
-                        self.entries.clear();
-                        self.state = JournalState::Idle;
-                        return Ok(entries);
-                    }
-                }
-                Err(e) => {
-                    crate::log_warn!("Journal recovery failed: {:?}", e);
-                    // 계속 진행 (저널이 없거나 손상된 경우)
-                }
-            }
+
+    /// 커밋됐지만 체크포인트가 끝나지 않은 트랜잭션 읽기 (디스크는 바꾸지 않음)
+    ///
+    /// # Returns
+    /// 재생할 엔트리 (없거나 커밋이 완료되지 않았으면 빈 벡터)
+    pub fn recover(&mut self, device: &mut dyn BlockDevice) -> Result<Vec<JournalEntry>, &'static str> {
+        let mut descriptor = [0u8; JOURNAL_BLOCK_SIZE];
+        device.read_block(self.start + 1, &mut descriptor)
+            .map_err(|_| "Failed to read journal descriptor")?;
+
+        // 순번이 다르면 이미 체크포인트된 트랜잭션
+        if &descriptor[0..4] != DESCRIPTOR_MAGIC || get_u64(&descriptor, 8) != self.sequence {
+            return Ok(Vec::new());
         }
-        
-        // 메모리 엔트리 반환 (없으면 빈 벡터)
-        let entries: Vec<JournalEntry> = self.entries.values().cloned().collect();
-        self.entries.clear();
-        self.state = JournalState::Idle;
-        Ok(entries)
-    }
-    
-    /// 디스크에서 저널 읽기
-    fn read_journal_from_disk(&self, device: &mut dyn BlockDevice, start_block: u64) -> Result<Vec<JournalEntry>, &'static str> {
-        let mut header = [0u8; 512];
-        device.read_block(start_block, &mut header).map_err(|_| "Failed to read journal header")?;
-        
-        // 매직 넘버 확인
-        if &header[0..4] != b"JRNL" {
-            return Err("Invalid journal magic");
+        let count = get_u32(&descriptor, 16) as usize;
+        if count == 0 || count > self.capacity() {
+            crate::log_warn!("Journal transaction {} has invalid size {}", self.sequence, count);
+            return Ok(Vec::new());
+        }
+
+        let mut commit = [0u8; JOURNAL_BLOCK_SIZE];
+        device.read_block(self.start + 2 + count as u64, &mut commit)
+            .map_err(|_| "Failed to read journal commit block")?;
+        if &commit[0..4] != COMMIT_MAGIC || get_u64(&commit, 8) != self.sequence || get_u32(&commit, 16) as usize != count {
+            // 커밋 전에 중단된 트랜잭션: 원래 위치는 건드리지 않았으므로 버림
+            crate::log_warn!("Discarding uncommitted journal transaction {}", self.sequence);
+            return Ok(Vec::new());
         }
-        
-        // 커밋 상태 확인
-        if header[16] != 1 {
-            // 커밋되지 않은 저널 (부분적으로만 쓰여진 상태)
-            return Err("Journal not committed");
-        }
-        
-        // 엔트리 수 읽기
-        let entry_count = u32::from_le_bytes([
-            header[12], header[13], header[14], header[15]
-        ]) as usize;
-        
-        let mut entries = Vec::new();
-        let mut block_offset = 1;
-        
-        // 엔트리 읽기
-        for _ in 0..entry_count {
-            let mut entry_header = [0u8; 512];
-            device.read_block(start_block + block_offset, &mut entry_header)
-                .map_err(|_| "Failed to read journal entry header")?;
-            block_offset += 1;
-            
-            // 블록 번호
-            let block_num = u64::from_le_bytes([
-                entry_header[0], entry_header[1], entry_header[2], entry_header[3],
-                entry_header[4], entry_header[5], entry_header[6], entry_header[7],
-            ]);
-            
-            // 타입
-            let entry_type = match entry_header[8] {
-                0 => JournalEntryType::DataWrite,
-                1 => JournalEntryType::MetadataWrite,
-                _ => return Err("Invalid entry type"),
-            };
-            
-            // 시퀀스
-            let sequence = u64::from_le_bytes([
-                entry_header[9], entry_header[10], entry_header[11], entry_header[12],
-                entry_header[13], entry_header[14], entry_header[15], entry_header[16],
-            ]);
-            
-            // 데이터 읽기
+
+        let mut entries = Vec::with_capacity(count);
+        for i in 0..count {
+            let off = DESCRIPTOR_HEADER + i * DESCRIPTOR_ENTRY;
+            let block_num = get_u64(&descriptor, off);
+            let entry_type = JournalEntryType::from_byte(descriptor[off + 8]).ok_or("Invalid entry type")?;
             let mut data = [0u8; 512];
-            device.read_block(start_block + block_offset, &mut data)
+            device.read_block(self.start + 2 + i as u64, &mut data)
                 .map_err(|_| "Failed to read journal entry data")?;
-            block_offset += 1;
-            
-            entries.push(JournalEntry {
-                block_num,
-                data,
-                entry_type,
-                sequence,
-            });
-        }
-        
-        // 커밋 마커 확인
-        let mut commit_marker = [0u8; 512];
-        device.read_block(start_block + block_offset, &mut commit_marker)
-            .map_err(|_| "Failed to read commit marker")?;
-        
-        if &commit_marker[0..4] != b"COMM" {
-            return Err("Journal commit marker not found");
-        }
-        
+            entries.push(JournalEntry { block_num, data, entry_type, sequence: self.sequence });
+        }
+
+        let refs: Vec<&JournalEntry> = entries.iter().collect();
+        let in_journal = |e: &JournalEntry| e.block_num >= self.start && e.block_num < self.start + self.blocks;
+        if tx_checksum(&descriptor, &refs) != get_u32(&commit, 20)
+            || entries.iter().any(|e| in_journal(e) || e.block_num >= device.num_blocks())
+        {
+            crate::log_warn!("Discarding corrupted journal transaction {}", self.sequence);
+            return Ok(Vec::new());
+        }
+
         Ok(entries)
     }
-    
+
+    /// 남은 트랜잭션을 원래 위치에 재생
+    ///
+    /// # Returns
+    /// 재생한 트랜잭션 순번 (없으면 `None`)
+    pub fn replay(&mut self, device: &mut dyn BlockDevice) -> Result<Option<u64>, &'static str> {
+        if self.state != JournalState::Idle {
+            return Err("Journal busy");
+        }
+        let entries = self.recover(device)?;
+        if entries.is_empty() {
+            return Ok(None);
+        }
+
+        let sequence = self.sequence;
+        self.entries = entries.into_iter().map(|e| (e.block_num, e)).collect();
+        self.state = JournalState::Checkpointing;
+        self.checkpoint(device)?;
+        Ok(Some(sequence))
+    }
+
     /// 현재 상태
     pub fn state(&self) -> JournalState {
         self.state
     }
 }
 
-/// 전역 저널 인스턴스
-static GLOBAL_JOURNAL: Mutex<Journal> = Mutex::new(Journal {
-    entries: BTreeMap::new(),
-    state: JournalState::Idle,
-    sequence: 0,
-    max_entries: 64,
-});
-
-/// 저널 초기화
-pub fn init() {
-    crate::log_info!("Journal initialized");
+/// 저널 영역이 비어 있는지 확인 (새 저널을 만들어도 되는지 판단)
+pub fn area_is_empty(device: &mut dyn BlockDevice, start: u64, blocks: u64) -> Result<bool, &'static str> {
+    let mut buf = vec![0u8; JOURNAL_BLOCK_SIZE];
+    for block in start..start + blocks {
+        device.read_block(block, &mut buf).map_err(|_| "Failed to read journal area")?;
+        if buf.iter().any(|&b| b != 0) {
+            return Ok(false);
+        }
+    }
+    Ok(true)
 }
 
-/// 트랜잭션 시작
-pub fn begin_transaction() {
-    GLOBAL_JOURNAL.lock().begin_transaction();
-}
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::drivers::ata::BlockDeviceError;
+    use alloc::sync::Arc;
+    use spin::Mutex;
 
-/// 트랜잭션이 진행 중인지 확인
-pub fn in_transaction() -> bool {
-    GLOBAL_JOURNAL.lock().in_transaction()
-}
+    /// 메모리 블록 디바이스 (512바이트 섹터, 쓰지 않은 섹터는 0)
+    ///
+    /// 복제본은 같은 내용을 공유하므로 파일시스템에 넘긴 뒤에도 내용을 확인하거나
+    /// 새 인스턴스로 다시 열 수 있습니다 (재부팅 흉내).
+    #[derive(Clone)]
+    pub(crate) struct MemDevice {
+        sectors: Arc<Mutex<BTreeMap<u64, [u8; 512]>>>,
+        num_blocks: u64,
+    }
 
-/// 저널 엔트리 추가
-pub fn add_entry(block_num: u64, data: &[u8], entry_type: JournalEntryType) -> Result<(), &'static str> {
-    GLOBAL_JOURNAL.lock().add_entry(block_num, data, entry_type)
-}
+    impl MemDevice {
+        pub(crate) fn new(num_blocks: u64) -> Self {
+            Self { sectors: Arc::new(Mutex::new(BTreeMap::new())), num_blocks }
+        }
 
-/// 트랜잭션 커밋
-/// 
-/// # Arguments
-/// * `device` - 블록 디바이스 (저널 영역에 쓰기용)
-/// * `journal_start_block` - 저널 영역 시작 블록
-pub fn commit(_device: Option<&mut dyn BlockDevice>, _journal_start_block: Option<u64>) -> Result<(), &'static str> {
-    GLOBAL_JOURNAL.lock().commit()
-}
+        /// 섹터 내용
+        pub(crate) fn sector(&self, block: u64) -> [u8; 512] {
+            self.sectors.lock().get(&block).copied().unwrap_or([0; 512])
+        }
 
-/// 체크포인트
-/// 
-/// # Arguments
-/// * `device` - 블록 디바이스 (파일시스템에 쓰기용)
-pub fn checkpoint(_device: Option<&mut dyn BlockDevice>) -> Result<(), &'static str> {
-    GLOBAL_JOURNAL.lock().checkpoint()
-}
+        /// 섹터의 한 바이트를 뒤집음 (일부만 기록된 쓰기 흉내)
+        pub(crate) fn corrupt(&self, block: u64, offset: usize) {
+            self.sectors.lock().entry(block).or_insert([0; 512])[offset] ^= 0xFF;
+        }
+    }
 
-/// 트랜잭션 롤백
-pub fn rollback() {
-    GLOBAL_JOURNAL.lock().rollback();
-}
+    impl BlockDevice for MemDevice {
+        fn block_size(&self) -> usize {
+            512
+        }
 
-/// 저널 복구
-/// 
-/// # Arguments
-/// * `device` - 블록 디바이스 (저널 읽기용)
-/// * `journal_start_block` - 저널 영역 시작 블록
-pub fn recover(device: Option<&mut dyn BlockDevice>, journal_start_block: Option<u64>) -> Result<Vec<JournalEntry>, &'static str> {
-    GLOBAL_JOURNAL.lock().recover(device, journal_start_block)
-}
+        fn read_block(&mut self, block: u64, buf: &mut [u8]) -> Result<usize, BlockDeviceError> {
+            if block >= self.num_blocks {
+                return Err(BlockDeviceError::InvalidBlock);
+            }
+            buf[..512].copy_from_slice(&self.sector(block));
+            Ok(512)
+        }
+
+        fn write_block(&mut self, block: u64, buf: &[u8]) -> Result<usize, BlockDeviceError> {
+            if block >= self.num_blocks {
+                return Err(BlockDeviceError::InvalidBlock);
+            }
+            let mut data = [0u8; 512];
+            data.copy_from_slice(&buf[..512]);
+            self.sectors.lock().insert(block, data);
+            Ok(512)
+        }
+
+        fn num_blocks(&self) -> u64 {
+            self.num_blocks
+        }
+    }
 
+    #[test_case]
+    fn test_journal_replay_after_crash() {
+        let mut device = MemDevice::new(64);
+        assert!(area_is_empty(&mut device, 8, 8).unwrap());
+        let mut journal = Journal::format(&mut device, 8, 8).unwrap();
+        assert_eq!(journal.capacity(), 5);
+
+        // 커밋 후 체크포인트 전에 중단
+        journal.begin_transaction();
+        journal.add_entry(40, &[0xAA; 512], JournalEntryType::MetadataWrite).unwrap();
+        journal.add_entry(41, &[0xBB; 512], JournalEntryType::MetadataWrite).unwrap();
+        assert_eq!(journal.add_entry(9, &[0; 512], JournalEntryType::DataWrite), Err("Block inside journal area"));
+        journal.commit(&mut device).unwrap();
+        assert_eq!(device.sector(40), [0u8; 512]);
+
+        let mut reopened = Journal::open(&mut device, 8, 8).unwrap();
+        assert_eq!(reopened.recover(&mut device).unwrap().len(), 2);
+        assert_eq!(reopened.replay(&mut device).unwrap(), Some(1));
+        assert_eq!(device.sector(40), [0xAA; 512]);
+        assert_eq!(device.sector(41), [0xBB; 512]);
+        // 재생한 트랜잭션은 다시 재생하지 않음
+        let mut reopened = Journal::open(&mut device, 8, 8).unwrap();
+        assert_eq!(reopened.replay(&mut device).unwrap(), None);
+    }
+
+    #[test_case]
+    fn test_journal_discards_torn_transaction() {
+        let mut device = MemDevice::new(64);
+        let mut journal = Journal::format(&mut device, 8, 8).unwrap();
+        journal.begin_transaction();
+        journal.add_entry(40, &[0xAA; 512], JournalEntryType::MetadataWrite).unwrap();
+        journal.commit(&mut device).unwrap();
+
+        // 사본 섹터가 일부만 기록됨
+        device.corrupt(10, 100);
+        let mut reopened = Journal::open(&mut device, 8, 8).unwrap();
+        assert!(reopened.recover(&mut device).unwrap().is_empty());
+        assert_eq!(reopened.replay(&mut device).unwrap(), None);
+        assert_eq!(device.sector(40), [0u8; 512]);
+
+        // 헤더가 손상되면 열지 않음
+        device.corrupt(8, 20);
+        assert!(Journal::open(&mut device, 8, 8).is_err());
+    }
+}
@@ -9,63 +9,61 @@ pub mod cache;
 pub mod journal;
 pub mod fsck;
 pub mod simple_journal_fs;
+pub mod ext2;
+pub mod mount;
+pub mod tmpfs;
+pub mod partition;
+pub mod devfs;
+pub mod procfs;
 
-use crate::fs::vfs::{FileSystem, FsResult};
+pub use mount::{FileSystemManager, MountFlags, MountInfo};
+
+use crate::fs::vfs::{FileSystem, FsError, FsResult};
 use crate::fs::fat32::Fat32FileSystem;
+use crate::fs::simple_journal_fs::Sjfs;
+use crate::fs::ext2::Ext2;
 use crate::drivers::ata::BlockDevice;
 use alloc::boxed::Box;
 use spin::Mutex;
 
-/// 파일시스템 매니저
-/// 
-/// 전역 파일시스템 인스턴스를 관리합니다.
-pub struct FileSystemManager {
-    root_fs: Option<*mut (dyn FileSystem)>,
+/// 주 디스크(ATA Primary Master)의 디바이스 경로
+pub const PRIMARY_DISK: &str = "/dev/ata0";
+
+/// 디바이스 경로로 블록 디바이스 열기 (devfs에 등록된 디스크와 파티션)
+///
+/// # Arguments
+/// * `source` - 디바이스 경로 (`/dev/ata0p1` 또는 `ata0p1`, 예전 이름 `hda`도 허용)
+pub fn open_block_device(source: &str) -> FsResult<Box<dyn BlockDevice>> {
+    devfs::open_block(device_node_name(source))
 }
 
-impl FileSystemManager {
-    /// 새 파일시스템 매니저 생성
-    pub fn new() -> Self {
-        Self {
-            root_fs: None,
-        }
+/// 디바이스 경로의 devfs 노드 이름 (`/dev/hda` → `ata0`, `/dev/ata0p1` → `ata0p1`)
+pub fn device_node_name(source: &str) -> &str {
+    match source.trim_start_matches("/dev/") {
+        "hda" => "ata0",
+        name => name,
     }
-    
-    /// 루트 파일시스템 마운트
-    /// 
-    /// # Arguments
-    /// * `device` - 블록 디바이스
-    /// 
-    /// # Returns
-    /// 성공 시 Ok(()), 실패 시 오류
-    pub fn mount_root(&mut self, device: Box<dyn BlockDevice>) -> FsResult<()> {
-        // FAT32 파일시스템 생성
-        let mut fs = Fat32FileSystem::new(device)?;
-        
-        // 마운트
-        fs.mount()?;
+}
 
-        // 커널 수명으로 누수(leak)하여 전역으로 보관
-        let leaked: &'static mut (dyn FileSystem) = Box::leak(Box::new(fs));
-        self.root_fs = Some(leaked as *mut dyn FileSystem);
-        Ok(())
-    }
-    
-    /// 루트 파일시스템 가져오기
-    pub fn get_root(&mut self) -> Option<&'static mut dyn FileSystem> {
-        self.root_fs.map(|p| unsafe { &mut *p })
-    }
-    
-    /// 루트 파일시스템이 마운트되어 있는지 확인
-    pub fn is_mounted(&self) -> bool {
-        match self.root_fs {
-            Some(p) => unsafe { (&*p).is_mounted() },
-            None => false,
-        }
+/// 블록 디바이스 위의 파일시스템 열기 (마운트 전)
+///
+/// # Arguments
+/// * `fs_type` - "sjfs", "ext2", "fat32" 또는 "auto" (Sjfs, ext2 슈퍼블록 순서로 확인하고 없으면 FAT32)
+/// * `device` - 블록 디바이스
+pub fn open_filesystem(fs_type: &str, mut device: Box<dyn BlockDevice>) -> FsResult<Box<dyn FileSystem>> {
+    let fs_type = match fs_type {
+        "auto" if Sjfs::probe(&mut *device) => "sjfs",
+        "auto" if Ext2::probe(&mut *device) => "ext2",
+        "auto" => "fat32",
+        other => other,
+    };
+    match fs_type {
+        "sjfs" => Ok(Box::new(Sjfs::new(device)?)),
+        "ext2" => Ok(Box::new(Ext2::new(device)?)),
+        "fat32" | "vfat" => Ok(Box::new(Fat32FileSystem::new(device)?)),
+        _ => Err(FsError::InvalidFilesystem),
     }
 }
 
-// 전역 파일시스템 매니저
-// TODO: ATA 드라이버 구현 후 활성화
-// pub static FS_MANAGER: Mutex<FileSystemManager> = Mutex::new(FileSystemManager::new());
-
+/// 전역 파일시스템 매니저 (마운트 테이블)
+pub static FS_MANAGER: Mutex<FileSystemManager> = Mutex::new(FileSystemManager::new());
@@ -0,0 +1,590 @@
+//! 마운트 테이블과 경로 해석
+//!
+//! 파일시스템을 아무 디렉토리에나 마운트하고, 절대 경로를 가장 길게 일치하는 마운트
+//! 지점으로 해석해 해당 파일시스템 안의 경로로 바꿉니다. 같은 파일시스템의 하위
+//! 디렉토리를 다른 곳에 보여주는 바인드 마운트와 읽기 전용 마운트를 지원합니다.
+//!
+//! 파일시스템은 `Arc<Mutex<..>>`로 공유됩니다. 마운트 항목, 바인드 마운트, 열린 파일과
+//! 디렉토리 핸들이 각각 참조를 가지므로 사용 중인 파일시스템은 언마운트할 수 없고,
+//! 핸들의 모든 연산은 파일시스템 잠금 안에서 실행됩니다.
+//!
+//! 잠금 순서는 항상 마운트 테이블(`FS_MANAGER`) → 파일시스템입니다.
+
+use super::path::Path;
+use super::devfs::DevFs;
+use super::procfs::ProcFs;
+use super::tmpfs::{TmpFs, TmpfsOptions};
+use super::vfs::{Directory, File, FileMetadata, FileSystem, FileType, FsError, FsResult, FsStats, Offset};
+use super::FS_MANAGER;
+use crate::drivers::ata::BlockDevice;
+use alloc::boxed::Box;
+use alloc::string::{String, ToString};
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use spin::Mutex;
+
+/// 마운트된 파일시스템 (마운트 항목과 핸들이 공유)
+type SharedFs = Arc<Mutex<Box<dyn FileSystem>>>;
+
+/// 마운트 옵션
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct MountFlags {
+    /// 쓰기 연산을 `FsError::ReadOnly`로 거부
+    pub read_only: bool,
+}
+
+/// 마운트 테이블 항목
+struct Mount {
+    /// 마운트 지점 (정규화된 절대 경로)
+    target: String,
+    /// 원본 (디바이스 경로, 파일시스템 이름, 바인드 원본 경로)
+    source: String,
+    /// 파일시스템 안에서 이 마운트가 보여주는 디렉토리 (바인드 마운트가 아니면 "/")
+    root: String,
+    flags: MountFlags,
+    bind: bool,
+    fs: SharedFs,
+}
+
+/// 마운트 정보 (mount/df 출력용)
+#[derive(Debug, Clone)]
+pub struct MountInfo {
+    pub target: String,
+    pub source: String,
+    pub fs_type: &'static str,
+    pub read_only: bool,
+    pub bind: bool,
+}
+
+/// 경로 해석 결과
+struct Resolved {
+    fs: SharedFs,
+    /// 파일시스템 안의 경로
+    path: String,
+    read_only: bool,
+    /// 경로가 마운트 지점 자체인지
+    is_mount_point: bool,
+}
+
+/// 절대 경로 정규화 (`.`과 `..` 처리, 중복 `/` 제거)
+fn normalize(path: &str) -> FsResult<String> {
+    let parsed = Path::parse(path).map_err(|_| FsError::InvalidPath)?;
+    if !parsed.absolute {
+        return Err(FsError::InvalidPath);
+    }
+    Ok(parsed.normalize().to_string())
+}
+
+/// `name`이 `disk`의 파티션 노드(`<디스크>p<N>`)인지 확인
+fn is_partition_of(name: &str, disk: &str) -> bool {
+    name.strip_prefix(disk)
+        .and_then(|rest| rest.strip_prefix('p'))
+        .is_some_and(|number| !number.is_empty() && number.bytes().all(|b| b.is_ascii_digit()))
+}
+
+/// `path`가 마운트 지점 `target` 아래에 있으면 마운트 안의 나머지 경로 반환
+fn strip_mount_point<'a>(target: &str, path: &'a str) -> Option<&'a str> {
+    if target == "/" {
+        return Some(path);
+    }
+    let rest = path.strip_prefix(target)?;
+    if rest.is_empty() {
+        Some("/")
+    } else if rest.starts_with('/') {
+        Some(rest)
+    } else {
+        None
+    }
+}
+
+/// 파일시스템 안의 디렉토리 `root` 아래 경로 `rest` 결합
+fn join_root(root: &str, rest: &str) -> String {
+    match (root, rest) {
+        ("/", _) => rest.to_string(),
+        (_, "/") => root.to_string(),
+        _ => alloc::format!("{}{}", root, rest),
+    }
+}
+
+/// 파일시스템 매니저 (마운트 테이블)
+pub struct FileSystemManager {
+    mounts: Vec<Mount>,
+}
+
+impl FileSystemManager {
+    /// 빈 마운트 테이블 생성
+    pub const fn new() -> Self {
+        Self { mounts: Vec::new() }
+    }
+
+    /// 경로를 덮는 마운트 중 마운트 지점이 가장 긴 것 (같은 지점이면 나중 것)
+    fn find_mount(&self, path: &str) -> Option<(&Mount, String)> {
+        // `max_by_key`는 같은 값 중 마지막 항목을 고르므로 마운트 순서대로 순회
+        self.mounts
+            .iter()
+            .filter_map(|mount| strip_mount_point(&mount.target, path).map(|rest| (mount, rest)))
+            .max_by_key(|(mount, _)| mount.target.len())
+            .map(|(mount, rest)| (mount, join_root(&mount.root, rest)))
+    }
+
+    /// 절대 경로를 파일시스템과 그 안의 경로로 해석
+    fn resolve(&self, path: &str) -> FsResult<Resolved> {
+        let path = normalize(path)?;
+        let (mount, inner) = self.find_mount(&path).ok_or(FsError::NotFound)?;
+        Ok(Resolved {
+            fs: mount.fs.clone(),
+            path: inner,
+            read_only: mount.flags.read_only,
+            is_mount_point: mount.target == path,
+        })
+    }
+
+    /// 파일시스템을 `target`에 마운트
+    ///
+    /// 마운트 지점은 아직 없어도 되며, 있으면 디렉토리여야 합니다. 부모 디렉토리 목록에는
+    /// 마운트 지점이 항상 나타납니다. 이미 마운트된 지점에 다시 마운트하면 새 마운트가
+    /// 기존 마운트를 가리고, 언마운트하면 다시 드러납니다.
+    ///
+    /// 같은 블록 디바이스(`/dev/...`)를 두 파일시스템 인스턴스로 마운트하면 서로의
+    /// 캐시와 할당 상태를 모르고 덮어쓰므로 `Busy`로 거부합니다. 같은 내용을 다른 곳에
+    /// 보이려면 바인드 마운트를 사용합니다.
+    ///
+    /// # Arguments
+    /// * `target` - 마운트 지점 (절대 경로)
+    /// * `source` - 원본 이름 (예: "/dev/ata0", "tmpfs")
+    /// * `fs` - 마운트할 파일시스템 (아직 마운트되지 않았으면 여기서 `mount()` 호출)
+    /// * `flags` - 마운트 옵션
+    pub fn mount(&mut self, target: &str, source: &str, mut fs: Box<dyn FileSystem>, flags: MountFlags) -> FsResult<()> {
+        let target = normalize(target)?;
+        if source.starts_with("/dev/") && self.is_source_mounted(source) {
+            return Err(FsError::Busy);
+        }
+        self.check_mount_point(&target)?;
+        if !fs.is_mounted() {
+            fs.mount()?;
+        }
+        crate::log_info!("vfs: mounted {} ({}) on {}{}", source, fs.fs_type(), target, if flags.read_only { " (ro)" } else { "" });
+        self.mounts.push(Mount {
+            target,
+            source: source.to_string(),
+            root: String::from("/"),
+            flags,
+            bind: false,
+            fs: Arc::new(Mutex::new(fs)),
+        });
+        Ok(())
+    }
+
+    /// 루트 파일시스템 마운트
+    /// 
+    /// Sjfs 슈퍼블록이 있으면 Sjfs로, 없으면 FAT32로 마운트합니다.
+    /// 
+    /// # Arguments
+    /// * `source` - 디바이스 경로
+    /// * `device` - 블록 디바이스
+    pub fn mount_root(&mut self, source: &str, device: Box<dyn BlockDevice>) -> FsResult<()> {
+        let fs = super::open_filesystem("auto", device)?;
+        self.mount("/", source, fs, MountFlags::default())
+    }
+
+    /// 새 tmpfs를 `target`에 마운트
+    ///
+    /// # Arguments
+    /// * `target` - 마운트 지점 (절대 경로)
+    /// * `options` - 크기와 inode 한도
+    /// * `flags` - 마운트 옵션
+    pub fn mount_tmpfs(&mut self, target: &str, options: TmpfsOptions, flags: MountFlags) -> FsResult<()> {
+        self.mount(target, "tmpfs", Box::new(TmpFs::new(options)), flags)
+    }
+
+    /// devfs를 `target`에 마운트
+    pub fn mount_devfs(&mut self, target: &str, flags: MountFlags) -> FsResult<()> {
+        self.mount(target, "devfs", Box::new(DevFs::new()), flags)
+    }
+
+    /// procfs를 `target`에 마운트
+    pub fn mount_procfs(&mut self, target: &str, flags: MountFlags) -> FsResult<()> {
+        self.mount(target, "procfs", Box::new(ProcFs::new()), flags)
+    }
+
+    /// 이미 마운트된 디렉토리 `source`를 `target`에도 보이게 함 (바인드 마운트)
+    pub fn bind(&mut self, source: &str, target: &str, flags: MountFlags) -> FsResult<()> {
+        let source = normalize(source)?;
+        let target = normalize(target)?;
+        let resolved = self.resolve(&source)?;
+        if resolved.fs.lock().metadata(&resolved.path)?.file_type != FileType::Directory {
+            return Err(FsError::NotADirectory);
+        }
+        self.check_mount_point(&target)?;
+        crate::log_info!("vfs: bind mounted {} on {}", source, target);
+        self.mounts.push(Mount {
+            target,
+            source,
+            root: resolved.path,
+            // 원본이 읽기 전용이면 바인드도 읽기 전용
+            flags: MountFlags { read_only: flags.read_only || resolved.read_only },
+            bind: true,
+            fs: resolved.fs,
+        });
+        Ok(())
+    }
+
+    /// 새 마운트 지점 검사 (기존 항목은 디렉토리여야 함, 마운트 지점 위에 쌓는 것은 허용)
+    fn check_mount_point(&self, target: &str) -> FsResult<()> {
+        if let Ok(resolved) = self.resolve(target) {
+            match resolved.fs.lock().metadata(&resolved.path) {
+                Ok(metadata) if metadata.file_type != FileType::Directory => return Err(FsError::NotADirectory),
+                Ok(_) | Err(FsError::NotFound) => {}
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(())
+    }
+
+    /// `target`의 마운트 해제
+    ///
+    /// 아래에 다른 마운트가 있으면 `Busy`입니다. 파일시스템을 참조하는 마지막 마운트면
+    /// 열린 핸들이 없는지 확인한 뒤 파일시스템의 `unmount()`도 호출합니다.
+    pub fn umount(&mut self, target: &str) -> FsResult<()> {
+        let target = normalize(target)?;
+        let index = self.mounts.iter().rposition(|mount| mount.target == target).ok_or(FsError::NotFound)?;
+        let nested = self.mounts.iter().enumerate().any(|(i, mount)| {
+            i != index && mount.target != target && strip_mount_point(&target, &mount.target).is_some()
+        });
+        if nested {
+            return Err(FsError::Busy);
+        }
+
+        let fs = &self.mounts[index].fs;
+        let sharing = self.mounts.iter().filter(|mount| Arc::ptr_eq(&mount.fs, fs)).count();
+        if sharing == 1 {
+            // 마지막 마운트일 때만 열린 핸들을 검사 (다른 마운트가 남아 있으면 핸들은 계속 유효)
+            if Arc::strong_count(fs) > 1 {
+                return Err(FsError::Busy);
+            }
+            fs.lock().unmount()?;
+        }
+        let mount = self.mounts.remove(index);
+        crate::log_info!("vfs: unmounted {} from {}", mount.source, mount.target);
+        Ok(())
+    }
+
+    /// 마운트 목록 (마운트한 순서)
+    pub fn mounts(&self) -> Vec<MountInfo> {
+        self.mounts
+            .iter()
+            .map(|mount| MountInfo {
+                target: mount.target.clone(),
+                source: mount.source.clone(),
+                fs_type: mount.fs.lock().fs_type(),
+                read_only: mount.flags.read_only,
+                bind: mount.bind,
+            })
+            .collect()
+    }
+
+    /// `source`가 마운트되어 있는지 확인 (바인드 마운트 제외)
+    ///
+    /// 디바이스 경로는 devfs 노드 이름으로 비교합니다 (`/dev/hda`와 `/dev/ata0`는 같음).
+    pub fn is_source_mounted(&self, source: &str) -> bool {
+        let name = super::device_node_name(source);
+        self.mounts.iter().any(|mount| !mount.bind && super::device_node_name(&mount.source) == name)
+    }
+
+    /// 디스크가 사용 중인지 확인
+    ///
+    /// 디스크 자체, 그 파티션(`<디스크>p<N>`), 또는 파티션이면 그 파티션을 담은 디스크가
+    /// 마운트되어 있으면 사용 중입니다.
+    pub fn is_disk_in_use(&self, disk: &str) -> bool {
+        let disk = super::device_node_name(disk);
+        self.mounts.iter().filter(|mount| !mount.bind).any(|mount| {
+            let name = super::device_node_name(&mount.source);
+            name == disk || is_partition_of(name, disk) || is_partition_of(disk, name)
+        })
+    }
+
+    /// 루트 파일시스템이 마운트되어 있는지 확인
+    pub fn is_mounted(&self) -> bool {
+        self.mounts.iter().any(|mount| mount.target == "/")
+    }
+
+    /// 디렉토리 `dir` 바로 아래의 마운트 지점 이름
+    fn child_mount_points(&self, dir: &str) -> Vec<String> {
+        self.mounts
+            .iter()
+            .filter_map(|mount| {
+                let rest = strip_mount_point(dir, &mount.target)?;
+                let name = rest.strip_prefix('/')?;
+                (!name.is_empty() && !name.contains('/')).then(|| name.to_string())
+            })
+            .collect()
+    }
+}
+
+/// 전역 마운트 테이블에서 경로 해석 (테이블 잠금은 바로 해제)
+fn resolve(path: &str) -> FsResult<Resolved> {
+    FS_MANAGER.lock().resolve(path)
+}
+
+/// 쓰기 연산용 경로 해석 (읽기 전용 마운트와 마운트 지점 자체는 거부)
+fn resolve_writable(path: &str) -> FsResult<Resolved> {
+    let resolved = resolve(path)?;
+    if resolved.read_only {
+        return Err(FsError::ReadOnly);
+    }
+    if resolved.is_mount_point {
+        return Err(FsError::Busy);
+    }
+    Ok(resolved)
+}
+
+/// 파일 열기
+pub fn open_file(path: &str) -> FsResult<Box<dyn File>> {
+    let resolved = resolve(path)?;
+    let inner = resolved.fs.lock().open_file(&resolved.path)?;
+    Ok(Box::new(MountedFile { fs: resolved.fs, inner, read_only: resolved.read_only }))
+}
+
+/// 파일 생성 및 열기
+pub fn create_file(path: &str) -> FsResult<Box<dyn File>> {
+    let resolved = resolve_writable(path)?;
+    let inner = resolved.fs.lock().create_file(&resolved.path)?;
+    Ok(Box::new(MountedFile { fs: resolved.fs, inner, read_only: false }))
+}
+
+/// 디렉토리 열기
+pub fn open_dir(path: &str) -> FsResult<Box<dyn Directory>> {
+    let path = normalize(path)?;
+    let resolved = resolve(&path)?;
+    let inner = resolved.fs.lock().open_dir(&resolved.path)?;
+    Ok(Box::new(MountedDirectory { fs: resolved.fs, inner, path, read_only: resolved.read_only }))
+}
+
+/// 디렉토리 생성
+pub fn create_dir(path: &str) -> FsResult<()> {
+    let resolved = resolve_writable(path)?;
+    let result = resolved.fs.lock().create_dir(&resolved.path);
+    result
+}
+
+/// 파일/디렉토리 삭제
+pub fn remove(path: &str) -> FsResult<()> {
+    let resolved = resolve_writable(path)?;
+    let result = resolved.fs.lock().remove(&resolved.path);
+    result
+}
+
+/// 파일/디렉토리 이름 변경 (같은 마운트 안에서만)
+pub fn rename(old_path: &str, new_path: &str) -> FsResult<()> {
+    let old = resolve_writable(old_path)?;
+    let new = resolve_writable(new_path)?;
+    if !Arc::ptr_eq(&old.fs, &new.fs) {
+        return Err(FsError::InvalidPath);
+    }
+    let result = old.fs.lock().rename(&old.path, &new.path);
+    result
+}
+
+/// 파일/디렉토리 메타데이터
+pub fn metadata(path: &str) -> FsResult<FileMetadata> {
+    let resolved = resolve(path)?;
+    let result = resolved.fs.lock().metadata(&resolved.path);
+    result
+}
+
+/// 심볼릭 링크 생성 (`target`은 해석하지 않고 그대로 저장)
+pub fn symlink(target: &str, path: &str) -> FsResult<()> {
+    let resolved = resolve_writable(path)?;
+    let result = resolved.fs.lock().symlink(target, &resolved.path);
+    result
+}
+
+/// 심볼릭 링크의 대상 경로
+pub fn read_link(path: &str) -> FsResult<String> {
+    let resolved = resolve(path)?;
+    let result = resolved.fs.lock().read_link(&resolved.path);
+    result
+}
+
+/// 경로가 속한 파일시스템의 용량 정보
+pub fn statfs(path: &str) -> FsResult<FsStats> {
+    let resolved = resolve(path)?;
+    let result = resolved.fs.lock().statfs();
+    result
+}
+
+/// 마운트 테이블을 거쳐 연 파일 (파일시스템 참조 유지)
+struct MountedFile {
+    fs: SharedFs,
+    inner: Box<dyn File>,
+    read_only: bool,
+}
+
+impl File for MountedFile {
+    fn read(&mut self, buf: &mut [u8], offset: Option<Offset>) -> FsResult<usize> {
+        let _fs = self.fs.lock();
+        self.inner.read(buf, offset)
+    }
+
+    fn write(&mut self, buf: &[u8], offset: Option<Offset>) -> FsResult<usize> {
+        if self.read_only {
+            return Err(FsError::ReadOnly);
+        }
+        // 마운트된 파일시스템은 자기 캐시를 믿으므로 밑에서 바꾸면 안 됨.
+        // 잠금 순서(마운트 테이블 → 파일시스템)를 지키도록 파일시스템 락 전에 확인
+        if let Some(disk) = self.inner.block_device() {
+            if FS_MANAGER.lock().is_disk_in_use(disk) {
+                return Err(FsError::Busy);
+            }
+        }
+        let _fs = self.fs.lock();
+        self.inner.write(buf, offset)
+    }
+
+    fn metadata(&self) -> FsResult<FileMetadata> {
+        let _fs = self.fs.lock();
+        self.inner.metadata()
+    }
+
+    fn size(&self) -> FsResult<u64> {
+        let _fs = self.fs.lock();
+        self.inner.size()
+    }
+
+    fn seek(&mut self, offset: Offset) -> FsResult<Offset> {
+        let _fs = self.fs.lock();
+        self.inner.seek(offset)
+    }
+
+    fn tell(&self) -> FsResult<Offset> {
+        self.inner.tell()
+    }
+}
+
+/// 마운트 테이블을 거쳐 연 디렉토리 (파일시스템 참조 유지)
+struct MountedDirectory {
+    fs: SharedFs,
+    inner: Box<dyn Directory>,
+    /// 정규화된 절대 경로 (하위 마운트 지점 표시용)
+    path: String,
+    read_only: bool,
+}
+
+impl MountedDirectory {
+    fn check_writable(&self) -> FsResult<()> {
+        if self.read_only {
+            return Err(FsError::ReadOnly);
+        }
+        Ok(())
+    }
+}
+
+impl Directory for MountedDirectory {
+    fn read_dir(&self) -> FsResult<Vec<(String, FileType)>> {
+        let mut entries = {
+            let _fs = self.fs.lock();
+            self.inner.read_dir()?
+        };
+        // 마운트 지점은 아래 파일시스템에 디렉토리가 없어도 보이도록
+        for name in FS_MANAGER.lock().child_mount_points(&self.path) {
+            if !entries.iter().any(|(existing, _)| *existing == name) {
+                entries.push((name, FileType::Directory));
+            }
+        }
+        Ok(entries)
+    }
+
+    fn create_file(&mut self, name: &str) -> FsResult<()> {
+        self.check_writable()?;
+        let _fs = self.fs.lock();
+        self.inner.create_file(name)
+    }
+
+    fn create_dir(&mut self, name: &str) -> FsResult<()> {
+        self.check_writable()?;
+        let _fs = self.fs.lock();
+        self.inner.create_dir(name)
+    }
+
+    fn remove(&mut self, name: &str) -> FsResult<()> {
+        self.check_writable()?;
+        let _fs = self.fs.lock();
+        self.inner.remove(name)
+    }
+
+    fn rename(&mut self, old_name: &str, new_name: &str) -> FsResult<()> {
+        self.check_writable()?;
+        let _fs = self.fs.lock();
+        self.inner.rename(old_name, new_name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test_case]
+    fn test_mount_point_prefix() {
+        assert_eq!(strip_mount_point("/", "/a/b"), Some("/a/b"));
+        assert_eq!(strip_mount_point("/mnt", "/mnt"), Some("/"));
+        assert_eq!(strip_mount_point("/mnt", "/mnt/x/y"), Some("/x/y"));
+        // 구성요소 경계에서만 일치
+        assert_eq!(strip_mount_point("/mnt", "/mnt2/x"), None);
+        assert_eq!(strip_mount_point("/mnt", "/"), None);
+    }
+
+    #[test_case]
+    fn test_mount_join_root() {
+        assert_eq!(join_root("/", "/a"), "/a");
+        assert_eq!(join_root("/data/shared", "/"), "/data/shared");
+        assert_eq!(join_root("/data/shared", "/x/y"), "/data/shared/x/y");
+        assert_eq!(normalize("/a/./b/../c//d").as_deref(), Ok("/a/c/d"));
+        assert_eq!(normalize("relative"), Err(FsError::InvalidPath));
+    }
+
+    fn tmpfs() -> Box<dyn FileSystem> {
+        Box::new(TmpFs::new(TmpfsOptions::default()))
+    }
+
+    #[test_case]
+    fn test_mount_stacked_on_same_point() {
+        let mut table = FileSystemManager::new();
+        table.mount("/", "tmpfs", tmpfs(), MountFlags::default()).unwrap();
+        table.mount("/mnt", "lower", tmpfs(), MountFlags::default()).unwrap();
+        let lower = table.resolve("/mnt").unwrap();
+        drop(lower.fs.lock().create_file("/only-in-lower").unwrap());
+        drop(lower);
+
+        // 나중 마운트가 같은 지점의 이전 마운트를 가림
+        table.mount("/mnt", "upper", tmpfs(), MountFlags::default()).unwrap();
+        let top = table.resolve("/mnt/only-in-lower").unwrap();
+        assert_eq!(top.fs.lock().metadata(&top.path).err(), Some(FsError::NotFound));
+        drop(top);
+        assert_eq!(table.mounts().last().map(|m| m.source.clone()).as_deref(), Some("upper"));
+
+        // 위 마운트를 해제하면 아래 마운트가 다시 보임
+        table.umount("/mnt").unwrap();
+        let visible = table.resolve("/mnt/only-in-lower").unwrap();
+        assert!(visible.fs.lock().metadata(&visible.path).is_ok());
+    }
+
+    #[test_case]
+    fn test_mount_rejects_device_mounted_twice() {
+        let mut table = FileSystemManager::new();
+        table.mount("/", "tmpfs", tmpfs(), MountFlags::default()).unwrap();
+        table.mount("/a", "/dev/ata0p1", tmpfs(), MountFlags::default()).unwrap();
+        assert_eq!(table.mount("/b", "/dev/ata0p1", tmpfs(), MountFlags::default()), Err(FsError::Busy));
+        // 같은 내용을 다른 곳에 보이는 것은 바인드 마운트로
+        table.bind("/a", "/b", MountFlags::default()).unwrap();
+        // 디바이스가 아닌 원본(tmpfs 등)은 여러 번 마운트 가능
+        table.mount("/c", "tmpfs", tmpfs(), MountFlags::default()).unwrap();
+
+        assert!(table.is_disk_in_use("/dev/ata0"));
+        assert!(!table.is_disk_in_use("/dev/ata1"));
+        assert!(!table.is_disk_in_use("/dev/ata0p2"));
+        table.mount("/d", "/dev/hda", tmpfs(), MountFlags::default()).unwrap();
+        // 디스크 전체가 마운트되면 그 파티션도 사용 중
+        assert!(table.is_disk_in_use("/dev/ata0p2"));
+        assert_eq!(table.mount("/e", "/dev/ata0", tmpfs(), MountFlags::default()), Err(FsError::Busy));
+    }
+}
@@ -0,0 +1,214 @@
+//! 파티션 테이블 (MBR, GPT)
+//!
+//! 디스크 0번 섹터의 MBR에서 주 파티션 네 개를 읽고, 보호 MBR(유형 0xEE)이면
+//! GPT 헤더와 파티션 항목 배열을 읽습니다. 확장 파티션(논리 드라이브)은 지원하지 않습니다.
+//!
+//! 찾은 파티션은 `PartitionDevice`로 감싸 디스크의 일부 구간을 독립된 블록 디바이스처럼
+//! 쓸 수 있습니다 (devfs의 `/dev/ata0p1` 등).
+
+use super::vfs::{FsError, FsResult};
+use crate::drivers::ata::{BlockDevice, BlockDeviceError};
+use alloc::boxed::Box;
+use alloc::vec;
+use alloc::vec::Vec;
+
+/// MBR 파티션 항목 배열 오프셋
+const MBR_TABLE_OFFSET: usize = 446;
+/// MBR 부트 시그니처 오프셋 (0x55, 0xAA)
+const MBR_SIGNATURE_OFFSET: usize = 510;
+/// GPT 보호 MBR 파티션 유형
+const MBR_TYPE_GPT_PROTECTIVE: u8 = 0xEE;
+/// 확장 파티션 유형 (CHS, LBA)
+const MBR_TYPE_EXTENDED: [u8; 2] = [0x05, 0x0F];
+/// GPT 헤더 시그니처
+const GPT_SIGNATURE: &[u8; 8] = b"EFI PART";
+/// 읽을 GPT 항목 수 상한
+const GPT_MAX_ENTRIES: u32 = 128;
+
+/// 파티션 하나
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Partition {
+    /// 1부터 시작하는 파티션 번호 (테이블의 위치)
+    pub number: u32,
+    /// 시작 블록 (디바이스 블록 단위)
+    pub start: u64,
+    /// 블록 수
+    pub blocks: u64,
+}
+
+fn le_u32(bytes: &[u8], offset: usize) -> u32 {
+    u32::from_le_bytes([bytes[offset], bytes[offset + 1], bytes[offset + 2], bytes[offset + 3]])
+}
+
+fn le_u64(bytes: &[u8], offset: usize) -> u64 {
+    le_u32(bytes, offset) as u64 | (le_u32(bytes, offset + 4) as u64) << 32
+}
+
+/// MBR 해석 결과
+#[derive(Debug, PartialEq, Eq)]
+enum Mbr {
+    /// 파티션 테이블 없음
+    None,
+    /// 보호 MBR (GPT를 읽어야 함)
+    Protective,
+    Partitions(Vec<Partition>),
+}
+
+/// 0번 섹터의 MBR 해석
+fn parse_mbr(sector: &[u8]) -> Mbr {
+    if sector.len() < 512 || sector[MBR_SIGNATURE_OFFSET] != 0x55 || sector[MBR_SIGNATURE_OFFSET + 1] != 0xAA {
+        return Mbr::None;
+    }
+    let mut partitions = Vec::new();
+    for i in 0..4 {
+        let entry = &sector[MBR_TABLE_OFFSET + i * 16..MBR_TABLE_OFFSET + (i + 1) * 16];
+        // 부트 플래그가 0x00/0x80이 아니면 FAT 부트 섹터의 부트 코드 (파티션 테이블 아님)
+        if entry[0] != 0x00 && entry[0] != 0x80 {
+            return Mbr::None;
+        }
+        let kind = entry[4];
+        if kind == MBR_TYPE_GPT_PROTECTIVE {
+            return Mbr::Protective;
+        }
+        let start = le_u32(entry, 8) as u64;
+        let blocks = le_u32(entry, 12) as u64;
+        if kind == 0 || MBR_TYPE_EXTENDED.contains(&kind) || blocks == 0 {
+            continue;
+        }
+        partitions.push(Partition { number: i as u32 + 1, start, blocks });
+    }
+    if partitions.is_empty() {
+        Mbr::None
+    } else {
+        Mbr::Partitions(partitions)
+    }
+}
+
+/// GPT 헤더(LBA 1)와 항목 배열 읽기
+fn scan_gpt(device: &mut dyn BlockDevice) -> FsResult<Vec<Partition>> {
+    let sector_size = device.block_size();
+    let mut sector = vec![0u8; sector_size];
+    device.read_block(1, &mut sector).map_err(|_| FsError::IOError)?;
+    if &sector[0..8] != GPT_SIGNATURE {
+        return Err(FsError::InvalidFilesystem);
+    }
+    let entries_lba = le_u64(&sector, 72);
+    let entry_count = le_u32(&sector, 80).min(GPT_MAX_ENTRIES);
+    let entry_size = le_u32(&sector, 84) as usize;
+    if entry_size < 128 || entry_size > sector_size || sector_size % entry_size != 0 {
+        return Err(FsError::InvalidFilesystem);
+    }
+
+    let per_sector = (sector_size / entry_size) as u32;
+    let mut partitions = Vec::new();
+    for index in 0..entry_count {
+        if index % per_sector == 0 {
+            device.read_block(entries_lba + (index / per_sector) as u64, &mut sector).map_err(|_| FsError::IOError)?;
+        }
+        let offset = (index % per_sector) as usize * entry_size;
+        let entry = &sector[offset..offset + entry_size];
+        // 유형 GUID가 모두 0이면 빈 항목
+        if entry[0..16].iter().all(|&b| b == 0) {
+            continue;
+        }
+        let first = le_u64(entry, 32);
+        let last = le_u64(entry, 40);
+        if last < first {
+            continue;
+        }
+        partitions.push(Partition { number: index + 1, start: first, blocks: last - first + 1 });
+    }
+    Ok(partitions)
+}
+
+/// 디바이스의 파티션 목록 (파티션 테이블이 없으면 빈 목록)
+///
+/// 디바이스 범위를 벗어나는 파티션은 제외합니다.
+pub fn scan(device: &mut dyn BlockDevice) -> FsResult<Vec<Partition>> {
+    let mut sector = vec![0u8; device.block_size()];
+    device.read_block(0, &mut sector).map_err(|_| FsError::IOError)?;
+    let mut partitions = match parse_mbr(&sector) {
+        Mbr::None => Vec::new(),
+        Mbr::Protective => scan_gpt(device)?,
+        Mbr::Partitions(partitions) => partitions,
+    };
+    let total = device.num_blocks();
+    partitions.retain(|p| p.start > 0 && p.start.checked_add(p.blocks).is_some_and(|end| end <= total));
+    Ok(partitions)
+}
+
+/// 디스크의 한 파티션을 블록 디바이스로 노출
+pub struct PartitionDevice {
+    inner: Box<dyn BlockDevice>,
+    start: u64,
+    blocks: u64,
+}
+
+impl PartitionDevice {
+    pub fn new(inner: Box<dyn BlockDevice>, partition: Partition) -> Self {
+        Self { inner, start: partition.start, blocks: partition.blocks }
+    }
+
+    fn translate(&self, block: u64) -> Result<u64, BlockDeviceError> {
+        if block >= self.blocks {
+            return Err(BlockDeviceError::InvalidBlock);
+        }
+        Ok(self.start + block)
+    }
+}
+
+impl BlockDevice for PartitionDevice {
+    fn block_size(&self) -> usize {
+        self.inner.block_size()
+    }
+
+    fn read_block(&mut self, block: u64, buf: &mut [u8]) -> Result<usize, BlockDeviceError> {
+        let block = self.translate(block)?;
+        self.inner.read_block(block, buf)
+    }
+
+    fn write_block(&mut self, block: u64, buf: &[u8]) -> Result<usize, BlockDeviceError> {
+        let block = self.translate(block)?;
+        self.inner.write_block(block, buf)
+    }
+
+    fn num_blocks(&self) -> u64 {
+        self.blocks
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mbr_with(entries: &[(u8, u32, u32)]) -> Vec<u8> {
+        let mut sector = vec![0u8; 512];
+        for (i, &(kind, start, blocks)) in entries.iter().enumerate() {
+            let entry = MBR_TABLE_OFFSET + i * 16;
+            sector[entry + 4] = kind;
+            sector[entry + 8..entry + 12].copy_from_slice(&start.to_le_bytes());
+            sector[entry + 12..entry + 16].copy_from_slice(&blocks.to_le_bytes());
+        }
+        sector[510] = 0x55;
+        sector[511] = 0xAA;
+        sector
+    }
+
+    #[test_case]
+    fn test_parse_mbr() {
+        let sector = mbr_with(&[(0x83, 2048, 1000), (0x05, 4096, 100), (0x0C, 8192, 50)]);
+        assert_eq!(
+            parse_mbr(&sector),
+            Mbr::Partitions(vec![
+                Partition { number: 1, start: 2048, blocks: 1000 },
+                Partition { number: 3, start: 8192, blocks: 50 },
+            ])
+        );
+        assert_eq!(parse_mbr(&mbr_with(&[(0xEE, 1, 0xFFFF_FFFF)])), Mbr::Protective);
+        assert_eq!(parse_mbr(&mbr_with(&[])), Mbr::None);
+        assert_eq!(parse_mbr(&[0u8; 512]), Mbr::None);
+        let mut boot_code = mbr_with(&[(0x0C, 2048, 100)]);
+        boot_code[MBR_TABLE_OFFSET] = 0x33;
+        assert_eq!(parse_mbr(&boot_code), Mbr::None);
+    }
+}
@@ -0,0 +1,538 @@
+//! procfs: 커널 상태 조회 파일시스템
+//!
+//! 커널 내부 통계를 텍스트 파일로 보여줍니다. 파일 내용은 열 때 만들어지므로 같은 핸들을
+//! 계속 읽으면 연 시점의 스냅샷을 봅니다.
+//!
+//! - `meminfo`: 힙, 물리 프레임, 스왑, tmpfs, 페이지 캐시 사용량
+//! - `metrics`: `monitoring::metrics` 카운터 (`이름 값`)
+//! - `pci`: 디바이스 모델에 등록된 PCI 함수
+//! - `power/stats`, `power/battery`, `power/thermal`
+//! - `<tid>/status`: 실행 중이거나 준비 상태인 스레드
+//!
+//! 쓰기 가능한 튜너블 (값 하나를 씀, 앞뒤 공백 무시):
+//! - `power/mode`: `performance`, `balanced`, `powersave`
+//! - `sys/vm/writeback_policy`: `immediate`, `periodic`, `bursty`
+//! - `sys/kernel/log_level`: `error`, `warn`, `info`, `debug`, `trace`
+
+use super::cache::{self, WritebackPolicy};
+use super::path::Path;
+use super::vfs::{Directory, File, FileMetadata, FileMode, FileSystem, FileType, FsError, FsResult, FsStats, Offset};
+use crate::drivers::base::{self, DeviceInfo};
+use crate::logging::{self, LogLevel};
+use crate::power::policy::PowerMode;
+use crate::scheduler::thread::{Thread, ThreadState};
+use crate::security::user::get_current_uid;
+use alloc::boxed::Box;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt::{self, Write};
+
+/// 파일 내용 생성 함수
+type ShowFn = fn(&mut String) -> fmt::Result;
+/// 튜너블 값 적용 함수
+type StoreFn = fn(&str) -> FsResult<()>;
+
+/// 고정 파일 하나
+struct Entry {
+    /// procfs 루트 기준 경로
+    path: &'static str,
+    show: ShowFn,
+    /// 쓰기 가능한 튜너블이면 `Some`
+    store: Option<StoreFn>,
+}
+
+static ENTRIES: &[Entry] = &[
+    Entry { path: "meminfo", show: show_meminfo, store: None },
+    Entry { path: "metrics", show: show_metrics, store: None },
+    Entry { path: "pci", show: show_pci, store: None },
+    Entry { path: "power/stats", show: show_power_stats, store: None },
+    Entry { path: "power/battery", show: show_battery, store: None },
+    Entry { path: "power/thermal", show: show_thermal, store: None },
+    Entry { path: "power/mode", show: show_power_mode, store: Some(store_power_mode) },
+    Entry { path: "sys/vm/writeback_policy", show: show_writeback_policy, store: Some(store_writeback_policy) },
+    Entry { path: "sys/kernel/log_level", show: show_log_level, store: Some(store_log_level) },
+];
+
+fn show_meminfo(out: &mut String) -> fmt::Result {
+    let (_, heap_size) = crate::memory::heap::heap_bounds();
+    let (_, _, heap_in_use) = crate::kernel::watchdog::get_memory_usage();
+    writeln!(out, "HeapSize:        {} kB", heap_size / 1024)?;
+    writeln!(out, "HeapInUse:       {} kB", heap_in_use / 1024)?;
+    writeln!(out, "HeapAvailable:   {} kB", crate::memory::heap::heap_free() / 1024)?;
+    if let Some((allocated, deallocated)) = crate::memory::frame::get_frame_stats() {
+        writeln!(out, "FramesAllocated: {}", allocated)?;
+        writeln!(out, "FramesFreed:     {}", deallocated)?;
+        writeln!(out, "FramesInUse:     {} kB", allocated.saturating_sub(deallocated) * 4)?;
+    }
+    let swap = crate::memory::swap::get_swap_stats();
+    writeln!(out, "SwapTotal:       {} kB", swap.max_slots as u64 * 4)?;
+    writeln!(out, "SwapFree:        {} kB", swap.available_slots as u64 * 4)?;
+    writeln!(out, "SwappedPages:    {}", swap.swapped_pages)?;
+    writeln!(out, "SwapIns:         {}", swap.swap_in_count)?;
+    writeln!(out, "SwapOuts:        {}", swap.swap_out_count)?;
+    writeln!(out, "Tmpfs:           {} kB", super::tmpfs::total_bytes() / 1024)?;
+    let stats = cache::get_cache_stats();
+    writeln!(out, "Cached:          {} kB", stats.size * cache::PAGE_SIZE / 1024)?;
+    writeln!(out, "CacheHits:       {}", stats.hits)?;
+    writeln!(out, "CacheMisses:     {}", stats.misses)?;
+    writeln!(out, "CacheReadahead:  {}", stats.readahead)
+}
+
+fn show_metrics(out: &mut String) -> fmt::Result {
+    crate::monitoring::metrics::update_metrics();
+    for (name, value) in crate::monitoring::metrics::get_metrics().fields() {
+        writeln!(out, "{} {}", name, value)?;
+    }
+    Ok(())
+}
+
+fn show_pci(out: &mut String) -> fmt::Result {
+    for entry in base::devices() {
+        let DeviceInfo::Pci(pci) = entry.device.info else { continue };
+        writeln!(
+            out,
+            "{} {:04x}:{:04x} class {:02x}{:02x}{:02x} irq {} {:?} {}",
+            entry.device.name,
+            pci.vendor_id,
+            pci.device_id,
+            pci.class_code,
+            pci.subclass,
+            pci.prog_if,
+            pci.interrupt_line,
+            entry.state,
+            entry.driver.unwrap_or("-"),
+        )?;
+    }
+    Ok(())
+}
+
+fn show_power_stats(out: &mut String) -> fmt::Result {
+    let stats = crate::power::stats::get_statistics();
+    writeln!(out, "AvgPower:     {} mW", stats.avg_power_mw)?;
+    writeln!(out, "PeakPower:    {} mW", stats.peak_power_mw)?;
+    writeln!(out, "Energy:       {} mJ", stats.energy_consumed_mj)?;
+    writeln!(out, "Uptime:       {} ms", stats.uptime_ms)?;
+    writeln!(out, "WakeupRate:   {:.2}/s", stats.get_wakeup_rate())?;
+    let states = crate::power::get_manager().and_then(|pm| pm.lock().as_ref().map(|m| (m.get_current_p_state(), m.get_current_c_state())));
+    if let Some((p_state, c_state)) = states {
+        writeln!(out, "PState:       P{}", p_state)?;
+        writeln!(out, "CState:       C{}", c_state)?;
+    }
+    for state in 0..8 {
+        writeln!(out, "C{}Residency:  {:.1}%", state, stats.get_c_state_residency(state))?;
+    }
+    Ok(())
+}
+
+fn show_battery(out: &mut String) -> fmt::Result {
+    let info = crate::power::battery::get_battery_info();
+    writeln!(out, "Status:         {:?}", info.status)?;
+    writeln!(out, "Level:          {}%", info.level_percent)?;
+    if let Some(capacity) = info.capacity_mah {
+        writeln!(out, "Capacity:       {} mAh", capacity)?;
+    }
+    if let Some(design) = info.design_capacity_mah {
+        writeln!(out, "DesignCapacity: {} mAh", design)?;
+    }
+    if let Some(voltage) = info.voltage_mv {
+        writeln!(out, "Voltage:        {} mV", voltage)?;
+    }
+    Ok(())
+}
+
+fn show_thermal(out: &mut String) -> fmt::Result {
+    match crate::power::temps::read_package_temperature_c() {
+        Some(temp) => writeln!(out, "Temperature: {} C", temp)?,
+        None => writeln!(out, "Temperature: unavailable")?,
+    }
+    writeln!(out, "Throttling:  {}", crate::power::temps::is_thermal_throttling() as u8)
+}
+
+/// 튜너블 값 이름 (쓸 때 받는 이름과 같음)
+const POWER_MODES: [(&str, PowerMode); 3] = [
+    ("performance", PowerMode::Performance),
+    ("balanced", PowerMode::Balanced),
+    ("powersave", PowerMode::PowerSaving),
+];
+const WRITEBACK_POLICIES: [(&str, WritebackPolicy); 3] = [
+    ("immediate", WritebackPolicy::Immediate),
+    ("periodic", WritebackPolicy::Periodic),
+    ("bursty", WritebackPolicy::Bursty),
+];
+
+/// 값 목록을 한 줄로 쓰고 현재 값을 `[]`로 표시 (예: "immediate periodic [bursty]")
+fn show_choices<T: PartialEq>(out: &mut String, choices: &[(&str, T)], current: Option<T>) -> fmt::Result {
+    for (i, (name, value)) in choices.iter().enumerate() {
+        let separator = if i == 0 { "" } else { " " };
+        if current.as_ref() == Some(value) {
+            write!(out, "{}[{}]", separator, name)?;
+        } else {
+            write!(out, "{}{}", separator, name)?;
+        }
+    }
+    writeln!(out)
+}
+
+/// 이름으로 값 찾기 (없으면 `InvalidPath`)
+fn parse_choice<T: Copy>(choices: &[(&str, T)], input: &str) -> FsResult<T> {
+    choices
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case(input))
+        .map(|&(_, value)| value)
+        .ok_or(FsError::InvalidPath)
+}
+
+fn show_power_mode(out: &mut String) -> fmt::Result {
+    let current = crate::power::get_manager().and_then(|pm| pm.lock().as_ref().map(|m| m.get_policy()));
+    show_choices(out, &POWER_MODES, current)
+}
+
+fn store_power_mode(input: &str) -> FsResult<()> {
+    let mode = parse_choice(&POWER_MODES, input)?;
+    crate::power::set_mode(mode).map_err(|e| {
+        crate::log_warn!("procfs: cannot set power mode: {:?}", e);
+        FsError::IOError
+    })
+}
+
+fn show_writeback_policy(out: &mut String) -> fmt::Result {
+    show_choices(out, &WRITEBACK_POLICIES, Some(cache::get_writeback_policy()))
+}
+
+fn store_writeback_policy(input: &str) -> FsResult<()> {
+    cache::set_writeback_policy(parse_choice(&WRITEBACK_POLICIES, input)?);
+    Ok(())
+}
+
+fn log_levels() -> [(&'static str, LogLevel); 5] {
+    LogLevel::ALL.map(|level| (level.name(), level))
+}
+
+fn show_log_level(out: &mut String) -> fmt::Result {
+    show_choices(out, &log_levels(), Some(logging::level()))
+}
+
+fn store_log_level(input: &str) -> FsResult<()> {
+    logging::set_level(parse_choice(&log_levels(), input)?);
+    Ok(())
+}
+
+/// `/proc/<tid>/status`
+fn show_thread_status(out: &mut String, thread: &Thread) -> fmt::Result {
+    let state = match thread.state {
+        ThreadState::Ready => "R (ready)",
+        ThreadState::Running => "R (running)",
+        ThreadState::Blocked => "S (blocked)",
+        ThreadState::Terminated => "X (terminated)",
+    };
+    writeln!(out, "Name:         {}", thread.name)?;
+    writeln!(out, "Tid:          {}", thread.id)?;
+    writeln!(out, "State:        {}", state)?;
+    writeln!(out, "Priority:     {:?}", thread.priority)?;
+    writeln!(out, "Cpu:          {}", thread.cpu)?;
+    writeln!(out, "CpusAllowed:  {}", thread.affinity)?;
+    writeln!(out, "StackSize:    {} kB", thread.stack_size / 1024)?;
+    writeln!(out, "Frames:       {}", thread.allocated_frames_len())
+}
+
+/// 현재 스레드 ID 목록 (오름차순)
+fn thread_ids() -> Vec<u64> {
+    let mut ids: Vec<u64> = crate::scheduler::threads().iter().map(|t| t.lock().id).collect();
+    ids.sort_unstable();
+    ids
+}
+
+/// 경로가 가리키는 노드
+enum Node {
+    Dir,
+    Entry(&'static Entry),
+    ThreadStatus(u64),
+}
+
+/// 경로를 루트 기준 이름으로 (루트는 빈 문자열)
+fn node_name(path: &str) -> FsResult<String> {
+    let path = Path::parse(path).map_err(|_| FsError::InvalidPath)?.normalize();
+    let names: Vec<&str> = path.components.iter().map(|c| c.as_str()).collect();
+    Ok(names.join("/"))
+}
+
+/// 스레드 디렉토리 이름이면 스레드 ID
+fn thread_dir(name: &str) -> Option<u64> {
+    let tid: u64 = name.parse().ok()?;
+    thread_ids().contains(&tid).then_some(tid)
+}
+
+fn resolve(name: &str) -> FsResult<Node> {
+    if let Some(entry) = ENTRIES.iter().find(|e| e.path == name) {
+        return Ok(Node::Entry(entry));
+    }
+    if name.is_empty() || ENTRIES.iter().any(|e| e.path.strip_prefix(name).is_some_and(|rest| rest.starts_with('/'))) {
+        return Ok(Node::Dir);
+    }
+    let (dir, file) = name.split_once('/').unwrap_or((name, ""));
+    match (thread_dir(dir), file) {
+        (Some(_), "") => Ok(Node::Dir),
+        (Some(tid), "status") => Ok(Node::ThreadStatus(tid)),
+        _ => Err(FsError::NotFound),
+    }
+}
+
+/// 디렉토리 `dir` 바로 아래 항목
+fn children(dir: &str) -> Vec<(String, FileType)> {
+    let mut entries: Vec<(String, FileType)> = Vec::new();
+    for entry in ENTRIES {
+        let rest = match dir {
+            "" => entry.path,
+            _ => match entry.path.strip_prefix(dir).and_then(|rest| rest.strip_prefix('/')) {
+                Some(rest) => rest,
+                None => continue,
+            },
+        };
+        let child = match rest.split_once('/') {
+            Some((subdir, _)) => (subdir.to_string(), FileType::Directory),
+            None => (rest.to_string(), FileType::Regular),
+        };
+        if !entries.contains(&child) {
+            entries.push(child);
+        }
+    }
+    if dir.is_empty() {
+        entries.extend(thread_ids().into_iter().map(|tid| (tid.to_string(), FileType::Directory)));
+    } else if thread_dir(dir).is_some() {
+        entries.push((String::from("status"), FileType::Regular));
+    }
+    entries
+}
+
+/// procfs 파일 메타데이터 (root 소유, 크기는 알 수 없으므로 0)
+fn proc_metadata(file_type: FileType, mode: u32) -> FileMetadata {
+    FileMetadata {
+        file_type,
+        size: 0,
+        mode: FileMode::new(mode),
+        created: 0,
+        modified: 0,
+        accessed: 0,
+        uid: 0,
+        gid: 0,
+    }
+}
+
+fn node_metadata(node: &Node) -> FileMetadata {
+    match node {
+        Node::Dir => proc_metadata(FileType::Directory, 0o555),
+        Node::Entry(Entry { store: Some(_), .. }) => proc_metadata(FileType::Regular, 0o644),
+        Node::Entry(_) | Node::ThreadStatus(_) => proc_metadata(FileType::Regular, 0o444),
+    }
+}
+
+/// procfs 파일 핸들 (열 때 만든 내용을 읽음)
+struct ProcFile {
+    data: Vec<u8>,
+    offset: Offset,
+    store: Option<StoreFn>,
+}
+
+impl File for ProcFile {
+    fn read(&mut self, buf: &mut [u8], offset: Option<Offset>) -> FsResult<usize> {
+        let start = offset.unwrap_or(self.offset);
+        if start < 0 {
+            return Ok(0);
+        }
+        let start = (start as usize).min(self.data.len());
+        let count = buf.len().min(self.data.len() - start);
+        buf[..count].copy_from_slice(&self.data[start..start + count]);
+        if offset.is_none() {
+            self.offset = (start + count) as Offset;
+        }
+        Ok(count)
+    }
+
+    /// 튜너블 값 적용 (쓰기 한 번이 값 하나, 오프셋 무시)
+    ///
+    /// 튜너블은 root 소유 0644이므로 root만 쓸 수 있습니다.
+    fn write(&mut self, buf: &[u8], _offset: Option<Offset>) -> FsResult<usize> {
+        let store = self.store.ok_or(FsError::PermissionDenied)?;
+        if get_current_uid() != 0 {
+            return Err(FsError::PermissionDenied);
+        }
+        let value = core::str::from_utf8(buf).map_err(|_| FsError::InvalidPath)?;
+        store(value.trim())?;
+        Ok(buf.len())
+    }
+
+    fn metadata(&self) -> FsResult<FileMetadata> {
+        let mode = if self.store.is_some() { 0o644 } else { 0o444 };
+        Ok(proc_metadata(FileType::Regular, mode))
+    }
+
+    fn size(&self) -> FsResult<u64> {
+        Ok(self.data.len() as u64)
+    }
+
+    fn seek(&mut self, offset: Offset) -> FsResult<Offset> {
+        if offset < 0 {
+            return Err(FsError::InvalidPath);
+        }
+        self.offset = offset;
+        Ok(offset)
+    }
+
+    fn tell(&self) -> FsResult<Offset> {
+        Ok(self.offset)
+    }
+}
+
+/// procfs 인스턴스 (내용은 모두 커널 상태에서 만들어짐)
+pub struct ProcFs {
+    mounted: bool,
+}
+
+impl ProcFs {
+    pub const fn new() -> Self {
+        Self { mounted: false }
+    }
+
+    fn check_mounted(&self) -> FsResult<()> {
+        if !self.mounted {
+            return Err(FsError::InvalidFilesystem);
+        }
+        Ok(())
+    }
+}
+
+impl Default for ProcFs {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FileSystem for ProcFs {
+    fn mount(&mut self) -> FsResult<()> {
+        if self.mounted {
+            return Err(FsError::Busy);
+        }
+        self.mounted = true;
+        Ok(())
+    }
+
+    fn unmount(&mut self) -> FsResult<()> {
+        self.check_mounted()?;
+        self.mounted = false;
+        Ok(())
+    }
+
+    fn open_file(&mut self, path: &str) -> FsResult<Box<dyn File>> {
+        self.check_mounted()?;
+        let mut data = String::new();
+        let store = match resolve(&node_name(path)?)? {
+            Node::Dir => return Err(FsError::IsDirectory),
+            Node::Entry(entry) => {
+                (entry.show)(&mut data).map_err(|_| FsError::IOError)?;
+                entry.store
+            }
+            Node::ThreadStatus(tid) => {
+                let thread = crate::scheduler::threads().into_iter().find(|t| t.lock().id == tid).ok_or(FsError::NotFound)?;
+                show_thread_status(&mut data, &thread.lock()).map_err(|_| FsError::IOError)?;
+                None
+            }
+        };
+        Ok(Box::new(ProcFile { data: data.into_bytes(), offset: 0, store }))
+    }
+
+    fn create_file(&mut self, _path: &str) -> FsResult<Box<dyn File>> {
+        Err(FsError::PermissionDenied)
+    }
+
+    fn open_dir(&mut self, path: &str) -> FsResult<Box<dyn Directory>> {
+        self.check_mounted()?;
+        let name = node_name(path)?;
+        match resolve(&name)? {
+            Node::Dir => Ok(Box::new(ProcfsDirectory { name })),
+            _ => Err(FsError::NotADirectory),
+        }
+    }
+
+    fn create_dir(&mut self, _path: &str) -> FsResult<()> {
+        Err(FsError::PermissionDenied)
+    }
+
+    fn remove(&mut self, _path: &str) -> FsResult<()> {
+        Err(FsError::PermissionDenied)
+    }
+
+    fn rename(&mut self, _old_path: &str, _new_path: &str) -> FsResult<()> {
+        Err(FsError::PermissionDenied)
+    }
+
+    fn metadata(&mut self, path: &str) -> FsResult<FileMetadata> {
+        self.check_mounted()?;
+        Ok(node_metadata(&resolve(&node_name(path)?)?))
+    }
+
+    fn is_mounted(&self) -> bool {
+        self.mounted
+    }
+
+    fn fs_type(&self) -> &'static str {
+        "procfs"
+    }
+
+    fn statfs(&mut self) -> FsResult<FsStats> {
+        Ok(FsStats {
+            block_size: 4096,
+            total_blocks: 0,
+            free_blocks: 0,
+            total_inodes: 0,
+            free_inodes: 0,
+        })
+    }
+}
+
+/// procfs 디렉토리 핸들 (읽기 전용)
+struct ProcfsDirectory {
+    name: String,
+}
+
+impl Directory for ProcfsDirectory {
+    fn read_dir(&self) -> FsResult<Vec<(String, FileType)>> {
+        Ok(children(&self.name))
+    }
+
+    fn create_file(&mut self, _name: &str) -> FsResult<()> {
+        Err(FsError::PermissionDenied)
+    }
+
+    fn create_dir(&mut self, _name: &str) -> FsResult<()> {
+        Err(FsError::PermissionDenied)
+    }
+
+    fn remove(&mut self, _name: &str) -> FsResult<()> {
+        Err(FsError::PermissionDenied)
+    }
+
+    fn rename(&mut self, _old_name: &str, _new_name: &str) -> FsResult<()> {
+        Err(FsError::PermissionDenied)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test_case]
+    fn test_procfs_tunables() {
+        let mut out = String::new();
+        show_choices(&mut out, &WRITEBACK_POLICIES, Some(WritebackPolicy::Periodic)).unwrap();
+        assert_eq!(out, "immediate [periodic] bursty\n");
+        assert_eq!(parse_choice(&POWER_MODES, "Balanced"), Ok(PowerMode::Balanced));
+        assert_eq!(parse_choice(&POWER_MODES, "turbo"), Err(FsError::InvalidPath));
+        assert_eq!(parse_choice(&log_levels(), "WARN"), Ok(LogLevel::Warn));
+
+        let names: Vec<String> = children("sys").into_iter().map(|(name, _)| name).collect();
+        assert_eq!(names, ["vm", "kernel"]);
+        assert!(matches!(resolve("power"), Ok(Node::Dir)));
+        assert!(matches!(resolve("power/mode"), Ok(Node::Entry(Entry { store: Some(_), .. }))));
+        assert!(matches!(resolve("power/missing"), Err(FsError::NotFound)));
+    }
+}
@@ -0,0 +1,149 @@
+//! Sjfs 블록/inode 비트맵 할당자
+//!
+//! 비트맵 블록은 메타데이터이므로 트랜잭션을 거쳐 읽고 씁니다. 블록 비트맵의 비트 `n`은
+//! 블록 `n`, inode 비트맵의 비트 `n`은 inode `n + 1`에 대응합니다. mkfs가 메타데이터 영역과
+//! 범위를 벗어나는 꼬리 비트를 미리 사용 중으로 표시해 두므로 할당자는 경계를 따로
+//! 확인하지 않습니다.
+
+use super::layout::BLOCK_SIZE;
+use super::Sjfs;
+use crate::fs::vfs::{FsError, FsResult};
+
+const BITS_PER_BLOCK: u64 = (BLOCK_SIZE * 8) as u64;
+
+/// 비트맵 블록에서 `from`번째 비트부터 첫 빈 비트 찾기
+fn find_clear_bit(bitmap: &[u8], from: usize) -> Option<usize> {
+    let mut bit = from;
+    while bit < bitmap.len() * 8 {
+        let byte = bitmap[bit / 8];
+        if byte == 0xFF && bit % 8 == 0 {
+            bit += 8;
+            continue;
+        }
+        if byte & (1 << (bit % 8)) == 0 {
+            return Some(bit);
+        }
+        bit += 1;
+    }
+    None
+}
+
+impl Sjfs {
+    /// 비트맵에서 `start`부터 빈 비트를 찾아 설정
+    ///
+    /// `skip`이 `true`를 돌려주는 비트는 건너뜁니다.
+    fn allocate_bit(
+        &mut self,
+        bitmap_start: u64,
+        bitmap_blocks: u32,
+        start: u64,
+        skip: impl Fn(&Self, u64) -> bool,
+    ) -> FsResult<Option<u64>> {
+        let total_bits = bitmap_blocks as u64 * BITS_PER_BLOCK;
+        let start = if start >= total_bits { 0 } else { start };
+        // start부터 끝까지, 그다음 처음부터 start까지
+        for (from, to) in [(start, total_bits), (0, start)] {
+            let mut bit = from;
+            while bit < to {
+                let block_index = bit / BITS_PER_BLOCK;
+                let mut bitmap = self.read_meta(bitmap_start + block_index)?;
+                let mut offset = (bit % BITS_PER_BLOCK) as usize;
+                while let Some(found) = find_clear_bit(&bitmap, offset) {
+                    let candidate = block_index * BITS_PER_BLOCK + found as u64;
+                    if candidate >= to {
+                        break;
+                    }
+                    if skip(self, candidate) {
+                        offset = found + 1;
+                        continue;
+                    }
+                    bitmap[found / 8] |= 1 << (found % 8);
+                    self.write_meta(bitmap_start + block_index, bitmap)?;
+                    return Ok(Some(candidate));
+                }
+                bit = (block_index + 1) * BITS_PER_BLOCK;
+            }
+        }
+        Ok(None)
+    }
+
+    fn clear_bit(&mut self, bitmap_start: u64, bit: u64) -> FsResult<()> {
+        let block = bitmap_start + bit / BITS_PER_BLOCK;
+        let offset = (bit % BITS_PER_BLOCK) as usize;
+        let mut bitmap = self.read_meta(block)?;
+        if bitmap[offset / 8] & (1 << (offset % 8)) == 0 {
+            crate::log_warn!("sjfs: freeing unallocated bit {} (bitmap at {})", bit, bitmap_start);
+            return Err(FsError::InvalidFilesystem);
+        }
+        bitmap[offset / 8] &= !(1 << (offset % 8));
+        self.write_meta(block, bitmap)
+    }
+
+    /// 데이터 블록 할당 (`hint` 근처부터 검색해 파일을 연속으로 유지)
+    pub(super) fn alloc_block(&mut self, hint: u64) -> FsResult<u64> {
+        if self.sb.free_blocks == 0 {
+            return Err(FsError::OutOfSpace);
+        }
+        let g = self.sb.geometry;
+        let hint = hint.max(g.data_start);
+        let freed_in_tx = |fs: &Self, block: u64| fs.tx.as_ref().is_some_and(|tx| tx.freed.contains(&block));
+        let block = self
+            .allocate_bit(g.block_bitmap_start, g.block_bitmap_blocks, hint, freed_in_tx)?
+            .ok_or(FsError::OutOfSpace)?;
+        self.sb.free_blocks -= 1;
+        Ok(block)
+    }
+
+    /// 데이터 블록 해제
+    pub(super) fn free_block(&mut self, block: u64) -> FsResult<()> {
+        let g = self.sb.geometry;
+        if block < g.data_start || block >= g.total_blocks {
+            return Err(FsError::InvalidFilesystem);
+        }
+        self.clear_bit(g.block_bitmap_start, block)?;
+        self.sb.free_blocks += 1;
+        if let Some(tx) = self.tx.as_mut() {
+            tx.freed.insert(block);
+        }
+        Ok(())
+    }
+
+    /// inode 번호 할당
+    pub(super) fn alloc_inode(&mut self) -> FsResult<u32> {
+        if self.sb.free_inodes == 0 {
+            return Err(FsError::OutOfSpace);
+        }
+        let g = self.sb.geometry;
+        let bit = self
+            .allocate_bit(g.inode_bitmap_start, g.inode_bitmap_blocks, 0, |_, _| false)?
+            .ok_or(FsError::OutOfSpace)?;
+        self.sb.free_inodes -= 1;
+        Ok(bit as u32 + 1)
+    }
+
+    /// inode 번호 해제
+    pub(super) fn free_inode(&mut self, ino: u32) -> FsResult<()> {
+        // 번호가 재사용되기 전에 캐시된 내용 버림
+        crate::fs::cache::invalidate(self.cache_key(ino));
+        self.clear_bit(self.sb.geometry.inode_bitmap_start, ino as u64 - 1)?;
+        self.sb.free_inodes += 1;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test_case]
+    fn test_find_clear_bit() {
+        let mut bitmap = [0xFFu8; 4];
+        assert_eq!(find_clear_bit(&bitmap, 0), None);
+        bitmap[2] = 0b1110_1111;
+        assert_eq!(find_clear_bit(&bitmap, 0), Some(20));
+        assert_eq!(find_clear_bit(&bitmap, 21), None);
+        bitmap[0] = 0b1111_1110;
+        assert_eq!(find_clear_bit(&bitmap, 0), Some(0));
+        assert_eq!(find_clear_bit(&bitmap, 1), Some(20));
+    }
+}
@@ -0,0 +1,175 @@
+//! Sjfs 해시 디렉토리
+//!
+//! 디렉토리 내용은 블록 `n`개짜리 해시 테이블입니다. 이름 해시 `h`를 가진 항목은 항상
+//! 디렉토리의 `h % n`번째 블록(버킷)에 들어가므로 찾기는 블록 하나만 읽습니다.
+//! 버킷이 가득 차면 디렉토리를 두 배로 늘리고 모든 항목을 다시 배치합니다.
+//! `.`과 `..`은 저장하지 않습니다 (부모는 inode의 `parent`).
+
+use super::layout::{name_hash, DirSlot, Inode, BLOCK_SIZE, MAX_NAME_LEN, SLOTS_PER_BLOCK};
+use super::Sjfs;
+use crate::fs::vfs::{FileType, FsError, FsResult};
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+
+/// 디렉토리 최대 블록 수 (재배치가 트랜잭션 하나에 들어가야 함)
+pub const MAX_DIR_BLOCKS: u64 = 128;
+
+/// 디렉토리 항목 이름 검사
+pub(super) fn validate_name(name: &str) -> FsResult<()> {
+    if name.is_empty() || name == "." || name == ".." || name.len() > MAX_NAME_LEN {
+        return Err(FsError::InvalidPath);
+    }
+    if name.bytes().any(|b| b == b'/' || b == 0) {
+        return Err(FsError::InvalidPath);
+    }
+    Ok(())
+}
+
+/// 항목들을 블록 `blocks`개짜리 해시 테이블로 배치 (넘치는 버킷이 있으면 `None`)
+fn build_table(entries: &[(String, u32, FileType)], blocks: u64) -> Option<Vec<Vec<u8>>> {
+    let mut table = vec![vec![0u8; BLOCK_SIZE]; blocks as usize];
+    for (name, ino, file_type) in entries {
+        let hash = name_hash(name.as_bytes());
+        let bucket = &mut table[(hash as u64 % blocks) as usize];
+        let index = (0..SLOTS_PER_BLOCK).find(|&i| DirSlot::is_free(bucket, i))?;
+        DirSlot { hash, inode: *ino, file_type: *file_type, name: name.as_bytes() }.encode(bucket, index);
+    }
+    Some(table)
+}
+
+impl Sjfs {
+    /// 이름이 들어갈 버킷 블록
+    fn bucket_block(dir: &Inode, hash: u32) -> FsResult<u64> {
+        let blocks = dir.block_count();
+        if blocks == 0 {
+            return Err(FsError::InvalidFilesystem);
+        }
+        dir.map_block(hash as u64 % blocks).ok_or(FsError::InvalidFilesystem)
+    }
+
+    /// 디렉토리에서 이름 찾기
+    pub(super) fn dir_lookup(&mut self, dir: &Inode, name: &str) -> FsResult<Option<(u32, FileType)>> {
+        let hash = name_hash(name.as_bytes());
+        let block = self.read_meta(Self::bucket_block(dir, hash)?)?;
+        let found = (0..SLOTS_PER_BLOCK)
+            .filter_map(|i| DirSlot::decode(&block, i))
+            .find(|slot| slot.hash == hash && slot.name == name.as_bytes())
+            .map(|slot| (slot.inode, slot.file_type));
+        Ok(found)
+    }
+
+    /// 디렉토리의 모든 항목 (이름, inode, 종류)
+    pub(super) fn dir_entries(&mut self, dir: &Inode) -> FsResult<Vec<(String, u32, FileType)>> {
+        let mut entries = Vec::new();
+        for logical in 0..dir.block_count() {
+            let block = self.read_meta(dir.map_block(logical).ok_or(FsError::InvalidFilesystem)?)?;
+            for slot in (0..SLOTS_PER_BLOCK).filter_map(|i| DirSlot::decode(&block, i)) {
+                let name = String::from_utf8_lossy(slot.name).into_owned();
+                entries.push((name, slot.inode, slot.file_type));
+            }
+        }
+        Ok(entries)
+    }
+
+    /// 디렉토리가 비었는지 확인
+    pub(super) fn dir_is_empty(&mut self, dir: &Inode) -> FsResult<bool> {
+        for logical in 0..dir.block_count() {
+            let block = self.read_meta(dir.map_block(logical).ok_or(FsError::InvalidFilesystem)?)?;
+            if (0..SLOTS_PER_BLOCK).any(|i| !DirSlot::is_free(&block, i)) {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+
+    /// 디렉토리에 항목 추가 (디렉토리 inode 기록은 호출자 몫)
+    pub(super) fn dir_insert(&mut self, dir: &mut Inode, name: &str, ino: u32, file_type: FileType) -> FsResult<()> {
+        validate_name(name)?;
+        if self.dir_lookup(dir, name)?.is_some() {
+            return Err(FsError::AlreadyExists);
+        }
+        let hash = name_hash(name.as_bytes());
+        loop {
+            let bucket = Self::bucket_block(dir, hash)?;
+            let mut block = self.read_meta(bucket)?;
+            if let Some(index) = (0..SLOTS_PER_BLOCK).find(|&i| DirSlot::is_free(&block, i)) {
+                DirSlot { hash, inode: ino, file_type, name: name.as_bytes() }.encode(&mut block, index);
+                return self.write_meta(bucket, block);
+            }
+            self.dir_grow(dir)?;
+        }
+    }
+
+    /// 디렉토리에서 항목 제거
+    ///
+    /// # Returns
+    /// 제거한 항목의 inode와 종류
+    pub(super) fn dir_remove(&mut self, dir: &Inode, name: &str) -> FsResult<(u32, FileType)> {
+        let hash = name_hash(name.as_bytes());
+        let bucket = Self::bucket_block(dir, hash)?;
+        let mut block = self.read_meta(bucket)?;
+        let (index, ino, file_type) = (0..SLOTS_PER_BLOCK)
+            .filter_map(|i| DirSlot::decode(&block, i).map(|slot| (i, slot)))
+            .find(|(_, slot)| slot.hash == hash && slot.name == name.as_bytes())
+            .map(|(i, slot)| (i, slot.inode, slot.file_type))
+            .ok_or(FsError::NotFound)?;
+        DirSlot::clear(&mut block, index);
+        self.write_meta(bucket, block)?;
+        Ok((ino, file_type))
+    }
+
+    /// 디렉토리 크기를 두 배 이상으로 늘리고 항목 재배치
+    fn dir_grow(&mut self, dir: &mut Inode) -> FsResult<()> {
+        let entries = self.dir_entries(dir)?;
+        let current = dir.block_count();
+        let mut blocks = current * 2;
+        let table = loop {
+            if blocks > MAX_DIR_BLOCKS {
+                return Err(FsError::OutOfSpace);
+            }
+            // 해시가 몰려 넘치면 더 늘림
+            match build_table(&entries, blocks) {
+                Some(table) => break table,
+                None => blocks *= 2,
+            }
+        };
+        self.extend_inode(dir, blocks - current)?;
+        for (logical, data) in table.into_iter().enumerate() {
+            let block = dir.map_block(logical as u64).ok_or(FsError::InvalidFilesystem)?;
+            self.write_meta(block, data)?;
+        }
+        dir.size = blocks * BLOCK_SIZE as u64;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::format;
+
+    #[test_case]
+    fn test_dir_name_validation() {
+        assert!(validate_name("report.txt").is_ok());
+        assert_eq!(validate_name(""), Err(FsError::InvalidPath));
+        assert_eq!(validate_name(".."), Err(FsError::InvalidPath));
+        assert_eq!(validate_name("a/b"), Err(FsError::InvalidPath));
+        assert_eq!(validate_name(&"x".repeat(MAX_NAME_LEN + 1)), Err(FsError::InvalidPath));
+    }
+
+    #[test_case]
+    fn test_dir_table_places_entries_in_buckets() {
+        let entries: Vec<(String, u32, FileType)> =
+            (0..40).map(|i| (format!("file{}", i), i + 2, FileType::Regular)).collect();
+        // 40개는 한 블록(32슬롯)에 들어가지 않음
+        assert!(build_table(&entries, 1).is_none());
+        let table = build_table(&entries, 4).unwrap();
+        for (name, ino, _) in &entries {
+            let hash = name_hash(name.as_bytes());
+            let bucket = &table[(hash % 4) as usize];
+            let slot = (0..SLOTS_PER_BLOCK).filter_map(|i| DirSlot::decode(bucket, i)).find(|s| s.inode == *ino);
+            assert_eq!(slot.map(|s| s.name), Some(name.as_bytes()));
+        }
+    }
+}
@@ -0,0 +1,166 @@
+//! Sjfs inode 테이블과 익스텐트 관리
+
+use super::layout::{Extent, Inode, BLOCK_SIZE, INODES_PER_BLOCK, INODE_SIZE, MAX_EXTENTS};
+use super::Sjfs;
+use crate::fs::vfs::{FsError, FsResult};
+use alloc::vec;
+
+impl Sjfs {
+    /// inode가 들어 있는 테이블 블록과 블록 내 오프셋
+    fn inode_location(&self, ino: u32) -> FsResult<(u64, usize)> {
+        if ino == 0 || ino > self.sb.geometry.inode_count {
+            return Err(FsError::InvalidFilesystem);
+        }
+        let index = (ino - 1) as usize;
+        let block = self.sb.geometry.inode_table_start + (index / INODES_PER_BLOCK) as u64;
+        Ok((block, (index % INODES_PER_BLOCK) * INODE_SIZE))
+    }
+
+    /// inode 읽기 (익스텐트 블록 포함)
+    pub(super) fn read_inode(&mut self, ino: u32) -> FsResult<Inode> {
+        let (block, offset) = self.inode_location(ino)?;
+        let table = self.read_meta(block)?;
+        let (mut inode, overflow) = Inode::decode(&table[offset..offset + INODE_SIZE]);
+        if inode.mode == 0 {
+            // 해제된 inode를 가리키는 디렉토리 항목
+            return Err(FsError::InvalidFilesystem);
+        }
+        if overflow > 0 {
+            if inode.extent_block == 0 {
+                return Err(FsError::InvalidFilesystem);
+            }
+            let extents = self.read_meta(inode.extent_block)?;
+            inode.decode_overflow(&extents, overflow);
+        }
+        Ok(inode)
+    }
+
+    /// inode 기록 (필요하면 익스텐트 블록 할당/해제)
+    pub(super) fn write_inode(&mut self, ino: u32, inode: &mut Inode) -> FsResult<()> {
+        match inode.encode_overflow() {
+            Some(extents) => {
+                if inode.extent_block == 0 {
+                    let hint = inode.extents.last().map_or(0, |e| e.end());
+                    inode.extent_block = self.alloc_block(hint)?;
+                }
+                self.write_meta(inode.extent_block, extents)?;
+            }
+            None if inode.extent_block != 0 => {
+                self.free_block(inode.extent_block)?;
+                inode.extent_block = 0;
+            }
+            None => {}
+        }
+        let (block, offset) = self.inode_location(ino)?;
+        let mut table = self.read_meta(block)?;
+        inode.encode(&mut table[offset..offset + INODE_SIZE]);
+        self.write_meta(block, table)
+    }
+
+    /// 해제한 inode 레코드 지우기
+    pub(super) fn clear_inode(&mut self, ino: u32) -> FsResult<()> {
+        let (block, offset) = self.inode_location(ino)?;
+        let mut table = self.read_meta(block)?;
+        table[offset..offset + INODE_SIZE].fill(0);
+        self.write_meta(block, table)
+    }
+
+    /// inode 끝에 블록 `count`개 추가
+    ///
+    /// 가능하면 마지막 익스텐트 바로 뒤 블록을 할당해 익스텐트를 늘립니다.
+    /// 익스텐트 블록은 `write_inode`가 할당합니다.
+    pub(super) fn extend_inode(&mut self, inode: &mut Inode, count: u64) -> FsResult<()> {
+        for _ in 0..count {
+            let hint = inode.extents.last().map_or(0, |e| e.end());
+            let block = self.alloc_block(hint)?;
+            if let Some(last) = inode.extents.last_mut().filter(|e| e.end() == block && e.len < u32::MAX) {
+                last.len += 1;
+            } else if inode.extents.len() >= MAX_EXTENTS {
+                return Err(FsError::OutOfSpace);
+            } else {
+                inode.extents.push(Extent { start: block, len: 1 });
+            }
+        }
+        Ok(())
+    }
+
+    /// inode의 데이터 블록과 익스텐트 블록 모두 해제
+    pub(super) fn free_inode_blocks(&mut self, inode: &mut Inode) -> FsResult<()> {
+        for extent in core::mem::take(&mut inode.extents) {
+            for block in extent.start..extent.end() {
+                self.free_block(block)?;
+            }
+        }
+        if inode.extent_block != 0 {
+            self.free_block(inode.extent_block)?;
+            inode.extent_block = 0;
+        }
+        inode.size = 0;
+        Ok(())
+    }
+
+    /// 파일 데이터 읽기 (저널을 거치지 않음)
+    pub(super) fn read_data(&mut self, inode: &Inode, offset: u64, buf: &mut [u8]) -> FsResult<usize> {
+        if offset >= inode.size {
+            return Ok(0);
+        }
+        let len = buf.len().min((inode.size - offset) as usize);
+        let mut block_buf = vec![0u8; BLOCK_SIZE];
+        let mut done = 0;
+        while done < len {
+            let pos = offset + done as u64;
+            let in_block = (pos % BLOCK_SIZE as u64) as usize;
+            let chunk = (BLOCK_SIZE - in_block).min(len - done);
+            let block = inode.map_block(pos / BLOCK_SIZE as u64).ok_or(FsError::InvalidFilesystem)?;
+            self.read_block(block, &mut block_buf)?;
+            buf[done..done + chunk].copy_from_slice(&block_buf[in_block..in_block + chunk]);
+            done += chunk;
+        }
+        Ok(len)
+    }
+
+    /// 파일 데이터 쓰기 (트랜잭션 안에서 호출)
+    ///
+    /// 필요한 블록을 할당하고 데이터를 바로 기록합니다. 현재 크기를 넘어 쓰면 그 사이는
+    /// 0으로 채웁니다. inode의 크기와 익스텐트만 갱신하며 기록은 호출자가 합니다.
+    pub(super) fn write_data(&mut self, inode: &mut Inode, offset: u64, data: &[u8]) -> FsResult<usize> {
+        let end = offset.checked_add(data.len() as u64).ok_or(FsError::OutOfSpace)?;
+        let needed = end.div_ceil(BLOCK_SIZE as u64);
+        let allocated = inode.block_count();
+        if needed > allocated {
+            if needed - allocated > self.sb.free_blocks {
+                return Err(FsError::OutOfSpace);
+            }
+            self.extend_inode(inode, needed - allocated)?;
+        }
+
+        // 마지막 블록의 파일 끝 이후는 항상 0이므로, 새 블록만 0으로 채우면
+        // 이전 끝과 쓰기 시작점 사이가 0이 됨
+        let mut block_buf = vec![0u8; BLOCK_SIZE];
+        let first = inode.size.min(offset) / BLOCK_SIZE as u64;
+        for logical in first..needed {
+            let fresh = logical >= allocated;
+            let block_start = logical * BLOCK_SIZE as u64;
+            let copy_from = offset.max(block_start);
+            let copy_to = end.min(block_start + BLOCK_SIZE as u64);
+            if !fresh && copy_from >= copy_to {
+                continue;
+            }
+            let block = inode.map_block(logical).ok_or(FsError::InvalidFilesystem)?;
+            let covered = copy_from == block_start && copy_to == block_start + BLOCK_SIZE as u64;
+            if fresh || covered {
+                block_buf.fill(0);
+            } else {
+                self.read_block(block, &mut block_buf)?;
+            }
+            if copy_from < copy_to {
+                let dst = (copy_from - block_start) as usize..(copy_to - block_start) as usize;
+                let src = (copy_from - offset) as usize..(copy_to - offset) as usize;
+                block_buf[dst].copy_from_slice(&data[src]);
+            }
+            self.write_block(block, &block_buf)?;
+        }
+        inode.size = inode.size.max(end);
+        Ok(data.len())
+    }
+}
@@ -0,0 +1,198 @@
+//! Sjfs 메타데이터 저널
+//!
+//! 파일시스템 연산 하나가 트랜잭션 하나입니다. 트랜잭션 동안 바뀐 메타데이터 블록
+//! (슈퍼블록, 비트맵, inode 테이블, 익스텐트 블록, 디렉토리 블록)은 메모리에 모아 두고,
+//! 커밋 시 저널 영역에 다음 순서로 기록합니다.
+//!
+//! ```text
+//! | 디스크립터 (SJJD, 순번, 블록 수, 체크섬, 원래 위치 목록) | 블록 사본 ... | 커밋 (SJJC) |
+//! ```
+//!
+//! 커밋 블록까지 기록되면 블록을 원래 위치에 쓰고(체크포인트) 디스크립터를 지웁니다.
+//! 체크포인트 도중 전원이 끊기면 다음 마운트에서 저널을 재생합니다.
+//!
+//! 파일 데이터는 저널에 넣지 않고 커밋 전에 바로 기록합니다 (ordered 모드). 같은
+//! 트랜잭션에서 해제한 블록은 다시 할당하지 않아, 커밋 전에 쓴 데이터가 아직 유효한
+//! 파일의 블록을 덮어쓰지 않습니다.
+
+use super::layout::{self, Superblock, BLOCK_SIZE};
+use super::Sjfs;
+use crate::fs::vfs::{FsError, FsResult};
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::vec;
+use alloc::vec::Vec;
+
+const DESCRIPTOR_MAGIC: u32 = u32::from_le_bytes(*b"SJJD");
+const COMMIT_MAGIC: u32 = u32::from_le_bytes(*b"SJJC");
+
+/// 디스크립터 헤더 크기 (매직, 순번, 블록 수, 체크섬)
+const DESCRIPTOR_HEADER: usize = 24;
+
+/// 트랜잭션 하나에 담을 수 있는 최대 블록 수 (디스크립터 한 블록 기준)
+pub const MAX_TX_BLOCKS: usize = (BLOCK_SIZE - DESCRIPTOR_HEADER) / 8;
+
+/// 진행 중인 트랜잭션
+pub(super) struct Transaction {
+    /// 바뀐 메타데이터 블록 (블록 번호 -> 새 내용)
+    blocks: BTreeMap<u64, Vec<u8>>,
+    /// 이번 트랜잭션에서 해제한 블록 (커밋 전 재할당 금지)
+    pub(super) freed: BTreeSet<u64>,
+    /// 중단 시 되돌릴 슈퍼블록
+    saved_sb: Superblock,
+}
+
+/// 트랜잭션 블록들의 체크섬 (원래 위치와 내용)
+fn tx_checksum(seq: u64, blocks: &BTreeMap<u64, Vec<u8>>) -> u32 {
+    let mut crc = layout::crc32_update(0xFFFF_FFFF, &seq.to_le_bytes());
+    for (block, data) in blocks {
+        crc = layout::crc32_update(crc, &block.to_le_bytes());
+        crc = layout::crc32_update(crc, data);
+    }
+    crc ^ 0xFFFF_FFFF
+}
+
+impl Sjfs {
+    /// 트랜잭션 안에서 연산 실행 (실패하면 메모리 변경을 모두 버림)
+    pub(super) fn transaction<T>(&mut self, op: impl FnOnce(&mut Self) -> FsResult<T>) -> FsResult<T> {
+        debug_assert!(self.tx.is_none(), "nested sjfs transaction");
+        self.tx = Some(Transaction { blocks: BTreeMap::new(), freed: BTreeSet::new(), saved_sb: self.sb });
+        match op(self) {
+            Ok(value) => {
+                self.commit()?;
+                Ok(value)
+            }
+            Err(e) => {
+                self.abort();
+                Err(e)
+            }
+        }
+    }
+
+    fn abort(&mut self) {
+        if let Some(tx) = self.tx.take() {
+            self.sb = tx.saved_sb;
+        }
+    }
+
+    /// 메타데이터 블록 읽기 (진행 중인 트랜잭션의 변경 반영)
+    pub(super) fn read_meta(&mut self, block: u64) -> FsResult<Vec<u8>> {
+        if let Some(data) = self.tx.as_ref().and_then(|tx| tx.blocks.get(&block)) {
+            return Ok(data.clone());
+        }
+        let mut buf = vec![0u8; BLOCK_SIZE];
+        self.read_block(block, &mut buf)?;
+        Ok(buf)
+    }
+
+    /// 메타데이터 블록 변경 (커밋 때 저널을 거쳐 기록)
+    pub(super) fn write_meta(&mut self, block: u64, data: Vec<u8>) -> FsResult<()> {
+        debug_assert_eq!(data.len(), BLOCK_SIZE);
+        let tx = self.tx.as_mut().ok_or(FsError::IOError)?;
+        tx.blocks.insert(block, data);
+        Ok(())
+    }
+
+    /// 트랜잭션 커밋 후 체크포인트
+    fn commit(&mut self) -> FsResult<()> {
+        let Some(mut tx) = self.tx.take() else { return Ok(()) };
+        if tx.blocks.is_empty() {
+            return Ok(());
+        }
+        let seq = self.sb.journal_seq;
+        self.sb.journal_seq += 1;
+        tx.blocks.insert(0, self.sb.encode());
+
+        let journal_start = self.sb.geometry.journal_start;
+        let capacity = (self.sb.geometry.journal_blocks as usize - 2).min(MAX_TX_BLOCKS);
+        if tx.blocks.len() > capacity {
+            crate::log_warn!("sjfs: transaction of {} blocks exceeds journal ({})", tx.blocks.len(), capacity);
+            self.sb = tx.saved_sb;
+            return Err(FsError::OutOfSpace);
+        }
+
+        let checksum = tx_checksum(seq, &tx.blocks);
+        let mut descriptor = vec![0u8; BLOCK_SIZE];
+        layout::put_u32(&mut descriptor, 0, DESCRIPTOR_MAGIC);
+        layout::put_u64(&mut descriptor, 8, seq);
+        layout::put_u32(&mut descriptor, 16, tx.blocks.len() as u32);
+        layout::put_u32(&mut descriptor, 20, checksum);
+        for (i, &block) in tx.blocks.keys().enumerate() {
+            layout::put_u64(&mut descriptor, DESCRIPTOR_HEADER + i * 8, block);
+        }
+        let mut commit = vec![0u8; BLOCK_SIZE];
+        layout::put_u32(&mut commit, 0, COMMIT_MAGIC);
+        layout::put_u64(&mut commit, 8, seq);
+        layout::put_u32(&mut commit, 20, checksum);
+
+        let written = (|| {
+            self.write_block(journal_start, &descriptor)?;
+            for (i, data) in tx.blocks.values().enumerate() {
+                self.write_block(journal_start + 1 + i as u64, data)?;
+            }
+            self.write_block(journal_start + 1 + tx.blocks.len() as u64, &commit)
+        })();
+        if let Err(e) = written {
+            // 커밋 블록이 없으면 재생되지 않으므로 디스크는 이전 상태 그대로
+            self.sb = tx.saved_sb;
+            return Err(e);
+        }
+
+        // 여기서부터 실패해도 다음 마운트의 재생으로 복구됨
+        for (&block, data) in &tx.blocks {
+            self.write_block(block, data)?;
+        }
+        self.write_block(journal_start, &vec![0u8; BLOCK_SIZE])
+    }
+
+    /// 커밋됐지만 체크포인트가 끝나지 않은 트랜잭션 재생
+    ///
+    /// # Returns
+    /// 재생한 트랜잭션 순번 (없으면 `None`)
+    pub(super) fn replay_journal(&mut self) -> FsResult<Option<u64>> {
+        let journal_start = self.sb.geometry.journal_start;
+        let journal_blocks = self.sb.geometry.journal_blocks as usize;
+        let mut descriptor = vec![0u8; BLOCK_SIZE];
+        self.read_block(journal_start, &mut descriptor)?;
+        if layout::get_u32(&descriptor, 0) != DESCRIPTOR_MAGIC {
+            return Ok(None);
+        }
+
+        let seq = layout::get_u64(&descriptor, 8);
+        let count = layout::get_u32(&descriptor, 16) as usize;
+        let checksum = layout::get_u32(&descriptor, 20);
+        let valid_count = count > 0 && count <= MAX_TX_BLOCKS && count + 2 <= journal_blocks;
+        let committed = valid_count && {
+            let mut commit = vec![0u8; BLOCK_SIZE];
+            self.read_block(journal_start + 1 + count as u64, &mut commit)?;
+            layout::get_u32(&commit, 0) == COMMIT_MAGIC
+                && layout::get_u64(&commit, 8) == seq
+                && layout::get_u32(&commit, 20) == checksum
+        };
+
+        let mut blocks = BTreeMap::new();
+        if committed {
+            for i in 0..count {
+                let home = layout::get_u64(&descriptor, DESCRIPTOR_HEADER + i * 8);
+                let mut data = vec![0u8; BLOCK_SIZE];
+                self.read_block(journal_start + 1 + i as u64, &mut data)?;
+                blocks.insert(home, data);
+            }
+        }
+        let total_blocks = self.sb.geometry.total_blocks;
+        let intact = committed
+            && tx_checksum(seq, &blocks) == checksum
+            && blocks.keys().all(|&b| b < total_blocks && (b < journal_start || b >= journal_start + journal_blocks as u64));
+        if !intact {
+            // 커밋 전에 중단된 트랜잭션: 원래 위치는 건드리지 않았으므로 버림
+            crate::log_warn!("sjfs: discarding incomplete journal transaction {}", seq);
+            self.write_block(journal_start, &vec![0u8; BLOCK_SIZE])?;
+            return Ok(None);
+        }
+
+        for (&block, data) in &blocks {
+            self.write_block(block, data)?;
+        }
+        self.write_block(journal_start, &vec![0u8; BLOCK_SIZE])?;
+        Ok(Some(seq))
+    }
+}
@@ -0,0 +1,542 @@
+//! Sjfs 디스크 형식
+//!
+//! 모든 다중 바이트 필드는 리틀 엔디언입니다. 블록 크기는 4 KiB로 고정이며,
+//! 디스크는 다음 순서로 나뉩니다.
+//!
+//! ```text
+//! | 0: 슈퍼블록 | 저널 | 블록 비트맵 | inode 비트맵 | inode 테이블 | 데이터 ... |
+//! ```
+//!
+//! - inode는 128바이트이며 번호는 1부터 시작합니다 (0은 "없음", 1은 루트 디렉토리).
+//! - 파일 데이터는 익스텐트(시작 블록, 길이) 목록으로 매핑됩니다. inode에 4개가 들어가고
+//!   넘치면 익스텐트 블록 하나를 추가로 사용합니다.
+//! - 디렉토리 블록은 128바이트 슬롯의 해시 테이블입니다 (`DirSlot`).
+
+use super::super::vfs::FileType;
+use alloc::vec;
+use alloc::vec::Vec;
+
+/// 파일시스템 블록 크기 (바이트)
+pub const BLOCK_SIZE: usize = 4096;
+
+/// 슈퍼블록 매직 ("SJFS")
+pub const MAGIC: [u8; 4] = *b"SJFS";
+
+/// 디스크 형식 버전
+pub const VERSION: u32 = 1;
+
+/// 루트 디렉토리 inode 번호
+pub const ROOT_INODE: u32 = 1;
+
+/// mkfs가 허용하는 최소 크기 (블록, 1 MiB)
+pub const MIN_BLOCKS: u64 = 256;
+
+/// inode 하나당 데이터 블록 수 (16 KiB당 inode 1개)
+pub const BLOCKS_PER_INODE: u64 = 4;
+
+/// 깨끗하게 언마운트됨
+pub const STATE_CLEAN: u32 = 1;
+/// 마운트 중 (다음 마운트 시 이 값이면 비정상 종료)
+pub const STATE_MOUNTED: u32 = 2;
+
+/// 파일 종류 비트 (`st_mode`와 같은 값)
+pub const S_IFMT: u16 = 0o170000;
+pub const S_IFDIR: u16 = 0o040000;
+pub const S_IFREG: u16 = 0o100000;
+/// 권한 비트 (setuid/setgid/sticky 포함)
+pub const PERM_MASK: u16 = 0o7777;
+
+pub const INODE_SIZE: usize = 128;
+pub const INODES_PER_BLOCK: usize = BLOCK_SIZE / INODE_SIZE;
+
+/// inode에 직접 들어가는 익스텐트 수
+pub const INLINE_EXTENTS: usize = 4;
+pub const EXTENT_SIZE: usize = 12;
+/// 익스텐트 블록 하나에 들어가는 익스텐트 수
+pub const EXTENTS_PER_BLOCK: usize = BLOCK_SIZE / EXTENT_SIZE;
+/// 파일 하나가 가질 수 있는 최대 익스텐트 수
+pub const MAX_EXTENTS: usize = INLINE_EXTENTS + EXTENTS_PER_BLOCK;
+
+pub const DIR_SLOT_SIZE: usize = 128;
+pub const SLOTS_PER_BLOCK: usize = BLOCK_SIZE / DIR_SLOT_SIZE;
+/// 디렉토리 항목 이름 최대 길이 (바이트)
+pub const MAX_NAME_LEN: usize = DIR_SLOT_SIZE - 10;
+
+pub fn get_u16(buf: &[u8], off: usize) -> u16 {
+    u16::from_le_bytes([buf[off], buf[off + 1]])
+}
+
+pub fn get_u32(buf: &[u8], off: usize) -> u32 {
+    let mut bytes = [0u8; 4];
+    bytes.copy_from_slice(&buf[off..off + 4]);
+    u32::from_le_bytes(bytes)
+}
+
+pub fn get_u64(buf: &[u8], off: usize) -> u64 {
+    let mut bytes = [0u8; 8];
+    bytes.copy_from_slice(&buf[off..off + 8]);
+    u64::from_le_bytes(bytes)
+}
+
+pub fn put_u16(buf: &mut [u8], off: usize, value: u16) {
+    buf[off..off + 2].copy_from_slice(&value.to_le_bytes());
+}
+
+pub fn put_u32(buf: &mut [u8], off: usize, value: u32) {
+    buf[off..off + 4].copy_from_slice(&value.to_le_bytes());
+}
+
+pub fn put_u64(buf: &mut [u8], off: usize, value: u64) {
+    buf[off..off + 8].copy_from_slice(&value.to_le_bytes());
+}
+
+/// CRC-32 (IEEE 802.3 다항식)
+pub fn crc32(data: &[u8]) -> u32 {
+    crc32_update(0xFFFF_FFFF, data) ^ 0xFFFF_FFFF
+}
+
+/// 여러 버퍼에 걸친 CRC-32 계산용 (초기값 `0xFFFF_FFFF`, 마지막에 반전)
+pub fn crc32_update(mut crc: u32, data: &[u8]) -> u32 {
+    for &byte in data {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 != 0 { (crc >> 1) ^ 0xEDB8_8320 } else { crc >> 1 };
+        }
+    }
+    crc
+}
+
+/// 디렉토리 항목 이름 해시 (FNV-1a)
+pub fn name_hash(name: &[u8]) -> u32 {
+    let mut hash: u32 = 0x811C_9DC5;
+    for &byte in name {
+        hash ^= byte as u32;
+        hash = hash.wrapping_mul(0x0100_0193);
+    }
+    hash
+}
+
+/// 블록 수로부터 계산한 디스크 배치
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Geometry {
+    pub total_blocks: u64,
+    pub inode_count: u32,
+    pub journal_start: u64,
+    pub journal_blocks: u32,
+    pub block_bitmap_start: u64,
+    pub block_bitmap_blocks: u32,
+    pub inode_bitmap_start: u64,
+    pub inode_bitmap_blocks: u32,
+    pub inode_table_start: u64,
+    pub inode_table_blocks: u32,
+    pub data_start: u64,
+}
+
+impl Geometry {
+    /// 파일시스템 배치 계산 (너무 작으면 `None`)
+    pub fn compute(total_blocks: u64) -> Option<Self> {
+        if total_blocks < MIN_BLOCKS {
+            return None;
+        }
+        let bits_per_block = (BLOCK_SIZE * 8) as u64;
+        let journal_blocks = (total_blocks / 64).clamp(64, 1024) as u32;
+        let inode_count = (total_blocks / BLOCKS_PER_INODE)
+            .clamp(INODES_PER_BLOCK as u64, u32::MAX as u64 / 2) as u32;
+        let inode_count = inode_count - inode_count % INODES_PER_BLOCK as u32;
+        let block_bitmap_blocks = total_blocks.div_ceil(bits_per_block) as u32;
+        let inode_bitmap_blocks = (inode_count as u64).div_ceil(bits_per_block) as u32;
+        let inode_table_blocks = inode_count / INODES_PER_BLOCK as u32;
+
+        let journal_start = 1;
+        let block_bitmap_start = journal_start + journal_blocks as u64;
+        let inode_bitmap_start = block_bitmap_start + block_bitmap_blocks as u64;
+        let inode_table_start = inode_bitmap_start + inode_bitmap_blocks as u64;
+        let data_start = inode_table_start + inode_table_blocks as u64;
+        // 루트 디렉토리 블록과 약간의 여유
+        if data_start + 16 > total_blocks {
+            return None;
+        }
+        Some(Self {
+            total_blocks,
+            inode_count,
+            journal_start,
+            journal_blocks,
+            block_bitmap_start,
+            block_bitmap_blocks,
+            inode_bitmap_start,
+            inode_bitmap_blocks,
+            inode_table_start,
+            inode_table_blocks,
+            data_start,
+        })
+    }
+}
+
+/// 슈퍼블록 (블록 0)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Superblock {
+    pub geometry: Geometry,
+    pub state: u32,
+    pub free_blocks: u64,
+    pub free_inodes: u32,
+    /// 다음 저널 트랜잭션 순번
+    pub journal_seq: u64,
+    pub created: u64,
+    pub mounted_at: u64,
+    pub mount_count: u32,
+    pub label: [u8; 16],
+}
+
+impl Superblock {
+    /// 체크섬이 덮는 범위
+    const CHECKSUM_OFFSET: usize = 508;
+
+    pub fn encode(&self) -> Vec<u8> {
+        let g = &self.geometry;
+        let mut buf = vec![0u8; BLOCK_SIZE];
+        buf[0..4].copy_from_slice(&MAGIC);
+        put_u32(&mut buf, 4, VERSION);
+        put_u32(&mut buf, 8, BLOCK_SIZE as u32);
+        put_u32(&mut buf, 12, self.state);
+        put_u64(&mut buf, 16, g.total_blocks);
+        put_u32(&mut buf, 24, g.inode_count);
+        put_u32(&mut buf, 28, self.free_inodes);
+        put_u64(&mut buf, 32, self.free_blocks);
+        put_u64(&mut buf, 40, g.journal_start);
+        put_u32(&mut buf, 48, g.journal_blocks);
+        put_u32(&mut buf, 52, self.mount_count);
+        put_u64(&mut buf, 56, self.journal_seq);
+        put_u64(&mut buf, 64, g.block_bitmap_start);
+        put_u32(&mut buf, 72, g.block_bitmap_blocks);
+        put_u32(&mut buf, 76, g.inode_bitmap_blocks);
+        put_u64(&mut buf, 80, g.inode_bitmap_start);
+        put_u64(&mut buf, 88, g.inode_table_start);
+        put_u32(&mut buf, 96, g.inode_table_blocks);
+        put_u32(&mut buf, 100, ROOT_INODE);
+        put_u64(&mut buf, 104, g.data_start);
+        put_u64(&mut buf, 112, self.created);
+        put_u64(&mut buf, 120, self.mounted_at);
+        buf[128..144].copy_from_slice(&self.label);
+        let checksum = crc32(&buf[..Self::CHECKSUM_OFFSET]);
+        put_u32(&mut buf, Self::CHECKSUM_OFFSET, checksum);
+        buf
+    }
+
+    /// 슈퍼블록 해석 (매직, 버전, 체크섬, 배치 검증)
+    pub fn decode(buf: &[u8]) -> Option<Self> {
+        if buf.len() < 512 || buf[0..4] != MAGIC {
+            return None;
+        }
+        if get_u32(buf, 4) != VERSION || get_u32(buf, 8) != BLOCK_SIZE as u32 {
+            return None;
+        }
+        if crc32(&buf[..Self::CHECKSUM_OFFSET]) != get_u32(buf, Self::CHECKSUM_OFFSET) {
+            return None;
+        }
+        let geometry = Geometry {
+            total_blocks: get_u64(buf, 16),
+            inode_count: get_u32(buf, 24),
+            journal_start: get_u64(buf, 40),
+            journal_blocks: get_u32(buf, 48),
+            block_bitmap_start: get_u64(buf, 64),
+            block_bitmap_blocks: get_u32(buf, 72),
+            inode_bitmap_blocks: get_u32(buf, 76),
+            inode_bitmap_start: get_u64(buf, 80),
+            inode_table_start: get_u64(buf, 88),
+            inode_table_blocks: get_u32(buf, 96),
+            data_start: get_u64(buf, 104),
+        };
+        // 지금 버전의 mkfs가 만드는 배치와 같아야 함
+        if Geometry::compute(geometry.total_blocks) != Some(geometry) {
+            return None;
+        }
+        let mut label = [0u8; 16];
+        label.copy_from_slice(&buf[128..144]);
+        Some(Self {
+            geometry,
+            state: get_u32(buf, 12),
+            free_blocks: get_u64(buf, 32),
+            free_inodes: get_u32(buf, 28),
+            journal_seq: get_u64(buf, 56),
+            created: get_u64(buf, 112),
+            mounted_at: get_u64(buf, 120),
+            mount_count: get_u32(buf, 52),
+            label,
+        })
+    }
+
+    /// 볼륨 레이블 (NUL 패딩 제거)
+    pub fn label(&self) -> &str {
+        let len = self.label.iter().position(|&b| b == 0).unwrap_or(self.label.len());
+        core::str::from_utf8(&self.label[..len]).unwrap_or("")
+    }
+}
+
+/// 연속된 데이터 블록 범위
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Extent {
+    pub start: u64,
+    pub len: u32,
+}
+
+impl Extent {
+    pub fn end(&self) -> u64 {
+        self.start + self.len as u64
+    }
+
+    fn encode(&self, buf: &mut [u8], off: usize) {
+        put_u64(buf, off, self.start);
+        put_u32(buf, off + 8, self.len);
+    }
+
+    fn decode(buf: &[u8], off: usize) -> Self {
+        Self { start: get_u64(buf, off), len: get_u32(buf, off + 8) }
+    }
+}
+
+/// inode (메모리 표현)
+///
+/// `extents`는 inode 안의 익스텐트와 익스텐트 블록의 익스텐트를 합친 전체 목록입니다.
+/// 디스크의 128바이트 레코드는 앞의 `INLINE_EXTENTS`개만 담습니다.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Inode {
+    pub mode: u16,
+    pub links: u16,
+    pub uid: u32,
+    pub gid: u32,
+    /// 디렉토리의 부모 inode (`..`). 파일은 만든 디렉토리.
+    pub parent: u32,
+    pub size: u64,
+    pub atime: u64,
+    pub mtime: u64,
+    pub ctime: u64,
+    pub crtime: u64,
+    /// 넘친 익스텐트를 담은 블록 (0이면 없음)
+    pub extent_block: u64,
+    pub extents: Vec<Extent>,
+}
+
+impl Inode {
+    pub fn new(mode: u16, uid: u32, gid: u32, parent: u32, now: u64) -> Self {
+        Self {
+            mode,
+            links: if mode & S_IFMT == S_IFDIR { 2 } else { 1 },
+            uid,
+            gid,
+            parent,
+            size: 0,
+            atime: now,
+            mtime: now,
+            ctime: now,
+            crtime: now,
+            extent_block: 0,
+            extents: Vec::new(),
+        }
+    }
+
+    pub fn is_dir(&self) -> bool {
+        self.mode & S_IFMT == S_IFDIR
+    }
+
+    pub fn file_type(&self) -> FileType {
+        if self.is_dir() { FileType::Directory } else { FileType::Regular }
+    }
+
+    /// 할당된 데이터 블록 수
+    pub fn block_count(&self) -> u64 {
+        self.extents.iter().map(|e| e.len as u64).sum()
+    }
+
+    /// 파일 내 블록 번호를 디스크 블록 번호로 변환
+    pub fn map_block(&self, logical: u64) -> Option<u64> {
+        let mut base = 0u64;
+        for extent in &self.extents {
+            if logical < base + extent.len as u64 {
+                return Some(extent.start + (logical - base));
+            }
+            base += extent.len as u64;
+        }
+        None
+    }
+
+    /// 128바이트 레코드로 인코딩 (`buf`는 최소 `INODE_SIZE`)
+    pub fn encode(&self, buf: &mut [u8]) {
+        buf[..INODE_SIZE].fill(0);
+        put_u16(buf, 0, self.mode);
+        put_u16(buf, 2, self.links);
+        put_u32(buf, 4, self.uid);
+        put_u32(buf, 8, self.gid);
+        put_u32(buf, 12, self.parent);
+        put_u64(buf, 16, self.size);
+        put_u64(buf, 24, self.atime);
+        put_u64(buf, 32, self.mtime);
+        put_u64(buf, 40, self.ctime);
+        put_u64(buf, 48, self.crtime);
+        put_u64(buf, 56, self.extent_block);
+        put_u32(buf, 64, self.extents.len() as u32);
+        for (i, extent) in self.extents.iter().take(INLINE_EXTENTS).enumerate() {
+            extent.encode(buf, 72 + i * EXTENT_SIZE);
+        }
+    }
+
+    /// 레코드 해석. 익스텐트 블록에 있는 나머지 익스텐트 수를 함께 반환합니다.
+    pub fn decode(buf: &[u8]) -> (Self, usize) {
+        let count = (get_u32(buf, 64) as usize).min(MAX_EXTENTS);
+        let inline = count.min(INLINE_EXTENTS);
+        let extents = (0..inline).map(|i| Extent::decode(buf, 72 + i * EXTENT_SIZE)).collect();
+        let inode = Self {
+            mode: get_u16(buf, 0),
+            links: get_u16(buf, 2),
+            uid: get_u32(buf, 4),
+            gid: get_u32(buf, 8),
+            parent: get_u32(buf, 12),
+            size: get_u64(buf, 16),
+            atime: get_u64(buf, 24),
+            mtime: get_u64(buf, 32),
+            ctime: get_u64(buf, 40),
+            crtime: get_u64(buf, 48),
+            extent_block: get_u64(buf, 56),
+            extents,
+        };
+        (inode, count - inline)
+    }
+
+    /// 익스텐트 블록에 들어갈 나머지 익스텐트 인코딩
+    pub fn encode_overflow(&self) -> Option<Vec<u8>> {
+        if self.extents.len() <= INLINE_EXTENTS {
+            return None;
+        }
+        let mut buf = vec![0u8; BLOCK_SIZE];
+        for (i, extent) in self.extents[INLINE_EXTENTS..].iter().enumerate() {
+            extent.encode(&mut buf, i * EXTENT_SIZE);
+        }
+        Some(buf)
+    }
+
+    /// 익스텐트 블록에서 나머지 익스텐트 읽기
+    pub fn decode_overflow(&mut self, buf: &[u8], count: usize) {
+        let count = count.min(EXTENTS_PER_BLOCK);
+        self.extents.extend((0..count).map(|i| Extent::decode(buf, i * EXTENT_SIZE)));
+    }
+}
+
+/// 디렉토리 슬롯 (빈 슬롯은 `inode == 0`)
+///
+/// `hash`, `inode`, 종류 1바이트, 이름 길이 1바이트, 이름 최대 118바이트.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DirSlot<'a> {
+    pub hash: u32,
+    pub inode: u32,
+    pub file_type: FileType,
+    pub name: &'a [u8],
+}
+
+impl<'a> DirSlot<'a> {
+    const TYPE_REGULAR: u8 = 1;
+    const TYPE_DIRECTORY: u8 = 2;
+
+    /// 블록 안 `index`번째 슬롯 해석 (빈 슬롯이면 `None`)
+    pub fn decode(block: &'a [u8], index: usize) -> Option<Self> {
+        let off = index * DIR_SLOT_SIZE;
+        let inode = get_u32(block, off + 4);
+        if inode == 0 {
+            return None;
+        }
+        let file_type = match block[off + 8] {
+            Self::TYPE_DIRECTORY => FileType::Directory,
+            _ => FileType::Regular,
+        };
+        let len = (block[off + 9] as usize).min(MAX_NAME_LEN);
+        Some(Self { hash: get_u32(block, off), inode, file_type, name: &block[off + 10..off + 10 + len] })
+    }
+
+    pub fn encode(&self, block: &mut [u8], index: usize) {
+        let off = index * DIR_SLOT_SIZE;
+        block[off..off + DIR_SLOT_SIZE].fill(0);
+        put_u32(block, off, self.hash);
+        put_u32(block, off + 4, self.inode);
+        block[off + 8] = match self.file_type {
+            FileType::Directory => Self::TYPE_DIRECTORY,
+            _ => Self::TYPE_REGULAR,
+        };
+        block[off + 9] = self.name.len() as u8;
+        block[off + 10..off + 10 + self.name.len()].copy_from_slice(self.name);
+    }
+
+    /// 슬롯 비우기
+    pub fn clear(block: &mut [u8], index: usize) {
+        let off = index * DIR_SLOT_SIZE;
+        block[off..off + DIR_SLOT_SIZE].fill(0);
+    }
+
+    /// 슬롯이 비었는지 확인
+    pub fn is_free(block: &[u8], index: usize) -> bool {
+        get_u32(block, index * DIR_SLOT_SIZE + 4) == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test_case]
+    fn test_superblock_roundtrip() {
+        let geometry = Geometry::compute(65536).unwrap();
+        assert_eq!(geometry.journal_blocks, 1024);
+        assert!(geometry.data_start < 65536);
+        let mut label = [0u8; 16];
+        label[..4].copy_from_slice(b"data");
+        let sb = Superblock {
+            geometry,
+            state: STATE_CLEAN,
+            free_blocks: 60000,
+            free_inodes: 16383,
+            journal_seq: 7,
+            created: 1_700_000_000,
+            mounted_at: 0,
+            mount_count: 3,
+            label,
+        };
+        let mut buf = sb.encode();
+        assert_eq!(Superblock::decode(&buf), Some(sb));
+        assert_eq!(sb.label(), "data");
+        // 체크섬 불일치 거부
+        buf[20] ^= 1;
+        assert_eq!(Superblock::decode(&buf), None);
+        assert_eq!(Geometry::compute(MIN_BLOCKS - 1), None);
+    }
+
+    #[test_case]
+    fn test_inode_roundtrip_and_mapping() {
+        let mut inode = Inode::new(S_IFREG | 0o640, 1000, 100, ROOT_INODE, 42);
+        inode.size = 6 * BLOCK_SIZE as u64;
+        inode.extent_block = 900;
+        for i in 0..6u64 {
+            inode.extents.push(Extent { start: 100 + i * 10, len: 1 });
+        }
+        let mut record = [0u8; INODE_SIZE];
+        inode.encode(&mut record);
+        let overflow = inode.encode_overflow().unwrap();
+        let (mut decoded, rest) = Inode::decode(&record);
+        assert_eq!(rest, 2);
+        decoded.decode_overflow(&overflow, rest);
+        assert_eq!(decoded, inode);
+        assert_eq!(decoded.map_block(0), Some(100));
+        assert_eq!(decoded.map_block(5), Some(150));
+        assert_eq!(decoded.map_block(6), None);
+    }
+
+    #[test_case]
+    fn test_dir_slot_roundtrip() {
+        let mut block = vec![0u8; BLOCK_SIZE];
+        let name = b"notes.txt";
+        let slot = DirSlot { hash: name_hash(name), inode: 12, file_type: FileType::Regular, name };
+        slot.encode(&mut block, 3);
+        assert!(DirSlot::is_free(&block, 2));
+        assert_eq!(DirSlot::decode(&block, 3), Some(slot));
+        DirSlot::clear(&mut block, 3);
+        assert!(DirSlot::is_free(&block, 3));
+        assert_ne!(name_hash(b"a"), name_hash(b"b"));
+        assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+    }
+}
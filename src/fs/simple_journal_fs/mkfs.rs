@@ -0,0 +1,113 @@
+//! Sjfs 포맷 (mkfs)
+
+use super::layout::{Extent, Geometry, Inode, Superblock, BLOCK_SIZE, INODE_SIZE, ROOT_INODE, STATE_CLEAN, S_IFDIR};
+use super::{device_blocks, write_raw};
+use crate::drivers::ata::BlockDevice;
+use crate::fs::vfs::{FsError, FsResult};
+use alloc::vec;
+
+/// 비트맵 블록 `index`에서 `[from, to)` 범위의 비트 설정
+fn mark_bits(bitmap: &mut [u8], index: u64, from: u64, to: u64) {
+    let bits = (BLOCK_SIZE * 8) as u64;
+    let base = index * bits;
+    let from = from.clamp(base, base + bits);
+    let to = to.clamp(base, base + bits);
+    for bit in (from - base)..(to - base) {
+        bitmap[(bit / 8) as usize] |= 1 << (bit % 8);
+    }
+}
+
+/// 디바이스 전체에 빈 Sjfs 생성
+///
+/// 기존 내용은 모두 사라집니다. 중간에 실패해도 이전 파일시스템으로 잘못 인식되지 않도록
+/// 슈퍼블록을 먼저 지우고 마지막에 기록합니다.
+///
+/// # Arguments
+/// * `device` - 포맷할 블록 디바이스
+/// * `label` - 볼륨 레이블 (최대 16바이트)
+///
+/// # Returns
+/// 새 파일시스템의 슈퍼블록
+pub fn mkfs(device: &mut dyn BlockDevice, label: &str) -> FsResult<Superblock> {
+    if label.len() > 16 || label.bytes().any(|b| b == 0) {
+        return Err(FsError::InvalidPath);
+    }
+    let total_blocks = device_blocks(device)?;
+    let g = Geometry::compute(total_blocks).ok_or(FsError::OutOfSpace)?;
+    let zero = vec![0u8; BLOCK_SIZE];
+    write_raw(device, 0, &zero)?;
+
+    // 빈 저널 (디스크립터가 없으면 재생할 트랜잭션 없음)
+    write_raw(device, g.journal_start, &zero)?;
+
+    // 메타데이터 영역과 루트 디렉토리 블록은 사용 중, 디스크 끝 이후도 사용 중으로
+    let root_block = g.data_start;
+    for index in 0..g.block_bitmap_blocks as u64 {
+        let mut bitmap = vec![0u8; BLOCK_SIZE];
+        mark_bits(&mut bitmap, index, 0, root_block + 1);
+        mark_bits(&mut bitmap, index, total_blocks, u64::MAX);
+        write_raw(device, g.block_bitmap_start + index, &bitmap)?;
+    }
+    for index in 0..g.inode_bitmap_blocks as u64 {
+        let mut bitmap = vec![0u8; BLOCK_SIZE];
+        mark_bits(&mut bitmap, index, 0, ROOT_INODE as u64);
+        mark_bits(&mut bitmap, index, g.inode_count as u64, u64::MAX);
+        write_raw(device, g.inode_bitmap_start + index, &bitmap)?;
+    }
+
+    let now = crate::time::wallclock::now_unix();
+    let mut root = Inode::new(S_IFDIR | 0o755, 0, 0, ROOT_INODE, now);
+    root.extents.push(Extent { start: root_block, len: 1 });
+    root.size = BLOCK_SIZE as u64;
+    let mut table = vec![0u8; BLOCK_SIZE];
+    let root_offset = (ROOT_INODE as usize - 1) * INODE_SIZE;
+    root.encode(&mut table[root_offset..root_offset + INODE_SIZE]);
+    write_raw(device, g.inode_table_start, &table)?;
+    for block in 1..g.inode_table_blocks as u64 {
+        write_raw(device, g.inode_table_start + block, &zero)?;
+    }
+    write_raw(device, root_block, &zero)?;
+
+    let mut label_bytes = [0u8; 16];
+    label_bytes[..label.len()].copy_from_slice(label.as_bytes());
+    let sb = Superblock {
+        geometry: g,
+        state: STATE_CLEAN,
+        free_blocks: total_blocks - (root_block + 1),
+        free_inodes: g.inode_count - 1,
+        journal_seq: 1,
+        created: now,
+        mounted_at: 0,
+        mount_count: 0,
+        label: label_bytes,
+    };
+    write_raw(device, 0, &sb.encode())?;
+    crate::log_info!(
+        "sjfs: created '{}' with {} blocks, {} inodes, {}-block journal",
+        sb.label(),
+        total_blocks,
+        g.inode_count,
+        g.journal_blocks
+    );
+    Ok(sb)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test_case]
+    fn test_mkfs_mark_bits() {
+        let mut bitmap = vec![0u8; BLOCK_SIZE];
+        mark_bits(&mut bitmap, 0, 0, 10);
+        assert_eq!(bitmap[0], 0xFF);
+        assert_eq!(bitmap[1], 0b0000_0011);
+        // 다른 비트맵 블록의 범위는 무시
+        let mut second = vec![0u8; BLOCK_SIZE];
+        mark_bits(&mut second, 1, 0, 10);
+        assert!(second.iter().all(|&b| b == 0));
+        mark_bits(&mut second, 1, (BLOCK_SIZE * 8) as u64 + 3, u64::MAX);
+        assert_eq!(second[0], 0b1111_1000);
+        assert_eq!(second[BLOCK_SIZE - 1], 0xFF);
+    }
+}
@@ -1,25 +1,706 @@
-//! Simple journaling filesystem (skeleton)
+//! Simple Journaling File System (Sjfs)
+//!
+//! 커널 고유의 파일시스템입니다. FAT32와 달리 inode마다 Unix uid/gid/모드를 저장하고,
+//! 메타데이터 변경은 저널을 거쳐 원자적으로 반영됩니다.
+//!
+//! - `layout`: 슈퍼블록, inode, 디렉토리 슬롯의 디스크 형식
+//! - `journal`: 연산 단위 메타데이터 트랜잭션과 마운트 시 재생
+//! - `bitmap`: 블록/inode 할당자
+//! - `inode`: 익스텐트 매핑과 파일 데이터 입출력
+//! - `dir`: 해시 디렉토리
+//! - `mkfs`: 빈 파일시스템 생성 (셸의 `mkfs.sjfs`)
+//!
+//! 읽기는 접근 시간을 갱신하지 않습니다 (noatime).
 
-use crate::fs::vfs::{FileSystem, File, Directory, FsResult, FsError, FileMetadata, FileType, Offset};
+mod bitmap;
+mod dir;
+mod inode;
+mod journal;
+pub mod layout;
+mod mkfs;
+
+pub use layout::Superblock;
+pub use mkfs::mkfs;
+
+use self::layout::{Inode, BLOCK_SIZE, PERM_MASK, ROOT_INODE, STATE_CLEAN, STATE_MOUNTED, S_IFDIR, S_IFREG};
+use crate::drivers::ata::BlockDevice;
+use crate::fs::cache::{self, FileKey, PAGE_SIZE};
+use crate::fs::path::{Path, PathComponent};
+use crate::fs::vfs::{Directory, File, FileMetadata, FileMode, FileSystem, FileType, FsError, FsResult, FsStats, Offset};
+use crate::security::user::{get_current_gid, get_current_uid};
+use crate::time::wallclock;
 use alloc::boxed::Box;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+
+/// 디바이스 크기를 파일시스템 블록 수로 환산 (섹터 크기가 블록 크기를 나누어야 함)
+fn device_blocks(device: &dyn BlockDevice) -> FsResult<u64> {
+    let sector = device.block_size();
+    if sector == 0 || sector > BLOCK_SIZE || BLOCK_SIZE % sector != 0 {
+        return Err(FsError::InvalidFilesystem);
+    }
+    Ok(device.num_blocks() / (BLOCK_SIZE / sector) as u64)
+}
+
+/// 파일시스템 블록 하나 읽기 (디바이스 섹터 여러 개)
+fn read_raw(device: &mut dyn BlockDevice, block: u64, buf: &mut [u8]) -> FsResult<()> {
+    let sector = device.block_size();
+    let per_block = (BLOCK_SIZE / sector) as u64;
+    for (i, chunk) in buf[..BLOCK_SIZE].chunks_mut(sector).enumerate() {
+        device.read_block(block * per_block + i as u64, chunk).map_err(|_| FsError::IOError)?;
+    }
+    Ok(())
+}
+
+/// 파일시스템 블록 하나 쓰기
+fn write_raw(device: &mut dyn BlockDevice, block: u64, buf: &[u8]) -> FsResult<()> {
+    let sector = device.block_size();
+    let per_block = (BLOCK_SIZE / sector) as u64;
+    for (i, chunk) in buf[..BLOCK_SIZE].chunks(sector).enumerate() {
+        device.write_block(block * per_block + i as u64, chunk).map_err(|_| FsError::IOError)?;
+    }
+    Ok(())
+}
 
+/// Sjfs 인스턴스
 pub struct Sjfs {
+    device: Box<dyn BlockDevice>,
+    sb: Superblock,
     mounted: bool,
+    tx: Option<journal::Transaction>,
+    /// 페이지 캐시 인스턴스 번호
+    cache_id: u32,
 }
 
-impl Sjfs { pub fn new() -> Self { Self { mounted: false } } }
+impl Sjfs {
+    /// 디바이스의 Sjfs 열기 (슈퍼블록 검증)
+    ///
+    /// # Arguments
+    /// * `device` - 블록 디바이스
+    ///
+    /// # Returns
+    /// 마운트 전 파일시스템 또는 오류
+    pub fn new(mut device: Box<dyn BlockDevice>) -> FsResult<Self> {
+        let available = device_blocks(&*device)?;
+        let mut buf = vec![0u8; BLOCK_SIZE];
+        read_raw(&mut *device, 0, &mut buf)?;
+        let sb = Superblock::decode(&buf).ok_or(FsError::InvalidFilesystem)?;
+        if sb.geometry.total_blocks > available {
+            return Err(FsError::InvalidFilesystem);
+        }
+        Ok(Self { device, sb, mounted: false, tx: None, cache_id: cache::new_cache_id() })
+    }
+
+    /// 디바이스에 Sjfs 슈퍼블록이 있는지 확인
+    pub fn probe(device: &mut dyn BlockDevice) -> bool {
+        let mut buf = vec![0u8; BLOCK_SIZE];
+        device_blocks(device).is_ok()
+            && read_raw(device, 0, &mut buf).is_ok()
+            && Superblock::decode(&buf).is_some()
+    }
+
+    /// 슈퍼블록 참조 반환 (용량, 레이블 조회용)
+    pub fn superblock(&self) -> &Superblock {
+        &self.sb
+    }
+
+    fn read_block(&mut self, block: u64, buf: &mut [u8]) -> FsResult<()> {
+        read_raw(&mut *self.device, block, buf)
+    }
+
+    fn write_block(&mut self, block: u64, buf: &[u8]) -> FsResult<()> {
+        write_raw(&mut *self.device, block, buf)
+    }
+
+    /// 경로 구성요소를 따라 inode 찾기
+    fn walk(&mut self, components: &[PathComponent]) -> FsResult<u32> {
+        let mut ino = ROOT_INODE;
+        for component in components {
+            let dir = self.read_inode(ino)?;
+            if !dir.is_dir() {
+                return Err(FsError::NotADirectory);
+            }
+            ino = self.dir_lookup(&dir, component.as_str())?.ok_or(FsError::NotFound)?.0;
+        }
+        Ok(ino)
+    }
+
+    fn parse(path: &str) -> FsResult<Path> {
+        Ok(Path::parse(path).map_err(|_| FsError::InvalidPath)?.normalize())
+    }
+
+    /// 경로의 inode 번호
+    fn resolve(&mut self, path: &str) -> FsResult<u32> {
+        if !self.mounted {
+            return Err(FsError::InvalidFilesystem);
+        }
+        let path = Self::parse(path)?;
+        self.walk(&path.components)
+    }
+
+    /// 경로의 부모 디렉토리 inode와 마지막 이름
+    fn resolve_parent(&mut self, path: &str) -> FsResult<(u32, String)> {
+        if !self.mounted {
+            return Err(FsError::InvalidFilesystem);
+        }
+        let path = Self::parse(path)?;
+        let (name, parents) = path.components.split_last().ok_or(FsError::InvalidPath)?;
+        let parent = self.walk(parents)?;
+        Ok((parent, name.as_str().to_string()))
+    }
+
+    /// inode의 페이지 캐시 키
+    fn cache_key(&self, ino: u32) -> FileKey {
+        FileKey { fs: self.cache_id, ino: ino as u64 }
+    }
+
+    fn metadata_of(inode: &Inode) -> FileMetadata {
+        FileMetadata {
+            file_type: inode.file_type(),
+            size: inode.size,
+            mode: FileMode::new((inode.mode & PERM_MASK) as u32),
+            created: inode.crtime,
+            modified: inode.mtime,
+            accessed: inode.atime,
+            uid: inode.uid,
+            gid: inode.gid,
+        }
+    }
+
+    /// 디렉토리 `parent`에 파일 또는 디렉토리 생성
+    ///
+    /// 새 항목은 현재 사용자 소유이며 모드는 파일 0644, 디렉토리 0755입니다.
+    fn create_node(&mut self, parent: u32, name: &str, file_type: FileType) -> FsResult<u32> {
+        dir::validate_name(name)?;
+        self.transaction(|fs| {
+            let mut parent_inode = fs.read_inode(parent)?;
+            if !parent_inode.is_dir() {
+                return Err(FsError::NotADirectory);
+            }
+            if fs.dir_lookup(&parent_inode, name)?.is_some() {
+                return Err(FsError::AlreadyExists);
+            }
+            let now = wallclock::now_unix();
+            let ino = fs.alloc_inode()?;
+            let mode = match file_type {
+                FileType::Directory => S_IFDIR | 0o755,
+                _ => S_IFREG | 0o644,
+            };
+            let mut inode = Inode::new(mode, get_current_uid(), get_current_gid(), parent, now);
+            if inode.is_dir() {
+                fs.extend_inode(&mut inode, 1)?;
+                let block = inode.map_block(0).ok_or(FsError::InvalidFilesystem)?;
+                fs.write_meta(block, vec![0u8; BLOCK_SIZE])?;
+                inode.size = BLOCK_SIZE as u64;
+                parent_inode.links += 1;
+            }
+            fs.write_inode(ino, &mut inode)?;
+            fs.dir_insert(&mut parent_inode, name, ino, inode.file_type())?;
+            parent_inode.mtime = now;
+            parent_inode.ctime = now;
+            fs.write_inode(parent, &mut parent_inode)?;
+            Ok(ino)
+        })
+    }
+
+    /// 디렉토리 `parent`에서 항목 삭제 (디렉토리는 비어 있어야 함)
+    fn remove_node(&mut self, parent: u32, name: &str) -> FsResult<()> {
+        self.transaction(|fs| {
+            let mut parent_inode = fs.read_inode(parent)?;
+            if !parent_inode.is_dir() {
+                return Err(FsError::NotADirectory);
+            }
+            let (ino, _) = fs.dir_lookup(&parent_inode, name)?.ok_or(FsError::NotFound)?;
+            let mut inode = fs.read_inode(ino)?;
+            if inode.is_dir() {
+                if !fs.dir_is_empty(&inode)? {
+                    return Err(FsError::Busy);
+                }
+                parent_inode.links -= 1;
+            }
+            fs.dir_remove(&parent_inode, name)?;
+            fs.free_inode_blocks(&mut inode)?;
+            fs.clear_inode(ino)?;
+            fs.free_inode(ino)?;
+            let now = wallclock::now_unix();
+            parent_inode.mtime = now;
+            parent_inode.ctime = now;
+            fs.write_inode(parent, &mut parent_inode)
+        })
+    }
+
+    /// 항목 이름 변경 또는 다른 디렉토리로 이동
+    ///
+    /// 대상 이름이 이미 있으면 `AlreadyExists`, 디렉토리를 자기 하위로 옮기려 하면
+    /// `InvalidPath`를 반환합니다.
+    fn rename_node(&mut self, old_parent: u32, old_name: &str, new_parent: u32, new_name: &str) -> FsResult<()> {
+        dir::validate_name(new_name)?;
+        if old_parent == new_parent && old_name == new_name {
+            return Ok(());
+        }
+        self.transaction(|fs| {
+            let mut old_dir = fs.read_inode(old_parent)?;
+            if !old_dir.is_dir() {
+                return Err(FsError::NotADirectory);
+            }
+            let (ino, _) = fs.dir_lookup(&old_dir, old_name)?.ok_or(FsError::NotFound)?;
+            let mut inode = fs.read_inode(ino)?;
+            let mut new_dir = if new_parent == old_parent { None } else { Some(fs.read_inode(new_parent)?) };
+            let target = new_dir.as_ref().unwrap_or(&old_dir);
+            if !target.is_dir() {
+                return Err(FsError::NotADirectory);
+            }
+            if fs.dir_lookup(target, new_name)?.is_some() {
+                return Err(FsError::AlreadyExists);
+            }
+            if inode.is_dir() && new_dir.is_some() {
+                // 새 부모에서 루트까지 올라가며 자기 자신을 만나면 순환
+                let mut ancestor = new_parent;
+                while ancestor != ROOT_INODE {
+                    if ancestor == ino {
+                        return Err(FsError::InvalidPath);
+                    }
+                    ancestor = fs.read_inode(ancestor)?.parent;
+                }
+            }
+
+            let now = wallclock::now_unix();
+            fs.dir_remove(&old_dir, old_name)?;
+            match new_dir.as_mut() {
+                Some(new_dir) => {
+                    fs.dir_insert(new_dir, new_name, ino, inode.file_type())?;
+                    if inode.is_dir() {
+                        old_dir.links -= 1;
+                        new_dir.links += 1;
+                    }
+                    new_dir.mtime = now;
+                    new_dir.ctime = now;
+                    fs.write_inode(new_parent, new_dir)?;
+                }
+                None => fs.dir_insert(&mut old_dir, new_name, ino, inode.file_type())?,
+            }
+            old_dir.mtime = now;
+            old_dir.ctime = now;
+            fs.write_inode(old_parent, &mut old_dir)?;
+            inode.parent = new_parent;
+            inode.ctime = now;
+            fs.write_inode(ino, &mut inode)
+        })
+    }
+
+    /// 권한 비트 변경 (소유자 또는 root만 가능)
+    ///
+    /// # Arguments
+    /// * `path` - 대상 경로
+    /// * `mode` - 새 권한 비트 (예: `0o640`, 파일 종류 비트는 무시)
+    pub fn chmod(&mut self, path: &str, mode: u32) -> FsResult<()> {
+        let ino = self.resolve(path)?;
+        self.transaction(|fs| {
+            let mut inode = fs.read_inode(ino)?;
+            let uid = get_current_uid();
+            if uid != 0 && uid != inode.uid {
+                return Err(FsError::PermissionDenied);
+            }
+            inode.mode = (inode.mode & !PERM_MASK) | (mode as u16 & PERM_MASK);
+            inode.ctime = wallclock::now_unix();
+            fs.write_inode(ino, &mut inode)
+        })
+    }
+
+    /// 소유자와 그룹 변경 (root만 가능)
+    pub fn chown(&mut self, path: &str, uid: u32, gid: u32) -> FsResult<()> {
+        if get_current_uid() != 0 {
+            return Err(FsError::PermissionDenied);
+        }
+        let ino = self.resolve(path)?;
+        self.transaction(|fs| {
+            let mut inode = fs.read_inode(ino)?;
+            inode.uid = uid;
+            inode.gid = gid;
+            inode.ctime = wallclock::now_unix();
+            fs.write_inode(ino, &mut inode)
+        })
+    }
+
+    /// 슈퍼블록 상태 기록 (마운트/언마운트)
+    fn set_state(&mut self, state: u32) -> FsResult<()> {
+        self.transaction(|fs| {
+            fs.sb.state = state;
+            if state == STATE_MOUNTED {
+                fs.sb.mount_count += 1;
+                fs.sb.mounted_at = wallclock::now_unix();
+            }
+            // 커밋이 최신 슈퍼블록을 다시 넣지만, 빈 트랜잭션은 기록되지 않으므로 명시
+            let sb = fs.sb.encode();
+            fs.write_meta(0, sb)
+        })
+    }
+}
 
 impl FileSystem for Sjfs {
-    fn mount(&mut self) -> FsResult<()> { self.mounted = true; Ok(()) }
-    fn unmount(&mut self) -> FsResult<()> { self.mounted = false; Ok(()) }
-    fn open_file(&mut self, _path: &str) -> FsResult<Box<dyn File>> { Err(FsError::NotFound) }
-    fn create_file(&mut self, _path: &str) -> FsResult<Box<dyn File>> { Err(FsError::IOError) }
-    fn open_dir(&mut self, _path: &str) -> FsResult<Box<dyn Directory>> { Err(FsError::NotFound) }
-    fn create_dir(&mut self, _path: &str) -> FsResult<()> { Err(FsError::IOError) }
-    fn remove(&mut self, _path: &str) -> FsResult<()> { Err(FsError::IOError) }
-    fn rename(&mut self, _old: &str, _new: &str) -> FsResult<()> { Err(FsError::IOError) }
-    fn metadata(&mut self, _path: &str) -> FsResult<FileMetadata> { Err(FsError::NotFound) }
-    fn is_mounted(&self) -> bool { self.mounted }
+    fn mount(&mut self) -> FsResult<()> {
+        if self.mounted {
+            return Err(FsError::Busy);
+        }
+        let replayed = self.replay_journal()?;
+        let mut buf = vec![0u8; BLOCK_SIZE];
+        self.read_block(0, &mut buf)?;
+        self.sb = Superblock::decode(&buf).ok_or(FsError::InvalidFilesystem)?;
+        match replayed {
+            Some(seq) => {
+                crate::log_info!("sjfs: replayed journal transaction {}", seq);
+            }
+            None if self.sb.state == STATE_MOUNTED => {
+                crate::log_warn!("sjfs: '{}' was not cleanly unmounted", self.sb.label());
+            }
+            None => {}
+        }
+
+        self.set_state(STATE_MOUNTED)?;
+        self.mounted = true;
+        Ok(())
+    }
+
+    fn unmount(&mut self) -> FsResult<()> {
+        if !self.mounted {
+            return Err(FsError::InvalidFilesystem);
+        }
+        self.set_state(STATE_CLEAN)?;
+        cache::invalidate_fs(self.cache_id);
+        self.mounted = false;
+        Ok(())
+    }
+
+    fn open_file(&mut self, path: &str) -> FsResult<Box<dyn File>> {
+        let ino = self.resolve(path)?;
+        if self.read_inode(ino)?.is_dir() {
+            return Err(FsError::IsDirectory);
+        }
+        Ok(Box::new(SjfsFile { filesystem: self as *mut Sjfs, ino, offset: 0 }))
+    }
+
+    fn create_file(&mut self, path: &str) -> FsResult<Box<dyn File>> {
+        let (parent, name) = self.resolve_parent(path)?;
+        let ino = self.create_node(parent, &name, FileType::Regular)?;
+        Ok(Box::new(SjfsFile { filesystem: self as *mut Sjfs, ino, offset: 0 }))
+    }
+
+    fn open_dir(&mut self, path: &str) -> FsResult<Box<dyn Directory>> {
+        let ino = self.resolve(path)?;
+        if !self.read_inode(ino)?.is_dir() {
+            return Err(FsError::NotADirectory);
+        }
+        Ok(Box::new(SjfsDirectory { filesystem: self as *mut Sjfs, ino }))
+    }
+
+    fn create_dir(&mut self, path: &str) -> FsResult<()> {
+        let (parent, name) = self.resolve_parent(path)?;
+        self.create_node(parent, &name, FileType::Directory).map(|_| ())
+    }
+
+    fn remove(&mut self, path: &str) -> FsResult<()> {
+        let (parent, name) = self.resolve_parent(path)?;
+        self.remove_node(parent, &name)
+    }
+
+    fn rename(&mut self, old_path: &str, new_path: &str) -> FsResult<()> {
+        let (old_parent, old_name) = self.resolve_parent(old_path)?;
+        let (new_parent, new_name) = self.resolve_parent(new_path)?;
+        self.rename_node(old_parent, &old_name, new_parent, &new_name)
+    }
+
+    fn metadata(&mut self, path: &str) -> FsResult<FileMetadata> {
+        let ino = self.resolve(path)?;
+        let inode = self.read_inode(ino)?;
+        Ok(Self::metadata_of(&inode))
+    }
+
+    fn is_mounted(&self) -> bool {
+        self.mounted
+    }
+
+    fn fs_type(&self) -> &'static str {
+        "sjfs"
+    }
+
+    fn statfs(&mut self) -> FsResult<FsStats> {
+        let g = self.sb.geometry;
+        Ok(FsStats {
+            block_size: BLOCK_SIZE as u64,
+            total_blocks: g.total_blocks,
+            free_blocks: self.sb.free_blocks,
+            total_inodes: g.inode_count as u64,
+            free_inodes: self.sb.free_inodes as u64,
+        })
+    }
+}
+
+/// Sjfs 파일 핸들
+struct SjfsFile {
+    filesystem: *mut Sjfs,
+    ino: u32,
+    offset: Offset,
 }
 
+// Safety: 핸들은 파일시스템 계층이 소유한 Sjfs를 가리키며, 접근은 상위 파일시스템
+// 락으로 직렬화됩니다 (Fat32File과 같은 방식).
+unsafe impl Send for SjfsFile {}
+unsafe impl Sync for SjfsFile {}
+
+impl File for SjfsFile {
+    fn read(&mut self, buf: &mut [u8], offset: Option<Offset>) -> FsResult<usize> {
+        let read_offset = offset.unwrap_or(self.offset);
+        if read_offset < 0 {
+            return Ok(0);
+        }
+        let fs = unsafe { &mut *self.filesystem };
+        let inode = fs.read_inode(self.ino)?;
+        let bytes_read = cache::read(fs.cache_key(self.ino), inode.size, read_offset as u64, buf, |index, page| {
+            fs.read_data(&inode, index * PAGE_SIZE as u64, page).map(|_| ())
+        })?;
+        if offset.is_none() {
+            self.offset = read_offset + bytes_read as Offset;
+        }
+        Ok(bytes_read)
+    }
+
+    fn write(&mut self, buf: &[u8], offset: Option<Offset>) -> FsResult<usize> {
+        let write_offset = offset.unwrap_or(self.offset);
+        if write_offset < 0 {
+            return Err(FsError::InvalidPath);
+        }
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        let fs = unsafe { &mut *self.filesystem };
+        let ino = self.ino;
+        let key = fs.cache_key(ino);
+        let written = fs.transaction(|fs| {
+            let mut inode = fs.read_inode(ino)?;
+            let written = fs.write_data(&mut inode, write_offset as u64, buf)?;
+            let now = wallclock::now_unix();
+            inode.mtime = now;
+            inode.ctime = now;
+            fs.write_inode(ino, &mut inode)?;
+            Ok(written)
+        });
+        let bytes_written = match written {
+            Ok(bytes_written) => bytes_written,
+            Err(e) => {
+                // 데이터 블록은 커밋 전에 기록되므로 일부가 디스크에 남았을 수 있음
+                cache::invalidate(key);
+                return Err(e);
+            }
+        };
+        cache::update(key, write_offset as u64, &buf[..bytes_written]);
+        if offset.is_none() {
+            self.offset = write_offset + bytes_written as Offset;
+        }
+        Ok(bytes_written)
+    }
+
+    fn metadata(&self) -> FsResult<FileMetadata> {
+        let fs = unsafe { &mut *self.filesystem };
+        Ok(Sjfs::metadata_of(&fs.read_inode(self.ino)?))
+    }
+
+    fn size(&self) -> FsResult<u64> {
+        let fs = unsafe { &mut *self.filesystem };
+        Ok(fs.read_inode(self.ino)?.size)
+    }
+
+    fn seek(&mut self, offset: Offset) -> FsResult<Offset> {
+        // 파일 끝 너머로 이동 가능 (다음 쓰기가 사이를 0으로 채움)
+        if offset < 0 {
+            return Err(FsError::InvalidPath);
+        }
+        self.offset = offset;
+        Ok(offset)
+    }
+
+    fn tell(&self) -> FsResult<Offset> {
+        Ok(self.offset)
+    }
+}
+
+/// Sjfs 디렉토리 핸들
+struct SjfsDirectory {
+    filesystem: *mut Sjfs,
+    ino: u32,
+}
+
+unsafe impl Send for SjfsDirectory {}
+unsafe impl Sync for SjfsDirectory {}
+
+impl Directory for SjfsDirectory {
+    fn read_dir(&self) -> FsResult<Vec<(String, FileType)>> {
+        let fs = unsafe { &mut *self.filesystem };
+        let dir = fs.read_inode(self.ino)?;
+        let entries = fs.dir_entries(&dir)?;
+        Ok(entries.into_iter().map(|(name, _, file_type)| (name, file_type)).collect())
+    }
+
+    fn create_file(&mut self, name: &str) -> FsResult<()> {
+        let fs = unsafe { &mut *self.filesystem };
+        fs.create_node(self.ino, name, FileType::Regular).map(|_| ())
+    }
+
+    fn create_dir(&mut self, name: &str) -> FsResult<()> {
+        let fs = unsafe { &mut *self.filesystem };
+        fs.create_node(self.ino, name, FileType::Directory).map(|_| ())
+    }
+
+    fn remove(&mut self, name: &str) -> FsResult<()> {
+        let fs = unsafe { &mut *self.filesystem };
+        fs.remove_node(self.ino, name)
+    }
+
+    fn rename(&mut self, old_name: &str, new_name: &str) -> FsResult<()> {
+        let fs = unsafe { &mut *self.filesystem };
+        fs.rename_node(self.ino, old_name, self.ino, new_name)
+    }
+}
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::drivers::ata::BlockDeviceError;
+    use crate::fs::journal::tests::MemDevice;
+    use alloc::sync::Arc;
+    use core::sync::atomic::{AtomicBool, Ordering};
+    use spin::Mutex;
+
+    /// 섹터 단위로 본 파일시스템 블록 크기
+    const SECTORS_PER_BLOCK: u64 = (BLOCK_SIZE / 512) as u64;
+
+    /// 테스트 디스크 섹터 수 (mkfs 최소 크기)
+    const DISK_SECTORS: u64 = layout::MIN_BLOCKS * SECTORS_PER_BLOCK;
+
+    /// 쓰기 순서를 기록하고, 켜 두면 저널 커밋 블록 직후 전원이 끊긴 것처럼
+    /// 이후 쓰기를 모두 거부하는 디바이스
+    struct TraceDevice {
+        disk: MemDevice,
+        writes: Arc<Mutex<Vec<u64>>>,
+        crash_after_commit: Arc<AtomicBool>,
+        /// 기록된 커밋 블록의 섹터 범위
+        commit: Option<core::ops::Range<u64>>,
+    }
+
+    impl BlockDevice for TraceDevice {
+        fn block_size(&self) -> usize {
+            512
+        }
+
+        fn read_block(&mut self, block: u64, buf: &mut [u8]) -> Result<usize, BlockDeviceError> {
+            self.disk.read_block(block, buf)
+        }
+
+        fn write_block(&mut self, block: u64, buf: &[u8]) -> Result<usize, BlockDeviceError> {
+            if self.crash_after_commit.load(Ordering::Relaxed) {
+                if self.commit.as_ref().is_some_and(|commit| !commit.contains(&block)) {
+                    return Err(BlockDeviceError::WriteError);
+                }
+                if buf.starts_with(b"SJJC") {
+                    self.commit = Some(block..block + SECTORS_PER_BLOCK);
+                }
+            }
+            self.writes.lock().push(block);
+            self.disk.write_block(block, buf)
+        }
+
+        fn num_blocks(&self) -> u64 {
+            self.disk.num_blocks()
+        }
+    }
+
+    /// 포맷된 디스크와 그 위에 연 (마운트 전) 파일시스템
+    fn format() -> (MemDevice, Arc<Mutex<Vec<u64>>>, Arc<AtomicBool>, Sjfs) {
+        let mut disk = MemDevice::new(DISK_SECTORS);
+        mkfs(&mut disk, "test").unwrap();
+        let writes = Arc::new(Mutex::new(Vec::new()));
+        let crash = Arc::new(AtomicBool::new(false));
+        let device = TraceDevice {
+            disk: disk.clone(),
+            writes: writes.clone(),
+            crash_after_commit: crash.clone(),
+            commit: None,
+        };
+        (disk, writes, crash, Sjfs::new(Box::new(device)).unwrap())
+    }
+
+    fn read_all(fs: &mut Sjfs, path: &str) -> Vec<u8> {
+        let mut file = fs.open_file(path).unwrap();
+        let mut buf = vec![0u8; file.size().unwrap() as usize];
+        assert_eq!(file.read(&mut buf, Some(0)).unwrap(), buf.len());
+        buf
+    }
+
+    #[test_case]
+    fn test_sjfs_data_survives_remount() {
+        let (disk, _, _, mut fs) = format();
+        fs.mount().unwrap();
+        fs.create_dir("/docs").unwrap();
+        let data: Vec<u8> = (0..6000u32).map(|i| i as u8).collect();
+        let mut file = fs.create_file("/docs/note").unwrap();
+        assert_eq!(file.write(&data, None).unwrap(), data.len());
+        drop(file);
+        fs.unmount().unwrap();
+
+        let mut fs = Sjfs::new(Box::new(disk)).unwrap();
+        assert_eq!(fs.superblock().state, STATE_CLEAN);
+        fs.mount().unwrap();
+        assert_eq!(read_all(&mut fs, "/docs/note"), data);
+        let meta = fs.metadata("/docs/note").unwrap();
+        assert_eq!(meta.size, data.len() as u64);
+        assert_eq!((meta.uid, meta.gid), (get_current_uid(), get_current_gid()));
+        let entries = fs.open_dir("/docs").unwrap().read_dir().unwrap();
+        assert!(entries.iter().any(|(name, file_type)| name == "note" && *file_type == FileType::Regular));
+    }
+
+    #[test_case]
+    fn test_sjfs_replays_committed_transaction() {
+        let (disk, _, crash, mut fs) = format();
+        fs.mount().unwrap();
+        let mut file = fs.create_file("/a").unwrap();
+
+        // 커밋 블록까지 기록한 뒤 체크포인트 전에 전원 차단
+        crash.store(true, Ordering::Relaxed);
+        assert!(file.write(b"hello journal", None).is_err());
+        drop(file);
+        let journal_sector = fs.sb.geometry.journal_start * SECTORS_PER_BLOCK;
+        assert!(disk.sector(journal_sector).starts_with(b"SJJD"));
+        drop(fs);
+
+        // 재부팅: 마운트가 저널을 재생하고 디스크립터를 지움
+        let mut fs = Sjfs::new(Box::new(disk.clone())).unwrap();
+        fs.mount().unwrap();
+        assert_eq!(read_all(&mut fs, "/a"), b"hello journal");
+        assert_eq!(disk.sector(journal_sector), [0u8; 512]);
+    }
+
+    #[test_case]
+    fn test_sjfs_writes_data_before_commit() {
+        let (_, writes, _, mut fs) = format();
+        fs.mount().unwrap();
+        let mut file = fs.create_file("/ordered").unwrap();
+        writes.lock().clear();
+        file.write(&[0x5A; BLOCK_SIZE], None).unwrap();
+        drop(file);
+
+        let ino = fs.resolve("/ordered").unwrap();
+        let data_block = fs.read_inode(ino).unwrap().map_block(0).unwrap();
+        let data_sectors = data_block * SECTORS_PER_BLOCK..(data_block + 1) * SECTORS_PER_BLOCK;
+        let g = fs.sb.geometry;
+        let journal_sectors = g.journal_start * SECTORS_PER_BLOCK..(g.journal_start + g.journal_blocks as u64) * SECTORS_PER_BLOCK;
+
+        // 데이터는 저널을 거치지 않고 트랜잭션 기록보다 먼저 제자리에 한 번만 쓰임
+        let writes = writes.lock();
+        let first_journal = writes.iter().position(|s| journal_sectors.contains(s)).unwrap();
+        let data_writes: Vec<usize> = (0..writes.len()).filter(|&i| data_sectors.contains(&writes[i])).collect();
+        assert_eq!(data_writes.len(), SECTORS_PER_BLOCK as usize);
+        assert!(data_writes.iter().all(|&i| i < first_journal));
+        drop(writes);
+        assert_eq!(read_all(&mut fs, "/ordered"), vec![0x5A; BLOCK_SIZE]);
+    }
+}
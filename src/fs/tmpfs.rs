@@ -0,0 +1,666 @@
+//! tmpfs: 메모리 기반 파일시스템
+//!
+//! 디스크가 없어도 쓸 수 있는 임시 저장 공간입니다. 모든 inode와 파일 데이터는 커널 힙에
+//! 있으므로 언마운트하면 내용이 사라집니다.
+//!
+//! 인스턴스마다 데이터 크기(`size`)와 inode 수(`nr_inodes`) 한도가 있고, 모든 인스턴스가
+//! 쥔 데이터 양은 전역으로 집계됩니다(`total_bytes`). 데이터 양은 파일 길이가 아니라 실제로
+//! 잡아 둔 버퍼 용량으로 셉니다.
+//!
+//! 스왑이 없으므로 tmpfs 데이터는 회수할 수 없습니다. 메모리 압박 시 내보낼 곳이 없고
+//! OOM killer가 스레드를 종료해도 줄지 않으므로, 한도는 쓰기 시점에 강제합니다. 버퍼를
+//! 늘리는 쓰기는 한도 안이라도 재할당 동안의 힙 여유가 `HEAP_RESERVE` 아래로 떨어지게 되면
+//! `OutOfSpace`로 거부해 커널 할당이 실패하지 않게 합니다.
+
+use crate::fs::path::{Path, PathComponent};
+use crate::fs::vfs::{Directory, File, FileMetadata, FileMode, FileSystem, FileType, FsError, FsResult, FsStats, Offset};
+use crate::security::user::{get_current_gid, get_current_uid};
+use crate::time::wallclock;
+use alloc::boxed::Box;
+use alloc::collections::BTreeMap;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+/// 기본 데이터 한도 (바이트)
+pub const DEFAULT_SIZE: usize = 512 * 1024;
+/// 기본 inode 한도
+pub const DEFAULT_NR_INODES: usize = 1024;
+/// tmpfs 쓰기 후에도 남겨둘 최소 힙 여유 (바이트)
+const HEAP_RESERVE: usize = 128 * 1024;
+/// statfs가 보고하는 할당 단위
+const BLOCK_SIZE: usize = 4096;
+/// 이름 최대 길이 (바이트)
+const MAX_NAME_LEN: usize = 255;
+/// 루트 디렉토리 inode 번호
+const ROOT_INODE: u64 = 1;
+
+/// 모든 tmpfs 인스턴스가 쥔 파일 데이터 (바이트)
+static TOTAL_BYTES: AtomicUsize = AtomicUsize::new(0);
+
+/// 모든 tmpfs 인스턴스가 힙에 쥔 파일 데이터 양 (바이트)
+///
+/// 회수할 수 없는 메모리이므로 OOM 판단 시 함께 보고합니다.
+pub fn total_bytes() -> usize {
+    TOTAL_BYTES.load(Ordering::Relaxed)
+}
+
+/// 마운트 옵션
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TmpfsOptions {
+    /// 파일 데이터 한도 (바이트)
+    pub size: usize,
+    /// inode 한도 (루트 포함)
+    pub nr_inodes: usize,
+}
+
+impl Default for TmpfsOptions {
+    fn default() -> Self {
+        Self { size: DEFAULT_SIZE, nr_inodes: DEFAULT_NR_INODES }
+    }
+}
+
+impl TmpfsOptions {
+    /// `mount -o` 옵션 목록 해석
+    ///
+    /// # Arguments
+    /// * `options` - `size=<n>[k|m]`, `nr_inodes=<n>` 형식의 옵션들
+    pub fn parse(options: &[&str]) -> FsResult<Self> {
+        let mut parsed = Self::default();
+        for option in options {
+            let (key, value) = option.split_once('=').ok_or(FsError::InvalidPath)?;
+            match key {
+                "size" => parsed.size = parse_size(value)?,
+                "nr_inodes" => parsed.nr_inodes = value.parse().map_err(|_| FsError::InvalidPath)?,
+                _ => return Err(FsError::InvalidPath),
+            }
+        }
+        if parsed.nr_inodes == 0 {
+            return Err(FsError::InvalidPath);
+        }
+        Ok(parsed)
+    }
+}
+
+/// `64k`, `1m` 같은 크기 해석
+fn parse_size(value: &str) -> FsResult<usize> {
+    let (digits, unit) = match value.as_bytes().last() {
+        Some(b'k' | b'K') => (&value[..value.len() - 1], 1024),
+        Some(b'm' | b'M') => (&value[..value.len() - 1], 1024 * 1024),
+        _ => (value, 1),
+    };
+    let number: usize = digits.parse().map_err(|_| FsError::InvalidPath)?;
+    number.checked_mul(unit).ok_or(FsError::InvalidPath)
+}
+
+fn validate_name(name: &str) -> FsResult<()> {
+    if name.is_empty() || name == "." || name == ".." || name.len() > MAX_NAME_LEN {
+        return Err(FsError::InvalidPath);
+    }
+    if name.bytes().any(|b| b == b'/' || b == 0) {
+        return Err(FsError::InvalidPath);
+    }
+    Ok(())
+}
+
+/// inode 내용
+enum NodeData {
+    File(Vec<u8>),
+    Dir(BTreeMap<String, u64>),
+}
+
+/// tmpfs inode
+struct Node {
+    data: NodeData,
+    /// 부모 디렉토리 (루트는 자기 자신)
+    parent: u64,
+    mode: u32,
+    uid: u32,
+    gid: u32,
+    created: u64,
+    modified: u64,
+    accessed: u64,
+}
+
+impl Node {
+    fn new(data: NodeData, parent: u64, mode: u32, now: u64) -> Self {
+        Self {
+            data,
+            parent,
+            mode,
+            uid: get_current_uid(),
+            gid: get_current_gid(),
+            created: now,
+            modified: now,
+            accessed: now,
+        }
+    }
+
+    fn file_type(&self) -> FileType {
+        match self.data {
+            NodeData::File(_) => FileType::Regular,
+            NodeData::Dir(_) => FileType::Directory,
+        }
+    }
+
+    fn size(&self) -> u64 {
+        match &self.data {
+            NodeData::File(data) => data.len() as u64,
+            NodeData::Dir(entries) => entries.len() as u64,
+        }
+    }
+
+    fn metadata(&self) -> FileMetadata {
+        FileMetadata {
+            file_type: self.file_type(),
+            size: self.size(),
+            mode: FileMode::new(self.mode),
+            created: self.created,
+            modified: self.modified,
+            accessed: self.accessed,
+            uid: self.uid,
+            gid: self.gid,
+        }
+    }
+
+    fn entries(&self) -> FsResult<&BTreeMap<String, u64>> {
+        match &self.data {
+            NodeData::Dir(entries) => Ok(entries),
+            NodeData::File(_) => Err(FsError::NotADirectory),
+        }
+    }
+
+    fn entries_mut(&mut self) -> FsResult<&mut BTreeMap<String, u64>> {
+        match &mut self.data {
+            NodeData::Dir(entries) => Ok(entries),
+            NodeData::File(_) => Err(FsError::NotADirectory),
+        }
+    }
+}
+
+/// tmpfs 인스턴스
+pub struct TmpFs {
+    nodes: BTreeMap<u64, Node>,
+    next_ino: u64,
+    /// 이 인스턴스가 쥔 파일 버퍼 용량 (바이트)
+    used: usize,
+    options: TmpfsOptions,
+    mounted: bool,
+}
+
+impl TmpFs {
+    /// 빈 tmpfs 생성 (루트 디렉토리만 있음, 모드 1777)
+    pub fn new(options: TmpfsOptions) -> Self {
+        let mut nodes = BTreeMap::new();
+        let root = Node::new(NodeData::Dir(BTreeMap::new()), ROOT_INODE, 0o1777, wallclock::now_unix());
+        nodes.insert(ROOT_INODE, root);
+        Self { nodes, next_ino: ROOT_INODE + 1, used: 0, options, mounted: false }
+    }
+
+    /// 마운트 옵션
+    pub fn options(&self) -> TmpfsOptions {
+        self.options
+    }
+
+    fn node(&self, ino: u64) -> FsResult<&Node> {
+        self.nodes.get(&ino).ok_or(FsError::NotFound)
+    }
+
+    fn node_mut(&mut self, ino: u64) -> FsResult<&mut Node> {
+        self.nodes.get_mut(&ino).ok_or(FsError::NotFound)
+    }
+
+    /// 경로 구성요소를 따라 inode 찾기
+    fn walk(&self, components: &[PathComponent]) -> FsResult<u64> {
+        let mut ino = ROOT_INODE;
+        for component in components {
+            ino = *self.node(ino)?.entries()?.get(component.as_str()).ok_or(FsError::NotFound)?;
+        }
+        Ok(ino)
+    }
+
+    fn parse(path: &str) -> FsResult<Path> {
+        Ok(Path::parse(path).map_err(|_| FsError::InvalidPath)?.normalize())
+    }
+
+    /// 경로의 inode 번호
+    fn resolve(&self, path: &str) -> FsResult<u64> {
+        if !self.mounted {
+            return Err(FsError::InvalidFilesystem);
+        }
+        let path = Self::parse(path)?;
+        self.walk(&path.components)
+    }
+
+    /// 경로의 부모 디렉토리 inode와 마지막 이름
+    fn resolve_parent(&self, path: &str) -> FsResult<(u64, String)> {
+        if !self.mounted {
+            return Err(FsError::InvalidFilesystem);
+        }
+        let path = Self::parse(path)?;
+        let (name, parents) = path.components.split_last().ok_or(FsError::InvalidPath)?;
+        let parent = self.walk(parents)?;
+        Ok((parent, name.as_str().to_string()))
+    }
+
+    /// `HEAP_RESERVE`를 남기고 지금 새로 할당할 수 있는 힙 메모리
+    fn heap_available() -> usize {
+        crate::memory::heap::heap_free().saturating_sub(HEAP_RESERVE)
+    }
+
+    /// 남은 데이터 공간 (인스턴스 한도와 힙 여유 중 작은 쪽)
+    fn available(&self) -> usize {
+        self.options.size.saturating_sub(self.used).min(Self::heap_available())
+    }
+
+    /// 파일 버퍼 용량 `bytes`만큼 사용 기록
+    fn charge(&mut self, bytes: usize) {
+        self.used += bytes;
+        TOTAL_BYTES.fetch_add(bytes, Ordering::Relaxed);
+    }
+
+    fn release(&mut self, bytes: usize) {
+        self.used -= bytes;
+        TOTAL_BYTES.fetch_sub(bytes, Ordering::Relaxed);
+    }
+
+    /// 디렉토리 `parent`에 파일 또는 디렉토리 생성
+    ///
+    /// 새 항목은 현재 사용자 소유이며 모드는 파일 0644, 디렉토리 0755입니다.
+    fn create_node(&mut self, parent: u64, name: &str, file_type: FileType) -> FsResult<u64> {
+        validate_name(name)?;
+        if self.node(parent)?.entries()?.contains_key(name) {
+            return Err(FsError::AlreadyExists);
+        }
+        if self.nodes.len() >= self.options.nr_inodes {
+            return Err(FsError::OutOfSpace);
+        }
+        let now = wallclock::now_unix();
+        let node = match file_type {
+            FileType::Directory => Node::new(NodeData::Dir(BTreeMap::new()), parent, 0o755, now),
+            _ => Node::new(NodeData::File(Vec::new()), parent, 0o644, now),
+        };
+        let ino = self.next_ino;
+        self.next_ino += 1;
+        self.nodes.insert(ino, node);
+        let parent_node = self.node_mut(parent)?;
+        parent_node.entries_mut()?.insert(name.to_string(), ino);
+        parent_node.modified = now;
+        Ok(ino)
+    }
+
+    /// 디렉토리 `parent`에서 항목 삭제 (디렉토리는 비어 있어야 함)
+    fn remove_node(&mut self, parent: u64, name: &str) -> FsResult<()> {
+        let ino = *self.node(parent)?.entries()?.get(name).ok_or(FsError::NotFound)?;
+        let freed = match &self.node(ino)?.data {
+            NodeData::Dir(entries) if !entries.is_empty() => return Err(FsError::Busy),
+            NodeData::Dir(_) => 0,
+            NodeData::File(data) => data.capacity(),
+        };
+        let parent_node = self.node_mut(parent)?;
+        parent_node.entries_mut()?.remove(name);
+        parent_node.modified = wallclock::now_unix();
+        self.nodes.remove(&ino);
+        self.release(freed);
+        Ok(())
+    }
+
+    /// 항목 이름 변경 또는 다른 디렉토리로 이동
+    ///
+    /// 대상 이름이 이미 있으면 `AlreadyExists`, 디렉토리를 자기 하위로 옮기려 하면
+    /// `InvalidPath`를 반환합니다.
+    fn rename_node(&mut self, old_parent: u64, old_name: &str, new_parent: u64, new_name: &str) -> FsResult<()> {
+        validate_name(new_name)?;
+        let ino = *self.node(old_parent)?.entries()?.get(old_name).ok_or(FsError::NotFound)?;
+        if old_parent == new_parent && old_name == new_name {
+            return Ok(());
+        }
+        if self.node(new_parent)?.entries()?.contains_key(new_name) {
+            return Err(FsError::AlreadyExists);
+        }
+        // 새 부모에서 루트까지 올라가며 자기 자신을 만나면 순환
+        let mut ancestor = new_parent;
+        while ancestor != ROOT_INODE {
+            if ancestor == ino {
+                return Err(FsError::InvalidPath);
+            }
+            ancestor = self.node(ancestor)?.parent;
+        }
+
+        let now = wallclock::now_unix();
+        let old_dir = self.node_mut(old_parent)?;
+        old_dir.entries_mut()?.remove(old_name);
+        old_dir.modified = now;
+        let new_dir = self.node_mut(new_parent)?;
+        new_dir.entries_mut()?.insert(new_name.to_string(), ino);
+        new_dir.modified = now;
+        self.node_mut(ino)?.parent = new_parent;
+        Ok(())
+    }
+
+    /// 파일 `ino`의 `offset`부터 읽기
+    fn read_data(&mut self, ino: u64, offset: u64, buf: &mut [u8]) -> FsResult<usize> {
+        let node = self.node_mut(ino)?;
+        let NodeData::File(data) = &node.data else {
+            return Err(FsError::IsDirectory);
+        };
+        let start = (offset as usize).min(data.len());
+        let count = buf.len().min(data.len() - start);
+        buf[..count].copy_from_slice(&data[start..start + count]);
+        node.accessed = wallclock::now_unix();
+        Ok(count)
+    }
+
+    /// 파일 `ino`의 `offset`부터 쓰기 (파일 끝 너머면 사이를 0으로 채움)
+    fn write_data(&mut self, ino: u64, offset: u64, buf: &[u8]) -> FsResult<usize> {
+        let offset = usize::try_from(offset).map_err(|_| FsError::OutOfSpace)?;
+        let end = offset.checked_add(buf.len()).ok_or(FsError::OutOfSpace)?;
+        let capacity = match &self.node(ino)?.data {
+            NodeData::File(data) => data.capacity(),
+            NodeData::Dir(_) => return Err(FsError::IsDirectory),
+        };
+        if end > capacity {
+            self.grow(ino, capacity, end)?;
+        }
+
+        let node = self.nodes.get_mut(&ino).ok_or(FsError::NotFound)?;
+        let NodeData::File(data) = &mut node.data else {
+            return Err(FsError::IsDirectory);
+        };
+        if end > data.len() {
+            data.resize(end, 0);
+        }
+        data[offset..end].copy_from_slice(buf);
+        node.modified = wallclock::now_unix();
+        Ok(buf.len())
+    }
+
+    /// 파일 `ino`의 버퍼를 최소 `end` 바이트로 늘리기
+    ///
+    /// 용량을 두 배씩 늘려 이어 쓰기가 분할 상환 O(1)이 되게 하되, 인스턴스 한도를 넘는
+    /// 만큼은 잡지 않습니다. 재할당 중에는 옛 버퍼와 새 버퍼가 함께 힙에 있으므로 새 버퍼
+    /// 전체가 힙 여유 안에 들어가야 합니다. 두 배가 들어가지 않으면 `end`만큼만 잡습니다.
+    fn grow(&mut self, ino: u64, capacity: usize, end: usize) -> FsResult<()> {
+        let quota = capacity + self.options.size.saturating_sub(self.used);
+        if end > quota {
+            return Err(FsError::OutOfSpace);
+        }
+        let heap = Self::heap_available();
+        let mut target = end.max(capacity.saturating_mul(2)).min(quota);
+        if target > heap {
+            target = end;
+        }
+        if target > heap {
+            return Err(FsError::OutOfSpace);
+        }
+
+        let node = self.nodes.get_mut(&ino).ok_or(FsError::NotFound)?;
+        let NodeData::File(data) = &mut node.data else {
+            return Err(FsError::IsDirectory);
+        };
+        // 힙 여유 추정보다 실제 할당이 먼저 실패할 수도 있음
+        data.try_reserve_exact(target - data.len()).map_err(|_| FsError::OutOfSpace)?;
+        let grown = data.capacity() - capacity;
+        self.charge(grown);
+        Ok(())
+    }
+}
+
+impl Drop for TmpFs {
+    fn drop(&mut self) {
+        TOTAL_BYTES.fetch_sub(self.used, Ordering::Relaxed);
+    }
+}
+
+impl FileSystem for TmpFs {
+    fn mount(&mut self) -> FsResult<()> {
+        if self.mounted {
+            return Err(FsError::Busy);
+        }
+        self.mounted = true;
+        Ok(())
+    }
+
+    fn unmount(&mut self) -> FsResult<()> {
+        if !self.mounted {
+            return Err(FsError::InvalidFilesystem);
+        }
+        self.mounted = false;
+        Ok(())
+    }
+
+    fn open_file(&mut self, path: &str) -> FsResult<Box<dyn File>> {
+        let ino = self.resolve(path)?;
+        if self.node(ino)?.file_type() == FileType::Directory {
+            return Err(FsError::IsDirectory);
+        }
+        Ok(Box::new(TmpfsFile { filesystem: self as *mut TmpFs, ino, offset: 0 }))
+    }
+
+    fn create_file(&mut self, path: &str) -> FsResult<Box<dyn File>> {
+        let (parent, name) = self.resolve_parent(path)?;
+        let ino = self.create_node(parent, &name, FileType::Regular)?;
+        Ok(Box::new(TmpfsFile { filesystem: self as *mut TmpFs, ino, offset: 0 }))
+    }
+
+    fn open_dir(&mut self, path: &str) -> FsResult<Box<dyn Directory>> {
+        let ino = self.resolve(path)?;
+        self.node(ino)?.entries()?;
+        Ok(Box::new(TmpfsDirectory { filesystem: self as *mut TmpFs, ino }))
+    }
+
+    fn create_dir(&mut self, path: &str) -> FsResult<()> {
+        let (parent, name) = self.resolve_parent(path)?;
+        self.create_node(parent, &name, FileType::Directory).map(|_| ())
+    }
+
+    fn remove(&mut self, path: &str) -> FsResult<()> {
+        let (parent, name) = self.resolve_parent(path)?;
+        self.remove_node(parent, &name)
+    }
+
+    fn rename(&mut self, old_path: &str, new_path: &str) -> FsResult<()> {
+        let (old_parent, old_name) = self.resolve_parent(old_path)?;
+        let (new_parent, new_name) = self.resolve_parent(new_path)?;
+        self.rename_node(old_parent, &old_name, new_parent, &new_name)
+    }
+
+    fn metadata(&mut self, path: &str) -> FsResult<FileMetadata> {
+        let ino = self.resolve(path)?;
+        Ok(self.node(ino)?.metadata())
+    }
+
+    fn is_mounted(&self) -> bool {
+        self.mounted
+    }
+
+    fn fs_type(&self) -> &'static str {
+        "tmpfs"
+    }
+
+    fn statfs(&mut self) -> FsResult<FsStats> {
+        // 힙이 부족하면 한도보다 적게 쓸 수 있으므로 전체 크기도 그만큼 줄여서 보고
+        let total = (self.used + self.available()) as u64;
+        Ok(FsStats {
+            block_size: BLOCK_SIZE as u64,
+            total_blocks: total.div_ceil(BLOCK_SIZE as u64),
+            free_blocks: (self.available() / BLOCK_SIZE) as u64,
+            total_inodes: self.options.nr_inodes as u64,
+            free_inodes: self.options.nr_inodes.saturating_sub(self.nodes.len()) as u64,
+        })
+    }
+}
+
+/// tmpfs 파일 핸들
+struct TmpfsFile {
+    filesystem: *mut TmpFs,
+    ino: u64,
+    offset: Offset,
+}
+
+// Safety: 핸들은 파일시스템 계층이 소유한 TmpFs를 가리키며, 접근은 상위 파일시스템
+// 락으로 직렬화됩니다 (SjfsFile과 같은 방식).
+unsafe impl Send for TmpfsFile {}
+unsafe impl Sync for TmpfsFile {}
+
+impl File for TmpfsFile {
+    fn read(&mut self, buf: &mut [u8], offset: Option<Offset>) -> FsResult<usize> {
+        let read_offset = offset.unwrap_or(self.offset);
+        if read_offset < 0 {
+            return Ok(0);
+        }
+        let fs = unsafe { &mut *self.filesystem };
+        let bytes_read = fs.read_data(self.ino, read_offset as u64, buf)?;
+        if offset.is_none() {
+            self.offset = read_offset + bytes_read as Offset;
+        }
+        Ok(bytes_read)
+    }
+
+    fn write(&mut self, buf: &[u8], offset: Option<Offset>) -> FsResult<usize> {
+        let write_offset = offset.unwrap_or(self.offset);
+        if write_offset < 0 {
+            return Err(FsError::InvalidPath);
+        }
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        let fs = unsafe { &mut *self.filesystem };
+        let bytes_written = fs.write_data(self.ino, write_offset as u64, buf)?;
+        if offset.is_none() {
+            self.offset = write_offset + bytes_written as Offset;
+        }
+        Ok(bytes_written)
+    }
+
+    fn metadata(&self) -> FsResult<FileMetadata> {
+        let fs = unsafe { &*self.filesystem };
+        Ok(fs.node(self.ino)?.metadata())
+    }
+
+    fn size(&self) -> FsResult<u64> {
+        let fs = unsafe { &*self.filesystem };
+        Ok(fs.node(self.ino)?.size())
+    }
+
+    fn seek(&mut self, offset: Offset) -> FsResult<Offset> {
+        // 파일 끝 너머로 이동 가능 (다음 쓰기가 사이를 0으로 채움)
+        if offset < 0 {
+            return Err(FsError::InvalidPath);
+        }
+        self.offset = offset;
+        Ok(offset)
+    }
+
+    fn tell(&self) -> FsResult<Offset> {
+        Ok(self.offset)
+    }
+}
+
+/// tmpfs 디렉토리 핸들
+struct TmpfsDirectory {
+    filesystem: *mut TmpFs,
+    ino: u64,
+}
+
+unsafe impl Send for TmpfsDirectory {}
+unsafe impl Sync for TmpfsDirectory {}
+
+impl Directory for TmpfsDirectory {
+    fn read_dir(&self) -> FsResult<Vec<(String, FileType)>> {
+        let fs = unsafe { &*self.filesystem };
+        fs.node(self.ino)?
+            .entries()?
+            .iter()
+            .map(|(name, &ino)| Ok((name.clone(), fs.node(ino)?.file_type())))
+            .collect()
+    }
+
+    fn create_file(&mut self, name: &str) -> FsResult<()> {
+        let fs = unsafe { &mut *self.filesystem };
+        fs.create_node(self.ino, name, FileType::Regular).map(|_| ())
+    }
+
+    fn create_dir(&mut self, name: &str) -> FsResult<()> {
+        let fs = unsafe { &mut *self.filesystem };
+        fs.create_node(self.ino, name, FileType::Directory).map(|_| ())
+    }
+
+    fn remove(&mut self, name: &str) -> FsResult<()> {
+        let fs = unsafe { &mut *self.filesystem };
+        fs.remove_node(self.ino, name)
+    }
+
+    fn rename(&mut self, old_name: &str, new_name: &str) -> FsResult<()> {
+        let fs = unsafe { &mut *self.filesystem };
+        fs.rename_node(self.ino, old_name, self.ino, new_name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test_case]
+    fn test_tmpfs_options() {
+        assert_eq!(TmpfsOptions::parse(&[]), Ok(TmpfsOptions::default()));
+        let options = TmpfsOptions::parse(&["size=64k", "nr_inodes=16"]).unwrap();
+        assert_eq!(options, TmpfsOptions { size: 64 * 1024, nr_inodes: 16 });
+        assert_eq!(TmpfsOptions::parse(&["size=2M"]).map(|o| o.size), Ok(2 * 1024 * 1024));
+        assert_eq!(TmpfsOptions::parse(&["size=big"]), Err(FsError::InvalidPath));
+        assert_eq!(TmpfsOptions::parse(&["mode=1777"]), Err(FsError::InvalidPath));
+    }
+
+    #[test_case]
+    fn test_tmpfs_files_and_limits() {
+        let mut fs = TmpFs::new(TmpfsOptions { size: 8, nr_inodes: 4 });
+        fs.mount().unwrap();
+        fs.create_dir("/a").unwrap();
+        let mut file = fs.create_file("/a/f").unwrap();
+        assert_eq!(file.write(b"hello", None), Ok(5));
+        // 한도(8바이트)를 넘는 쓰기는 거부되고 내용은 그대로
+        assert_eq!(file.write(b"world", None), Err(FsError::OutOfSpace));
+        let mut buf = [0u8; 8];
+        assert_eq!(file.read(&mut buf, Some(0)), Ok(5));
+        assert_eq!(&buf[..5], b"hello");
+        drop(file);
+
+        fs.create_dir("/b").unwrap();
+        fs.rename("/a/f", "/b/g").unwrap();
+        assert_eq!(fs.metadata("/b/g").map(|m| m.size), Ok(5));
+        fs.rename("/b", "/a/b").unwrap();
+        assert_eq!(fs.rename("/a", "/a/b/c"), Err(FsError::InvalidPath));
+        // 루트 + a, b, g로 inode 한도(4)에 도달
+        assert_eq!(fs.create_file("/c").err(), Some(FsError::OutOfSpace));
+        assert_eq!(fs.remove("/a"), Err(FsError::Busy));
+        fs.remove("/a/b/g").unwrap();
+        assert_eq!(fs.used, 0);
+    }
+
+    #[test_case]
+    fn test_tmpfs_append_growth() {
+        let mut fs = TmpFs::new(TmpfsOptions { size: 100, nr_inodes: 4 });
+        fs.mount().unwrap();
+        let mut file = fs.create_file("/f").unwrap();
+        // 한 바이트씩 이어 쓰면 용량이 두 배씩 늘어 재할당이 로그 횟수로 줄어듦
+        let mut reallocations = 0;
+        let mut last = 0;
+        for _ in 0..64 {
+            assert_eq!(file.write(b"x", None), Ok(1));
+            if fs.used != last {
+                reallocations += 1;
+                last = fs.used;
+            }
+        }
+        assert_eq!(fs.used, 64);
+        assert_eq!(reallocations, 7);
+        // 다음 두 배(128)는 한도(100)로 잘리고, 한도를 넘는 쓰기는 거부
+        assert_eq!(file.write(&[0; 30], None), Ok(30));
+        assert_eq!(fs.used, 100);
+        assert_eq!(file.write(&[0; 7], None), Err(FsError::OutOfSpace));
+        assert_eq!(file.size(), Ok(94));
+        drop(file);
+        fs.remove("/f").unwrap();
+        assert_eq!(fs.used, 0);
+    }
+}
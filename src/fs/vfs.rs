@@ -61,6 +61,16 @@ pub struct FileMetadata {
     pub gid: u32,        // 소유자 그룹 ID
 }
 
+/// 파일시스템 용량 정보 (df)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FsStats {
+    pub block_size: u64,    // 할당 단위 (바이트)
+    pub total_blocks: u64,  // 전체 블록 수
+    pub free_blocks: u64,   // 빈 블록 수
+    pub total_inodes: u64,  // 전체 inode 수 (개념이 없으면 0)
+    pub free_inodes: u64,   // 빈 inode 수
+}
+
 /// 파일 오프셋 타입
 pub type Offset = i64;
 
@@ -79,6 +89,7 @@ pub enum FsError {
     InvalidFilesystem,  // 잘못된 파일시스템
     OutOfSpace,         // 공간 부족
     Busy,               // 파일시스템이 사용 중
+    ReadOnly,           // 읽기 전용 마운트
 }
 
 /// 파일시스템 결과 타입
@@ -117,6 +128,13 @@ pub trait File: Send + Sync {
     
     /// 현재 읽기/쓰기 위치 가져오기
     fn tell(&self) -> FsResult<Offset>;
+    
+    /// 이 파일이 가리키는 블록 디바이스 이름 (디바이스 파일이 아니면 `None`)
+    ///
+    /// 마운트 계층은 파일시스템 락을 잡기 전에 이 이름으로 마운트된 디스크인지 확인합니다.
+    fn block_device(&self) -> Option<&str> {
+        None
+    }
 }
 
 /// 디렉토리 핸들
@@ -169,7 +187,27 @@ pub trait FileSystem: Send + Sync {
     /// 파일/디렉토리 메타데이터 가져오기
     fn metadata(&mut self, path: &str) -> FsResult<FileMetadata>;
     
+    /// 심볼릭 링크 생성 (`path`가 `target`을 가리킴, 지원하지 않으면 `PermissionDenied`)
+    fn symlink(&mut self, _target: &str, _path: &str) -> FsResult<()> {
+        Err(FsError::PermissionDenied)
+    }
+    
+    /// 심볼릭 링크의 대상 경로 읽기 (링크가 아니면 `InvalidPath`)
+    fn read_link(&mut self, _path: &str) -> FsResult<String> {
+        Err(FsError::InvalidPath)
+    }
+    
     /// 파일시스템이 마운트되어 있는지 확인
     fn is_mounted(&self) -> bool;
+    
+    /// 파일시스템 종류 이름 (예: "fat32")
+    fn fs_type(&self) -> &'static str {
+        "unknown"
+    }
+    
+    /// 용량 정보 가져오기
+    fn statfs(&mut self) -> FsResult<FsStats> {
+        Err(FsError::IOError)
+    }
 }
 
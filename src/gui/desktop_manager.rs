@@ -4,6 +4,7 @@
 
 use super::desktop::{self, LauncherAction, AppIcon};
 use super::applications::*;
+use super::notifications;
 use crate::drivers::mouse::MouseEvent;
 use spin::Mutex;
 use alloc::vec::Vec;
@@ -43,6 +44,7 @@ impl DesktopManager {
     /// 데스크톱 관리자 초기화
     pub fn init(&mut self) {
         desktop::init();
+        notifications::init();
     }
 
     /// 애플리케이션 실행
@@ -118,9 +120,18 @@ impl DesktopManager {
                 }
             }
         }
+        // 3. 알림 토스트는 항상 맨 위에
+        notifications::render();
         self.dirty = false;
     }
 
+    /// 커널 이벤트 알림 갱신
+    pub fn poll_notifications(&mut self, now_ms: u64) {
+        if notifications::poll(now_ms) {
+            self.dirty = true;
+        }
+    }
+
     /// 데스크톱 표시 여부 설정
     pub fn set_show_desktop(&mut self, show: bool) {
         self.show_desktop = show;
@@ -147,6 +158,11 @@ pub fn render() {
     DESKTOP_MANAGER.lock().render();
 }
 
+/// 커널 이벤트 알림 갱신
+pub fn poll_notifications(now_ms: u64) {
+    DESKTOP_MANAGER.lock().poll_notifications(now_ms);
+}
+
 /// 데스크톱 표시 여부 설정
 pub fn set_show_desktop(show: bool) {
     DESKTOP_MANAGER.lock().set_show_desktop(show);
@@ -8,6 +8,7 @@ pub mod compositor;
 pub mod applications;
 pub mod desktop;
 pub mod desktop_manager;
+pub mod notifications;
 
 pub use window::Window;
 pub use widget::{Widget, Button, TextBox};
@@ -0,0 +1,118 @@
+//! 데스크톱 알림
+//!
+//! 커널 이벤트 버스를 구독해 USB 디바이스 연결, AC 전원 분리, 열 조치 같은 이벤트를
+//! 화면 우측 상단에 잠시 표시되는 토스트로 보여줍니다.
+
+use crate::drivers::font;
+use crate::drivers::framebuffer::{self, Color};
+use crate::kernel::uevent::{self, EventFilter, KernelEvent, Subscription};
+use crate::power::battery::BatteryStatus;
+use crate::power::temps::ThermalAction;
+use alloc::collections::VecDeque;
+use alloc::format;
+use alloc::string::String;
+use spin::Mutex;
+
+/// 토스트 표시 시간 (밀리초)
+const TOAST_DURATION_MS: u64 = 5000;
+/// 동시에 표시할 최대 토스트 수
+const MAX_TOASTS: usize = 4;
+/// 배터리 부족 알림 기준 (퍼센트)
+const LOW_BATTERY_PERCENT: u8 = 20;
+
+const TOAST_WIDTH: usize = 300;
+const TOAST_HEIGHT: usize = 28;
+const TOAST_MARGIN: usize = 10;
+
+/// 표시 중인 알림
+struct Toast {
+    text: String,
+    color: Color,
+    expires_ms: u64,
+}
+
+struct Notifications {
+    subscription: Option<Subscription>,
+    toasts: VecDeque<Toast>,
+}
+
+static NOTIFICATIONS: Mutex<Notifications> = Mutex::new(Notifications {
+    subscription: None,
+    toasts: VecDeque::new(),
+});
+
+/// 알림 구독 시작
+pub fn init() {
+    let mut notifications = NOTIFICATIONS.lock();
+    if notifications.subscription.is_none() {
+        notifications.subscription = Some(uevent::subscribe(EventFilter::ALL));
+    }
+}
+
+/// 이벤트를 알림 문구와 강조 색으로 변환 (알릴 필요 없는 이벤트는 `None`)
+fn describe(event: &KernelEvent) -> Option<(String, Color)> {
+    match *event {
+        // 부팅 시 열거되는 PCI/플랫폼 디바이스는 알리지 않고 핫플러그 버스만 알림
+        KernelEvent::DeviceAdded { info, .. } | KernelEvent::DeviceRemoved { info, .. }
+            if info.bus() != crate::drivers::base::BusType::Usb => None,
+        KernelEvent::DeviceAdded { .. } => Some((format!("{}", event), Color::new(0, 120, 215))),
+        KernelEvent::DeviceRemoved { .. } => Some((format!("{}", event), Color::GRAY)),
+        KernelEvent::PowerSource { on_ac } => {
+            Some((format!("{}", event), if on_ac { Color::new(0, 160, 80) } else { Color::new(200, 140, 0) }))
+        }
+        KernelEvent::Battery { percent, status: BatteryStatus::Discharging } if percent <= LOW_BATTERY_PERCENT => {
+            Some((format!("Battery low: {}%", percent), Color::new(200, 60, 0)))
+        }
+        KernelEvent::Battery { .. } => None,
+        KernelEvent::Thermal { action: ThermalAction::Normal, .. } => Some((format!("{}", event), Color::new(0, 160, 80))),
+        KernelEvent::Thermal { .. } => Some((format!("{}", event), Color::RED)),
+        KernelEvent::NetworkLink { up } => {
+            Some((format!("{}", event), if up { Color::new(0, 160, 80) } else { Color::GRAY }))
+        }
+    }
+}
+
+/// 새 이벤트를 토스트로 만들고 만료된 토스트 제거
+///
+/// # Returns
+/// 표시할 내용이 바뀌었으면 `true` (화면 다시 그리기 필요)
+pub fn poll(now_ms: u64) -> bool {
+    let mut notifications = NOTIFICATIONS.lock();
+    let Notifications { subscription, toasts } = &mut *notifications;
+    let mut changed = false;
+
+    if let Some(subscription) = subscription {
+        while let Some(record) = subscription.try_recv() {
+            let Some((text, color)) = describe(&record.event) else { continue };
+            if toasts.len() >= MAX_TOASTS {
+                toasts.pop_front();
+            }
+            toasts.push_back(Toast { text, color, expires_ms: now_ms + TOAST_DURATION_MS });
+            changed = true;
+        }
+    }
+
+    let before = toasts.len();
+    toasts.retain(|toast| toast.expires_ms > now_ms);
+    changed || toasts.len() != before
+}
+
+/// 토스트 렌더링 (우측 상단, 최신 알림이 아래)
+pub fn render() {
+    let notifications = NOTIFICATIONS.lock();
+    let screen_width = framebuffer::get_width();
+    if screen_width < TOAST_WIDTH + TOAST_MARGIN {
+        return;
+    }
+    let x = screen_width - TOAST_WIDTH - TOAST_MARGIN;
+    let max_chars = (TOAST_WIDTH - 20) / 8;
+
+    for (i, toast) in notifications.toasts.iter().enumerate() {
+        let y = TOAST_MARGIN + i * (TOAST_HEIGHT + 6);
+        framebuffer::fill_rect(x, y, TOAST_WIDTH, TOAST_HEIGHT, Color::new(45, 45, 45));
+        framebuffer::fill_rect(x, y, 4, TOAST_HEIGHT, toast.color);
+        framebuffer::draw_rect(x, y, TOAST_WIDTH, TOAST_HEIGHT, Color::new(90, 90, 90));
+        let text: String = toast.text.chars().take(max_chars).collect();
+        font::draw_str(x + 12, y + (TOAST_HEIGHT - 8) / 2, &text, Color::WHITE);
+    }
+}
@@ -0,0 +1,106 @@
+//! GDT / TSS 설정
+//!
+//! 부트로더가 만든 GDT에는 TSS가 없어 IST(Interrupt Stack Table)를 사용할 수 없습니다.
+//! 각 CPU는 자신의 GDT와 TSS를 로드하며, 더블 폴트와 NMI는 TSS의 IST 스택에서
+//! 처리되므로 커널 스택이 넘친 상황에서도 핸들러가 실행됩니다.
+//!
+//! BSP의 테이블은 힙 초기화 전에 필요하므로 정적 저장소를 사용하고,
+//! AP의 테이블은 AP 시작 시 힙에 할당합니다.
+
+use alloc::boxed::Box;
+use alloc::vec;
+use core::ptr::addr_of;
+use spin::Once;
+use x86_64::instructions::segmentation::{Segment, CS, DS, ES, SS};
+use x86_64::instructions::tables::load_tss;
+use x86_64::registers::segmentation::SegmentSelector;
+use x86_64::structures::gdt::{Descriptor, GlobalDescriptorTable};
+use x86_64::structures::tss::TaskStateSegment;
+use x86_64::VirtAddr;
+
+/// 더블 폴트 핸들러용 IST 인덱스
+pub const DOUBLE_FAULT_IST_INDEX: u16 = 0;
+/// NMI 핸들러용 IST 인덱스
+pub const NMI_IST_INDEX: u16 = 1;
+
+/// IST 스택 크기
+const IST_STACK_SIZE: usize = 16 * 1024;
+
+/// IST 스택 수 (더블 폴트, NMI)
+const IST_STACK_COUNT: usize = 2;
+
+/// GDT 세그먼트 셀렉터
+struct Selectors {
+    code: SegmentSelector,
+    data: SegmentSelector,
+    tss: SegmentSelector,
+}
+
+/// CPU별 GDT
+struct CpuGdt {
+    gdt: GlobalDescriptorTable,
+    selectors: Selectors,
+}
+
+#[repr(C, align(16))]
+struct IstStack([u8; IST_STACK_SIZE]);
+
+/// BSP IST 스택
+static mut BSP_IST_STACKS: [IstStack; IST_STACK_COUNT] =
+    [const { IstStack([0; IST_STACK_SIZE]) }; IST_STACK_COUNT];
+
+static BSP_TSS: Once<TaskStateSegment> = Once::new();
+static BSP_GDT: Once<CpuGdt> = Once::new();
+
+/// BSP GDT/TSS 로드
+///
+/// # Safety
+/// IST 인덱스를 사용하는 IDT를 로드하기 전에 BSP에서 한 번 호출되어야 합니다.
+pub unsafe fn init() {
+    let tss = BSP_TSS.call_once(|| {
+        let mut tss = TaskStateSegment::new();
+        for i in 0..IST_STACK_COUNT {
+            let stack = addr_of!(BSP_IST_STACKS[i]) as u64;
+            tss.interrupt_stack_table[i] = VirtAddr::new(stack + IST_STACK_SIZE as u64);
+        }
+        tss
+    });
+    let gdt = BSP_GDT.call_once(|| build_gdt(tss));
+    load(gdt);
+}
+
+/// AP GDT/TSS 생성 및 로드
+///
+/// # Safety
+/// AP 시작 직후 해당 CPU에서 한 번 호출되어야 합니다. 힙이 초기화되어 있어야 합니다.
+pub unsafe fn init_ap() {
+    let mut tss = TaskStateSegment::new();
+    for i in 0..IST_STACK_COUNT {
+        let stack: &'static mut [u8] = Box::leak(vec![0u8; IST_STACK_SIZE].into_boxed_slice());
+        let top = (stack.as_mut_ptr() as u64 + IST_STACK_SIZE as u64) & !0xF;
+        tss.interrupt_stack_table[i] = VirtAddr::new(top);
+    }
+    let tss: &'static TaskStateSegment = Box::leak(Box::new(tss));
+    let gdt: &'static CpuGdt = Box::leak(Box::new(build_gdt(tss)));
+    load(gdt);
+}
+
+fn build_gdt(tss: &'static TaskStateSegment) -> CpuGdt {
+    let mut gdt = GlobalDescriptorTable::new();
+    let code = gdt.add_entry(Descriptor::kernel_code_segment());
+    let data = gdt.add_entry(Descriptor::kernel_data_segment());
+    let tss = gdt.add_entry(Descriptor::tss_segment(tss));
+    CpuGdt { gdt, selectors: Selectors { code, data, tss } }
+}
+
+/// GDT 로드 후 세그먼트 레지스터와 TR 재설정
+///
+/// FS/GS는 건드리지 않습니다 (셀렉터를 다시 로드하면 GS 베이스가 지워짐).
+unsafe fn load(gdt: &'static CpuGdt) {
+    gdt.gdt.load();
+    CS::set_reg(gdt.selectors.code);
+    SS::set_reg(gdt.selectors.data);
+    DS::set_reg(gdt.selectors.data);
+    ES::set_reg(gdt.selectors.data);
+    load_tss(gdt.selectors.tss);
+}
@@ -5,7 +5,6 @@
 use x86_64::structures::idt::{InterruptDescriptorTable, InterruptStackFrame, PageFaultErrorCode};
 use x86_64::instructions::interrupts;
 
-use crate::interrupts::pic;
 use crate::{log_error, log_warn, log_debug, log_info};
 
 /// 16바이트 정렬된 IDT 저장소 (MSVC/LLVM 정렬 요구 대응)
@@ -21,13 +20,17 @@ pub static mut IDT: AlignedIdt = AlignedIdt(InterruptDescriptorTable::new());
 pub unsafe fn init() {
     IDT.0.divide_error.set_handler_fn(divide_error_handler);
     IDT.0.debug.set_handler_fn(debug_handler);
-    IDT.0.non_maskable_interrupt.set_handler_fn(nmi_handler);
+    IDT.0.non_maskable_interrupt
+        .set_handler_fn(nmi_handler)
+        .set_stack_index(crate::interrupts::gdt::NMI_IST_INDEX);
     IDT.0.breakpoint.set_handler_fn(breakpoint_handler);
     IDT.0.overflow.set_handler_fn(overflow_handler);
     IDT.0.bound_range_exceeded.set_handler_fn(bound_range_exceeded_handler);
     IDT.0.invalid_opcode.set_handler_fn(invalid_opcode_handler);
     IDT.0.device_not_available.set_handler_fn(device_not_available_handler);
-    IDT.0.double_fault.set_handler_fn(double_fault_handler);
+    IDT.0.double_fault
+        .set_handler_fn(double_fault_handler)
+        .set_stack_index(crate::interrupts::gdt::DOUBLE_FAULT_IST_INDEX);
     // IDT.coprocessor_segment_overrun.set_handler_fn(coprocessor_segment_overrun_handler);
     IDT.0.invalid_tss.set_handler_fn(invalid_tss_handler);
     IDT.0.segment_not_present.set_handler_fn(segment_not_present_handler);
@@ -41,18 +44,27 @@ pub unsafe fn init() {
     IDT.0.virtualization.set_handler_fn(virtualization_handler);
     IDT.0.security_exception.set_handler_fn(security_exception_handler);
 
-    // 하드웨어 인터럽트 (PIC 인터럽트)
-    // IRQ 0: 타이머 (인터럽트 32)
-    IDT.0[32].set_handler_fn(crate::drivers::timer::timer_interrupt_handler);
-    // IRQ 1: 키보드 (인터럽트 33)
-    IDT.0[33].set_handler_fn(crate::drivers::keyboard::keyboard_interrupt_handler);
-    // IRQ 12: PS/2 마우스 (인터럽트 44)
-    IDT.0[44].set_handler_fn(mouse_interrupt_handler);
+    // 하드웨어 인터럽트: 드라이버는 `irq::request_irq`로 핸들러를 등록
+    crate::interrupts::irq::install_handlers(&mut IDT.0);
+    // LAPIC one-shot 타이머 (tickless 모드)
+    IDT.0[crate::drivers::lapic_timer::LAPIC_TIMER_VECTOR as usize]
+        .set_handler_fn(crate::drivers::timer::lapic_timer_interrupt_handler);
+    // 재스케줄링 IPI (유휴 AP 깨우기)
+    IDT.0[crate::smp::ipi::RESCHEDULE_VECTOR as usize]
+        .set_handler_fn(crate::smp::ipi::reschedule_interrupt_handler);
 
     // IDT 로드
     IDT.0.load();
 }
 
+/// 이미 설정된 IDT를 현재 CPU에 로드 (AP 시작 시 사용, 모든 CPU가 공유)
+///
+/// # Safety
+/// BSP에서 `init`이 완료된 후 호출되어야 합니다.
+pub unsafe fn load() {
+    IDT.0.load();
+}
+
 /// 시스템 콜 핸들러 등록
 ///
 /// # Arguments
@@ -474,20 +486,6 @@ extern "x86-interrupt" fn security_exception_handler(stack_frame: InterruptStack
     log_error!("Security Exception");
 }
 
-/// 하드웨어 인터럽트: PS/2 마우스 (IRQ 12)
-extern "x86-interrupt" fn mouse_interrupt_handler(_stack_frame: InterruptStackFrame) {
-    // 마우스 드라이버의 인터럽트 핸들러 호출
-    crate::drivers::mouse::handle_interrupt();
-    
-    // 사용자 활동 기록
-    crate::power::user_activity::record_activity(crate::power::user_activity::ActivityType::Mouse);
-    
-    // PIC에 EOI 전송 (IRQ 12는 슬레이브 PIC)
-    unsafe {
-        pic::end_of_interrupt(12);
-    }
-}
-
 /// 인터럽트 활성화
 pub fn enable_interrupts() {
     unsafe {
@@ -0,0 +1,185 @@
+//! I/O APIC 드라이버
+//!
+//! MADT에 기술된 I/O APIC들을 매핑하고 GSI(Global System Interrupt)별
+//! 리다이렉션 엔트리(벡터, 극성, 트리거 모드, 대상 CPU, 마스크)를 프로그래밍합니다.
+//! IRQ 등록과 벡터 할당은 `interrupts::irq`가 담당합니다.
+
+use alloc::vec::Vec;
+use core::ptr::{read_volatile, write_volatile};
+use spin::Mutex;
+use x86_64::PhysAddr;
+
+use crate::power::acpi_madt::{MadtInfo, Polarity, TriggerMode};
+
+/// I/O APIC 레지스터
+mod reg {
+    pub const IOREGSEL: u64 = 0x00;     // I/O Register Select
+    pub const IOWIN: u64 = 0x10;        // I/O Window
+
+    // I/O APIC 레지스터 인덱스
+    pub const ID: u32 = 0x00;
+    pub const VER: u32 = 0x01;
+    pub const REDTBL_BASE: u32 = 0x10;  // Redirection Table Base
+}
+
+/// 리다이렉션 엔트리 비트
+const RTE_POLARITY_LOW: u32 = 1 << 13;
+const RTE_TRIGGER_LEVEL: u32 = 1 << 15;
+const RTE_MASKED: u32 = 1 << 16;
+
+/// 리다이렉션 엔트리 설정
+#[derive(Debug, Clone, Copy)]
+pub struct RedirectionEntry {
+    /// 인터럽트 벡터
+    pub vector: u8,
+    /// 대상 CPU의 APIC ID (물리 목적지 모드)
+    pub dest_apic_id: u8,
+    pub polarity: Polarity,
+    pub trigger: TriggerMode,
+    pub masked: bool,
+}
+
+impl RedirectionEntry {
+    /// 레지스터 값 (low, high)으로 변환 (고정 전달 모드, 물리 목적지)
+    fn encode(&self) -> (u32, u32) {
+        let mut low = self.vector as u32;
+        if self.polarity == Polarity::ActiveLow {
+            low |= RTE_POLARITY_LOW;
+        }
+        if self.trigger == TriggerMode::Level {
+            low |= RTE_TRIGGER_LEVEL;
+        }
+        if self.masked {
+            low |= RTE_MASKED;
+        }
+        (low, (self.dest_apic_id as u32) << 24)
+    }
+}
+
+/// 매핑된 I/O APIC
+struct IoApic {
+    id: u8,
+    /// 레지스터 가상 주소
+    base: u64,
+    /// 첫 번째 GSI
+    gsi_base: u32,
+    /// 리다이렉션 엔트리 수
+    entries: u32,
+}
+
+impl IoApic {
+    fn handles(&self, gsi: u32) -> bool {
+        gsi >= self.gsi_base && gsi < self.gsi_base + self.entries
+    }
+
+    unsafe fn read(&self, index: u32) -> u32 {
+        write_volatile((self.base + reg::IOREGSEL) as *mut u32, index);
+        read_volatile((self.base + reg::IOWIN) as *const u32)
+    }
+
+    unsafe fn write(&self, index: u32, value: u32) {
+        write_volatile((self.base + reg::IOREGSEL) as *mut u32, index);
+        write_volatile((self.base + reg::IOWIN) as *mut u32, value);
+    }
+
+    unsafe fn write_entry(&self, gsi: u32, entry: &RedirectionEntry) {
+        let index = reg::REDTBL_BASE + (gsi - self.gsi_base) * 2;
+        let (low, high) = entry.encode();
+        // 엔트리를 바꾸는 동안 인터럽트가 반쯤 설정된 상태로 전달되지 않도록 먼저 마스크
+        self.write(index, RTE_MASKED);
+        self.write(index + 1, high);
+        self.write(index, low);
+    }
+
+    unsafe fn set_masked(&self, gsi: u32, masked: bool) {
+        let index = reg::REDTBL_BASE + (gsi - self.gsi_base) * 2;
+        let low = self.read(index);
+        let low = if masked { low | RTE_MASKED } else { low & !RTE_MASKED };
+        self.write(index, low);
+    }
+}
+
+/// 시스템의 I/O APIC 목록
+static IO_APICS: Mutex<Vec<IoApic>> = Mutex::new(Vec::new());
+
+/// MADT의 I/O APIC 매핑 및 모든 엔트리 마스크
+///
+/// # Returns
+/// 관리하는 GSI 수 (I/O APIC이 없으면 오류)
+///
+/// # Safety
+/// 메모리 관리가 초기화된 후 BSP에서 한 번 호출되어야 합니다.
+pub unsafe fn init(madt: &MadtInfo) -> Result<u32, &'static str> {
+    let mut io_apics = Vec::new();
+    for desc in &madt.io_apics {
+        let virt = crate::memory::paging::phys_to_virt(PhysAddr::new(desc.address as u64))
+            .ok_or("physical memory offset not available")?;
+        let mut io_apic = IoApic {
+            id: desc.id,
+            base: virt.as_u64(),
+            gsi_base: desc.gsi_base,
+            entries: 0,
+        };
+        let version = io_apic.read(reg::VER);
+        io_apic.entries = ((version >> 16) & 0xFF) + 1;
+
+        for gsi in io_apic.gsi_base..io_apic.gsi_base + io_apic.entries {
+            io_apic.write_entry(gsi, &RedirectionEntry {
+                vector: 0,
+                dest_apic_id: 0,
+                polarity: Polarity::ActiveHigh,
+                trigger: TriggerMode::Edge,
+                masked: true,
+            });
+        }
+
+        crate::log_info!(
+            "I/O APIC {} (hw ID {}) at 0x{:X}: GSI {}-{}",
+            io_apic.id,
+            (io_apic.read(reg::ID) >> 24) & 0x0F,
+            desc.address,
+            io_apic.gsi_base,
+            io_apic.gsi_base + io_apic.entries - 1
+        );
+        io_apics.push(io_apic);
+    }
+
+    if io_apics.is_empty() {
+        return Err("no I/O APIC described in MADT");
+    }
+    let total = io_apics.iter().map(|io| io.entries).sum();
+    *IO_APICS.lock() = io_apics;
+    Ok(total)
+}
+
+/// I/O APIC 사용 가능 여부
+pub fn is_present() -> bool {
+    !IO_APICS.lock().is_empty()
+}
+
+/// GSI가 어떤 I/O APIC에 연결되어 있는지 확인
+pub fn handles(gsi: u32) -> bool {
+    IO_APICS.lock().iter().any(|io| io.handles(gsi))
+}
+
+/// GSI의 리다이렉션 엔트리 설정
+///
+/// # Safety
+/// `init` 이후에 호출되어야 합니다.
+pub unsafe fn set_entry(gsi: u32, entry: &RedirectionEntry) -> Result<(), &'static str> {
+    let io_apics = IO_APICS.lock();
+    let io_apic = io_apics.iter().find(|io| io.handles(gsi)).ok_or("GSI not handled by any I/O APIC")?;
+    io_apic.write_entry(gsi, entry);
+    Ok(())
+}
+
+/// GSI 마스크/언마스크
+///
+/// # Safety
+/// `init` 이후에 호출되어야 합니다.
+pub unsafe fn set_masked(gsi: u32, masked: bool) {
+    let io_apics = IO_APICS.lock();
+    if let Some(io_apic) = io_apics.iter().find(|io| io.handles(gsi)) {
+        io_apic.set_masked(gsi, masked);
+    }
+}
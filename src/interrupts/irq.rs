@@ -0,0 +1,521 @@
+//! 공통 IRQ 관리
+//!
+//! 드라이버는 `request_irq(gsi, handler)`로 인터럽트 핸들러를 등록합니다.
+//! 벡터 할당, 인터럽트 컨트롤러(I/O APIC 또는 8259 PIC) 프로그래밍, EOI 전송은
+//! 이 모듈이 처리하므로 드라이버가 IDT 벡터나 PIC 포트를 직접 다룰 필요가 없습니다.
+//!
+//! IRQ 벡터는 `IRQ_VECTOR_BASE`부터 `NR_IRQ_VECTORS`개이며, 모든 벡터는 같은
+//! 디스패처를 거칩니다. GSI 0-15는 PIC 배치와 같은 벡터(32 + GSI)를 사용하고,
+//! 16 이상의 GSI(I/O APIC 모드에서만 가능)는 남은 벡터를 동적으로 할당받습니다.
+//! 같은 GSI에 여러 핸들러를 등록하면 (공유 IRQ) 등록 순서대로 모두 호출됩니다.
+//!
+//! PCI MSI/MSI-X 벡터는 `request_msi`로 동적 구간에서 할당받습니다. MSI는
+//! Local APIC로 직접 전달되므로 I/O APIC 모드에서만 사용할 수 있습니다.
+
+use alloc::vec::Vec;
+use core::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use spin::Mutex;
+use x86_64::instructions::interrupts;
+use x86_64::structures::idt::{InterruptDescriptorTable, InterruptStackFrame};
+
+use crate::interrupts::{ioapic, pic};
+use crate::power::acpi_madt::{self, Polarity, TriggerMode};
+
+/// IRQ 핸들러 (인자: GSI, MSI 핸들러는 인터럽트 벡터)
+pub type IrqHandler = fn(gsi: u32);
+
+/// 첫 번째 IRQ 벡터 (PIC 리매핑 오프셋과 동일)
+pub const IRQ_VECTOR_BASE: u8 = pic::PIC1_OFFSET;
+
+/// IRQ 벡터 수
+pub const NR_IRQ_VECTORS: usize = 48;
+
+/// ISA IRQ 수 (벡터가 GSI에 고정된 구간)
+const LEGACY_IRQS: u32 = 16;
+
+/// IRQ 하나를 공유할 수 있는 최대 핸들러 수
+const MAX_SHARED_HANDLERS: usize = 4;
+
+/// MSI 메시지 주소 베이스 (Local APIC 인터럽트 영역)
+const MSI_ADDRESS_BASE: u64 = 0xFEE0_0000;
+
+/// 인터럽트 컨트롤러 종류
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Controller {
+    /// 레거시 8259 PIC (GSI 0-15, 에지 트리거만)
+    Pic,
+    /// I/O APIC + Local APIC
+    IoApic,
+}
+
+/// 벡터별 IRQ 디스크립터
+#[derive(Clone, Copy)]
+struct IrqDesc {
+    /// 연결된 GSI (`None` = MSI 벡터 또는 사용하지 않는 벡터)
+    gsi: Option<u32>,
+    /// MSI/MSI-X 벡터 여부
+    msi: bool,
+    handlers: [Option<IrqHandler>; MAX_SHARED_HANDLERS],
+    polarity: Polarity,
+    trigger: TriggerMode,
+    /// 대상 CPU의 APIC ID
+    dest_apic_id: u8,
+    enabled: bool,
+}
+
+impl IrqDesc {
+    const EMPTY: Self = Self {
+        gsi: None,
+        msi: false,
+        handlers: [None; MAX_SHARED_HANDLERS],
+        polarity: Polarity::ActiveHigh,
+        trigger: TriggerMode::Edge,
+        dest_apic_id: 0,
+        enabled: false,
+    };
+
+    fn is_free(&self) -> bool {
+        self.gsi.is_none() && !self.msi
+    }
+
+    fn has_handlers(&self) -> bool {
+        self.handlers.iter().any(Option::is_some)
+    }
+}
+
+/// IRQ 상태 정보 (통계 출력용)
+#[derive(Debug, Clone, Copy)]
+pub struct IrqInfo {
+    /// GSI (`None` = MSI/MSI-X)
+    pub gsi: Option<u32>,
+    pub vector: u8,
+    pub handlers: usize,
+    pub polarity: Polarity,
+    pub trigger: TriggerMode,
+    pub dest_apic_id: u8,
+    pub enabled: bool,
+    /// 처리한 인터럽트 수
+    pub count: u64,
+}
+
+/// 벡터별 디스크립터 (인터럽트 핸들러에서도 잠그므로 수정은 인터럽트를 끄고 수행)
+static IRQ_DESCS: Mutex<[IrqDesc; NR_IRQ_VECTORS]> = Mutex::new([IrqDesc::EMPTY; NR_IRQ_VECTORS]);
+
+/// 벡터별 인터럽트 횟수
+static IRQ_COUNTS: [AtomicU64; NR_IRQ_VECTORS] = [const { AtomicU64::new(0) }; NR_IRQ_VECTORS];
+
+/// I/O APIC 모드 여부
+static IOAPIC_MODE: AtomicBool = AtomicBool::new(false);
+
+/// MSI 메시지 (디바이스가 인터럽트를 알릴 때 쓰는 주소와 데이터)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MsiMessage {
+    pub address: u64,
+    pub data: u32,
+}
+
+/// suspend 전에 저장한 벡터별 활성화 상태
+static SAVED_ENABLED: Mutex<Option<[bool; NR_IRQ_VECTORS]>> = Mutex::new(None);
+
+/// 인터럽트 컨트롤러 선택
+///
+/// MADT에 I/O APIC이 있으면 8259 PIC를 모두 마스크하고 I/O APIC으로 전환합니다.
+/// 그렇지 않으면 PIC를 계속 사용합니다.
+///
+/// # Safety
+/// 메모리 관리가 초기화된 후, 첫 `request_irq` 전에 BSP에서 한 번 호출되어야 합니다.
+pub unsafe fn init() {
+    let Some(madt) = acpi_madt::load() else {
+        crate::log_info!("IRQ: no MADT, using legacy 8259 PIC");
+        return;
+    };
+    if madt.io_apics.is_empty() || !crate::drivers::lapic_timer::lapic_supported() {
+        crate::log_info!("IRQ: no I/O APIC, using legacy 8259 PIC");
+        return;
+    }
+
+    let lines = match ioapic::init(madt) {
+        Ok(lines) => lines,
+        Err(e) => {
+            crate::log_warn!("IRQ: I/O APIC initialization failed ({}), using 8259 PIC", e);
+            return;
+        }
+    };
+    if let Err(e) = crate::smp::apic::init_local_apic() {
+        crate::log_warn!("IRQ: Local APIC unavailable ({}), using 8259 PIC", e);
+        return;
+    }
+
+    interrupts::without_interrupts(|| {
+        debug_assert!(IRQ_DESCS.lock().iter().all(IrqDesc::is_free));
+        pic::write_mask(0xFF, 0xFF);
+        IOAPIC_MODE.store(true, Ordering::Release);
+    });
+    crate::log_info!(
+        "IRQ: routing through I/O APIC ({} GSIs, {} override(s)), 8259 PIC masked",
+        lines,
+        madt.overrides.len()
+    );
+}
+
+/// 현재 인터럽트 컨트롤러
+pub fn controller() -> Controller {
+    if IOAPIC_MODE.load(Ordering::Acquire) {
+        Controller::IoApic
+    } else {
+        Controller::Pic
+    }
+}
+
+/// ISA IRQ 번호를 GSI로 변환
+///
+/// I/O APIC 모드에서는 MADT 인터럽트 소스 오버라이드를 반영합니다
+/// (예: PIT IRQ 0 -> GSI 2). PIC 모드에서는 그대로 반환합니다.
+pub fn isa_irq_to_gsi(irq: u8) -> u32 {
+    if controller() == Controller::IoApic {
+        if let Some(o) = acpi_madt::get().and_then(|m| m.isa_override(irq)) {
+            return o.gsi;
+        }
+    }
+    irq as u32
+}
+
+/// GSI의 기본 극성/트리거 모드
+///
+/// 오버라이드가 있으면 그 값을, 없으면 ISA(GSI 0-15: active high, edge) 또는
+/// PCI(그 외: active low, level) 규격의 기본값을 사용합니다.
+fn default_type(gsi: u32) -> (Polarity, TriggerMode) {
+    let (isa_polarity, isa_trigger) = if gsi < LEGACY_IRQS {
+        (Polarity::ActiveHigh, TriggerMode::Edge)
+    } else {
+        (Polarity::ActiveLow, TriggerMode::Level)
+    };
+    match acpi_madt::get().and_then(|m| m.gsi_override(gsi)) {
+        Some(o) => (
+            if o.polarity == Polarity::BusDefault { Polarity::ActiveHigh } else { o.polarity },
+            if o.trigger == TriggerMode::BusDefault { TriggerMode::Edge } else { o.trigger },
+        ),
+        None => (isa_polarity, isa_trigger),
+    }
+}
+
+/// GSI에 해당하는 벡터 슬롯 찾기 또는 할당
+fn slot_for(descs: &[IrqDesc; NR_IRQ_VECTORS], gsi: u32) -> Option<usize> {
+    if let Some(slot) = descs.iter().position(|d| d.gsi == Some(gsi)) {
+        return Some(slot);
+    }
+    if gsi < LEGACY_IRQS {
+        return Some(gsi as usize);
+    }
+    free_dynamic_slot(descs)
+}
+
+/// 동적 구간(GSI 16 이상, MSI)에서 빈 벡터 슬롯 찾기
+fn free_dynamic_slot(descs: &[IrqDesc; NR_IRQ_VECTORS]) -> Option<usize> {
+    (LEGACY_IRQS as usize..NR_IRQ_VECTORS).find(|&slot| descs[slot].is_free())
+}
+
+/// 디스크립터 상태를 인터럽트 컨트롤러에 반영
+unsafe fn program(slot: usize, desc: &IrqDesc) {
+    let Some(gsi) = desc.gsi else { return };
+    if controller() == Controller::IoApic {
+        let entry = ioapic::RedirectionEntry {
+            vector: IRQ_VECTOR_BASE + slot as u8,
+            dest_apic_id: desc.dest_apic_id,
+            polarity: desc.polarity,
+            trigger: desc.trigger,
+            masked: !desc.enabled,
+        };
+        if let Err(e) = ioapic::set_entry(gsi, &entry) {
+            crate::log_warn!("IRQ: cannot program GSI {}: {}", gsi, e);
+        }
+    } else {
+        pic::set_mask(gsi as u8, desc.enabled);
+    }
+}
+
+/// GSI의 디스크립터에 대해 작업 수행 (인터럽트 비활성화 상태)
+fn with_desc<R>(gsi: u32, f: impl FnOnce(usize, &mut IrqDesc) -> R) -> Option<R> {
+    interrupts::without_interrupts(|| {
+        let mut descs = IRQ_DESCS.lock();
+        let slot = descs.iter().position(|d| d.gsi == Some(gsi))?;
+        Some(f(slot, &mut descs[slot]))
+    })
+}
+
+/// 새 IRQ의 기본 대상 CPU (BSP)
+///
+/// 병렬 initcall은 AP에서 드라이버를 초기화하므로, 등록한 CPU가 아니라 항상 틱이
+/// 도는 BSP로 보냅니다. 다른 CPU로 옮기려면 `set_irq_affinity`를 사용합니다.
+fn default_dest_apic_id() -> u8 {
+    crate::kernel::percpu::cpu(0)
+        .map_or(0, |bsp| bsp.apic_id.load(Ordering::Relaxed) as u8)
+}
+
+/// IRQ 핸들러 등록
+///
+/// GSI에 벡터를 할당하고 컨트롤러에서 언마스크합니다. 인터럽트는 BSP로 전달됩니다. 같은 GSI에 다른 핸들러가
+/// 이미 있으면 공유 IRQ로 추가되며, 같은 핸들러가 이미 등록되어 있으면
+/// IRQ를 다시 활성화하기만 합니다.
+///
+/// # Arguments
+/// * `gsi` - Global System Interrupt 번호 (ISA IRQ는 `isa_irq_to_gsi`로 변환)
+/// * `handler` - 인터럽트 컨텍스트에서 호출될 핸들러
+///
+/// # Returns
+/// 할당된 인터럽트 벡터
+pub fn request_irq(gsi: u32, handler: IrqHandler) -> Result<u8, &'static str> {
+    let io_apic = controller() == Controller::IoApic;
+    if !io_apic && gsi >= LEGACY_IRQS {
+        return Err("GSI above 15 requires an I/O APIC");
+    }
+    if io_apic && !ioapic::handles(gsi) {
+        return Err("GSI not handled by any I/O APIC");
+    }
+
+    interrupts::without_interrupts(|| {
+        let mut descs = IRQ_DESCS.lock();
+        let slot = slot_for(&descs, gsi).ok_or("no free IRQ vector")?;
+        let desc = &mut descs[slot];
+
+        if desc.gsi.is_none() {
+            let (polarity, trigger) = default_type(gsi);
+            *desc = IrqDesc {
+                gsi: Some(gsi),
+                polarity,
+                trigger,
+                dest_apic_id: default_dest_apic_id(),
+                ..IrqDesc::EMPTY
+            };
+        }
+
+        let registered = desc.handlers.iter().flatten().any(|&h| h as usize == handler as usize);
+        if !registered {
+            let free = desc.handlers.iter_mut().find(|h| h.is_none()).ok_or("too many handlers sharing IRQ")?;
+            *free = Some(handler);
+        }
+
+        desc.enabled = true;
+        unsafe { program(slot, desc) };
+        Ok(IRQ_VECTOR_BASE + slot as u8)
+    })
+}
+
+/// IRQ 핸들러 해제
+///
+/// 마지막 핸들러가 해제되면 GSI를 마스크하고 벡터를 반환합니다.
+pub fn free_irq(gsi: u32, handler: IrqHandler) {
+    with_desc(gsi, |slot, desc| {
+        for h in desc.handlers.iter_mut() {
+            if matches!(h, Some(f) if *f as usize == handler as usize) {
+                *h = None;
+            }
+        }
+        if !desc.has_handlers() {
+            desc.enabled = false;
+            unsafe { program(slot, desc) };
+            *desc = IrqDesc::EMPTY;
+        }
+    });
+}
+
+/// IRQ 활성화 (등록된 GSI만)
+pub fn enable_irq(gsi: u32) {
+    with_desc(gsi, |slot, desc| {
+        desc.enabled = true;
+        unsafe { program(slot, desc) };
+    });
+}
+
+/// IRQ 비활성화 (등록된 GSI만)
+pub fn disable_irq(gsi: u32) {
+    with_desc(gsi, |slot, desc| {
+        desc.enabled = false;
+        unsafe { program(slot, desc) };
+    });
+}
+
+/// MSI/MSI-X 벡터 할당
+///
+/// 동적 구간에서 벡터를 하나 할당하고 핸들러를 연결합니다. 반환된 메시지를
+/// 디바이스의 MSI 캐퍼빌리티 또는 MSI-X 테이블 엔트리에 기록하면 BSP로
+/// 인터럽트가 전달됩니다. 핸들러에는 벡터 번호가 전달됩니다.
+///
+/// # Returns
+/// (할당된 벡터, 디바이스에 기록할 MSI 메시지)
+pub fn request_msi(handler: IrqHandler) -> Result<(u8, MsiMessage), &'static str> {
+    if controller() != Controller::IoApic {
+        return Err("MSI requires the Local APIC");
+    }
+
+    interrupts::without_interrupts(|| {
+        let mut descs = IRQ_DESCS.lock();
+        let slot = free_dynamic_slot(&descs).ok_or("no free IRQ vector")?;
+        let mut handlers = [None; MAX_SHARED_HANDLERS];
+        handlers[0] = Some(handler);
+        descs[slot] = IrqDesc {
+            msi: true,
+            handlers,
+            dest_apic_id: default_dest_apic_id(),
+            enabled: true,
+            ..IrqDesc::EMPTY
+        };
+        let vector = IRQ_VECTOR_BASE + slot as u8;
+        Ok((vector, msi_message(vector, descs[slot].dest_apic_id)))
+    })
+}
+
+/// MSI/MSI-X 벡터 해제
+///
+/// 디바이스 쪽 MSI를 먼저 비활성화(마스크)한 뒤 호출해야 합니다.
+pub fn free_msi(vector: u8) {
+    let Some(slot) = vector.checked_sub(IRQ_VECTOR_BASE).map(usize::from) else { return };
+    if slot >= NR_IRQ_VECTORS {
+        return;
+    }
+    interrupts::without_interrupts(|| {
+        let mut descs = IRQ_DESCS.lock();
+        if descs[slot].msi {
+            descs[slot] = IrqDesc::EMPTY;
+        }
+    });
+}
+
+/// 벡터와 대상 APIC ID로 MSI 메시지 구성 (고정 전달, 물리 목적지, 에지 트리거)
+fn msi_message(vector: u8, dest_apic_id: u8) -> MsiMessage {
+    MsiMessage {
+        address: MSI_ADDRESS_BASE | (dest_apic_id as u64) << 12,
+        data: vector as u32,
+    }
+}
+
+/// IRQ 극성과 트리거 모드 설정
+///
+/// 8259 PIC는 에지 트리거만 지원합니다.
+pub fn set_irq_type(gsi: u32, polarity: Polarity, trigger: TriggerMode) -> Result<(), &'static str> {
+    if controller() == Controller::Pic && trigger == TriggerMode::Level {
+        return Err("8259 PIC supports edge-triggered IRQs only");
+    }
+    with_desc(gsi, |slot, desc| {
+        desc.polarity = polarity;
+        desc.trigger = trigger;
+        unsafe { program(slot, desc) };
+    })
+    .ok_or("IRQ not requested")
+}
+
+/// IRQ를 처리할 CPU 지정
+///
+/// # Arguments
+/// * `gsi` - 등록된 GSI
+/// * `cpu` - 논리 CPU 번호
+pub fn set_irq_affinity(gsi: u32, cpu: u8) -> Result<(), &'static str> {
+    if controller() != Controller::IoApic {
+        return Err("IRQ affinity requires an I/O APIC");
+    }
+    let apic_id = crate::kernel::percpu::cpu(cpu)
+        .ok_or("CPU not online")?
+        .apic_id
+        .load(Ordering::Relaxed) as u8;
+    with_desc(gsi, |slot, desc| {
+        desc.dest_apic_id = apic_id;
+        unsafe { program(slot, desc) };
+    })
+    .ok_or("IRQ not requested")
+}
+
+/// 등록된 IRQ 목록
+pub fn irq_list() -> Vec<IrqInfo> {
+    let descs = interrupts::without_interrupts(|| *IRQ_DESCS.lock());
+    descs
+        .iter()
+        .enumerate()
+        .filter(|(_, desc)| !desc.is_free())
+        .map(|(slot, desc)| IrqInfo {
+            gsi: desc.gsi,
+            vector: IRQ_VECTOR_BASE + slot as u8,
+            handlers: desc.handlers.iter().flatten().count(),
+            polarity: desc.polarity,
+            trigger: desc.trigger,
+            dest_apic_id: desc.dest_apic_id,
+            enabled: desc.enabled,
+            count: IRQ_COUNTS[slot].load(Ordering::Relaxed),
+        })
+        .collect()
+}
+
+/// IRQ 활성화 상태 저장 (suspend 전)
+pub fn save_interrupt_mask() {
+    let enabled = interrupts::without_interrupts(|| IRQ_DESCS.lock().map(|d| d.enabled));
+    *SAVED_ENABLED.lock() = Some(enabled);
+}
+
+/// IRQ 활성화 상태 복원 및 컨트롤러 재프로그래밍 (resume 후)
+pub fn restore_interrupt_mask() {
+    let Some(saved) = SAVED_ENABLED.lock().take() else { return };
+    interrupts::without_interrupts(|| {
+        let mut descs = IRQ_DESCS.lock();
+        for (slot, desc) in descs.iter_mut().enumerate() {
+            desc.enabled = saved[slot];
+            unsafe { program(slot, desc) };
+        }
+    });
+}
+
+/// IRQ 벡터 핸들러 설치
+///
+/// # Safety
+/// IDT 초기화 중에만 호출되어야 합니다.
+pub(crate) unsafe fn install_handlers(idt: &mut InterruptDescriptorTable) {
+    for (slot, stub) in IRQ_STUBS.iter().enumerate() {
+        idt[IRQ_VECTOR_BASE as usize + slot].set_handler_fn(*stub);
+    }
+}
+
+/// 인터럽트 종료 신호 전송
+fn end_of_interrupt(slot: usize) {
+    if controller() == Controller::IoApic {
+        crate::smp::apic::send_eoi();
+    } else if slot < LEGACY_IRQS as usize {
+        unsafe { pic::end_of_interrupt(slot as u8) };
+    }
+}
+
+/// 공통 IRQ 디스패처
+fn dispatch(slot: usize) {
+    crate::kernel::softirq::irq_enter();
+    crate::monitoring::record_interrupt();
+    IRQ_COUNTS[slot].fetch_add(1, Ordering::Relaxed);
+
+    let desc = IRQ_DESCS.lock()[slot];
+    let arg = match desc.gsi {
+        Some(gsi) => Some(gsi),
+        None if desc.msi => Some((IRQ_VECTOR_BASE as usize + slot) as u32),
+        None => None,
+    };
+    if let Some(arg) = arg {
+        for handler in desc.handlers.iter().flatten() {
+            handler(arg);
+        }
+    }
+
+    end_of_interrupt(slot);
+    crate::kernel::softirq::irq_exit();
+}
+
+extern "x86-interrupt" fn irq_stub<const SLOT: usize>(_stack_frame: InterruptStackFrame) {
+    dispatch(SLOT);
+}
+
+macro_rules! irq_stubs {
+    ($($slot:literal)*) => {
+        [$(irq_stub::<$slot> as extern "x86-interrupt" fn(InterruptStackFrame)),*]
+    };
+}
+
+/// 벡터별 진입점
+static IRQ_STUBS: [extern "x86-interrupt" fn(InterruptStackFrame); NR_IRQ_VECTORS] = irq_stubs!(
+    0 1 2 3 4 5 6 7 8 9 10 11 12 13 14 15
+    16 17 18 19 20 21 22 23 24 25 26 27 28 29 30 31
+    32 33 34 35 36 37 38 39 40 41 42 43 44 45 46 47
+);
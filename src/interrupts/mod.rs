@@ -2,9 +2,13 @@
 //!
 //! 이 모듈은 인터럽트 디스크립터 테이블(IDT) 설정 및 인터럽트 핸들러를 담당합니다.
 
+pub mod gdt;
 pub mod idt;
 pub mod pic;
+pub mod ioapic;
+pub mod irq;
 pub mod exception_recovery;
 
 pub use idt::{init as init_idt, enable_interrupts, disable_interrupts};
 pub use pic::{init as init_pic, PIC1_OFFSET, PIC2_OFFSET, set_mask, end_of_interrupt};
+pub use irq::{request_irq, free_irq, enable_irq, disable_irq, isa_irq_to_gsi, request_msi, free_msi, IrqHandler, MsiMessage};
@@ -0,0 +1,336 @@
+//! Initcall (서브시스템 초기화 순서 관리)
+//!
+//! 힙과 per-CPU 데이터가 준비된 뒤의 서브시스템 초기화를 이름과 의존성을 선언한
+//! `Initcall` 테이블로 등록합니다. `run`은 의존성을 위상 정렬해 실행하며, 매 단계마다
+//! 의존성이 모두 끝난 initcall 중 테이블에서 가장 앞선 것을 고릅니다. 따라서 의존성
+//! 깊이가 같아도 뒤에 있는 initcall은 앞선 initcall의 후속보다 늦게 실행될 수 있습니다.
+//!
+//! - `parallel`로 표시된 initcall은 AP가 온라인이면 AP의 유휴 루프에서 실행되고,
+//!   BSP는 그동안 다른 initcall을 계속 진행합니다. 남는 작업이 없으면 BSP도 큐를 돕습니다.
+//!   AP는 initcall 단계 동안 LAPIC 타이머 틱을 켜 두므로 initcall이 `hlt`로 기다려도
+//!   깨어나며, initcall이 등록한 IRQ는 BSP로 전달됩니다 (`interrupts::irq`).
+//! - 각 initcall의 소요 시간은 부팅 타임라인에 기록됩니다.
+//! - `optional` initcall이 실패하면 경고만 남기고, 그에 의존하는 initcall은 건너뜁니다.
+//!   필수 initcall이 실패하면 부팅을 계속할 수 없으므로 패닉합니다.
+
+use alloc::collections::VecDeque;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::sync::atomic::{AtomicBool, Ordering};
+use spin::Mutex;
+
+/// initcall 함수 (실패 시 이유 반환)
+pub type InitFn = fn() -> Result<(), &'static str>;
+
+/// 등록된 initcall
+#[derive(Clone, Copy)]
+pub struct Initcall {
+    pub name: &'static str,
+    /// 먼저 끝나야 하는 initcall 이름
+    pub deps: &'static [&'static str],
+    pub func: InitFn,
+    /// 실패해도 부팅을 계속할 수 있는 서브시스템
+    pub optional: bool,
+    /// AP에서 다른 initcall과 동시에 실행해도 안전함
+    pub parallel: bool,
+}
+
+impl Initcall {
+    /// 필수, BSP 전용 initcall
+    pub const fn new(name: &'static str, deps: &'static [&'static str], func: InitFn) -> Self {
+        Self { name, deps, func, optional: false, parallel: false }
+    }
+
+    /// 실패해도 부팅을 계속하도록 표시
+    pub const fn optional(mut self) -> Self {
+        self.optional = true;
+        self
+    }
+
+    /// AP에서 병렬 실행 가능하도록 표시
+    pub const fn parallel(mut self) -> Self {
+        self.parallel = true;
+        self
+    }
+}
+
+/// initcall 테이블 오류
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TableError {
+    /// 같은 이름이 두 번 등록됨
+    Duplicate(&'static str),
+    /// 테이블에 없는 initcall에 의존
+    UnknownDependency { call: &'static str, dep: &'static str },
+    /// 순환 의존성 (순환에 포함된 initcall 중 하나)
+    Cycle(&'static str),
+}
+
+/// initcall 실행 결과
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InitState {
+    Pending,
+    /// AP 큐에서 대기 또는 실행 중
+    Queued,
+    Done,
+    Failed(&'static str),
+    /// 의존하는 initcall이 실패하거나 건너뛰어짐
+    Skipped,
+}
+
+/// initcall 실행 기록
+#[derive(Debug, Clone, Copy)]
+pub struct InitcallRecord {
+    pub name: &'static str,
+    pub state: InitState,
+    /// 실행한 CPU
+    pub cpu: u8,
+    pub duration_us: u64,
+}
+
+/// AP가 끝낸 initcall
+struct Completion {
+    index: usize,
+    result: Result<(), &'static str>,
+    cpu: u8,
+    start_us: u64,
+    duration_us: u64,
+}
+
+/// 병렬 initcall 실행 중 (AP가 멈추지 않고 큐를 폴링)
+static PARALLEL_ACTIVE: AtomicBool = AtomicBool::new(false);
+
+/// AP가 가져갈 initcall
+static AP_QUEUE: Mutex<VecDeque<(usize, InitFn)>> = Mutex::new(VecDeque::new());
+
+/// AP가 끝낸 initcall
+static AP_DONE: Mutex<Vec<Completion>> = Mutex::new(Vec::new());
+
+/// 마지막 `run`의 실행 기록
+static RECORDS: Mutex<Vec<InitcallRecord>> = Mutex::new(Vec::new());
+
+/// 의존성 위상 정렬
+///
+/// # Returns
+/// 실행 순서 (테이블 인덱스). 동시에 준비되는 initcall은 테이블 순서를 유지합니다.
+pub fn sort(calls: &[Initcall]) -> Result<Vec<usize>, TableError> {
+    let deps = resolve_deps(calls)?;
+    let mut placed = vec![false; calls.len()];
+    let mut order = Vec::with_capacity(calls.len());
+    while order.len() < calls.len() {
+        let next = (0..calls.len()).find(|&i| !placed[i] && deps[i].iter().all(|&d| placed[d]));
+        match next {
+            Some(i) => {
+                placed[i] = true;
+                order.push(i);
+            }
+            None => {
+                let stuck = (0..calls.len()).find(|&i| !placed[i]).unwrap_or(0);
+                return Err(TableError::Cycle(calls[stuck].name));
+            }
+        }
+    }
+    Ok(order)
+}
+
+/// 의존성 이름을 테이블 인덱스로 변환
+fn resolve_deps(calls: &[Initcall]) -> Result<Vec<Vec<usize>>, TableError> {
+    let index_of = |name: &str| calls.iter().position(|c| c.name == name);
+
+    let mut deps: Vec<Vec<usize>> = Vec::with_capacity(calls.len());
+    for (i, call) in calls.iter().enumerate() {
+        if index_of(call.name) != Some(i) {
+            return Err(TableError::Duplicate(call.name));
+        }
+        let mut indices = Vec::with_capacity(call.deps.len());
+        for &dep in call.deps {
+            let j = index_of(dep).ok_or(TableError::UnknownDependency { call: call.name, dep })?;
+            indices.push(j);
+        }
+        deps.push(indices);
+    }
+    Ok(deps)
+}
+
+fn now_us() -> u64 {
+    crate::time::now_ns() / crate::time::NSEC_PER_USEC
+}
+
+fn execute(index: usize, func: InitFn) -> Completion {
+    let start_us = now_us();
+    let result = func();
+    Completion {
+        index,
+        result,
+        cpu: crate::kernel::percpu::cpu_id(),
+        start_us,
+        duration_us: now_us().saturating_sub(start_us),
+    }
+}
+
+/// AP 유휴 루프에서 호출: 대기 중인 병렬 initcall 하나 실행
+///
+/// # Returns
+/// initcall 실행 단계가 진행 중이면 `true` (호출자는 멈추지 말고 다시 폴링해야 함)
+pub fn ap_poll() -> bool {
+    if !PARALLEL_ACTIVE.load(Ordering::Acquire) {
+        return false;
+    }
+    let work = AP_QUEUE.lock().pop_front();
+    match work {
+        Some((index, func)) => {
+            // initcall이 hlt로 기다려도 깨어나도록 이 CPU의 틱을 켬
+            crate::drivers::timer::start_ap_tick();
+            let completion = execute(index, func);
+            AP_DONE.lock().push(completion);
+        }
+        None => core::hint::spin_loop(),
+    }
+    true
+}
+
+/// 병렬 initcall 실행 중 여부 (AP 틱이 멈추지 않아야 함)
+pub fn parallel_active() -> bool {
+    PARALLEL_ACTIVE.load(Ordering::Acquire)
+}
+
+/// 다른 CPU가 병렬 initcall을 가져갈 수 있는지
+///
+/// AP에서 틱을 켤 수 없으면 (LAPIC 타이머 없음) 잠든 initcall이 깨어나지 못하므로
+/// BSP에서 실행합니다.
+fn aps_online() -> bool {
+    crate::kernel::percpu::iter().nth(1).is_some() && crate::drivers::lapic_timer::is_initialized()
+}
+
+/// initcall 테이블 실행 (BSP에서 한 번 호출)
+///
+/// # Panics
+/// 테이블이 잘못되었거나 (중복, 없는 의존성, 순환) 필수 initcall이 실패하면 패닉합니다.
+pub fn run(calls: &[Initcall]) {
+    let (order, deps) = match sort(calls).and_then(|order| Ok((order, resolve_deps(calls)?))) {
+        Ok(table) => table,
+        Err(e) => panic!("Invalid initcall table: {:?}", e),
+    };
+    let mut states = vec![InitState::Pending; calls.len()];
+    let mut records: Vec<InitcallRecord> = Vec::with_capacity(calls.len());
+    let mut remaining = calls.len();
+
+    PARALLEL_ACTIVE.store(true, Ordering::Release);
+    while remaining > 0 {
+        // AP가 끝낸 initcall 반영
+        let completed: Vec<Completion> = core::mem::take(&mut *AP_DONE.lock());
+        for completion in completed {
+            finish(calls, &mut states, &mut records, completion);
+            remaining -= 1;
+        }
+
+        let mut progressed = false;
+        for &i in &order {
+            if states[i] != InitState::Pending {
+                continue;
+            }
+            let call = &calls[i];
+            if let Some(&dep) = deps[i].iter().find(|&&j| matches!(states[j], InitState::Failed(_) | InitState::Skipped)) {
+                crate::log_warn!("initcall {}: skipped (dependency {} unavailable)", call.name, calls[dep].name);
+                states[i] = InitState::Skipped;
+                records.push(InitcallRecord { name: call.name, state: InitState::Skipped, cpu: 0, duration_us: 0 });
+                remaining -= 1;
+                progressed = true;
+                continue;
+            }
+            if !deps[i].iter().all(|&j| states[j] == InitState::Done) {
+                continue;
+            }
+            if call.parallel && aps_online() {
+                states[i] = InitState::Queued;
+                AP_QUEUE.lock().push_back((i, call.func));
+                progressed = true;
+                continue;
+            }
+            let completion = execute(i, call.func);
+            finish(calls, &mut states, &mut records, completion);
+            remaining -= 1;
+            progressed = true;
+            // 상태가 바뀌었으므로 순서의 처음부터 다시 확인
+            break;
+        }
+
+        if !progressed {
+            // BSP가 할 일이 없으면 큐에 남은 병렬 initcall을 직접 실행
+            let work = AP_QUEUE.lock().pop_front();
+            match work {
+                Some((index, func)) => {
+                    let completion = execute(index, func);
+                    finish(calls, &mut states, &mut records, completion);
+                    remaining -= 1;
+                }
+                None => core::hint::spin_loop(),
+            }
+        }
+    }
+    PARALLEL_ACTIVE.store(false, Ordering::Release);
+
+    *RECORDS.lock() = records;
+}
+
+fn finish(calls: &[Initcall], states: &mut [InitState], records: &mut Vec<InitcallRecord>, completion: Completion) {
+    let call = &calls[completion.index];
+    let state = match completion.result {
+        Ok(()) => InitState::Done,
+        Err(e) if call.optional => {
+            crate::log_warn!("initcall {}: failed ({}), continuing without it", call.name, e);
+            InitState::Failed(e)
+        }
+        Err(e) => panic!("Required initcall {} failed: {}", call.name, e),
+    };
+    states[completion.index] = state;
+    crate::boot::timeline::record_initcall(call.name, completion.start_us, completion.duration_us);
+    crate::log_debug!(
+        "initcall {}: {}.{:03}ms on CPU {}",
+        call.name,
+        completion.duration_us / 1000,
+        completion.duration_us % 1000,
+        completion.cpu
+    );
+    records.push(InitcallRecord { name: call.name, state, cpu: completion.cpu, duration_us: completion.duration_us });
+}
+
+/// 마지막 initcall 실행 기록 (완료 순서)
+pub fn records() -> Vec<InitcallRecord> {
+    RECORDS.lock().clone()
+}
+
+/// 이름으로 initcall 성공 여부 확인
+pub fn succeeded(name: &str) -> bool {
+    RECORDS.lock().iter().any(|r| r.name == name && r.state == InitState::Done)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ok() -> Result<(), &'static str> {
+        Ok(())
+    }
+
+    #[test_case]
+    fn test_initcall_sort_respects_dependencies() {
+        let calls = [
+            Initcall::new("net", &["pci", "timer"], ok),
+            Initcall::new("timer", &[], ok),
+            Initcall::new("pci", &["timer"], ok),
+            Initcall::new("vga", &[], ok),
+        ];
+        // 매 단계 준비된 것 중 테이블 순서가 앞선 것부터 (vga는 깊이 0이지만 마지막)
+        assert_eq!(sort(&calls), Ok(vec![1, 2, 0, 3]));
+    }
+
+    #[test_case]
+    fn test_initcall_sort_rejects_bad_tables() {
+        let cycle = [Initcall::new("a", &["b"], ok), Initcall::new("b", &["a"], ok)];
+        assert_eq!(sort(&cycle), Err(TableError::Cycle("a")));
+        let unknown = [Initcall::new("a", &["missing"], ok)];
+        assert_eq!(sort(&unknown), Err(TableError::UnknownDependency { call: "a", dep: "missing" }));
+        let duplicate = [Initcall::new("a", &[], ok), Initcall::new("a", &[], ok)];
+        assert_eq!(sort(&duplicate), Err(TableError::Duplicate("a")));
+    }
+}
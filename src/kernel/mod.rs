@@ -2,6 +2,10 @@
 //!
 //! 커널의 핵심 모듈들
 
+pub mod percpu;
 pub mod watchdog;
+pub mod softirq;
 pub mod error_recovery;
+pub mod uevent;
+pub mod initcall;
 
@@ -0,0 +1,219 @@
+//! CPU별 데이터 (per-CPU data)
+//!
+//! 각 CPU는 GS 베이스 레지스터가 가리키는 `PerCpu` 블록을 가집니다. 블록의 첫 필드는
+//! 블록 자신의 주소이므로 `mov reg, gs:[0]` 한 번으로 현재 CPU의 블록을 얻을 수 있고,
+//! LAPIC ID를 읽거나 전역 락을 잡을 필요가 없습니다.
+//!
+//! 블록은 주로 소유 CPU가 갱신하지만 다른 CPU도 읽을 수 있으므로(통계 합산, 작업 가져오기)
+//! 모든 필드는 원자 타입이나 락으로 감쌉니다. 접근은 `per_cpu!` 매크로를 사용합니다.
+//!
+//! ```ignore
+//! crate::per_cpu!(stats.interrupts).fetch_add(1, Ordering::Relaxed);
+//! let depth = crate::per_cpu!(irq_depth).load(Ordering::Relaxed);
+//! ```
+
+use alloc::boxed::Box;
+use alloc::vec;
+use core::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, AtomicU8, AtomicUsize, Ordering};
+use spin::Mutex;
+use x86_64::registers::model_specific::GsBase;
+use x86_64::VirtAddr;
+
+use crate::scheduler::affinity::MAX_CPUS;
+use crate::scheduler::round_robin::RoundRobinScheduler;
+
+/// CPU별 스크래치 스택 크기
+pub const SCRATCH_STACK_SIZE: usize = 16 * 1024;
+
+/// 현재 CPU의 per-CPU 필드 참조
+///
+/// `per_cpu!(irq_depth)`는 `&this_cpu().irq_depth`와 같습니다.
+#[macro_export]
+macro_rules! per_cpu {
+    ($($field:ident).+) => {
+        &$crate::kernel::percpu::this_cpu().$($field).+
+    };
+}
+
+/// CPU별 이벤트 카운터
+pub struct CpuStats {
+    pub context_switches: AtomicU64,
+    pub interrupts: AtomicU64,
+    pub syscalls: AtomicU64,
+    pub page_faults: AtomicU64,
+}
+
+impl CpuStats {
+    const fn new() -> Self {
+        Self {
+            context_switches: AtomicU64::new(0),
+            interrupts: AtomicU64::new(0),
+            syscalls: AtomicU64::new(0),
+            page_faults: AtomicU64::new(0),
+        }
+    }
+}
+
+/// CPU별 유휴 통계
+pub struct IdleStats {
+    /// 마지막으로 진입한 C-State
+    pub c_state: AtomicU8,
+    /// 현재 C-State 진입 시각 (ms)
+    pub c_state_entry_ms: AtomicU64,
+    /// 유휴 진입 횟수
+    pub entries: AtomicU64,
+    /// 누적 유휴 시간 (ns)
+    pub idle_ns: AtomicU64,
+}
+
+impl IdleStats {
+    const fn new() -> Self {
+        Self {
+            c_state: AtomicU8::new(0),
+            c_state_entry_ms: AtomicU64::new(0),
+            entries: AtomicU64::new(0),
+            idle_ns: AtomicU64::new(0),
+        }
+    }
+}
+
+/// CPU별 데이터 블록
+#[repr(C)]
+pub struct PerCpu {
+    /// 블록 자신의 주소 (반드시 첫 필드: `gs:[0]`으로 읽음)
+    self_addr: AtomicUsize,
+    /// 논리 CPU 번호 (0 = BSP)
+    pub cpu_id: u8,
+    /// Local APIC ID
+    pub apic_id: AtomicU32,
+    /// 하드웨어 인터럽트 중첩 깊이
+    pub irq_depth: AtomicU32,
+    /// softirq 실행 중 여부
+    pub in_softirq: AtomicBool,
+    /// 현재 실행 중인 스레드 ID (0 = 없음)
+    pub current_tid: AtomicU64,
+    /// 실행 큐 (현재 스레드 포함)
+    pub run_queue: Mutex<Option<RoundRobinScheduler>>,
+    /// 스크래치 스택 최상단 주소 (0 = 없음)
+    pub scratch_stack_top: AtomicU64,
+    /// 이벤트 카운터
+    pub stats: CpuStats,
+    /// 유휴 통계
+    pub idle: IdleStats,
+    /// tickless 유휴로 이 CPU의 틱이 멈춰 있는지
+    pub tick_stopped: AtomicBool,
+    /// 이 CPU가 할당 실패 복구 중인지 (복구 도중의 할당 실패는 다시 복구하지 않음)
+    pub in_alloc_recovery: AtomicBool,
+}
+
+impl PerCpu {
+    const fn new(cpu_id: u8) -> Self {
+        Self {
+            self_addr: AtomicUsize::new(0),
+            cpu_id,
+            apic_id: AtomicU32::new(0),
+            irq_depth: AtomicU32::new(0),
+            in_softirq: AtomicBool::new(false),
+            current_tid: AtomicU64::new(0),
+            run_queue: Mutex::new(None),
+            scratch_stack_top: AtomicU64::new(0),
+            stats: CpuStats::new(),
+            idle: IdleStats::new(),
+            tick_stopped: AtomicBool::new(false),
+            in_alloc_recovery: AtomicBool::new(false),
+        }
+    }
+}
+
+/// BSP의 블록 (GS 베이스 설정 전에도 사용)
+static BSP: PerCpu = PerCpu::new(0);
+
+/// CPU 번호 → 블록 주소
+static CPUS: [AtomicUsize; MAX_CPUS] = [const { AtomicUsize::new(0) }; MAX_CPUS];
+
+/// BSP의 GS 베이스 설정 완료 여부
+static GS_READY: AtomicBool = AtomicBool::new(false);
+
+/// BSP per-CPU 블록 초기화
+///
+/// # Safety
+/// 힙 초기화 후 BSP에서 한 번 호출되어야 합니다.
+pub unsafe fn init_bsp() {
+    install(&BSP, initial_apic_id());
+    GS_READY.store(true, Ordering::Release);
+    crate::log_info!("Per-CPU data: BSP block at {:#x}", &BSP as *const PerCpu as usize);
+}
+
+/// AP per-CPU 블록 생성 및 설치
+///
+/// AP가 per-CPU 데이터에 접근하기 전에 해당 AP에서 가장 먼저 호출해야 합니다.
+///
+/// # Safety
+/// AP 진입 직후, 해당 CPU에서 한 번만 호출되어야 합니다.
+pub unsafe fn init_ap(cpu_id: u8) -> &'static PerCpu {
+    let block: &'static PerCpu = Box::leak(Box::new(PerCpu::new(cpu_id)));
+    install(block, initial_apic_id());
+    block
+}
+
+unsafe fn install(block: &'static PerCpu, apic_id: u32) {
+    let addr = block as *const PerCpu as usize;
+    block.self_addr.store(addr, Ordering::Release);
+    block.apic_id.store(apic_id, Ordering::Release);
+
+    let stack: &'static mut [u8] = Box::leak(vec![0u8; SCRATCH_STACK_SIZE].into_boxed_slice());
+    let top = (stack.as_mut_ptr() as u64 + SCRATCH_STACK_SIZE as u64) & !0xF;
+    block.scratch_stack_top.store(top, Ordering::Release);
+
+    GsBase::write(VirtAddr::new(addr as u64));
+    if let Some(slot) = CPUS.get(block.cpu_id as usize) {
+        slot.store(addr, Ordering::Release);
+    }
+}
+
+/// CPUID에서 초기 APIC ID 읽기
+fn initial_apic_id() -> u32 {
+    core::arch::x86_64::__cpuid(1).ebx >> 24
+}
+
+/// 현재 CPU의 블록
+#[inline]
+pub fn this_cpu() -> &'static PerCpu {
+    if !GS_READY.load(Ordering::Acquire) {
+        return &BSP;
+    }
+    let addr: usize;
+    // SAFETY: GS 베이스는 `install`에서 유효한 `PerCpu`를 가리키도록 설정되었고,
+    // 블록은 해제되지 않음
+    unsafe {
+        core::arch::asm!("mov {}, gs:[0]", out(reg) addr, options(nostack, preserves_flags, readonly));
+        &*(addr as *const PerCpu)
+    }
+}
+
+/// 현재 CPU 번호
+#[inline]
+pub fn cpu_id() -> u8 {
+    this_cpu().cpu_id
+}
+
+/// 지정한 CPU의 블록
+pub fn cpu(cpu_id: u8) -> Option<&'static PerCpu> {
+    let addr = CPUS.get(cpu_id as usize)?.load(Ordering::Acquire);
+    if addr == 0 {
+        // BSP는 GS 설정 전에도 사용 가능
+        return if cpu_id == 0 { Some(&BSP) } else { None };
+    }
+    // SAFETY: 등록된 주소는 해제되지 않는 `PerCpu`를 가리킴
+    Some(unsafe { &*(addr as *const PerCpu) })
+}
+
+/// 등록된 모든 CPU의 블록
+pub fn iter() -> impl Iterator<Item = &'static PerCpu> {
+    (0..MAX_CPUS as u8).filter_map(cpu)
+}
+
+/// 모든 CPU의 카운터 합계
+pub fn sum(field: impl Fn(&PerCpu) -> &AtomicU64) -> u64 {
+    iter().map(|c| field(c).load(Ordering::Relaxed)).sum()
+}
@@ -0,0 +1,126 @@
+//! Softirq (지연된 인터럽트 처리)
+//!
+//! 하드웨어 인터럽트 핸들러는 최소한의 작업만 하고 softirq를 올린(raise) 뒤 EOI를 보냅니다.
+//! 핸들러 종료 직전 `irq_exit`가 인터럽트를 다시 허용한 상태에서 대기 중인 softirq를 실행합니다.
+//!
+//! softirq 핸들러는 인터럽트가 허용된 상태로 실행되므로, 스레드 컨텍스트와 공유하는
+//! 락은 스레드 쪽에서 `without_interrupts`로 감싸서 잡아야 합니다.
+//!
+//! 하드웨어 인터럽트 중첩 깊이와 softirq 실행 여부는 CPU별 데이터(`PerCpu`)에 기록되므로
+//! 각 CPU는 자신이 받은 인터럽트의 softirq를 독립적으로 처리합니다.
+
+use core::sync::atomic::{AtomicU32, Ordering};
+use spin::Mutex;
+use x86_64::instructions::interrupts;
+
+/// Softirq 종류
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum SoftIrq {
+    /// 타이머 만료 처리 (hrtimer, 타이머 휠)
+    Timer = 0,
+    /// 네트워크 수신/송신 후처리
+    Net = 1,
+}
+
+/// 최대 softirq 종류 수
+const MAX_SOFTIRQS: usize = 8;
+
+/// 한 번의 `irq_exit`에서 반복 처리할 최대 라운드 수 (무한 재발생 방지)
+const MAX_RESTART: u32 = 10;
+
+/// 대기 중인 softirq 비트맵
+static PENDING: AtomicU32 = AtomicU32::new(0);
+
+/// 등록된 핸들러
+static HANDLERS: Mutex<[Option<fn()>; MAX_SOFTIRQS]> = Mutex::new([None; MAX_SOFTIRQS]);
+
+/// softirq 핸들러 등록
+pub fn register(irq: SoftIrq, handler: fn()) {
+    interrupts::without_interrupts(|| {
+        HANDLERS.lock()[irq as usize] = Some(handler);
+    });
+}
+
+/// softirq 올리기
+///
+/// 인터럽트 컨텍스트와 스레드 컨텍스트 모두에서 호출할 수 있습니다.
+pub fn raise(irq: SoftIrq) {
+    PENDING.fetch_or(1 << irq as u32, Ordering::AcqRel);
+}
+
+/// 대기 중인 softirq가 있는지 확인
+pub fn has_pending() -> bool {
+    PENDING.load(Ordering::Acquire) != 0
+}
+
+/// 하드웨어 인터럽트 핸들러 진입 시 호출
+///
+/// `irq_exit`와 짝을 이뤄 현재 CPU의 인터럽트 중첩 깊이를 기록합니다.
+pub fn irq_enter() {
+    crate::per_cpu!(irq_depth).fetch_add(1, Ordering::Relaxed);
+}
+
+/// 하드웨어 인터럽트 핸들러 종료 시 호출
+///
+/// EOI를 보낸 뒤 호출해야 합니다. 대기 중인 softirq가 있으면 인터럽트를 허용한 상태로
+/// 실행하고, 반환 전에 다시 인터럽트를 막습니다. 중첩된 인터럽트에서는 softirq를
+/// 실행하지 않고 가장 바깥쪽 핸들러에 맡깁니다.
+pub fn irq_exit() {
+    let depth = crate::per_cpu!(irq_depth);
+    let outer = depth
+        .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |d| Some(d.saturating_sub(1)))
+        .map_or(true, |prev| prev <= 1);
+    if !outer || !has_pending() {
+        return;
+    }
+    let in_softirq = crate::per_cpu!(in_softirq);
+    if in_softirq.swap(true, Ordering::AcqRel) {
+        // 이미 바깥쪽에서 softirq 실행 중: 그쪽 루프가 처리
+        return;
+    }
+
+    interrupts::enable();
+    run_handlers();
+    interrupts::disable();
+
+    in_softirq.store(false, Ordering::Release);
+}
+
+/// 현재 CPU가 하드웨어 인터럽트나 softirq를 처리 중인지 확인
+pub fn in_interrupt() -> bool {
+    crate::per_cpu!(irq_depth).load(Ordering::Relaxed) > 0
+        || crate::per_cpu!(in_softirq).load(Ordering::Relaxed)
+}
+
+/// 스레드 컨텍스트에서 대기 중인 softirq 실행 (예: 유휴 루프)
+pub fn run_pending() {
+    if !has_pending() {
+        return;
+    }
+    let in_softirq = crate::per_cpu!(in_softirq);
+    if in_softirq.swap(true, Ordering::AcqRel) {
+        return;
+    }
+    run_handlers();
+    in_softirq.store(false, Ordering::Release);
+}
+
+/// 대기 비트를 비우면서 핸들러 실행
+fn run_handlers() {
+    let handlers = interrupts::without_interrupts(|| *HANDLERS.lock());
+
+    for _ in 0..MAX_RESTART {
+        let pending = PENDING.swap(0, Ordering::AcqRel);
+        if pending == 0 {
+            return;
+        }
+        for (i, handler) in handlers.iter().enumerate() {
+            if pending & (1 << i) != 0 {
+                if let Some(handler) = handler {
+                    handler();
+                }
+            }
+        }
+    }
+}
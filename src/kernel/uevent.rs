@@ -0,0 +1,296 @@
+//! 커널 이벤트 버스 (uevent)
+//!
+//! 서브시스템이 디바이스 추가/제거, 전원 소스 변경, 배터리 수준, 열 조치,
+//! 네트워크 링크 변화 같은 타입이 있는 이벤트를 발행(`publish`)하고, 데스크톱 알림이나
+//! 셸 같은 소비자가 카테고리 필터로 구독(`subscribe`)합니다.
+//!
+//! ## 컨텍스트
+//! - `publish`는 인터럽트 핸들러와 softirq(타이머 틱의 열 점검 등)에서도 호출되므로
+//!   힙을 할당하지 않습니다. 구독자 큐는 구독 시점에 미리 할당되며, 가득 차면 가장
+//!   오래된 이벤트를 버리고 버린 개수를 셉니다.
+//! - 최근 이벤트는 고정 크기 링 버퍼(`history`)에도 남아 구독 이전 이벤트를 조회할 수 있습니다.
+
+use alloc::collections::VecDeque;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::fmt;
+use core::ops::BitOr;
+use spin::Mutex;
+use x86_64::instructions::interrupts;
+
+use crate::drivers::base::{DeviceId, DeviceInfo};
+use crate::power::battery::BatteryStatus;
+use crate::power::temps::ThermalAction;
+use crate::task::Event;
+
+/// 구독자별 큐 길이
+const SUBSCRIBER_QUEUE_LEN: usize = 32;
+
+/// 전역 이력 링 버퍼 길이
+const HISTORY_LEN: usize = 64;
+
+/// 커널 이벤트
+#[derive(Debug, Clone, Copy)]
+pub enum KernelEvent {
+    /// 디바이스 모델에 디바이스 등록
+    DeviceAdded { id: DeviceId, info: DeviceInfo },
+    /// 디바이스 모델에서 디바이스 제거
+    DeviceRemoved { id: DeviceId, info: DeviceInfo },
+    /// 전원 소스 변경 (AC 연결/분리)
+    PowerSource { on_ac: bool },
+    /// 배터리 상태 또는 잔량 변경
+    Battery { percent: u8, status: BatteryStatus },
+    /// 열 조치 단계 변경
+    Thermal { action: ThermalAction, temperature_c: Option<u8> },
+    /// 네트워크 링크 상태 변경
+    NetworkLink { up: bool },
+}
+
+impl KernelEvent {
+    /// 이벤트가 속한 필터 카테고리
+    pub fn category(&self) -> EventFilter {
+        match self {
+            KernelEvent::DeviceAdded { .. } | KernelEvent::DeviceRemoved { .. } => EventFilter::DEVICE,
+            KernelEvent::PowerSource { .. } => EventFilter::POWER,
+            KernelEvent::Battery { .. } => EventFilter::BATTERY,
+            KernelEvent::Thermal { .. } => EventFilter::THERMAL,
+            KernelEvent::NetworkLink { .. } => EventFilter::NETWORK,
+        }
+    }
+}
+
+/// 디바이스 정보 요약 (`pci 8086:100e`, `usb 046d:c52b`, `acpi PNP0303`)
+fn describe_device(info: &DeviceInfo, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match info {
+        DeviceInfo::Pci(pci) => write!(f, "pci {:04x}:{:04x}", pci.vendor_id, pci.device_id),
+        DeviceInfo::Usb { vendor_id, product_id, .. } => write!(f, "usb {:04x}:{:04x}", vendor_id, product_id),
+        DeviceInfo::I2c { address, hid } => match hid {
+            Some(hid) => write!(f, "i2c {} @{:02x}", hid.as_str(), address),
+            None => write!(f, "i2c @{:02x}", address),
+        },
+        DeviceInfo::Platform { hid: Some(hid), .. } => write!(f, "acpi {}", hid.as_str()),
+        DeviceInfo::Platform { hid: None, .. } => write!(f, "platform"),
+    }
+}
+
+impl fmt::Display for KernelEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            KernelEvent::DeviceAdded { id, info } => {
+                write!(f, "Device {} added (", id)?;
+                describe_device(info, f)?;
+                write!(f, ")")
+            }
+            KernelEvent::DeviceRemoved { id, info } => {
+                write!(f, "Device {} removed (", id)?;
+                describe_device(info, f)?;
+                write!(f, ")")
+            }
+            KernelEvent::PowerSource { on_ac: true } => write!(f, "AC power connected"),
+            KernelEvent::PowerSource { on_ac: false } => write!(f, "Running on battery"),
+            KernelEvent::Battery { percent, status } => write!(f, "Battery {}% ({:?})", percent, status),
+            KernelEvent::Thermal { action, temperature_c: Some(t) } => write!(f, "Thermal {:?} at {}C", action, t),
+            KernelEvent::Thermal { action, temperature_c: None } => write!(f, "Thermal {:?}", action),
+            KernelEvent::NetworkLink { up: true } => write!(f, "Network link up"),
+            KernelEvent::NetworkLink { up: false } => write!(f, "Network link down"),
+        }
+    }
+}
+
+/// 구독 필터 (이벤트 카테고리 비트마스크)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EventFilter(u32);
+
+impl EventFilter {
+    pub const NONE: Self = Self(0);
+    pub const DEVICE: Self = Self(1 << 0);
+    pub const POWER: Self = Self(1 << 1);
+    pub const BATTERY: Self = Self(1 << 2);
+    pub const THERMAL: Self = Self(1 << 3);
+    pub const NETWORK: Self = Self(1 << 4);
+    pub const ALL: Self = Self(0x1F);
+
+    /// 필터가 이벤트를 통과시키는지 확인
+    pub fn accepts(&self, event: &KernelEvent) -> bool {
+        self.0 & event.category().0 != 0
+    }
+
+    /// 카테고리 이름으로 필터 생성 (`device`, `power`, `battery`, `thermal`, `network`, `all`)
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "device" => Some(Self::DEVICE),
+            "power" => Some(Self::POWER),
+            "battery" => Some(Self::BATTERY),
+            "thermal" => Some(Self::THERMAL),
+            "network" => Some(Self::NETWORK),
+            "all" => Some(Self::ALL),
+            _ => None,
+        }
+    }
+}
+
+impl BitOr for EventFilter {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
+    }
+}
+
+/// 발행된 이벤트 기록
+#[derive(Debug, Clone, Copy)]
+pub struct EventRecord {
+    /// 발행 순번 (1부터 단조 증가)
+    pub seq: u64,
+    /// 발행 시각 (부팅 후 밀리초)
+    pub time_ms: u64,
+    pub event: KernelEvent,
+}
+
+struct Subscriber {
+    filter: EventFilter,
+    queue: VecDeque<EventRecord>,
+    /// 큐가 가득 차서 버린 이벤트 수
+    dropped: u64,
+    event: Arc<Event>,
+}
+
+struct Bus {
+    next_seq: u64,
+    history: [Option<EventRecord>; HISTORY_LEN],
+    /// 다음에 기록할 이력 슬롯
+    history_head: usize,
+    subscribers: Vec<Option<Subscriber>>,
+}
+
+static BUS: Mutex<Bus> = Mutex::new(Bus {
+    next_seq: 1,
+    history: [None; HISTORY_LEN],
+    history_head: 0,
+    subscribers: Vec::new(),
+});
+
+/// 이벤트 발행 (인터럽트 컨텍스트에서 호출 가능)
+pub fn publish(event: KernelEvent) {
+    let time_ms = crate::drivers::timer::get_milliseconds();
+    interrupts::without_interrupts(|| {
+        let mut bus = BUS.lock();
+        let record = EventRecord { seq: bus.next_seq, time_ms, event };
+        bus.next_seq += 1;
+        let head = bus.history_head;
+        bus.history[head] = Some(record);
+        bus.history_head = (head + 1) % HISTORY_LEN;
+
+        for sub in bus.subscribers.iter_mut().flatten() {
+            if !sub.filter.accepts(&event) {
+                continue;
+            }
+            // 용량을 넘지 않게 유지해 재할당하지 않음
+            if sub.queue.len() >= SUBSCRIBER_QUEUE_LEN {
+                sub.queue.pop_front();
+                sub.dropped += 1;
+            }
+            sub.queue.push_back(record);
+            sub.event.signal();
+        }
+    });
+}
+
+/// 필터에 맞는 이벤트 구독
+///
+/// 반환된 `Subscription`을 drop하면 구독이 해제됩니다.
+pub fn subscribe(filter: EventFilter) -> Subscription {
+    let event = Arc::new(Event::new());
+    let subscriber = Subscriber {
+        filter,
+        queue: VecDeque::with_capacity(SUBSCRIBER_QUEUE_LEN),
+        dropped: 0,
+        event: event.clone(),
+    };
+    let slot = interrupts::without_interrupts(|| {
+        let mut bus = BUS.lock();
+        match bus.subscribers.iter().position(|s| s.is_none()) {
+            Some(slot) => {
+                bus.subscribers[slot] = Some(subscriber);
+                slot
+            }
+            None => {
+                bus.subscribers.push(Some(subscriber));
+                bus.subscribers.len() - 1
+            }
+        }
+    });
+    Subscription { slot, event }
+}
+
+/// 이벤트 구독 핸들
+pub struct Subscription {
+    slot: usize,
+    event: Arc<Event>,
+}
+
+impl Subscription {
+    /// 대기하지 않고 다음 이벤트 가져오기
+    pub fn try_recv(&self) -> Option<EventRecord> {
+        interrupts::without_interrupts(|| {
+            BUS.lock().subscribers.get_mut(self.slot)?.as_mut()?.queue.pop_front()
+        })
+    }
+
+    /// 다음 이벤트가 올 때까지 대기
+    pub async fn recv(&self) -> EventRecord {
+        loop {
+            if let Some(record) = self.try_recv() {
+                return record;
+            }
+            self.event.wait().await;
+        }
+    }
+
+    /// 큐가 가득 차서 버려진 이벤트 수
+    pub fn dropped(&self) -> u64 {
+        interrupts::without_interrupts(|| {
+            BUS.lock().subscribers.get(self.slot).and_then(|s| s.as_ref()).map_or(0, |s| s.dropped)
+        })
+    }
+}
+
+impl Drop for Subscription {
+    fn drop(&mut self) {
+        // 큐 해제는 락 밖에서
+        let removed = interrupts::without_interrupts(|| {
+            BUS.lock().subscribers.get_mut(self.slot).and_then(Option::take)
+        });
+        drop(removed);
+    }
+}
+
+/// 최근 이벤트 이력 (오래된 것부터)
+pub fn history(filter: EventFilter) -> Vec<EventRecord> {
+    let (records, head) = interrupts::without_interrupts(|| {
+        let bus = BUS.lock();
+        (bus.history, bus.history_head)
+    });
+    (0..HISTORY_LEN)
+        .filter_map(|i| records[(head + i) % HISTORY_LEN])
+        .filter(|r| filter.accepts(&r.event))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test_case]
+    fn test_event_filter_categories() {
+        let battery = KernelEvent::Battery { percent: 50, status: BatteryStatus::Discharging };
+        let link = KernelEvent::NetworkLink { up: true };
+        let filter = EventFilter::BATTERY | EventFilter::POWER;
+        assert!(filter.accepts(&battery));
+        assert!(!filter.accepts(&link));
+        assert!(EventFilter::ALL.accepts(&link));
+        assert!(!EventFilter::NONE.accepts(&battery));
+        assert_eq!(EventFilter::from_name("thermal"), Some(EventFilter::THERMAL));
+        assert_eq!(EventFilter::from_name("bogus"), None);
+    }
+}
@@ -8,26 +8,49 @@ use spin::Mutex;
 /// Watchdog 타임아웃 (초)
 const WATCHDOG_TIMEOUT_SEC: u64 = 60; // 60초
 
+/// Watchdog 체크 주기 (ms)
+const WATCHDOG_CHECK_INTERVAL_MS: u64 = 1000;
+
 /// 마지막 heartbeat 시간 (타이머 틱)
 static LAST_HEARTBEAT: AtomicU64 = AtomicU64::new(0);
 
 /// Watchdog 활성화 여부
 static WATCHDOG_ENABLED: AtomicBool = AtomicBool::new(true);
 
+/// 주기 체크 타이머 ID
+static CHECK_TIMER: Mutex<Option<crate::time::TimerId>> = Mutex::new(None);
+
 /// Watchdog 초기화
+///
+/// 타이밍 휠에 1초 주기 체크 타이머를 등록합니다.
+/// 타이머 서브시스템(`time::init`) 초기화 후에 호출되어야 합니다.
 pub fn init() {
     LAST_HEARTBEAT.store(crate::drivers::timer::get_milliseconds(), Ordering::Release);
     WATCHDOG_ENABLED.store(true, Ordering::Release);
+    
+    let mut timer = CHECK_TIMER.lock();
+    if timer.is_none() {
+        *timer = Some(crate::time::wheel::add_timer_ms(WATCHDOG_CHECK_INTERVAL_MS, check_timer_expired, 0));
+    }
+    
     crate::log_info!("Watchdog initialized (timeout: {}s)", WATCHDOG_TIMEOUT_SEC);
 }
 
+/// 주기 체크 타이머 콜백 (타이머 softirq)
+fn check_timer_expired(_data: usize) -> crate::time::TimerRestart {
+    check();
+    crate::time::TimerRestart::Restart {
+        period_ns: WATCHDOG_CHECK_INTERVAL_MS * crate::time::NSEC_PER_MSEC,
+    }
+}
+
 /// Heartbeat 업데이트 (scheduler tick에서 호출)
 pub fn heartbeat() {
     let now = crate::drivers::timer::get_milliseconds();
     LAST_HEARTBEAT.store(now, Ordering::Release);
 }
 
-/// Watchdog 체크 (타이머 인터럽트에서 호출)
+/// Watchdog 체크 (주기 타이머에서 호출)
 pub fn check() {
     if !WATCHDOG_ENABLED.load(Ordering::Acquire) {
         return;
@@ -38,8 +38,10 @@ fn panic(info: &core::panic::PanicInfo) -> ! {
 
 // 모듈 선언
 pub mod boot;
+pub mod time;
 pub mod memory;
 pub mod scheduler;
+pub mod task;
 pub mod power;
 pub mod drivers;
 pub mod interrupts;
@@ -52,7 +54,7 @@ pub mod fs;
 pub mod net;
 #[cfg(feature = "gui")]
 pub mod gui;
-#[cfg(feature = "smp")]
+// Local APIC(`smp::apic`)은 항상 사용하며, `smp` 기능은 AP 시작 여부만 결정
 pub mod smp;
 
 pub mod logging;
@@ -4,6 +4,7 @@
 //! 현재는 시리얼 포트를 통한 로깅만 지원합니다.
 
 use core::fmt;
+use core::sync::atomic::{AtomicU8, Ordering};
 use spin::Mutex;
 
 /// 로그 레벨
@@ -21,7 +22,22 @@ pub enum LogLevel {
     Trace = 4,
 }
 
-/// 현재 로그 레벨 (컴파일 타임/프로파일 기반 설정)
+impl LogLevel {
+    pub const ALL: [LogLevel; 5] = [LogLevel::Error, LogLevel::Warn, LogLevel::Info, LogLevel::Debug, LogLevel::Trace];
+
+    /// 소문자 이름 (예: "info")
+    pub fn name(&self) -> &'static str {
+        match self {
+            LogLevel::Error => "error",
+            LogLevel::Warn => "warn",
+            LogLevel::Info => "info",
+            LogLevel::Debug => "debug",
+            LogLevel::Trace => "trace",
+        }
+    }
+}
+
+/// 기본 로그 레벨 (컴파일 타임/프로파일 기반 설정, 런타임에 `set_level`로 변경 가능)
 ///
 /// - debug 빌드: Debug
 /// - release + power_saver: Warn (저전력, 경량)
@@ -31,6 +47,19 @@ pub const LOG_LEVEL: LogLevel = LogLevel::Debug;
 #[cfg(not(debug_assertions))]
 pub const LOG_LEVEL: LogLevel = LogLevel::Info;
 
+/// 현재 로그 레벨 (`LogLevel` 값)
+static CURRENT_LEVEL: AtomicU8 = AtomicU8::new(LOG_LEVEL as u8);
+
+/// 현재 로그 레벨
+pub fn level() -> LogLevel {
+    LogLevel::ALL[CURRENT_LEVEL.load(Ordering::Relaxed) as usize]
+}
+
+/// 로그 레벨 변경 (이 레벨보다 상세한 로그는 버림)
+pub fn set_level(level: LogLevel) {
+    CURRENT_LEVEL.store(level as u8, Ordering::Relaxed);
+}
+
 const RING_CAPACITY: usize = 256;
 const MAX_LOG_LINE_LEN: usize = 128;
 
@@ -118,7 +147,7 @@ static LOG_RING: Mutex<LogRing> = Mutex::new(LogRing::new());
 
 /// 로그 출력 함수
 pub fn log(level: LogLevel, args: fmt::Arguments) {
-    if level <= LOG_LEVEL {
+    if level <= self::level() {
         let prefix = match level {
             LogLevel::Error => "[ERROR]",
             LogLevel::Warn => "[WARN] ",
@@ -13,6 +13,7 @@
 extern crate simple_os;
 use simple_os::drivers::serial;
 use simple_os::interrupts;
+use simple_os::kernel::initcall::Initcall;
 use bootloader_api::{BootInfo, entry_point};
 
 entry_point!(kernel_main);
@@ -25,16 +26,15 @@ entry_point!(kernel_main);
 /// 초기화 순서:
 /// 1. 인터럽트 디스크립터 테이블 (IDT) 설정
 /// 2. 메모리 관리자 초기화
-/// 3. 스케줄러 초기화
-/// 4. 전력 관리 초기화
-/// 5. 드라이버 초기화
-/// 6. Shell/GUI 시작
+/// 3. initcall 테이블 (타이머, 스케줄러, 전력 관리, 드라이버 등)
+/// 4. Shell/GUI 시작
 fn kernel_main(boot_info: &'static mut BootInfo) -> ! {
     // 커널 초기화
     kernel_init(boot_info);
     
     // 메인 루프
     loop {
+        simple_os::task::run_ready();
         // 유휴 상태에서 CPU를 대기 상태로 전환 (전력 절약)
         if let Some(pm) = simple_os::power::get_manager() {
             let mut guard = pm.lock();
@@ -58,6 +58,7 @@ fn kernel_init(boot_info: &'static mut BootInfo) {
     serial::init();
     simple_os::boot::mark_stage(simple_os::boot::BootStage::SerialInit);
     simple_os::log_info!("Simple OS Kernel Starting...");
+    simple_os::config::cmdline::init(simple_os::config::cmdline::BUILTIN_CMDLINE);
     
     // 2. 부트 정보 저장
     unsafe {
@@ -82,8 +83,9 @@ fn kernel_init(boot_info: &'static mut BootInfo) {
     simple_os::boot::mark_stage(simple_os::boot::BootStage::PicRemap);
     simple_os::log_info!("PIC remapped");
     
-    // 4. IDT 설정
+    // 4. GDT/TSS 및 IDT 설정 (IST 스택을 쓰는 IDT보다 TSS가 먼저 로드되어야 함)
     unsafe {
+        interrupts::gdt::init();
         interrupts::idt::init();
     }
     simple_os::boot::mark_stage(simple_os::boot::BootStage::IdtInit);
@@ -94,7 +96,7 @@ fn kernel_init(boot_info: &'static mut BootInfo) {
     simple_os::boot::mark_stage(simple_os::boot::BootStage::InterruptsEnabled);
     simple_os::log_info!("Interrupts enabled");
     
-    // 6. 메모리 관리자 초기화
+    // 6. 메모리 관리자 초기화 (이후 initcall 테이블이 힙을 사용)
     unsafe {
         match simple_os::memory::init(boot_info) {
             Ok(()) => {
@@ -108,6 +110,9 @@ fn kernel_init(boot_info: &'static mut BootInfo) {
                 } else {
                     simple_os::log_info!("ASLR enabled");
                 }
+                
+                // BSP per-CPU 데이터 (GS 베이스) 설정
+                simple_os::kernel::percpu::init_bsp();
             }
             Err(e) => {
                 simple_os::log_error!("Failed to initialize memory management: {:?}", e);
@@ -116,295 +121,25 @@ fn kernel_init(boot_info: &'static mut BootInfo) {
         }
     }
     
-    // 7. 타이머 드라이버 초기화
-    unsafe {
-        simple_os::drivers::timer::init();
-        // PIC에서 타이머 인터럽트 활성화 (IRQ 0)
-        interrupts::pic::set_mask(0, true);
-    }
-    simple_os::boot::mark_stage(simple_os::boot::BootStage::TimerInit);
-    simple_os::log_info!("Timer driver initialized");
-    
-    // 8. 키보드 드라이버 초기화
-    unsafe {
-        simple_os::drivers::keyboard::init();
-        // PIC에서 키보드 인터럽트 활성화 (IRQ 1)
-        interrupts::pic::set_mask(1, true);
-    }
-    simple_os::boot::mark_stage(simple_os::boot::BootStage::KeyboardInit);
-    simple_os::log_info!("Keyboard driver initialized");
-    
-    // 9. VGA 드라이버 초기화
-    simple_os::drivers::vga::init();
-    simple_os::boot::mark_stage(simple_os::boot::BootStage::VgaInit);
-    simple_os::vga_println!("Simple OS Kernel");
-    simple_os::vga_println!("==================");
-    simple_os::vga_println!("Uptime: {} ms", simple_os::drivers::timer::get_milliseconds());
-    
-    // 10. 스케줄러 초기화
-    // 시간 할당량: 10 타이머 틱 (약 10ms)
-    simple_os::scheduler::init(10);
-    simple_os::boot::mark_stage(simple_os::boot::BootStage::SchedulerInit);
-    simple_os::log_info!("Scheduler initialized");
-    
-    // 11. 시스템 콜 핸들러 초기화
-    simple_os::syscall::init_syscall_handler();
-    simple_os::boot::mark_stage(simple_os::boot::BootStage::SyscallInit);
-    simple_os::log_info!("System call handler initialized");
-    
-    // 12. ATA 드라이버 초기화 (파일시스템 기능 사용 시에만)
-    #[cfg(feature = "fs")]
-    {
-        unsafe { simple_os::drivers::ata::init(); }
-        simple_os::boot::mark_stage(simple_os::boot::BootStage::AtaInit);
-        simple_os::log_info!("ATA driver initialization attempted");
-        match simple_os::config::profile::current_profile() {
-            simple_os::config::profile::Profile::PowerSaver => {
-                simple_os::drivers::ata::set_idle_timeout_ms(30000);
-                simple_os::log_info!("ATA idle timeout set to 30000ms (power_saver)");
-            }
-            _ => {}
-        }
-    }
-    
-    // 13. 파일시스템 초기화 (ATA가 감지된 경우)
-    // TODO: FAT32와 ATA를 완전히 통합한 후 활성화
-    #[cfg(feature = "fs")]
-    {
-        // NVMe 초기화(옵션)
-        #[cfg(feature = "nvme")]
-        unsafe {
-            match simple_os::drivers::nvme::init() {
-                Ok(()) => {
-                    simple_os::log_info!("NVMe controller initialized (skeleton)");
-                }
-                Err(_e) => {
-                    simple_os::log_info!("No NVMe controller or init failed (skipping)");
-                }
-            }
-        }
-        simple_os::boot::mark_stage(simple_os::boot::BootStage::FilesystemReady);
-        simple_os::log_info!("Filesystem module ready");
-    }
-    
-    // 14. 전력 관리 초기화
-    unsafe {
-        match simple_os::power::init() {
-            Ok(()) => {
-                simple_os::boot::mark_stage(simple_os::boot::BootStage::PowerInit);
-                simple_os::log_info!("Power management initialized");
-                
-                // RAPL 전력 측정 초기화 (Intel only)
-                #[cfg(feature = "intel_rapl")]
-                simple_os::power::rapl::init_rapl_measurement();
-                
-                // CPU 온도 모니터링 초기화
-                simple_os::power::temps::init_thermal_monitoring();
-                
-                // 디바이스별 전력 프로파일 초기화
-                simple_os::power::device_policy::init_device_power_profiles();
-                
-                // 전력 프로파일 적용
-                if let Err(e) = simple_os::power::device_policy::apply_device_power_policies() {
-                    simple_os::log_warn!("Failed to apply device power policies: {:?}", e);
-                }
-                
-                // 메모리 압축 초기화
-                simple_os::memory::compression::init_compression(32); // 최대 32개 페이지 압축
-                
-                // 메모리 단편화 모니터링 초기화
-                simple_os::memory::fragmentation::init_fragmentation_monitoring(100); // 최대 100개 히스토리
-                
-                // OOM Killer 초기화
-                let oom_config = simple_os::memory::oom_killer::OomKillerConfig {
-                    memory_threshold_percent: 5,
-                    min_memory_bytes: 1024 * 1024, // 1MB
-                    enabled: true,
-                };
-                simple_os::memory::oom_killer::init_oom_killer(oom_config);
-                
-                // 메모리 누수 감지기 활성화 (디버그 모드)
-                #[cfg(debug_assertions)]
-                {
-                    simple_os::memory::leak_detector::set_enabled(true);
-                    simple_os::log_info!("Memory leak detector enabled");
-                }
-                
-                // 사용자 활동 감지기 초기화
-                simple_os::power::user_activity::init_user_activity_detector(100, 30000); // 30초 유휴 임계값
-                
-                // 배터리 관리자 초기화
-                if let Err(e) = simple_os::power::battery::init_battery_manager() {
-                    simple_os::log_warn!("Failed to initialize battery manager: {:?}", e);
-                }
-            }
-            Err(e) => {
-                simple_os::log_warn!("Failed to initialize power management: {:?}", e);
-                // 전력 관리 초기화 실패해도 커널은 계속 실행 가능
-            }
-        }
-    }
-    
-    // 15. 네트워크 드라이버 초기화
-    #[cfg(feature = "net")]
-    unsafe {
-        match simple_os::net::init_network() {
-            Ok(()) => {
-                simple_os::boot::mark_stage(simple_os::boot::BootStage::NetworkInit);
-                simple_os::log_info!("Network driver initialized");
-                if let Ok(mac) = simple_os::net::get_mac_address() {
-                    simple_os::log_info!("Network MAC address: {}", mac);
-                }
-                match simple_os::config::profile::current_profile() {
-                    simple_os::config::profile::Profile::PowerSaver => {
-                        simple_os::drivers::rtl8139::set_idle_timeout_ms(10000);
-                        simple_os::log_info!("Network idle timeout set to 10000ms (power_saver)");
-                    }
-                    _ => {}
-                }
-                // Auto DHCP bring-up (wired or once Wi‑Fi is up)
-                simple_os::net::driver::bringup_ipv4_via_dhcp();
-            }
-            Err(e) => {
-                simple_os::log_warn!("Failed to initialize network driver: {:?}", e);
-            }
-        }
-    }
-
-    // 15.2. Wi‑Fi 초기화 (가능한 경우)
-    #[cfg(feature = "wifi")]
-    unsafe {
-        match simple_os::drivers::wifi::init() {
-            Ok(()) => {
-                simple_os::log_info!("Wi‑Fi driver initialized (skeleton)");
-                // 연결 프로파일이 있다면 자동 스캔/연결 시도
-                if let (Some(ssid), Some(psk)) = (option_env!("SIMPLEOS_WIFI_SSID"), option_env!("SIMPLEOS_WIFI_PSK")) {
-                    simple_os::log_info!("Wi‑Fi autoconnect profile found: {}", ssid);
-                    let _ = simple_os::net::ieee80211::start_scan();
-                    let ssid_obj = simple_os::net::ieee80211::Ssid(alloc::string::String::from(ssid));
-                    let sec = simple_os::net::ieee80211::WifiSec::Wpa2Psk(alloc::string::String::from(psk));
-                    let _ = simple_os::net::ieee80211::connect(&ssid_obj, &sec);
-                } else {
-                    simple_os::log_info!("No Wi‑Fi autoconnect profile (set SIMPLEOS_WIFI_SSID/PSK at build time)");
-                }
-            }
-            Err(_e) => {
-                simple_os::log_info!("No supported Wi‑Fi device or init failed (skipping)");
-            }
-        }
-    }
-    
-    // 15.3. 오디오 드라이버 초기화
-    #[cfg(feature = "audio")]
-    unsafe {
-        match simple_os::drivers::audio::init() {
-            Ok(()) => {
-                simple_os::log_info!("Audio subsystem initialized");
-            }
-            Err(e) => {
-                simple_os::log_warn!("Failed to initialize audio subsystem: {}", e);
-                // 오디오 초기화 실패해도 커널은 계속 실행 가능
-            }
-        }
-    }
+    // 7. 서브시스템 initcall (의존성 순서, 병렬 가능한 것은 AP에서 실행)
+    simple_os::kernel::initcall::run(INITCALLS);
     
-    // 15.5. USB 드라이버 초기화
-    #[cfg(feature = "usb")]
-    unsafe {
-        match simple_os::drivers::usb::init() {
-            Ok(()) => {
-                simple_os::log_info!("USB subsystem initialized");
-                // USB 디바이스 열거 시도
-                if let Err(e) = simple_os::drivers::usb::enumerate_devices() {
-                    simple_os::log_warn!("Failed to enumerate USB devices: {}", e);
-                    // 열거 실패해도 커널은 계속 실행 가능
-                }
-            }
-            Err(e) => {
-                simple_os::log_warn!("Failed to initialize USB subsystem: {}", e);
-                // USB 초기화 실패해도 커널은 계속 실행 가능
-            }
-        }
-    }
-    
-    // 15.6. Unsafe 블록 통계 출력 (디버그 모드)
+    // 8. Unsafe 블록 통계 출력 (디버그 모드)
     #[cfg(debug_assertions)]
     {
         simple_os::safety::print_unsafe_stats();
     }
     
-    // 16. 프레임버퍼 초기화 (GUI 지원)
-    #[cfg(feature = "gui")]
-    unsafe {
-        if let Some(framebuffer) = simple_os::boot::info::get_framebuffer() {
-            simple_os::drivers::framebuffer::init(framebuffer);
-            simple_os::boot::mark_stage(simple_os::boot::BootStage::FramebufferInit);
-            simple_os::log_info!("Framebuffer initialized");
-            match simple_os::gui::init() {
-                Ok(()) => {
-                    simple_os::boot::mark_stage(simple_os::boot::BootStage::GuiInit);
-                    simple_os::log_info!("GUI system initialized");
-                }
-                Err(e) => {
-                    simple_os::log_warn!("Failed to initialize GUI: {}", e);
-                }
-            }
-        } else {
-            simple_os::log_warn!("No framebuffer available, GUI disabled");
-        }
-    }
-    
-    // 17. 마우스 드라이버 초기화
-    unsafe {
-        simple_os::drivers::mouse::init();
-        // PIC에서 마우스 인터럽트 활성화 (IRQ 12)
-        simple_os::interrupts::pic::set_mask(12, true);
-    }
-    simple_os::boot::mark_stage(simple_os::boot::BootStage::MouseInit);
-    simple_os::log_info!("Mouse driver initialized");
-    
-    // 18. I2C 및 트랙패드 드라이버 초기화 (선택적)
-    #[cfg(feature = "touchpad")]
-    unsafe {
-        // ACPI에서 I2C 장치 정보 찾기
-        if let Some(i2c_info) = simple_os::power::acpi::find_i2c_touchpad() {
-            // I2C 컨트롤러 초기화
-            match simple_os::drivers::i2c::init_controller(0, i2c_info.base_address) {
-                Ok(()) => {
-                    simple_os::log_info!("I2C controller initialized at 0x{:X}", i2c_info.base_address.as_u64());
-                    
-                    // 트랙패드 초기화
-                    match simple_os::drivers::touchpad::init(i2c_info.slave_address) {
-                        Ok(()) => {
-                            simple_os::boot::mark_stage(simple_os::boot::BootStage::TouchpadInit);
-                            simple_os::log_info!("ELAN touchpad initialized at I2C address 0x{:02X}", i2c_info.slave_address);
-                        }
-                        Err(e) => {
-                            simple_os::log_warn!("Failed to initialize touchpad: {:?}", e);
-                            simple_os::log_info!("Falling back to PS/2 mouse only");
-                        }
-                    }
-                }
-                Err(e) => {
-                    simple_os::log_warn!("Failed to initialize I2C controller: {:?}", e);
-                    simple_os::log_info!("Touchpad disabled, using PS/2 mouse only");
-                }
-            }
-        } else {
-            simple_os::log_info!("No I2C touchpad found, using PS/2 mouse only");
-        }
-    }
-    
     simple_os::boot::mark_stage(simple_os::boot::BootStage::KernelInitComplete);
     simple_os::log_info!("Kernel initialization complete");
     
     // 부팅 타임라인 출력
-    if let Some(total_ms) = simple_os::boot::get_total_boot_time_ms() {
-        simple_os::log_info!("Total boot time: {}ms", total_ms);
+    if let Some(total_us) = simple_os::boot::get_total_boot_time_us() {
+        simple_os::log_info!("Total boot time: {}.{:03}ms", total_us / 1000, total_us % 1000);
     }
     simple_os::boot::print_timeline();
     
-    // 19. GUI 데스크톱 환경 시작 (프레임버퍼가 사용 가능한 경우)
+    // 9. GUI 데스크톱 환경 시작 (프레임버퍼가 사용 가능한 경우)
     #[cfg(feature = "gui")]
     if simple_os::drivers::framebuffer::is_initialized() {
         simple_os::boot::mark_stage(simple_os::boot::BootStage::DesktopStart);
@@ -426,11 +161,352 @@ fn kernel_init(boot_info: &'static mut BootInfo) {
     }
 }
 
+/// 서브시스템 initcall 테이블
+///
+/// 힙과 per-CPU 데이터가 준비된 뒤 `kernel::initcall::run`이 의존성 순서대로 실행합니다.
+/// 동시에 준비되는 initcall은 테이블 순서를 따릅니다.
+static INITCALLS: &[Initcall] = &[
+    Initcall::new("irq", &[], init_irq),
+    Initcall::new("pci", &["irq"], init_pci),
+    Initcall::new("devices", &["pci"], init_device_model),
+    Initcall::new("timer", &["irq"], init_timer),
+    Initcall::new("keyboard", &["devices"], init_keyboard),
+    Initcall::new("vga", &[], init_vga),
+    Initcall::new("sched", &["timer"], init_scheduler),
+    #[cfg(feature = "smp")]
+    Initcall::new("smp", &["sched"], init_smp).optional(),
+    Initcall::new("syscall", &[], init_syscall),
+    #[cfg(feature = "fs")]
+    Initcall::new("ata", &["devices", "timer"], init_ata).optional(),
+    #[cfg(feature = "fs")]
+    Initcall::new("rootfs", &["ata"], init_rootfs).optional(),
+    #[cfg(feature = "fs")]
+    Initcall::new("tmpfs", &[], init_tmpfs).optional(),
+    #[cfg(feature = "fs")]
+    Initcall::new("devfs", &[], init_devfs).optional(),
+    #[cfg(feature = "fs")]
+    Initcall::new("procfs", &[], init_procfs).optional(),
+    #[cfg(all(feature = "fs", feature = "nvme"))]
+    Initcall::new("nvme", &["devices", "timer"], init_nvme).optional().parallel(),
+    Initcall::new("power", &["devices", "timer"], init_power).optional(),
+    #[cfg(feature = "net")]
+    Initcall::new("net", &["devices", "timer"], init_net).optional().parallel(),
+    #[cfg(feature = "wifi")]
+    Initcall::new("wifi", &["net"], init_wifi).optional(),
+    #[cfg(feature = "audio")]
+    Initcall::new("audio", &["devices", "timer"], init_audio).optional().parallel(),
+    #[cfg(feature = "usb")]
+    Initcall::new("usb", &["devices", "timer"], init_usb).optional().parallel(),
+    #[cfg(feature = "gui")]
+    Initcall::new("framebuffer", &["devices"], init_framebuffer).optional(),
+    #[cfg(feature = "gui")]
+    Initcall::new("gui", &["framebuffer"], init_gui).optional(),
+    Initcall::new("mouse", &["devices"], init_mouse),
+    #[cfg(feature = "touchpad")]
+    Initcall::new("i2c", &["devices", "timer"], init_i2c).optional(),
+    #[cfg(feature = "touchpad")]
+    Initcall::new("touchpad", &["i2c"], init_touchpad).optional(),
+];
+
+/// 인터럽트 컨트롤러 선택 (MADT에 I/O APIC이 있으면 8259 PIC 대신 사용)
+fn init_irq() -> Result<(), &'static str> {
+    unsafe { interrupts::irq::init() };
+    Ok(())
+}
+
+/// PCIe 구성 공간 접근 (ACPI MCFG에 ECAM이 있으면 메모리 매핑 사용)
+fn init_pci() -> Result<(), &'static str> {
+    unsafe {
+        simple_os::drivers::pci::init();
+        if let simple_os::config::profile::Profile::PowerSaver = simple_os::config::profile::current_profile() {
+            simple_os::drivers::pci::enable_all_pcie_aspm();
+        }
+    }
+    Ok(())
+}
+
+/// 디바이스 모델: 플랫폼/PCI 디바이스 열거 (드라이버는 각 initcall에서 등록)
+fn init_device_model() -> Result<(), &'static str> {
+    unsafe { simple_os::drivers::base::init() };
+    Ok(())
+}
+
+/// 타이머, 클럭소스, hrtimer/타이밍 휠, 벽시계, watchdog
+fn init_timer() -> Result<(), &'static str> {
+    unsafe {
+        simple_os::drivers::timer::init();
+        // 클럭소스 (HPET/TSC) 탐지 및 보정
+        simple_os::time::clocksource::init();
+        // LAPIC one-shot 타이머로 tickless 모드 전환, 불가능하면 PIT 주기 틱 사용 (IRQ 0)
+        if !simple_os::drivers::timer::enable_tickless() {
+            simple_os::drivers::timer::enable_pit_irq();
+        }
+    }
+    simple_os::time::init();
+    unsafe {
+        // RTC 기반 벽시계 (FAT 타임스탬프, date 명령)
+        simple_os::time::wallclock::init();
+    }
+    simple_os::kernel::watchdog::init();
+    Ok(())
+}
+
+/// 키보드 드라이버 (i8042 키보드 포트에 바인딩, ISA IRQ 1)
+fn init_keyboard() -> Result<(), &'static str> {
+    simple_os::drivers::base::register_driver(simple_os::drivers::keyboard::DRIVER);
+    Ok(())
+}
+
+fn init_vga() -> Result<(), &'static str> {
+    simple_os::drivers::vga::init();
+    simple_os::vga_println!("Simple OS Kernel");
+    simple_os::vga_println!("==================");
+    simple_os::vga_println!("Uptime: {} ms", simple_os::drivers::timer::get_milliseconds());
+    Ok(())
+}
+
+/// 스케줄러 (시간 할당량: 10 타이머 틱, 약 10ms)
+fn init_scheduler() -> Result<(), &'static str> {
+    simple_os::scheduler::init(10);
+    simple_os::scheduler::affinity::init();
+    Ok(())
+}
+
+/// AP 시작 (각 AP는 자신의 GDT/TSS, per-CPU 데이터, 실행 큐를 설정한 뒤 유휴 루프로 진입)
+#[cfg(feature = "smp")]
+fn init_smp() -> Result<(), &'static str> {
+    unsafe { simple_os::smp::init() }
+}
+
+fn init_syscall() -> Result<(), &'static str> {
+    simple_os::syscall::init_syscall_handler();
+    Ok(())
+}
+
+/// ATA 드라이버 (레거시 IDE 컨트롤러에 바인딩)
+#[cfg(feature = "fs")]
+fn init_ata() -> Result<(), &'static str> {
+    simple_os::drivers::base::register_driver(simple_os::drivers::ata::DRIVER);
+    if simple_os::drivers::ata::PRIMARY_MASTER.lock().is_none() {
+        return Err("no ATA disk");
+    }
+    if let Err(e) = simple_os::fs::devfs::add_disk("ata0", simple_os::drivers::ata::PrimaryMasterDevice::open) {
+        simple_os::log_warn!("devfs: cannot add ata0: {:?}", e);
+    }
+    if let simple_os::config::profile::Profile::PowerSaver = simple_os::config::profile::current_profile() {
+        simple_os::drivers::ata::set_idle_timeout_ms(30000);
+        simple_os::log_info!("ATA idle timeout set to 30000ms (power_saver)");
+    }
+    Ok(())
+}
+
+/// 주 디스크를 루트(/)에 마운트 (Sjfs 또는 FAT32)
+#[cfg(feature = "fs")]
+fn init_rootfs() -> Result<(), &'static str> {
+    use simple_os::fs::{self, FS_MANAGER, PRIMARY_DISK};
+    let device = fs::open_block_device(PRIMARY_DISK).map_err(|_| "no root disk")?;
+    FS_MANAGER.lock().mount_root(PRIMARY_DISK, device).map_err(|e| {
+        simple_os::log_warn!("rootfs: cannot mount {}: {:?}", PRIMARY_DISK, e);
+        "no filesystem on root disk"
+    })
+}
+
+/// 문자 디바이스 노드 등록 후 /dev에 devfs 마운트 (디스크는 각 드라이버 initcall이 추가)
+#[cfg(feature = "fs")]
+fn init_devfs() -> Result<(), &'static str> {
+    use simple_os::fs::{devfs, MountFlags, FS_MANAGER};
+    devfs::init();
+    FS_MANAGER.lock().mount_devfs("/dev", MountFlags::default()).map_err(|e| {
+        simple_os::log_warn!("devfs: cannot mount /dev: {:?}", e);
+        "cannot mount /dev"
+    })
+}
+
+/// /proc에 procfs 마운트 (커널 상태 조회와 튜너블)
+#[cfg(feature = "fs")]
+fn init_procfs() -> Result<(), &'static str> {
+    use simple_os::fs::{MountFlags, FS_MANAGER};
+    FS_MANAGER.lock().mount_procfs("/proc", MountFlags::default()).map_err(|e| {
+        simple_os::log_warn!("procfs: cannot mount /proc: {:?}", e);
+        "cannot mount /proc"
+    })
+}
+
+/// /tmp에 tmpfs 마운트 (디스크가 없어도 쓸 수 있는 임시 공간)
+#[cfg(feature = "fs")]
+fn init_tmpfs() -> Result<(), &'static str> {
+    use simple_os::fs::tmpfs::TmpfsOptions;
+    use simple_os::fs::{MountFlags, FS_MANAGER};
+    FS_MANAGER.lock().mount_tmpfs("/tmp", TmpfsOptions::default(), MountFlags::default()).map_err(|e| {
+        simple_os::log_warn!("tmpfs: cannot mount /tmp: {:?}", e);
+        "cannot mount /tmp"
+    })
+}
+
+#[cfg(all(feature = "fs", feature = "nvme"))]
+fn init_nvme() -> Result<(), &'static str> {
+    unsafe { simple_os::drivers::nvme::init() }.map_err(|_| "no NVMe controller or init failed")?;
+    if let Err(e) = simple_os::fs::devfs::add_disk("nvme0n1", simple_os::drivers::nvme::NvmeBlockDevice::open) {
+        simple_os::log_warn!("devfs: cannot add nvme0n1: {:?}", e);
+    }
+    Ok(())
+}
+
+/// 전력 관리와 그에 딸린 모니터링 (열, 메모리 압박, 사용자 활동, 배터리)
+fn init_power() -> Result<(), &'static str> {
+    unsafe { simple_os::power::init() }.map_err(|e| {
+        simple_os::log_debug!("Power management init error: {:?}", e);
+        "power management unavailable"
+    })?;
+    
+    // RAPL 전력 측정 초기화 (Intel only)
+    #[cfg(feature = "intel_rapl")]
+    simple_os::power::rapl::init_rapl_measurement();
+    
+    // CPU 온도 모니터링 초기화
+    simple_os::power::temps::init_thermal_monitoring();
+    
+    // 디바이스별 전력 프로파일 초기화
+    simple_os::power::device_policy::init_device_power_profiles();
+    
+    // 전력 프로파일 적용
+    if let Err(e) = simple_os::power::device_policy::apply_device_power_policies() {
+        simple_os::log_warn!("Failed to apply device power policies: {:?}", e);
+    }
+    
+    // 메모리 압축 초기화
+    simple_os::memory::compression::init_compression(32); // 최대 32개 페이지 압축
+    
+    // 메모리 단편화 모니터링 초기화
+    simple_os::memory::fragmentation::init_fragmentation_monitoring(100); // 최대 100개 히스토리
+    
+    // OOM Killer 초기화
+    let oom_config = simple_os::memory::oom_killer::OomKillerConfig {
+        memory_threshold_percent: 5,
+        min_memory_bytes: 1024 * 1024, // 1MB
+        enabled: true,
+    };
+    simple_os::memory::oom_killer::init_oom_killer(oom_config);
+    
+    // 메모리 누수 감지기 활성화 (디버그 모드)
+    #[cfg(debug_assertions)]
+    {
+        simple_os::memory::leak_detector::set_enabled(true);
+        simple_os::log_info!("Memory leak detector enabled");
+    }
+    
+    // 사용자 활동 감지기 초기화
+    simple_os::power::user_activity::init_user_activity_detector(100, 30000); // 30초 유휴 임계값
+    
+    // 배터리 관리자 초기화
+    if let Err(e) = simple_os::power::battery::init_battery_manager() {
+        simple_os::log_warn!("Failed to initialize battery manager: {:?}", e);
+    }
+    Ok(())
+}
+
+/// 네트워크 드라이버와 DHCP
+#[cfg(feature = "net")]
+fn init_net() -> Result<(), &'static str> {
+    unsafe { simple_os::net::init_network() }.map_err(|e| {
+        simple_os::log_debug!("Network init error: {:?}", e);
+        "no network device"
+    })?;
+    if let Ok(mac) = simple_os::net::get_mac_address() {
+        simple_os::log_info!("Network MAC address: {}", mac);
+    }
+    if let simple_os::config::profile::Profile::PowerSaver = simple_os::config::profile::current_profile() {
+        simple_os::drivers::rtl8139::set_idle_timeout_ms(10000);
+        simple_os::log_info!("Network idle timeout set to 10000ms (power_saver)");
+    }
+    // TCP 재전송 태스크
+    simple_os::net::tcp::init();
+    // Auto DHCP bring-up (wired or once Wi‑Fi is up)
+    simple_os::net::driver::bringup_ipv4_via_dhcp();
+    Ok(())
+}
+
+#[cfg(feature = "wifi")]
+fn init_wifi() -> Result<(), &'static str> {
+    unsafe { simple_os::drivers::wifi::init() }.map_err(|_| "no supported Wi‑Fi device")?;
+    // 연결 프로파일이 있다면 자동 스캔/연결 시도
+    if let (Some(ssid), Some(psk)) = (option_env!("SIMPLEOS_WIFI_SSID"), option_env!("SIMPLEOS_WIFI_PSK")) {
+        simple_os::log_info!("Wi‑Fi autoconnect profile found: {}", ssid);
+        let _ = simple_os::net::ieee80211::start_scan();
+        let ssid_obj = simple_os::net::ieee80211::Ssid(alloc::string::String::from(ssid));
+        let sec = simple_os::net::ieee80211::WifiSec::Wpa2Psk(alloc::string::String::from(psk));
+        let _ = simple_os::net::ieee80211::connect(&ssid_obj, &sec);
+    } else {
+        simple_os::log_info!("No Wi‑Fi autoconnect profile (set SIMPLEOS_WIFI_SSID/PSK at build time)");
+    }
+    Ok(())
+}
+
+#[cfg(feature = "audio")]
+fn init_audio() -> Result<(), &'static str> {
+    unsafe { simple_os::drivers::audio::init() }.map_err(|e| {
+        simple_os::log_debug!("Audio init error: {}", e);
+        "audio subsystem unavailable"
+    })
+}
+
+/// USB 호스트 컨트롤러와 디바이스 열거
+#[cfg(feature = "usb")]
+fn init_usb() -> Result<(), &'static str> {
+    unsafe {
+        simple_os::drivers::usb::init().map_err(|e| {
+            simple_os::log_debug!("USB init error: {:?}", e);
+            "USB subsystem unavailable"
+        })?;
+        // 열거 실패해도 커널은 계속 실행 가능
+        if let Err(e) = simple_os::drivers::usb::enumerate_devices() {
+            simple_os::log_warn!("Failed to enumerate USB devices: {:?}", e);
+        }
+    }
+    Ok(())
+}
+
+/// 부트 프레임버퍼 드라이버
+#[cfg(feature = "gui")]
+fn init_framebuffer() -> Result<(), &'static str> {
+    simple_os::drivers::base::register_driver(simple_os::drivers::framebuffer::DRIVER);
+    if !simple_os::drivers::framebuffer::is_initialized() {
+        return Err("no framebuffer available, GUI disabled");
+    }
+    Ok(())
+}
+
+#[cfg(feature = "gui")]
+fn init_gui() -> Result<(), &'static str> {
+    simple_os::gui::init()
+}
+
+/// 마우스 드라이버 (i8042 보조 포트에 바인딩, ISA IRQ 12)
+fn init_mouse() -> Result<(), &'static str> {
+    simple_os::drivers::base::register_driver(simple_os::drivers::mouse::DRIVER);
+    Ok(())
+}
+
+/// ACPI로 열거된 I2C 컨트롤러 (트랙패드보다 먼저 바인딩되어야 함)
+#[cfg(feature = "touchpad")]
+fn init_i2c() -> Result<(), &'static str> {
+    simple_os::drivers::base::register_driver(simple_os::drivers::i2c::DRIVER);
+    Ok(())
+}
+
+#[cfg(feature = "touchpad")]
+fn init_touchpad() -> Result<(), &'static str> {
+    simple_os::drivers::base::register_driver(simple_os::drivers::touchpad::DRIVER);
+    if !simple_os::drivers::touchpad::is_initialized() {
+        return Err("no I2C touchpad bound, using PS/2 mouse only");
+    }
+    Ok(())
+}
+
 /// 데스크톱 환경 메인 루프
 #[cfg(feature = "gui")]
 fn desktop_loop() -> ! {
     use simple_os::drivers::mouse;
-    use simple_os::drivers::touchpad;
     use simple_os::drivers::timer;
     
     let mut last_render_time = 0u64;
@@ -444,22 +520,16 @@ fn desktop_loop() -> ! {
     loop {
         let current_time = timer::get_milliseconds();
         
-        // 트랙패드 이벤트 폴링 (트랙패드가 초기화된 경우)
-        if touchpad::is_initialized() {
-            if let Some(event) = touchpad::poll_event() {
-                simple_os::gui::desktop_manager::handle_mouse_event(event);
-                last_input_time = current_time;
-                if simple_os::drivers::framebuffer::is_blank() { simple_os::drivers::framebuffer::unblank(); }
-            }
-        }
-        
-        // PS/2 마우스 이벤트 처리 (백업 또는 외장 마우스)
+        // 마우스 이벤트 처리 (PS/2, USB HID, 트랙패드 리포트 태스크)
         if let Some(event) = mouse::get_event() {
             simple_os::gui::desktop_manager::handle_mouse_event(event);
             last_input_time = current_time;
             if simple_os::drivers::framebuffer::is_blank() { simple_os::drivers::framebuffer::unblank(); }
         }
         
+        // 커널 이벤트 알림 (USB 연결, 전원/열 상태 변화)
+        simple_os::gui::desktop_manager::poll_notifications(current_time);
+        
         // 주기적으로 화면 렌더링
         if current_time - last_render_time >= render_interval || simple_os::gui::compositor::needs_redraw() {
             if !simple_os::drivers::framebuffer::is_blank() {
@@ -478,7 +548,8 @@ fn desktop_loop() -> ! {
             }
         }
         
-        // CPU 절전
+        // CPU 절전 (tickless 모드에서는 다음 렌더링 시각까지 잠들 수 있음)
+        timer::request_wakeup_at(last_render_time + render_interval);
         if let Some(pm) = simple_os::power::get_manager() {
             let mut guard = pm.lock();
             if let Some(manager) = guard.as_mut() {
@@ -489,6 +560,8 @@ fn desktop_loop() -> ! {
         } else {
             x86_64::instructions::hlt();
         }
+        // 깨운 인터럽트/타이머가 준비시킨 비동기 태스크 실행
+        simple_os::task::run_ready();
         // Wi‑Fi polling tick (if enabled)
         #[cfg(feature = "wifi")]
         simple_os::drivers::wifi::tick();
@@ -516,9 +589,10 @@ fn desktop_loop() -> ! {
         
         // 전력 모니터링 대시보드 업데이트 (1초마다) - monitor 모듈 미구현으로 제거
 
-        // USB HID 폴링 (임시, Interrupt IN 구현 전 보완)
+        // USB 포트 연결/분리 처리와 HID 폴링 (임시, Interrupt IN 구현 전 보완)
         #[cfg(feature = "usb")]
         {
+            simple_os::drivers::usb::core::poll_port_changes();
             simple_os::drivers::usb::core::poll_hid();
         }
     }
@@ -9,6 +9,11 @@ use alloc::vec::Vec;
 
 use crate::memory::map::get as get_memory_map;
 
+/// 프레임 할당자가 사용하지 않는 하위 물리 메모리 (1 MiB 미만)
+///
+/// 실제 모드에서 실행되는 AP 트램펄린처럼 1 MiB 아래에 있어야 하는 용도로 남겨둡니다.
+pub const LOW_MEMORY_RESERVED: u64 = 0x10_0000;
+
 /// 프레임 할당자 구현
 ///
 /// 사용 가능한 메모리 영역을 추적하고 프레임을 할당합니다.
@@ -57,8 +62,8 @@ impl BootInfoFrameAllocator {
                 let start = region.start.as_u64();
                 let end = start + region.length;
                 
-                // 4KB 정렬
-                let aligned_start = align_up(start, Size4KiB::SIZE);
+                // 4KB 정렬 (하위 1 MiB 제외)
+                let aligned_start = align_up(start.max(LOW_MEMORY_RESERVED), Size4KiB::SIZE);
                 let aligned_end = align_down(end, Size4KiB::SIZE);
                 
                 if aligned_start < aligned_end {
@@ -2,6 +2,8 @@
 //!
 //! 이 모듈은 커널 힙 할당자를 초기화하고 전역 할당자로 설정합니다.
 
+use core::alloc::{GlobalAlloc, Layout};
+use core::sync::atomic::Ordering;
 use linked_list_allocator::LockedHeap;
 use x86_64::{
     structures::paging::{
@@ -23,7 +25,51 @@ pub(crate) static HEAP_SIZE_BYTES: Mutex<usize> = Mutex::new(HEAP_INITIAL_SIZE);
 
 /// 전역 힙 할당자
 #[global_allocator]
-static ALLOCATOR: LockedHeap = LockedHeap::empty();
+static ALLOCATOR: RecoveringHeap = RecoveringHeap::empty();
+
+/// 할당이 실패하면 페이지 캐시를 줄인 뒤 한 번 다시 시도하는 힙
+///
+/// `alloc_error_handler`는 돌아올 수 없으므로 복구는 할당자 안에서 실패를 돌려주기 전에
+/// 해야 합니다. 실패한 할당이 어떤 락을 잡고 있는지 모르므로 락을 기다리지 않는 캐시
+/// 줄이기만 하고, 힙 확장은 하지 않습니다. 다시 시도해도 실패하면 널을 돌려줍니다.
+struct RecoveringHeap(LockedHeap);
+
+impl RecoveringHeap {
+    const fn empty() -> Self {
+        Self(LockedHeap::empty())
+    }
+}
+
+impl core::ops::Deref for RecoveringHeap {
+    type Target = LockedHeap;
+
+    fn deref(&self) -> &LockedHeap {
+        &self.0
+    }
+}
+
+unsafe impl GlobalAlloc for RecoveringHeap {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = self.0.alloc(layout);
+        if !ptr.is_null() {
+            return ptr;
+        }
+        // 같은 CPU에서 복구 도중(캐시 페이지 해제 등) 실패한 할당은 그대로 실패
+        let recovering = crate::per_cpu!(in_alloc_recovery);
+        if recovering.swap(true, Ordering::Acquire) {
+            return ptr;
+        }
+        // 힙 락을 놓은 상태에서 줄임. 다른 CPU가 이미 캐시를 줄이는 중이면 여기서는
+        // 아무것도 버리지 못해도 그 결과로 빈 공간이 생겼을 수 있으므로 다시 시도
+        crate::memory::recovery::reclaim_in_allocator(layout);
+        recovering.store(false, Ordering::Release);
+        self.0.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        self.0.dealloc(ptr, layout)
+    }
+}
 
 /// 힙 할당자 초기화
 ///
@@ -79,8 +125,8 @@ pub unsafe fn init_heap(
 
 /// 힙 할당 오류 핸들러
 ///
-/// 힙 할당이 실패했을 때 호출됩니다.
-/// Graceful degradation을 시도: 메모리 압박 시 힙 확장 시도
+/// 할당자가 메모리 복구 뒤 다시 시도해도 실패했을 때 호출됩니다. 돌아올 수 없으므로
+/// 상태를 기록하고 패닉합니다.
 #[alloc_error_handler]
 fn alloc_error_handler(layout: core::alloc::Layout) -> ! {
     // 힙 할당 실패 로깅
@@ -98,29 +144,7 @@ fn alloc_error_handler(layout: core::alloc::Layout) -> ! {
         }
     }
     
-    // 메모리 복구 메커니즘 시도
-    unsafe {
-        use crate::memory::recovery::{try_recover_allocation, RecoveryResult};
-        
-        match try_recover_allocation(layout) {
-            RecoveryResult::Success => {
-                crate::log_info!("Memory recovery successful, retrying allocation");
-                // 복구 성공 시 재시도 (할당은 자동으로 재시도됨)
-                // 하지만 alloc_error_handler는 반환할 수 없으므로 여기서는 패닉
-                // 실제로는 allocator가 재시도해야 함
-                panic!("allocation error: {:?} (recovery attempted but handler cannot return)", layout);
-            }
-            RecoveryResult::Partial => {
-                crate::log_warn!("Partial memory recovery, may still fail");
-                // 부분 복구는 여전히 실패할 수 있음
-            }
-            RecoveryResult::Failed => {
-                crate::log_error!("All memory recovery strategies failed");
-            }
-        }
-    }
-    
-    // 복구 불가능: 패닉
+    // 복구는 할당자에서 이미 시도함
     panic!("allocation error: {:?}", layout)
 }
 
@@ -130,3 +154,11 @@ pub fn heap_bounds() -> (usize, usize) {
     (HEAP_START, *size_guard)
 }
 
+/// 지금 할당할 수 있는 힙 메모리 (바이트)
+///
+/// 현재 힙의 빈 공간에 최대 크기(`HEAP_MAX_SIZE`)까지 확장할 수 있는 여유를 더한 값입니다.
+/// tmpfs처럼 힙에 데이터를 쌓는 사용자가 메모리 압박을 판단할 때 씁니다.
+pub fn heap_free() -> usize {
+    let size = *HEAP_SIZE_BYTES.lock();
+    ALLOCATOR.lock().free() + HEAP_MAX_SIZE.saturating_sub(size)
+}
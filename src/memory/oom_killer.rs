@@ -175,6 +175,16 @@ impl OomKiller {
         crate::log_warn!("OOM detected: {:.1}% memory available ({} bytes)", 
                         available_percent, available_bytes);
         
+        // tmpfs 데이터는 회수할 수 없음 (스왑이 없고 스레드를 종료해도 줄지 않음).
+        // 한도는 tmpfs가 쓰기 시점에 강제하므로 여기서는 원인 파악용으로 보고만 함
+        #[cfg(feature = "fs")]
+        {
+            let tmpfs_bytes = crate::fs::tmpfs::total_bytes();
+            if tmpfs_bytes > 0 {
+                crate::log_warn!("OOM: tmpfs holds {} bytes (free it by removing files under tmpfs mounts)", tmpfs_bytes);
+            }
+        }
+        
         // 종료할 스레드 선택
         if let Some(thread_id) = self.select_thread_to_kill() {
             crate::log_warn!("OOM Killer: Terminating thread {} to free memory", thread_id);
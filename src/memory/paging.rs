@@ -5,7 +5,7 @@
 //! 추가 매핑과 페이지 테이블 조작을 위한 유틸리티를 제공합니다.
 
 use x86_64::{
-    structures::paging::{Mapper, OffsetPageTable, Page, PageTableFlags, Size4KiB, PageSize, mapper::MapToError, PhysFrame, FrameAllocator},
+    structures::paging::{Mapper, OffsetPageTable, Page, PageTableFlags, Size2MiB, Size4KiB, PageSize, mapper::MapToError, PhysFrame, FrameAllocator},
     VirtAddr,
 };
 use x86_64::structures::paging::page_table::PageTableEntry;
@@ -75,6 +75,48 @@ pub fn set_physical_memory_offset(offset: VirtAddr) {
     *guard = Some(offset);
 }
 
+/// 물리 주소를 물리 메모리 오프셋 매핑상의 가상 주소로 변환
+///
+/// LAPIC 같은 MMIO 레지스터 접근에 사용합니다.
+/// 메모리 관리가 초기화되기 전에는 `None`을 반환합니다.
+pub fn phys_to_virt(phys: x86_64::PhysAddr) -> Option<VirtAddr> {
+    let guard = PHYSICAL_MEMORY_OFFSET.lock();
+    guard.map(|offset| offset + phys.as_u64())
+}
+
+/// MMIO 영역을 캐시 불가(UC)로 매핑 (2MiB 페이지)
+///
+/// 물리 메모리 오프셋 매핑은 write-back 캐시 속성이므로 ECAM처럼 큰 MMIO 영역은
+/// 별도 가상 주소 창에 `NO_CACHE | WRITE_THROUGH`로 매핑해서 접근해야 합니다.
+/// `virt`와 `phys`는 2MiB 정렬이어야 하며 `size`는 2MiB 단위로 올림됩니다.
+///
+/// # Safety
+/// `virt` 범위가 다른 용도로 매핑되어 있지 않아야 하고, `phys`는 MMIO 영역이어야 합니다.
+pub unsafe fn map_mmio_uncached(virt: VirtAddr, phys: x86_64::PhysAddr, size: u64) -> Result<(), MapToError<Size2MiB>> {
+    let offset = {
+        let guard = PHYSICAL_MEMORY_OFFSET.lock();
+        guard.ok_or(MapToError::FrameAllocationFailed)?
+    };
+
+    let mut mapper = init_mapper(offset);
+    let mut frame_allocator = BootInfoFrameAllocator::new();
+    let flags = PageTableFlags::PRESENT
+        | PageTableFlags::WRITABLE
+        | PageTableFlags::NO_CACHE
+        | PageTableFlags::WRITE_THROUGH
+        | PageTableFlags::NO_EXECUTE;
+
+    let pages = size.div_ceil(Size2MiB::SIZE);
+    for i in 0..pages {
+        let page = Page::<Size2MiB>::from_start_address(virt + i * Size2MiB::SIZE)
+            .map_err(|_| MapToError::FrameAllocationFailed)?;
+        let frame = PhysFrame::<Size2MiB>::from_start_address(phys + i * Size2MiB::SIZE)
+            .map_err(|_| MapToError::FrameAllocationFailed)?;
+        mapper.map_to(page, frame, flags, &mut frame_allocator)?.flush();
+    }
+    Ok(())
+}
+
 /// Map a zero-initialized 4KiB page at the given virtual address (page-aligned)
 ///
 /// Safety: caller must ensure the address is valid to map and not already mapped.
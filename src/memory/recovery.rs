@@ -1,14 +1,18 @@
 //! 메모리 할당 실패 복구 메커니즘
 //!
-//! 이 모듈은 힙 할당 실패 시 복구를 시도합니다.
+//! 이 모듈은 힙 할당 실패 시 복구를 시도합니다. 전역 할당자는 실패를 돌려주기 전에
+//! 락을 기다리지 않는 `reclaim_in_allocator`(페이지 캐시 비우기)만 부르고 한 번 다시
+//! 시도합니다. 힙 확장처럼 락을 잡는 나머지 단계는 락을 잡지 않은 컨텍스트에서
+//! `try_recover_allocation`으로 수행합니다.
 //!
 //! # 복구 전략
 //!
-//! 1. **힙 확장 시도**: 사용 가능한 프레임이 있으면 힙 확장
-//! 2. **메모리 압축 시도**: 사용되지 않는 메모리 압축
-//! 3. **스왑 아웃 시도**: LRU 페이지를 스왑으로 내보내기
-//! 4. **대체 할당자 시도**: Slab 할당자 사용 (작은 할당의 경우)
-//! 5. **OOM Killer**: 최후의 수단으로 프로세스 종료
+//! 1. **페이지 캐시 비우기**: 파일 페이지 캐시를 LRU 순으로 버림 (`fs` 기능)
+//! 2. **힙 확장 시도**: 사용 가능한 프레임이 있으면 힙 확장
+//! 3. **메모리 압축 시도**: 사용되지 않는 메모리 압축
+//! 4. **스왑 아웃 시도**: LRU 페이지를 스왑으로 내보내기
+//! 5. **대체 할당자 시도**: Slab 할당자 사용 (작은 할당의 경우)
+//! 6. **OOM Killer**: 최후의 수단으로 프로세스 종료
 
 use core::alloc::Layout;
 use alloc::vec::Vec;
@@ -34,9 +38,30 @@ pub enum RecoveryResult {
     Partial,
 }
 
+/// 전역 할당자 안에서 하는 복구: 페이지 캐시 비우기
+///
+/// 실패한 할당이 어떤 락(프레임 할당자, 로거 등)을 잡고 있는지 모르므로 캐시 락도
+/// 기다리지 않고, 로그도 남기지 않습니다.
+///
+/// # Returns
+/// 버린 페이지가 있으면 `true`
+pub fn reclaim_in_allocator(layout: Layout) -> bool {
+    #[cfg(feature = "fs")]
+    {
+        let pages = layout.size().div_ceil(crate::fs::cache::PAGE_SIZE).max(16);
+        crate::fs::cache::shrink(pages) > 0
+    }
+    #[cfg(not(feature = "fs"))]
+    {
+        let _ = layout;
+        false
+    }
+}
+
 /// 힙 할당 실패 복구 시도
 ///
-/// 다양한 전략을 사용하여 메모리를 확보합니다.
+/// 다양한 전략을 사용하여 메모리를 확보합니다. 프레임 할당자, 페이지 테이블, 로거 락을
+/// 잡으므로 전역 할당자 안에서 부르면 안 됩니다.
 ///
 /// # Arguments
 /// * `layout` - 요청된 할당 레이아웃
@@ -47,13 +72,24 @@ pub unsafe fn try_recover_allocation(layout: Layout) -> RecoveryResult {
     crate::log_warn!("Attempting memory recovery for allocation: size={}, align={}", 
                     layout.size(), layout.align());
     
-    // 전략 1: 힙 확장 시도
+    // 전략 1: 페이지 캐시 비우기 (캐시 페이지는 디스크와 같으므로 바로 버릴 수 있음)
+    #[cfg(feature = "fs")]
+    {
+        let pages = layout.size().div_ceil(crate::fs::cache::PAGE_SIZE).max(16);
+        let evicted = crate::fs::cache::shrink(pages);
+        if evicted > 0 {
+            crate::log_info!("Dropped {} page cache pages", evicted);
+            return RecoveryResult::Success;
+        }
+    }
+    
+    // 전략 2: 힙 확장 시도
     if let Ok(()) = try_expand_heap(layout.size()) {
         crate::log_info!("Heap expansion successful");
         return RecoveryResult::Success;
     }
     
-    // 전략 2: 스왑 아웃 시도 (메모리 확보)
+    // 전략 3: 스왑 아웃 시도 (메모리 확보)
     if is_swap_enabled() {
         if let Ok(()) = try_swap_out_lru() {
             crate::log_info!("Swap out successful, retrying heap expansion");
@@ -63,7 +99,7 @@ pub unsafe fn try_recover_allocation(layout: Layout) -> RecoveryResult {
         }
     }
     
-    // 전략 3: 작은 할당의 경우 Slab 할당자 시도
+    // 전략 4: 작은 할당의 경우 Slab 할당자 시도
     if layout.size() <= 256 {
         crate::log_info!("Trying slab allocator for small allocation");
         // Slab 할당자는 이미 전역으로 설정되어 있으므로, 여기서는 힘 확장만 시도
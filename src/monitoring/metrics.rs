@@ -1,16 +1,21 @@
 //! 성능 메트릭 수집 및 추적
 //!
 //! 시스템 성능 메트릭을 수집하고 추적합니다.
+//!
+//! 컨텍스트 스위칭, 인터럽트, 시스템 콜, 페이지 폴트처럼 자주 발생하는 이벤트는
+//! 락 경합을 피하기 위해 CPU별 카운터(`PerCpu::stats`)에 기록하고, 조회할 때 합산합니다.
 
+use core::sync::atomic::Ordering;
 use spin::Mutex;
+use crate::kernel::percpu;
 use alloc::vec::Vec;
 
 /// 성능 메트릭
 #[derive(Clone, Copy)]
 pub struct PerformanceMetrics {
-    /// 부팅 시간 (ms)
-    pub boot_time_ms: u64,
-    /// 총 CPU 사이클 (예상)
+    /// 부팅 이후 경과 시간 (µs, 클럭소스 기준)
+    pub boot_time_us: u64,
+    /// 총 CPU 사이클 (TSC)
     pub cpu_cycles: u64,
     /// 컨텍스트 스위칭 횟수
     pub context_switches: u64,
@@ -43,7 +48,7 @@ pub struct PerformanceMetrics {
 impl Default for PerformanceMetrics {
     fn default() -> Self {
         Self {
-            boot_time_ms: 0,
+            boot_time_us: 0,
             cpu_cycles: 0,
             context_switches: 0,
             interrupts: 0,
@@ -66,7 +71,10 @@ impl PerformanceMetrics {
     /// 메트릭 업데이트
     pub fn update(&mut self) {
         // 부팅 시간 업데이트
-        self.boot_time_ms = crate::drivers::timer::get_milliseconds();
+        self.boot_time_us = crate::time::now_ns() / crate::time::NSEC_PER_USEC;
+        if crate::time::tsc::supported() {
+            self.cpu_cycles = crate::time::tsc::rdtsc();
+        }
         
         // 메모리 사용량 업데이트
         let (allocated, deallocated, in_use) = crate::kernel::watchdog::get_memory_usage();
@@ -89,7 +97,7 @@ impl PerformanceMetrics {
     /// 메트릭 출력
     pub fn print_report(&self) {
         crate::log_info!("=== Performance Metrics ===");
-        crate::log_info!("Boot time: {} ms", self.boot_time_ms);
+        crate::log_info!("Boot time: {}.{:03} ms", self.boot_time_us / 1000, self.boot_time_us % 1000);
         crate::log_info!("Context switches: {}", self.context_switches);
         crate::log_info!("Interrupts: {}", self.interrupts);
         crate::log_info!("Syscalls: {}", self.syscalls);
@@ -106,28 +114,41 @@ impl PerformanceMetrics {
         crate::log_info!("========================");
     }
     
+    /// (이름, 값) 목록 (CSV와 procfs `metrics` 파일이 같은 이름을 씀)
+    ///
+    /// 기존 소비자를 위해 `boot_time_ms`를 유지하고, 마이크로초 값은 `boot_time_us`로 추가합니다.
+    pub fn fields(&self) -> [(&'static str, u64); 16] {
+        [
+            ("boot_time_ms", self.boot_time_us / 1000),
+            ("boot_time_us", self.boot_time_us),
+            ("cpu_cycles", self.cpu_cycles),
+            ("context_switches", self.context_switches),
+            ("interrupts", self.interrupts),
+            ("syscalls", self.syscalls),
+            ("page_faults", self.page_faults),
+            ("heap_allocations", self.heap_allocations),
+            ("heap_deallocations", self.heap_deallocations),
+            ("memory_used_bytes", self.memory_used_bytes),
+            ("memory_peak_bytes", self.memory_peak_bytes),
+            ("active_threads", self.active_threads as u64),
+            ("hid_events", self.hid_events),
+            ("audio_underruns", self.audio_underruns),
+            ("tls_failures", self.tls_failures),
+            ("s3_success", self.s3_success),
+        ]
+    }
+
     /// CSV 형식으로 내보내기
     pub fn export_csv(&self) {
         crate::serial_println!("metric,value");
-        crate::serial_println!("boot_time_ms,{}", self.boot_time_ms);
-        crate::serial_println!("context_switches,{}", self.context_switches);
-        crate::serial_println!("interrupts,{}", self.interrupts);
-        crate::serial_println!("syscalls,{}", self.syscalls);
-        crate::serial_println!("page_faults,{}", self.page_faults);
-        crate::serial_println!("heap_allocations,{}", self.heap_allocations);
-        crate::serial_println!("heap_deallocations,{}", self.heap_deallocations);
-        crate::serial_println!("memory_used_bytes,{}", self.memory_used_bytes);
-        crate::serial_println!("memory_peak_bytes,{}", self.memory_peak_bytes);
-        crate::serial_println!("active_threads,{}", self.active_threads);
-        crate::serial_println!("hid_events,{}", self.hid_events);
-        crate::serial_println!("audio_underruns,{}", self.audio_underruns);
-        crate::serial_println!("tls_failures,{}", self.tls_failures);
-        crate::serial_println!("s3_success,{}", self.s3_success);
+        for (name, value) in self.fields() {
+            crate::serial_println!("{},{}", name, value);
+        }
     }
 }
 
 static METRICS: Mutex<PerformanceMetrics> = Mutex::new(PerformanceMetrics {
-    boot_time_ms: 0,
+    boot_time_us: 0,
     cpu_cycles: 0,
     context_switches: 0,
     interrupts: 0,
@@ -152,14 +173,12 @@ pub fn update_metrics() {
 
 /// 컨텍스트 스위칭 기록
 pub fn record_context_switch() {
-    let mut metrics = METRICS.lock();
-    metrics.context_switches += 1;
+    crate::per_cpu!(stats.context_switches).fetch_add(1, Ordering::Relaxed);
 }
 
 /// 인터럽트 기록
 pub fn record_interrupt() {
-    let mut metrics = METRICS.lock();
-    metrics.interrupts += 1;
+    crate::per_cpu!(stats.interrupts).fetch_add(1, Ordering::Relaxed);
 }
 
 /// HID event record
@@ -188,14 +207,12 @@ pub fn record_s3_success() {
 
 /// 시스템 콜 기록
 pub fn record_syscall() {
-    let mut metrics = METRICS.lock();
-    metrics.syscalls += 1;
+    crate::per_cpu!(stats.syscalls).fetch_add(1, Ordering::Relaxed);
 }
 
 /// 페이지 폴트 기록
 pub fn record_page_fault() {
-    let mut metrics = METRICS.lock();
-    metrics.page_faults += 1;
+    crate::per_cpu!(stats.page_faults).fetch_add(1, Ordering::Relaxed);
 }
 
 /// 활성 스레드 수 업데이트
@@ -204,21 +221,24 @@ pub fn update_active_threads(count: u32) {
     metrics.active_threads = count;
 }
 
-/// 메트릭 가져오기
+/// 메트릭 가져오기 (CPU별 카운터 합산 포함)
 pub fn get_metrics() -> PerformanceMetrics {
-    *METRICS.lock()
+    let mut metrics = *METRICS.lock();
+    metrics.context_switches = percpu::sum(|c| &c.stats.context_switches);
+    metrics.interrupts = percpu::sum(|c| &c.stats.interrupts);
+    metrics.syscalls = percpu::sum(|c| &c.stats.syscalls);
+    metrics.page_faults = percpu::sum(|c| &c.stats.page_faults);
+    metrics
 }
 
 /// 메트릭 리포트 출력
 pub fn print_report() {
-    let metrics = METRICS.lock();
-    metrics.print_report();
+    get_metrics().print_report();
 }
 
 /// CSV 형식으로 메트릭 내보내기
 pub fn export_csv() {
-    let metrics = METRICS.lock();
-    metrics.export_csv();
+    get_metrics().export_csv();
 }
 
 
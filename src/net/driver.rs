@@ -2,13 +2,18 @@
 //!
 //! 이 모듈은 네트워크 드라이버를 관리하고 초기화합니다.
 
-use crate::drivers::pci;
+use crate::drivers::base::{self, Device, DeviceInfo, DeviceMatch, Driver};
+use crate::drivers::pci::{self, PciDevice};
+use crate::drivers::pci_msi::{self, MsiVectors};
 use crate::drivers::rtl8139::{Rtl8139Driver, is_rtl8139};
 #[cfg(feature = "net_r8168")]
 use crate::drivers::rtl8168::{Rtl8168Driver, is_rtl8168};
+use crate::interrupts::irq;
+use crate::kernel::uevent::{self, KernelEvent};
 use crate::net::ethernet::{EthernetDriver, NetworkError, MacAddress, PacketBuffer};
 use spin::Mutex;
 use alloc::boxed::Box;
+use alloc::vec::Vec;
 
 /// 네트워크 드라이버 매니저
 ///
@@ -25,6 +30,8 @@ pub struct NetworkDriverManager {
     initialized: bool,
     /// 네트워크 인터럽트 IRQ 번호
     irq: Option<u8>,
+    /// MSI 벡터 (`None`이면 레거시 INTx 사용)
+    msi: Option<MsiVectors>,
 }
 
 // The manager only accesses hardware under a single global Mutex; mark Send safely.
@@ -38,25 +45,17 @@ impl NetworkDriverManager {
             driver: None,
             initialized: false,
             irq: None,
+            msi: None,
         }
     }
     
     /// 네트워크 드라이버 초기화
     ///
-    /// PCI 버스를 스캔하여 네트워크 디바이스를 찾고 초기화합니다.
+    /// 디바이스 모델이 바인딩한 PCI 이더넷 컨트롤러에 맞는 드라이버를 초기화합니다.
     ///
     /// # Safety
     /// 메모리 관리가 초기화된 후에 호출되어야 합니다.
-    pub unsafe fn init(&mut self) -> Result<(), NetworkError> {
-        // PCI 버스에서 네트워크 디바이스 찾기
-        let pci_device = match pci::find_network_device() {
-            Some(device) => device,
-            None => {
-                crate::log_warn!("No network device found on PCI bus");
-                return Err(NetworkError::DeviceNotFound);
-            }
-        };
-        
+    pub unsafe fn init(&mut self, pci_device: PciDevice) -> Result<(), NetworkError> {
         crate::log_info!("Found network device: Vendor=0x{:04X}, Device=0x{:04X}", 
                          pci_device.vendor_id, pci_device.device_id);
         
@@ -72,14 +71,7 @@ impl NetworkDriverManager {
             self.irq = Some(pci_device.interrupt_line);
             
             // 네트워크 인터럽트 핸들러 등록 및 활성화
-            let interrupt_num = crate::interrupts::pic::PIC1_OFFSET + pci_device.interrupt_line;
-            unsafe {
-                crate::interrupts::idt::IDT.0[interrupt_num as usize]
-                    .set_handler_fn(network_interrupt_handler);
-                crate::interrupts::pic::set_mask(pci_device.interrupt_line, true);
-            }
-            crate::log_info!("Network interrupt handler registered (IRQ {}, interrupt {})",
-                           pci_device.interrupt_line, interrupt_num);
+            self.msi = request_network_irq(&pci_device);
             
             Ok(())
         } else {
@@ -92,12 +84,7 @@ impl NetworkDriverManager {
                     self.driver = Some(ActiveDriver::Rtl8168(driver));
                     self.initialized = true;
                     self.irq = Some(pci_device.interrupt_line);
-                    let interrupt_num = crate::interrupts::pic::PIC1_OFFSET + pci_device.interrupt_line;
-                    unsafe {
-                        crate::interrupts::idt::IDT.0[interrupt_num as usize]
-                            .set_handler_fn(network_interrupt_handler);
-                        crate::interrupts::pic::set_mask(pci_device.interrupt_line, true);
-                    }
+                    self.msi = request_network_irq(&pci_device);
                     return Ok(());
                 }
             }
@@ -147,38 +134,137 @@ impl NetworkDriverManager {
         }
     }
     
+    /// 절전 진입: 수신을 멈추고 레거시 IRQ 마스크
+    fn suspend(&mut self) {
+        match self.driver {
+            Some(ActiveDriver::Rtl8139(ref mut d)) => crate::drivers::rtl8139::enter_low_power(d),
+            #[cfg(feature = "net_r8168")] Some(ActiveDriver::Rtl8168(_)) => {}
+            _ => {}
+        }
+        if let (None, Some(line)) = (&self.msi, self.irq) {
+            irq::disable_irq(irq::isa_irq_to_gsi(line));
+        }
+    }
+
+    /// 절전 복귀: 레거시 IRQ 해제 후 수신 재개
+    fn resume(&mut self) {
+        if let (None, Some(line)) = (&self.msi, self.irq) {
+            irq::enable_irq(irq::isa_irq_to_gsi(line));
+        }
+        match self.driver {
+            Some(ActiveDriver::Rtl8139(ref mut d)) => crate::drivers::rtl8139::wake_on_activity(d),
+            #[cfg(feature = "net_r8168")] Some(ActiveDriver::Rtl8168(_)) => {}
+            _ => {}
+        }
+    }
+
+    /// 드라이버 해제 (인터럽트 반환)
+    unsafe fn remove(&mut self, pci_device: &PciDevice) {
+        match self.msi.take() {
+            Some(msi) => pci_msi::disable(pci_device, msi),
+            None => {
+                if let Some(line) = self.irq {
+                    irq::free_irq(irq::isa_irq_to_gsi(line), network_irq_handler);
+                }
+            }
+        }
+        self.driver = None;
+        self.irq = None;
+        self.initialized = false;
+    }
+    
     /// 초기화 여부 확인
     pub fn is_initialized(&self) -> bool {
         self.initialized
     }
 }
 
+/// PCI 이더넷 컨트롤러 드라이버 (디바이스 모델)
+///
+/// 전역 매니저가 디바이스 하나만 다루므로 첫 번째로 probe에 성공한 컨트롤러만 바인딩됩니다.
+struct NetworkDriver;
+
+/// 디바이스 모델에 등록할 네트워크 드라이버
+static DRIVER: &dyn Driver = &NetworkDriver;
+
+impl Driver for NetworkDriver {
+    fn name(&self) -> &'static str {
+        "ethernet"
+    }
+
+    fn match_table(&self) -> &'static [DeviceMatch] {
+        &[DeviceMatch::PciClass { class: pci::PCI_CLASS_NETWORK, subclass: pci::PCI_SUBCLASS_ETHERNET, prog_if: None }]
+    }
+
+    fn probe(&self, device: &Device) -> Result<(), &'static str> {
+        let DeviceInfo::Pci(pci_device) = device.info else {
+            return Err("not a PCI device");
+        };
+        let mut manager = NETWORK_MANAGER.lock();
+        if manager.initialized {
+            return Err("network manager already has a device");
+        }
+        unsafe { manager.init(pci_device) }.map_err(|_| "unsupported or failed network device")?;
+        drop(manager);
+        uevent::publish(KernelEvent::NetworkLink { up: true });
+        Ok(())
+    }
+
+    fn remove(&self, device: &Device) {
+        if let DeviceInfo::Pci(pci_device) = device.info {
+            unsafe { NETWORK_MANAGER.lock().remove(&pci_device) };
+            uevent::publish(KernelEvent::NetworkLink { up: false });
+        }
+    }
+
+    fn suspend(&self, _device: &Device) -> Result<(), &'static str> {
+        NETWORK_MANAGER.lock().suspend();
+        uevent::publish(KernelEvent::NetworkLink { up: false });
+        Ok(())
+    }
+
+    fn resume(&self, _device: &Device) -> Result<(), &'static str> {
+        NETWORK_MANAGER.lock().resume();
+        uevent::publish(KernelEvent::NetworkLink { up: true });
+        Ok(())
+    }
+}
+
 /// 전역 네트워크 드라이버 매니저
 static NETWORK_MANAGER: Mutex<NetworkDriverManager> = Mutex::new(NetworkDriverManager {
     driver: None,
     initialized: false,
     irq: None,
+    msi: None,
 });
 
 /// 네트워크 드라이버 초기화
 ///
+/// 드라이버를 디바이스 모델에 등록하며, 이더넷 컨트롤러가 바인딩되지 않으면
+/// `DeviceNotFound`를 반환합니다.
+///
 /// # Safety
-/// 메모리 관리가 초기화된 후에 호출되어야 합니다.
+/// 메모리 관리와 `drivers::base::init`이 끝난 후에 호출되어야 합니다.
 pub unsafe fn init() -> Result<(), NetworkError> {
-    let mut manager = NETWORK_MANAGER.lock();
-    manager.init()
+    base::register_driver(DRIVER);
+    if NETWORK_MANAGER.lock().initialized {
+        Ok(())
+    } else {
+        crate::log_warn!("No supported network device found on PCI bus");
+        Err(NetworkError::DeviceNotFound)
+    }
 }
 
 /// 패킷 송신
+///
+/// IRQ 핸들러도 매니저 락을 잡으므로 인터럽트를 막고 잠급니다.
 pub fn send_packet(packet: &PacketBuffer) -> Result<(), NetworkError> {
-    let mut manager = NETWORK_MANAGER.lock();
-    manager.send_packet(packet)
+    x86_64::instructions::interrupts::without_interrupts(|| NETWORK_MANAGER.lock().send_packet(packet))
 }
 
 /// 패킷 수신
 pub fn receive_packet() -> Option<PacketBuffer> {
-    let mut manager = NETWORK_MANAGER.lock();
-    manager.receive_packet()
+    x86_64::instructions::interrupts::without_interrupts(|| NETWORK_MANAGER.lock().receive_packet())
 }
 
 /// MAC 주소 가져오기
@@ -205,41 +291,66 @@ pub fn bringup_ipv4_via_dhcp() {
     }
 }
 
-/// 네트워크 인터럽트 핸들러
+/// 네트워크 인터럽트 핸들러 등록
 ///
-/// 네트워크 인터럽트가 발생했을 때 호출됩니다.
-pub extern "x86-interrupt" fn network_interrupt_handler(
-    _stack_frame: x86_64::structures::idt::InterruptStackFrame
-) {
-    let mut manager = NETWORK_MANAGER.lock();
-    
-    if let Some(ref mut drv) = manager.driver {
-        match drv {
-            ActiveDriver::Rtl8139(d) => {
-                d.handle_interrupt();
-                while let Some(packet) = d.receive_packet() {
-                    if let Err(e) = crate::net::ethernet_frame::handle_ethernet_frame(&packet) {
-                        crate::log_warn!("Failed to handle Ethernet frame: {:?}", e);
+/// MSI를 우선 사용하고, 지원하지 않으면 PCI 인터럽트 라인(ISA IRQ)을 사용합니다.
+unsafe fn request_network_irq(device: &PciDevice) -> Option<MsiVectors> {
+    match pci_msi::enable_msi(device, network_irq_handler) {
+        Ok(msi) => return Some(msi),
+        Err(e) => {
+            crate::log_debug!("Network: MSI unavailable ({}), using INTx", e);
+        }
+    }
+
+    let line = device.interrupt_line;
+    let gsi = irq::isa_irq_to_gsi(line);
+    match irq::request_irq(gsi, network_irq_handler) {
+        Ok(vector) => {
+            crate::log_info!("Network interrupt handler registered (IRQ {}, GSI {}, vector {})",
+                           line, gsi, vector);
+        }
+        Err(e) => {
+            crate::log_warn!("Failed to request network IRQ {}: {}", line, e);
+        }
+    }
+    None
+}
+
+/// 네트워크 IRQ 핸들러
+///
+/// 네트워크 인터럽트가 발생했을 때 호출됩니다. 받은 프레임을 모은 뒤 매니저 락을 놓고
+/// 처리합니다. 프레임 처리 중 보내는 응답(ARP, ICMP, TCP ACK)이 `send_packet`에서 같은
+/// 락을 다시 잡기 때문입니다.
+pub fn network_irq_handler(irq: u32) {
+    let packets = {
+        let mut manager = NETWORK_MANAGER.lock();
+        let mut packets = Vec::new();
+        if let Some(ref mut drv) = manager.driver {
+            match drv {
+                ActiveDriver::Rtl8139(d) => {
+                    d.handle_interrupt();
+                    while let Some(packet) = d.receive_packet() {
+                        packets.push(packet);
                     }
                 }
-            }
-            #[cfg(feature = "net_r8168")]
-            ActiveDriver::Rtl8168(d) => {
-                d.handle_interrupt();
-                while let Some(packet) = d.receive_packet() {
-                    if let Err(e) = crate::net::ethernet_frame::handle_ethernet_frame(&packet) {
-                        crate::log_warn!("Failed to handle Ethernet frame: {:?}", e);
+                #[cfg(feature = "net_r8168")]
+                ActiveDriver::Rtl8168(d) => {
+                    d.handle_interrupt();
+                    while let Some(packet) = d.receive_packet() {
+                        packets.push(packet);
                     }
                 }
             }
         }
-    }
+        packets
+    };
     
-    // PIC에 인터럽트 종료 신호 전송
-    if let Some(irq) = manager.irq {
-        unsafe {
-            crate::interrupts::pic::end_of_interrupt(irq);
+    for packet in &packets {
+        if let Err(e) = crate::net::ethernet_frame::handle_ethernet_frame(packet) {
+            crate::log_warn!("Failed to handle Ethernet frame: {:?}", e);
         }
     }
+    
+    crate::task::event::signal_irq(irq);
 }
 
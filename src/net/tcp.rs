@@ -1,10 +1,20 @@
 //! TCP (Transmission Control Protocol) 모듈
 //!
-//! 이 모듈은 TCP 프로토콜을 구현합니다.
-//! 현재는 기본 구조만 구현되어 있으며, 완전한 TCP 스택은 향후 구현 예정입니다.
+//! 이 모듈은 TCP 세그먼트 송수신과 재전송을 구현합니다. 연결 상태 관리는 아직 없습니다.
+//!
+//! 데이터/SYN/FIN이 담긴 세그먼트는 모두 `send_segment`를 거쳐 재전송 큐에 들어가며,
+//! 재전송 타이머가 만료되면 `init`이 생성한 재전송 태스크가 깨어나 다시 보냅니다.
+//! 수신한 ACK는 확인된 세그먼트를 큐에서 지웁니다.
 
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+use core::sync::atomic::{AtomicU64, Ordering};
+use spin::Mutex;
+use x86_64::instructions::interrupts;
 use crate::net::ethernet::{PacketBuffer, NetworkError};
-use crate::net::ip::Ipv4Address;
+use crate::net::ip::{Ipv4Address, IpProtocol};
+use crate::task::event::{Event, EventWait};
+use crate::time::{TimerId, TimerRestart};
 
 /// TCP 포트 번호
 pub type TcpPort = u16;
@@ -161,6 +171,11 @@ impl TcpHeader {
         self.window = u16::to_be(window);
     }
     
+    /// 체크섬 설정
+    pub fn set_checksum(&mut self, checksum: u16) {
+        self.checksum = u16::to_be(checksum);
+    }
+    
     /// 패킷 버퍼에서 TCP 헤더 읽기
     pub fn from_packet(packet: &PacketBuffer) -> Option<&TcpHeader> {
         if packet.length < Self::BASE_SIZE {
@@ -200,10 +215,18 @@ impl TcpHeader {
     }
 }
 
+/// TCP 데이터 수신 이벤트
+static DATA_EVENT: Event = Event::new();
+
+/// 데이터(또는 FIN)가 담긴 TCP 세그먼트가 도착할 때까지 대기
+pub fn wait_data() -> EventWait<'static> {
+    DATA_EVENT.wait()
+}
+
 /// TCP 패킷 처리
 ///
-/// 수신된 TCP 패킷을 처리합니다.
-/// 현재는 기본 구조만 구현되어 있으며, 완전한 TCP 연결 관리는 향후 구현 예정입니다.
+/// 수신한 ACK로 재전송 큐를 정리하고, 데이터나 FIN/RST가 담겨 있으면 기다리는 태스크를
+/// 깨웁니다.
 pub fn handle_tcp_packet(ip_src: Ipv4Address, packet: &PacketBuffer) -> Result<(), NetworkError> {
     let header = match TcpHeader::from_packet(packet) {
         Some(h) => h,
@@ -227,18 +250,24 @@ pub fn handle_tcp_packet(ip_src: Ipv4Address, packet: &PacketBuffer) -> Result<(
         data.len()
     );
     
-    // TODO: TCP 연결 관리 구현
-    // - 연결 상태 관리 (LISTEN, SYN_SENT, SYN_RECEIVED, ESTABLISHED, FIN_WAIT, etc.)
-    // - 시퀀스 번호 및 확인 번호 관리
-    // - 슬라이딩 윈도우
-    // - 재전송 메커니즘
-    // - 연결 종료 처리
+    // 확인된 세그먼트는 재전송 큐에서 제거
+    if flags.ack {
+        acknowledge(ip_src, header.dst_port(), header.src_port(), header.acknowledgment());
+    }
+
+    // 데이터를 기다리는 태스크 깨우기
+    if !data.is_empty() || flags.fin || flags.rst {
+        DATA_EVENT.signal();
+    }
     
     Ok(())
 }
 
 /// TCP 패킷 생성
-pub fn create_tcp_packet(
+///
+/// 세그먼트는 `send_segment`로만 보내므로 모듈 밖에서는 사용하지 않습니다.
+/// 체크섬은 시퀀스/확인 번호를 채운 뒤 `transmit`이 계산합니다.
+fn create_tcp_packet(
     buffer: &mut PacketBuffer,
     src_port: TcpPort,
     dst_port: TcpPort,
@@ -268,3 +297,245 @@ pub fn create_tcp_packet(
     Ok(())
 }
 
+/// TCP 체크섬 계산 (RFC 793: 의사 헤더 + 세그먼트)
+///
+/// `segment`의 체크섬 필드는 0이어야 합니다.
+fn tcp_checksum(src_ip: Ipv4Address, dst_ip: Ipv4Address, segment: &[u8]) -> u16 {
+    let mut data = Vec::with_capacity(12 + segment.len());
+    data.extend_from_slice(&src_ip.0);
+    data.extend_from_slice(&dst_ip.0);
+    data.push(0);
+    data.push(u8::from(IpProtocol::Tcp));
+    data.extend_from_slice(&(segment.len() as u16).to_be_bytes());
+    data.extend_from_slice(segment);
+    crate::net::ip::calculate_checksum(&data)
+}
+
+/// 재전송 타임아웃 초기값 (RFC 6298: 1초)
+const TCP_INITIAL_RTO_MS: u64 = 1000;
+/// 재전송 타임아웃 최대값
+const TCP_MAX_RTO_MS: u64 = 60_000;
+/// 세그먼트당 최대 재전송 횟수
+const TCP_MAX_RETRIES: u32 = 5;
+
+/// 재전송 타이머가 만료되면 재전송 태스크를 깨우는 이벤트
+static RETRANSMIT_EVENT: Event = Event::new();
+
+/// 확인 응답을 기다리는 송신 세그먼트
+struct UnackedSegment {
+    dst_ip: Ipv4Address,
+    src_port: TcpPort,
+    dst_port: TcpPort,
+    seq: u32,
+    ack: u32,
+    flags: TcpFlags,
+    payload: Vec<u8>,
+    /// 현재 재전송 타임아웃 (지수 백오프)
+    rto_ms: u64,
+    retries: u32,
+    timer: Option<TimerId>,
+    /// 타임아웃이 만료되어 재전송이 필요한지 여부
+    due: bool,
+}
+
+impl UnackedSegment {
+    /// 이 세그먼트가 차지하는 시퀀스 공간의 끝 (SYN/FIN은 1씩 차지)
+    fn seq_end(&self) -> u32 {
+        let mut len = self.payload.len() as u32;
+        if self.flags.syn { len += 1; }
+        if self.flags.fin { len += 1; }
+        self.seq.wrapping_add(len)
+    }
+}
+
+/// 재전송 큐 (키: 세그먼트 번호)
+///
+/// 수신 IRQ와 타이머 softirq도 접근하므로 어느 컨텍스트에서든 인터럽트를 막고 잠급니다.
+static RETRANSMIT_QUEUE: Mutex<BTreeMap<u64, UnackedSegment>> = Mutex::new(BTreeMap::new());
+static NEXT_SEGMENT_KEY: AtomicU64 = AtomicU64::new(1);
+
+/// `a`가 `b`보다 같거나 뒤의 시퀀스 번호인지 (32비트 wrap-around 고려)
+fn seq_after_eq(a: u32, b: u32) -> bool {
+    (a.wrapping_sub(b) as i32) >= 0
+}
+
+/// TCP 세그먼트 송신
+///
+/// 모든 송신 세그먼트가 이 함수를 거칩니다. 데이터나 SYN/FIN을 포함한 세그먼트는
+/// 재전송 큐에 넣고 재전송 타이머를 설정하며, 타이머가 만료되면 재전송 태스크가
+/// 지수 백오프로 다시 보냅니다. ACK/RST만 담긴 세그먼트는 한 번만 보냅니다.
+pub fn send_segment(
+    dst_ip: Ipv4Address,
+    src_port: TcpPort,
+    dst_port: TcpPort,
+    seq: u32,
+    ack: u32,
+    flags: TcpFlags,
+    data: &[u8],
+) -> Result<(), NetworkError> {
+    transmit(dst_ip, src_port, dst_port, seq, ack, flags, data)?;
+
+    if data.is_empty() && !flags.syn && !flags.fin {
+        return Ok(());
+    }
+
+    let key = NEXT_SEGMENT_KEY.fetch_add(1, Ordering::Relaxed);
+    let timer = crate::time::wheel::add_timer_ms(TCP_INITIAL_RTO_MS, retransmit_timer_expired, key as usize);
+    let segment = UnackedSegment {
+        dst_ip,
+        src_port,
+        dst_port,
+        seq,
+        ack,
+        flags,
+        payload: data.to_vec(),
+        rto_ms: TCP_INITIAL_RTO_MS,
+        retries: 0,
+        timer: Some(timer),
+        due: false,
+    };
+    interrupts::without_interrupts(|| {
+        RETRANSMIT_QUEUE.lock().insert(key, segment);
+    });
+
+    Ok(())
+}
+
+/// 수신한 ACK 처리: `ack` 이전까지의 세그먼트를 재전송 큐에서 제거
+fn acknowledge(remote_ip: Ipv4Address, local_port: TcpPort, remote_port: TcpPort, ack: u32) {
+    let timers: Vec<TimerId> = interrupts::without_interrupts(|| {
+        let mut queue = RETRANSMIT_QUEUE.lock();
+        let acked: Vec<u64> = queue
+            .iter()
+            .filter(|(_, s)| {
+                s.dst_ip == remote_ip
+                    && s.src_port == local_port
+                    && s.dst_port == remote_port
+                    && seq_after_eq(ack, s.seq_end())
+            })
+            .map(|(&k, _)| k)
+            .collect();
+        acked.iter().filter_map(|k| queue.remove(k).and_then(|s| s.timer)).collect()
+    });
+
+    for timer in timers {
+        crate::time::wheel::cancel(timer);
+    }
+}
+
+/// 재전송 타이머 콜백 (타이머 softirq)
+///
+/// NIC 락을 softirq에서 잡지 않도록 재전송 필요 표시만 하고 재전송 태스크를 깨웁니다.
+/// 실제 송신은 태스크가 스레드 컨텍스트에서 수행합니다.
+fn retransmit_timer_expired(key: usize) -> TimerRestart {
+    interrupts::without_interrupts(|| {
+        if let Some(segment) = RETRANSMIT_QUEUE.lock().get_mut(&(key as u64)) {
+            segment.due = true;
+            segment.timer = None;
+        }
+    });
+    RETRANSMIT_EVENT.signal();
+    TimerRestart::NoRestart
+}
+
+/// TCP 초기화: 재전송 태스크 생성
+pub fn init() {
+    crate::task::spawn("tcp-retransmit", retransmit_task());
+}
+
+/// 재전송 태스크 (재전송 타이머가 만료될 때만 실행됨)
+async fn retransmit_task() {
+    loop {
+        RETRANSMIT_EVENT.wait().await;
+        retransmit_due();
+    }
+}
+
+/// 재전송 타이머가 만료된 세그먼트 재전송
+///
+/// RTO는 재전송마다 두 배가 되며, 최대 횟수를 넘으면 그 포트의 세그먼트를 모두 포기합니다.
+fn retransmit_due() {
+    let due: Vec<u64> = interrupts::without_interrupts(|| {
+        RETRANSMIT_QUEUE.lock().iter().filter(|(_, s)| s.due).map(|(&k, _)| k).collect()
+    });
+
+    for key in due {
+        let segment = interrupts::without_interrupts(|| RETRANSMIT_QUEUE.lock().remove(&key));
+        let mut segment = match segment {
+            Some(s) => s,
+            None => continue,
+        };
+
+        if segment.retries >= TCP_MAX_RETRIES {
+            crate::log_warn!(
+                "TCP: giving up on segment seq={} to {}:{} after {} retries",
+                segment.seq, segment.dst_ip, segment.dst_port, segment.retries
+            );
+            drop_segments(segment.src_port);
+            DATA_EVENT.signal();
+            continue;
+        }
+
+        segment.retries += 1;
+        segment.rto_ms = (segment.rto_ms * 2).min(TCP_MAX_RTO_MS);
+        segment.due = false;
+        crate::log_debug!(
+            "TCP: retransmit seq={} to {}:{} (try {}, rto {}ms)",
+            segment.seq, segment.dst_ip, segment.dst_port, segment.retries, segment.rto_ms
+        );
+
+        if let Err(e) = transmit(
+            segment.dst_ip,
+            segment.src_port,
+            segment.dst_port,
+            segment.seq,
+            segment.ack,
+            segment.flags,
+            &segment.payload,
+        ) {
+            crate::log_debug!("TCP: retransmit failed: {:?}", e);
+        }
+
+        segment.timer = Some(crate::time::wheel::add_timer_ms(segment.rto_ms, retransmit_timer_expired, key as usize));
+        interrupts::without_interrupts(|| {
+            RETRANSMIT_QUEUE.lock().insert(key, segment);
+        });
+    }
+}
+
+/// 로컬 포트의 재전송 대기 세그먼트를 모두 버림
+fn drop_segments(local_port: TcpPort) {
+    let timers: Vec<TimerId> = interrupts::without_interrupts(|| {
+        let mut queue = RETRANSMIT_QUEUE.lock();
+        let keys: Vec<u64> = queue.iter().filter(|(_, s)| s.src_port == local_port).map(|(&k, _)| k).collect();
+        keys.iter().filter_map(|k| queue.remove(k).and_then(|s| s.timer)).collect()
+    });
+    for timer in timers {
+        crate::time::wheel::cancel(timer);
+    }
+}
+
+/// 세그먼트를 만들어 IP로 전송
+///
+/// 시퀀스/확인 번호를 채운 뒤 마지막에 체크섬을 계산합니다.
+fn transmit(
+    dst_ip: Ipv4Address,
+    src_port: TcpPort,
+    dst_port: TcpPort,
+    seq: u32,
+    ack: u32,
+    flags: TcpFlags,
+    data: &[u8],
+) -> Result<(), NetworkError> {
+    let mut tcp_buffer = PacketBuffer::new();
+    create_tcp_packet(&mut tcp_buffer, src_port, dst_port, flags, data)?;
+    if let Some(header) = TcpHeader::from_packet_mut(&mut tcp_buffer) {
+        header.set_sequence(seq);
+        header.set_acknowledgment(ack);
+    }
+    let checksum = tcp_checksum(crate::net::ip::current_ipv4(), dst_ip, tcp_buffer.as_slice());
+    if let Some(header) = TcpHeader::from_packet_mut(&mut tcp_buffer) {
+        header.set_checksum(checksum);
+    }
+    crate::net::ip::send_ip_packet(dst_ip, IpProtocol::Tcp, tcp_buffer.as_slice())
+}
@@ -10,6 +10,9 @@ use crate::power::acpi_fadt::{parse_fadt, FadtInfo};
 /// ACPI RSDP 시그니처
 const RSDP_SIGNATURE: &[u8; 8] = b"RSD PTR ";
 
+/// 모든 ACPI 시스템 기술 테이블(SDT) 공통 헤더 길이
+pub const SDT_HEADER_LEN: usize = 36;
+
 /// I2C 장치 정보
 #[derive(Debug, Clone, Copy)]
 pub struct I2cDeviceInfo {
@@ -17,6 +20,8 @@ pub struct I2cDeviceInfo {
     pub base_address: PhysAddr,
     /// I2C 슬레이브 주소
     pub slave_address: u8,
+    /// I2C 컨트롤러 인터럽트 GSI
+    pub gsi: u32,
     /// HID (Hardware ID)
     pub hid: [u8; 8],
 }
@@ -145,6 +150,7 @@ impl AcpiParser {
         Some(I2cDeviceInfo {
             base_address: PhysAddr::new(0xFEDC3000), // I2C 컨트롤러 0
             slave_address: 0x15,                      // ELAN 트랙패드
+            gsi: 11,                                  // I2C 컨트롤러 0 (FCH I2CB)
             hid: *b"PNP0C50\0",                      // I2C HID 장치 표준 HID
         })
     }
@@ -300,7 +306,70 @@ pub fn find_i2c_touchpad() -> Option<I2cDeviceInfo> {
     Some(I2cDeviceInfo {
         base_address: PhysAddr::new(0xFEDC3000),
         slave_address: 0x15,
+        gsi: 11,
         hid: *b"PNP0C50\0",
     })
 }
 
+/// RSDT/XSDT를 따라가 시그니처가 일치하는 ACPI 테이블 찾기
+///
+/// 테이블은 물리 메모리 오프셋 매핑을 통해 접근하며, 길이와 체크섬을 검증합니다.
+/// 같은 시그니처의 테이블이 여러 개면 첫 번째 것을 반환합니다.
+///
+/// # Safety
+/// 메모리 관리가 초기화된 후에 호출되어야 합니다.
+pub unsafe fn find_table(signature: &[u8]) -> Option<&'static [u8]> {
+    let rsdp_phys = acpi_rsdp_addr()?;
+    let rsdp = map_phys(rsdp_phys, 36)?;
+    if &rsdp[0..8] != RSDP_SIGNATURE || !checksum_ok(&rsdp[0..20]) {
+        return None;
+    }
+
+    // ACPI 2.0+ (revision >= 2)이면 64비트 엔트리의 XSDT 사용
+    let revision = rsdp[15];
+    let xsdt_addr = u64::from_le_bytes(rsdp[24..32].try_into().ok()?);
+    let (root_phys, entry_size) = if revision >= 2 && xsdt_addr != 0 {
+        (xsdt_addr, 8)
+    } else {
+        (u32::from_le_bytes(rsdp[16..20].try_into().ok()?) as u64, 4)
+    };
+
+    let root = map_sdt(root_phys)?;
+    for entry in root[SDT_HEADER_LEN..].chunks_exact(entry_size) {
+        let table_phys = if entry_size == 8 {
+            u64::from_le_bytes(entry.try_into().ok()?)
+        } else {
+            u32::from_le_bytes(entry.try_into().ok()?) as u64
+        };
+        if let Some(table) = map_sdt(table_phys) {
+            if &table[0..4] == signature {
+                return Some(table);
+            }
+        }
+    }
+    None
+}
+
+/// SDT 헤더의 길이 필드를 읽어 테이블 전체를 슬라이스로 매핑 (체크섬 검증 포함)
+unsafe fn map_sdt(phys: u64) -> Option<&'static [u8]> {
+    let header = map_phys(phys, SDT_HEADER_LEN)?;
+    let len = u32::from_le_bytes(header[4..8].try_into().ok()?) as usize;
+    if len < SDT_HEADER_LEN {
+        return None;
+    }
+    let table = map_phys(phys, len)?;
+    if checksum_ok(table) { Some(table) } else { None }
+}
+
+unsafe fn map_phys(phys: u64, len: usize) -> Option<&'static [u8]> {
+    if phys == 0 {
+        return None;
+    }
+    let virt = crate::memory::paging::phys_to_virt(PhysAddr::new(phys))?;
+    Some(core::slice::from_raw_parts(virt.as_ptr::<u8>(), len))
+}
+
+/// 바이트 합이 0(mod 256)인지 확인
+fn checksum_ok(bytes: &[u8]) -> bool {
+    bytes.iter().fold(0u8, |acc, &b| acc.wrapping_add(b)) == 0
+}
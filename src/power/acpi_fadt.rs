@@ -9,10 +9,20 @@ pub struct FadtInfo {
     pub pm1a_cnt_blk: u32,
     pub pm1b_cnt_blk: u32,
     pub s3_sleep_type: Option<u16>,
+    /// CMOS RTC 세기 레지스터 인덱스 (0 = 없음)
+    pub century_reg: u8,
+    /// IA-PC Boot Architecture Flags
+    pub iapc_boot_arch: u16,
 }
 
+/// IAPC_BOOT_ARCH: OS가 PCIe ASPM을 켜면 안 됨
+pub const IAPC_BOOT_ARCH_NO_ASPM: u16 = 1 << 4;
+
 impl FadtInfo {
     pub fn is_valid(&self) -> bool { self.pm1a_cnt_blk != 0 }
+
+    /// 펌웨어가 OS의 ASPM 제어를 허용하는지 확인
+    pub fn aspm_allowed(&self) -> bool { self.iapc_boot_arch & IAPC_BOOT_ARCH_NO_ASPM == 0 }
 }
 
 /// Parse a very small subset of FADT from a raw memory slice.
@@ -22,15 +32,21 @@ pub fn parse_fadt(data: &[u8]) -> Option<FadtInfo> {
     if data.len() < 0x84 || &data[0..4] != b"FACP" { return None; }
 
     // Offsets are per ACPI 2.0+ (we keep it minimal and tolerant):
-    // PM1a_CNT_BLK at offset 0x64 (32-bit)
-    // PM1b_CNT_BLK at offset 0x68 (32-bit)
+    // PM1a_CNT_BLK at offset 0x40 (32-bit)
+    // PM1b_CNT_BLK at offset 0x44 (32-bit)
     // Sleep type values are not directly stored; typically via _Sx objects in DSDT.
     // For pragmatic use, we default S3 SLP_TYP to 5 if not discoverable.
 
-    let pm1a = u32::from_le_bytes([data[0x64], data[0x65], data[0x66], data[0x67]]);
-    let pm1b = u32::from_le_bytes([data[0x68], data[0x69], data[0x6A], data[0x6B]]);
+    let pm1a = u32::from_le_bytes([data[0x40], data[0x41], data[0x42], data[0x43]]);
+    let pm1b = u32::from_le_bytes([data[0x44], data[0x45], data[0x46], data[0x47]]);
+
+    // CENTURY at offset 0x6C (8-bit CMOS index)
+    let century = data[0x6C];
+
+    // IAPC_BOOT_ARCH at offset 0x6D (16-bit, ACPI 2.0+)
+    let iapc_boot_arch = u16::from_le_bytes([data[0x6D], data[0x6E]]);
 
-    Some(FadtInfo { pm1a_cnt_blk: pm1a, pm1b_cnt_blk: pm1b, s3_sleep_type: Some(5) })
+    Some(FadtInfo { pm1a_cnt_blk: pm1a, pm1b_cnt_blk: pm1b, s3_sleep_type: Some(5), century_reg: century, iapc_boot_arch })
 }
 
 
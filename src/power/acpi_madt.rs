@@ -0,0 +1,384 @@
+//! ACPI MADT (Multiple APIC Description Table) 파서
+//!
+//! Local APIC(프로세서), I/O APIC, 인터럽트 소스 오버라이드, NMI 엔트리를 읽어
+//! SMP 초기화와 인터럽트 라우팅에 제공합니다.
+
+use alloc::vec::Vec;
+use spin::Once;
+use crate::power::acpi::SDT_HEADER_LEN;
+
+/// MADT 고정 필드 길이 (SDT 헤더 + Local APIC 주소 + 플래그)
+const MADT_FIXED_LEN: usize = SDT_HEADER_LEN + 8;
+
+/// MADT 플래그: 8259 PIC 쌍이 함께 장착됨
+pub const PCAT_COMPAT: u32 = 1 << 0;
+
+/// 모든 프로세서를 가리키는 ACPI 프로세서 UID (Local APIC NMI 엔트리)
+pub const ALL_PROCESSORS: u32 = 0xFF;
+
+/// x2APIC NMI 엔트리에서 모든 프로세서를 가리키는 UID
+const ALL_PROCESSORS_X2APIC: u32 = 0xFFFF_FFFF;
+
+/// MADT 엔트리 타입
+mod entry_type {
+    pub const LOCAL_APIC: u8 = 0;
+    pub const IO_APIC: u8 = 1;
+    pub const INTERRUPT_OVERRIDE: u8 = 2;
+    pub const NMI_SOURCE: u8 = 3;
+    pub const LOCAL_APIC_NMI: u8 = 4;
+    pub const LOCAL_APIC_ADDRESS_OVERRIDE: u8 = 5;
+    pub const LOCAL_X2APIC: u8 = 9;
+    pub const LOCAL_X2APIC_NMI: u8 = 10;
+}
+
+/// Local APIC 플래그
+const LAPIC_ENABLED: u32 = 1 << 0;
+const LAPIC_ONLINE_CAPABLE: u32 = 1 << 1;
+
+/// 인터럽트 극성 (MPS INTI 플래그 비트 0-1)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Polarity {
+    /// 버스 규격을 따름 (ISA: active high)
+    BusDefault,
+    ActiveHigh,
+    ActiveLow,
+}
+
+/// 트리거 모드 (MPS INTI 플래그 비트 2-3)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TriggerMode {
+    /// 버스 규격을 따름 (ISA: edge)
+    BusDefault,
+    Edge,
+    Level,
+}
+
+/// MPS INTI 플래그 해석
+fn decode_inti_flags(flags: u16) -> (Polarity, TriggerMode) {
+    let polarity = match flags & 0b11 {
+        0b01 => Polarity::ActiveHigh,
+        0b11 => Polarity::ActiveLow,
+        _ => Polarity::BusDefault,
+    };
+    let trigger = match (flags >> 2) & 0b11 {
+        0b01 => TriggerMode::Edge,
+        0b11 => TriggerMode::Level,
+        _ => TriggerMode::BusDefault,
+    };
+    (polarity, trigger)
+}
+
+/// 프로세서 Local APIC (타입 0 또는 x2APIC 타입 9)
+#[derive(Debug, Clone, Copy)]
+pub struct MadtLocalApic {
+    /// ACPI 프로세서 UID
+    pub processor_uid: u32,
+    /// Local APIC ID
+    pub apic_id: u32,
+    /// 부팅 시 사용 가능
+    pub enabled: bool,
+    /// 비활성 상태지만 런타임에 켤 수 있음 (ACPI 6.3+)
+    pub online_capable: bool,
+}
+
+/// I/O APIC (타입 1)
+#[derive(Debug, Clone, Copy)]
+pub struct MadtIoApic {
+    pub id: u8,
+    /// 레지스터 물리 주소
+    pub address: u32,
+    /// 이 I/O APIC의 첫 번째 GSI (Global System Interrupt)
+    pub gsi_base: u32,
+}
+
+/// 인터럽트 소스 오버라이드 (타입 2)
+///
+/// ISA IRQ가 같은 번호의 GSI에 연결되지 않았거나 극성/트리거가 다를 때 사용됩니다.
+#[derive(Debug, Clone, Copy)]
+pub struct InterruptOverride {
+    pub bus: u8,
+    /// ISA IRQ 번호
+    pub source: u8,
+    pub gsi: u32,
+    pub polarity: Polarity,
+    pub trigger: TriggerMode,
+}
+
+/// NMI로 연결된 GSI (타입 3)
+#[derive(Debug, Clone, Copy)]
+pub struct NmiSource {
+    pub gsi: u32,
+    pub polarity: Polarity,
+    pub trigger: TriggerMode,
+}
+
+/// Local APIC LINT 핀의 NMI 연결 (타입 4 또는 x2APIC 타입 10)
+#[derive(Debug, Clone, Copy)]
+pub struct LocalApicNmi {
+    /// ACPI 프로세서 UID (`ALL_PROCESSORS` = 모든 프로세서)
+    pub processor_uid: u32,
+    /// LINT 핀 번호 (0 또는 1)
+    pub lint: u8,
+    pub polarity: Polarity,
+    pub trigger: TriggerMode,
+}
+
+impl LocalApicNmi {
+    /// 지정한 프로세서에 적용되는지 확인
+    pub fn applies_to(&self, processor_uid: u32) -> bool {
+        self.processor_uid == ALL_PROCESSORS || self.processor_uid == processor_uid
+    }
+}
+
+/// 파싱된 MADT 정보
+#[derive(Debug, Clone, Default)]
+pub struct MadtInfo {
+    /// Local APIC 물리 주소 (주소 오버라이드 엔트리 반영)
+    pub local_apic_addr: u64,
+    /// MADT 플래그 (`PCAT_COMPAT` 등)
+    pub flags: u32,
+    pub local_apics: Vec<MadtLocalApic>,
+    pub io_apics: Vec<MadtIoApic>,
+    pub overrides: Vec<InterruptOverride>,
+    pub nmi_sources: Vec<NmiSource>,
+    pub local_apic_nmis: Vec<LocalApicNmi>,
+}
+
+impl MadtInfo {
+    /// 8259 PIC가 장착되어 있는지 확인
+    pub fn has_legacy_pic(&self) -> bool {
+        self.flags & PCAT_COMPAT != 0
+    }
+
+    /// 부팅 시 사용 가능한 프로세서
+    pub fn enabled_cpus(&self) -> impl Iterator<Item = &MadtLocalApic> {
+        self.local_apics.iter().filter(|lapic| lapic.enabled)
+    }
+
+    /// 부팅 시 또는 이후에 켤 수 있는 프로세서 수
+    pub fn possible_cpu_count(&self) -> usize {
+        self.local_apics.iter().filter(|l| l.enabled || l.online_capable).count()
+    }
+
+    /// APIC ID로 프로세서 UID 찾기
+    pub fn processor_uid(&self, apic_id: u32) -> Option<u32> {
+        self.local_apics
+            .iter()
+            .find(|lapic| lapic.apic_id == apic_id)
+            .map(|lapic| lapic.processor_uid)
+    }
+
+    /// ISA IRQ에 대한 오버라이드 찾기
+    pub fn isa_override(&self, irq: u8) -> Option<&InterruptOverride> {
+        self.overrides.iter().find(|o| o.bus == 0 && o.source == irq)
+    }
+
+    /// GSI를 대상으로 하는 오버라이드 찾기
+    pub fn gsi_override(&self, gsi: u32) -> Option<&InterruptOverride> {
+        self.overrides.iter().find(|o| o.gsi == gsi)
+    }
+
+    /// GSI를 담당하는 I/O APIC 찾기
+    pub fn io_apic_for_gsi(&self, gsi: u32) -> Option<&MadtIoApic> {
+        // GSI 범위의 끝은 I/O APIC 레지스터에서만 알 수 있으므로 가장 가까운 시작점을 선택
+        self.io_apics
+            .iter()
+            .filter(|io| io.gsi_base <= gsi)
+            .max_by_key(|io| io.gsi_base)
+    }
+}
+
+fn read_u16(data: &[u8], off: usize) -> u16 {
+    u16::from_le_bytes([data[off], data[off + 1]])
+}
+
+fn read_u32(data: &[u8], off: usize) -> u32 {
+    u32::from_le_bytes([data[off], data[off + 1], data[off + 2], data[off + 3]])
+}
+
+fn read_u64(data: &[u8], off: usize) -> u64 {
+    (read_u32(data, off) as u64) | ((read_u32(data, off + 4) as u64) << 32)
+}
+
+/// MADT 파싱
+///
+/// `data`는 체크섬이 검증된 테이블 전체여야 합니다 (`acpi::find_table(b"APIC")`).
+/// 알 수 없는 엔트리 타입은 건너뛰며, 길이가 잘못된 엔트리를 만나면 그 지점에서 멈춥니다.
+pub fn parse_madt(data: &[u8]) -> Option<MadtInfo> {
+    if data.len() < MADT_FIXED_LEN || &data[0..4] != b"APIC" {
+        return None;
+    }
+
+    let mut info = MadtInfo {
+        local_apic_addr: read_u32(data, SDT_HEADER_LEN) as u64,
+        flags: read_u32(data, SDT_HEADER_LEN + 4),
+        ..MadtInfo::default()
+    };
+
+    let mut off = MADT_FIXED_LEN;
+    while off + 2 <= data.len() {
+        let kind = data[off];
+        let len = data[off + 1] as usize;
+        if len < 2 || off + len > data.len() {
+            break;
+        }
+        let e = &data[off..off + len];
+
+        match (kind, len) {
+            (entry_type::LOCAL_APIC, 8..) => {
+                let flags = read_u32(e, 4);
+                info.local_apics.push(MadtLocalApic {
+                    processor_uid: e[2] as u32,
+                    apic_id: e[3] as u32,
+                    enabled: flags & LAPIC_ENABLED != 0,
+                    online_capable: flags & LAPIC_ONLINE_CAPABLE != 0,
+                });
+            }
+            (entry_type::IO_APIC, 12..) => {
+                info.io_apics.push(MadtIoApic {
+                    id: e[2],
+                    address: read_u32(e, 4),
+                    gsi_base: read_u32(e, 8),
+                });
+            }
+            (entry_type::INTERRUPT_OVERRIDE, 10..) => {
+                let (polarity, trigger) = decode_inti_flags(read_u16(e, 8));
+                info.overrides.push(InterruptOverride {
+                    bus: e[2],
+                    source: e[3],
+                    gsi: read_u32(e, 4),
+                    polarity,
+                    trigger,
+                });
+            }
+            (entry_type::NMI_SOURCE, 8..) => {
+                let (polarity, trigger) = decode_inti_flags(read_u16(e, 2));
+                info.nmi_sources.push(NmiSource { gsi: read_u32(e, 4), polarity, trigger });
+            }
+            (entry_type::LOCAL_APIC_NMI, 6..) => {
+                let (polarity, trigger) = decode_inti_flags(read_u16(e, 3));
+                info.local_apic_nmis.push(LocalApicNmi {
+                    processor_uid: e[2] as u32,
+                    lint: e[5],
+                    polarity,
+                    trigger,
+                });
+            }
+            (entry_type::LOCAL_APIC_ADDRESS_OVERRIDE, 12..) => {
+                info.local_apic_addr = read_u64(e, 4);
+            }
+            (entry_type::LOCAL_X2APIC, 16..) => {
+                let flags = read_u32(e, 8);
+                info.local_apics.push(MadtLocalApic {
+                    processor_uid: read_u32(e, 12),
+                    apic_id: read_u32(e, 4),
+                    enabled: flags & LAPIC_ENABLED != 0,
+                    online_capable: flags & LAPIC_ONLINE_CAPABLE != 0,
+                });
+            }
+            (entry_type::LOCAL_X2APIC_NMI, 12..) => {
+                let (polarity, trigger) = decode_inti_flags(read_u16(e, 2));
+                let uid = read_u32(e, 4);
+                info.local_apic_nmis.push(LocalApicNmi {
+                    processor_uid: if uid == ALL_PROCESSORS_X2APIC { ALL_PROCESSORS } else { uid },
+                    lint: e[8],
+                    polarity,
+                    trigger,
+                });
+            }
+            _ => {}
+        }
+
+        off += len;
+    }
+
+    Some(info)
+}
+
+/// 파싱된 MADT (부팅 중 한 번 읽음)
+static MADT: Once<Option<MadtInfo>> = Once::new();
+
+/// MADT 읽기 및 파싱 (처음 호출할 때만 테이블을 읽음)
+///
+/// # Safety
+/// 메모리 관리가 초기화된 후에 호출되어야 합니다 (ACPI 테이블 접근).
+pub unsafe fn load() -> Option<&'static MadtInfo> {
+    MADT.call_once(|| {
+        let madt = crate::power::acpi_table_fetch(b"APIC").and_then(parse_madt);
+        match &madt {
+            Some(info) => {
+                crate::log_info!(
+                    "ACPI MADT: {} Local APIC(s), {} I/O APIC(s), {} override(s), {} NMI(s)",
+                    info.local_apics.len(),
+                    info.io_apics.len(),
+                    info.overrides.len(),
+                    info.nmi_sources.len() + info.local_apic_nmis.len()
+                );
+            }
+            None => {
+                crate::log_warn!("ACPI MADT not found");
+            }
+        }
+        madt
+    })
+    .as_ref()
+}
+
+/// 이미 읽은 MADT 반환 (`load` 이전이거나 MADT가 없으면 `None`)
+pub fn get() -> Option<&'static MadtInfo> {
+    MADT.get().and_then(|madt| madt.as_ref())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    /// 헤더 + 엔트리로 테스트용 MADT 생성 (체크섬은 파서가 보지 않음)
+    fn build_madt(entries: &[&[u8]]) -> Vec<u8> {
+        let mut data = vec![0u8; MADT_FIXED_LEN];
+        data[0..4].copy_from_slice(b"APIC");
+        data[SDT_HEADER_LEN..SDT_HEADER_LEN + 4].copy_from_slice(&0xFEE0_0000u32.to_le_bytes());
+        data[SDT_HEADER_LEN + 4..SDT_HEADER_LEN + 8].copy_from_slice(&PCAT_COMPAT.to_le_bytes());
+        for e in entries {
+            data.extend_from_slice(e);
+        }
+        let len = data.len() as u32;
+        data[4..8].copy_from_slice(&len.to_le_bytes());
+        data
+    }
+
+    #[test_case]
+    fn test_parse_madt_entries() {
+        let data = build_madt(&[
+            &[0, 8, 0, 0, 1, 0, 0, 0],                // CPU 0, APIC 0, enabled
+            &[0, 8, 1, 2, 2, 0, 0, 0],                // CPU 1, APIC 2, online capable
+            &[1, 12, 4, 0, 0, 0, 0xC0, 0xFE, 0, 0, 0, 0], // I/O APIC 4 @ 0xFEC00000
+            &[2, 10, 0, 0, 2, 0, 0, 0, 0, 0],         // IRQ0 -> GSI2
+            &[2, 10, 0, 9, 9, 0, 0, 0, 0x0F, 0],      // IRQ9 level, active low
+            &[4, 6, 0xFF, 0x05, 0x00, 1],             // 모든 CPU LINT1 = NMI
+        ]);
+        let madt = parse_madt(&data).unwrap();
+
+        assert_eq!(madt.local_apic_addr, 0xFEE0_0000);
+        assert!(madt.has_legacy_pic());
+        assert_eq!(madt.enabled_cpus().count(), 1);
+        assert_eq!(madt.possible_cpu_count(), 2);
+        assert_eq!(madt.processor_uid(2), Some(1));
+        assert_eq!(madt.io_apics[0].address, 0xFEC0_0000);
+        assert_eq!(madt.io_apic_for_gsi(9).map(|io| io.id), Some(4));
+        assert_eq!(madt.isa_override(0).map(|o| o.gsi), Some(2));
+        let irq9 = madt.isa_override(9).unwrap();
+        assert_eq!((irq9.polarity, irq9.trigger), (Polarity::ActiveLow, TriggerMode::Level));
+        assert!(madt.isa_override(1).is_none());
+        assert_eq!(madt.local_apic_nmis[0].lint, 1);
+        assert!(madt.local_apic_nmis[0].applies_to(7));
+    }
+
+    #[test_case]
+    fn test_parse_madt_rejects_other_tables() {
+        let mut data = build_madt(&[]);
+        data[0..4].copy_from_slice(b"FACP");
+        assert!(parse_madt(&data).is_none());
+    }
+}
@@ -0,0 +1,133 @@
+//! ACPI MCFG (PCI Express Memory-mapped Configuration) 파서
+//!
+//! PCI 세그먼트 그룹별 ECAM(Enhanced Configuration Access Mechanism) 영역의
+//! 물리 주소와 버스 범위를 읽어 `drivers::pci`의 확장 구성 공간 접근에 제공합니다.
+
+use alloc::vec::Vec;
+use spin::Once;
+use crate::power::acpi::SDT_HEADER_LEN;
+
+/// MCFG 고정 필드 길이 (SDT 헤더 + 예약 8바이트)
+const MCFG_FIXED_LEN: usize = SDT_HEADER_LEN + 8;
+
+/// 구성 공간 영역 엔트리 길이
+const MCFG_ENTRY_LEN: usize = 16;
+
+/// ECAM 영역 하나 (세그먼트 그룹의 버스 범위)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EcamRegion {
+    /// 버스 0에 해당하는 물리 주소 (`start_bus`가 0이 아니어도 버스 0 기준)
+    pub base_address: u64,
+    pub segment: u16,
+    pub start_bus: u8,
+    pub end_bus: u8,
+}
+
+impl EcamRegion {
+    /// 버스 번호가 이 영역에 포함되는지 확인
+    pub fn contains(&self, segment: u16, bus: u8) -> bool {
+        self.segment == segment && bus >= self.start_bus && bus <= self.end_bus
+    }
+
+    /// 함수 구성 공간의 물리 주소 (버스 << 20 | 디바이스 << 15 | 함수 << 12)
+    pub fn function_address(&self, bus: u8, device: u8, function: u8) -> u64 {
+        self.base_address
+            + ((bus as u64) << 20)
+            + (((device & 0x1F) as u64) << 15)
+            + (((function & 0x07) as u64) << 12)
+    }
+
+    /// 영역이 덮는 물리 메모리 범위 (시작, 길이)
+    pub fn mapped_range(&self) -> (u64, u64) {
+        let start = self.base_address + ((self.start_bus as u64) << 20);
+        let buses = self.end_bus as u64 - self.start_bus as u64 + 1;
+        (start, buses << 20)
+    }
+}
+
+fn read_u16(data: &[u8], off: usize) -> u16 {
+    u16::from_le_bytes([data[off], data[off + 1]])
+}
+
+fn read_u64(data: &[u8], off: usize) -> u64 {
+    u64::from_le_bytes(data[off..off + 8].try_into().unwrap_or([0; 8]))
+}
+
+/// MCFG 파싱
+///
+/// `data`는 체크섬이 검증된 테이블 전체여야 합니다 (`acpi::find_table(b"MCFG")`).
+/// 버스 범위가 뒤집힌 엔트리는 건너뜁니다.
+pub fn parse_mcfg(data: &[u8]) -> Option<Vec<EcamRegion>> {
+    if data.len() < MCFG_FIXED_LEN || &data[0..4] != b"MCFG" {
+        return None;
+    }
+
+    let regions = data[MCFG_FIXED_LEN..]
+        .chunks_exact(MCFG_ENTRY_LEN)
+        .map(|e| EcamRegion {
+            base_address: read_u64(e, 0),
+            segment: read_u16(e, 8),
+            start_bus: e[10],
+            end_bus: e[11],
+        })
+        .filter(|r| r.base_address != 0 && r.start_bus <= r.end_bus)
+        .collect();
+    Some(regions)
+}
+
+/// 파싱된 MCFG (부팅 중 한 번 읽음)
+static MCFG: Once<Option<Vec<EcamRegion>>> = Once::new();
+
+/// MCFG를 읽어 캐시
+///
+/// # Safety
+/// 메모리 관리가 초기화된 후에 호출되어야 합니다 (ACPI 테이블 접근).
+pub unsafe fn load() -> Option<&'static [EcamRegion]> {
+    MCFG.call_once(|| {
+        let mcfg = crate::power::acpi_table_fetch(b"MCFG").and_then(parse_mcfg);
+        match &mcfg {
+            Some(regions) => {
+                for r in regions {
+                    crate::log_info!(
+                        "ACPI MCFG: segment {} buses {:02X}-{:02X} ECAM at 0x{:X}",
+                        r.segment, r.start_bus, r.end_bus, r.base_address
+                    );
+                }
+            }
+            None => {
+                crate::log_info!("ACPI MCFG not found, PCI config space limited to 256 bytes");
+            }
+        }
+        mcfg
+    })
+    .as_deref()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    #[test_case]
+    fn test_parse_mcfg_regions() {
+        let mut data = vec![0u8; MCFG_FIXED_LEN];
+        data[0..4].copy_from_slice(b"MCFG");
+        let mut entry = [0u8; MCFG_ENTRY_LEN];
+        entry[0..8].copy_from_slice(&0xB000_0000u64.to_le_bytes());
+        entry[10] = 0x00;
+        entry[11] = 0xFF;
+        data.extend_from_slice(&entry);
+        // 버스 범위가 뒤집힌 엔트리는 무시
+        entry[10] = 0x20;
+        entry[11] = 0x10;
+        data.extend_from_slice(&entry);
+
+        let regions = parse_mcfg(&data).unwrap();
+        assert_eq!(regions.len(), 1);
+        let ecam = regions[0];
+        assert!(ecam.contains(0, 0x3A));
+        assert!(!ecam.contains(1, 0x3A));
+        assert_eq!(ecam.function_address(1, 2, 3), 0xB000_0000 + (1 << 20) + (2 << 15) + (3 << 12));
+        assert_eq!(ecam.mapped_range(), (0xB000_0000, 256 << 20));
+    }
+}
@@ -90,6 +90,7 @@ impl BatteryManager {
         // 현재는 기본값으로 설정
         // TODO: ACPI 배터리 정보 읽기 구현
         
+        let previous = self.battery_info;
         self.battery_info = BatteryInfo {
             status: BatteryStatus::Unknown,
             level_percent: 100, // 기본값: 100%
@@ -99,6 +100,7 @@ impl BatteryManager {
         };
         
         self.last_update_time = crate::drivers::timer::get_milliseconds();
+        publish_changes(&previous, &self.battery_info);
         
         Ok(())
     }
@@ -194,6 +196,29 @@ impl BatteryManager {
     }
 }
 
+/// AC 전원으로 동작 중인지 (상태를 모르면 `None`)
+fn on_ac_power(status: BatteryStatus) -> Option<bool> {
+    match status {
+        BatteryStatus::NotPresent | BatteryStatus::Charging | BatteryStatus::Full => Some(true),
+        BatteryStatus::Discharging => Some(false),
+        BatteryStatus::Unknown => None,
+    }
+}
+
+/// 배터리 상태/잔량과 전원 소스 변화를 커널 이벤트로 발행
+fn publish_changes(previous: &BatteryInfo, current: &BatteryInfo) {
+    use crate::kernel::uevent::{self, KernelEvent};
+
+    if let Some(on_ac) = on_ac_power(current.status) {
+        if on_ac_power(previous.status) != Some(on_ac) {
+            uevent::publish(KernelEvent::PowerSource { on_ac });
+        }
+    }
+    if previous.status != current.status || previous.level_percent != current.level_percent {
+        uevent::publish(KernelEvent::Battery { percent: current.level_percent, status: current.status });
+    }
+}
+
 /// 전역 배터리 관리자
 static BATTERY_MANAGER: Mutex<BatteryManager> = Mutex::new(BatteryManager {
     battery_info: BatteryInfo { status: BatteryStatus::Unknown, level_percent: 100, capacity_mah: None, design_capacity_mah: None, voltage_mv: None },
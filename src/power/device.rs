@@ -34,28 +34,6 @@ pub fn apply_disk_power_policy(_policy: &DiskPowerPolicy) {
     }
 }
 
-/// Power off disk (suspend 준비)
-pub fn power_off_disk() -> Result<(), PowerError> {
-    #[cfg(feature = "fs")]
-    {
-        // ATA STANDBY 명령 전송
-        crate::log_info!("Disk power off: entering standby");
-        // 실제 구현: drivers::ata::standby()
-    }
-    Ok(())
-}
-
-/// Power on disk (resume)
-pub fn power_on_disk() -> Result<(), PowerError> {
-    #[cfg(feature = "fs")]
-    {
-        // 디스크 활성화 (IDLE 명령으로 깨우기)
-        crate::log_info!("Disk power on: waking from standby");
-        // 실제 구현: drivers::ata::wake()
-    }
-    Ok(())
-}
-
 /// Enable network low power mode
 pub fn enable_network_low_power() {
     #[cfg(feature = "net")]
@@ -66,50 +44,6 @@ pub fn enable_network_low_power() {
     }
 }
 
-/// Power off network (suspend 준비)
-pub fn power_off_network() -> Result<(), PowerError> {
-    #[cfg(feature = "net")]
-    {
-        // 네트워크 인터럽트 비활성화 및 low-power 모드
-        enable_network_low_power();
-        crate::log_info!("Network power off");
-    }
-    Ok(())
-}
-
-/// Power on network (resume)
-pub fn power_on_network() -> Result<(), PowerError> {
-    #[cfg(feature = "net")]
-    {
-        // 네트워크 인터럽트 재활성화 및 normal 모드
-        crate::log_info!("Network power on");
-        // 실제 구현: drivers::rtl8139::wake()
-    }
-    Ok(())
-}
-
-/// Power off input devices (suspend 준비)
-pub fn power_off_input() -> Result<(), PowerError> {
-    // 입력 장치 인터럽트 비활성화
-    unsafe {
-        crate::interrupts::pic::set_mask(1, false);  // 키보드
-        crate::interrupts::pic::set_mask(12, false);  // 마우스
-    }
-    crate::log_info!("Input devices power off");
-    Ok(())
-}
-
-/// Power on input devices (resume)
-pub fn power_on_input() -> Result<(), PowerError> {
-    // 입력 장치 인터럽트 재활성화
-    unsafe {
-        crate::interrupts::pic::set_mask(1, true);   // 키보드
-        crate::interrupts::pic::set_mask(12, true);   // 마우스
-    }
-    crate::log_info!("Input devices power on");
-    Ok(())
-}
-
 /// DPMS: Display Power Management Signaling
 pub fn dpms_set_display_sleep(sleep: bool) {
     if sleep {
@@ -150,24 +84,12 @@ pub fn get_backlight_brightness() -> u8 {
     }
 }
 
-/// Power off display (suspend 준비)
-pub fn power_off_display() -> Result<(), PowerError> {
-    dpms_set_display_sleep(true);
-    Ok(())
-}
-
-/// Power on display (resume)
-pub fn power_on_display() -> Result<(), PowerError> {
-    dpms_set_display_sleep(false);
-    Ok(())
-}
-
 /// Save device state before suspend
 pub fn save_device_state() -> Result<(), PowerError> {
     let mut state = DeviceState::default();
     
-    // PIC 마스크 상태 저장
-    crate::interrupts::pic::save_interrupt_mask();
+    // IRQ 마스크 상태 저장
+    crate::interrupts::irq::save_interrupt_mask();
     
     // 네트워크 상태 저장
     #[cfg(feature = "net")]
@@ -190,8 +112,8 @@ pub fn save_device_state() -> Result<(), PowerError> {
 pub fn restore_device_state() -> Result<(), PowerError> {
     let state = SAVED_DEVICE_STATE.lock().take();
     
-    // PIC 마스크 상태 복원
-    crate::interrupts::pic::restore_interrupt_mask();
+    // IRQ 마스크 상태 복원 (I/O APIC 리다이렉션 엔트리 재프로그래밍 포함)
+    crate::interrupts::irq::restore_interrupt_mask();
     
     if let Some(s) = state {
         // 디스플레이 상태 복원
@@ -210,55 +132,37 @@ pub fn restore_device_state() -> Result<(), PowerError> {
 }
 
 /// Quiesce all devices before suspend
+///
+/// 디바이스 트리를 자식부터 정지합니다 (`drivers::base::suspend_all`).
 pub fn quiesce_all_devices() -> Result<(), PowerError> {
     crate::log_info!("Quiescing all devices...");
     
     // 장치 상태 저장
     save_device_state()?;
     
-    // 디스플레이 끄기
-    power_off_display()?;
-    
-    // 네트워크 low-power 모드
-    power_off_network()?;
-    
-    // 디스크 standby
-    power_off_disk()?;
-    
-    // 입력 장치 인터럽트 비활성화
-    power_off_input()?;
-
-    // 등록된 서스펜드 훅 실행(베스트 에포트)
-    let _ = crate::power::device_hooks::run_suspend_hooks();
+    // 드라이버별 suspend (실패하면 이미 정지한 디바이스는 다시 재개됨)
+    if let Err(e) = crate::drivers::base::suspend_all() {
+        crate::log_warn!("Device suspend aborted: {}", e);
+        let _ = restore_device_state();
+        return Err(PowerError::DeviceSuspendFailed);
+    }
     
     crate::log_info!("All devices quiesced");
     Ok(())
 }
 
 /// Resume all devices after wake
+///
+/// 디바이스 트리를 부모부터 재개합니다 (`drivers::base::resume_all`).
 pub fn resume_all_devices() -> Result<(), PowerError> {
     crate::log_info!("Resuming all devices...");
     
-    // 입력 장치 재활성화
-    power_on_input()?;
-    
-    // 디스크 활성화
-    power_on_disk()?;
-    
-    // 네트워크 활성화
-    power_on_network()?;
-    
-    // 디스플레이 켜기
-    power_on_display()?;
+    // 드라이버별 resume (베스트 에포트, 실패한 디바이스는 로그만 남김)
+    let _ = crate::drivers::base::resume_all();
     
     // 장치 상태 복원
     restore_device_state()?;
-
-    // 등록된 리줌 훅 실행(베스트 에포트)
-    let _ = crate::power::device_hooks::run_resume_hooks();
     
     crate::log_info!("All devices resumed");
     Ok(())
 }
-
-
@@ -1,5 +1,8 @@
 //! CPU Idle (C-State) management
+//!
+//! 현재 C-State와 진입 시각은 CPU마다 다르므로 per-CPU 데이터(`PerCpu::idle`)에 기록합니다.
 
+use core::sync::atomic::Ordering;
 use spin::Mutex;
 
 /// Represents a CPU C-State
@@ -22,9 +25,6 @@ pub struct IdleStateManager {
     last_wakeup_time_ms: u64,
 }
 
-static CURRENT_C_STATE: Mutex<u8> = Mutex::new(0);
-static C_STATE_ENTRY_TIME: Mutex<u64> = Mutex::new(0);
-
 impl IdleStateManager {
     pub fn new() -> Self {
         // Snapshot current global defaults; if empty, we keep HLT fallback
@@ -120,18 +120,16 @@ impl IdleStateManager {
         self.enter_c_state(optimal_level);
     }
 
+    /// C-State 진입
+    ///
+    /// 인터럽트가 비활성화된 상태에서 호출해야 합니다. `sti` 바로 뒤에 `hlt`/`mwait`를 실행해
+    /// 원자적으로 잠들므로 호출자가 확인한 뒤 온 인터럽트를 놓치지 않습니다. 반환 시
+    /// 인터럽트는 활성화되어 있습니다.
     #[inline]
     pub unsafe fn enter_c_state(&mut self, target_level: u8) {
         // Record C-state entry
         let now_ms = crate::drivers::timer::get_milliseconds();
-        let mut current = CURRENT_C_STATE.lock();
-        let mut entry_time = C_STATE_ENTRY_TIME.lock();
-        
-        // Update residency tracking if state changed
-        if *current != target_level {
-            *current = target_level;
-            *entry_time = now_ms;
-        }
+        record_c_state_entry(target_level, now_ms);
         
         // Entry time 기록 (동적 조정용)
         self.last_entry_time_ms = now_ms;
@@ -141,16 +139,16 @@ impl IdleStateManager {
             // MONITOR/MWAIT pair - best-effort
             #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
             {
-                // Hint in EAX, extensions in ECX (0)
+                // Hint in EAX, extensions in ECX (0); STI shadow covers MWAIT
                 core::arch::asm!(
-                    "mfence; mov eax, {hint:e}; xor ecx, ecx; mwait",
+                    "mfence; mov eax, {hint:e}; xor ecx, ecx; sti; mwait",
                     hint = in(reg) state.mwait_hint,
-                    options(nostack, preserves_flags)
+                    options(nostack)
                 );
                 return;
             }
         }
-        x86_64::instructions::hlt();
+        x86_64::instructions::interrupts::enable_and_hlt();
     }
 
     #[inline]
@@ -168,13 +166,7 @@ impl IdleStateManager {
         
         // Record C-state entry
         let now_ms = crate::drivers::timer::get_milliseconds();
-        let mut current = CURRENT_C_STATE.lock();
-        let mut entry_time = C_STATE_ENTRY_TIME.lock();
-        
-        if *current != target_level {
-            *current = target_level;
-            *entry_time = now_ms;
-        }
+        record_c_state_entry(target_level, now_ms);
         
         if let Some(state) = best {
             #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
@@ -191,14 +183,22 @@ impl IdleStateManager {
     }
 }
 
-/// Get current C-state
+/// Update this CPU's residency tracking if the C-state changed
+fn record_c_state_entry(level: u8, now_ms: u64) {
+    let idle = crate::per_cpu!(idle);
+    if idle.c_state.swap(level, Ordering::Relaxed) != level {
+        idle.c_state_entry_ms.store(now_ms, Ordering::Relaxed);
+    }
+}
+
+/// Get current C-state (this CPU)
 pub fn get_current_c_state() -> u8 {
-    *CURRENT_C_STATE.lock()
+    crate::per_cpu!(idle.c_state).load(Ordering::Relaxed)
 }
 
-/// Get C-state entry time
+/// Get C-state entry time (this CPU)
 pub fn get_c_state_entry_time_ms() -> u64 {
-    *C_STATE_ENTRY_TIME.lock()
+    crate::per_cpu!(idle.c_state_entry_ms).load(Ordering::Relaxed)
 }
 
 // Global default C-state table populated by ACPI (or fallback)
@@ -105,16 +105,20 @@ impl PowerManager {
                 self.cpu_scaling.set_max_performance()?;
                 // 고성능 모드에서는 잦은 타이머 wakeup이 필요하므로 coalescing 비활성화
                 crate::drivers::timer::set_idle_tick_coalescing(false, 0);
+                crate::time::set_default_slack_ns(0);
             }
             PowerMode::Balanced => {
                 self.cpu_scaling.set_balanced()?;
                 // 균형 모드: 중간 수준의 coalescing (약 200Hz 등가)
                 crate::drivers::timer::set_idle_tick_coalescing(true, 4);
+                crate::time::set_default_slack_ns(50 * crate::time::NSEC_PER_USEC);
             }
             PowerMode::PowerSaving => {
                 self.cpu_scaling.set_power_saving()?;
                 // 절전 모드: aggressive coalescing (약 100Hz 등가)
                 crate::drivers::timer::set_idle_tick_coalescing(true, 9);
+                // 타이머 slack을 늘려 만료 시점을 묶음
+                crate::time::set_default_slack_ns(2 * crate::time::NSEC_PER_MSEC);
             }
         }
         
@@ -131,9 +135,20 @@ impl PowerManager {
     /// 유휴 상태에서 CPU를 최대한 절전 모드로 전환합니다.
     /// 지연 시간 기반 동적 조정을 사용합니다.
     ///
+    /// 인터럽트를 끈 채로 대기 조건을 확인하고 틱을 멈춘 뒤 `sti; hlt`로 잠들므로
+    /// (`timer::idle_wait`와 같은 방식) 그 사이에 온 인터럽트를 놓치지 않습니다.
+    ///
     /// # Safety
-    /// 인터럽트가 활성화된 상태에서 호출되어야 합니다.
+    /// 인터럽트가 활성화된 상태에서 호출되어야 합니다. 반환 시에도 활성화되어 있습니다.
     pub unsafe fn enter_idle(&mut self) {
+        x86_64::instructions::interrupts::disable();
+
+        // 실행 대기 중인 비동기 태스크가 있으면 잠들지 않음
+        if crate::task::has_ready() {
+            x86_64::instructions::interrupts::enable();
+            return;
+        }
+
         // CPU 사용률 업데이트
         crate::power::cpu_usage::update_cpu_usage();
         
@@ -142,11 +157,26 @@ impl PowerManager {
         let threshold = self.policy.get_idle_threshold();
         let recommended = self.idle_manager.get_recommended_c_state(threshold, cpu_usage);
         
+        // tickless 모드: 다음 이벤트까지 타이머 틱 정지
+        crate::drivers::timer::nohz_idle_enter();
+        
         // 지연 시간 기반 동적 조정 시도
         // 현재는 정책 기반 선택, 향후 enter_optimal_c_state 사용 가능
+        // (인터럽트를 켜면서 잠들고, 깨어나면 인터럽트가 켜진 상태)
+        let idle_start_ns = crate::time::now_ns();
         self.idle_manager.enter_c_state(recommended);
         
-        // Wakeup 시 record_wakeup 호출 필요 (인터럽트 핸들러에서)
+        // 깨어난 후 틱 재개 및 wakeup 기록
+        crate::drivers::timer::nohz_idle_exit();
+        self.idle_manager.record_wakeup();
+        
+        // 이 CPU의 유휴 통계
+        let idle = crate::per_cpu!(idle);
+        idle.entries.fetch_add(1, core::sync::atomic::Ordering::Relaxed);
+        idle.idle_ns.fetch_add(
+            crate::time::now_ns().saturating_sub(idle_start_ns),
+            core::sync::atomic::Ordering::Relaxed,
+        );
     }
     
     /// CPU 사용률에 따른 동적 스케일링
@@ -264,7 +294,10 @@ impl PowerManager {
         // 2. 타이머 재초기화
         unsafe {
             crate::drivers::timer::init();
-            crate::interrupts::pic::set_mask(0, true); // 타이머 인터럽트 활성화
+            // LAPIC 타이머를 쓸 수 없으면 PIT 타이머 인터럽트 활성화
+            if !crate::drivers::timer::enable_tickless() {
+                crate::drivers::timer::enable_pit_irq();
+            }
         }
         
         // 3. 장치 복원
@@ -3,7 +3,7 @@
 //! 이 모듈은 CPU 전력 관리 및 ACPI 파싱을 담당합니다.
 //!
 //! ## 기능
-//! - ACPI 테이블 파싱 (RSDP, RSDT/XSDT, FADT 등)
+//! - ACPI 테이블 파싱 (RSDP, RSDT/XSDT, FADT, MADT, MCFG 등)
 //! - CPU 클럭 스케일링 (P-State 제어)
 //! - CPU 유휴 상태 관리 (C-State 제어)
 //! - 전력 정책 관리
@@ -11,6 +11,8 @@
 pub mod manager;
 pub mod acpi;
 pub mod acpi_fadt;
+pub mod acpi_madt;
+pub mod acpi_mcfg;
 pub mod user_activity;
 pub mod battery;
 pub mod scaling;
@@ -22,7 +24,6 @@ pub mod stats;
 pub mod rapl;
 pub mod temps;
 pub mod cpu_usage;
-pub mod device_hooks;
 
 pub use manager::PowerManager;
 pub use policy::{PowerPolicy, PowerMode};
@@ -34,9 +35,12 @@ use spin::Mutex;
 /// 전역 전력 관리자 인스턴스
 static POWER_MANAGER: Mutex<Option<PowerManager>> = Mutex::new(None);
 
-/// Minimal ACPI table fetch stub (returns None until a real ACPI parser is wired)
-pub unsafe fn acpi_table_fetch(_sig: &[u8]) -> Option<&'static [u8]> {
-    None
+/// ACPI 테이블 가져오기 (RSDT/XSDT 탐색)
+///
+/// # Safety
+/// 메모리 관리가 초기화된 후에 호출되어야 합니다.
+pub unsafe fn acpi_table_fetch(sig: &[u8]) -> Option<&'static [u8]> {
+    acpi::find_table(sig)
 }
 
 /// 전력 관리자 초기화
@@ -105,4 +109,6 @@ pub enum PowerError {
     NotInitialized,
     /// 지원하지 않는 기능
     Unsupported,
+    /// 디바이스 드라이버가 절전 진입을 거부함
+    DeviceSuspendFailed,
 }
@@ -128,13 +128,16 @@ static LAST_POWER_MEASUREMENT: Mutex<Option<PowerMeasurement>> = Mutex::new(None
 #[derive(Debug, Clone, Copy)]
 struct PowerMeasurement {
     energy_nj: u64,
-    timestamp_ms: u64,
+    timestamp_ns: u64,
 }
 
 /// Read current power consumption from RAPL (Watts)
 ///
 /// 이전 측정값과 비교하여 실제 전력 소비를 계산합니다.
-pub fn read_power_watts(now_ms: u64) -> Option<f32> {
+///
+/// # Arguments
+/// * `now_ns` - 현재 시각 (`time::now_ns()`, 클럭소스 해상도)
+pub fn read_power_watts(now_ns: u64) -> Option<f32> {
     if !msr_supported() { return None; }
     
     let energy_unit_nj = read_energy_unit_nanojoules()?;
@@ -147,18 +150,18 @@ pub fn read_power_watts(now_ms: u64) -> Option<f32> {
     
     if let Some(last) = *last_measurement {
         let delta_energy_nj = current_energy_nj.wrapping_sub(last.energy_nj);
-        let delta_time_ms = now_ms.saturating_sub(last.timestamp_ms);
+        let delta_time_ns = now_ns.saturating_sub(last.timestamp_ns);
         
-        if delta_time_ms > 0 && delta_time_ms < 10000 {
+        if delta_time_ns > 0 && delta_time_ns < 10_000_000_000 {
             // 전력 = 에너지 / 시간
-            // delta_energy_nj는 나노줄, delta_time_ms는 밀리초
-            // 전력 (W) = (나노줄 / 1e9) / (밀리초 / 1000) = (나노줄 * 1000) / (밀리초 * 1e9)
-            let power_watts = (delta_energy_nj as f32 * 1000.0) / (delta_time_ms as f32 * 1_000_000_000.0);
+            // delta_energy_nj는 나노줄, delta_time_ns는 나노초
+            // 전력 (W) = (나노줄 / 1e9) / (나노초 / 1e9) = 나노줄 / 나노초
+            let power_watts = delta_energy_nj as f32 / delta_time_ns as f32;
             
             // 측정값 업데이트
             *last_measurement = Some(PowerMeasurement {
                 energy_nj: current_energy_nj,
-                timestamp_ms: now_ms,
+                timestamp_ns: now_ns,
             });
             
             Some(power_watts)
@@ -166,7 +169,7 @@ pub fn read_power_watts(now_ms: u64) -> Option<f32> {
             // 시간 간격이 너무 크거나 작음 (래핑 또는 초기 측정)
             *last_measurement = Some(PowerMeasurement {
                 energy_nj: current_energy_nj,
-                timestamp_ms: now_ms,
+                timestamp_ns: now_ns,
             });
             None
         }
@@ -174,7 +177,7 @@ pub fn read_power_watts(now_ms: u64) -> Option<f32> {
         // 첫 측정
         *last_measurement = Some(PowerMeasurement {
             energy_nj: current_energy_nj,
-            timestamp_ms: now_ms,
+            timestamp_ns: now_ns,
         });
         None
     }
@@ -192,7 +195,7 @@ pub fn init_rapl_measurement() {
             let current_energy_nj = (energy_status as u64).wrapping_mul(energy_unit_nj);
             *last_measurement = Some(PowerMeasurement {
                 energy_nj: current_energy_nj,
-                timestamp_ms: crate::drivers::timer::get_milliseconds(),
+                timestamp_ns: crate::time::now_ns(),
             });
             crate::log_info!("RAPL power measurement initialized");
         } else {
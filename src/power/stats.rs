@@ -181,13 +181,18 @@ pub fn get_statistics() -> PowerStatistics {
     *STATS.lock()
 }
 
+/// Record a wakeup from idle (called by the idle loop after the CPU resumes)
+pub fn record_wakeup(now_ms: u64) {
+    STATS.lock().record_wakeup(now_ms);
+}
+
 /// Periodic tick to accumulate stats and occasionally print a report
 pub fn tick(now_ms: u64) {
     let mut s = STATS.lock();
     s.uptime_ms = now_ms;
     
     // Get current power from RAPL if available (개선된 측정)
-    let instant_power_mw = if let Some(power_watts) = crate::power::rapl::read_power_watts(now_ms) {
+    let instant_power_mw = if let Some(power_watts) = crate::power::rapl::read_power_watts(crate::time::now_ns()) {
         let power_mw = (power_watts * 1000.0) as u32; // Watts -> mW
         
         // 피크 전력 업데이트
@@ -57,6 +57,8 @@ pub struct ThermalMonitor {
     last_temperature_c: Option<u8>,
     /// Thermal runaway 카운터
     runaway_counter: u32,
+    /// 마지막으로 알린 동작 (변화가 있을 때만 이벤트 발행)
+    last_action: ThermalAction,
 }
 
 impl ThermalMonitor {
@@ -67,6 +69,7 @@ impl ThermalMonitor {
             max_temperature_c: 95,     // 95도 최대 온도
             last_temperature_c: None,
             runaway_counter: 0,
+            last_action: ThermalAction::Normal,
         }
     }
     
@@ -140,6 +143,7 @@ static THERMAL_MONITOR: Mutex<ThermalMonitor> = Mutex::new(ThermalMonitor {
     max_temperature_c: 95,
     last_temperature_c: None,
     runaway_counter: 0,
+    last_action: ThermalAction::Normal,
 });
 
 /// CPU 온도 모니터링 초기화
@@ -184,7 +188,15 @@ pub fn periodic_thermal_check() {
 
 /// Thermal 상태 확인 및 throttle hook 호출
 pub fn check_thermal_and_throttle() -> ThermalAction {
-    let action = THERMAL_MONITOR.lock().check_thermal_state();
+    let (action, changed, temperature_c) = {
+        let mut monitor = THERMAL_MONITOR.lock();
+        let action = monitor.check_thermal_state();
+        let changed = core::mem::replace(&mut monitor.last_action, action) != action;
+        (action, changed, monitor.last_temperature_c)
+    };
+    if changed {
+        crate::kernel::uevent::publish(crate::kernel::uevent::KernelEvent::Thermal { action, temperature_c });
+    }
     
     match action {
         ThermalAction::Throttle => {
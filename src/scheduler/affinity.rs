@@ -0,0 +1,253 @@
+//! CPU 친화도 (affinity)와 코어 격리
+//!
+//! 스레드마다 실행 가능한 CPU 집합(`CpuMask`)을 가지며, 스레드 배치와
+//! 다음 스레드 선택(유휴 CPU의 작업 가져오기 포함) 시 이를 따릅니다.
+//!
+//! 격리된 CPU(`isolcpus=` 부트 옵션)에는 일반 스레드가 배치되지 않습니다.
+//! 격리된 CPU에서만 실행되도록 명시적으로 고정한 스레드(예: 오디오 처리)만
+//! 그 코어를 사용하므로, 지연 시간에 민감한 작업이 방해받지 않습니다.
+
+use core::fmt;
+use core::sync::atomic::{AtomicU64, Ordering};
+
+/// 지원하는 최대 CPU 수
+pub const MAX_CPUS: usize = 64;
+
+/// CPU 집합 (비트 i = CPU i)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CpuMask(u64);
+
+impl CpuMask {
+    /// 모든 CPU
+    pub const ALL: CpuMask = CpuMask(u64::MAX);
+    /// 빈 집합
+    pub const EMPTY: CpuMask = CpuMask(0);
+
+    /// 비트마스크에서 생성
+    pub const fn from_bits(bits: u64) -> Self {
+        CpuMask(bits)
+    }
+
+    /// 단일 CPU
+    pub fn single(cpu: u8) -> Self {
+        let mut mask = Self::EMPTY;
+        mask.insert(cpu);
+        mask
+    }
+
+    /// CPU 0..count
+    pub fn first_n(count: usize) -> Self {
+        if count >= MAX_CPUS {
+            Self::ALL
+        } else {
+            CpuMask((1u64 << count) - 1)
+        }
+    }
+
+    /// 원시 비트마스크
+    pub fn bits(&self) -> u64 {
+        self.0
+    }
+
+    pub fn contains(&self, cpu: u8) -> bool {
+        (cpu as usize) < MAX_CPUS && self.0 & (1 << cpu) != 0
+    }
+
+    pub fn insert(&mut self, cpu: u8) {
+        if (cpu as usize) < MAX_CPUS {
+            self.0 |= 1 << cpu;
+        }
+    }
+
+    pub fn remove(&mut self, cpu: u8) {
+        if (cpu as usize) < MAX_CPUS {
+            self.0 &= !(1 << cpu);
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn count(&self) -> u32 {
+        self.0.count_ones()
+    }
+
+    /// 교집합
+    pub fn and(self, other: CpuMask) -> CpuMask {
+        CpuMask(self.0 & other.0)
+    }
+
+    /// 차집합
+    pub fn without(self, other: CpuMask) -> CpuMask {
+        CpuMask(self.0 & !other.0)
+    }
+
+    /// 가장 낮은 번호의 CPU
+    pub fn first(&self) -> Option<u8> {
+        if self.0 == 0 { None } else { Some(self.0.trailing_zeros() as u8) }
+    }
+
+    /// 포함된 CPU 순회
+    pub fn iter(&self) -> impl Iterator<Item = u8> {
+        let bits = self.0;
+        (0..MAX_CPUS as u8).filter(move |&cpu| bits & (1 << cpu) != 0)
+    }
+
+    /// CPU 목록 문자열 파싱 (예: "0-3,6", "2")
+    pub fn parse(s: &str) -> Result<Self, &'static str> {
+        let mut mask = Self::EMPTY;
+        for part in s.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+            let (start, end) = match part.split_once('-') {
+                Some((a, b)) => (parse_cpu(a)?, parse_cpu(b)?),
+                None => {
+                    let cpu = parse_cpu(part)?;
+                    (cpu, cpu)
+                }
+            };
+            if start > end {
+                return Err("invalid CPU range");
+            }
+            for cpu in start..=end {
+                mask.insert(cpu);
+            }
+        }
+        if mask.is_empty() {
+            return Err("empty CPU list");
+        }
+        Ok(mask)
+    }
+}
+
+fn parse_cpu(s: &str) -> Result<u8, &'static str> {
+    let cpu: u8 = s.trim().parse().map_err(|_| "invalid CPU number")?;
+    if cpu as usize >= MAX_CPUS {
+        return Err("CPU number out of range");
+    }
+    Ok(cpu)
+}
+
+/// CPU 목록 형식으로 출력 (예: "0-3,6")
+impl fmt::Display for CpuMask {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.is_empty() {
+            return write!(f, "(none)");
+        }
+        let mut first = true;
+        let mut cpu = 0usize;
+        while cpu < MAX_CPUS {
+            if !self.contains(cpu as u8) {
+                cpu += 1;
+                continue;
+            }
+            let start = cpu;
+            while cpu + 1 < MAX_CPUS && self.contains(cpu as u8 + 1) {
+                cpu += 1;
+            }
+            if !first {
+                write!(f, ",")?;
+            }
+            first = false;
+            if start == cpu {
+                write!(f, "{}", start)?;
+            } else {
+                write!(f, "{}-{}", start, cpu)?;
+            }
+            cpu += 1;
+        }
+        Ok(())
+    }
+}
+
+/// 격리된 CPU 집합
+static ISOLATED_CPUS: AtomicU64 = AtomicU64::new(0);
+
+/// 부트 옵션에서 격리 CPU 설정 읽기 (`isolcpus=<목록>`)
+pub fn init() {
+    let Some(list) = crate::config::cmdline::get("isolcpus") else {
+        return;
+    };
+    match CpuMask::parse(list).and_then(set_isolated_cpus) {
+        Ok(()) => {
+            crate::log_info!("Isolated CPUs: {}", isolated_cpus());
+        }
+        Err(e) => {
+            crate::log_warn!("Ignoring isolcpus={}: {}", list, e);
+        }
+    }
+}
+
+/// 격리된 CPU 집합
+pub fn isolated_cpus() -> CpuMask {
+    CpuMask(ISOLATED_CPUS.load(Ordering::Acquire))
+}
+
+/// 격리 CPU 설정
+///
+/// 일반 스레드를 실행할 CPU가 최소 하나는 남아 있어야 하므로 CPU 0은 격리할 수 없습니다.
+pub fn set_isolated_cpus(mask: CpuMask) -> Result<(), &'static str> {
+    if mask.contains(0) {
+        return Err("CPU 0 cannot be isolated");
+    }
+    ISOLATED_CPUS.store(mask.bits(), Ordering::Release);
+    Ok(())
+}
+
+/// 온라인 CPU 집합
+pub fn online_cpus() -> CpuMask {
+    #[cfg(feature = "smp")]
+    let count = crate::smp::cpu_count().max(1);
+    #[cfg(not(feature = "smp"))]
+    let count = 1;
+    CpuMask::first_n(count)
+}
+
+/// 현재 CPU 번호
+pub fn this_cpu() -> u8 {
+    crate::kernel::percpu::cpu_id()
+}
+
+/// 스레드의 친화도 마스크에서 실제로 사용할 CPU 집합
+///
+/// 온라인 CPU로 제한하고, 일반 CPU가 하나라도 허용되면 격리 CPU는 제외합니다.
+/// 격리 CPU에만 고정된 스레드는 그대로 격리 CPU에서 실행됩니다.
+pub fn effective(mask: CpuMask) -> CpuMask {
+    let allowed = mask.and(online_cpus());
+    let general = allowed.without(isolated_cpus());
+    if general.is_empty() { allowed } else { general }
+}
+
+/// 마스크가 비어 있지 않고 온라인 CPU만 포함하는지 검증
+pub fn validate(mask: CpuMask) -> Result<(), &'static str> {
+    if mask.is_empty() {
+        return Err("affinity mask is empty");
+    }
+    if !mask.without(online_cpus()).is_empty() {
+        return Err("affinity mask contains CPUs that are not online");
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test_case]
+    fn test_cpu_mask_parse_and_display() {
+        let mask = CpuMask::parse("0-3,6").unwrap();
+        assert_eq!(mask.bits(), 0b100_1111);
+        assert_eq!(alloc::format!("{}", mask), "0-3,6");
+        assert_eq!(CpuMask::parse("2").unwrap(), CpuMask::single(2));
+        assert!(CpuMask::parse("3-1").is_err());
+        assert!(CpuMask::parse("64").is_err());
+    }
+
+    #[test_case]
+    fn test_validate_rejects_empty_and_offline() {
+        assert!(validate(CpuMask::EMPTY).is_err());
+        assert!(validate(CpuMask::single(0)).is_ok());
+        assert!(validate(online_cpus()).is_ok());
+        assert!(validate(CpuMask::single(MAX_CPUS as u8 - 1)).is_err());
+        assert!(validate(CpuMask::ALL).is_err());
+    }
+}
@@ -5,6 +5,7 @@
 use alloc::vec::Vec;
 use alloc::sync::Arc;
 use spin::Mutex;
+use crate::scheduler::affinity::{self, CpuMask};
 use crate::scheduler::thread::Thread;
 
 /// 로드 밸런싱 전략
@@ -91,33 +92,56 @@ impl LoadBalancer {
     /// # Returns
     /// 선택된 CPU ID
     pub fn select_cpu_for_thread(&mut self) -> u8 {
-        match self.strategy {
-            BalancingStrategy::RoundRobin => self.select_cpu_round_robin(),
-            BalancingStrategy::LeastLoaded => self.select_cpu_least_loaded(),
-            BalancingStrategy::WorkStealing => self.select_cpu_least_loaded(), // 동일하게 처리
-        }
+        self.select_cpu_in(affinity::effective(CpuMask::ALL))
+    }
+
+    /// 허용된 CPU 중에서 스레드를 할당할 CPU 선택
+    ///
+    /// # Arguments
+    /// * `allowed` - 스레드가 실행될 수 있는 CPU 집합 (`affinity::effective` 적용 후)
+    pub fn select_cpu_in(&mut self, allowed: CpuMask) -> u8 {
+        let selected = match self.strategy {
+            BalancingStrategy::RoundRobin => self.select_cpu_round_robin(allowed),
+            BalancingStrategy::LeastLoaded => self.select_cpu_least_loaded(allowed),
+            BalancingStrategy::WorkStealing => self.select_cpu_least_loaded(allowed), // 동일하게 처리
+        };
+        selected.or_else(|| allowed.first()).unwrap_or(0)
     }
     
     /// Round-Robin 방식으로 CPU 선택
-    fn select_cpu_round_robin(&mut self) -> u8 {
-        let cpu_id = self.next_cpu as u8;
-        self.next_cpu = (self.next_cpu + 1) % self.cpu_loads.len();
-        cpu_id
+    fn select_cpu_round_robin(&mut self, allowed: CpuMask) -> Option<u8> {
+        let count = self.cpu_loads.len();
+        for _ in 0..count {
+            let cpu_id = self.next_cpu as u8;
+            self.next_cpu = (self.next_cpu + 1) % count;
+            if allowed.contains(cpu_id) {
+                return Some(cpu_id);
+            }
+        }
+        None
     }
     
     /// 가장 부하가 적은 CPU 선택
-    fn select_cpu_least_loaded(&self) -> u8 {
-        let mut min_load = usize::MAX;
-        let mut selected_cpu = 0u8;
-        
-        for load in &self.cpu_loads {
-            if load.thread_count < min_load {
-                min_load = load.thread_count;
-                selected_cpu = load.cpu_id;
-            }
-        }
-        
-        selected_cpu
+    fn select_cpu_least_loaded(&self, allowed: CpuMask) -> Option<u8> {
+        self.cpu_loads
+            .iter()
+            .filter(|load| allowed.contains(load.cpu_id))
+            .min_by_key(|load| load.thread_count)
+            .map(|load| load.cpu_id)
+    }
+
+    /// 유휴 CPU가 작업을 가져올 CPU 선택 (가장 부하가 큰 CPU)
+    ///
+    /// 격리된 CPU에서는 가져오지 않으며, 실제로 가져갈 스레드는
+    /// 스케줄러가 친화도를 확인해 고릅니다.
+    pub fn steal_source(&self, idle_cpu: u8) -> Option<u8> {
+        let isolated = affinity::isolated_cpus();
+        self.cpu_loads
+            .iter()
+            .filter(|load| load.cpu_id != idle_cpu && !isolated.contains(load.cpu_id))
+            .filter(|load| load.thread_count > 1)
+            .max_by_key(|load| load.thread_count)
+            .map(|load| load.cpu_id)
     }
     
     /// CPU에 스레드 추가
@@ -248,6 +272,23 @@ pub fn select_cpu_for_thread() -> u8 {
     }
 }
 
+/// 친화도 마스크를 고려해 스레드를 할당할 CPU 선택
+pub fn select_cpu_for_mask(mask: CpuMask) -> u8 {
+    let allowed = affinity::effective(mask);
+    let mut balancer = LOAD_BALANCER.lock();
+    if let Some(ref mut lb) = *balancer {
+        lb.select_cpu_in(allowed)
+    } else {
+        allowed.first().unwrap_or(0)
+    }
+}
+
+/// 유휴 CPU가 작업을 가져올 CPU 선택
+pub fn steal_source(idle_cpu: u8) -> Option<u8> {
+    let balancer = LOAD_BALANCER.lock();
+    balancer.as_ref().and_then(|lb| lb.steal_source(idle_cpu))
+}
+
 /// CPU에 스레드 추가 통지
 pub fn notify_thread_added(cpu_id: u8) {
     let mut balancer = LOAD_BALANCER.lock();
@@ -2,55 +2,151 @@
 //!
 //! 이 모듈은 프로세스와 스레드의 스케줄링을 담당합니다.
 
+pub mod affinity;
 pub mod thread;
 pub mod round_robin;
 pub mod context_switch;
 pub mod load_balancer;
 
 use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::sync::atomic::{AtomicU32, AtomicU64, Ordering};
 use spin::Mutex;
+use x86_64::instructions::interrupts;
+use affinity::CpuMask;
 use thread::Thread;
 use round_robin::RoundRobinScheduler;
+use crate::kernel::percpu::{self, PerCpu};
 
-/// 전역 스케줄러 인스턴스
-static SCHEDULER: Mutex<Option<RoundRobinScheduler>> = Mutex::new(None);
+/// 기본 시간 할당량 (타이머 틱 수, `init`에서 설정)
+static TIME_QUANTUM: AtomicU32 = AtomicU32::new(10);
+
+/// 다음 스레드 ID (모든 CPU에서 공유)
+static NEXT_THREAD_ID: AtomicU64 = AtomicU64::new(1);
 
 /// 스케줄러 초기화
 ///
+/// 시간 할당량을 설정하고 현재 CPU(BSP)의 실행 큐를 만듭니다.
+/// AP는 각자 `init_cpu`를 호출합니다.
+///
 /// # Arguments
 /// * `time_quantum` - 각 스레드의 시간 할당량 (타이머 틱 수, 기본값: 10)
 pub fn init(time_quantum: u32) {
-    let mut scheduler = SCHEDULER.lock();
-    *scheduler = Some(RoundRobinScheduler::new(time_quantum));
+    TIME_QUANTUM.store(time_quantum, Ordering::Relaxed);
+    init_cpu();
     crate::log_info!("Scheduler initialized with time quantum: {} ticks", time_quantum);
 }
 
+/// 현재 CPU의 실행 큐 생성
+pub fn init_cpu() {
+    let cpu = percpu::this_cpu();
+    let quantum = TIME_QUANTUM.load(Ordering::Relaxed);
+    interrupts::without_interrupts(|| {
+        *cpu.run_queue.lock() = Some(RoundRobinScheduler::new(cpu.cpu_id, quantum));
+    });
+}
+
+/// 지정한 CPU의 실행 큐에 대해 `f` 실행 (인터럽트 비활성화 상태)
+fn with_run_queue<R>(cpu: &PerCpu, f: impl FnOnce(&mut RoundRobinScheduler) -> R) -> Option<R> {
+    interrupts::without_interrupts(|| cpu.run_queue.lock().as_mut().map(f))
+}
+
+/// 현재 CPU의 실행 큐에 대해 `f` 실행
+fn with_local_run_queue<R>(f: impl FnOnce(&mut RoundRobinScheduler) -> R) -> Option<R> {
+    with_run_queue(percpu::this_cpu(), f)
+}
+
 /// 스레드를 스케줄러에 추가
 ///
+/// 친화도에 맞는 CPU를 골라 그 CPU의 실행 큐에 넣습니다. 선택한 CPU의
+/// 실행 큐가 아직 없으면 현재 CPU에 넣습니다.
+///
 /// # Arguments
 /// * `thread` - 추가할 스레드
 pub fn add_thread(thread: Arc<Mutex<Thread>>) {
-    let mut scheduler = SCHEDULER.lock();
-    if let Some(ref mut sched) = *scheduler {
-        sched.add_thread(thread);
+    // 로드 밸런서 락은 타이머 틱에서도 잡으므로 인터럽트를 끈 채로 배치
+    interrupts::without_interrupts(|| {
+        let mask = thread.lock().affinity;
+        let target = percpu::cpu(load_balancer::select_cpu_for_mask(mask))
+            .filter(|cpu| cpu.run_queue.lock().is_some())
+            .unwrap_or_else(percpu::this_cpu);
+        with_run_queue(target, |rq| rq.add_thread(thread));
+        kick(target);
+    });
+}
+
+/// 다른 CPU에 스레드를 넣었으면 재스케줄링 IPI로 깨움
+///
+/// 유휴 AP는 틱 없이 잠들어 있으므로 깨워야 새 스레드를 실행합니다.
+fn kick(target: &PerCpu) {
+    if !core::ptr::eq(target, percpu::this_cpu()) {
+        crate::smp::ipi::send_reschedule_ipi(target.apic_id.load(Ordering::Relaxed) as u8);
     }
 }
 
 /// 타이머 틱 처리
 ///
-/// 시간 할당량이 만료되면 다음 스레드로 전환합니다.
+/// 시간 할당량이 만료되면 다음 스레드로 전환합니다. 현재 CPU의 준비 큐가
+/// 비어 있으면 가장 바쁜 CPU에서 스레드 하나를 가져옵니다.
 ///
 /// # Returns
 /// 컨텍스트 스위칭이 필요한 경우 `true`, 그렇지 않으면 `false`
 pub fn tick() -> bool {
     // Watchdog heartbeat 업데이트
     crate::kernel::watchdog::heartbeat();
-    
-    let mut scheduler = SCHEDULER.lock();
-    if let Some(ref mut sched) = *scheduler {
-        sched.tick()
-    } else {
-        false
+
+    let local = percpu::this_cpu();
+    if with_run_queue(local, |rq| rq.ready_count()) == Some(0) {
+        steal_into(local);
+    }
+
+    with_run_queue(local, |rq| {
+        let switched = rq.tick();
+        local.current_tid.store(rq.current_thread_id(), Ordering::Relaxed);
+        switched
+    })
+    .unwrap_or(false)
+}
+
+/// 유휴 루프에서 다음 스레드 선택
+///
+/// 현재 CPU에 실행 중인 스레드가 없으면 준비 스레드(없으면 다른 CPU에서 가져온
+/// 스레드)를 실행 상태로 만듭니다. 이후 전환은 타이머 틱(`tick`)이 처리합니다.
+///
+/// # Returns
+/// 현재 CPU에 실행 중이거나 준비된 스레드가 있으면 `true` (틱 필요)
+pub fn schedule() -> bool {
+    let local = percpu::this_cpu();
+    if with_run_queue(local, |rq| !rq.has_current_thread() && rq.ready_count() == 0) == Some(true) {
+        steal_into(local);
+    }
+
+    with_run_queue(local, |rq| {
+        if !rq.has_current_thread() {
+            rq.switch_to_next();
+        }
+        local.current_tid.store(rq.current_thread_id(), Ordering::Relaxed);
+        rq.has_current_thread() || rq.ready_count() > 0
+    })
+    .unwrap_or(false)
+}
+
+/// 현재 CPU에 실행 중이거나 준비된 스레드가 있는지 여부
+pub fn has_local_work() -> bool {
+    with_local_run_queue(|rq| rq.has_current_thread() || rq.ready_count() > 0).unwrap_or(false)
+}
+
+/// 다른 CPU의 준비 스레드를 `local`로 가져오기
+fn steal_into(local: &'static PerCpu) {
+    let Some(source) = load_balancer::steal_source(local.cpu_id).and_then(percpu::cpu) else {
+        return;
+    };
+    if core::ptr::eq(source, local) {
+        return;
+    }
+    // 두 실행 큐 락을 동시에 잡지 않도록 꺼낸 뒤 넣음
+    if let Some(thread) = with_run_queue(source, |rq| rq.take_ready_for_cpu(local.cpu_id)).flatten() {
+        with_run_queue(local, |rq| rq.push_ready(thread));
     }
 }
 
@@ -59,21 +155,20 @@ pub fn tick() -> bool {
 /// # Arguments
 /// * `thread_id` - 블로킹할 스레드 ID
 pub fn block_thread(thread_id: u64) {
-    let mut scheduler = SCHEDULER.lock();
-    if let Some(ref mut sched) = *scheduler {
-        sched.block_thread(thread_id);
-    }
+    with_local_run_queue(|rq| rq.block_thread(thread_id));
 }
 
 /// 스레드 언블로킹
 ///
+/// 스레드가 마지막으로 배치된 CPU의 실행 큐로 돌려보냅니다.
+///
 /// # Arguments
 /// * `thread` - 언블로킹할 스레드
 pub fn unblock_thread(thread: Arc<Mutex<Thread>>) {
-    let mut scheduler = SCHEDULER.lock();
-    if let Some(ref mut sched) = *scheduler {
-        sched.unblock_thread(thread);
-    }
+    let cpu = thread.lock().cpu;
+    let target = percpu::cpu(cpu).unwrap_or_else(percpu::this_cpu);
+    with_run_queue(target, |rq| rq.unblock_thread(thread));
+    kick(target);
 }
 
 /// 스레드 종료
@@ -81,39 +176,119 @@ pub fn unblock_thread(thread: Arc<Mutex<Thread>>) {
 /// # Arguments
 /// * `thread_id` - 종료할 스레드 ID
 pub fn terminate_thread(thread_id: u64) {
-    let mut scheduler = SCHEDULER.lock();
-    if let Some(ref mut sched) = *scheduler {
-        sched.terminate_thread(thread_id);
+    for cpu in percpu::iter() {
+        if with_run_queue(cpu, |rq| rq.terminate_thread(thread_id)) == Some(true) {
+            return;
+        }
     }
 }
 
 /// 다음 스레드 ID 할당
 pub fn allocate_thread_id() -> u64 {
-    let mut scheduler = SCHEDULER.lock();
-    if let Some(ref mut sched) = *scheduler {
-        sched.allocate_thread_id()
-    } else {
-        0
-    }
+    NEXT_THREAD_ID.fetch_add(1, Ordering::Relaxed)
 }
 
-/// 현재 실행 중인 스레드 가져오기
+/// 현재 CPU에서 실행 중인 스레드 가져오기
 pub fn current_thread() -> Option<Arc<Mutex<Thread>>> {
-    let scheduler = SCHEDULER.lock();
-    if let Some(ref sched) = *scheduler {
-        sched.current_thread()
-    } else {
-        None
-    }
+    with_local_run_queue(|rq| rq.current_thread()).flatten()
 }
 
-/// 준비 큐의 스레드 수 반환
+/// 모든 CPU 준비 큐의 스레드 수 합계
 pub fn ready_count() -> usize {
-    let scheduler = SCHEDULER.lock();
-    if let Some(ref sched) = *scheduler {
-        sched.ready_count()
-    } else {
-        0
+    percpu::iter()
+        .filter_map(|cpu| with_run_queue(cpu, |rq| rq.ready_count()))
+        .sum()
+}
+
+/// 모든 CPU의 실행 중·준비 상태 스레드 스냅샷
+pub fn threads() -> Vec<Arc<Mutex<Thread>>> {
+    let mut threads = Vec::new();
+    for cpu in percpu::iter() {
+        with_run_queue(cpu, |rq| threads.extend(rq.threads().cloned()));
     }
+    threads
+}
+
+/// ID로 스레드 찾기 (모든 CPU 검색)
+fn find_thread(thread_id: u64) -> Option<Arc<Mutex<Thread>>> {
+    percpu::iter().find_map(|cpu| with_run_queue(cpu, |rq| rq.find_thread(thread_id)).flatten())
 }
 
+/// 스레드 CPU 친화도 설정
+///
+/// 준비 상태 스레드가 현재 배치된 CPU에서 더 이상 실행될 수 없으면
+/// 허용된 CPU의 실행 큐로 옮깁니다. 실행 중인 스레드는 다음 전환 때
+/// 준비 큐로 돌아간 뒤 허용된 CPU가 가져갑니다.
+///
+/// # Arguments
+/// * `thread_id` - 대상 스레드 ID
+/// * `mask` - 실행을 허용할 CPU 집합
+pub fn set_thread_affinity(thread_id: u64, mask: CpuMask) -> Result<(), &'static str> {
+    affinity::validate(mask)?;
+    let thread = find_thread(thread_id).ok_or("thread not found")?;
+    let (old_cpu, allowed) = {
+        let mut t = thread.lock();
+        t.affinity = mask;
+        (t.cpu, t.can_run_on(t.cpu))
+    };
+
+    if !allowed {
+        let moved = percpu::cpu(old_cpu)
+            .and_then(|cpu| with_run_queue(cpu, |rq| rq.remove_ready(thread_id)))
+            .flatten();
+        if let Some(thread) = moved {
+            load_balancer::notify_thread_removed(old_cpu);
+            add_thread(thread);
+        }
+    }
+
+    crate::log_info!("Thread {} affinity set to CPU {}", thread_id, mask);
+    Ok(())
+}
+
+/// 스레드 CPU 친화도 조회
+pub fn thread_affinity(thread_id: u64) -> Option<CpuMask> {
+    let thread = find_thread(thread_id)?;
+    let affinity = thread.lock().affinity;
+    Some(affinity)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test_case]
+    fn test_pinned_thread_runs_on_its_cpu() {
+        // AP가 있으면 마지막 AP, 없으면 BSP에 고정
+        let target = affinity::online_cpus().iter().last().unwrap_or(0);
+        if percpu::this_cpu().run_queue.lock().is_none() {
+            init_cpu();
+        }
+        let cpu = percpu::cpu(target).expect("online CPU has a per-CPU block");
+
+        let stack = alloc::vec![0u64; 512].leak();
+        let id = allocate_thread_id();
+        let mut thread = Thread::new(id, "pinned", 0, stack.as_ptr_range().end as u64);
+        thread.affinity = CpuMask::single(target);
+        add_thread(Arc::new(Mutex::new(thread)));
+
+        // AP는 재스케줄링 IPI로 깨어나 유휴 루프에서 스레드를 고름
+        let mut running = false;
+        for _ in 0..50_000_000u64 {
+            if cpu.current_tid.load(Ordering::Relaxed) == id {
+                running = true;
+                break;
+            }
+            if target == affinity::this_cpu() {
+                schedule();
+                tick();
+            } else {
+                core::hint::spin_loop();
+            }
+        }
+        let placed = find_thread(id).is_some_and(|t| t.lock().cpu == target);
+        terminate_thread(id);
+        assert!(running);
+        assert!(placed);
+    }
+}
@@ -6,19 +6,21 @@
 use alloc::collections::VecDeque;
 use alloc::sync::Arc;
 use spin::Mutex;
+use crate::scheduler::load_balancer;
 use crate::scheduler::thread::{Thread, ThreadState, ThreadPriority};
 
 /// Round-Robin 스케줄러 (우선순위 지원)
 ///
 /// 준비 큐(ready queue)를 사용하여 스레드를 순환적으로 스케줄링합니다.
 /// 우선순위가 높은 스레드가 먼저 실행됩니다.
+/// CPU마다 하나씩 per-CPU 데이터(`PerCpu::run_queue`)에 들어 있습니다.
 pub struct RoundRobinScheduler {
     /// 준비 큐 (우선순위별로 분리)
     ready_queues: [VecDeque<Arc<Mutex<Thread>>>; 4], // Priority 0-3
     /// 현재 실행 중인 스레드
     current_thread: Option<Arc<Mutex<Thread>>>,
-    /// 다음 스레드 ID
-    next_thread_id: u64,
+    /// 이 실행 큐를 소유한 CPU
+    cpu: u8,
     /// 시간 할당량 (타이머 틱 수, 우선순위별)
     time_quantum: [u32; 4], // Priority별 시간 할당량
     /// 현재 실행 시간 (타이머 틱 수)
@@ -29,8 +31,9 @@ impl RoundRobinScheduler {
     /// 새로운 Round-Robin 스케줄러 생성
     ///
     /// # Arguments
+    /// * `cpu` - 실행 큐를 소유한 CPU 번호
     /// * `time_quantum` - 기본 시간 할당량 (타이머 틱 수)
-    pub fn new(time_quantum: u32) -> Self {
+    pub fn new(cpu: u8, time_quantum: u32) -> Self {
         // 우선순위별 시간 할당량 설정
         // 높은 우선순위일수록 더 많은 시간 할당
         let time_quantums = [
@@ -48,7 +51,7 @@ impl RoundRobinScheduler {
                 VecDeque::new(),  // Realtime
             ],
             current_thread: None,
-            next_thread_id: 1,
+            cpu,
             time_quantum: time_quantums,
             current_time: 0,
         }
@@ -56,10 +59,12 @@ impl RoundRobinScheduler {
 
     /// 스레드를 스케줄러에 추가
     ///
+    /// 스레드는 이미 이 CPU에 배치(`Thread::cpu`)되어 있어야 합니다.
+    ///
     /// # Arguments
     /// * `thread` - 추가할 스레드
     pub fn add_thread(&mut self, thread: Arc<Mutex<Thread>>) {
-        let priority = {
+        {
             let mut t = thread.lock();
             t.set_ready();
             
@@ -72,10 +77,16 @@ impl RoundRobinScheduler {
                 t.allocated_frames_len(),
             );
             
-            t.priority
-        };
+            t.cpu = self.cpu;
+            load_balancer::notify_thread_added(t.cpu);
+        }
         
-        // 우선순위별 큐에 추가
+        self.push_ready(thread);
+    }
+
+    /// 준비 상태 스레드를 우선순위별 큐에 추가 (다른 CPU에서 가져온 스레드 포함)
+    pub fn push_ready(&mut self, thread: Arc<Mutex<Thread>>) {
+        let priority = thread.lock().priority;
         let priority_index = priority.to_u8() as usize;
         if priority_index < 4 {
             self.ready_queues[priority_index].push_back(thread);
@@ -183,18 +194,46 @@ impl RoundRobinScheduler {
     
     /// 다음 스레드 선택 (우선순위 기반)
     fn select_next_thread(&mut self) -> bool {
-        // 우선순위가 높은 큐부터 확인
+        if let Some(next) = self.take_ready_for_cpu(self.cpu) {
+            next.lock().set_running();
+            self.current_thread = Some(next);
+            // 컨텍스트 스위칭 메트릭 기록
+            crate::monitoring::record_context_switch();
+            return true;
+        }
+        
+        false
+    }
+
+    /// `cpu`에서 실행할 수 있는 가장 높은 우선순위의 준비 스레드를 큐에서 꺼냄
+    ///
+    /// 같은 우선순위에서는 `cpu`에 배치된 스레드를 먼저 고르고, 없으면 친화도가
+    /// 허용하는 다른 CPU의 스레드를 가져옵니다 (work stealing).
+    pub fn take_ready_for_cpu(&mut self, cpu: u8) -> Option<Arc<Mutex<Thread>>> {
         for priority_idx in (0..4).rev() {
-            if let Some(next) = self.ready_queues[priority_idx].pop_front() {
-                next.lock().set_running();
-                self.current_thread = Some(next);
-                // 컨텍스트 스위칭 메트릭 기록
-                crate::monitoring::record_context_switch();
-                return true;
+            let queue = &mut self.ready_queues[priority_idx];
+            let pos = queue
+                .iter()
+                .position(|t| {
+                    let t = t.lock();
+                    t.cpu == cpu && t.can_run_on(cpu)
+                })
+                .or_else(|| queue.iter().position(|t| t.lock().can_run_on(cpu)));
+            
+            if let Some(thread) = pos.and_then(|pos| queue.remove(pos)) {
+                {
+                    let mut t = thread.lock();
+                    if t.cpu != cpu {
+                        load_balancer::notify_thread_removed(t.cpu);
+                        load_balancer::notify_thread_added(cpu);
+                        t.cpu = cpu;
+                    }
+                }
+                return Some(thread);
             }
         }
         
-        false
+        None
     }
 
     /// 스레드 블로킹
@@ -247,18 +286,22 @@ impl RoundRobinScheduler {
     ///
     /// # Arguments
     /// * `thread_id` - 종료할 스레드 ID
-    pub fn terminate_thread(&mut self, thread_id: u64) {
+    ///
+    /// # Returns
+    /// 이 실행 큐에서 스레드를 찾았으면 `true`
+    pub fn terminate_thread(&mut self, thread_id: u64) -> bool {
         // 현재 실행 중인 스레드 확인
         if let Some(current) = &self.current_thread {
             let mut thread = current.lock();
             if thread.id == thread_id {
                 // 리소스 정리
+                load_balancer::notify_thread_removed(thread.cpu);
                 thread.cleanup();
                 drop(thread);
                 self.current_thread = None;
                 self.current_time = 0;
                 self.switch_to_next();
-                return;
+                return true;
             }
         }
         
@@ -268,6 +311,7 @@ impl RoundRobinScheduler {
             queue.retain(|t| {
                 let mut thread = t.lock();
                 if thread.id == thread_id {
+                    load_balancer::notify_thread_removed(thread.cpu);
                     thread.cleanup();
                     found = true;
                     false // 제거
@@ -283,13 +327,22 @@ impl RoundRobinScheduler {
         if found {
             crate::log_info!("Thread {} terminated and removed from ready queue", thread_id);
         }
+        found
     }
 
-    /// 다음 스레드 ID 생성
-    pub fn allocate_thread_id(&mut self) -> u64 {
-        let id = self.next_thread_id;
-        self.next_thread_id += 1;
-        id
+    /// 준비 큐에서 스레드를 꺼냄 (다른 CPU로 옮길 때 사용)
+    pub fn remove_ready(&mut self, thread_id: u64) -> Option<Arc<Mutex<Thread>>> {
+        for queue in &mut self.ready_queues {
+            if let Some(pos) = queue.iter().position(|t| t.lock().id == thread_id) {
+                return queue.remove(pos);
+            }
+        }
+        None
+    }
+
+    /// 현재 실행 중인 스레드 ID (없으면 0)
+    pub fn current_thread_id(&self) -> u64 {
+        self.current_thread.as_ref().map_or(0, |t| t.lock().id)
     }
 
     /// 준비 큐의 스레드 수 반환
@@ -326,6 +379,20 @@ impl RoundRobinScheduler {
         false
     }
 
+    /// ID로 스레드 찾기 (실행 중이거나 준비 큐에 있는 스레드)
+    pub fn find_thread(&self, thread_id: u64) -> Option<Arc<Mutex<Thread>>> {
+        self.current_thread
+            .iter()
+            .chain(self.ready_queues.iter().flatten())
+            .find(|t| t.lock().id == thread_id)
+            .cloned()
+    }
+
+    /// 실행 중이거나 준비 큐에 있는 모든 스레드
+    pub fn threads(&self) -> impl Iterator<Item = &Arc<Mutex<Thread>>> {
+        self.current_thread.iter().chain(self.ready_queues.iter().flatten())
+    }
+
     /// 현재 실행 중인 스레드가 있는지 확인
     pub fn has_current_thread(&self) -> bool {
         self.current_thread.is_some()
@@ -3,6 +3,8 @@
 //! 이 모듈은 스레드의 상태와 CPU 컨텍스트를 관리합니다.
 use x86_64::structures::paging::PageSize;
 
+use crate::scheduler::affinity::{self, CpuMask};
+
 /// 스레드 상태
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ThreadState {
@@ -160,6 +162,10 @@ pub struct Thread {
     dynamic_stack: bool,
     /// 스택 카나리 값 (스택 오버플로우 보호)
     stack_canary: Option<crate::memory::stack_canary::StackCanary>,
+    /// 실행 가능한 CPU 집합
+    pub affinity: CpuMask,
+    /// 배치된 CPU
+    pub cpu: u8,
 }
 
 impl Thread {
@@ -204,6 +210,8 @@ impl Thread {
             allocated_frames: alloc::vec::Vec::new(),
             dynamic_stack: false, // 정적 스택
             stack_canary: canary,
+            affinity: CpuMask::ALL,
+            cpu: 0,
         }
     }
     
@@ -240,6 +248,8 @@ impl Thread {
             allocated_frames: alloc::vec::Vec::new(),
             dynamic_stack: false, // 기본값: 정적 스택
             stack_canary: canary,
+            affinity: CpuMask::ALL,
+            cpu: 0,
         }
     }
     
@@ -305,6 +315,8 @@ impl Thread {
             allocated_frames,
             dynamic_stack: true,
             stack_canary: canary,
+            affinity: CpuMask::ALL,
+            cpu: 0,
         })
     }
     
@@ -313,6 +325,11 @@ impl Thread {
         self.allocated_frames.push(frame);
     }
 
+    /// 이 CPU에서 실행할 수 있는지 확인 (격리 CPU 규칙 포함)
+    pub fn can_run_on(&self, cpu: u8) -> bool {
+        affinity::effective(self.affinity).contains(cpu)
+    }
+
     /// 스레드 상태를 Ready로 변경
     pub fn set_ready(&mut self) {
         self.state = ThreadState::Ready;
@@ -17,12 +17,19 @@ pub enum Command {
     Clear,
     Echo,
     Uptime,
+    Date,     // 날짜/시간 표시 및 설정
     Exit,
     Disk,     // 디스크 정보 표시
     Read,     // 섹터 읽기 (테스트용)
     Write,    // 섹터 쓰기 (테스트용)
     Power,    // 전력 관련 명령
     Fw,       // 방화벽 설정 명령
+    Taskset,  // 스레드 CPU 친화도
+    Events,   // 커널 이벤트 이력
+    Mkfs,     // 디스크를 Sjfs로 포맷
+    Mount,    // 파일시스템 마운트/목록
+    Umount,   // 파일시스템 마운트 해제
+    Df,       // 파일시스템 용량
 }
 
 impl Command {
@@ -33,12 +40,19 @@ impl Command {
             "clear" | "cls" => Some(Command::Clear),
             "echo" => Some(Command::Echo),
             "uptime" => Some(Command::Uptime),
+            "date" => Some(Command::Date),
             "exit" | "quit" => Some(Command::Exit),
             "disk" => Some(Command::Disk),
             "read" => Some(Command::Read),
             "write" => Some(Command::Write),
             "power" => Some(Command::Power),
             "fw" => Some(Command::Fw),
+            "taskset" => Some(Command::Taskset),
+            "events" => Some(Command::Events),
+            "mkfs.sjfs" => Some(Command::Mkfs),
+            "mount" => Some(Command::Mount),
+            "umount" => Some(Command::Umount),
+            "df" => Some(Command::Df),
             _ => None,
         }
     }
@@ -50,12 +64,19 @@ impl Command {
             Command::Clear => self.cmd_clear(),
             Command::Echo => self.cmd_echo(args),
             Command::Uptime => self.cmd_uptime(),
+            Command::Date => self.cmd_date(args),
             Command::Exit => self.cmd_exit(),
             Command::Disk => self.cmd_disk(),
             Command::Read => self.cmd_read(args),
             Command::Write => self.cmd_write(args),
             Command::Power => self.cmd_power(args),
             Command::Fw => self.cmd_fw(args),
+            Command::Taskset => self.cmd_taskset(args),
+            Command::Events => self.cmd_events(args),
+            Command::Mkfs => self.cmd_mkfs(args),
+            Command::Mount => self.cmd_mount(args),
+            Command::Umount => self.cmd_umount(args),
+            Command::Df => self.cmd_df(),
         }
     }
 
@@ -66,6 +87,9 @@ impl Command {
         vga_println!("  clear, cls        - Clear the screen");
         vga_println!("  echo <text>       - Print text to the screen");
         vga_println!("  uptime            - Show system uptime");
+        vga_println!("  date [-u]         - Show local (or UTC) date and time");
+        vga_println!("  date set <YYYY-MM-DD> <HH:MM:SS> - Set local time (also RTC)");
+        vga_println!("  date tz <+HH:MM>  - Set timezone offset (default +09:00)");
         vga_println!("  disk              - Show disk information");
         vga_println!("  read <sector>     - Read a sector from disk (test)");
         vga_println!("  write <sector>    - Write test data to sector (test)");
@@ -77,6 +101,18 @@ impl Command {
         vga_println!("  fw allow tcp <p>  - Allow ingress TCP port p");
         vga_println!("  fw allow udp <p>  - Allow ingress UDP port p");
         vga_println!("  fw allow icmp     - Allow ICMP ingress");
+        vga_println!("  taskset <tid> [cpus] - Show or set thread CPU affinity (e.g. 0-1,3)");
+        vga_println!("  taskset isolated  - Show CPUs isolated from general threads");
+        vga_println!("  events [category] - Show recent kernel events (device|power|battery|thermal|network)");
+        vga_println!("  mkfs.sjfs [-L label] [-f] - Format the disk as Sjfs (-f overwrites an existing filesystem)");
+        vga_println!("  mount             - List mounted filesystems");
+        vga_println!("  mount [-t type] [-o ro] <device> <dir> - Mount a filesystem (type: auto|sjfs|ext2|fat32)");
+        vga_println!("  mount -t tmpfs [-o size=N,nr_inodes=N] tmpfs <dir> - Mount a RAM-backed filesystem");
+        vga_println!("  mount -t devfs devfs <dir> - Mount device nodes (disks: /dev/ata0, /dev/ata0p1, ...)");
+        vga_println!("  mount -t procfs proc <dir> - Mount kernel state files (meminfo, <tid>/status, tunables)");
+        vga_println!("  mount --bind [-o ro] <src> <dir> - Make a directory visible at another path");
+        vga_println!("  umount <dir>      - Unmount the filesystem at dir");
+        vga_println!("  df                - Show filesystem disk space usage");
         vga_println!("  exit, quit        - Exit the shell (reboot simulation)");
         Ok(())
     }
@@ -159,6 +195,283 @@ impl Command {
         Ok(())
     }
 
+    /// date 명령어: 날짜/시간 표시 및 설정
+    fn cmd_date(&self, args: &[&str]) -> Result<(), String> {
+        use crate::time::wallclock::{self, DateTime};
+        const WEEKDAYS: [&str; 7] = ["Sun", "Mon", "Tue", "Wed", "Thu", "Fri", "Sat"];
+        
+        match args.first().copied() {
+            None => {
+                let now = wallclock::local_now();
+                vga_println!(
+                    "{} {} (UTC{})",
+                    WEEKDAYS[now.weekday() as usize], now,
+                    wallclock::format_offset(wallclock::tz_offset_minutes())
+                );
+                Ok(())
+            }
+            Some("-u") => {
+                let now = wallclock::utc_now();
+                vga_println!("{} {} UTC", WEEKDAYS[now.weekday() as usize], now);
+                Ok(())
+            }
+            Some("set") => {
+                if args.len() < 3 {
+                    return Err(String::from("Usage: date set <YYYY-MM-DD> <HH:MM:SS>"));
+                }
+                let date = parse_fields(args[1], '-')
+                    .ok_or_else(|| String::from("Invalid date (expected YYYY-MM-DD)"))?;
+                let time = parse_fields(args[2], ':')
+                    .ok_or_else(|| String::from("Invalid time (expected HH:MM:SS)"))?;
+                // 캐스팅 전에 범위를 확인 (예: 월 257이 1로 잘리지 않도록)
+                if !(1970..=u16::MAX as u32).contains(&date[0])
+                    || !(1..=12).contains(&date[1])
+                    || !(1..=31).contains(&date[2])
+                    || time[0] >= 24
+                    || time[1] >= 60
+                    || time[2] >= 60
+                {
+                    return Err(String::from("Invalid date/time"));
+                }
+                let local = DateTime {
+                    year: date[0] as u16,
+                    month: date[1] as u8,
+                    day: date[2] as u8,
+                    hour: time[0] as u8,
+                    minute: time[1] as u8,
+                    second: time[2] as u8,
+                };
+                if !local.is_valid() {
+                    return Err(String::from("Invalid date/time"));
+                }
+                wallclock::set_time(wallclock::local_to_unix(local.to_unix()), true);
+                vga_println!("Date set: {}", wallclock::local_now());
+                Ok(())
+            }
+            Some("tz") => {
+                let arg = args.get(1).ok_or_else(|| String::from("Usage: date tz <+HH:MM>"))?;
+                let (sign, rest) = match arg.as_bytes().first() {
+                    Some(b'-') => (-1, &arg[1..]),
+                    Some(b'+') => (1, &arg[1..]),
+                    _ => (1, *arg),
+                };
+                let mut parts = rest.splitn(2, ':');
+                let hours: i32 = parts.next().unwrap_or("").parse()
+                    .map_err(|_| String::from("Invalid timezone offset"))?;
+                let minutes: i32 = parts.next().unwrap_or("0").parse()
+                    .map_err(|_| String::from("Invalid timezone offset"))?;
+                if !(0..60).contains(&minutes) {
+                    return Err(String::from("Invalid timezone offset"));
+                }
+                wallclock::set_tz_offset_minutes(sign * (hours * 60 + minutes)).map_err(String::from)?;
+                vga_println!("Timezone: UTC{}", wallclock::format_offset(wallclock::tz_offset_minutes()));
+                Ok(())
+            }
+            Some(_) => Err(String::from("Usage: date [-u | set <YYYY-MM-DD> <HH:MM:SS> | tz <+HH:MM>]")),
+        }
+    }
+
+    /// taskset 명령어: 스레드 CPU 친화도 조회/설정
+    fn cmd_taskset(&self, args: &[&str]) -> Result<(), String> {
+        use crate::scheduler::{self, affinity::{self, CpuMask}};
+
+        const USAGE: &str = "Usage: taskset <tid> [cpus] | taskset isolated";
+        match args {
+            ["isolated"] => {
+                vga_println!("Online CPUs:   {}", affinity::online_cpus());
+                vga_println!("Isolated CPUs: {}", affinity::isolated_cpus());
+                Ok(())
+            }
+            [tid] => {
+                let tid: u64 = tid.parse().map_err(|_| String::from("Invalid thread id"))?;
+                let mask = scheduler::thread_affinity(tid).ok_or_else(|| String::from("Thread not found"))?;
+                vga_println!("Thread {} affinity: {} (effective {})", tid, mask, affinity::effective(mask));
+                Ok(())
+            }
+            [tid, cpus] => {
+                let tid: u64 = tid.parse().map_err(|_| String::from("Invalid thread id"))?;
+                let mask = CpuMask::parse(cpus).map_err(String::from)?;
+                scheduler::set_thread_affinity(tid, mask).map_err(String::from)?;
+                vga_println!("Thread {} affinity: {}", tid, mask);
+                Ok(())
+            }
+            _ => Err(String::from(USAGE)),
+        }
+    }
+
+    /// events 명령어: 최근 커널 이벤트 이력 (카테고리로 필터)
+    fn cmd_events(&self, args: &[&str]) -> Result<(), String> {
+        use crate::kernel::uevent::{self, EventFilter};
+
+        let mut filter = if args.is_empty() { EventFilter::ALL } else { EventFilter::NONE };
+        for name in args {
+            filter = filter | EventFilter::from_name(name).ok_or_else(|| format!("Unknown event category: {}", name))?;
+        }
+        let records = uevent::history(filter);
+        if records.is_empty() {
+            vga_println!("No kernel events");
+        }
+        for record in records {
+            vga_println!("[{:>6}.{:03}] #{} {}", record.time_ms / 1000, record.time_ms % 1000, record.seq, record.event);
+        }
+        Ok(())
+    }
+
+    /// mkfs.sjfs 명령어: 주 디스크를 Sjfs로 포맷
+    fn cmd_mkfs(&self, args: &[&str]) -> Result<(), String> {
+        use crate::drivers::ata::PRIMARY_MASTER;
+        use crate::fs::simple_journal_fs::{self, Sjfs};
+
+        let mut label = "";
+        let mut force = false;
+        let mut rest = args.iter();
+        while let Some(&arg) = rest.next() {
+            match arg {
+                "-f" => force = true,
+                "-L" => label = rest.next().ok_or_else(|| String::from("Usage: mkfs.sjfs [-L label] [-f]"))?,
+                _ => return Err(String::from("Usage: mkfs.sjfs [-L label] [-f]")),
+            }
+        }
+        if label.len() > 16 {
+            return Err(String::from("Label must be at most 16 bytes"));
+        }
+
+        // 디스크 전체를 포맷하므로 파티션이 마운트되어 있어도 거부
+        if crate::fs::FS_MANAGER.lock().is_disk_in_use(crate::fs::PRIMARY_DISK) {
+            return Err(format!("{} or one of its partitions is mounted; unmount it first", crate::fs::PRIMARY_DISK));
+        }
+
+        let mut disk = PRIMARY_MASTER.lock();
+        let driver = disk.as_mut().ok_or_else(|| String::from("No disk available"))?;
+
+        // 기존 파일시스템이나 파티션 테이블을 실수로 지우지 않도록
+        if !force {
+            let mut sector = [0u8; 512];
+            driver.read_block(0, &mut sector).map_err(|e| format!("Failed to read disk: {:?}", e))?;
+            let existing = if Sjfs::probe(driver) {
+                Some("an Sjfs filesystem")
+            } else if sector[510] == 0x55 && sector[511] == 0xAA {
+                Some("a FAT filesystem or partition table")
+            } else {
+                None
+            };
+            if let Some(existing) = existing {
+                return Err(format!("Disk contains {}; use -f to overwrite", existing));
+            }
+        }
+
+        vga_println!("Formatting primary disk as Sjfs...");
+        let sb = simple_journal_fs::mkfs(driver, label).map_err(|e| format!("mkfs failed: {:?}", e))?;
+        let g = sb.geometry;
+        vga_println!("  Label:   {}", if sb.label().is_empty() { "(none)" } else { sb.label() });
+        vga_println!("  Size:    {} MB ({} blocks of 4 KiB)", g.total_blocks * 4 / 1024, g.total_blocks);
+        vga_println!("  Inodes:  {}", g.inode_count);
+        vga_println!("  Journal: {} blocks", g.journal_blocks);
+        vga_println!("  Free:    {} blocks", sb.free_blocks);
+        Ok(())
+    }
+
+    /// mount 명령어: 마운트 목록 표시, 디바이스 마운트, 바인드 마운트
+    fn cmd_mount(&self, args: &[&str]) -> Result<(), String> {
+        use crate::fs::tmpfs::TmpfsOptions;
+        use crate::fs::{self, MountFlags, FS_MANAGER};
+
+        const USAGE: &str = "Usage: mount [-t type] [-o options] <device> <dir> | mount --bind [-o ro] <src> <dir>";
+        if args.is_empty() {
+            for mount in FS_MANAGER.lock().mounts() {
+                let mut options = String::from(if mount.read_only { "ro" } else { "rw" });
+                if mount.bind {
+                    options.push_str(",bind");
+                }
+                vga_println!("{} on {} type {} ({})", mount.source, mount.target, mount.fs_type, options);
+            }
+            return Ok(());
+        }
+
+        let mut fs_type = "auto";
+        let mut flags = MountFlags::default();
+        let mut bind = false;
+        // ro/rw 외의 옵션은 파일시스템에 전달 (tmpfs의 size=, nr_inodes=)
+        let mut fs_options = alloc::vec::Vec::new();
+        let mut operands = alloc::vec::Vec::new();
+        let mut rest = args.iter();
+        while let Some(&arg) = rest.next() {
+            match arg {
+                "-t" => fs_type = rest.next().ok_or_else(|| String::from(USAGE))?,
+                "-o" => {
+                    for option in rest.next().ok_or_else(|| String::from(USAGE))?.split(',') {
+                        match option {
+                            "ro" => flags.read_only = true,
+                            "rw" => flags.read_only = false,
+                            _ => fs_options.push(option),
+                        }
+                    }
+                }
+                "--bind" => bind = true,
+                _ => operands.push(arg),
+            }
+        }
+        let [source, target] = operands[..] else {
+            return Err(String::from(USAGE));
+        };
+
+        if fs_type != "tmpfs" {
+            if let Some(option) = fs_options.first() {
+                return Err(format!("Unknown mount option: {}", option));
+            }
+        }
+
+        let result = if bind {
+            FS_MANAGER.lock().bind(source, target, flags)
+        } else if fs_type == "tmpfs" {
+            let options = TmpfsOptions::parse(&fs_options).map_err(|_| String::from("Invalid tmpfs options (size=<n>[k|m], nr_inodes=<n>)"))?;
+            FS_MANAGER.lock().mount_tmpfs(target, options, flags)
+        } else if fs_type == "devfs" {
+            FS_MANAGER.lock().mount_devfs(target, flags)
+        } else if fs_type == "procfs" {
+            FS_MANAGER.lock().mount_procfs(target, flags)
+        } else {
+            let device = fs::open_block_device(source).map_err(|_| format!("No such device: {}", source))?;
+            let filesystem = fs::open_filesystem(fs_type, device).map_err(|e| format!("Cannot open {} as {}: {:?}", source, fs_type, e))?;
+            FS_MANAGER.lock().mount(target, source, filesystem, flags)
+        };
+        result.map_err(|e| format!("mount failed: {:?}", e))
+    }
+
+    /// umount 명령어: 마운트 해제
+    fn cmd_umount(&self, args: &[&str]) -> Result<(), String> {
+        let [target] = args else {
+            return Err(String::from("Usage: umount <dir>"));
+        };
+        let result = crate::fs::FS_MANAGER.lock().umount(target);
+        result.map_err(|e| match e {
+            crate::fs::vfs::FsError::Busy => format!("{}: target is busy", target),
+            crate::fs::vfs::FsError::NotFound => format!("{}: not mounted", target),
+            e => format!("umount failed: {:?}", e),
+        })
+    }
+
+    /// df 명령어: 마운트된 파일시스템 용량 표시
+    fn cmd_df(&self) -> Result<(), String> {
+        let mounts = crate::fs::FS_MANAGER.lock().mounts();
+        vga_println!("{:<12} {:>10} {:>10} {:>10} {:>5}  {}", "Filesystem", "1K-blocks", "Used", "Available", "Use%", "Mounted on");
+        for mount in mounts {
+            match crate::fs::mount::statfs(&mount.target) {
+                Ok(stats) => {
+                    let total = stats.total_blocks * stats.block_size / 1024;
+                    let free = stats.free_blocks * stats.block_size / 1024;
+                    let used = total - free.min(total);
+                    let percent = if total == 0 { 0 } else { (used * 100).div_ceil(total) };
+                    vga_println!("{:<12} {:>10} {:>10} {:>10} {:>4}%  {}", mount.source, total, used, free, percent, mount.target);
+                }
+                Err(_) => {
+                    vga_println!("{:<12} {:>10} {:>10} {:>10} {:>5}  {}", mount.source, "-", "-", "-", "-", mount.target);
+                }
+            }
+        }
+        Ok(())
+    }
+
     /// exit 명령어: Shell 종료 (재부팅 시뮬레이션)
     fn cmd_exit(&self) -> Result<(), String> {
         vga_println!("Exiting shell...");
@@ -336,3 +649,16 @@ impl Command {
         }
     }
 }
+
+/// `sep`으로 구분된 숫자 세 개 파싱 (예: "2024-01-31", "12:34:56")
+fn parse_fields(s: &str, sep: char) -> Option<[u32; 3]> {
+    let mut out = [0u32; 3];
+    let mut parts = s.split(sep);
+    for field in out.iter_mut() {
+        *field = parts.next()?.parse().ok()?;
+    }
+    if parts.next().is_some() {
+        return None;
+    }
+    Some(out)
+}
@@ -1,100 +1,368 @@
 //! AP (Application Processor) 부트 코드
 //!
-//! AP가 시작할 때 실행되는 코드입니다.
+//! BSP가 INIT/SIPI를 보내면 AP는 실제 모드(16비트)에서 트램펄린을 실행합니다.
+//! 트램펄린은 보호 모드를 거쳐 롱 모드로 전환한 뒤 `ap_entry`로 점프하고,
+//! `ap_entry`는 CPU별 GDT/TSS, 공유 IDT, per-CPU 데이터, Local APIC, 실행 큐를
+//! 설정한 다음 CPU별 유휴 루프에 들어갑니다.
+//!
+//! 트램펄린은 1 MiB 미만의 물리 메모리(프레임 할당자가 쓰지 않는 영역)에 복사되며,
+//! 다음과 같이 연속된 4 KiB 페이지를 사용합니다.
+//! - 0: 코드와 매개변수 블록 (`TrampolineParams`, 첫 명령어 바로 뒤)
+//! - 1: 임시 PML4 (BSP PML4 복사, 0번 엔트리만 교체)
+//! - 2: 임시 PDPT (가상 0 ~ 1 GiB)
+//! - 3: 임시 PD (가상 0 ~ 2 MiB를 2 MiB 페이지 하나로 항등 매핑)
+//!
+//! 페이징을 켜는 순간에도 트램펄린 코드가 같은 주소에 보여야 하므로 임시 페이지 테이블에
+//! 항등 매핑을 추가하고, `ap_entry`에서 곧바로 커널 페이지 테이블로 전환합니다.
+//! 32비트 모드에서 CR3를 로드하므로 임시 PML4는 4 GiB 아래에 있어야 합니다.
 
-use x86_64::instructions::interrupts;
+use alloc::vec;
+use core::mem::{offset_of, size_of};
+use core::sync::atomic::{AtomicU64, Ordering};
+use spin::Mutex;
+use x86_64::registers::control::{Cr0, Cr3, Cr3Flags, Cr4, Cr4Flags};
+use x86_64::registers::model_specific::{Efer, EferFlags};
+use x86_64::structures::paging::PhysFrame;
+use x86_64::PhysAddr;
 
-/// AP 부트 코드 주소 (1MB 미만, 4KB 정렬)
-const AP_BOOT_CODE_ADDR: u64 = 0x1000;
+use crate::memory::frame::LOW_MEMORY_RESERVED;
 
-/// AP 부트 코드 진입점
-///
-/// # Safety
-/// 이 코드는 AP가 시작할 때 실행됩니다.
-/// 페이지 테이블, 스택 등이 설정된 후에 실행되어야 합니다.
-///
-/// # Note
-/// 실제 구현에서는 어셈블리 코드를 메모리에 복사해야 합니다.
-/// 여기서는 C 함수로 래핑하여 구현합니다.
-#[no_mangle]
-pub unsafe extern "C" fn ap_boot_entry() -> ! {
-    // 인터럽트 비활성화
-    x86_64::instructions::interrupts::disable();
-    
-    // 페이지 테이블 설정 (BSP와 동일한 페이지 테이블 사용)
-    use x86_64::registers::control::Cr3;
-    let (frame, _) = Cr3::read();
-    // 재설정 없이 현재 CR3 유지 (BSP 테이블 사용)
-    Cr3::write(frame, x86_64::registers::control::Cr3::read().1);
-    
-    // 스택 설정 (임시, 이후 스케줄러가 할당)
-    // 실제로는 각 AP마다 별도의 스택이 필요
-    
-    // Local APIC 초기화
-    ap_init_local_apic();
-    
-    // AP 초기화 완료
-    ap_init_complete();
-    
-    // 무한 루프 (스케줄러가 작업 할당할 때까지)
-    loop {
-        x86_64::instructions::hlt();
-    }
+/// 트램펄린이 사용하는 연속 페이지 수
+const TRAMPOLINE_PAGES: u64 = 4;
+
+/// 페이지 크기
+const PAGE_SIZE: u64 = 4096;
+
+/// AP 커널 스택 크기
+const AP_STACK_SIZE: usize = 64 * 1024;
+
+/// 트램펄린 GDT 셀렉터
+const TRAMPOLINE_CODE32: u16 = 0x08;
+const TRAMPOLINE_DATA32: u16 = 0x10;
+const TRAMPOLINE_CODE64: u16 = 0x18;
+
+/// 페이지 테이블 엔트리 플래그
+const PTE_PRESENT: u64 = 1 << 0;
+const PTE_WRITABLE: u64 = 1 << 1;
+const PTE_HUGE: u64 = 1 << 7;
+const PTE_ADDR_MASK: u64 = 0x000F_FFFF_FFFF_F000;
+
+/// 트램펄린 매개변수 블록 (트램펄린 어셈블리와 배치를 공유)
+#[repr(C)]
+struct TrampolineParams {
+    /// null, 32비트 코드, 32비트 데이터, 64비트 코드
+    gdt: [u64; 4],
+    _pad0: u16,
+    gdtr_limit: u16,
+    gdtr_base: u32,
+    /// 보호 모드 진입용 far 포인터 (m16:32)
+    far32_offset: u32,
+    far32_selector: u16,
+    _pad1: u16,
+    /// 롱 모드 진입용 far 포인터 (m16:32)
+    far64_offset: u32,
+    far64_selector: u16,
+    _pad2: u16,
+    cr0: u64,
+    cr3: u64,
+    cr4: u64,
+    efer: u64,
+    stack_top: u64,
+    entry: u64,
+    cpu_id: u64,
 }
 
-/// AP Local APIC 초기화 (C 함수)
-///
-/// # Safety
-/// AP가 시작할 때 호출됩니다.
-#[no_mangle]
-pub unsafe extern "C" fn ap_init_local_apic() {
-    // Local APIC 초기화 (BSP와 동일)
-    crate::smp::apic::init_local_apic().unwrap_or_else(|e| {
-        crate::log_error!("AP Local APIC initialization failed: {}", e);
-    });
+/// 트램펄린 시작부터 매개변수 블록까지의 오프셋 (첫 명령어인 short jmp 다음)
+const PARAMS_OFFSET: usize = 8;
+
+core::arch::global_asm!(
+    ".pushsection .text.ap_trampoline, \"ax\"",
+    ".global ap_trampoline_start",
+    ".global ap_trampoline_end",
+    ".global ap_trampoline_pm32",
+    ".global ap_trampoline_lm64",
+    ".balign 8",
+    ".code16",
+    "ap_trampoline_start:",
+    "    jmp 3f",
+    ".balign 8",
+    "    .skip {params_size}",
+    "3:",
+    "    cli",
+    "    cld",
+    "    mov ax, cs",
+    "    mov ds, ax",
+    // EBX = 트램펄린 물리 주소 (CS * 16), 이후 모드에서도 기준 주소로 사용
+    "    xor ebx, ebx",
+    "    mov bx, ax",
+    "    shl ebx, 4",
+    "    lgdt [{gdtr}]",
+    "    mov eax, cr0",
+    "    or eax, 1",
+    "    mov cr0, eax",
+    // jmp far dword ptr [far32]
+    "    .byte 0x66, 0xFF, 0x2E",
+    "    .2byte {far32}",
+    ".code32",
+    "ap_trampoline_pm32:",
+    "    mov ax, {data32}",
+    "    mov ds, ax",
+    "    mov es, ax",
+    "    mov ss, ax",
+    "    mov eax, [ebx + {cr4}]",
+    "    mov cr4, eax",
+    "    mov eax, [ebx + {cr3}]",
+    "    mov cr3, eax",
+    "    mov ecx, 0xC0000080",
+    "    mov eax, [ebx + {efer}]",
+    "    xor edx, edx",
+    "    wrmsr",
+    // EFER.LME가 설정된 상태에서 페이징을 켜면 롱 모드(호환 모드) 진입
+    "    mov eax, [ebx + {cr0}]",
+    "    mov cr0, eax",
+    // jmp far dword ptr [ebx + far64]
+    "    .byte 0xFF, 0xAB",
+    "    .4byte {far64}",
+    ".code64",
+    "ap_trampoline_lm64:",
+    "    mov ebx, ebx",
+    "    xor eax, eax",
+    "    mov ds, ax",
+    "    mov es, ax",
+    "    mov ss, ax",
+    "    mov rsp, [rbx + {stack}]",
+    "    mov rdi, [rbx + {cpu}]",
+    "    mov rax, [rbx + {entry}]",
+    "    call rax",
+    "4:",
+    "    hlt",
+    "    jmp 4b",
+    "ap_trampoline_end:",
+    ".popsection",
+    params_size = const size_of::<TrampolineParams>(),
+    gdtr = const PARAMS_OFFSET + offset_of!(TrampolineParams, gdtr_limit),
+    far32 = const PARAMS_OFFSET + offset_of!(TrampolineParams, far32_offset),
+    far64 = const PARAMS_OFFSET + offset_of!(TrampolineParams, far64_offset),
+    data32 = const TRAMPOLINE_DATA32,
+    cr0 = const PARAMS_OFFSET + offset_of!(TrampolineParams, cr0),
+    cr3 = const PARAMS_OFFSET + offset_of!(TrampolineParams, cr3),
+    cr4 = const PARAMS_OFFSET + offset_of!(TrampolineParams, cr4),
+    efer = const PARAMS_OFFSET + offset_of!(TrampolineParams, efer),
+    stack = const PARAMS_OFFSET + offset_of!(TrampolineParams, stack_top),
+    cpu = const PARAMS_OFFSET + offset_of!(TrampolineParams, cpu_id),
+    entry = const PARAMS_OFFSET + offset_of!(TrampolineParams, entry),
+);
+
+extern "C" {
+    static ap_trampoline_start: u8;
+    static ap_trampoline_end: u8;
+    static ap_trampoline_pm32: u8;
+    static ap_trampoline_lm64: u8;
 }
 
-/// AP 초기화 완료
-///
-/// # Safety
-/// AP가 시작할 때 호출됩니다.
-#[no_mangle]
-pub unsafe extern "C" fn ap_init_complete() {
-    let apic_id = crate::smp::apic::get_local_apic_id();
-    crate::log_info!("AP with APIC ID {} initialized", apic_id);
-    
-    // 초기화 완료 플래그 설정
-    crate::smp::set_ap_initialized(apic_id);
-    
-    // CPU 정보 등록
-    let cpu_info = crate::smp::cpu::CpuInfo::new((apic_id as u64).try_into().unwrap(), false);
-    crate::smp::register_cpu(cpu_info);
+/// 트램펄린 물리 주소 (0 = 준비 안 됨)
+static TRAMPOLINE_BASE: AtomicU64 = AtomicU64::new(0);
+
+/// AP가 `ap_entry`에서 전환할 커널 페이지 테이블
+static KERNEL_CR3: Mutex<Option<(PhysFrame, Cr3Flags)>> = Mutex::new(None);
+
+/// 트램펄린 심볼의 시작 기준 오프셋
+fn trampoline_offset(symbol: &u8) -> u64 {
+    // SAFETY: 링커가 정의한 심볼의 주소만 사용
+    let start = unsafe { &ap_trampoline_start as *const u8 as u64 };
+    symbol as *const u8 as u64 - start
+}
+
+/// 물리 주소를 쓰기 가능한 포인터로 변환
+fn phys_ptr<T>(phys: u64) -> Result<*mut T, &'static str> {
+    crate::memory::paging::phys_to_virt(PhysAddr::new(phys))
+        .map(|v| v.as_mut_ptr())
+        .ok_or("physical memory offset not available")
+}
+
+/// 트램펄린을 둘 1 MiB 미만의 사용 가능 영역 찾기
+unsafe fn find_trampoline_base() -> Option<u64> {
+    let needed = TRAMPOLINE_PAGES * PAGE_SIZE;
+    crate::memory::map::get().usable_regions().find_map(|region| {
+        let start = (region.start.as_u64().max(PAGE_SIZE) + PAGE_SIZE - 1) & !(PAGE_SIZE - 1);
+        let end = (region.start.as_u64() + region.length).min(LOW_MEMORY_RESERVED);
+        (start + needed <= end).then_some(start)
+    })
 }
 
 /// AP 부트 코드를 메모리에 복사
 ///
+/// 트램펄린 코드와 임시 페이지 테이블을 준비합니다. 개별 AP의 스택과 번호는
+/// `prepare_ap_start`에서 채웁니다.
+///
 /// # Safety
-/// 메모리 관리가 초기화된 후에 호출되어야 합니다.
+/// 메모리 관리가 초기화된 후 BSP에서 호출되어야 합니다.
 pub unsafe fn prepare_ap_boot_code() -> Result<(), &'static str> {
-    // AP 부트 코드를 메모리에 복사
-    // 실제로는 어셈블리 코드를 바이너리에 포함시켜야 함
-    // 여기서는 기본 구조만 제공
-    
-    // 부트 코드 페이지 매핑 확인
-    let boot_page = x86_64::structures::paging::Page::<x86_64::structures::paging::Size4KiB>::containing_address(
-        x86_64::VirtAddr::new(AP_BOOT_CODE_ADDR)
-    );
-    
-    // 페이지가 매핑되어 있는지 확인
-    // TODO: 페이지 매핑 확인 및 필요시 매핑
-    
-    crate::log_info!("AP boot code prepared at 0x{:X}", AP_BOOT_CODE_ADDR);
-    
+    let base = find_trampoline_base().ok_or("no usable memory below 1 MiB for AP trampoline")?;
+
+    // 1. 코드 복사
+    let code_len = trampoline_offset(&ap_trampoline_end);
+    if code_len > PAGE_SIZE {
+        return Err("AP trampoline larger than one page");
+    }
+    let code: *mut u8 = phys_ptr(base)?;
+    core::ptr::copy_nonoverlapping(&ap_trampoline_start as *const u8, code, code_len as usize);
+
+    // 2. 임시 페이지 테이블 (PML4 -> PDPT -> PD, 0 ~ 2 MiB 항등 매핑)
+    let (kernel_frame, kernel_flags) = Cr3::read();
+    let pml4_phys = base + PAGE_SIZE;
+    let pdpt_phys = base + 2 * PAGE_SIZE;
+    let pd_phys = base + 3 * PAGE_SIZE;
+
+    let kernel_pml4: *const [u64; 512] = phys_ptr(kernel_frame.start_address().as_u64())?;
+    let pml4: *mut [u64; 512] = phys_ptr(pml4_phys)?;
+    let pdpt: *mut [u64; 512] = phys_ptr(pdpt_phys)?;
+    let pd: *mut [u64; 512] = phys_ptr(pd_phys)?;
+
+    *pml4 = *kernel_pml4;
+    *pdpt = [0; 512];
+    *pd = [0; 512];
+
+    // 커널이 가상 주소 하위 영역을 쓰고 있으면 해당 매핑을 유지
+    let kernel_pml4e = (*kernel_pml4)[0];
+    if kernel_pml4e & PTE_PRESENT != 0 {
+        let kernel_pdpt: *const [u64; 512] = phys_ptr(kernel_pml4e & PTE_ADDR_MASK)?;
+        *pdpt = *kernel_pdpt;
+        let kernel_pdpte = (*kernel_pdpt)[0];
+        if kernel_pdpte & PTE_PRESENT != 0 {
+            if kernel_pdpte & PTE_HUGE != 0 {
+                return Err("low 1 GiB mapped with a huge page");
+            }
+            let kernel_pd: *const [u64; 512] = phys_ptr(kernel_pdpte & PTE_ADDR_MASK)?;
+            *pd = *kernel_pd;
+            if (*pd)[0] & PTE_PRESENT != 0 {
+                return Err("low 2 MiB already mapped");
+            }
+        }
+    }
+    (*pml4)[0] = pdpt_phys | PTE_PRESENT | PTE_WRITABLE;
+    (*pdpt)[0] = pd_phys | PTE_PRESENT | PTE_WRITABLE;
+    (*pd)[0] = PTE_PRESENT | PTE_WRITABLE | PTE_HUGE;
+
+    // 3. 매개변수 블록의 고정 부분
+    let params: *mut TrampolineParams = phys_ptr(base + PARAMS_OFFSET as u64)?;
+    let params = &mut *params;
+    params.gdt = [
+        0,
+        0x00CF_9A00_0000_FFFF, // 32비트 코드
+        0x00CF_9200_0000_FFFF, // 32비트 데이터
+        0x00AF_9A00_0000_FFFF, // 64비트 코드
+    ];
+    params.gdtr_limit = (size_of::<[u64; 4]>() - 1) as u16;
+    params.gdtr_base = (base + PARAMS_OFFSET as u64) as u32
+        + offset_of!(TrampolineParams, gdt) as u32;
+    params.far32_offset = (base + trampoline_offset(&ap_trampoline_pm32)) as u32;
+    params.far32_selector = TRAMPOLINE_CODE32;
+    params.far64_offset = (base + trampoline_offset(&ap_trampoline_lm64)) as u32;
+    params.far64_selector = TRAMPOLINE_CODE64;
+    params.cr0 = Cr0::read_raw();
+    params.cr3 = pml4_phys;
+    // PCIDE는 롱 모드 진입 전에 설정할 수 없음
+    params.cr4 = (Cr4::read() - Cr4Flags::PCID).bits();
+    params.efer = (Efer::read() - EferFlags::LONG_MODE_ACTIVE).bits() | EferFlags::LONG_MODE_ENABLE.bits();
+    params.entry = ap_entry as extern "C" fn(u64) -> ! as usize as u64;
+
+    *KERNEL_CR3.lock() = Some((kernel_frame, kernel_flags));
+    TRAMPOLINE_BASE.store(base, Ordering::Release);
+
+    crate::log_info!("AP boot code prepared at 0x{:X}", base);
+
     Ok(())
 }
 
-/// AP 부트 코드 시작 페이지 번호 (4KB 단위)
+/// 다음에 시작할 AP의 스택과 CPU 번호 설정
+///
+/// AP는 한 번에 하나씩 시작하므로 매개변수 블록 하나를 재사용합니다.
+///
+/// # Safety
+/// `prepare_ap_boot_code` 이후, 이전 AP가 `ap_entry`에 진입한 뒤 호출되어야 합니다.
+pub unsafe fn prepare_ap_start(cpu_id: u8) -> Result<(), &'static str> {
+    let base = TRAMPOLINE_BASE.load(Ordering::Acquire);
+    if base == 0 {
+        return Err("AP boot code not prepared");
+    }
+    let stack: &'static mut [u8] = alloc::boxed::Box::leak(vec![0u8; AP_STACK_SIZE].into_boxed_slice());
+    let stack_top = (stack.as_mut_ptr() as u64 + AP_STACK_SIZE as u64) & !0xF;
+
+    let params: *mut TrampolineParams = phys_ptr(base + PARAMS_OFFSET as u64)?;
+    core::ptr::addr_of_mut!((*params).stack_top).write_volatile(stack_top);
+    core::ptr::addr_of_mut!((*params).cpu_id).write_volatile(cpu_id as u64);
+    Ok(())
+}
+
+/// AP 부트 코드 시작 페이지 번호 (4KB 단위, SIPI 벡터)
 pub fn ap_boot_code_page() -> u8 {
-    (AP_BOOT_CODE_ADDR >> 12) as u8
+    (TRAMPOLINE_BASE.load(Ordering::Acquire) >> 12) as u8
+}
+
+/// AP 롱 모드 진입점 (트램펄린에서 호출)
+///
+/// 임시 페이지 테이블과 트램펄린 GDT를 사용하는 상태로 진입합니다.
+extern "C" fn ap_entry(cpu_id: u64) -> ! {
+    let cpu_id = cpu_id as u8;
+    unsafe {
+        // 1. 커널 페이지 테이블로 전환
+        let kernel_cr3 = *KERNEL_CR3.lock();
+        if let Some((frame, flags)) = kernel_cr3 {
+            Cr3::write(frame, flags);
+        }
+
+        // 2. per-CPU 데이터 (GS 베이스, 이후 코드가 `per_cpu!`를 사용할 수 있도록 가장 먼저)
+        crate::kernel::percpu::init_ap(cpu_id);
+
+        // 3. CPU별 GDT/TSS, 공유 IDT
+        crate::interrupts::gdt::init_ap();
+        crate::interrupts::idt::load();
+
+        // 4. Local APIC (LINT 핀은 MADT NMI 엔트리만 다시 켬)
+        if let Err(e) = super::apic::init_local_apic() {
+            crate::log_error!("CPU {}: Local APIC initialization failed: {}", cpu_id, e);
+        }
+        super::apic::mask_local_interrupts();
+        super::configure_local_nmis();
+    }
+
+    // 5. 실행 큐와 스케줄러 틱용 LAPIC 타이머 (보정값은 BSP와 공유)
+    crate::scheduler::init_cpu();
+    if let Err(e) = unsafe { crate::drivers::lapic_timer::init_ap() } {
+        crate::log_warn!("CPU {}: no scheduler tick ({})", cpu_id, e);
+    }
+
+    let apic_id = super::apic::get_local_apic_id();
+    super::ap_online(cpu_id, apic_id);
+    crate::log_info!("CPU {} (APIC ID {}) online", cpu_id, apic_id);
+
+    ap_idle_loop()
 }
 
+/// AP 유휴 루프
+///
+/// 대기 중인 softirq를 처리하고, 실행 큐에 스레드가 있으면 스케줄러를 돌려
+/// 다음 스레드를 고른 뒤 LAPIC 타이머 틱을 시작합니다. 그 다음 IPI나 틱이 올
+/// 때까지 멈춥니다. 부팅 중 병렬 initcall 단계에서는 멈추지 않고 initcall 큐를 폴링합니다.
+fn ap_idle_loop() -> ! {
+    x86_64::instructions::interrupts::enable();
+    loop {
+        crate::kernel::softirq::run_pending();
+        if crate::kernel::initcall::ap_poll() {
+            continue;
+        }
+
+        // 확인과 잠들기 사이에 온 재스케줄링 IPI를 놓치지 않도록 인터럽트를 끈 채 확인
+        x86_64::instructions::interrupts::disable();
+        if crate::scheduler::schedule() {
+            crate::drivers::timer::start_ap_tick();
+        }
+
+        let idle = crate::per_cpu!(idle);
+        let start_ns = crate::time::now_ns();
+        x86_64::instructions::interrupts::enable_and_hlt();
+        idle.entries.fetch_add(1, Ordering::Relaxed);
+        idle.idle_ns.fetch_add(crate::time::now_ns().saturating_sub(start_ns), Ordering::Relaxed);
+    }
+}
@@ -1,20 +1,16 @@
 //! APIC (Advanced Programmable Interrupt Controller) 드라이버
 //!
-//! Local APIC와 I/O APIC을 관리합니다.
+//! 각 CPU의 Local APIC을 관리합니다. I/O APIC은 `interrupts::ioapic`이 담당합니다.
 
-use x86_64::structures::paging::{Page, PageTableFlags, PhysFrame};
 use x86_64::PhysAddr;
-use spin::Mutex;
 use core::ptr::{read_volatile, write_volatile};
+use core::sync::atomic::{AtomicU64, Ordering};
 
 /// Local APIC 기본 물리 주소
 const LOCAL_APIC_BASE: u64 = 0xFEE0_0000;
 
-/// I/O APIC 기본 물리 주소 (ACPI MADT에서 읽어야 하지만 기본값 사용)
-const IO_APIC_BASE: u64 = 0xFEC0_0000;
-
 /// Local APIC 레지스터 오프셋
-mod local_apic_reg {
+pub(crate) mod local_apic_reg {
     pub const ID: u32 = 0x20;           // Local APIC ID
     pub const VERSION: u32 = 0x30;      // APIC 버전
     pub const TPR: u32 = 0x80;          // Task Priority Register
@@ -31,26 +27,16 @@ mod local_apic_reg {
     pub const TIMER_DIV: u32 = 0x3E0;   // Timer Divide Configuration
 }
 
-/// I/O APIC 레지스터
-mod io_apic_reg {
-    pub const IOREGSEL: u32 = 0x00;     // I/O Register Select
-    pub const IOWIN: u32 = 0x10;        // I/O Window
-    
-    // I/O APIC 레지스터 인덱스
-    pub const ID: u8 = 0x00;
-    pub const VER: u8 = 0x01;
-    pub const ARB: u8 = 0x02;
-    pub const REDTBL_BASE: u8 = 0x10;   // Redirection Table Base
-}
-
-/// Local APIC 가상 주소
-static LOCAL_APIC_ADDR: Mutex<Option<u64>> = Mutex::new(None);
-
-/// I/O APIC 가상 주소
-static IO_APIC_ADDR: Mutex<Option<u64>> = Mutex::new(None);
+/// Local APIC 가상 주소 (0 = 미초기화, 인터럽트 핸들러의 EOI에서도 읽으므로 락 없이 보관)
+static LOCAL_APIC_ADDR: AtomicU64 = AtomicU64::new(0);
 
 /// Local APIC 초기화
 ///
+/// 현재 CPU의 Local APIC을 활성화합니다. LVT 엔트리는 건드리지 않으므로 BSP의
+/// LINT0(8259 PIC 가상 와이어)와 LAPIC 타이머 설정은 유지됩니다.
+/// 여러 번 호출해도 안전하며 (IRQ 라우팅, LAPIC 타이머, SMP, S3 재개), 이미 설정된
+/// 스퓨리어스 벡터는 유지합니다.
+///
 /// # Safety
 /// 메모리 관리가 초기화된 후에 호출되어야 합니다.
 pub unsafe fn init_local_apic() -> Result<(), &'static str> {
@@ -62,48 +48,67 @@ pub unsafe fn init_local_apic() -> Result<(), &'static str> {
         write_apic_base_msr(apic_base | (1 << 11));
     }
     
-    // 2. Local APIC 메모리 매핑
-    // TODO: 페이지 테이블에 매핑 (현재는 직접 물리 주소 사용)
-    // 실제로는 페이지 테이블을 통해 가상 주소에 매핑해야 함
-    *LOCAL_APIC_ADDR.lock() = Some(LOCAL_APIC_BASE);
+    // 2. Local APIC 메모리 매핑 (물리 메모리 오프셋 매핑 사용, 모든 CPU가 같은 주소)
+    let phys = match apic_base & 0xFFFF_F000 {
+        0 => LOCAL_APIC_BASE,
+        base => base,
+    };
+    let virt = crate::memory::paging::phys_to_virt(PhysAddr::new(phys))
+        .ok_or("physical memory offset not available")?;
+    let first = LOCAL_APIC_ADDR.swap(virt.as_u64(), Ordering::AcqRel) == 0;
     
     // 3. Spurious Interrupt Vector Register 설정
-    // APIC 활성화 (비트 8) + 스퓨리어스 벡터 (0xFF)
-    write_local_apic_reg(local_apic_reg::SVR, 0x1FF);
+    // APIC 활성화 (비트 8), 스퓨리어스 벡터가 없으면 0xFF 사용
+    let svr = read_local_apic_reg(local_apic_reg::SVR);
+    let spurious = if svr & 0xFF == 0 { 0xFF } else { 0 };
+    write_local_apic_reg(local_apic_reg::SVR, svr | spurious | (1 << 8));
     
     // 4. Task Priority Register를 0으로 설정 (모든 인터럽트 허용)
     write_local_apic_reg(local_apic_reg::TPR, 0);
     
-    // 5. LVT 엔트리 마스킹 해제
+    if first {
+        crate::log_info!("Local APIC initialized at 0x{:X}", phys);
+    }
+    
+    Ok(())
+}
+
+/// Local APIC이 매핑되었는지 여부
+pub fn is_initialized() -> bool {
+    LOCAL_APIC_ADDR.load(Ordering::Acquire) != 0
+}
+
+/// 현재 CPU의 LVT 엔트리 마스킹 (AP 시작 시)
+///
+/// AP는 PIC 가상 와이어(LINT0)와 NMI(LINT1)를 받지 않으며,
+/// 타이머는 필요할 때 별도로 설정합니다.
+///
+/// # Safety
+/// `init_local_apic` 이후 AP에서 호출되어야 합니다.
+pub unsafe fn mask_local_interrupts() {
     write_local_apic_reg(local_apic_reg::LVT_TIMER, 0x10000); // Masked
     write_local_apic_reg(local_apic_reg::LVT_LINT0, 0x10000); // Masked
     write_local_apic_reg(local_apic_reg::LVT_LINT1, 0x10000); // Masked
     write_local_apic_reg(local_apic_reg::LVT_ERROR, 0x10000); // Masked
-    
-    crate::log_info!("Local APIC initialized at 0x{:X}", LOCAL_APIC_BASE);
-    
-    Ok(())
 }
 
-/// I/O APIC 초기화
+/// LINT 핀을 NMI로 설정 (MADT Local APIC NMI 엔트리)
+///
+/// NMI는 항상 에지 트리거로 전달되므로 트리거 모드는 무시합니다.
 ///
 /// # Safety
-/// Local APIC가 초기화된 후에 호출되어야 합니다.
-pub unsafe fn init_io_apic() -> Result<(), &'static str> {
-    // I/O APIC 메모리 매핑
-    *IO_APIC_ADDR.lock() = Some(IO_APIC_BASE);
-    
-    // I/O APIC 버전 읽기
-    let version = read_io_apic_reg(io_apic_reg::VER);
-    let max_redirects = ((version >> 16) & 0xFF) + 1;
-    
-    crate::log_info!("I/O APIC initialized at 0x{:X}, max redirects: {}", 
-                     IO_APIC_BASE, max_redirects);
-    
-    // TODO: 인터럽트 라우팅 설정
-    // 각 IRQ를 적절한 벡터에 매핑
-    
-    Ok(())
+/// `init_local_apic` 이후 해당 CPU에서 호출되어야 합니다.
+pub unsafe fn set_lint_nmi(lint: u8, active_low: bool) {
+    const DELIVERY_NMI: u32 = 0b100 << 8;
+    const POLARITY_LOW: u32 = 1 << 13;
+
+    let reg = match lint {
+        0 => local_apic_reg::LVT_LINT0,
+        1 => local_apic_reg::LVT_LINT1,
+        _ => return,
+    };
+    let polarity = if active_low { POLARITY_LOW } else { 0 };
+    write_local_apic_reg(reg, DELIVERY_NMI | polarity);
 }
 
 /// Local APIC ID 반환
@@ -114,66 +119,50 @@ pub fn get_local_apic_id() -> u8 {
     }
 }
 
+/// ICR (Interrupt Command Register)에 쓰고 전송 완료 대기
+///
+/// # Safety
+/// Local APIC가 초기화된 후에 호출되어야 합니다.
+pub unsafe fn write_icr(high: u32, low: u32) {
+    const DELIVERY_STATUS_BIT: u32 = 1 << 12;
+
+    write_local_apic_reg(local_apic_reg::ICR_HIGH, high);
+    write_local_apic_reg(local_apic_reg::ICR_LOW, low);
+
+    // Delivery Status 비트가 0이 될 때까지 대기
+    while read_local_apic_reg(local_apic_reg::ICR_LOW) & DELIVERY_STATUS_BIT != 0 {
+        core::hint::spin_loop();
+    }
+}
+
 /// Local APIC EOI (End of Interrupt) 신호 전송
+///
+/// Local APIC이 초기화되기 전에는 아무것도 하지 않습니다.
 pub fn send_eoi() {
     unsafe {
         write_local_apic_reg(local_apic_reg::EOI, 0);
     }
 }
 
-/// Local APIC 레지스터 읽기
+/// Local APIC 레지스터 읽기 (초기화 전에는 0)
 ///
 /// # Safety
 /// Local APIC가 초기화된 후에 호출되어야 합니다.
-unsafe fn read_local_apic_reg(offset: u32) -> u32 {
-    let addr = LOCAL_APIC_ADDR.lock();
-    if let Some(base) = *addr {
-        let ptr = (base + offset as u64) as *const u32;
-        read_volatile(ptr)
-    } else {
-        0
+pub(crate) unsafe fn read_local_apic_reg(offset: u32) -> u32 {
+    match LOCAL_APIC_ADDR.load(Ordering::Acquire) {
+        0 => 0,
+        base => read_volatile((base + offset as u64) as *const u32),
     }
 }
 
-/// Local APIC 레지스터 쓰기
+/// Local APIC 레지스터 쓰기 (초기화 전에는 무시)
 ///
 /// # Safety
 /// Local APIC가 초기화된 후에 호출되어야 합니다.
-unsafe fn write_local_apic_reg(offset: u32, value: u32) {
-    let addr = LOCAL_APIC_ADDR.lock();
-    if let Some(base) = *addr {
-        let ptr = (base + offset as u64) as *mut u32;
-        write_volatile(ptr, value);
-    }
-}
-
-/// I/O APIC 레지스터 읽기
-unsafe fn read_io_apic_reg(reg: u8) -> u32 {
-    let addr = IO_APIC_ADDR.lock();
-    if let Some(base) = *addr {
-        // IOREGSEL에 레지스터 인덱스 쓰기
-        let sel_ptr = (base + io_apic_reg::IOREGSEL as u64) as *mut u32;
-        write_volatile(sel_ptr, reg as u32);
-        
-        // IOWIN에서 값 읽기
-        let win_ptr = (base + io_apic_reg::IOWIN as u64) as *const u32;
-        read_volatile(win_ptr)
-    } else {
-        0
-    }
-}
-
-/// I/O APIC 레지스터 쓰기
-unsafe fn write_io_apic_reg(reg: u8, value: u32) {
-    let addr = IO_APIC_ADDR.lock();
-    if let Some(base) = *addr {
-        // IOREGSEL에 레지스터 인덱스 쓰기
-        let sel_ptr = (base + io_apic_reg::IOREGSEL as u64) as *mut u32;
-        write_volatile(sel_ptr, reg as u32);
-        
-        // IOWIN에 값 쓰기
-        let win_ptr = (base + io_apic_reg::IOWIN as u64) as *mut u32;
-        write_volatile(win_ptr, value);
+pub(crate) unsafe fn write_local_apic_reg(offset: u32, value: u32) {
+    match LOCAL_APIC_ADDR.load(Ordering::Acquire) {
+        0 => {}
+        base => write_volatile((base + offset as u64) as *mut u32, value),
     }
 }
 
@@ -190,24 +179,3 @@ unsafe fn write_apic_base_msr(value: u64) {
     let mut msr = Msr::new(0x1B); // IA32_APIC_BASE
     msr.write(value);
 }
-
-/// I/O APIC Redirection Table 엔트리 설정
-///
-/// # Arguments
-/// * `irq` - IRQ 번호
-/// * `vector` - 인터럽트 벡터
-/// * `dest_apic_id` - 대상 CPU의 APIC ID
-pub unsafe fn set_io_apic_redirect(irq: u8, vector: u8, dest_apic_id: u8) {
-    let redirect_reg = io_apic_reg::REDTBL_BASE + (irq * 2);
-    
-    // Low 32비트: 벡터, 전달 모드, 목적지 모드, 극성, 트리거 모드
-    let low = vector as u32;
-    
-    // High 32비트: 목적지 APIC ID
-    let high = (dest_apic_id as u32) << 24;
-    
-    write_io_apic_reg(redirect_reg, low);
-    write_io_apic_reg(redirect_reg + 1, high);
-}
-
-
@@ -107,8 +107,10 @@ fn send_ipi_full(
         let icr_high = (dest_apic_id as u32) << 24;
         
         // Low 32비트: 벡터, 전달 모드, 목적지 모드 등
+        // Level 비트(14)는 INIT de-assert 외의 모든 전달 모드에서 1이어야 함
         let icr_low = (vector as u32)
             | ((delivery_mode as u32) << 8)
+            | (1 << 14)
             | ((destination_shorthand as u32) << 18);
         
         // ICR에 쓰기 (먼저 High, 그 다음 Low)
@@ -121,35 +123,7 @@ fn send_ipi_full(
 /// # Safety
 /// Local APIC가 초기화된 후에 호출되어야 합니다.
 unsafe fn write_icr(high: u32, low: u32) {
-    const ICR_HIGH: u32 = 0x310;
-    const ICR_LOW: u32 = 0x300;
-    
-    // Local APIC 레지스터에 직접 쓰기
-    // 실제 구현에서는 apic 모듈의 함수 사용
-    let base = 0xFEE0_0000u64;
-    
-    let high_ptr = (base + ICR_HIGH as u64) as *mut u32;
-    let low_ptr = (base + ICR_LOW as u64) as *mut u32;
-    
-    core::ptr::write_volatile(high_ptr, high);
-    core::ptr::write_volatile(low_ptr, low);
-    
-    // ICR 전송 완료 대기
-    wait_for_icr_ready();
-}
-
-/// ICR 전송 완료 대기
-unsafe fn wait_for_icr_ready() {
-    const ICR_LOW: u32 = 0x300;
-    const DELIVERY_STATUS_BIT: u32 = 1 << 12;
-    
-    let base = 0xFEE0_0000u64;
-    let low_ptr = (base + ICR_LOW as u64) as *const u32;
-    
-    // Delivery Status 비트가 0이 될 때까지 대기
-    while core::ptr::read_volatile(low_ptr) & DELIVERY_STATUS_BIT != 0 {
-        core::hint::spin_loop();
-    }
+    apic::write_icr(high, low);
 }
 
 /// TLB (Translation Lookaside Buffer) 플러시 IPI
@@ -161,15 +135,24 @@ pub fn send_tlb_flush_ipi() {
     broadcast_ipi(TLB_FLUSH_VECTOR);
 }
 
+/// 재스케줄링 IPI 벡터
+pub const RESCHEDULE_VECTOR: u8 = 0xFC;
+
 /// 스케줄러 재스케줄링 IPI
 ///
-/// 특정 CPU에 재스케줄링을 요청합니다.
+/// 특정 CPU에 재스케줄링을 요청합니다. 대상 CPU는 유휴 상태에서 깨어나
+/// 유휴 루프에서 실행 큐를 다시 확인합니다.
 pub fn send_reschedule_ipi(dest_apic_id: u8) {
-    // 재스케줄링용 벡터 번호 (사용자 정의)
-    const RESCHEDULE_VECTOR: u8 = 0xFC;
     send_ipi(dest_apic_id, RESCHEDULE_VECTOR);
 }
 
+/// 재스케줄링 IPI 핸들러
+///
+/// `hlt`에서 깨우는 것이 목적이므로 EOI만 보냅니다.
+pub extern "x86-interrupt" fn reschedule_interrupt_handler(_stack_frame: x86_64::structures::idt::InterruptStackFrame) {
+    apic::send_eoi();
+}
+
 
 
 
@@ -9,6 +9,7 @@ mod ap_boot;
 
 use alloc::vec::Vec;
 use spin::Mutex;
+use crate::power::acpi_madt::{MadtInfo, Polarity};
 use crate::smp::cpu::CpuInfo;
 
 /// 전역 CPU 정보 리스트
@@ -24,36 +25,53 @@ static CPU_COUNT: Mutex<usize> = Mutex::new(0);
 pub unsafe fn init() -> Result<(), &'static str> {
     crate::log_info!("Initializing SMP support...");
     
-    // 1. Local APIC 초기화 (BSP - Bootstrap Processor)
+    // 1. ACPI MADT에서 프로세서 정보 읽기
+    let madt = crate::power::acpi_madt::load();
+    
+    // 2. Local APIC 초기화 (BSP - Bootstrap Processor)
+    // I/O APIC은 `interrupts::irq::init`에서 이미 설정됨
     apic::init_local_apic()?;
+    configure_local_nmis();
     crate::log_info!("Local APIC initialized on BSP");
     
-    // 2. I/O APIC 초기화
-    apic::init_io_apic()?;
-    crate::log_info!("I/O APIC initialized");
-    
     // 3. BSP CPU 정보 등록
-    let bsp_info = cpu::CpuInfo::new(0, true);
+    let bsp_apic_id = apic::get_local_apic_id();
+    let bsp_info = cpu::CpuInfo::new(bsp_apic_id, true);
     CPUS.lock().push(bsp_info);
     *CPU_COUNT.lock() = 1;
     
-    // 4. AP (Application Processor) 초기화 준비
-    // ACPI 테이블에서 CPU 정보 읽기
-    let cpu_count = detect_cpu_count();
-    crate::log_info!("Detected {} CPU(s)", cpu_count);
+    // 4. 시작할 AP 목록 (MADT의 활성 프로세서, MADT가 없으면 CPUID 추정)
+    let (detected, ap_apic_ids) = match madt {
+        Some(m) => {
+            let possible = m.possible_cpu_count();
+            let enabled = m.enabled_cpus().count();
+            if possible > enabled {
+                crate::log_info!("{} CPU(s) are online-capable but disabled", possible - enabled);
+            }
+            (enabled, madt_ap_apic_ids(m, bsp_apic_id))
+        }
+        None => {
+            crate::log_warn!("Falling back to CPUID topology for AP discovery");
+            let detected = detect_cpu_count();
+            (detected, detect_ap_apic_ids(detected))
+        }
+    };
+    crate::log_info!("Detected {} CPU(s)", detected);
     
     // 5. AP 부트 코드 준비
     ap_boot::prepare_ap_boot_code()?;
     
     // 6. AP 초기화 (추가 CPU가 있는 경우)
-    if cpu_count > 1 {
-        crate::log_info!("Starting {} Application Processor(s)...", cpu_count - 1);
-        
-        // ACPI MADT에서 APIC ID 목록 읽기 (간단한 구현)
-        let ap_apic_ids = detect_ap_apic_ids(cpu_count);
+    if !ap_apic_ids.is_empty() {
+        crate::log_info!("Starting {} Application Processor(s)...", ap_apic_ids.len());
         
         for apic_id in ap_apic_ids {
-            if let Err(e) = init_application_processor(apic_id) {
+            // 논리 CPU 번호는 시작에 성공한 순서대로 연속 할당
+            let cpu_id = cpu_count() as u8;
+            if cpu_id as usize >= crate::scheduler::affinity::MAX_CPUS {
+                break;
+            }
+            if let Err(e) = init_application_processor(apic_id, cpu_id) {
                 crate::log_warn!("Failed to initialize AP with APIC ID {}: {}", apic_id, e);
             } else {
                 crate::log_info!("AP with APIC ID {} initialized successfully", apic_id);
@@ -61,17 +79,43 @@ pub unsafe fn init() -> Result<(), &'static str> {
         }
     }
     
-    *CPU_COUNT.lock() = cpu_count;
+    // 7. 온라인 CPU 수에 맞춰 로드 밸런서 설정
+    let online = cpu_count();
+    x86_64::instructions::interrupts::without_interrupts(|| {
+        crate::scheduler::load_balancer::init(
+            online,
+            crate::scheduler::load_balancer::BalancingStrategy::LeastLoaded,
+        );
+    });
+    crate::log_info!("SMP: {} of {} CPU(s) online", online, detected);
     
     Ok(())
 }
 
-/// CPU 수 감지
+/// MADT에서 시작할 AP의 APIC ID 목록 추출
+///
+/// 부팅 시 활성화된 프로세서만 포함하며, xAPIC 모드에서 주소를 지정할 수 없는
+/// APIC ID(255 이상)는 건너뜁니다.
+fn madt_ap_apic_ids(madt: &MadtInfo, bsp_apic_id: u8) -> Vec<u8> {
+    let mut apic_ids = Vec::new();
+    for lapic in madt.enabled_cpus() {
+        if lapic.apic_id == bsp_apic_id as u32 {
+            continue;
+        }
+        match u8::try_from(lapic.apic_id) {
+            Ok(id) if id != 0xFF => apic_ids.push(id),
+            _ => {
+                crate::log_warn!("Skipping CPU with x2APIC ID {} (xAPIC mode)", lapic.apic_id);
+            }
+        }
+    }
+    apic_ids
+}
+
+/// CPU 수 감지 (MADT가 없을 때)
 ///
-/// ACPI 테이블에서 CPU 수를 읽습니다.
+/// CPUID 리프 0xB의 논리 프로세서 수를 사용합니다.
 fn detect_cpu_count() -> usize {
-    // TODO: ACPI MADT (Multiple APIC Description Table)에서 CPU 수 읽기
-    // 현재는 CPUID를 통해 논리 프로세서 수 확인
     unsafe {
         use core::arch::x86_64::{__cpuid, __cpuid_count};
         let max_leaf = __cpuid(0).eax;
@@ -84,14 +128,12 @@ fn detect_cpu_count() -> usize {
     }
 }
 
-/// AP APIC ID 목록 감지
+/// AP APIC ID 목록 추정 (MADT가 없을 때)
 fn detect_ap_apic_ids(total_cpus: usize) -> Vec<u8> {
-    // TODO: ACPI MADT에서 APIC ID 목록 읽기
-    // 현재는 기본 구현 (BSP APIC ID 제외)
     let bsp_apic_id = apic::get_local_apic_id();
     let mut apic_ids = Vec::new();
     
-    // 간단한 구현: 1부터 시작하는 연속된 APIC ID 가정
+    // 0부터 시작하는 연속된 APIC ID 가정 (BSP 제외)
     for i in 0..total_cpus {
         let apic_id = i as u8;
         if apic_id != bsp_apic_id {
@@ -102,73 +144,78 @@ fn detect_ap_apic_ids(total_cpus: usize) -> Vec<u8> {
     apic_ids
 }
 
+/// 현재 CPU의 LINT 핀을 MADT Local APIC NMI 엔트리에 따라 설정
+///
+/// # Safety
+/// `apic::init_local_apic` 이후 해당 CPU에서 호출되어야 합니다.
+pub unsafe fn configure_local_nmis() {
+    let Some(madt) = crate::power::acpi_madt::get() else { return };
+    let Some(uid) = madt.processor_uid(apic::get_local_apic_id() as u32) else { return };
+    for nmi in madt.local_apic_nmis.iter().filter(|nmi| nmi.applies_to(uid)) {
+        apic::set_lint_nmi(nmi.lint, nmi.polarity == Polarity::ActiveLow);
+    }
+}
+
 /// Application Processor 초기화
 ///
+/// INIT-SIPI-SIPI 순서로 AP를 깨우고, AP가 `ap_entry`에서 온라인을 알릴 때까지 기다립니다.
+///
 /// # Arguments
 /// * `apic_id` - 초기화할 AP의 APIC ID
+/// * `cpu_id` - AP에 할당할 논리 CPU 번호
 ///
 /// # Safety
 /// SMP 초기화 중에만 호출되어야 합니다.
-unsafe fn init_application_processor(apic_id: u8) -> Result<(), &'static str> {
-    crate::log_info!("Initializing AP with APIC ID {}...", apic_id);
+unsafe fn init_application_processor(apic_id: u8, cpu_id: u8) -> Result<(), &'static str> {
+    crate::log_info!("Initializing AP with APIC ID {} as CPU {}...", apic_id, cpu_id);
+    
+    ap_boot::prepare_ap_start(cpu_id)?;
     
     // 1. INIT IPI 전송 (CPU 리셋)
     ipi::send_init_ipi(apic_id);
     
     // INIT IPI 전송 후 10ms 대기
-    let start_ms = crate::drivers::timer::get_milliseconds();
-    while crate::drivers::timer::get_milliseconds() - start_ms < 10 {
-        core::hint::spin_loop();
-    }
+    wait_ms(10);
     
     // 2. SIPI 전송 (첫 번째)
     let boot_page = ap_boot::ap_boot_code_page();
     ipi::send_startup_ipi(apic_id, boot_page);
     
-    // SIPI 전송 후 200us 대기
-    let start_ms = crate::drivers::timer::get_milliseconds();
-    while crate::drivers::timer::get_milliseconds() - start_ms < 1 {
-        core::hint::spin_loop();
+    // AP가 온라인이 될 때까지 대기
+    if wait_for_ap(apic_id, AP_START_TIMEOUT_MS) {
+        return Ok(());
     }
     
     // 3. SIPI 전송 (두 번째, 첫 번째가 실패했을 경우)
-    // AP가 시작되지 않았는지 확인
-    let mut timeout = 1000; // 1초 타임아웃
-    while timeout > 0 {
-        // AP가 시작되었는지 확인 (AP가 초기화 완료 플래그 설정)
-        if is_ap_initialized(apic_id) {
-            break;
-        }
-        
-        timeout -= 1;
-        for _ in 0..100 {
-            core::hint::spin_loop();
-        }
+    ipi::send_startup_ipi(apic_id, boot_page);
+    if wait_for_ap(apic_id, AP_START_TIMEOUT_MS) {
+        return Ok(());
     }
     
-    if timeout == 0 {
-        // 두 번째 SIPI 전송
-        ipi::send_startup_ipi(apic_id, boot_page);
-        
-        // 추가 대기
-        timeout = 1000;
-        while timeout > 0 {
-            if is_ap_initialized(apic_id) {
-                break;
-            }
-            
-            timeout -= 1;
-            for _ in 0..100 {
-                core::hint::spin_loop();
-            }
-        }
-        
-        if timeout == 0 {
-            return Err("AP initialization timeout");
+    Err("AP initialization timeout")
+}
+
+/// AP 시작 대기 시간 (SIPI 한 번당, ms)
+const AP_START_TIMEOUT_MS: u64 = 100;
+
+/// 지정한 시간 동안 대기 (타이머 틱 기준)
+fn wait_ms(ms: u64) {
+    let start_ms = crate::drivers::timer::get_milliseconds();
+    while crate::drivers::timer::get_milliseconds() - start_ms < ms {
+        core::hint::spin_loop();
+    }
+}
+
+/// AP가 온라인이 될 때까지 최대 `timeout_ms` 대기
+fn wait_for_ap(apic_id: u8, timeout_ms: u64) -> bool {
+    let start_ms = crate::drivers::timer::get_milliseconds();
+    while crate::drivers::timer::get_milliseconds() - start_ms < timeout_ms {
+        if is_ap_initialized(apic_id) {
+            return true;
         }
+        core::hint::spin_loop();
     }
-    
-    Ok(())
+    is_ap_initialized(apic_id)
 }
 
 /// AP 초기화 완료 플래그 (APIC ID별)
@@ -186,6 +233,17 @@ pub fn set_ap_initialized(apic_id: u8) {
     flags.insert(apic_id, true);
 }
 
+/// AP 온라인 등록 (AP의 `ap_entry`에서 호출)
+///
+/// CPU 정보를 등록한 뒤 BSP에 시작 완료를 알립니다.
+fn ap_online(cpu_id: u8, apic_id: u8) {
+    let mut cpu_info = CpuInfo::new(apic_id, false);
+    cpu_info.set_state(cpu::CpuState::Active);
+    register_cpu(cpu_info);
+    debug_assert_eq!(cpu_count(), cpu_id as usize + 1);
+    set_ap_initialized(apic_id);
+}
+
 /// CPU 등록 (AP에서 호출)
 pub fn register_cpu(cpu_info: CpuInfo) {
     let mut cpus = CPUS.lock();
@@ -200,8 +258,13 @@ pub fn cpu_count() -> usize {
 
 /// 현재 CPU ID 반환
 ///
-/// Local APIC ID를 읽어 현재 실행 중인 CPU를 식별합니다.
+/// GS 베이스가 가리키는 per-CPU 블록에서 논리 CPU 번호를 읽습니다.
 pub fn current_cpu_id() -> u8 {
+    crate::kernel::percpu::cpu_id()
+}
+
+/// 현재 CPU의 Local APIC ID 반환
+pub fn current_apic_id() -> u8 {
     apic::get_local_apic_id()
 }
 
@@ -71,6 +71,12 @@ pub fn dispatch_syscall(
         SyscallNumber::GetPid => {
             implementations::sys_get_pid()
         }
+        SyscallNumber::SchedSetAffinity => {
+            implementations::sys_sched_set_affinity(arg1, arg2)
+        }
+        SyscallNumber::SchedGetAffinity => {
+            implementations::sys_sched_get_affinity(arg1)
+        }
     };
     
     // 결과를 i64로 변환
@@ -163,17 +163,8 @@ pub fn sys_sleep(milliseconds: u64) -> SyscallResult {
         return Ok(0);
     }
     
-    // 현재 시간 기록
-    let start_time = timer::get_milliseconds();
-    let target_time = start_time + milliseconds;
-    
-    // 목표 시간까지 대기
-    // TODO: 스레드를 블로킹하고 타이머 인터럽트에서 깨우기
-    // 현재는 busy-wait (실제 구현에서는 스레드 블로킹 사용)
-    while timer::get_milliseconds() < target_time {
-        // CPU 양보
-        x86_64::instructions::hlt();
-    }
+    // hrtimer 만료까지 유휴 대기 (tickless 모드에서는 그 사이 틱이 멈춤)
+    crate::time::hrtimer::sleep_ns(milliseconds.saturating_mul(crate::time::NSEC_PER_MSEC));
     
     Ok(0)
 }
@@ -207,3 +198,47 @@ pub fn sys_get_pid() -> SyscallResult {
     }
 }
 
+
+/// 대상 스레드 ID 결정 (0 = 현재 스레드)
+fn resolve_thread_id(thread_id: u64) -> Result<u64, SyscallError> {
+    if thread_id != 0 {
+        return Ok(thread_id);
+    }
+    scheduler::current_thread()
+        .map(|thread| thread.lock().id)
+        .ok_or(SyscallError::NotFound)
+}
+
+/// 시스템 콜: SchedSetAffinity
+///
+/// 스레드를 지정한 CPU 집합에서만 실행되도록 고정합니다.
+///
+/// # Arguments
+/// * `thread_id` - 대상 스레드 ID (0이면 현재 스레드)
+/// * `cpu_mask` - 허용할 CPU 비트마스크 (비트 i = CPU i)
+///
+/// # Returns
+/// 성공 시 0
+pub fn sys_sched_set_affinity(thread_id: u64, cpu_mask: u64) -> SyscallResult {
+    let thread_id = resolve_thread_id(thread_id)?;
+    let mask = scheduler::affinity::CpuMask::from_bits(cpu_mask);
+    // 빈 마스크, 온라인이 아닌 CPU가 포함된 마스크
+    scheduler::affinity::validate(mask).map_err(|_| SyscallError::InvalidArgument)?;
+    // 마스크는 이미 검증했으므로 남은 실패는 스레드가 없는 경우
+    scheduler::set_thread_affinity(thread_id, mask).map_err(|_| SyscallError::NotFound)?;
+    Ok(0)
+}
+
+/// 시스템 콜: SchedGetAffinity
+///
+/// # Arguments
+/// * `thread_id` - 대상 스레드 ID (0이면 현재 스레드)
+///
+/// # Returns
+/// 스레드의 CPU 비트마스크
+pub fn sys_sched_get_affinity(thread_id: u64) -> SyscallResult {
+    let thread_id = resolve_thread_id(thread_id)?;
+    scheduler::thread_affinity(thread_id)
+        .map(|mask| mask.bits())
+        .ok_or(SyscallError::NotFound)
+}
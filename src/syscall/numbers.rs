@@ -42,6 +42,16 @@ pub enum SyscallNumber {
     /// 파라미터: 없음
     /// 반환값: 현재 프로세스/스레드 ID
     GetPid = 6,
+    
+    /// 스레드 CPU 친화도 설정
+    /// 파라미터: thread_id (u64, 0이면 현재 스레드), cpu_mask (u64, 비트 i = CPU i)
+    /// 반환값: 0 (성공)
+    SchedSetAffinity = 7,
+    
+    /// 스레드 CPU 친화도 조회
+    /// 파라미터: thread_id (u64, 0이면 현재 스레드)
+    /// 반환값: cpu_mask (u64)
+    SchedGetAffinity = 8,
 }
 
 impl SyscallNumber {
@@ -55,6 +65,8 @@ impl SyscallNumber {
             4 => Some(SyscallNumber::Sleep),
             5 => Some(SyscallNumber::GetTime),
             6 => Some(SyscallNumber::GetPid),
+            7 => Some(SyscallNumber::SchedSetAffinity),
+            8 => Some(SyscallNumber::SchedGetAffinity),
             _ => None,
         }
     }
@@ -66,5 +78,5 @@ impl SyscallNumber {
 }
 
 /// 시스템 콜 최대 번호
-pub const MAX_SYSCALL_NUMBER: u64 = 6;
+pub const MAX_SYSCALL_NUMBER: u64 = 8;
 
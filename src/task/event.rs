@@ -0,0 +1,117 @@
+//! 인터럽트에서 신호를 보낼 수 있는 이벤트
+//!
+//! `Event::signal`은 인터럽트 핸들러와 softirq에서 호출할 수 있으며,
+//! `Event::wait`를 `await`하고 있는 태스크를 깨웁니다. 신호는 한 번 기록되어
+//! 대기자가 아직 없더라도 다음 `wait`에서 소비되므로 웨이크업을 잃지 않습니다.
+
+use core::future::Future;
+use core::pin::Pin;
+use core::sync::atomic::{AtomicBool, Ordering};
+use core::task::{Context, Poll, Waker};
+use spin::Mutex;
+use x86_64::instructions::interrupts;
+
+use crate::interrupts::irq::{IRQ_VECTOR_BASE, NR_IRQ_VECTORS};
+
+/// IRQ 이벤트 수
+///
+/// IRQ 핸들러 인자(GSI 또는 MSI/MSI-X 벡터)가 모두 들어가도록 IRQ 벡터 범위의 끝까지 둡니다.
+const IRQ_COUNT: usize = IRQ_VECTOR_BASE as usize + NR_IRQ_VECTORS;
+
+/// 단일 대기자 이벤트
+pub struct Event {
+    signaled: AtomicBool,
+    waker: Mutex<Option<Waker>>,
+}
+
+impl Event {
+    /// 신호가 없는 이벤트 생성
+    pub const fn new() -> Self {
+        Self {
+            signaled: AtomicBool::new(false),
+            waker: Mutex::new(None),
+        }
+    }
+
+    /// 이벤트 신호 (인터럽트 컨텍스트에서 호출 가능)
+    pub fn signal(&self) {
+        self.signaled.store(true, Ordering::Release);
+        let waker = interrupts::without_interrupts(|| self.waker.lock().take());
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+
+    /// 신호를 기다리지 않고 소비
+    ///
+    /// # Returns
+    /// 신호가 있었으면 `true`
+    pub fn try_take(&self) -> bool {
+        self.signaled.swap(false, Ordering::AcqRel)
+    }
+
+    /// 보류 중인 신호 제거 (새 요청을 시작하기 전에 호출)
+    pub fn reset(&self) {
+        self.signaled.store(false, Ordering::Release);
+    }
+
+    /// 신호가 올 때까지 대기하는 퓨처
+    pub fn wait(&self) -> EventWait<'_> {
+        EventWait { event: self }
+    }
+}
+
+impl Default for Event {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// `Event::wait` 퓨처
+pub struct EventWait<'a> {
+    event: &'a Event,
+}
+
+impl Future for EventWait<'_> {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let event = self.event;
+        if event.try_take() {
+            return Poll::Ready(());
+        }
+
+        interrupts::without_interrupts(|| {
+            let mut slot = event.waker.lock();
+            match slot.as_ref() {
+                Some(w) if w.will_wake(cx.waker()) => {}
+                _ => *slot = Some(cx.waker().clone()),
+            }
+        });
+
+        // 웨이커 등록 전에 들어온 신호 재확인
+        if event.try_take() {
+            return Poll::Ready(());
+        }
+        Poll::Pending
+    }
+}
+
+/// IRQ별 이벤트
+static IRQ_EVENTS: [Event; IRQ_COUNT] = [const { Event::new() }; IRQ_COUNT];
+
+/// IRQ 발생 알림 (인터럽트 핸들러에서 호출)
+///
+/// `irq`는 핸들러가 받은 인자(GSI 또는 MSI/MSI-X 벡터)입니다.
+pub fn signal_irq(irq: u32) {
+    if let Some(event) = IRQ_EVENTS.get(irq as usize) {
+        event.signal();
+    }
+}
+
+/// 해당 IRQ가 발생할 때까지 대기
+///
+/// 한 IRQ에 대해 동시에 하나의 태스크만 기다릴 수 있습니다.
+pub fn wait_irq(irq: u32) -> Option<EventWait<'static>> {
+    IRQ_EVENTS.get(irq as usize).map(|event| event.wait())
+}
@@ -0,0 +1,205 @@
+//! 태스크 실행기
+//!
+//! 태스크는 웨이커가 깨울 때만 폴링됩니다. 준비 큐는 고정 크기 링 버퍼라
+//! 인터럽트 핸들러에서 깨우더라도 힙 할당이 일어나지 않습니다.
+
+use alloc::boxed::Box;
+use alloc::collections::BTreeMap;
+use alloc::sync::Arc;
+use alloc::task::Wake;
+use core::future::Future;
+use core::pin::Pin;
+use core::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use core::task::{Context, Poll, Waker};
+use spin::Mutex;
+use x86_64::instructions::interrupts;
+
+/// 준비 큐 크기
+const READY_QUEUE_SIZE: usize = 256;
+
+/// 태스크 식별자
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct TaskId(u64);
+
+impl TaskId {
+    fn allocate() -> Self {
+        TaskId(NEXT_TASK_ID.fetch_add(1, Ordering::Relaxed))
+    }
+
+    /// 원시 ID 값
+    pub fn as_u64(&self) -> u64 {
+        self.0
+    }
+}
+
+static NEXT_TASK_ID: AtomicU64 = AtomicU64::new(1);
+
+/// 실행 중인 태스크
+struct Task {
+    name: &'static str,
+    future: Pin<Box<dyn Future<Output = ()> + Send>>,
+    waker: Arc<TaskWaker>,
+}
+
+/// 태스크 웨이커
+struct TaskWaker {
+    id: TaskId,
+    /// 이미 준비 큐에 들어가 있는지 (중복 삽입 방지)
+    queued: AtomicBool,
+}
+
+impl TaskWaker {
+    fn schedule(&self) {
+        if !self.queued.swap(true, Ordering::AcqRel) {
+            READY.push(self.id);
+        }
+    }
+}
+
+impl Wake for TaskWaker {
+    fn wake(self: Arc<Self>) {
+        self.schedule();
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        self.schedule();
+    }
+}
+
+/// 고정 크기 준비 큐
+struct ReadyQueue {
+    inner: Mutex<RingBuffer>,
+    /// 큐가 넘쳐 일부 웨이크업을 잃었는지 여부 (다음 실행에서 모든 태스크 폴링)
+    overflow: AtomicBool,
+}
+
+struct RingBuffer {
+    ids: [u64; READY_QUEUE_SIZE],
+    head: usize,
+    len: usize,
+}
+
+impl ReadyQueue {
+    const fn new() -> Self {
+        Self {
+            inner: Mutex::new(RingBuffer { ids: [0; READY_QUEUE_SIZE], head: 0, len: 0 }),
+            overflow: AtomicBool::new(false),
+        }
+    }
+
+    fn push(&self, id: TaskId) {
+        interrupts::without_interrupts(|| {
+            let mut q = self.inner.lock();
+            if q.len == READY_QUEUE_SIZE {
+                self.overflow.store(true, Ordering::Release);
+                return;
+            }
+            let tail = (q.head + q.len) % READY_QUEUE_SIZE;
+            q.ids[tail] = id.0;
+            q.len += 1;
+        });
+    }
+
+    fn pop(&self) -> Option<TaskId> {
+        interrupts::without_interrupts(|| {
+            let mut q = self.inner.lock();
+            if q.len == 0 {
+                return None;
+            }
+            let id = q.ids[q.head];
+            q.head = (q.head + 1) % READY_QUEUE_SIZE;
+            q.len -= 1;
+            Some(TaskId(id))
+        })
+    }
+
+    fn is_empty(&self) -> bool {
+        interrupts::without_interrupts(|| self.inner.lock().len == 0)
+            && !self.overflow.load(Ordering::Acquire)
+    }
+}
+
+/// 실행 대기 중인 태스크 (폴링 중인 태스크는 잠시 빠져 있음)
+static TASKS: Mutex<BTreeMap<TaskId, Task>> = Mutex::new(BTreeMap::new());
+static READY: ReadyQueue = ReadyQueue::new();
+
+/// 새 태스크 생성
+///
+/// 태스크는 다음 `run_ready` 호출에서 처음 폴링됩니다.
+pub fn spawn<F>(name: &'static str, future: F) -> TaskId
+where
+    F: Future<Output = ()> + Send + 'static,
+{
+    let id = TaskId::allocate();
+    let waker = Arc::new(TaskWaker { id, queued: AtomicBool::new(false) });
+    let task = Task { name, future: Box::pin(future), waker: waker.clone() };
+    interrupts::without_interrupts(|| {
+        TASKS.lock().insert(id, task);
+    });
+    waker.schedule();
+    crate::log_debug!("Task {} spawned (id {})", name, id.0);
+    id
+}
+
+/// 실행 가능한 태스크가 있는지 확인
+///
+/// 유휴 진입 전에 확인해 웨이크업을 놓치지 않도록 합니다.
+pub fn has_ready() -> bool {
+    !READY.is_empty()
+}
+
+/// 생성된 태스크 수
+pub fn task_count() -> usize {
+    interrupts::without_interrupts(|| TASKS.lock().len())
+}
+
+/// 준비된 태스크를 모두 폴링
+///
+/// 스레드 컨텍스트(메인 루프)에서 호출합니다. 폴링 중 다시 깨워진 태스크는
+/// 다음 호출에서 실행되므로, 한 번의 호출은 항상 유한한 시간 안에 끝납니다.
+///
+/// # Returns
+/// 폴링한 태스크 수
+pub fn run_ready() -> usize {
+    if READY.overflow.swap(false, Ordering::AcqRel) {
+        // 잃어버린 웨이크업 복구: 모든 태스크를 준비 상태로
+        let wakers: alloc::vec::Vec<Arc<TaskWaker>> = interrupts::without_interrupts(|| {
+            TASKS.lock().values().map(|t| t.waker.clone()).collect()
+        });
+        for waker in wakers {
+            waker.queued.store(false, Ordering::Release);
+            waker.schedule();
+        }
+    }
+
+    let budget = interrupts::without_interrupts(|| READY.inner.lock().len);
+    let mut polled = 0;
+    for _ in 0..budget {
+        let id = match READY.pop() {
+            Some(id) => id,
+            None => break,
+        };
+
+        // 폴링 중 spawn이 가능하도록 맵에서 꺼낸 뒤 락 없이 폴링
+        let mut task = match interrupts::without_interrupts(|| TASKS.lock().remove(&id)) {
+            Some(task) => task,
+            None => continue, // 이미 완료된 태스크
+        };
+        task.waker.queued.store(false, Ordering::Release);
+
+        let waker = Waker::from(task.waker.clone());
+        let mut cx = Context::from_waker(&waker);
+        polled += 1;
+        match task.future.as_mut().poll(&mut cx) {
+            Poll::Ready(()) => {
+                crate::log_debug!("Task {} finished (id {})", task.name, id.0);
+            }
+            Poll::Pending => {
+                interrupts::without_interrupts(|| {
+                    TASKS.lock().insert(id, task);
+                });
+            }
+        }
+    }
+    polled
+}
@@ -0,0 +1,47 @@
+//! 커널 비동기 태스크
+//!
+//! 드라이버 상태 머신을 `async fn`으로 작성할 수 있도록 no_std 실행기를 제공합니다.
+//! 폴링 루프 대신 완료 이벤트(인터럽트, 타이머 만료)를 `await`하면, 이벤트가 없는 동안
+//! 태스크는 실행되지 않고 CPU는 유휴 상태로 남습니다.
+//!
+//! - [`executor`]: 태스크 생성과 실행 (`spawn`, `run_ready`)
+//! - [`event`]: 인터럽트 핸들러에서 신호를 보낼 수 있는 이벤트, IRQ별 이벤트
+//! - [`timer`]: hrtimer 기반 `sleep`과 `with_timeout`
+//!
+//! 실행기는 메인 루프(스레드 컨텍스트)에서 `run_ready`로 구동되며,
+//! 웨이커는 인터럽트/softirq 컨텍스트에서도 안전하게 호출할 수 있습니다.
+
+pub mod event;
+pub mod executor;
+pub mod timer;
+
+pub use event::Event;
+pub use executor::{has_ready, run_ready, spawn, TaskId};
+pub use timer::{sleep, sleep_ms, with_timeout, TimedOut};
+
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+/// 다른 태스크에게 한 번 실행 기회를 양보
+pub fn yield_now() -> YieldNow {
+    YieldNow { yielded: false }
+}
+
+/// `yield_now` 퓨처
+pub struct YieldNow {
+    yielded: bool,
+}
+
+impl Future for YieldNow {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        if self.yielded {
+            return Poll::Ready(());
+        }
+        self.yielded = true;
+        cx.waker().wake_by_ref();
+        Poll::Pending
+    }
+}
@@ -0,0 +1,126 @@
+//! 타이머 퓨처
+//!
+//! hrtimer 만료 시 태스크를 깨우는 `sleep`과, 다른 퓨처에 제한 시간을 거는
+//! `with_timeout`을 제공합니다. 대기 중에는 타이머 외에 wakeup이 없으므로
+//! tickless 유휴 상태가 유지됩니다.
+
+use alloc::sync::Arc;
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use super::event::Event;
+use crate::time::{hrtimer, TimerId, TimerRestart, NSEC_PER_MSEC};
+
+/// 제한 시간 초과 오류
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimedOut;
+
+/// `ns` 나노초 동안 대기하는 퓨처
+pub fn sleep(ns: u64) -> Sleep {
+    Sleep {
+        deadline_ns: crate::time::now_ns().saturating_add(ns),
+        timer: None,
+        event: Arc::new(Event::new()),
+    }
+}
+
+/// `ms` 밀리초 동안 대기하는 퓨처
+pub fn sleep_ms(ms: u64) -> Sleep {
+    sleep(ms.saturating_mul(NSEC_PER_MSEC))
+}
+
+/// `sleep` 퓨처
+///
+/// 첫 폴링에서 hrtimer를 시작하며, 완료 전에 drop되면 타이머를 취소합니다.
+pub struct Sleep {
+    deadline_ns: u64,
+    timer: Option<TimerId>,
+    /// 타이머 콜백과 공유 (콜백에는 `Arc::into_raw`로 전달)
+    event: Arc<Event>,
+}
+
+impl Sleep {
+    /// 만료 시각 (ns)
+    pub fn deadline_ns(&self) -> u64 {
+        self.deadline_ns
+    }
+}
+
+impl Future for Sleep {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        if crate::time::now_ns() >= self.deadline_ns {
+            return Poll::Ready(());
+        }
+
+        if self.timer.is_none() {
+            let data = Arc::into_raw(self.event.clone()) as usize;
+            let id = hrtimer::start(
+                self.deadline_ns,
+                crate::time::default_slack_ns(),
+                sleep_timer_expired,
+                data,
+            );
+            self.timer = Some(id);
+        }
+
+        match Pin::new(&mut self.event.wait()).poll(cx) {
+            Poll::Ready(()) => {
+                self.timer = None;
+                Poll::Ready(())
+            }
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+impl Drop for Sleep {
+    fn drop(&mut self) {
+        if let Some(id) = self.timer.take() {
+            if hrtimer::cancel(id) {
+                // 콜백이 실행되지 않았으므로 넘겨준 참조를 회수
+                // SAFETY: `poll`에서 `Arc::into_raw`로 만든 포인터이며 아직 소비되지 않음
+                unsafe { drop(Arc::from_raw(Arc::as_ptr(&self.event))) };
+            }
+        }
+    }
+}
+
+fn sleep_timer_expired(data: usize) -> TimerRestart {
+    // SAFETY: `Sleep::poll`에서 `Arc::into_raw`로 넘긴 참조이며, 취소되지 않은 타이머만 실행됨
+    let event = unsafe { Arc::from_raw(data as *const Event) };
+    event.signal();
+    TimerRestart::NoRestart
+}
+
+/// 퓨처에 제한 시간 적용
+///
+/// `ns` 안에 완료되지 않으면 `Err(TimedOut)`을 반환하고 퓨처를 drop합니다.
+pub fn with_timeout<F: Future>(ns: u64, future: F) -> Timeout<F> {
+    Timeout { future, sleep: sleep(ns) }
+}
+
+/// `with_timeout` 퓨처
+pub struct Timeout<F> {
+    future: F,
+    sleep: Sleep,
+}
+
+impl<F: Future> Future for Timeout<F> {
+    type Output = Result<F::Output, TimedOut>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        // SAFETY: `future`는 이동하지 않으며 `sleep`은 Unpin
+        let this = unsafe { self.get_unchecked_mut() };
+        let future = unsafe { Pin::new_unchecked(&mut this.future) };
+        if let Poll::Ready(output) = future.poll(cx) {
+            return Poll::Ready(Ok(output));
+        }
+        match Pin::new(&mut this.sleep).poll(cx) {
+            Poll::Ready(()) => Poll::Ready(Err(TimedOut)),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
@@ -0,0 +1,251 @@
+//! 클럭소스 (clocksource) 프레임워크
+//!
+//! 자유 증가 카운터(TSC, HPET, PIT 틱)를 등록하고, 그 중 rating이 가장 높은 것을
+//! 단조 증가 나노초 시계의 기준으로 사용합니다.
+//!
+//! 시각은 `base_ns + (read() - base_cycles) * 1e9 / freq_hz`로 계산되며,
+//! 클럭소스가 바뀌거나 `update`가 호출될 때마다 기준점을 다시 잡습니다.
+//! 카운터가 32비트인 HPET처럼 래핑되는 클럭소스도 주기적인 `update`로 처리됩니다.
+//!
+//! 기준점은 시퀀스 락으로 보호되므로 인터럽트 핸들러를 포함한 어디서든
+//! 락 없이 `now_ns`를 호출할 수 있습니다.
+
+use alloc::vec::Vec;
+use core::sync::atomic::{AtomicU32, AtomicU64, AtomicUsize, Ordering};
+use spin::Mutex;
+use x86_64::instructions::interrupts;
+
+use super::NSEC_PER_SEC;
+
+/// 클럭소스 rating 기준
+///
+/// - 1..99: 부팅 초기용, 해상도 낮음 (PIT 틱)
+/// - 100..199: 동작은 하지만 권장하지 않음 (non-invariant TSC)
+/// - 200..299: 정확하고 안정적 (HPET)
+/// - 300..: 이상적인 클럭소스 (invariant TSC)
+pub mod rating {
+    pub const JIFFIES: u32 = 1;
+    pub const TSC_UNSTABLE: u32 = 100;
+    pub const HPET: u32 = 250;
+    pub const TSC: u32 = 300;
+}
+
+/// 클럭소스 설명자
+#[derive(Debug, Clone, Copy)]
+pub struct ClockSource {
+    /// 이름 (예: "tsc", "hpet")
+    pub name: &'static str,
+    /// 선택 우선순위 (높을수록 선호)
+    pub rating: u32,
+    /// 카운터 읽기 함수
+    pub read: fn() -> u64,
+    /// 카운터 유효 비트 마스크 (32비트 카운터면 `u32::MAX`)
+    pub mask: u64,
+    /// 카운터 주파수 (Hz)
+    pub freq_hz: u64,
+}
+
+/// 등록된 클럭소스 목록
+static SOURCES: Mutex<Vec<ClockSource>> = Mutex::new(Vec::new());
+
+/// 기준점 갱신을 직렬화하는 락 (인터럽트 비활성화 상태에서만 잡음)
+static WRITER: Mutex<()> = Mutex::new(());
+
+/// 시퀀스 카운터 (홀수 = 갱신 중)
+static SEQ: AtomicU64 = AtomicU64::new(0);
+/// 현재 클럭소스의 읽기 함수 포인터 (0 = 아직 없음, PIT 틱 사용)
+static READ_FN: AtomicUsize = AtomicUsize::new(0);
+static MASK: AtomicU64 = AtomicU64::new(u64::MAX);
+static FREQ_HZ: AtomicU64 = AtomicU64::new(0);
+static BASE_CYCLES: AtomicU64 = AtomicU64::new(0);
+static BASE_NS: AtomicU64 = AtomicU64::new(0);
+/// 현재 클럭소스의 rating과 `SOURCES` 인덱스
+static CURRENT_RATING: AtomicU32 = AtomicU32::new(0);
+static CURRENT_INDEX: AtomicUsize = AtomicUsize::new(usize::MAX);
+
+/// 지금까지 반환한 가장 큰 시각 (클럭소스 전환 시에도 역행 방지)
+static LAST_NS: AtomicU64 = AtomicU64::new(0);
+
+/// 클럭소스 초기화
+///
+/// PIT 틱 클럭소스를 등록한 뒤 HPET과 TSC를 탐지해 등록합니다.
+/// rating이 가장 높은 클럭소스가 자동으로 선택됩니다.
+///
+/// # Safety
+/// 메모리 관리와 PIT 초기화 후, LAPIC 타이머 초기화 전에 한 번 호출되어야 합니다.
+pub unsafe fn init() {
+    register(ClockSource {
+        name: "jiffies",
+        rating: rating::JIFFIES,
+        read: crate::drivers::timer::get_ticks,
+        mask: u64::MAX,
+        freq_hz: crate::drivers::timer::TICKS_PER_SECOND as u64,
+    });
+
+    if let Err(e) = crate::drivers::hpet::init() {
+        crate::log_info!("HPET not available: {}", e);
+    }
+
+    if let Err(e) = super::tsc::init() {
+        crate::log_info!("TSC clocksource not available: {}", e);
+    }
+
+    crate::log_info!("Clocksource: using {}", current_name().unwrap_or("none"));
+}
+
+/// 클럭소스 등록
+///
+/// 현재 클럭소스보다 rating이 높으면 즉시 전환합니다.
+pub fn register(cs: ClockSource) {
+    if cs.freq_hz == 0 {
+        crate::log_warn!("Clocksource {}: zero frequency, ignored", cs.name);
+        return;
+    }
+
+    let index = interrupts::without_interrupts(|| {
+        let mut sources = SOURCES.lock();
+        sources.push(cs);
+        sources.len() - 1
+    });
+    crate::log_info!("Clocksource {} registered ({} Hz, rating {})", cs.name, cs.freq_hz, cs.rating);
+
+    if CURRENT_INDEX.load(Ordering::Acquire) == usize::MAX
+        || cs.rating > CURRENT_RATING.load(Ordering::Acquire)
+    {
+        switch_to(index, cs);
+    }
+}
+
+/// 이름으로 클럭소스 강제 선택
+pub fn select(name: &str) -> Result<(), &'static str> {
+    let found = interrupts::without_interrupts(|| {
+        SOURCES.lock().iter().enumerate().find(|(_, cs)| cs.name == name).map(|(i, cs)| (i, *cs))
+    });
+    let (index, cs) = found.ok_or("clocksource not found")?;
+    switch_to(index, cs);
+    Ok(())
+}
+
+/// 현재 클럭소스 이름
+pub fn current_name() -> Option<&'static str> {
+    let index = CURRENT_INDEX.load(Ordering::Acquire);
+    interrupts::without_interrupts(|| SOURCES.lock().get(index).map(|cs| cs.name))
+}
+
+/// 현재 클럭소스 주파수 (Hz)
+pub fn current_freq_hz() -> u64 {
+    FREQ_HZ.load(Ordering::Acquire)
+}
+
+/// 등록된 클럭소스 목록
+pub fn list() -> Vec<ClockSource> {
+    interrupts::without_interrupts(|| SOURCES.lock().clone())
+}
+
+/// 부팅 이후 경과 시간 (ns, 단조 증가)
+pub fn now_ns() -> u64 {
+    let ns = loop {
+        let seq = SEQ.load(Ordering::Acquire);
+        if seq & 1 != 0 {
+            core::hint::spin_loop();
+            continue;
+        }
+
+        let raw = READ_FN.load(Ordering::Acquire);
+        let ns = if raw == 0 {
+            // 클럭소스 초기화 전: PIT 틱(ms) 사용
+            crate::drivers::timer::get_ticks() * (NSEC_PER_SEC / crate::drivers::timer::TICKS_PER_SECOND as u64)
+        } else {
+            // SAFETY: READ_FN에는 `fn() -> u64` 포인터만 저장됨
+            let read: fn() -> u64 = unsafe { core::mem::transmute(raw) };
+            let delta = read().wrapping_sub(BASE_CYCLES.load(Ordering::Acquire)) & MASK.load(Ordering::Acquire);
+            BASE_NS.load(Ordering::Acquire) + cycles_to_ns(delta, FREQ_HZ.load(Ordering::Acquire))
+        };
+
+        if SEQ.load(Ordering::Acquire) == seq {
+            break ns;
+        }
+    };
+
+    let last = LAST_NS.fetch_max(ns, Ordering::AcqRel);
+    ns.max(last)
+}
+
+/// 기준점을 현재 카운터 값으로 옮김
+///
+/// 래핑되는 카운터(32비트 HPET 등)가 한 바퀴 돌기 전에 주기적으로 호출되어야 합니다
+/// (타이머 하우스키핑에서 1초마다 호출).
+pub fn update() {
+    interrupts::without_interrupts(|| {
+        let _guard = WRITER.lock();
+        let raw = READ_FN.load(Ordering::Acquire);
+        if raw == 0 {
+            return;
+        }
+        // SAFETY: READ_FN에는 `fn() -> u64` 포인터만 저장됨
+        let read: fn() -> u64 = unsafe { core::mem::transmute(raw) };
+        let cycles = read();
+        let base_cycles = BASE_CYCLES.load(Ordering::Acquire);
+        let delta = cycles.wrapping_sub(base_cycles) & MASK.load(Ordering::Acquire);
+        let now = BASE_NS.load(Ordering::Acquire) + cycles_to_ns(delta, FREQ_HZ.load(Ordering::Acquire));
+        write_begin();
+        BASE_CYCLES.store(cycles, Ordering::Release);
+        BASE_NS.store(now, Ordering::Release);
+        write_end();
+    });
+}
+
+/// 카운터 사이클 수를 나노초로 변환
+pub fn cycles_to_ns(cycles: u64, freq_hz: u64) -> u64 {
+    if freq_hz == 0 {
+        return 0;
+    }
+    (cycles as u128 * NSEC_PER_SEC as u128 / freq_hz as u128) as u64
+}
+
+fn switch_to(index: usize, cs: ClockSource) {
+    interrupts::without_interrupts(|| {
+        let _guard = WRITER.lock();
+        // 이전 클럭소스 기준의 현재 시각에서 이어서 시작
+        let now = now_ns();
+        write_begin();
+        READ_FN.store(cs.read as usize, Ordering::Release);
+        MASK.store(cs.mask, Ordering::Release);
+        FREQ_HZ.store(cs.freq_hz, Ordering::Release);
+        BASE_CYCLES.store((cs.read)(), Ordering::Release);
+        BASE_NS.store(now, Ordering::Release);
+        CURRENT_RATING.store(cs.rating, Ordering::Release);
+        CURRENT_INDEX.store(index, Ordering::Release);
+        write_end();
+    });
+}
+
+fn write_begin() {
+    SEQ.fetch_add(1, Ordering::AcqRel);
+}
+
+fn write_end() {
+    SEQ.fetch_add(1, Ordering::AcqRel);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test_case]
+    fn test_cycles_to_ns() {
+        assert_eq!(cycles_to_ns(1000, 1000), NSEC_PER_SEC);
+        // HPET 14.318180 MHz
+        assert_eq!(cycles_to_ns(14_318_180, 14_318_180), NSEC_PER_SEC);
+        // 3 GHz TSC, 3 사이클 = 1ns
+        assert_eq!(cycles_to_ns(3, 3_000_000_000), 1);
+        assert_eq!(cycles_to_ns(5, 0), 0);
+    }
+
+    #[test_case]
+    fn test_now_ns_monotonic() {
+        let a = now_ns();
+        let b = now_ns();
+        assert!(b >= a);
+    }
+}
@@ -0,0 +1,248 @@
+//! 고해상도 타이머 (hrtimer)
+//!
+//! 나노초 단위 절대 만료 시각을 갖는 타이머를 관리합니다.
+//! 각 타이머는 `[soft, hard]` 만료 구간을 가집니다 (`hard = soft + slack`).
+//! 다음 인터럽트는 가장 이른 hard 만료 시각에 프로그래밍되고, 그 시점에
+//! soft 만료가 지난 모든 타이머가 함께 실행되어 wakeup 횟수를 줄입니다.
+
+use alloc::collections::BTreeMap;
+use alloc::sync::Arc;
+use core::sync::atomic::{AtomicBool, Ordering};
+use spin::Mutex;
+use x86_64::instructions::interrupts;
+
+use super::{TimerCallback, TimerId, TimerRestart};
+use crate::scheduler::thread::{Thread, ThreadState};
+
+/// 등록된 hrtimer
+#[derive(Clone, Copy)]
+struct HrTimer {
+    /// 늦어도 이 시각까지는 실행되어야 함 (ns)
+    hard_expires_ns: u64,
+    /// 허용된 지연 (ns)
+    slack_ns: u64,
+    callback: TimerCallback,
+    data: usize,
+}
+
+/// hrtimer 큐
+///
+/// (soft 만료 시각, ID) 순으로 정렬됩니다.
+struct HrTimerQueue {
+    timers: BTreeMap<(u64, TimerId), HrTimer>,
+    /// ID → soft 만료 시각 (취소용)
+    index: BTreeMap<TimerId, u64>,
+}
+
+impl HrTimerQueue {
+    const fn new() -> Self {
+        Self {
+            timers: BTreeMap::new(),
+            index: BTreeMap::new(),
+        }
+    }
+
+    fn insert(&mut self, id: TimerId, soft_ns: u64, timer: HrTimer) {
+        self.timers.insert((soft_ns, id), timer);
+        self.index.insert(id, soft_ns);
+    }
+
+    fn remove(&mut self, id: TimerId) -> bool {
+        match self.index.remove(&id) {
+            Some(soft) => self.timers.remove(&(soft, id)).is_some(),
+            None => false,
+        }
+    }
+
+    /// 가장 이른 hard 만료 시각
+    fn next_hard_expiry(&self) -> Option<u64> {
+        let mut best: Option<u64> = None;
+        for (&(soft, _), timer) in self.timers.iter() {
+            // soft <= hard 이므로, soft가 현재 최소 hard보다 크면 더 볼 필요 없음
+            if let Some(b) = best {
+                if soft >= b {
+                    break;
+                }
+            }
+            best = Some(best.map_or(timer.hard_expires_ns, |b| b.min(timer.hard_expires_ns)));
+        }
+        best
+    }
+
+    fn first_soft_expiry(&self) -> Option<u64> {
+        self.timers.keys().next().map(|&(soft, _)| soft)
+    }
+}
+
+static QUEUE: Mutex<HrTimerQueue> = Mutex::new(HrTimerQueue::new());
+
+/// 절대 시각 `expires_ns`에 만료되는 타이머 시작
+///
+/// # Arguments
+/// * `expires_ns` - 만료 시각 (`time::now_ns()` 기준)
+/// * `slack_ns` - 허용 지연 (다른 타이머와 묶어서 처리할 수 있는 범위)
+/// * `callback` - 타이머 softirq에서 호출될 함수
+/// * `data` - 콜백 인자
+pub fn start(expires_ns: u64, slack_ns: u64, callback: TimerCallback, data: usize) -> TimerId {
+    let id = TimerId::allocate();
+    enqueue(id, expires_ns, slack_ns, callback, data);
+    id
+}
+
+/// 지금으로부터 `delta_ns` 후 만료되는 타이머 시작 (기본 slack 사용)
+pub fn start_relative(delta_ns: u64, callback: TimerCallback, data: usize) -> TimerId {
+    let expires = super::now_ns().saturating_add(delta_ns);
+    start(expires, super::default_slack_ns(), callback, data)
+}
+
+/// `period_ns` 주기로 반복되는 타이머 시작
+///
+/// 콜백이 `TimerRestart::NoRestart`를 반환하면 중단됩니다.
+/// 주기 타이머의 slack은 주기의 1/8 또는 기본 slack 중 큰 값입니다.
+pub fn start_periodic(period_ns: u64, callback: TimerCallback, data: usize) -> TimerId {
+    let expires = super::now_ns().saturating_add(period_ns);
+    let slack = (period_ns / 8).max(super::default_slack_ns());
+    start(expires, slack, callback, data)
+}
+
+/// 타이머 취소
+///
+/// # Returns
+/// 대기 중인 타이머를 제거했으면 `true`, 이미 만료되었거나 없으면 `false`
+pub fn cancel(id: TimerId) -> bool {
+    interrupts::without_interrupts(|| QUEUE.lock().remove(id))
+}
+
+/// 가장 이른 hard 만료 시각 (ns)
+pub fn next_expiry_ns() -> Option<u64> {
+    interrupts::without_interrupts(|| QUEUE.lock().next_hard_expiry())
+}
+
+/// `now_ns` 시점에 만료된 타이머가 있는지 확인
+pub fn has_expired(now_ns: u64) -> bool {
+    interrupts::without_interrupts(|| {
+        QUEUE.lock().first_soft_expiry().map_or(false, |soft| soft <= now_ns)
+    })
+}
+
+/// soft 만료 시각이 지난 타이머 실행 (타이머 softirq에서 호출)
+pub fn run_expired(now_ns: u64) {
+    loop {
+        // 락을 잡은 채로 콜백을 실행하지 않도록 하나씩 꺼냄
+        let next = interrupts::without_interrupts(|| {
+            let mut queue = QUEUE.lock();
+            let key = match queue.timers.keys().next() {
+                Some(&(soft, id)) if soft <= now_ns => (soft, id),
+                _ => return None,
+            };
+            let timer = queue.timers.remove(&key)?;
+            queue.index.remove(&key.1);
+            Some((key.1, timer))
+        });
+
+        let (id, timer) = match next {
+            Some(entry) => entry,
+            None => return,
+        };
+
+        if let TimerRestart::Restart { period_ns } = (timer.callback)(timer.data) {
+            let expires = now_ns.saturating_add(period_ns.max(1));
+            interrupts::without_interrupts(|| {
+                QUEUE.lock().insert(id, expires, HrTimer {
+                    hard_expires_ns: expires.saturating_add(timer.slack_ns),
+                    ..timer
+                });
+            });
+        }
+    }
+}
+
+/// `ns` 나노초 동안 대기
+///
+/// 현재 스레드가 있으면 Blocked로 바꿔 실행 큐에서 빼고, hrtimer 콜백이 다시
+/// 준비 상태로 돌려놓을 때까지 CPU를 유휴 상태로 둡니다 (tickless 모드에서는 그 사이
+/// 틱이 멈춤). 스케줄러가 시작되기 전에는 완료 플래그를 기다리고, 타이머 softirq가
+/// 등록되기 전(`time::init` 이전)에는 시계를 직접 확인하며 대기합니다.
+pub fn sleep_ns(ns: u64) {
+    if ns == 0 {
+        return;
+    }
+
+    let deadline = super::now_ns().saturating_add(ns);
+
+    // 콜백을 실행할 타이머 softirq가 아직 없음
+    if !super::is_initialized() {
+        while super::now_ns() < deadline {
+            core::hint::spin_loop();
+        }
+        return;
+    }
+
+    match crate::scheduler::current_thread() {
+        Some(thread) => park_until(thread, deadline),
+        None => wait_until(deadline),
+    }
+}
+
+/// 현재 스레드를 `deadline`까지 블로킹
+fn park_until(thread: Arc<Mutex<Thread>>, deadline: u64) {
+    let tid = thread.lock().id;
+    // 콜백이 블로킹보다 먼저 실행되면 깨울 대상이 없으므로 블로킹한 뒤 타이머를 시작
+    crate::scheduler::block_thread(tid);
+    // 콜백이 참조를 넘겨받아 해제하므로 취소하지 않음
+    start(deadline, super::default_slack_ns(), wake_thread, Arc::into_raw(thread.clone()) as usize);
+
+    loop {
+        interrupts::disable();
+        if thread.lock().state != ThreadState::Blocked {
+            interrupts::enable();
+            break;
+        }
+        crate::drivers::timer::idle_wait();
+    }
+}
+
+/// 스케줄러 시작 전: 완료 플래그가 설정될 때까지 유휴 대기
+fn wait_until(deadline: u64) {
+    let done = AtomicBool::new(false);
+    // `done`은 이 함수가 반환하기 전까지 유효하며, 반환 전 타이머가 실행되었거나
+    // 취소되었음을 보장하므로 콜백이 dangling 포인터를 보지 않습니다.
+    let id = start(deadline, super::default_slack_ns(), wake_sleeper, &done as *const AtomicBool as usize);
+
+    loop {
+        interrupts::disable();
+        if done.load(Ordering::Acquire) {
+            interrupts::enable();
+            break;
+        }
+        crate::drivers::timer::idle_wait();
+    }
+    cancel(id);
+}
+
+/// `park_until` 완료 콜백: 블로킹된 스레드를 실행 큐로 돌려놓음
+fn wake_thread(data: usize) -> TimerRestart {
+    // SAFETY: `park_until`이 `Arc::into_raw`로 넘긴 참조이며 콜백은 한 번만 실행됨
+    let thread = unsafe { Arc::from_raw(data as *const Mutex<Thread>) };
+    crate::scheduler::unblock_thread(thread);
+    TimerRestart::NoRestart
+}
+
+/// `wait_until` 완료 콜백
+fn wake_sleeper(data: usize) -> TimerRestart {
+    let done = unsafe { &*(data as *const AtomicBool) };
+    done.store(true, Ordering::Release);
+    TimerRestart::NoRestart
+}
+
+fn enqueue(id: TimerId, expires_ns: u64, slack_ns: u64, callback: TimerCallback, data: usize) {
+    let timer = HrTimer {
+        hard_expires_ns: expires_ns.saturating_add(slack_ns),
+        slack_ns,
+        callback,
+        data,
+    };
+    interrupts::without_interrupts(|| {
+        QUEUE.lock().insert(id, expires_ns, timer);
+    });
+}
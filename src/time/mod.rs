@@ -0,0 +1,124 @@
+//! 시간 및 타이머 서브시스템
+//!
+//! 단조 증가 나노초 시계와 두 종류의 커널 타이머를 제공합니다.
+//!
+//! - [`clocksource`]: TSC/HPET/PIT 중 가장 좋은 카운터를 고르는 단조 나노초 시계
+//! - [`wallclock`]: RTC 기반 벽시계 시간과 시간대
+//! - [`hrtimer`]: 나노초 정밀도의 고해상도 타이머 (sleep, 장치 폴링 주기 등)
+//! - [`wheel`]: 밀리초 단위의 계층형 타이밍 휠 (재전송/워치독 같은 타임아웃)
+//!
+//! 타이머 콜백은 타이머 softirq 컨텍스트(인터럽트 허용 상태)에서 실행됩니다.
+//! 콜백은 짧아야 하며, 스레드 컨텍스트와 공유하는 락은 스레드 쪽에서
+//! `without_interrupts`로 보호해야 합니다.
+
+pub mod clocksource;
+pub mod hrtimer;
+pub mod tsc;
+pub mod wallclock;
+pub mod wheel;
+
+use core::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use crate::kernel::softirq::{self, SoftIrq};
+
+/// 나노초 단위 상수
+pub const NSEC_PER_USEC: u64 = 1_000;
+pub const NSEC_PER_MSEC: u64 = 1_000_000;
+pub const NSEC_PER_SEC: u64 = 1_000_000_000;
+
+/// 타이머 식별자
+///
+/// hrtimer와 타이밍 휠이 같은 카운터를 공유하므로 ID는 전역적으로 유일합니다.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct TimerId(u64);
+
+impl TimerId {
+    /// 새 타이머 ID 할당
+    pub(crate) fn allocate() -> Self {
+        TimerId(NEXT_TIMER_ID.fetch_add(1, Ordering::Relaxed))
+    }
+
+    /// 원시 ID 값
+    pub fn as_u64(&self) -> u64 {
+        self.0
+    }
+}
+
+static NEXT_TIMER_ID: AtomicU64 = AtomicU64::new(1);
+
+/// 타이머 콜백 반환값
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimerRestart {
+    /// 타이머 종료
+    NoRestart,
+    /// 지정한 주기(ns) 후 다시 실행 (같은 ID 유지)
+    Restart { period_ns: u64 },
+}
+
+/// 타이머 콜백
+///
+/// 인자는 타이머 등록 시 전달한 `data` 값입니다.
+pub type TimerCallback = fn(data: usize) -> TimerRestart;
+
+/// 기본 타이머 slack (ns)
+///
+/// hrtimer는 `[expires, expires + slack]` 구간 안에서 만료되며,
+/// 같은 구간에 들어오는 타이머들은 한 번의 wakeup으로 묶여 처리됩니다.
+static DEFAULT_SLACK_NS: AtomicU64 = AtomicU64::new(50 * NSEC_PER_USEC);
+
+/// 타이머 softirq 핸들러가 등록되었는지 여부
+static INITIALIZED: AtomicBool = AtomicBool::new(false);
+
+/// 타이머 콜백이 실행될 수 있는지 (`init` 이후인지) 여부
+pub fn is_initialized() -> bool {
+    INITIALIZED.load(Ordering::Acquire)
+}
+
+/// 타이머 서브시스템 초기화
+///
+/// 타이머 softirq 핸들러를 등록합니다. 타이머 드라이버 초기화 후에 호출해야 합니다.
+pub fn init() {
+    softirq::register(SoftIrq::Timer, run_timer_softirq);
+    INITIALIZED.store(true, Ordering::Release);
+    crate::log_info!("Timer subsystem initialized (default slack: {} ns)", default_slack_ns());
+}
+
+/// 부팅 이후 경과 시간 (ns, 단조 증가)
+pub fn now_ns() -> u64 {
+    clocksource::now_ns()
+}
+
+/// 기본 타이머 slack (ns)
+pub fn default_slack_ns() -> u64 {
+    DEFAULT_SLACK_NS.load(Ordering::Relaxed)
+}
+
+/// 기본 타이머 slack 설정 (전력 정책에서 호출)
+pub fn set_default_slack_ns(slack_ns: u64) {
+    DEFAULT_SLACK_NS.store(slack_ns, Ordering::Relaxed);
+}
+
+/// 가장 이른 타이머 만료 시각 (ns)
+///
+/// tickless 모드에서 다음 타이머 인터럽트 시점을 정하는 데 사용됩니다.
+pub fn next_event_ns() -> Option<u64> {
+    let hr = hrtimer::next_expiry_ns();
+    let coarse = wheel::next_expiry_ms().map(|ms| ms.saturating_mul(NSEC_PER_MSEC));
+    match (hr, coarse) {
+        (Some(a), Some(b)) => Some(a.min(b)),
+        (a, b) => a.or(b),
+    }
+}
+
+/// 타이머 틱에서 호출: 만료된 타이머가 있으면 타이머 softirq를 올림
+pub fn check_expired(now_ns: u64) {
+    if hrtimer::has_expired(now_ns) || wheel::has_expired(now_ns / NSEC_PER_MSEC) {
+        softirq::raise(SoftIrq::Timer);
+    }
+}
+
+/// 타이머 softirq 핸들러
+fn run_timer_softirq() {
+    let now = now_ns();
+    hrtimer::run_expired(now);
+    wheel::run_expired(now / NSEC_PER_MSEC);
+}
@@ -0,0 +1,115 @@
+//! TSC (Time Stamp Counter) 클럭소스
+//!
+//! TSC 주파수는 HPET이 있으면 HPET으로, 없으면 PIT 채널 2로 보정합니다.
+//! Invariant TSC는 C-State/P-State와 무관하게 일정하게 증가하므로 가장 높은 rating을 받고,
+//! 그렇지 않은 TSC는 HPET보다 낮은 rating으로 등록됩니다.
+
+use core::arch::x86_64::{__cpuid, _rdtsc};
+use core::sync::atomic::{AtomicU64, Ordering};
+
+use super::clocksource::{self, rating, ClockSource};
+
+/// 보정 구간 길이 (ms)
+const CALIBRATION_MS: u64 = 10;
+
+/// 보정된 TSC 주파수 (kHz, 0 = 미보정)
+static TSC_KHZ: AtomicU64 = AtomicU64::new(0);
+
+/// CPU가 TSC를 지원하는지 확인 (CPUID.1:EDX[4])
+pub fn supported() -> bool {
+    unsafe { __cpuid(1).edx & (1 << 4) != 0 }
+}
+
+/// Invariant TSC 지원 여부 (CPUID.80000007H:EDX[8])
+pub fn invariant() -> bool {
+    unsafe {
+        if __cpuid(0x8000_0000).eax < 0x8000_0007 {
+            return false;
+        }
+        __cpuid(0x8000_0007).edx & (1 << 8) != 0
+    }
+}
+
+/// TSC 읽기
+#[inline]
+pub fn rdtsc() -> u64 {
+    unsafe { _rdtsc() }
+}
+
+/// 보정된 TSC 주파수 (kHz = 밀리초당 사이클 수, 미보정 시 0)
+pub fn khz() -> u64 {
+    TSC_KHZ.load(Ordering::Acquire)
+}
+
+/// TSC 주파수 보정
+///
+/// 이미 보정되었으면 저장된 값을 반환합니다.
+///
+/// # Returns
+/// TSC 주파수 (kHz)
+pub fn calibrate() -> Result<u64, &'static str> {
+    let cached = khz();
+    if cached != 0 {
+        return Ok(cached);
+    }
+    if !supported() {
+        return Err("TSC not supported");
+    }
+
+    let (khz, reference) = if crate::drivers::hpet::is_available() {
+        (calibrate_against_hpet(), "HPET")
+    } else {
+        let cycles = crate::drivers::timer::calibrate_against_pit(CALIBRATION_MS as u32, rdtsc);
+        (cycles / CALIBRATION_MS, "PIT")
+    };
+    if khz == 0 {
+        return Err("TSC calibration failed");
+    }
+
+    TSC_KHZ.store(khz, Ordering::Release);
+    crate::log_info!("TSC calibrated against {}: {} kHz", reference, khz);
+    Ok(khz)
+}
+
+/// TSC 클럭소스 초기화 및 등록
+pub fn init() -> Result<(), &'static str> {
+    let khz = calibrate()?;
+    let invariant = invariant();
+    if !invariant {
+        crate::log_warn!("TSC is not invariant; preferring other clocksources");
+    }
+
+    clocksource::register(ClockSource {
+        name: "tsc",
+        rating: if invariant { rating::TSC } else { rating::TSC_UNSTABLE },
+        read: rdtsc,
+        mask: u64::MAX,
+        freq_hz: khz * 1000,
+    });
+    Ok(())
+}
+
+/// HPET 메인 카운터 기준으로 `CALIBRATION_MS` 동안의 TSC 증가량 측정
+fn calibrate_against_hpet() -> u64 {
+    use crate::drivers::hpet;
+
+    let hpet_freq = hpet::frequency_hz();
+    let window = hpet_freq * CALIBRATION_MS / 1000;
+    let mask = hpet::counter_mask();
+
+    let hpet_start = hpet::read_counter();
+    let tsc_start = rdtsc();
+    let mut hpet_now = hpet_start;
+    while hpet_now.wrapping_sub(hpet_start) & mask < window {
+        core::hint::spin_loop();
+        hpet_now = hpet::read_counter();
+    }
+    let tsc_end = rdtsc();
+
+    let hpet_elapsed = hpet_now.wrapping_sub(hpet_start) & mask;
+    if hpet_elapsed == 0 {
+        return 0;
+    }
+    // kHz = TSC 사이클 / 경과 ms = tsc * hpet_freq / (hpet_elapsed * 1000)
+    ((tsc_end - tsc_start) as u128 * hpet_freq as u128 / (hpet_elapsed as u128 * 1000)) as u64
+}
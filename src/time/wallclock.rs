@@ -0,0 +1,279 @@
+//! 벽시계 (wall-clock) 시간
+//!
+//! 부팅 시 CMOS RTC에서 읽은 UTC 시각을 기준으로, 이후 경과 시간은 단조 시계
+//! (`time::now_ns`)로 계산합니다. RTC를 매번 읽지 않으므로 인터럽트 컨텍스트에서도
+//! 사용할 수 있습니다.
+//!
+//! 로컬 시간은 UTC에 시간대 오프셋을 더해 계산하며, 기본 시간대는 KST(UTC+9)입니다.
+
+use core::fmt;
+use core::sync::atomic::{fence, AtomicI32, AtomicU64, Ordering};
+
+use super::NSEC_PER_SEC;
+
+/// 기본 시간대 오프셋 (KST, UTC+9, 분 단위)
+pub const DEFAULT_TZ_OFFSET_MINUTES: i32 = 9 * 60;
+
+/// 기준 시점 시퀀스 카운터 (seqlock, 홀수 = 갱신 중)
+///
+/// 읽기는 인터럽트 컨텍스트에서도 일어나므로 락 대신 seqlock으로
+/// 두 기준값을 한 쌍으로 읽습니다.
+static BASE_SEQ: AtomicU64 = AtomicU64::new(0);
+/// 기준 시점의 Unix 시각 (초)
+static BASE_UNIX_SECS: AtomicU64 = AtomicU64::new(0);
+/// 기준 시점의 단조 시계 값 (ns)
+static BASE_MONO_NS: AtomicU64 = AtomicU64::new(0);
+/// 시간대 오프셋 (분, UTC 기준)
+static TZ_OFFSET_MINUTES: AtomicI32 = AtomicI32::new(DEFAULT_TZ_OFFSET_MINUTES);
+
+/// 달력 날짜/시간 (시간대 정보 없음)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DateTime {
+    pub year: u16,
+    /// 1..=12
+    pub month: u8,
+    /// 1..=31
+    pub day: u8,
+    /// 0..=23
+    pub hour: u8,
+    /// 0..=59
+    pub minute: u8,
+    /// 0..=59
+    pub second: u8,
+}
+
+impl DateTime {
+    /// 유효한 날짜/시간인지 확인 (1970년 이후)
+    pub fn is_valid(&self) -> bool {
+        self.year >= 1970
+            && (1..=12).contains(&self.month)
+            && self.day >= 1
+            && self.day <= days_in_month(self.year, self.month)
+            && self.hour < 24
+            && self.minute < 60
+            && self.second < 60
+    }
+
+    /// Unix 시각(초)으로 변환
+    pub fn to_unix(&self) -> u64 {
+        let days = days_from_civil(self.year as i64, self.month as u32, self.day as u32);
+        (days * 86_400) as u64
+            + self.hour as u64 * 3600
+            + self.minute as u64 * 60
+            + self.second as u64
+    }
+
+    /// Unix 시각(초)에서 변환
+    pub fn from_unix(secs: u64) -> Self {
+        let days = (secs / 86_400) as i64;
+        let rem = secs % 86_400;
+        let (year, month, day) = civil_from_days(days);
+        Self {
+            year: year as u16,
+            month: month as u8,
+            day: day as u8,
+            hour: (rem / 3600) as u8,
+            minute: (rem % 3600 / 60) as u8,
+            second: (rem % 60) as u8,
+        }
+    }
+
+    /// 요일 (0 = 일요일)
+    pub fn weekday(&self) -> u8 {
+        // 1970-01-01은 목요일
+        ((days_from_civil(self.year as i64, self.month as u32, self.day as u32) + 4).rem_euclid(7)) as u8
+    }
+}
+
+impl fmt::Display for DateTime {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{:04}-{:02}-{:02} {:02}:{:02}:{:02}",
+            self.year, self.month, self.day, self.hour, self.minute, self.second
+        )
+    }
+}
+
+/// 벽시계 초기화
+///
+/// RTC에서 현재 UTC 시각을 읽어 기준점으로 설정합니다.
+/// RTC를 읽을 수 없으면 Unix epoch에서 시작합니다.
+///
+/// # Safety
+/// 메모리 관리와 클럭소스가 초기화된 후에 호출되어야 합니다.
+pub unsafe fn init() {
+    crate::drivers::rtc::init();
+    let unix = crate::drivers::rtc::read_time().map(|t| t.to_unix()).unwrap_or(0);
+    set_base(unix);
+    crate::log_info!("Wall clock: {} (UTC{})", local_now(), format_offset(tz_offset_minutes()));
+}
+
+/// 현재 Unix 시각 (ns)
+pub fn now_unix_ns() -> u64 {
+    let (unix_secs, mono_ns) = read_base();
+    let elapsed = super::now_ns().saturating_sub(mono_ns);
+    unix_secs * NSEC_PER_SEC + elapsed
+}
+
+/// 현재 Unix 시각 (초)
+pub fn now_unix() -> u64 {
+    now_unix_ns() / NSEC_PER_SEC
+}
+
+/// 현재 UTC 날짜/시간
+pub fn utc_now() -> DateTime {
+    DateTime::from_unix(now_unix())
+}
+
+/// 현재 로컬 날짜/시간 (시간대 오프셋 적용)
+pub fn local_now() -> DateTime {
+    DateTime::from_unix(unix_to_local(now_unix()))
+}
+
+/// 시스템 시각 설정
+///
+/// # Arguments
+/// * `unix_secs` - 새 UTC Unix 시각
+/// * `write_rtc` - RTC에도 기록할지 여부
+pub fn set_time(unix_secs: u64, write_rtc: bool) {
+    set_base(unix_secs);
+    if write_rtc {
+        crate::drivers::rtc::write_time(&DateTime::from_unix(unix_secs));
+    }
+    crate::log_info!("Wall clock set: {} UTC", DateTime::from_unix(unix_secs));
+}
+
+/// 시간대 오프셋 (분)
+pub fn tz_offset_minutes() -> i32 {
+    TZ_OFFSET_MINUTES.load(Ordering::Relaxed)
+}
+
+/// 시간대 오프셋 설정 (분, UTC 기준, -12h..=+14h)
+pub fn set_tz_offset_minutes(minutes: i32) -> Result<(), &'static str> {
+    if !(-12 * 60..=14 * 60).contains(&minutes) {
+        return Err("timezone offset out of range");
+    }
+    TZ_OFFSET_MINUTES.store(minutes, Ordering::Relaxed);
+    Ok(())
+}
+
+/// UTC Unix 시각을 로컬 시각 기준 초로 변환
+pub fn unix_to_local(unix_secs: u64) -> u64 {
+    (unix_secs as i64 + tz_offset_minutes() as i64 * 60).max(0) as u64
+}
+
+/// 로컬 시각 기준 초를 UTC Unix 시각으로 변환
+pub fn local_to_unix(local_secs: u64) -> u64 {
+    (local_secs as i64 - tz_offset_minutes() as i64 * 60).max(0) as u64
+}
+
+/// 시간대 오프셋 문자열 (예: "+09:00")
+pub fn format_offset(minutes: i32) -> alloc::string::String {
+    let sign = if minutes < 0 { '-' } else { '+' };
+    let abs = minutes.unsigned_abs();
+    alloc::format!("{}{:02}:{:02}", sign, abs / 60, abs % 60)
+}
+
+/// 기준 시점 (Unix 초, 단조 시계 ns)을 찢어지지 않은 한 쌍으로 읽기
+fn read_base() -> (u64, u64) {
+    loop {
+        let seq = BASE_SEQ.load(Ordering::Acquire);
+        if seq & 1 != 0 {
+            core::hint::spin_loop();
+            continue;
+        }
+        let unix_secs = BASE_UNIX_SECS.load(Ordering::Relaxed);
+        let mono_ns = BASE_MONO_NS.load(Ordering::Relaxed);
+        fence(Ordering::Acquire);
+        if BASE_SEQ.load(Ordering::Relaxed) == seq {
+            return (unix_secs, mono_ns);
+        }
+    }
+}
+
+/// 기준 시점 갱신
+///
+/// 쓰는 동안 인터럽트를 막아, 같은 CPU의 인터럽트 핸들러가 홀수 시퀀스에서
+/// 무한히 기다리지 않도록 합니다. 쓰기 측은 CAS로 직렬화합니다.
+fn set_base(unix_secs: u64) {
+    x86_64::instructions::interrupts::without_interrupts(|| {
+        let mut seq = BASE_SEQ.load(Ordering::Relaxed);
+        loop {
+            if seq & 1 != 0 {
+                core::hint::spin_loop();
+                seq = BASE_SEQ.load(Ordering::Relaxed);
+                continue;
+            }
+            match BASE_SEQ.compare_exchange_weak(seq, seq + 1, Ordering::Acquire, Ordering::Relaxed) {
+                Ok(_) => break,
+                Err(current) => seq = current,
+            }
+        }
+        fence(Ordering::Release);
+        BASE_MONO_NS.store(super::now_ns(), Ordering::Relaxed);
+        BASE_UNIX_SECS.store(unix_secs, Ordering::Relaxed);
+        BASE_SEQ.store(seq + 2, Ordering::Release);
+    });
+}
+
+/// 윤년 여부
+pub fn is_leap_year(year: u16) -> bool {
+    (year % 4 == 0 && year % 100 != 0) || year % 400 == 0
+}
+
+/// 해당 월의 일 수
+pub fn days_in_month(year: u16, month: u8) -> u8 {
+    match month {
+        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+        4 | 6 | 9 | 11 => 30,
+        2 => if is_leap_year(year) { 29 } else { 28 },
+        _ => 0,
+    }
+}
+
+/// 그레고리력 날짜 → 1970-01-01 기준 일 수 (Howard Hinnant 알고리즘)
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let y = if month <= 2 { year - 1 } else { year };
+    let era = y.div_euclid(400);
+    let yoe = y - era * 400;
+    let mp = (month as i64 + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + day as i64 - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+/// 1970-01-01 기준 일 수 → 그레고리력 날짜
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test_case]
+    fn test_unix_roundtrip() {
+        let epoch = DateTime { year: 1970, month: 1, day: 1, hour: 0, minute: 0, second: 0 };
+        assert_eq!(epoch.to_unix(), 0);
+        assert_eq!(epoch.weekday(), 4);
+
+        // 2024-02-29 12:34:56 UTC (윤일)
+        let leap = DateTime { year: 2024, month: 2, day: 29, hour: 12, minute: 34, second: 56 };
+        assert_eq!(leap.to_unix(), 1_709_210_096);
+        assert_eq!(DateTime::from_unix(1_709_210_096), leap);
+
+        let y2100 = DateTime { year: 2100, month: 3, day: 1, hour: 0, minute: 0, second: 0 };
+        assert_eq!(DateTime::from_unix(y2100.to_unix()), y2100);
+        assert!(!is_leap_year(2100));
+    }
+}
@@ -0,0 +1,289 @@
+//! 계층형 타이밍 휠 (coarse 타이머)
+//!
+//! 재전송 타임아웃이나 워치독처럼 밀리초 단위 정밀도면 충분한 타이머를 위한 자료구조입니다.
+//! 레벨 `n`의 슬롯 하나는 `8^n` ms를 나타내며, 각 레벨은 64개 슬롯을 가집니다.
+//! 타이머는 만료까지 남은 시간에 맞는 레벨에 한 번만 배치되고(cascading 없음),
+//! 만료 시각은 해당 레벨의 단위로 올림됩니다. 먼 타이머일수록 늦게 실행될 수 있지만
+//! 그만큼 wakeup이 묶여 전력 소모가 줄어듭니다.
+
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+use spin::Mutex;
+use x86_64::instructions::interrupts;
+
+use super::{TimerCallback, TimerId, TimerRestart, NSEC_PER_MSEC};
+
+/// 레벨당 슬롯 수 (2^6)
+const LVL_BITS: u32 = 6;
+const LVL_SIZE: usize = 1 << LVL_BITS;
+const LVL_MASK: u64 = (LVL_SIZE as u64) - 1;
+/// 레벨 간 단위 배율 (2^3 = 8배)
+const LVL_CLK_SHIFT: u32 = 3;
+/// 레벨 수 (최대 범위: 64 * 8^7 ms ≈ 1.5일 이상은 마지막 레벨에 고정)
+const LVL_DEPTH: usize = 8;
+
+/// 슬롯에 저장되는 타이머
+struct WheelEntry<T> {
+    id: TimerId,
+    expires: u64,
+    payload: T,
+}
+
+/// 계층형 타이밍 휠
+///
+/// 시간 단위는 호출자가 정합니다 (커널 전역 휠은 ms 사용).
+pub struct TimerWheel<T> {
+    /// [레벨][슬롯] → 타이머 목록
+    slots: Vec<Vec<WheelEntry<T>>>,
+    /// 레벨별 비어있지 않은 슬롯 비트맵
+    pending: [u64; LVL_DEPTH],
+    /// ID → (레벨, 슬롯)
+    index: BTreeMap<TimerId, (usize, usize)>,
+    /// 마지막으로 처리한 시각
+    clk: u64,
+}
+
+impl<T> TimerWheel<T> {
+    /// 시각 `now`에서 시작하는 빈 휠 생성
+    pub fn new(now: u64) -> Self {
+        let mut slots = Vec::with_capacity(LVL_DEPTH * LVL_SIZE);
+        for _ in 0..LVL_DEPTH * LVL_SIZE {
+            slots.push(Vec::new());
+        }
+        Self {
+            slots,
+            pending: [0; LVL_DEPTH],
+            index: BTreeMap::new(),
+            clk: now,
+        }
+    }
+
+    /// 등록된 타이머 수
+    pub fn len(&self) -> usize {
+        self.index.len()
+    }
+
+    /// 비어 있는지 확인
+    pub fn is_empty(&self) -> bool {
+        self.index.is_empty()
+    }
+
+    /// 타이머 추가
+    pub fn insert(&mut self, id: TimerId, expires: u64, payload: T) {
+        let (lvl, slot) = self.calc_slot(expires);
+        self.slots[lvl * LVL_SIZE + slot].push(WheelEntry { id, expires, payload });
+        self.pending[lvl] |= 1 << slot;
+        self.index.insert(id, (lvl, slot));
+    }
+
+    /// 타이머 제거
+    pub fn remove(&mut self, id: TimerId) -> Option<T> {
+        let (lvl, slot) = self.index.remove(&id)?;
+        let list = &mut self.slots[lvl * LVL_SIZE + slot];
+        let pos = list.iter().position(|e| e.id == id)?;
+        let entry = list.swap_remove(pos);
+        if list.is_empty() {
+            self.pending[lvl] &= !(1 << slot);
+        }
+        Some(entry.payload)
+    }
+
+    /// 시각을 `now`까지 진행하고 만료된 타이머를 반환
+    ///
+    /// 지나간 슬롯에 있지만 아직 만료되지 않은 타이머는 현재 시각 기준으로 재배치됩니다.
+    pub fn advance(&mut self, now: u64) -> Vec<(TimerId, T)> {
+        let mut expired = Vec::new();
+        if now < self.clk {
+            return expired;
+        }
+
+        let mut requeue = Vec::new();
+        for lvl in 0..LVL_DEPTH {
+            if self.pending[lvl] == 0 {
+                continue;
+            }
+            let shift = lvl as u32 * LVL_CLK_SHIFT;
+            let from = self.clk >> shift;
+            let to = now >> shift;
+            // 한 바퀴 이상 지나면 모든 슬롯 확인
+            let steps = (to - from).min(LVL_SIZE as u64);
+            for step in 1..=steps {
+                let slot = ((from + step) & LVL_MASK) as usize;
+                if self.pending[lvl] & (1 << slot) == 0 {
+                    continue;
+                }
+                let list = core::mem::take(&mut self.slots[lvl * LVL_SIZE + slot]);
+                self.pending[lvl] &= !(1 << slot);
+                for entry in list {
+                    self.index.remove(&entry.id);
+                    if entry.expires <= now {
+                        expired.push((entry.id, entry.payload));
+                    } else {
+                        requeue.push(entry);
+                    }
+                }
+            }
+        }
+
+        self.clk = now;
+        for entry in requeue {
+            self.insert(entry.id, entry.expires, entry.payload);
+        }
+        expired
+    }
+
+    /// 다음으로 처리될 슬롯의 시각 (타이머가 실제로 실행되는 가장 이른 시각)
+    pub fn next_expiry(&self) -> Option<u64> {
+        let mut best: Option<u64> = None;
+        for lvl in 0..LVL_DEPTH {
+            let bitmap = self.pending[lvl];
+            if bitmap == 0 {
+                continue;
+            }
+            let shift = lvl as u32 * LVL_CLK_SHIFT;
+            let base = (self.clk >> shift) + 1;
+            let start = (base & LVL_MASK) as u32;
+            let offset = bitmap.rotate_right(start).trailing_zeros() as u64;
+            let when = (base + offset) << shift;
+            best = Some(best.map_or(when, |b| b.min(when)));
+        }
+        best
+    }
+
+    /// 만료 시각에 맞는 (레벨, 슬롯) 계산
+    fn calc_slot(&self, expires: u64) -> (usize, usize) {
+        let delta = expires.saturating_sub(self.clk);
+        let mut lvl = 0;
+        while lvl < LVL_DEPTH - 1 && delta >= (LVL_SIZE as u64 - 1) << (lvl as u32 * LVL_CLK_SHIFT) {
+            lvl += 1;
+        }
+        let shift = lvl as u32 * LVL_CLK_SHIFT;
+        let granularity = 1u64 << shift;
+        // 레벨 단위로 올림, 최소한 현재 이후 슬롯에 배치
+        let mut when = expires.saturating_add(granularity - 1) >> shift;
+        let now = self.clk >> shift;
+        if when <= now {
+            when = now + 1;
+        }
+        if when > now + LVL_MASK {
+            when = now + LVL_MASK;
+        }
+        (lvl, (when & LVL_MASK) as usize)
+    }
+}
+
+/// 커널 전역 coarse 타이머
+#[derive(Clone, Copy)]
+struct CoarseTimer {
+    callback: TimerCallback,
+    data: usize,
+}
+
+/// 전역 타이밍 휠 (ms 단위)
+static WHEEL: Mutex<Option<TimerWheel<CoarseTimer>>> = Mutex::new(None);
+
+/// `delay_ms` 밀리초 후 실행되는 coarse 타이머 추가
+///
+/// 만료 시각은 남은 시간에 비례하는 단위로 올림되므로 다소 늦게 실행될 수 있습니다.
+pub fn add_timer_ms(delay_ms: u64, callback: TimerCallback, data: usize) -> TimerId {
+    let id = TimerId::allocate();
+    let expires = super::now_ns() / NSEC_PER_MSEC + delay_ms;
+    insert(id, expires, CoarseTimer { callback, data });
+    id
+}
+
+/// coarse 타이머 취소
+///
+/// # Returns
+/// 대기 중인 타이머를 제거했으면 `true`
+pub fn cancel(id: TimerId) -> bool {
+    interrupts::without_interrupts(|| {
+        WHEEL.lock().as_mut().map_or(false, |w| w.remove(id).is_some())
+    })
+}
+
+/// 다음 coarse 타이머 실행 시각 (ms)
+pub fn next_expiry_ms() -> Option<u64> {
+    interrupts::without_interrupts(|| WHEEL.lock().as_ref().and_then(|w| w.next_expiry()))
+}
+
+/// `now_ms` 시점에 처리할 슬롯이 있는지 확인
+pub fn has_expired(now_ms: u64) -> bool {
+    next_expiry_ms().map_or(false, |t| t <= now_ms)
+}
+
+/// 만료된 coarse 타이머 실행 (타이머 softirq에서 호출)
+pub fn run_expired(now_ms: u64) {
+    let expired = interrupts::without_interrupts(|| {
+        WHEEL.lock().as_mut().map(|w| w.advance(now_ms)).unwrap_or_default()
+    });
+
+    for (id, timer) in expired {
+        if let TimerRestart::Restart { period_ns } = (timer.callback)(timer.data) {
+            let period_ms = (period_ns / NSEC_PER_MSEC).max(1);
+            insert(id, now_ms + period_ms, timer);
+        }
+    }
+}
+
+fn insert(id: TimerId, expires_ms: u64, timer: CoarseTimer) {
+    interrupts::without_interrupts(|| {
+        let mut wheel = WHEEL.lock();
+        let wheel = wheel.get_or_insert_with(|| TimerWheel::new(super::now_ns() / NSEC_PER_MSEC));
+        wheel.insert(id, expires_ms, timer);
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test_case]
+    fn test_wheel_expires_in_order() {
+        let mut wheel = TimerWheel::new(0);
+        wheel.insert(TimerId(1), 5, 'a');
+        wheel.insert(TimerId(2), 40, 'b');
+        assert_eq!(wheel.next_expiry(), Some(5));
+
+        let fired = wheel.advance(10);
+        assert_eq!(fired.len(), 1);
+        assert_eq!(fired[0].1, 'a');
+        assert_eq!(wheel.len(), 1);
+
+        let fired = wheel.advance(40);
+        assert_eq!(fired.len(), 1);
+        assert_eq!(fired[0].1, 'b');
+        assert!(wheel.is_empty());
+    }
+
+    #[test_case]
+    fn test_wheel_far_timer_rounds_up() {
+        let mut wheel = TimerWheel::new(0);
+        // 레벨 1 (8ms 단위)에 배치됨
+        wheel.insert(TimerId(1), 100, ());
+        let next = wheel.next_expiry().unwrap();
+        assert!(next >= 100 && next < 108);
+        assert!(wheel.advance(99).is_empty());
+        assert_eq!(wheel.advance(next).len(), 1);
+    }
+
+    #[test_case]
+    fn test_wheel_large_jump_and_remove() {
+        let mut wheel = TimerWheel::new(0);
+        wheel.insert(TimerId(1), 3, 1u32);
+        wheel.insert(TimerId(2), 70_000, 2u32);
+        assert_eq!(wheel.remove(TimerId(1)), Some(1));
+        // 여러 바퀴를 한 번에 건너뛰어도 만료된 타이머를 놓치지 않음
+        let fired = wheel.advance(1_000_000);
+        assert_eq!(fired.len(), 1);
+        assert_eq!(fired[0].1, 2);
+    }
+
+    #[test_case]
+    fn test_wheel_past_deadline_fires_next() {
+        let mut wheel = TimerWheel::new(50);
+        wheel.insert(TimerId(1), 10, ());
+        assert_eq!(wheel.next_expiry(), Some(51));
+        assert_eq!(wheel.advance(51).len(), 1);
+    }
+}